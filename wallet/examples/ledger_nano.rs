@@ -35,6 +35,7 @@ async fn main() -> Result<()> {
         .await?;
 
     println!("{:?}", manager.get_ledger_nano_status().await?);
+    println!("{:?}", manager.get_ledger_nano_capabilities().await?);
 
     // Get account or create a new one
     let account_alias = "ledger";
@@ -54,6 +55,12 @@ async fn main() -> Result<()> {
 
     println!("{address:?}");
 
+    // Show the address on the device, so it can be compared with the one printed above
+    let verified = account
+        .verify_address_on_device(&address[0].address().to_bech32())
+        .await?;
+    println!("Address verified on device: {verified}");
+
     let now = Instant::now();
     let balance = account.sync(None).await?;
     println!("Syncing took: {:.2?}", now.elapsed());
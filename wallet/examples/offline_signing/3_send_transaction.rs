@@ -29,7 +29,7 @@ async fn main() -> Result<()> {
         read_signed_transaction_from_file(account.client(), SIGNED_TRANSACTION_FILE_NAME).await?;
 
     // Sends offline signed transaction online.
-    let result = account.submit_and_store_transaction(signed_transaction_data).await?;
+    let result = account.submit_transaction(signed_transaction_data).await?;
 
     println!(
         "Transaction sent: https://explorer.iota.org/devnet/block/{}",
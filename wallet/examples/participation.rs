@@ -73,6 +73,7 @@ async fn main() -> Result<()> {
             node,
             events_to_ignore: Some(vec![event_id]),
             events_to_register: None,
+            event_type: None,
         })
         .await?;
 
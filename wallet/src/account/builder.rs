@@ -14,15 +14,22 @@ use iota_client::{
 use tokio::sync::Mutex;
 use tokio::sync::RwLock;
 
+#[cfg(feature = "ledger_nano")]
+use crate::account_manager::{LedgerNanoOperation, LedgerNanoQueue};
 #[cfg(feature = "events")]
 use crate::events::EventEmitter;
 #[cfg(feature = "storage")]
-use crate::storage::manager::StorageManagerHandle;
+use crate::storage::{
+    manager::StorageManagerHandle,
+    seed_fingerprint::{verify_unverified_seed_fingerprint, SeedFingerprint},
+};
 use crate::{
     account::{
+        constants::WATCH_ACCOUNT_INDEX_OFFSET,
         handle::AccountHandle,
+        operations::{syncing::request_limiter::RequestLimiter, transaction::signing_policy::SigningGuard},
         types::{address::AddressWrapper, AccountAddress},
-        Account,
+        Account, SpamFilter,
     },
     ClientOptions, Error,
 };
@@ -32,9 +39,17 @@ pub struct AccountBuilder {
     addresses: Option<Vec<AccountAddress>>,
     alias: Option<String>,
     bech32_hrp: Option<String>,
+    metadata: HashMap<String, String>,
+    watch_only: bool,
+    account_client_options: Option<ClientOptions>,
+    account_coin_type: Option<u32>,
     client_options: Arc<RwLock<ClientOptions>>,
     coin_type: u32,
     secret_manager: Arc<RwLock<SecretManager>>,
+    signing_guard: Arc<RwLock<SigningGuard>>,
+    request_limiter: Arc<RequestLimiter>,
+    #[cfg(feature = "ledger_nano")]
+    ledger_nano_queue: Arc<LedgerNanoQueue>,
     accounts: Arc<RwLock<Vec<AccountHandle>>>,
     #[cfg(feature = "events")]
     event_emitter: Arc<Mutex<EventEmitter>>,
@@ -49,6 +64,9 @@ impl AccountBuilder {
         client_options: Arc<RwLock<ClientOptions>>,
         coin_type: u32,
         secret_manager: Arc<RwLock<SecretManager>>,
+        signing_guard: Arc<RwLock<SigningGuard>>,
+        request_limiter: Arc<RequestLimiter>,
+        #[cfg(feature = "ledger_nano")] ledger_nano_queue: Arc<LedgerNanoQueue>,
         #[cfg(feature = "events")] event_emitter: Arc<Mutex<EventEmitter>>,
         #[cfg(feature = "storage")] storage_manager: StorageManagerHandle,
     ) -> Self {
@@ -56,9 +74,17 @@ impl AccountBuilder {
             addresses: None,
             alias: None,
             bech32_hrp: None,
+            metadata: HashMap::new(),
+            watch_only: false,
+            account_client_options: None,
+            account_coin_type: None,
             client_options,
             coin_type,
             secret_manager,
+            signing_guard,
+            request_limiter,
+            #[cfg(feature = "ledger_nano")]
+            ledger_nano_queue,
             accounts,
             #[cfg(feature = "events")]
             event_emitter,
@@ -86,12 +112,51 @@ impl AccountBuilder {
         self
     }
 
+    /// Set the coin type of the account, if it should differ from the one of the account manager
+    pub fn with_coin_type(mut self, coin_type: u32) -> Self {
+        self.account_coin_type.replace(coin_type);
+        self
+    }
+
+    /// Set the client options of the account, if it should use nodes of another network than the account manager
+    pub fn with_client_options(mut self, client_options: ClientOptions) -> Self {
+        self.account_client_options.replace(client_options);
+        self
+    }
+
+    /// Set metadata of the application for the account, like a color, an icon or an external id
+    pub fn with_metadata(mut self, metadata: HashMap<String, String>) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Create a watch-only account, which only tracks the provided addresses and can't sign transactions
+    pub(crate) fn with_watch_only(mut self) -> Self {
+        self.watch_only = true;
+        self
+    }
+
     /// Build the Account and add it to the accounts from AccountManager
     /// Also generates the first address of the account and if it's not the first account, the address for the first
     /// account will also be generated and compared, so no accounts get generated with different seeds
     pub async fn finish(&mut self) -> crate::Result<AccountHandle> {
         let mut accounts = self.accounts.write().await;
-        let account_index = accounts.len() as u32;
+        // Use the next index after the highest one of the active and archived accounts, so an archived account can
+        // always be restored. Watch-only accounts have their own range, so they don't take indexes of the seed.
+        let in_range = |index: u32| (index >= WATCH_ACCOUNT_INDEX_OFFSET) == self.watch_only;
+        let mut account_index = if self.watch_only { WATCH_ACCOUNT_INDEX_OFFSET } else { 0 };
+        for account_handle in accounts.iter() {
+            let index = account_handle.read().await.index;
+            if in_range(index) {
+                account_index = account_index.max(index + 1);
+            }
+        }
+        #[cfg(feature = "storage")]
+        for archived_account_index in self.storage_manager.lock().await.archived_account_indexes().await? {
+            if in_range(archived_account_index) {
+                account_index = account_index.max(archived_account_index + 1);
+            }
+        }
         // If no alias is provided, the account index will be set as alias
         let account_alias = self.alias.clone().unwrap_or_else(|| account_index.to_string());
         log::debug!(
@@ -100,37 +165,73 @@ impl AccountBuilder {
             account_index
         );
 
-        // Check that the alias isn't already used for another account and that the coin type is the same for new and
-        // existing accounts
+        let coin_type = self.account_coin_type.unwrap_or(self.coin_type);
+
+        // Check that the alias isn't already used for another account and, if the coin type isn't set for the account,
+        // that the coin type of the account manager is used by existing accounts, so a changed coin type is detected
+        let mut existing_coin_types = Vec::new();
         for account_handle in accounts.iter() {
             let account = account_handle.read().await;
-            let existing_coin_type = account.coin_type;
-            if existing_coin_type != self.coin_type {
-                return Err(Error::InvalidCoinType {
-                    new_coin_type: self.coin_type,
-                    existing_coin_type,
-                });
-            }
+            existing_coin_types.push(account.coin_type);
             if account.alias().to_lowercase() == account_alias.to_lowercase() {
                 return Err(Error::AccountAliasAlreadyExists(account_alias));
             }
         }
+        if let (None, Some(existing_coin_type)) = (self.account_coin_type, existing_coin_types.first()) {
+            if !existing_coin_types.contains(&coin_type) {
+                return Err(Error::InvalidCoinType {
+                    new_coin_type: coin_type,
+                    existing_coin_type: *existing_coin_type,
+                });
+            }
+        }
 
-        let client = self.client_options.read().await.clone().finish()?;
+        if self.watch_only && self.addresses.as_ref().map_or(true, |addresses| addresses.is_empty()) {
+            return Err(Error::MissingParameter("addresses"));
+        }
+
+        let client = match &self.account_client_options {
+            Some(client_options) => client_options.clone().finish()?,
+            None => self.client_options.read().await.clone().finish()?,
+        };
 
         // If addresses are provided we will use them directly without the additional checks, because then we assume
         // that it's for offline signing and the secretManager can't be used
         let addresses = match &self.addresses {
             Some(addresses) => addresses.clone(),
             None => {
+                #[cfg(feature = "storage")]
+                verify_unverified_seed_fingerprint(
+                    &self.storage_manager,
+                    &self.secret_manager,
+                    #[cfg(feature = "ledger_nano")]
+                    &self.ledger_nano_queue,
+                )
+                .await?;
                 let mut bech32_hrp = self.bech32_hrp.clone();
-                if let Some(first_account) = accounts.first() {
-                    let first_account_coin_type = *first_account.read().await.coin_type();
+                // Watch-only accounts have addresses of other seeds
+                let mut signing_accounts = Vec::new();
+                for account_handle in accounts.iter() {
+                    if !account_handle.read().await.watch_only {
+                        signing_accounts.push(account_handle);
+                    }
+                }
+                if let Some(first_account) = signing_accounts.first() {
+                    let (first_account_coin_type, first_account_index) = {
+                        let first_account = first_account.read().await;
+                        (first_account.coin_type, first_account.index)
+                    };
                     // Generate the first address of the first account and compare it to the stored address from the
                     // first account to prevent having multiple accounts created with different
                     // seeds
-                    let first_account_public_address =
-                        get_first_public_address(&self.secret_manager, first_account_coin_type, 0).await?;
+                    let first_account_public_address = get_first_public_address(
+                        &self.secret_manager,
+                        #[cfg(feature = "ledger_nano")]
+                        &self.ledger_nano_queue,
+                        first_account_coin_type,
+                        first_account_index,
+                    )
+                    .await?;
                     let first_account_addresses = first_account.public_addresses().await;
 
                     if first_account_public_address
@@ -145,9 +246,12 @@ impl AccountBuilder {
                         ));
                     }
 
-                    // Get bech32_hrp from address
+                    // Get bech32_hrp from address, if the accounts use the same network
                     if let Some(address) = first_account_addresses.first() {
-                        if bech32_hrp.is_none() {
+                        if bech32_hrp.is_none()
+                            && self.account_client_options.is_none()
+                            && first_account.client_options.is_none()
+                        {
                             bech32_hrp = Some(address.address.bech32_hrp.clone());
                         }
                     }
@@ -161,8 +265,14 @@ impl AccountBuilder {
                     }
                 };
 
-                let first_public_address =
-                    get_first_public_address(&self.secret_manager, self.coin_type, account_index).await?;
+                let first_public_address = get_first_public_address(
+                    &self.secret_manager,
+                    #[cfg(feature = "ledger_nano")]
+                    &self.ledger_nano_queue,
+                    coin_type,
+                    account_index,
+                )
+                .await?;
 
                 let first_public_account_address = AccountAddress {
                     address: AddressWrapper::new(first_public_address, bech32_hrp),
@@ -177,7 +287,7 @@ impl AccountBuilder {
 
         let account = Account {
             index: account_index,
-            coin_type: self.coin_type,
+            coin_type,
             alias: account_alias,
             public_addresses: addresses,
             internal_addresses: Vec::new(),
@@ -190,34 +300,83 @@ impl AccountBuilder {
             incoming_transactions: HashMap::new(),
             inaccessible_incoming_transactions: HashSet::new(),
             native_token_foundries: HashMap::new(),
+            watch_only: self.watch_only,
+            metadata: self.metadata,
+            address_buckets: HashMap::new(),
+            minted_nfts: Vec::new(),
+            aliases: HashMap::new(),
+            spam_filter: SpamFilter::default(),
+            internal_transfers: HashMap::new(),
+            idempotency_keys: HashMap::new(),
+            unconfirmed_deposits: HashMap::new(),
+            cached_balance: None,
+            spending_reservations: Vec::new(),
         };
 
         let account_handle = AccountHandle::new(
             account,
             client,
             self.secret_manager.clone(),
+            self.signing_guard.clone(),
+            self.request_limiter.clone(),
+            #[cfg(feature = "ledger_nano")]
+            self.ledger_nano_queue.clone(),
             #[cfg(feature = "events")]
             self.event_emitter.clone(),
             #[cfg(feature = "storage")]
             self.storage_manager.clone(),
-        );
+        )
+        .with_client_options(self.account_client_options.clone());
         #[cfg(feature = "storage")]
         account_handle.save(None).await?;
+        #[cfg(feature = "storage")]
+        if let Some(client_options) = &self.account_client_options {
+            self.storage_manager
+                .lock()
+                .await
+                .save_account_client_options(account_index, Some(client_options))
+                .await?;
+        }
+        // Store the fingerprint of the seed, so a secret manager with another seed can be detected later
+        #[cfg(feature = "storage")]
+        if self.addresses.is_none() && !self.storage_manager.lock().await.has_seed_fingerprint().await? {
+            let seed_fingerprint = SeedFingerprint::new(
+                &self.secret_manager,
+                #[cfg(feature = "ledger_nano")]
+                &self.ledger_nano_queue,
+                coin_type,
+            )
+            .await?;
+            self.storage_manager
+                .lock()
+                .await
+                .save_seed_fingerprint(&seed_fingerprint)
+                .await?;
+        }
         accounts.push(account_handle.clone());
 
         Ok(account_handle)
     }
 }
 
-/// Generate the first public address of an account
+/// Generate the first public address of an account, through the queue if the secret manager is a Ledger Nano
 pub(crate) async fn get_first_public_address(
     secret_manager: &Arc<RwLock<SecretManager>>,
+    #[cfg(feature = "ledger_nano")] ledger_nano_queue: &LedgerNanoQueue,
     coin_type: u32,
     account_index: u32,
 ) -> crate::Result<Address> {
-    Ok(secret_manager
-        .read()
-        .await
-        .generate_addresses(coin_type, account_index, 0..1, false, None)
-        .await?[0])
+    let secret_manager = secret_manager.read().await;
+    let generate_address = async {
+        Ok(secret_manager
+            .generate_addresses(coin_type, account_index, 0..1, false, None)
+            .await?[0])
+    };
+    #[cfg(feature = "ledger_nano")]
+    let generate_address = ledger_nano_queue.run_for(
+        &secret_manager,
+        LedgerNanoOperation::GenerateAddresses,
+        generate_address,
+    );
+    generate_address.await
 }
@@ -17,3 +17,15 @@ pub(crate) const MIN_SYNC_INTERVAL: u128 = 5;
 
 // Default expiration time for [ExpirationUnlockCondition] when sending native tokens, one day in seconds
 pub(crate) const DEFAULT_EXPIRATION_TIME: u32 = 86400;
+
+/// Seconds that are subtracted from the milestone timestamp of address sync cursors, so outputs from milestones that
+/// the indexer didn't process yet during the previous sync are requested again
+#[cfg(feature = "storage")]
+pub(crate) const ADDRESS_SYNC_CURSOR_OVERLAP: u32 = 60;
+
+/// The first index of watch-only accounts, they don't derive addresses from the seed, so they don't use the BIP44
+/// account indexes
+pub(crate) const WATCH_ACCOUNT_INDEX_OFFSET: u32 = 1 << 31;
+
+/// Amount of counterpart addresses that are returned in the account statistics
+pub(crate) const TOP_COUNTERPARTS_AMOUNT: usize = 10;
@@ -1,10 +1,15 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{collections::HashMap, ops::Deref, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Deref,
+    sync::Arc,
+};
 
 use iota_client::{
     block::{
+        address::Address,
         output::{FoundryId, Output, OutputId, TokenId},
         payload::transaction::TransactionId,
     },
@@ -14,19 +19,28 @@ use iota_client::{
 use serde::{Deserialize, Serialize};
 use tokio::sync::{Mutex, RwLock};
 
+#[cfg(feature = "ledger_nano")]
+use crate::account_manager::LedgerNanoQueue;
 #[cfg(feature = "events")]
-use crate::events::EventEmitter;
+use crate::events::{
+    types::{AlertCode, SyncProgressEvent},
+    EventEmitter,
+};
 #[cfg(feature = "storage")]
 use crate::storage::manager::StorageManagerHandle;
 use crate::{
     account::{
+        operations::{
+            syncing::{freshness::SyncFreshness, request_limiter::RequestLimiter},
+            transaction::signing_policy::SigningGuard,
+        },
         types::{
             address::{AccountAddress, AddressWithUnspentOutputs},
-            OutputData, Transaction,
+            InclusionState, OutputData, Transaction,
         },
         Account,
     },
-    Result,
+    ClientOptions, Result,
 };
 
 /// Options to filter outputs
@@ -41,6 +55,51 @@ pub struct FilterOptions {
     /// Filter all outputs for the provided types (Basic = 3, Alias = 4, Foundry = 5, NFT = 6).
     #[serde(rename = "outputTypes")]
     pub output_types: Option<Vec<u8>>,
+    /// Filter all outputs where the amount is below the specified amount
+    #[serde(rename = "minAmount")]
+    pub min_amount: Option<u64>,
+    /// Filter all outputs where the amount is above the specified amount
+    #[serde(rename = "maxAmount")]
+    pub max_amount: Option<u64>,
+    /// Filter all outputs that don't belong to one of the provided Bech32 encoded addresses of the account
+    pub addresses: Option<Vec<String>>,
+    /// Filter all outputs that don't hold the provided native token
+    #[serde(rename = "nativeTokenId")]
+    pub native_token_id: Option<TokenId>,
+}
+
+/// Options to filter transactions
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct TransactionFilterOptions {
+    /// Filter all transactions that don't have the provided label
+    pub label: Option<String>,
+    /// Filter all transactions where the note doesn't contain the provided text, case-insensitively
+    pub note: Option<String>,
+    /// Filter all transactions that aren't in the provided inclusion state
+    #[serde(rename = "inclusionState")]
+    pub inclusion_state: Option<InclusionState>,
+}
+
+impl TransactionFilterOptions {
+    fn matches(&self, transaction: &Transaction) -> bool {
+        if let Some(label) = &self.label {
+            if !transaction.labels.contains(label) {
+                return false;
+            }
+        }
+        if let Some(note) = &self.note {
+            match &transaction.note {
+                Some(transaction_note) if transaction_note.to_lowercase().contains(&note.to_lowercase()) => {}
+                _ => return false,
+            }
+        }
+        if let Some(inclusion_state) = self.inclusion_state {
+            if transaction.inclusion_state != inclusion_state {
+                return false;
+            }
+        }
+        true
+    }
 }
 
 /// A thread guard over an account, so we can lock the account during operations.
@@ -48,13 +107,27 @@ pub struct FilterOptions {
 pub struct AccountHandle {
     account: Arc<RwLock<Account>>,
     pub(crate) client: Client,
+    // Client options of the account, if it doesn't use the ones of the account manager
+    pub(crate) client_options: Option<ClientOptions>,
     pub(crate) secret_manager: Arc<RwLock<SecretManager>>,
+    pub(crate) signing_guard: Arc<RwLock<SigningGuard>>,
+    // Shared by the accounts that use the client of the account manager
+    pub(crate) request_limiter: Arc<RequestLimiter>,
+    #[cfg(feature = "ledger_nano")]
+    pub(crate) ledger_nano_queue: Arc<LedgerNanoQueue>,
     // mutex to prevent multiple sync calls at the same or almost the same time, the u128 is a timestamp
     // if the last synced time was < `MIN_SYNC_INTERVAL` second ago, we don't sync, but only calculate the balance
     // again, because sending transactions can change that
     pub(crate) last_synced: Arc<Mutex<u128>>,
+    // When the account was synced the last time and which ledger state the data reflects
+    pub(crate) sync_freshness: Arc<Mutex<SyncFreshness>>,
+    // Idempotency keys of sends that are in progress, so a retried call doesn't send a second transaction
+    pub(crate) idempotency_keys_in_flight: Arc<std::sync::Mutex<HashSet<String>>>,
     #[cfg(feature = "events")]
     pub(crate) event_emitter: Arc<Mutex<EventEmitter>>,
+    // Progress of the running sync, only one sync can run at a time because of `last_synced`
+    #[cfg(feature = "events")]
+    pub(crate) sync_progress: Arc<Mutex<SyncProgressEvent>>,
     #[cfg(feature = "storage")]
     pub(crate) storage_manager: StorageManagerHandle,
 }
@@ -65,21 +138,44 @@ impl AccountHandle {
         account: Account,
         client: Client,
         secret_manager: Arc<RwLock<SecretManager>>,
+        signing_guard: Arc<RwLock<SigningGuard>>,
+        request_limiter: Arc<RequestLimiter>,
+        #[cfg(feature = "ledger_nano")] ledger_nano_queue: Arc<LedgerNanoQueue>,
         #[cfg(feature = "events")] event_emitter: Arc<Mutex<EventEmitter>>,
         #[cfg(feature = "storage")] storage_manager: StorageManagerHandle,
     ) -> Self {
         Self {
             account: Arc::new(RwLock::new(account)),
             client,
+            client_options: None,
             secret_manager,
+            signing_guard,
+            request_limiter,
+            #[cfg(feature = "ledger_nano")]
+            ledger_nano_queue,
             last_synced: Default::default(),
+            sync_freshness: Default::default(),
+            idempotency_keys_in_flight: Default::default(),
             #[cfg(feature = "events")]
             event_emitter,
+            #[cfg(feature = "events")]
+            sync_progress: Default::default(),
             #[cfg(feature = "storage")]
             storage_manager,
         }
     }
 
+    // Set the client options the client of the account was built with, if they differ from the ones of the account
+    // manager
+    pub(crate) fn with_client_options(mut self, client_options: Option<ClientOptions>) -> Self {
+        // Other nodes get their own request budget
+        if client_options.is_some() {
+            self.request_limiter = Arc::new(RequestLimiter::new(self.request_limiter.limits()));
+        }
+        self.client_options = client_options;
+        self
+    }
+
     pub async fn alias(&self) -> String {
         self.read().await.alias.clone()
     }
@@ -89,6 +185,11 @@ impl AccountHandle {
         &self.client
     }
 
+    /// Get the client options of the account, if it doesn't use the ones of the account manager
+    pub fn client_options(&self) -> Option<&ClientOptions> {
+        self.client_options.as_ref()
+    }
+
     /// Get the [`OutputData`] of an output stored in the account
     pub async fn get_output(&self, output_id: &OutputId) -> Option<OutputData> {
         let account = self.read().await;
@@ -153,58 +254,13 @@ impl AccountHandle {
 
     /// Returns outputs of the account
     pub async fn outputs(&self, filter: Option<FilterOptions>) -> Result<Vec<OutputData>> {
-        let mut outputs = Vec::new();
-
-        for output in self.read().await.outputs.values() {
-            if let Some(filter_options) = &filter {
-                if let Some(lower_bound_booked_timestamp) = filter_options.lower_bound_booked_timestamp {
-                    if output.metadata.milestone_timestamp_booked < lower_bound_booked_timestamp {
-                        continue;
-                    }
-                }
-                if let Some(upper_bound_booked_timestamp) = filter_options.upper_bound_booked_timestamp {
-                    if output.metadata.milestone_timestamp_booked > upper_bound_booked_timestamp {
-                        continue;
-                    }
-                }
-                if let Some(output_types) = &filter_options.output_types {
-                    if !output_types.contains(&output.output.kind()) {
-                        continue;
-                    }
-                }
-            }
-            outputs.push(output.clone());
-        }
-
-        Ok(outputs)
+        filter_outputs(self.read().await.outputs.values(), filter)
     }
 
-    /// Returns unspent outputs of the account
+    /// Returns unspent outputs of the account. The filter can be used to pick the outputs that should fund a
+    /// transaction as [`TransactionOptions::custom_inputs`](crate::account::TransactionOptions::custom_inputs).
     pub async fn unspent_outputs(&self, filter: Option<FilterOptions>) -> Result<Vec<OutputData>> {
-        let mut outputs = Vec::new();
-
-        for output in self.read().await.unspent_outputs.values() {
-            if let Some(filter_options) = &filter {
-                if let Some(lower_bound_booked_timestamp) = filter_options.lower_bound_booked_timestamp {
-                    if output.metadata.milestone_timestamp_booked < lower_bound_booked_timestamp {
-                        continue;
-                    }
-                }
-                if let Some(upper_bound_booked_timestamp) = filter_options.upper_bound_booked_timestamp {
-                    if output.metadata.milestone_timestamp_booked > upper_bound_booked_timestamp {
-                        continue;
-                    }
-                }
-                if let Some(output_types) = &filter_options.output_types {
-                    if !output_types.contains(&output.output.kind()) {
-                        continue;
-                    }
-                }
-            }
-            outputs.push(output.clone());
-        }
-
-        Ok(outputs)
+        filter_outputs(self.read().await.unspent_outputs.values(), filter)
     }
 
     /// Returns all incoming transactions of the account
@@ -217,6 +273,19 @@ impl AccountHandle {
         Ok(self.read().await.transactions.values().cloned().collect())
     }
 
+    /// Returns the transactions of the account that match the filter, to search them by their local label, note or
+    /// inclusion state
+    pub async fn filter_transactions(&self, filter: TransactionFilterOptions) -> Result<Vec<Transaction>> {
+        Ok(self
+            .read()
+            .await
+            .transactions
+            .values()
+            .filter(|transaction| filter.matches(transaction))
+            .cloned()
+            .collect())
+    }
+
     /// Returns all pending transactions of the account
     pub async fn pending_transactions(&self) -> Result<Vec<Transaction>> {
         let mut transactions = Vec::new();
@@ -235,23 +304,58 @@ impl AccountHandle {
     /// saving
     #[cfg(feature = "storage")]
     pub(crate) async fn save(&self, updated_account: Option<&Account>) -> Result<()> {
+        let result = self.save_to_storage(updated_account).await;
+        #[cfg(feature = "events")]
+        if let Err(err) = &result {
+            // Don't read the account if it was passed, the caller holds the lock
+            let account_index = match updated_account {
+                Some(account) => account.index,
+                None => self.read().await.index,
+            };
+            self.emit_alert(account_index, AlertCode::StorageWriteFailed, err.to_string())
+                .await;
+        }
+        result
+    }
+
+    #[cfg(feature = "storage")]
+    async fn save_to_storage(&self, updated_account: Option<&Account>) -> Result<()> {
         log::debug!("[save] saving account to database");
         match updated_account {
             Some(account) => {
                 let mut storage_manager = self.storage_manager.lock().await;
                 storage_manager.save_account(account).await?;
+                // Release the reservations of outputs that aren't locked anymore
+                storage_manager
+                    .update_input_reservations(account.index, &account.locked_outputs)
+                    .await?;
                 drop(storage_manager);
             }
             None => {
                 let account = self.read().await;
                 let mut storage_manager = self.storage_manager.lock().await;
                 storage_manager.save_account(&account).await?;
+                storage_manager
+                    .update_input_reservations(account.index, &account.locked_outputs)
+                    .await?;
                 drop(storage_manager);
                 drop(account);
             }
         }
         Ok(())
     }
+
+    /// Exports the account with its addresses, outputs and transactions, but without any secrets, to a file encrypted
+    /// with the password. It can be imported in another account manager with the same seed with
+    /// [`crate::account_manager::AccountManager::import_account()`].
+    #[cfg(feature = "storage")]
+    pub async fn export(&self, path: impl AsRef<std::path::Path>, password: &str) -> Result<()> {
+        // Save first, so the file contains the current state
+        let account = self.read().await;
+        let mut storage_manager = self.storage_manager.lock().await;
+        storage_manager.save_account(&account).await?;
+        storage_manager.export_account(*account.index(), path, password).await
+    }
 }
 
 // impl Deref so we can use `account_handle.read()` instead of `account_handle.account.read()`
@@ -261,3 +365,69 @@ impl Deref for AccountHandle {
         self.account.deref()
     }
 }
+
+fn filter_outputs<'a>(
+    outputs: impl Iterator<Item = &'a OutputData>,
+    filter: Option<FilterOptions>,
+) -> Result<Vec<OutputData>> {
+    let filter_options = match filter {
+        Some(filter_options) => filter_options,
+        None => return Ok(outputs.cloned().collect()),
+    };
+    let addresses = match &filter_options.addresses {
+        Some(addresses) => Some(
+            addresses
+                .iter()
+                .map(|address| Ok(Address::try_from_bech32(address)?.1))
+                .collect::<Result<HashSet<Address>>>()?,
+        ),
+        None => None,
+    };
+
+    let mut filtered_outputs = Vec::new();
+    for output in outputs {
+        if let Some(lower_bound_booked_timestamp) = filter_options.lower_bound_booked_timestamp {
+            if output.metadata.milestone_timestamp_booked < lower_bound_booked_timestamp {
+                continue;
+            }
+        }
+        if let Some(upper_bound_booked_timestamp) = filter_options.upper_bound_booked_timestamp {
+            if output.metadata.milestone_timestamp_booked > upper_bound_booked_timestamp {
+                continue;
+            }
+        }
+        if let Some(output_types) = &filter_options.output_types {
+            if !output_types.contains(&output.output.kind()) {
+                continue;
+            }
+        }
+        if let Some(min_amount) = filter_options.min_amount {
+            if output.output.amount() < min_amount {
+                continue;
+            }
+        }
+        if let Some(max_amount) = filter_options.max_amount {
+            if output.output.amount() > max_amount {
+                continue;
+            }
+        }
+        if let Some(addresses) = &addresses {
+            if !addresses.contains(&output.address) {
+                continue;
+            }
+        }
+        if let Some(native_token_id) = &filter_options.native_token_id {
+            let holds_native_token = output.output.native_tokens().map_or(false, |native_tokens| {
+                native_tokens
+                    .iter()
+                    .any(|native_token| native_token.token_id() == native_token_id)
+            });
+            if !holds_native_token {
+                continue;
+            }
+        }
+        filtered_outputs.push(output.clone());
+    }
+
+    Ok(filtered_outputs)
+}
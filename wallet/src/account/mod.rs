@@ -24,7 +24,7 @@ use instant::SystemTime;
 use iota_client::{
     api_types::core::response::OutputWithMetadataResponse,
     block::{
-        output::{FoundryId, FoundryOutput, OutputId},
+        output::{AliasId, FoundryId, FoundryOutput, NftId, OutputId},
         payload::{
             transaction::{TransactionEssence, TransactionId},
             TransactionPayload,
@@ -34,26 +34,66 @@ use iota_client::{
 };
 use serde::{de, Deserialize, Deserializer, Serialize};
 
+#[cfg(feature = "storage")]
+pub use self::operations::{
+    offline_queue::{QueueSubmission, QueueSubmissionDto},
+    transaction_history::{TransactionHistoryFilter, TransactionPage, TransactionPageDto},
+};
 use self::types::{
-    address::{AccountAddress, AddressWithUnspentOutputs},
-    AccountBalance, OutputData, Transaction,
+    address::{AccountAddress, AddressWithUnspentOutputs, AddressWrapper},
+    AccountBalance, AliasRecord, OutputData, Transaction,
 };
 pub use self::{
-    handle::{AccountHandle, FilterOptions},
+    handle::{AccountHandle, FilterOptions, TransactionFilterOptions},
     operations::{
         address_generation::AddressGenerationOptions,
+        claimable_outputs::{ClaimBatch, ClaimBatchDto, ClaimableOutput, ClaimableOutputsFilter, FailedClaim},
+        expiring_outputs::ExpiringOutput,
+        history_export::{HistoryExportFormat, HistoryExportRow},
+        nft_collections::AccountNft,
         output_claiming::OutputsToClaim,
+        output_consolidation::ConsolidationPolicy,
+        spam_filter::{QuarantinedOutput, SpamFilter, SpamReason},
         syncing::{
-            options::{AccountSyncOptions, AliasSyncOptions, NftSyncOptions},
+            cancellation::CancellationToken,
+            cross_verification::{CrossVerificationOptions, SyncDiscrepancy},
+            freshness::SyncFreshness,
+            options::{AccountSyncOptions, AliasSyncOptions, NftSyncOptions, ReattachmentOptions, SyncScope},
+            preview::{BalanceDelta, SyncPreview},
+            request_limiter::RequestLimits,
             SyncOptions,
         },
         transaction::{
-            high_level::minting::mint_native_token::MintTokenTransactionDto,
+            dust_policy::{DustPolicy, DustWarning},
+            high_level::{
+                atomic_swap::{SwapAcceptance, SwapAssets, SwapOffer, SWAP_OFFER_VERSION},
+                decline_output::DeclinedAssets,
+                estimate_send::SendEstimate,
+                minting::{
+                    mint_native_token::{
+                        MintTokenTransactionDto, PreparedMintTokenTransaction, PreparedMintTokenTransactionDto,
+                    },
+                    nft_mint_builder::{
+                        Irc27Attribute, Irc27Metadata, Irc27Nft, MintNftsTransaction, MintNftsTransactionDto,
+                        NftMintBuilder,
+                    },
+                },
+                native_token_operations::{NativeTokenEstimate, NativeTokenOperation},
+                send_batch::{BatchRecipient, BatchSend, BatchSendDto, RecipientResult},
+                send_with_conditions::{ConditionalSend, ConditionalSendDto, SendOptions, SendWarning},
+                sweep::{FailedSweep, Sweep, SweepDto, SweepOptions},
+                vesting::{VestingSchedule, VestingUnlock},
+            },
             prepare_output::{Assets, Features, OutputOptions, ReturnStrategy, StorageDeposit, Unlocks},
+            sign_request::{SignRequest, SignResponse, SIGN_REQUEST_VERSION},
+            signing_policy::{
+                SigningPolicy, SigningRequest, SpendingReservation, TransactionApproval, TransactionApprovalRequest,
+            },
             RemainderValueStrategy, TransactionOptions, TransactionOptionsDto,
         },
+        upcoming_unlocks::UpcomingUnlock,
     },
-    types::OutputDataDto,
+    types::{InternalTransfer, OutputDataDto, TransactionDirection},
 };
 use crate::account::types::InclusionState;
 
@@ -105,6 +145,43 @@ pub struct Account {
     /// Foundries for native tokens in outputs
     #[serde(default)]
     native_token_foundries: HashMap<FoundryId, FoundryOutput>,
+    /// Watch-only accounts track addresses that are controlled externally, they can't generate addresses or sign
+    /// transactions
+    #[serde(default)]
+    watch_only: bool,
+    /// Metadata of the application, like a color, an icon or an external id
+    #[serde(default)]
+    metadata: HashMap<String, String>,
+    /// Named buckets of addresses, like "deposits" or "cold", used for accounting and to restrict the input selection
+    #[serde(default)]
+    address_buckets: HashMap<String, HashSet<AddressWrapper>>,
+    /// Ids of the NFTs that were minted by the account, in the order they were minted
+    #[serde(default)]
+    pub(crate) minted_nfts: Vec<NftId>,
+    /// The aliases the account controls or controlled, updated when the account is synced
+    #[serde(default)]
+    aliases: HashMap<AliasId, AliasRecord>,
+    /// Allow and deny lists for unwanted NFTs and native tokens
+    #[serde(default)]
+    spam_filter: SpamFilter,
+    /// Transactions to or from other accounts of the wallet
+    #[serde(default)]
+    internal_transfers: HashMap<TransactionId, InternalTransfer>,
+    /// Idempotency keys of sent transactions, so a retried send returns the transaction instead of sending again. Keys
+    /// are removed with their transaction or after 30 days
+    #[serde(default)]
+    idempotency_keys: HashMap<String, TransactionId>,
+    /// Received outputs that don't have the confirmations of the sync options yet, with the confirmations they were
+    /// last reported with
+    #[serde(default)]
+    pub(crate) unconfirmed_deposits: HashMap<OutputId, Option<u32>>,
+    /// The balance from the last time it was calculated, adjusted for the inputs locked or unlocked since then. Cheap
+    /// to read, but the exact balance is returned by [`AccountHandle::balance()`](handle::AccountHandle::balance).
+    #[serde(default)]
+    cached_balance: Option<AccountBalance>,
+    /// The assets of the transactions that were signed in the last 24 hours, for the daily limits of the signing policy
+    #[serde(default)]
+    pub(crate) spending_reservations: Vec<SpendingReservation>,
 }
 
 // Custom deserialization to stay backwards compatible
@@ -158,6 +235,11 @@ pub(crate) fn build_transaction_from_payload_and_inputs(
         network_id: tx_essence.network_id(),
         incoming: true,
         note: None,
+        labels: Vec::new(),
+        reattachments: 0,
+        retry_of: None,
+        retried_by: None,
+        expires_at: None,
         inputs,
     })
 }
@@ -233,6 +315,11 @@ fn serialize() {
         inclusion_state: InclusionState::Pending,
         incoming: false,
         note: None,
+        labels: Vec::new(),
+        reattachments: 0,
+        retry_of: None,
+        retried_by: None,
+        expires_at: None,
         inputs: Vec::new(),
     };
 
@@ -257,6 +344,17 @@ fn serialize() {
         incoming_transactions,
         inaccessible_incoming_transactions: HashSet::new(),
         native_token_foundries: HashMap::new(),
+        watch_only: false,
+        metadata: HashMap::from([("color".to_string(), "#00ff00".to_string())]),
+        address_buckets: HashMap::new(),
+        minted_nfts: Vec::new(),
+        aliases: HashMap::new(),
+        spam_filter: SpamFilter::default(),
+        internal_transfers: HashMap::new(),
+        idempotency_keys: HashMap::new(),
+        unconfirmed_deposits: HashMap::new(),
+        cached_balance: None,
+        spending_reservations: Vec::new(),
     };
 
     serde_json::from_str::<Account>(&serde_json::to_string(&account).unwrap()).unwrap();
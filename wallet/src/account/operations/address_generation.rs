@@ -1,15 +1,25 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use iota_client::secret::{GenerateAddressOptions, SecretManage, SecretManager};
+use iota_client::{
+    block::address::Address,
+    secret::{GenerateAddressOptions, SecretManage, SecretManager},
+};
 use serde::{Deserialize, Serialize};
 
 use crate::account::{
     handle::AccountHandle,
     types::address::{AccountAddress, AddressWrapper},
 };
+#[cfg(feature = "ledger_nano")]
+use crate::account_manager::LedgerNanoOperation;
 #[cfg(all(feature = "events", any(feature = "ledger_nano", feature = "ledger_nano")))]
 use crate::events::types::{AddressData, WalletEvent};
+#[cfg(feature = "storage")]
+use crate::storage::{
+    audit_log::AuditOperation, derivation_cache::get_verified_cached_addresses,
+    seed_fingerprint::verify_unverified_seed_fingerprint,
+};
 
 /// Options for address generation
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -39,6 +49,38 @@ impl AccountHandle {
         options: Option<AddressGenerationOptions>,
     ) -> crate::Result<Vec<AccountAddress>> {
         let options = options.unwrap_or_default();
+        #[cfg(feature = "storage")]
+        let parameters = (amount, options.clone());
+
+        let result = self.generate_addresses_internal(amount, options).await;
+
+        #[cfg(feature = "storage")]
+        {
+            let account_index = *self.read().await.index();
+            if let Err(err) = self
+                .storage_manager
+                .lock()
+                .await
+                .audit(
+                    Some(account_index),
+                    AuditOperation::GenerateAddresses,
+                    &parameters,
+                    &result,
+                )
+                .await
+            {
+                log::debug!("[ADDRESS GENERATION] audit log error: {}", err);
+            }
+        }
+
+        result
+    }
+
+    async fn generate_addresses_internal(
+        &self,
+        amount: u32,
+        options: AddressGenerationOptions,
+    ) -> crate::Result<Vec<AccountAddress>> {
         log::debug!(
             "[ADDRESS GENERATION] generating {amount} addresses, internal: {}",
             options.internal
@@ -48,6 +90,9 @@ impl AccountHandle {
         }
 
         let account = self.read().await;
+        if account.watch_only {
+            return Err(crate::Error::WatchOnlyAccount);
+        }
 
         // get the highest index for the public or internal addresses
         let highest_current_index_plus_one = if options.internal {
@@ -66,64 +111,112 @@ impl AccountHandle {
 
         let address_range = highest_current_index_plus_one..highest_current_index_plus_one + amount;
 
+        // Addresses that were derived before don't need to be derived again, unless they should be shown on a device
+        #[cfg(feature = "storage")]
+        if !options.options.as_ref().map_or(false, |o| o.ledger_nano_prompt) {
+            if let Some(addresses) = get_verified_cached_addresses(
+                &self.storage_manager,
+                &self.secret_manager,
+                #[cfg(feature = "ledger_nano")]
+                &self.ledger_nano_queue,
+                account.index,
+                account.coin_type,
+                options.internal,
+                address_range.clone(),
+            )
+            .await?
+            {
+                drop(account);
+                log::debug!("[ADDRESS GENERATION] using cached addresses");
+                return self
+                    .add_generated_addresses(addresses, highest_current_index_plus_one, options.internal, bech32_hrp)
+                    .await;
+            }
+        }
+        #[cfg(feature = "storage")]
+        let (account_index, coin_type) = (account.index, account.coin_type);
+        #[cfg(feature = "storage")]
+        verify_unverified_seed_fingerprint(
+            &self.storage_manager,
+            &self.secret_manager,
+            #[cfg(feature = "ledger_nano")]
+            &self.ledger_nano_queue,
+        )
+        .await?;
+
         let addresses = match &*self.secret_manager.read().await {
             #[cfg(feature = "ledger_nano")]
             SecretManager::LedgerNano(ledger_nano) => {
-                // If we don't sync, then we want to display the prompt on the ledger with the address. But the user
-                // needs to have it visible on the computer first, so we need to generate it without the
-                // prompt first
-                if options.options.clone().unwrap_or_default().ledger_nano_prompt {
-                    let changed_options = options.options.clone().map(|mut options| {
-                        // Change options so ledger will not show the prompt the first time
-                        options.ledger_nano_prompt = false;
-                        options
-                    });
-                    let mut addresses = Vec::new();
-
-                    for address_index in address_range {
-                        #[cfg(feature = "events")]
-                        {
-                            // Generate without prompt to be able to display it
-                            let address = ledger_nano
-                                .generate_addresses(
-                                    account.coin_type,
-                                    account.index,
-                                    address_index..address_index + 1,
-                                    options.internal,
-                                    changed_options.clone(),
-                                )
-                                .await?;
-                            self.event_emitter.lock().await.emit(
-                                account.index,
-                                WalletEvent::LedgerAddressGeneration(AddressData {
-                                    address: address[0].to_bech32(bech32_hrp.clone()),
-                                }),
-                            );
-                        }
-                        // Generate with prompt so the user can verify
-                        let address = ledger_nano
-                            .generate_addresses(
-                                account.coin_type,
-                                account.index,
-                                address_index..address_index + 1,
-                                options.internal,
-                                options.options.clone(),
-                            )
-                            .await?;
-                        addresses.push(address[0]);
-                    }
-                    addresses
+                let operation = if options.options.as_ref().map_or(false, |o| o.ledger_nano_prompt) {
+                    LedgerNanoOperation::ShowAddresses
                 } else {
-                    ledger_nano
-                        .generate_addresses(
-                            account.coin_type,
-                            account.index,
-                            address_range.clone(),
-                            options.internal,
-                            options.options,
-                        )
-                        .await?
-                }
+                    LedgerNanoOperation::GenerateAddresses
+                };
+                self.ledger_nano_queue
+                    .run(
+                        operation,
+                        #[cfg(feature = "events")]
+                        Some((&*self.event_emitter, account.index)),
+                        async {
+                            // If we don't sync, then we want to display the prompt on the ledger with the address.
+                            // But the user needs to have it visible on the computer first, so we need to generate it
+                            // without the prompt first
+                            if options.options.clone().unwrap_or_default().ledger_nano_prompt {
+                                let changed_options = options.options.clone().map(|mut options| {
+                                    // Change options so ledger will not show the prompt the first time
+                                    options.ledger_nano_prompt = false;
+                                    options
+                                });
+                                let mut addresses = Vec::new();
+
+                                for address_index in address_range {
+                                    #[cfg(feature = "events")]
+                                    {
+                                        // Generate without prompt to be able to display it
+                                        let address = ledger_nano
+                                            .generate_addresses(
+                                                account.coin_type,
+                                                account.index,
+                                                address_index..address_index + 1,
+                                                options.internal,
+                                                changed_options.clone(),
+                                            )
+                                            .await?;
+                                        self.event_emitter.lock().await.emit(
+                                            account.index,
+                                            WalletEvent::LedgerAddressGeneration(AddressData {
+                                                address: address[0].to_bech32(bech32_hrp.clone()),
+                                            }),
+                                        );
+                                    }
+                                    // Generate with prompt so the user can verify
+                                    let address = ledger_nano
+                                        .generate_addresses(
+                                            account.coin_type,
+                                            account.index,
+                                            address_index..address_index + 1,
+                                            options.internal,
+                                            options.options.clone(),
+                                        )
+                                        .await?;
+                                    addresses.push(address[0]);
+                                }
+                                Ok(addresses)
+                            } else {
+                                ledger_nano
+                                    .generate_addresses(
+                                        account.coin_type,
+                                        account.index,
+                                        address_range.clone(),
+                                        options.internal,
+                                        options.options,
+                                    )
+                                    .await
+                                    .map_err(Into::into)
+                            }
+                        },
+                    )
+                    .await?
             }
             #[cfg(feature = "stronghold")]
             SecretManager::Stronghold(stronghold) => {
@@ -153,23 +246,103 @@ impl AccountHandle {
 
         drop(account);
 
+        #[cfg(feature = "storage")]
+        self.storage_manager
+            .lock()
+            .await
+            .cache_addresses(
+                account_index,
+                coin_type,
+                options.internal,
+                highest_current_index_plus_one,
+                &addresses,
+            )
+            .await?;
+
+        self.add_generated_addresses(addresses, highest_current_index_plus_one, options.internal, bech32_hrp)
+            .await
+    }
+
+    // Stores the generated addresses in the account
+    async fn add_generated_addresses(
+        &self,
+        addresses: Vec<Address>,
+        first_key_index: u32,
+        internal: bool,
+        bech32_hrp: String,
+    ) -> crate::Result<Vec<AccountAddress>> {
         let generate_addresses: Vec<AccountAddress> = addresses
             .into_iter()
             .enumerate()
             .map(|(index, address)| AccountAddress {
                 address: AddressWrapper::new(address, bech32_hrp.clone()),
-                key_index: highest_current_index_plus_one + index as u32,
-                internal: options.internal,
+                key_index: first_key_index + index as u32,
+                internal,
                 used: false,
             })
             .collect();
 
-        self.update_account_addresses(options.internal, generate_addresses.clone())
+        self.update_account_addresses(internal, generate_addresses.clone())
             .await?;
 
         Ok(generate_addresses)
     }
 
+    /// Derives a stored address again on the Ledger Nano and shows it on the device, so the user can confirm that it's
+    /// the address shown on the computer. Returns whether the address derived by the device matches the stored one,
+    /// fails if the user rejects the address on the device.
+    #[cfg(feature = "ledger_nano")]
+    pub async fn verify_address_on_device(&self, address: &str) -> crate::Result<bool> {
+        log::debug!("[ADDRESS GENERATION] verifying {address} on device");
+        let account = self.read().await;
+        let account_address = account
+            .public_addresses
+            .iter()
+            .chain(account.internal_addresses.iter())
+            .find(|account_address| account_address.address.to_bech32() == address)
+            .ok_or_else(|| crate::Error::AddressNotFoundInAccount(address.to_string()))?
+            .clone();
+        let (coin_type, account_index) = (account.coin_type, account.index);
+        drop(account);
+
+        match &*self.secret_manager.read().await {
+            SecretManager::LedgerNano(ledger_nano) => {
+                #[cfg(feature = "events")]
+                self.event_emitter.lock().await.emit(
+                    account_index,
+                    WalletEvent::LedgerAddressGeneration(AddressData {
+                        address: address.to_string(),
+                    }),
+                );
+                let derived_addresses = self
+                    .ledger_nano_queue
+                    .run(
+                        LedgerNanoOperation::ShowAddresses,
+                        #[cfg(feature = "events")]
+                        Some((&*self.event_emitter, account_index)),
+                        async {
+                            ledger_nano
+                                .generate_addresses(
+                                    coin_type,
+                                    account_index,
+                                    account_address.key_index..account_address.key_index + 1,
+                                    account_address.internal,
+                                    Some(GenerateAddressOptions {
+                                        ledger_nano_prompt: true,
+                                        ..Default::default()
+                                    }),
+                                )
+                                .await
+                                .map_err(Into::into)
+                        },
+                    )
+                    .await?;
+                Ok(derived_addresses.first() == Some(&account_address.address.inner))
+            }
+            _ => Err(iota_client::Error::SecretManagerMismatch.into()),
+        }
+    }
+
     /// Generate an internal address and store in the account, internal addresses are used for remainder outputs
     pub(crate) async fn generate_remainder_address(&self) -> crate::Result<AccountAddress> {
         let result = self
@@ -0,0 +1,22 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    account::handle::AccountHandle,
+    events::types::{AlertCode, AlertEvent, WalletEvent},
+};
+
+impl AccountHandle {
+    // Emits an Alert event, the account index is passed, because this is also called while the account is locked
+    pub(crate) async fn emit_alert(&self, account_index: u32, code: AlertCode, message: String) {
+        log::debug!("[ALERT] {:?}: {}", code, message);
+        self.event_emitter.lock().await.emit(
+            account_index,
+            WalletEvent::Alert(AlertEvent {
+                code,
+                severity: code.severity(),
+                message,
+            }),
+        );
+    }
+}
@@ -1,21 +1,48 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use iota_client::block::output::{unlock_condition::UnlockCondition, FoundryId, NativeTokensBuilder, Output, Rent};
+use iota_client::block::{
+    address::Address,
+    output::{unlock_condition::UnlockCondition, FoundryId, NativeTokensBuilder, Output, Rent},
+};
 use primitive_types::U256;
 
 use crate::account::{
     handle::AccountHandle,
-    operations::helpers::time::can_output_be_unlocked_forever_from_now_on,
-    types::{AccountBalance, BaseCoinBalance, NativeTokensBalance, RequiredStorageDeposit},
+    operations::{helpers::time::can_output_be_unlocked_forever_from_now_on, spam_filter::account_addresses},
+    types::{AccountBalance, BaseCoinBalance, NativeTokensBalance, OutputData, RequiredStorageDeposit},
     OutputsToClaim,
 };
+#[cfg(feature = "storage")]
+use crate::storage::balance_summary::{AccountBalanceSummary, BalanceSummary};
 
 impl AccountHandle {
     /// Get the AccountBalance
     pub async fn balance(&self) -> crate::Result<AccountBalance> {
+        let balance = self.balance_of_addresses(None).await?;
+        self.write().await.cached_balance = Some(balance.clone());
+        Ok(balance)
+    }
+
+    /// Get the balance of the outputs on the addresses of a bucket
+    pub async fn address_bucket_balance(&self, bucket: &str) -> crate::Result<AccountBalance> {
+        let bucket_addresses = self
+            .read()
+            .await
+            .address_buckets
+            .get(bucket)
+            .ok_or_else(|| crate::Error::AddressBucketNotFound(bucket.to_string()))?
+            .iter()
+            .map(|address| address.inner)
+            .collect();
+
+        self.balance_of_addresses(Some(&bucket_addresses)).await
+    }
+
+    // Get the balance of the outputs on the provided addresses, or of all outputs if `None`
+    async fn balance_of_addresses(&self, addresses: Option<&HashSet<Address>>) -> crate::Result<AccountBalance> {
         log::debug!("[BALANCE] get balance");
         let unlockable_outputs_with_multiple_unlock_conditions = self
             .get_unlockable_outputs_with_additional_unlock_conditions(OutputsToClaim::All)
@@ -36,14 +63,19 @@ impl AccountHandle {
         let mut aliases = Vec::new();
         let mut foundries = Vec::new();
         let mut nfts = Vec::new();
+        let mut quarantined = Vec::new();
 
         let account = self.read().await;
+        let all_account_addresses = account_addresses(&account);
 
         for output_data in account.unspent_outputs.values() {
             // Check if output is from the network we're currently connected to
             if output_data.network_id != network_id {
                 continue;
             }
+            if !is_on_addresses(output_data, addresses) {
+                continue;
+            }
 
             let rent = output_data.output.rent_cost(&rent_structure);
 
@@ -84,6 +116,12 @@ impl AccountHandle {
                     foundries.push(output.id());
                 }
                 _ => {
+                    // Flagged NFTs and native tokens are listed separately until they're reviewed
+                    if let Some(quarantined_output) = account.spam_filter.check(output_data, &all_account_addresses) {
+                        quarantined.push(quarantined_output);
+                        continue;
+                    }
+
                     // If there is only an [AddressUnlockCondition], then we can spend the output at any time without
                     // restrictions
                     if let [UnlockCondition::Address(_)] = output_data
@@ -235,7 +273,12 @@ impl AccountHandle {
         for locked_output in &account.locked_outputs {
             if let Some(output_data) = account.unspent_outputs.get(locked_output) {
                 // Only check outputs that are in this network
-                if output_data.network_id == network_id {
+                if output_data.network_id == network_id
+                    && is_on_addresses(output_data, addresses)
+                    && !quarantined
+                        .iter()
+                        .any(|quarantined_output| quarantined_output.output_id == *locked_output)
+                {
                     locked_amount += output_data.output.amount();
                     if let Some(native_tokens) = output_data.output.native_tokens() {
                         locked_native_tokens.add_native_tokens(native_tokens.clone())?;
@@ -285,9 +328,12 @@ impl AccountHandle {
                 #[cfg(not(feature = "participation"))]
                 available: total_amount.saturating_sub(locked_amount),
                 #[cfg(feature = "participation")]
-                available: total_amount
-                    .saturating_sub(locked_amount)
-                    .saturating_sub(self.get_voting_power().await?),
+                available: total_amount.saturating_sub(locked_amount).saturating_sub(
+                    self.get_voting_output()
+                        .await?
+                        .filter(|voting_output| is_on_addresses(voting_output, addresses))
+                        .map_or(0, |voting_output| voting_output.output.amount()),
+                ),
             },
             native_tokens: native_tokens_balance,
             required_storage_deposit,
@@ -295,20 +341,63 @@ impl AccountHandle {
             foundries,
             nfts,
             potentially_locked_outputs,
+            quarantined,
+            freshness: self.sync_freshness().await?,
+            // The rewards can't be split by addresses
+            #[cfg(feature = "participation")]
+            staking_rewards: match addresses {
+                Some(_) => Vec::new(),
+                None => self.staking_rewards_balance().await?,
+            },
         })
     }
+
+    // Record the balance after a completed sync, so it can be aggregated without loading the accounts
+    #[cfg(feature = "storage")]
+    pub(crate) async fn store_balance_summary(&self, balance: &AccountBalance) -> crate::Result<()> {
+        let (account_index, balance_summary) = {
+            let account = self.read().await;
+            let balance_summary = AccountBalanceSummary {
+                coin_type: account.coin_type,
+                metadata: account.metadata.clone(),
+                balance: BalanceSummary {
+                    base_coin: balance.base_coin.clone(),
+                    native_tokens: balance
+                        .native_tokens
+                        .iter()
+                        .map(|native_token| (native_token.token_id, native_token.total))
+                        .collect(),
+                    accounts: 1,
+                },
+            };
+            (account.index, balance_summary)
+        };
+
+        self.storage_manager
+            .lock()
+            .await
+            .save_balance_summary(account_index, &balance_summary)
+            .await
+    }
 }
 
 pub(crate) fn add_balances(balances: Vec<AccountBalance>) -> crate::Result<AccountBalance> {
     let mut total_balance: AccountBalance = Default::default();
+    // The total is only as fresh as the account that was synced first
+    let mut freshness = None;
 
     for balance in balances {
+        freshness = Some(match freshness {
+            Some(freshness) => balance.freshness.oldest(freshness),
+            None => balance.freshness,
+        });
         total_balance.base_coin.total += balance.base_coin.total;
         total_balance.base_coin.available += balance.base_coin.available;
         total_balance.required_storage_deposit += balance.required_storage_deposit;
         total_balance.nfts.extend(balance.nfts.into_iter());
         total_balance.aliases.extend(balance.aliases.into_iter());
         total_balance.foundries.extend(balance.foundries.into_iter());
+        total_balance.quarantined.extend(balance.quarantined.into_iter());
 
         for native_token_balance in &balance.native_tokens {
             if let Some(total_native_token_balance) = total_balance
@@ -327,7 +416,28 @@ pub(crate) fn add_balances(balances: Vec<AccountBalance>) -> crate::Result<Accou
                 })
             }
         }
+
+        #[cfg(feature = "participation")]
+        for staking_rewards in balance.staking_rewards {
+            if let Some(total_staking_rewards) = total_balance
+                .staking_rewards
+                .iter_mut()
+                .find(|s| s.event_id == staking_rewards.event_id)
+            {
+                total_staking_rewards.staked += staking_rewards.staked;
+                total_staking_rewards.rewards += staking_rewards.rewards;
+                total_staking_rewards.minimum_reached |= staking_rewards.minimum_reached;
+            } else {
+                total_balance.staking_rewards.push(staking_rewards);
+            }
+        }
     }
+    total_balance.freshness = freshness.unwrap_or_default();
 
     Ok(total_balance)
 }
+
+// Check if the output is on one of the addresses, all outputs are if there are no addresses
+fn is_on_addresses(output_data: &OutputData, addresses: Option<&HashSet<Address>>) -> bool {
+    addresses.map_or(true, |addresses| addresses.contains(&output_data.address))
+}
@@ -0,0 +1,68 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::ops::Range;
+
+use crate::{
+    account::{handle::AccountHandle, AccountBalance},
+    storage::balance_history::BalanceSnapshot,
+};
+
+impl AccountHandle {
+    /// Returns the balance snapshots that were recorded after syncs in the time range, as unix timestamps in
+    /// milliseconds. A snapshot is only recorded if the balance changed, so the balance stays the same until the next
+    /// snapshot. With a resolution in milliseconds, only the latest snapshot of each interval is returned, which is
+    /// enough for charting.
+    pub async fn balance_history(
+        &self,
+        range: Range<u64>,
+        resolution: Option<u64>,
+    ) -> crate::Result<Vec<BalanceSnapshot>> {
+        let account_index = *self.read().await.index();
+        let balance_history = self
+            .storage_manager
+            .lock()
+            .await
+            .get_balance_history(account_index)
+            .await?;
+
+        let mut snapshots: Vec<BalanceSnapshot> = Vec::new();
+        for snapshot in balance_history
+            .into_iter()
+            .filter(|snapshot| range.contains(&snapshot.timestamp))
+        {
+            match (resolution, snapshots.last_mut()) {
+                (Some(resolution), Some(previous_snapshot))
+                    if resolution > 0
+                        && (previous_snapshot.timestamp - range.start) / resolution
+                            == (snapshot.timestamp - range.start) / resolution =>
+                {
+                    *previous_snapshot = snapshot;
+                }
+                _ => snapshots.push(snapshot),
+            }
+        }
+
+        Ok(snapshots)
+    }
+
+    // Record the balance after a completed sync
+    pub(crate) async fn store_balance_snapshot(&self, balance: &AccountBalance, timestamp: u128) -> crate::Result<()> {
+        let account_index = *self.read().await.index();
+        let balance_snapshot = BalanceSnapshot {
+            timestamp: timestamp as u64,
+            base_coin: balance.base_coin.total,
+            native_tokens: balance
+                .native_tokens
+                .iter()
+                .map(|native_token| (native_token.token_id, native_token.total))
+                .collect(),
+        };
+
+        self.storage_manager
+            .lock()
+            .await
+            .add_balance_snapshot(account_index, balance_snapshot)
+            .await
+    }
+}
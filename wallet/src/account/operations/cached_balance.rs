@@ -0,0 +1,49 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_client::secret::types::InputSigningData;
+
+use crate::account::Account;
+
+impl Account {
+    // Adjusts the available balance of the cached balance for inputs that got locked by a transaction, or unlocked
+    // because the transaction won't be sent. Confirmed transactions are reflected with the next sync, which replaces
+    // the cached balance.
+    pub(crate) fn update_cached_balance_for_inputs(&mut self, inputs: &[InputSigningData], locked: bool) {
+        let Some(cached_balance) = &mut self.cached_balance else {
+            return;
+        };
+        for input in inputs {
+            let amount = input.output.amount();
+            let base_coin = &mut cached_balance.base_coin;
+            base_coin.available = if locked {
+                base_coin.available.saturating_sub(amount)
+            } else {
+                // The available balance never exceeds the total
+                base_coin.available.saturating_add(amount).min(base_coin.total)
+            };
+
+            for native_token in input
+                .output
+                .native_tokens()
+                .into_iter()
+                .flat_map(|native_tokens| native_tokens.iter())
+            {
+                if let Some(native_token_balance) = cached_balance
+                    .native_tokens
+                    .iter_mut()
+                    .find(|native_token_balance| native_token_balance.token_id == *native_token.token_id())
+                {
+                    native_token_balance.available = if locked {
+                        native_token_balance.available.saturating_sub(native_token.amount())
+                    } else {
+                        native_token_balance
+                            .available
+                            .saturating_add(native_token.amount())
+                            .min(native_token_balance.total)
+                    };
+                }
+            }
+        }
+    }
+}
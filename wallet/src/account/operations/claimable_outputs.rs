@@ -0,0 +1,217 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_client::block::output::{Output, OutputId};
+use serde::{Deserialize, Serialize};
+
+use crate::account::{
+    handle::AccountHandle,
+    operations::{
+        helpers::time::can_output_be_unlocked_now,
+        output_claiming::{sdr_not_expired, OutputsToClaim},
+    },
+    types::{Transaction, TransactionDto},
+};
+
+// Outputs that are claimed with one transaction, leaves room for additional inputs and for the storage deposit return,
+// nft and remainder outputs
+const CLAIM_BATCH_SIZE: usize = 50;
+
+/// Filter for [`AccountHandle::claimable_outputs()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaimableOutputsFilter {
+    /// Which outputs are returned.
+    pub outputs_to_claim: OutputsToClaim,
+    /// Also return the outputs that are timelocked now, but can be claimed by the account when the timelock expires.
+    #[serde(default)]
+    pub include_timelocked: bool,
+}
+
+impl Default for ClaimableOutputsFilter {
+    fn default() -> Self {
+        Self {
+            outputs_to_claim: OutputsToClaim::All,
+            include_timelocked: false,
+        }
+    }
+}
+
+/// An output with additional unlock conditions that can be claimed by the account.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaimableOutput {
+    /// The id of the output.
+    pub output_id: OutputId,
+    /// The base coin amount of the output.
+    pub amount: u64,
+    /// The amount that has to be sent back when the output is claimed, because of a storage deposit return unlock
+    /// condition.
+    pub storage_deposit_return: u64,
+    /// Unix timestamp in seconds when the timelock of the output expires, `None` if it can be claimed now.
+    pub claimable_at: Option<u32>,
+    /// Unix timestamp in seconds after which the output can't be claimed anymore, because it expires.
+    pub expires_at: Option<u32>,
+}
+
+/// The result of claiming outputs in batches.
+#[derive(Debug, Clone, Default)]
+pub struct ClaimBatch {
+    /// The claiming transactions that were sent.
+    pub transactions: Vec<Transaction>,
+    /// The outputs of the transactions that failed, with the error.
+    pub failed: Vec<FailedClaim>,
+}
+
+/// Dto for the result of claiming outputs in batches.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaimBatchDto {
+    /// The claiming transactions that were sent.
+    pub transactions: Vec<TransactionDto>,
+    /// The outputs of the transactions that failed, with the error.
+    pub failed: Vec<FailedClaim>,
+}
+
+impl From<&ClaimBatch> for ClaimBatchDto {
+    fn from(value: &ClaimBatch) -> Self {
+        Self {
+            transactions: value.transactions.iter().map(TransactionDto::from).collect(),
+            failed: value.failed.clone(),
+        }
+    }
+}
+
+/// A claiming transaction that couldn't be sent, or that wasn't included or synced before the next batch, in which case
+/// its outputs can still get claimed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FailedClaim {
+    /// The outputs that should have been claimed with the transaction.
+    pub output_ids: Vec<OutputId>,
+    /// The error of the transaction.
+    pub error: String,
+}
+
+impl AccountHandle {
+    /// Returns the basic and nft outputs with expiration, storage deposit return or timelock unlock conditions that
+    /// the account can claim, with the amount that has to be returned and when they can be claimed or expire. With
+    /// [`ClaimableOutputsFilter::include_timelocked`] outputs that can only be claimed in the future are also returned,
+    /// so claiming can be scheduled, for example with
+    /// [`AccountManager::set_auto_claim()`](crate::account_manager::AccountManager::set_auto_claim).
+    pub async fn claimable_outputs(
+        &self,
+        filter: Option<ClaimableOutputsFilter>,
+    ) -> crate::Result<Vec<ClaimableOutput>> {
+        log::debug!("[OUTPUT_CLAIMING] claimable_outputs");
+        let filter = filter.unwrap_or_default();
+        let current_time = self.client.get_time_checked().await?;
+        let account = self.read().await;
+
+        let mut claimable_outputs = Vec::new();
+        for (output_id, output_data) in &account.unspent_outputs {
+            if account.locked_outputs.contains(output_id) || !is_output_to_claim(&output_data.output, filter) {
+                continue;
+            }
+            let unlock_conditions = match output_data.output.unlock_conditions() {
+                // Outputs with only an address unlock condition are already owned without restrictions
+                Some(unlock_conditions) if unlock_conditions.len() > 1 => unlock_conditions,
+                _ => continue,
+            };
+            let claimable_at = unlock_conditions
+                .timelock()
+                .map(|timelock| timelock.timestamp())
+                .filter(|timestamp| *timestamp > current_time);
+            if claimable_at.is_some() && !filter.include_timelocked {
+                continue;
+            }
+
+            // Check if the account can unlock the output when the timelock expired
+            let claim_time = claimable_at.unwrap_or(current_time);
+            if !can_output_be_unlocked_now(
+                &account.addresses_with_unspent_outputs,
+                &[],
+                output_data,
+                claim_time,
+                None,
+            )? {
+                continue;
+            }
+
+            claimable_outputs.push(ClaimableOutput {
+                output_id: *output_id,
+                amount: output_data.output.amount(),
+                storage_deposit_return: sdr_not_expired(&output_data.output, claim_time).map_or(0, |sdr| sdr.amount()),
+                claimable_at,
+                expires_at: unlock_conditions
+                    .expiration()
+                    .filter(|expiration| expiration.return_address_expired(claim_time).is_none())
+                    .map(|expiration| expiration.timestamp()),
+            });
+        }
+
+        Ok(claimable_outputs)
+    }
+
+    /// Claim outputs with as many transactions as needed, a transaction is only sent after the previous one was
+    /// included, so its remainder can be used for the storage deposit of the next claim. A failed transaction doesn't
+    /// stop the other ones, its outputs are reported in [`ClaimBatch::failed`].
+    pub async fn claim_outputs_in_batches(&self, output_ids_to_claim: Vec<OutputId>) -> crate::Result<ClaimBatch> {
+        log::debug!(
+            "[OUTPUT_CLAIMING] claim_outputs_in_batches {} outputs",
+            output_ids_to_claim.len()
+        );
+        let mut claim_batch = ClaimBatch::default();
+
+        let chunks: Vec<&[OutputId]> = output_ids_to_claim.chunks(CLAIM_BATCH_SIZE).collect();
+        let chunk_count = chunks.len();
+        for (chunk_index, chunk) in chunks.into_iter().enumerate() {
+            let result = match self.claim_outputs(chunk.to_vec()).await {
+                // Wait until the transaction is included and synced, so the next one can use its remainder
+                Ok(transaction) if chunk_index + 1 < chunk_count => {
+                    match self
+                        .retry_transaction_until_included(&transaction.transaction_id, None, None)
+                        .await
+                    {
+                        Ok(_) => self.sync(None).await.map(|_| transaction),
+                        Err(error) => Err(error),
+                    }
+                }
+                result => result,
+            };
+            match result {
+                Ok(transaction) => claim_batch.transactions.push(transaction),
+                Err(error) => {
+                    log::warn!("[OUTPUT_CLAIMING] claiming transaction failed: {error}");
+                    claim_batch.failed.push(FailedClaim {
+                        output_ids: chunk.to_vec(),
+                        error: error.to_string(),
+                    });
+                }
+            }
+        }
+
+        Ok(claim_batch)
+    }
+}
+
+// Check if the output is one of the outputs that should be claimed, like in
+// `AccountHandle::get_unlockable_outputs_with_additional_unlock_conditions()`
+fn is_output_to_claim(output: &Output, filter: ClaimableOutputsFilter) -> bool {
+    let (unlock_conditions, native_tokens) = match output {
+        Output::Basic(basic_output) => (basic_output.unlock_conditions(), basic_output.native_tokens()),
+        Output::Nft(nft_output) => (nft_output.unlock_conditions(), nft_output.native_tokens()),
+        // Other output types can't have expiration, storage deposit return or timelock unlock conditions
+        _ => return false,
+    };
+    match filter.outputs_to_claim {
+        OutputsToClaim::None => false,
+        // Only micro transaction if the storage deposit return isn't the full amount
+        OutputsToClaim::MicroTransactions => unlock_conditions
+            .storage_deposit_return()
+            .map_or(false, |sdr| sdr.amount() != output.amount()),
+        OutputsToClaim::NativeTokens => !native_tokens.is_empty(),
+        OutputsToClaim::Nfts => matches!(output, Output::Nft(_)),
+        OutputsToClaim::All => true,
+    }
+}
@@ -0,0 +1,93 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::HashSet, time::Duration};
+
+use iota_client::block::{
+    address::Address,
+    output::{Output, OutputId},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::account::{handle::AccountHandle, types::OutputData};
+
+/// An output the account controls now, that can only be unlocked by its sender after its expiration.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpiringOutput {
+    /// The id of the output.
+    pub output_id: OutputId,
+    /// The base coin amount of the output.
+    pub amount: u64,
+    /// Unix timestamp in seconds when the output expires.
+    pub expires_at: u32,
+    /// Seconds until the output expires.
+    pub time_to_expiry: u32,
+    /// Bech32 encoded address that can unlock the output after it expired.
+    pub return_address: String,
+}
+
+impl AccountHandle {
+    /// Returns the outputs that the account can unlock now, but that expire back to an address of another sender,
+    /// ordered by their expiration. With `within` only the outputs that expire in this period are returned. The
+    /// outputs need to be claimed before they expire, for example with [`AccountHandle::claim_outputs()`].
+    pub async fn expiring_outputs(&self, within: Option<Duration>) -> crate::Result<Vec<ExpiringOutput>> {
+        log::debug!("[expiring_outputs]");
+        let current_time = self.client.get_time_checked().await?;
+        let account = self.read().await;
+
+        let bech32_hrp = match account.public_addresses.first() {
+            Some(address) => address.address.bech32_hrp.clone(),
+            None => return Ok(Vec::new()),
+        };
+        let account_addresses: HashSet<Address> = account
+            .public_addresses
+            .iter()
+            .chain(account.internal_addresses.iter())
+            .map(|address| address.address.inner)
+            .collect();
+
+        let mut expiring_outputs: Vec<ExpiringOutput> = account
+            .unspent_outputs
+            .values()
+            .filter_map(|output_data| expiring_output(output_data, &account_addresses, current_time, &bech32_hrp))
+            .filter(|expiring_output| {
+                within.map_or(true, |within| {
+                    u64::from(expiring_output.time_to_expiry) <= within.as_secs()
+                })
+            })
+            .collect();
+        expiring_outputs.sort_by_key(|expiring_output| expiring_output.expires_at);
+
+        Ok(expiring_outputs)
+    }
+}
+
+// Returns the output if it's sent to an address of the account and expires to an address of someone else
+fn expiring_output(
+    output_data: &OutputData,
+    account_addresses: &HashSet<Address>,
+    current_time: u32,
+    bech32_hrp: &str,
+) -> Option<ExpiringOutput> {
+    let unlock_conditions = match &output_data.output {
+        Output::Basic(basic_output) => basic_output.unlock_conditions(),
+        Output::Nft(nft_output) => nft_output.unlock_conditions(),
+        _ => return None,
+    };
+    let expiration = unlock_conditions.expiration()?;
+    if current_time >= expiration.timestamp()
+        || !account_addresses.contains(unlock_conditions.address()?.address())
+        || account_addresses.contains(expiration.return_address())
+    {
+        return None;
+    }
+
+    Some(ExpiringOutput {
+        output_id: output_data.output_id,
+        amount: output_data.output.amount(),
+        expires_at: expiration.timestamp(),
+        time_to_expiry: expiration.timestamp() - current_time,
+        return_address: expiration.return_address().to_bech32(bech32_hrp),
+    })
+}
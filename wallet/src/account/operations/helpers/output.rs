@@ -0,0 +1,12 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_client::block::{address::Address, output::Output};
+
+// Get the address from the address unlock condition
+pub(crate) fn output_address(output: &Output) -> Option<Address> {
+    output
+        .unlock_conditions()
+        .and_then(|unlock_conditions| unlock_conditions.address())
+        .map(|address_unlock_condition| *address_unlock_condition.address())
+}
@@ -0,0 +1,262 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::HashSet, ops::Range};
+
+use iota_client::block::{
+    address::Address,
+    output::{Output, TokenId},
+    payload::transaction::{TransactionEssence, TransactionId},
+};
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    account::{
+        handle::AccountHandle,
+        operations::helpers::output::output_address,
+        types::{InclusionState, Transaction, TransactionDirection},
+    },
+    time::UtcDateTime,
+};
+
+/// The format of an exported transaction history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HistoryExportFormat {
+    /// Comma separated values with a header row
+    Csv,
+    /// A JSON array of [`HistoryExportRow`]s
+    Json,
+}
+
+/// A row of an exported transaction history, there is a row for the base coin and one for every native token
+/// transferred in a transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryExportRow {
+    /// Unix timestamp in milliseconds when the transaction was created
+    pub timestamp: u128,
+    /// The timestamp as UTC date and time in the RFC 3339 format
+    pub date: String,
+    /// The transaction id
+    pub transaction_id: TransactionId,
+    /// If the transaction was sent or received
+    pub direction: TransactionDirection,
+    /// The inclusion state of the transaction
+    pub inclusion_state: InclusionState,
+    /// Bech32 encoded address of the receiver for sent transactions and of the sender for incoming ones, if known
+    pub counterpart: Option<String>,
+    /// The native token of the row, `None` for the base coin
+    pub token_id: Option<TokenId>,
+    /// The amount sent to other addresses, or received by the account for incoming transactions
+    pub amount: String,
+    /// The storage deposit of the transferred outputs that needs to be returned to the sender, only set in the base
+    /// coin row. Transactions are feeless, so this is the only amount that isn't transferred for good.
+    pub storage_deposit: u64,
+    /// The local note of the transaction
+    pub note: Option<String>,
+    /// The local labels of the transaction
+    pub labels: Vec<String>,
+}
+
+const CSV_HEADER: &str =
+    "timestamp,date,transactionId,direction,inclusionState,counterpart,tokenId,amount,storageDeposit,note,labels";
+
+impl AccountHandle {
+    /// Exports the sent and incoming transactions in the time range, as unix timestamps in milliseconds, oldest first,
+    /// for accounting and tax tools. Conflicting and expired transactions are ignored. Received transactions without
+    /// a sender feature are attributed to the address of the first input, if the inputs are known.
+    pub async fn export_history(&self, format: HistoryExportFormat, period: Range<u64>) -> crate::Result<String> {
+        log::debug!("[export_history]");
+        let rows = self.history_export_rows(period).await?;
+
+        match format {
+            HistoryExportFormat::Csv => {
+                let mut csv = String::from(CSV_HEADER);
+                csv.push_str("\r\n");
+                for row in &rows {
+                    csv.push_str(&csv_row(row));
+                    csv.push_str("\r\n");
+                }
+                Ok(csv)
+            }
+            HistoryExportFormat::Json => Ok(serde_json::to_string(&rows)?),
+        }
+    }
+
+    async fn history_export_rows(&self, period: Range<u64>) -> crate::Result<Vec<HistoryExportRow>> {
+        let account = self.read().await;
+
+        let bech32_hrp = match account.public_addresses.first() {
+            Some(address) => address.address().bech32_hrp().to_string(),
+            None => return Ok(Vec::new()),
+        };
+        let account_addresses: HashSet<Address> = account
+            .public_addresses
+            .iter()
+            .chain(account.internal_addresses.iter())
+            .map(|address| *address.address().as_ref())
+            .collect();
+
+        let period = period.start as u128..period.end as u128;
+        let mut transactions: Vec<&Transaction> = account
+            .transactions()
+            .values()
+            .chain(account.incoming_transactions().values())
+            .filter(|transaction| {
+                period.contains(&transaction.timestamp)
+                    && !matches!(
+                        transaction.inclusion_state,
+                        InclusionState::Conflicting | InclusionState::Expired
+                    )
+            })
+            .collect();
+        transactions.sort_by(|a, b| {
+            a.timestamp
+                .cmp(&b.timestamp)
+                .then(a.transaction_id.cmp(&b.transaction_id))
+        });
+
+        let mut rows = Vec::new();
+        let mut token_supply = None;
+        for transaction in transactions {
+            let TransactionEssence::Regular(essence) = transaction.payload.essence();
+            // Sent transactions transfer the outputs to other addresses, incoming ones the outputs to the account
+            let transferred_outputs: Vec<&Output> = essence
+                .outputs()
+                .iter()
+                .filter(|output| {
+                    output_address(output).map_or(false, |address| {
+                        account_addresses.contains(&address) == transaction.incoming
+                    })
+                })
+                .collect();
+
+            let mut counterpart = if transaction.incoming {
+                transferred_outputs.iter().find_map(|output| {
+                    output
+                        .features()
+                        .and_then(|features| features.sender())
+                        .map(|sender| *sender.address())
+                })
+            } else {
+                transferred_outputs.first().and_then(|output| output_address(output))
+            };
+            if counterpart.is_none() && transaction.incoming {
+                if let Some(input) = transaction.inputs.first() {
+                    let token_supply = match token_supply {
+                        Some(token_supply) => token_supply,
+                        None => *token_supply.insert(self.client.get_token_supply().await?),
+                    };
+                    let input = Output::try_from_dto(&input.output, token_supply)?;
+                    counterpart = output_address(&input).filter(|address| !account_addresses.contains(address));
+                }
+            }
+
+            let mut token_amounts: Vec<(TokenId, U256)> = Vec::new();
+            for native_token in transferred_outputs
+                .iter()
+                .flat_map(|output| output.native_tokens().into_iter().flat_map(|tokens| tokens.iter()))
+            {
+                match token_amounts
+                    .iter_mut()
+                    .find(|(token_id, _)| token_id == native_token.token_id())
+                {
+                    Some((_, amount)) => *amount = amount.saturating_add(*native_token.amount()),
+                    None => token_amounts.push((*native_token.token_id(), *native_token.amount())),
+                }
+            }
+
+            let storage_deposit = transferred_outputs
+                .iter()
+                .filter_map(|output| {
+                    output
+                        .unlock_conditions()
+                        .and_then(|unlock_conditions| unlock_conditions.storage_deposit_return())
+                })
+                .map(|storage_deposit_return| storage_deposit_return.amount())
+                .sum();
+            let amount: u64 = transferred_outputs.iter().map(|output| output.amount()).sum();
+
+            let row = HistoryExportRow {
+                timestamp: transaction.timestamp,
+                date: utc_date_time(transaction.timestamp),
+                transaction_id: transaction.transaction_id,
+                direction: if transaction.incoming {
+                    TransactionDirection::Incoming
+                } else {
+                    TransactionDirection::Outgoing
+                },
+                inclusion_state: transaction.inclusion_state,
+                counterpart: counterpart.map(|address| address.to_bech32(&bech32_hrp)),
+                token_id: None,
+                amount: amount.to_string(),
+                storage_deposit,
+                note: transaction.note.clone(),
+                labels: transaction.labels.clone(),
+            };
+            let token_rows: Vec<HistoryExportRow> = token_amounts
+                .into_iter()
+                .map(|(token_id, token_amount)| HistoryExportRow {
+                    token_id: Some(token_id),
+                    amount: token_amount.to_string(),
+                    storage_deposit: 0,
+                    ..row.clone()
+                })
+                .collect();
+            rows.push(row);
+            rows.extend(token_rows);
+        }
+
+        Ok(rows)
+    }
+}
+
+fn csv_row(row: &HistoryExportRow) -> String {
+    [
+        row.timestamp.to_string(),
+        row.date.clone(),
+        row.transaction_id.to_string(),
+        format!("{:?}", row.direction),
+        format!("{:?}", row.inclusion_state),
+        row.counterpart.clone().unwrap_or_default(),
+        row.token_id.map(|token_id| token_id.to_string()).unwrap_or_default(),
+        row.amount.clone(),
+        row.storage_deposit.to_string(),
+        row.note.clone().unwrap_or_default(),
+        row.labels.join(";"),
+    ]
+    .iter()
+    .map(|field| csv_field(field))
+    .collect::<Vec<_>>()
+    .join(",")
+}
+
+// Quotes fields with separators, quotes or line breaks, and fields that spreadsheets would evaluate as formula
+fn csv_field(field: &str) -> String {
+    let field = if field.starts_with(['=', '+', '-', '@']) {
+        format!("'{field}")
+    } else {
+        field.to_string()
+    };
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field
+    }
+}
+
+// Formats a unix timestamp in milliseconds as UTC date and time, like `2023-03-14T09:26:53.589Z`
+fn utc_date_time(timestamp: u128) -> String {
+    let date_time = UtcDateTime::from_unix_seconds((timestamp / 1000) as u64);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        date_time.year,
+        date_time.month,
+        date_time.day,
+        date_time.hour,
+        date_time.minute,
+        date_time.second,
+        timestamp % 1000
+    )
+}
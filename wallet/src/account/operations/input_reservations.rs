@@ -0,0 +1,67 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+
+use instant::SystemTime;
+use iota_client::{block::output::OutputId, secret::types::InputSigningData};
+
+use crate::{
+    account::{handle::AccountHandle, Account},
+    storage::input_reservations::InputReservation,
+};
+
+impl AccountHandle {
+    /// Returns the inputs of the account that are reserved by in-progress transactions, of this account manager and
+    /// of other account managers that share the database. Reservations are released when the transaction failed or
+    /// isn't pending anymore, or after a timeout if their account manager stopped.
+    pub async fn input_reservations(&self) -> crate::Result<Vec<InputReservation>> {
+        let account_index = *self.read().await.index();
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .expect("time went backwards")
+            .as_millis() as u64;
+        Ok(self
+            .storage_manager
+            .lock()
+            .await
+            .get_input_reservations(account_index)
+            .await?
+            .into_iter()
+            .filter(|input_reservation| input_reservation.expires_at > now)
+            .collect())
+    }
+
+    // Reserves the locked outputs of the account, returns the outputs that are reserved by other account managers
+    pub(crate) async fn update_input_reservations(&self, account: &Account) -> crate::Result<HashSet<OutputId>> {
+        self.storage_manager
+            .lock()
+            .await
+            .update_input_reservations(account.index, &account.locked_outputs)
+            .await
+    }
+
+    // Reserves the selected inputs that were locked in the account. If another account manager reserved one of them
+    // in the meantime, they're unlocked again, so the transaction isn't sent.
+    pub(crate) async fn reserve_selected_inputs(
+        &self,
+        account: &mut Account,
+        inputs: &[InputSigningData],
+    ) -> crate::Result<()> {
+        let reserved_inputs = self.update_input_reservations(account).await?;
+        let conflicting_inputs: Vec<OutputId> = inputs
+            .iter()
+            .map(|input| *input.output_id())
+            .filter(|output_id| reserved_inputs.contains(output_id))
+            .collect();
+        if conflicting_inputs.is_empty() {
+            return Ok(());
+        }
+
+        for input in inputs {
+            account.locked_outputs.remove(input.output_id());
+        }
+        self.update_input_reservations(account).await?;
+        Err(crate::Error::InputsReserved(conflicting_inputs))
+    }
+}
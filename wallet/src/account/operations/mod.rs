@@ -3,10 +3,32 @@
 
 /// The module for the address generation
 pub(crate) mod address_generation;
+/// The module to emit alerts for failed operations
+#[cfg(feature = "events")]
+pub(crate) mod alerts;
 /// The module to get the accounts balance
 pub(crate) mod balance;
+/// The module for the cached balance
+pub(crate) mod cached_balance;
+/// The module for the balance history
+#[cfg(feature = "storage")]
+pub(crate) mod balance_history;
+/// The module to find and claim the outputs with additional unlock conditions in batches
+pub(crate) mod claimable_outputs;
+/// The module to find outputs that expire back to their sender
+pub(crate) mod expiring_outputs;
 /// Helper functions
 pub(crate) mod helpers;
+/// The module to export the transaction history for accounting
+pub(crate) mod history_export;
+/// The module for the inputs reserved by in-progress transactions
+#[cfg(feature = "storage")]
+pub(crate) mod input_reservations;
+/// The module to list the NFTs of the account by collection
+pub(crate) mod nft_collections;
+/// The module for the offline queue of signed transactions
+#[cfg(feature = "storage")]
+pub(crate) mod offline_queue;
 /// The module for claiming of outputs with
 /// [`UnlockCondition`](iota_client::block::output::UnlockCondition)s that aren't only
 /// [`AddressUnlockCondition`](iota_client::block::output::unlock_condition::AddressUnlockCondition)
@@ -20,7 +42,16 @@ pub(crate) mod output_finder;
 pub(crate) mod participation;
 /// The module for retrying blocks or transactions
 pub(crate) mod retry;
+/// The module for the account statistics
+pub(crate) mod statistics;
+/// The module to quarantine unwanted NFTs and native tokens
+pub(crate) mod spam_filter;
 /// The module for synchronization of an account
 pub(crate) mod syncing;
 /// The module for transactions
 pub(crate) mod transaction;
+/// The module to page through the transaction history
+#[cfg(feature = "storage")]
+pub(crate) mod transaction_history;
+/// The module to find timelocked outputs that can't be unlocked yet
+pub(crate) mod upcoming_unlocks;
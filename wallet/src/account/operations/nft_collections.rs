@@ -0,0 +1,134 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use iota_client::block::{
+    address::Address,
+    output::{NftId, NftOutput, Output, OutputId},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::account::{
+    handle::AccountHandle, operations::transaction::high_level::minting::nft_mint_builder::Irc27Metadata,
+};
+
+/// An NFT of the account, with the collection it belongs to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountNft {
+    /// The id of the NFT.
+    pub nft_id: NftId,
+    /// The id of the output that holds the NFT.
+    pub output_id: OutputId,
+    /// The collection NFT that issued the NFT. Taken from the immutable issuer feature, which can only be set to an
+    /// NFT address if that NFT was unlocked when the NFT was minted.
+    pub collection_id: Option<NftId>,
+    /// Bech32 encoded immutable issuer of the NFT.
+    pub issuer: Option<String>,
+    /// The immutable metadata of the NFT, if it follows IRC-27.
+    pub metadata: Option<Irc27Metadata>,
+    /// `false` if the metadata claims a collection name that doesn't match the name of the collection NFT that
+    /// issued the NFT, or if there is no such collection NFT.
+    pub verified: bool,
+}
+
+impl AccountHandle {
+    /// Returns the NFTs of the account with the collection they belong to, with `collection_id` only the NFTs that
+    /// were issued by this collection NFT. The collection name in the metadata of an NFT is checked against the
+    /// metadata of its issuer, the collection NFT is requested from the node if the account doesn't own it.
+    pub async fn nfts(&self, collection_id: Option<NftId>) -> crate::Result<Vec<AccountNft>> {
+        log::debug!("[nfts] {collection_id:?}");
+        let bech32_hrp = self.client.get_bech32_hrp().await?;
+        let nft_outputs: Vec<(OutputId, NftOutput)> = self
+            .read()
+            .await
+            .unspent_outputs
+            .values()
+            .filter_map(|output_data| match &output_data.output {
+                Output::Nft(nft_output) => Some((output_data.output_id, nft_output.clone())),
+                _ => None,
+            })
+            .collect();
+
+        let mut collections: HashMap<NftId, Option<Irc27Metadata>> = HashMap::new();
+        let mut nfts = Vec::new();
+        for (output_id, nft_output) in nft_outputs {
+            let issuer = nft_output.immutable_features().issuer().map(|issuer| *issuer.address());
+            let nft_collection_id = match issuer {
+                Some(Address::Nft(nft_address)) => Some(*nft_address.nft_id()),
+                _ => None,
+            };
+            if collection_id.is_some() && nft_collection_id != collection_id {
+                continue;
+            }
+
+            let metadata = irc_27_metadata(&nft_output);
+            let claimed_collection = metadata.as_ref().and_then(|metadata| metadata.collection_name.as_ref());
+            let verified = match (claimed_collection, nft_collection_id) {
+                (None, _) => true,
+                (Some(_), None) => false,
+                (Some(collection_name), Some(nft_collection_id)) => {
+                    if !collections.contains_key(&nft_collection_id) {
+                        let collection_metadata = self.collection_metadata(nft_collection_id).await?;
+                        collections.insert(nft_collection_id, collection_metadata);
+                    }
+                    collections[&nft_collection_id].as_ref().map_or(false, |collection| {
+                        &collection.name == collection_name
+                            || collection.collection_name.as_ref() == Some(collection_name)
+                    })
+                }
+            };
+
+            nfts.push(AccountNft {
+                nft_id: nft_output.nft_id_non_null(&output_id),
+                output_id,
+                collection_id: nft_collection_id,
+                issuer: issuer.map(|issuer| issuer.to_bech32(&bech32_hrp)),
+                metadata,
+                verified,
+            });
+        }
+
+        Ok(nfts)
+    }
+
+    // Get the IRC-27 metadata of a collection NFT from the unspent outputs of the account or from the node, `None` if
+    // the collection NFT was burned or doesn't have IRC-27 metadata
+    async fn collection_metadata(&self, collection_id: NftId) -> crate::Result<Option<Irc27Metadata>> {
+        let owned_collection = self
+            .read()
+            .await
+            .unspent_outputs
+            .values()
+            .find_map(|output_data| match &output_data.output {
+                Output::Nft(nft_output) if nft_output.nft_id_non_null(&output_data.output_id) == collection_id => {
+                    Some(nft_output.clone())
+                }
+                _ => None,
+            });
+        if let Some(nft_output) = owned_collection {
+            return Ok(irc_27_metadata(&nft_output));
+        }
+
+        let output_id = match self.client.nft_output_id(collection_id).await {
+            Ok(output_id) => output_id,
+            Err(iota_client::Error::NotFound(_)) => return Ok(None),
+            Err(e) => return Err(crate::Error::Client(e.into())),
+        };
+        let token_supply = self.client.get_token_supply().await?;
+        let output_response = self.client.get_output(&output_id).await?;
+        match Output::try_from_dto(&output_response.output, token_supply)? {
+            Output::Nft(nft_output) => Ok(irc_27_metadata(&nft_output)),
+            _ => Ok(None),
+        }
+    }
+}
+
+// The immutable metadata of the NFT, if it's valid IRC-27 metadata
+fn irc_27_metadata(nft_output: &NftOutput) -> Option<Irc27Metadata> {
+    let metadata = nft_output.immutable_features().metadata()?;
+    serde_json::from_slice::<Irc27Metadata>(metadata.data())
+        .ok()
+        .filter(|metadata| metadata.validate().is_ok())
+}
@@ -0,0 +1,262 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use instant::SystemTime;
+use iota_client::{
+    api::{verify_semantic, PreparedTransactionData, SignedTransactionData, SignedTransactionDataDto},
+    block::{
+        output::{Output, OutputId},
+        payload::transaction::TransactionId,
+        semantic::ConflictReason,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    account::{
+        handle::AccountHandle,
+        types::{Transaction, TransactionDto},
+        TransactionOptions,
+    },
+    storage::offline_queue::{QueuedOperation, QueuedTransaction},
+};
+
+/// The result of submitting the offline queue of an account.
+#[derive(Debug, Clone, Default)]
+pub struct QueueSubmission {
+    /// The transactions that were submitted and are pending now.
+    pub submitted: Vec<Transaction>,
+    /// The queued transactions that were removed, because one of their inputs was spent or pruned in the meantime.
+    pub conflicting: Vec<QueuedTransaction>,
+}
+
+/// Dto for the result of submitting the offline queue of an account.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueSubmissionDto {
+    /// The transactions that were submitted and are pending now.
+    pub submitted: Vec<TransactionDto>,
+    /// The queued transactions that were removed, because one of their inputs was spent or pruned in the meantime.
+    pub conflicting: Vec<QueuedTransaction>,
+}
+
+impl From<&QueueSubmission> for QueueSubmissionDto {
+    fn from(value: &QueueSubmission) -> Self {
+        Self {
+            submitted: value.submitted.iter().map(TransactionDto::from).collect(),
+            conflicting: value.conflicting.clone(),
+        }
+    }
+}
+
+impl AccountHandle {
+    /// Prepare and sign a transaction without submitting it. The signed transaction is stored in the offline queue of
+    /// the account and its inputs stay locked until it's submitted with
+    /// [`AccountHandle::submit_queued_transactions()`]. Only the cached protocol parameters of the client are used, so
+    /// it works without a connection to a node, if the account was synced before.
+    pub async fn queue_transaction(
+        &self,
+        outputs: Vec<Output>,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<QueuedTransaction> {
+        log::debug!("[OFFLINE_QUEUE] queue_transaction");
+        let prepared_transaction_data = self.prepare_transaction(outputs, options).await?;
+        self.queue_prepared_transaction(QueuedOperation::Send, prepared_transaction_data)
+            .await
+    }
+
+    /// Prepare and sign a transaction to claim outputs, like [`AccountHandle::claim_outputs()`], and store it in the
+    /// offline queue of the account instead of submitting it.
+    pub async fn queue_claim_outputs(&self, output_ids_to_claim: Vec<OutputId>) -> crate::Result<QueuedTransaction> {
+        log::debug!("[OFFLINE_QUEUE] queue_claim_outputs");
+        let basic_outputs = self.get_basic_outputs_for_additional_inputs().await?;
+        let prepared_transaction_data = self
+            .prepare_claim_outputs_internal(output_ids_to_claim, basic_outputs)
+            .await?;
+        self.queue_prepared_transaction(QueuedOperation::ClaimOutputs, prepared_transaction_data)
+            .await
+    }
+
+    /// Returns the transactions in the offline queue of the account, oldest first.
+    pub async fn queued_transactions(&self) -> crate::Result<Vec<QueuedTransaction>> {
+        let account_index = *self.read().await.index();
+        self.storage_manager.lock().await.get_offline_queue(account_index).await
+    }
+
+    /// Remove a transaction from the offline queue without submitting it and unlock its inputs.
+    pub async fn remove_queued_transaction(&self, transaction_id: &TransactionId) -> crate::Result<()> {
+        log::debug!("[OFFLINE_QUEUE] remove_queued_transaction {transaction_id}");
+        let account_index = *self.read().await.index();
+        let mut storage_manager = self.storage_manager.lock().await;
+        let mut queued_transactions = storage_manager.get_offline_queue(account_index).await?;
+        let position = queued_transactions
+            .iter()
+            .position(|queued_transaction| &queued_transaction.transaction_id == transaction_id)
+            .ok_or_else(|| crate::Error::QueuedTransactionNotFound(*transaction_id))?;
+        let queued_transaction = queued_transactions.remove(position);
+        storage_manager
+            .save_offline_queue(account_index, &queued_transactions)
+            .await?;
+        drop(storage_manager);
+
+        self.unlock_queued_inputs(&queued_transaction).await
+    }
+
+    /// Submit the transactions of the offline queue in the order they were queued. The inputs are checked against the
+    /// ledger before, transactions with inputs that were spent in the meantime are removed from the queue and their
+    /// remaining inputs are unlocked. If the node can't be reached, the not yet submitted transactions stay queued.
+    pub async fn submit_queued_transactions(&self) -> crate::Result<QueueSubmission> {
+        log::debug!("[OFFLINE_QUEUE] submit_queued_transactions");
+        let account_index = *self.read().await.index();
+        let mut queued_transactions = self
+            .storage_manager
+            .lock()
+            .await
+            .get_offline_queue(account_index)
+            .await?;
+        let mut queue_submission = QueueSubmission::default();
+        if queued_transactions.is_empty() {
+            return Ok(queue_submission);
+        }
+
+        let protocol_parameters = self.client.get_protocol_parameters().await?;
+        while let Some(queued_transaction) = queued_transactions.first().cloned() {
+            let signed_transaction_data =
+                SignedTransactionData::try_from_dto(&queued_transaction.signed_transaction_data, &protocol_parameters)?;
+
+            let input_ids = signed_transaction_data
+                .inputs_data
+                .iter()
+                .map(|input| *input.output_id())
+                .collect::<Vec<OutputId>>();
+            let output_metadata_responses = self.client.try_get_outputs_metadata(input_ids.clone()).await?;
+            let inputs_unspent = output_metadata_responses.len() == input_ids.len()
+                && output_metadata_responses
+                    .iter()
+                    .all(|output_metadata_response| !output_metadata_response.is_spent);
+
+            if inputs_unspent {
+                // Conflicts that only show up with the node time are still caught here and unlock the inputs
+                let result = self.submit_transaction(signed_transaction_data).await;
+                match result {
+                    Ok(transaction) => queue_submission.submitted.push(transaction),
+                    Err(crate::Error::Client(error))
+                        if matches!(*error, iota_client::Error::TransactionSemantic(_)) =>
+                    {
+                        log::warn!(
+                            "[OFFLINE_QUEUE] queued transaction {} conflicts: {error}",
+                            queued_transaction.transaction_id
+                        );
+                        queue_submission.conflicting.push(queued_transaction);
+                    }
+                    Err(error) => return Err(error),
+                }
+            } else {
+                log::warn!(
+                    "[OFFLINE_QUEUE] inputs of queued transaction {} were spent in the meantime",
+                    queued_transaction.transaction_id
+                );
+                self.unlock_queued_inputs(&queued_transaction).await?;
+                queue_submission.conflicting.push(queued_transaction);
+            }
+            queued_transactions.remove(0);
+            self.save_offline_queue(account_index, &queued_transactions).await?;
+        }
+
+        Ok(queue_submission)
+    }
+
+    // Sign the prepared transaction, validate it with the local time and store it in the offline queue
+    async fn queue_prepared_transaction(
+        &self,
+        operation: QueuedOperation,
+        prepared_transaction_data: PreparedTransactionData,
+    ) -> crate::Result<QueuedTransaction> {
+        let signed_transaction_data = match self
+            .sign_and_verify_queued_transaction(&prepared_transaction_data)
+            .await
+        {
+            Ok(signed_transaction_data) => signed_transaction_data,
+            Err(err) => {
+                // unlock outputs so they are available for a new transaction
+                self.unlock_inputs(prepared_transaction_data.inputs_data).await?;
+                return Err(err);
+            }
+        };
+
+        let queued_transaction = QueuedTransaction {
+            transaction_id: signed_transaction_data.transaction_payload.id(),
+            operation,
+            timestamp: SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .expect("time went backwards")
+                .as_millis() as u64,
+            signed_transaction_data: SignedTransactionDataDto::from(&signed_transaction_data),
+        };
+
+        let account = self.read().await;
+        let account_index = *account.index();
+        let mut storage_manager = self.storage_manager.lock().await;
+        let mut queued_transactions = storage_manager.get_offline_queue(account_index).await?;
+        queued_transactions.push(queued_transaction.clone());
+        storage_manager
+            .save_offline_queue(account_index, &queued_transactions)
+            .await?;
+        drop(storage_manager);
+        // Store the locked inputs with the account
+        self.save(Some(&account)).await?;
+
+        log::debug!(
+            "[OFFLINE_QUEUE] queued transaction {}",
+            queued_transaction.transaction_id
+        );
+        Ok(queued_transaction)
+    }
+
+    async fn sign_and_verify_queued_transaction(
+        &self,
+        prepared_transaction_data: &PreparedTransactionData,
+    ) -> crate::Result<SignedTransactionData> {
+        let signed_transaction_data = self.sign_transaction(prepared_transaction_data).await?;
+
+        let local_time = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .expect("time went backwards")
+            .as_secs() as u32;
+        let conflict = verify_semantic(
+            &signed_transaction_data.inputs_data,
+            &signed_transaction_data.transaction_payload,
+            local_time,
+        )?;
+        if conflict != ConflictReason::None {
+            return Err(iota_client::Error::TransactionSemantic(conflict).into());
+        }
+        Ok(signed_transaction_data)
+    }
+
+    async fn unlock_queued_inputs(&self, queued_transaction: &QueuedTransaction) -> crate::Result<()> {
+        let input_ids = queued_transaction
+            .signed_transaction_data
+            .inputs_data
+            .iter()
+            .map(|input| Ok(input.output_metadata.output_id()?))
+            .collect::<crate::Result<Vec<OutputId>>>()?;
+        let mut account = self.write().await;
+        for input_id in &input_ids {
+            account.locked_outputs.remove(input_id);
+        }
+        self.save(Some(&account)).await
+    }
+
+    async fn save_offline_queue(
+        &self,
+        account_index: u32,
+        queued_transactions: &[QueuedTransaction],
+    ) -> crate::Result<()> {
+        self.storage_manager
+            .lock()
+            .await
+            .save_offline_queue(account_index, queued_transactions)
+            .await
+    }
+}
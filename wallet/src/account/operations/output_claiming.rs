@@ -4,7 +4,7 @@
 use std::collections::{HashMap, HashSet};
 
 use iota_client::{
-    api::input_selection::minimum_storage_deposit_basic_output,
+    api::{input_selection::minimum_storage_deposit_basic_output, PreparedTransactionData},
     block::{
         address::Address,
         output::{
@@ -16,8 +16,10 @@ use iota_client::{
 use serde::{Deserialize, Serialize};
 
 use crate::account::{
-    handle::AccountHandle, operations::helpers::time::can_output_be_unlocked_now, types::Transaction, OutputData,
-    TransactionOptions,
+    handle::AccountHandle,
+    operations::{helpers::time::can_output_be_unlocked_now, spam_filter::account_addresses},
+    types::Transaction,
+    OutputData, TransactionOptions,
 };
 
 /// Enum to specify which outputs should be claimed
@@ -46,11 +48,15 @@ impl AccountHandle {
 
         let local_time = self.client.get_time_checked().await?;
 
+        let account_addresses = account_addresses(&account);
+
         // Get outputs for the claim
         let mut output_ids_to_claim: HashSet<OutputId> = HashSet::new();
         for (output_id, output_data) in &account.unspent_outputs {
-            // Don't use outputs that are locked for other transactions
-            if !account.locked_outputs.contains(output_id) {
+            // Don't use outputs that are locked for other transactions, or quarantined until they're reviewed
+            if !account.locked_outputs.contains(output_id)
+                && account.spam_filter.check(output_data, &account_addresses).is_none()
+            {
                 if let Some(output) = account.outputs.get(output_id) {
                     match &output.output {
                         Output::Basic(basic_output) => {
@@ -190,10 +196,32 @@ impl AccountHandle {
     pub(crate) async fn claim_outputs_internal(
         &self,
         output_ids_to_claim: Vec<OutputId>,
-        mut possible_additional_inputs: Vec<OutputData>,
+        possible_additional_inputs: Vec<OutputData>,
     ) -> crate::Result<Transaction> {
         log::debug!("[OUTPUT_CLAIMING] claim_outputs_internal");
 
+        let prepared_transaction_data = self
+            .prepare_claim_outputs_internal(output_ids_to_claim, possible_additional_inputs)
+            .await?;
+        let claim_tx = self.sign_and_submit_transaction(prepared_transaction_data).await?;
+
+        log::debug!(
+            "[OUTPUT_CLAIMING] Claiming transaction created: block_id: {:?} tx_id: {:?}",
+            claim_tx.block_id,
+            claim_tx.transaction_id
+        );
+        Ok(claim_tx)
+    }
+
+    /// Prepare the transaction to claim basic outputs that have additional unlock conditions to their
+    /// [AddressUnlockCondition], the inputs are locked until the transaction is submitted.
+    pub(crate) async fn prepare_claim_outputs_internal(
+        &self,
+        output_ids_to_claim: Vec<OutputId>,
+        mut possible_additional_inputs: Vec<OutputData>,
+    ) -> crate::Result<PreparedTransactionData> {
+        log::debug!("[OUTPUT_CLAIMING] prepare_claim_outputs_internal");
+
         let current_time = self.client.get_time_checked().await?;
         let rent_structure = self.client.get_rent_structure().await?;
         let token_supply = self.client.get_token_supply().await?;
@@ -374,29 +402,21 @@ impl AccountHandle {
             })?;
         }
 
-        let claim_tx = self
-            .finish_transaction(
-                outputs_to_send,
-                Some(TransactionOptions {
-                    custom_inputs: Some(
-                        outputs_to_claim
-                            .iter()
-                            .map(|o| o.output_id)
-                            // add additional inputs
-                            .chain(additional_inputs)
-                            .collect::<Vec<OutputId>>(),
-                    ),
-                    ..Default::default()
-                }),
-            )
-            .await?;
-
-        log::debug!(
-            "[OUTPUT_CLAIMING] Claiming transaction created: block_id: {:?} tx_id: {:?}",
-            claim_tx.block_id,
-            claim_tx.transaction_id
-        );
-        Ok(claim_tx)
+        self.prepare_transaction(
+            outputs_to_send,
+            Some(TransactionOptions {
+                custom_inputs: Some(
+                    outputs_to_claim
+                        .iter()
+                        .map(|o| o.output_id)
+                        // add additional inputs
+                        .chain(additional_inputs)
+                        .collect::<Vec<OutputId>>(),
+                ),
+                ..Default::default()
+            }),
+        )
+        .await
     }
 }
 
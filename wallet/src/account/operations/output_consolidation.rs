@@ -10,6 +10,7 @@ use iota_client::block::{
 };
 #[cfg(feature = "ledger_nano")]
 use iota_client::secret::SecretManager;
+use serde::{Deserialize, Serialize};
 
 // Constants for the calculation of the amount of inputs we can use with a ledger nano
 #[cfg(feature = "ledger_nano")]
@@ -22,6 +23,8 @@ const MIN_OUTPUT_SIZE_IN_ESSENCE: usize = 46;
 
 #[cfg(feature = "ledger_nano")]
 use crate::account::constants::DEFAULT_LEDGER_OUTPUT_CONSOLIDATION_THRESHOLD;
+#[cfg(feature = "events")]
+use crate::events::types::{ConsolidationEvent, WalletEvent};
 use crate::{
     account::{
         constants::DEFAULT_OUTPUT_CONSOLIDATION_THRESHOLD,
@@ -33,6 +36,19 @@ use crate::{
     Result,
 };
 
+/// Policy for the automatic consolidation of the outputs of an account by the background syncing.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsolidationPolicy {
+    /// Outputs are consolidated once at least this many outputs of the account can be consolidated.
+    pub output_threshold: usize,
+    /// Maximum amount of inputs of a consolidation transaction, the limit of the protocol or the Ledger Nano is used if
+    /// it's lower or not set.
+    pub max_inputs: Option<u16>,
+    /// Minimum time in milliseconds between two consolidations of the account.
+    pub interval_in_milliseconds: u64,
+}
+
 impl AccountHandle {
     fn should_consolidate_output(
         &self,
@@ -70,6 +86,23 @@ impl AccountHandle {
         &self,
         force: bool,
         output_consolidation_threshold: Option<usize>,
+    ) -> Result<Transaction> {
+        self.consolidate_outputs_internal(force, output_consolidation_threshold, None)
+            .await
+    }
+
+    /// Consolidate the outputs like [`AccountHandle::consolidate_outputs()`] with the threshold and the maximum amount
+    /// of inputs of the policy.
+    pub async fn consolidate_outputs_with_policy(&self, policy: &ConsolidationPolicy) -> Result<Transaction> {
+        self.consolidate_outputs_internal(false, Some(policy.output_threshold), policy.max_inputs)
+            .await
+    }
+
+    async fn consolidate_outputs_internal(
+        &self,
+        force: bool,
+        output_consolidation_threshold: Option<usize>,
+        max_inputs: Option<u16>,
     ) -> Result<Transaction> {
         log::debug!("[OUTPUT_CONSOLIDATION] consolidating outputs if needed");
         #[cfg(feature = "participation")]
@@ -121,10 +154,17 @@ impl AccountHandle {
             });
         }
 
-        let max_inputs = match &*self.secret_manager.read().await {
+        let max_inputs_of_signer = match &*self.secret_manager.read().await {
             #[cfg(feature = "ledger_nano")]
             SecretManager::LedgerNano(ledger) => {
-                let ledger_nano_status = ledger.get_ledger_nano_status().await;
+                let ledger_nano_status = self
+                    .ledger_nano_queue
+                    .get_status(
+                        ledger,
+                        #[cfg(feature = "events")]
+                        Some((&*self.event_emitter, self.read().await.index)),
+                    )
+                    .await?;
                 // With blind signing we are only limited by the protocol
                 if ledger_nano_status.blind_signing_enabled() {
                     INPUT_COUNT_MAX
@@ -143,6 +183,7 @@ impl AccountHandle {
             }
             _ => INPUT_COUNT_MAX,
         };
+        let max_inputs = max_inputs.map_or(max_inputs_of_signer, |max_inputs| max_inputs.min(max_inputs_of_signer));
 
         let mut total_amount = 0;
         let mut custom_inputs = Vec::with_capacity(max_inputs.into());
@@ -187,6 +228,16 @@ impl AccountHandle {
             consolidation_tx.transaction_id
         );
 
+        #[cfg(feature = "events")]
+        self.event_emitter.lock().await.emit(
+            self.read().await.index,
+            WalletEvent::Consolidation(ConsolidationEvent {
+                transaction_id: consolidation_tx.transaction_id,
+                consolidated_outputs: consolidation_tx.inputs.len(),
+                amount: total_amount,
+            }),
+        );
+
         Ok(consolidation_tx)
     }
 }
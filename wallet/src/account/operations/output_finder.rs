@@ -3,10 +3,16 @@
 
 use std::cmp;
 
-use crate::account::{
-    handle::AccountHandle,
-    operations::{address_generation::AddressGenerationOptions, syncing::SyncOptions},
-    types::AddressWithUnspentOutputs,
+use crate::{
+    account::{
+        handle::AccountHandle,
+        operations::{
+            address_generation::AddressGenerationOptions,
+            syncing::{cancellation::CancellationToken, SyncOptions},
+        },
+        types::AddressWithUnspentOutputs,
+    },
+    account_manager::AddressGapReport,
 };
 
 impl AccountHandle {
@@ -14,11 +20,14 @@ impl AccountHandle {
     /// `address_gap_limit`: The number of addresses to search for, after the last address with unspent outputs
     /// Addresses that got crated during this operation and have a higher key_index than the latest one with outputs,
     /// will be removed again, to keep the account size smaller
+    /// Returns the amount of unspent outputs and up to which indexes addresses were searched
     pub(crate) async fn search_addresses_with_outputs(
         &self,
         mut address_gap_limit: u32,
         mut sync_options: Option<SyncOptions>,
-    ) -> crate::Result<usize> {
+        cancellation: &CancellationToken,
+    ) -> crate::Result<AddressGapReport> {
+        let initial_address_gap_limit = address_gap_limit;
         log::debug!("[search_addresses_with_outputs]");
 
         // store the current index, so we can remove new addresses with higher indexes later again, if they don't have
@@ -152,7 +161,7 @@ impl AccountHandle {
                 }),
             };
 
-            self.sync(sync_options).await?;
+            self.sync_with_cancellation(sync_options, cancellation.clone()).await?;
 
             let output_count = self.read().await.unspent_outputs.len();
 
@@ -245,9 +254,39 @@ impl AccountHandle {
             }
         }
 
+        let (highest_searched_address_index, highest_searched_address_index_internal) = {
+            let account = self.read().await;
+            (
+                account.public_addresses.iter().map(|a| a.key_index).max().unwrap_or(0),
+                account.internal_addresses.iter().map(|a| a.key_index).max(),
+            )
+        };
+
         self.clean_account_after_recovery(highest_public_address_index, highest_internal_address_index)
             .await;
 
+        let account = self.read().await;
+        let report = AddressGapReport {
+            account_index: *account.index(),
+            address_gap_limit: initial_address_gap_limit,
+            highest_searched_address_index,
+            highest_searched_address_index_internal,
+            highest_address_index_with_outputs: account
+                .addresses_with_unspent_outputs()
+                .iter()
+                .filter(|a| !a.internal)
+                .map(|a| a.key_index)
+                .max(),
+            highest_address_index_with_outputs_internal: account
+                .addresses_with_unspent_outputs()
+                .iter()
+                .filter(|a| a.internal)
+                .map(|a| a.key_index)
+                .max(),
+            outputs_count: latest_outputs_count,
+        };
+        drop(account);
+
         #[cfg(feature = "storage")]
         {
             log::debug!(
@@ -257,7 +296,7 @@ impl AccountHandle {
             self.save(None).await?;
         }
 
-        Ok(latest_outputs_count)
+        Ok(report)
     }
 
     /// During search_addresses_with_outputs we created new addresses that don't have funds, so we remove them again.
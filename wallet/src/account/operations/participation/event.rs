@@ -29,17 +29,18 @@ impl AccountHandle {
             .with_node_auth(options.node.url.as_str(), options.node.auth.clone())?
             .finish()?;
 
+        let event_type = options.event_type.unwrap_or(ParticipationEventType::Voting);
         let events_to_register = match &options.events_to_register {
             Some(events_to_register_) => {
                 if events_to_register_.is_empty() {
-                    self.get_participation_event_ids(&options.node, Some(ParticipationEventType::Voting))
+                    self.get_participation_event_ids(&options.node, Some(event_type))
                         .await?
                 } else {
                     events_to_register_.clone()
                 }
             }
             None => {
-                self.get_participation_event_ids(&options.node, Some(ParticipationEventType::Voting))
+                self.get_participation_event_ids(&options.node, Some(event_type))
                     .await?
             }
         };
@@ -9,9 +9,16 @@
 // If the user has designated funds to vote with, the resulting output MUST NOT be used for input selection.
 
 pub(crate) mod event;
+pub(crate) mod staking;
+pub(crate) mod tracking;
 pub(crate) mod voting;
 pub(crate) mod voting_power;
 
+pub use self::{
+    staking::{StakingRewardPeriod, StakingRewards},
+    tracking::{ActiveParticipationEvent, EventParticipation, ParticipationState},
+};
+
 use std::collections::{hash_map::Entry, HashMap, HashSet};
 
 use iota_client::{
@@ -0,0 +1,194 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use iota_client::{
+    api_types::plugins::participation::{
+        responses::TrackedParticipation,
+        types::{ParticipationEventId, ParticipationEventPayload, StakingEventPayload},
+    },
+    block::output::OutputId,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::account::{
+    handle::AccountHandle,
+    operations::participation::{AccountParticipationOverview, ParticipationEventWithNodes},
+    types::{StakingRewardsBalance, Transaction},
+};
+
+/// The staked amount and the earned rewards of the account for a staking event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StakingRewards {
+    /// The id of the staking event.
+    pub event_id: ParticipationEventId,
+    /// The name of the staking event.
+    pub name: String,
+    /// The symbol of the rewards.
+    pub symbol: String,
+    /// The amount that currently is staked.
+    pub staked: u64,
+    /// The rewards earned over all periods.
+    pub rewards: u64,
+    /// If the rewards reach the required minimum of the event, rewards below it aren't paid out.
+    pub minimum_reached: bool,
+    /// The rewards of every staked output, ordered by the milestone index the staking started.
+    pub history: Vec<StakingRewardPeriod>,
+}
+
+/// The rewards earned by one staked output in a range of milestones.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StakingRewardPeriod {
+    /// The id of the staked output.
+    pub output_id: OutputId,
+    /// The staked amount.
+    pub amount: u64,
+    /// The milestone index the rewards are counted from.
+    pub start_milestone_index: u32,
+    /// The milestone index the rewards are counted to, the latest milestone index if the output is still staked.
+    pub end_milestone_index: u32,
+    /// The rewards earned in this range.
+    pub rewards: u64,
+}
+
+impl AccountHandle {
+    /// Stakes the voting power of the account in a staking event, previous participations in other events are kept.
+    /// The voting power needs to be increased with [`AccountHandle::increase_voting_power()`] before.
+    pub async fn stake(&self, event_id: ParticipationEventId) -> crate::Result<Transaction> {
+        let event_data = match self.get_participation_event(event_id).await? {
+            Some(event_with_nodes) => event_with_nodes.data,
+            None => self.get_client_for_event(&event_id).await?.event(&event_id).await?,
+        };
+        if !matches!(event_data.payload(), ParticipationEventPayload::StakingEventPayload(_)) {
+            return Err(crate::Error::Voting(format!("event {event_id} is not a staking event")));
+        }
+
+        // Staking events have no questions, so participating without answers stakes
+        self.vote(Some(event_id), Some(Vec::new())).await
+    }
+
+    /// Calculates the staked amounts and the rewards for the registered staking events, with the rewards earned by
+    /// every staked output. Stored with the participation state, so the rewards are included in the balance.
+    pub async fn staking_rewards(&self) -> crate::Result<Vec<StakingRewards>> {
+        log::debug!("[staking_rewards]");
+        Ok(self.update_participation_state().await?.staking_rewards)
+    }
+
+    // The staking rewards of the stored participation state, without requesting them again
+    pub(crate) async fn staking_rewards_balance(&self) -> crate::Result<Vec<StakingRewardsBalance>> {
+        Ok(self
+            .get_participation_state()
+            .await?
+            .map(|state| {
+                state
+                    .staking_rewards
+                    .into_iter()
+                    .map(|staking_rewards| StakingRewardsBalance {
+                        event_id: staking_rewards.event_id,
+                        symbol: staking_rewards.symbol,
+                        staked: staking_rewards.staked,
+                        rewards: staking_rewards.rewards,
+                        minimum_reached: staking_rewards.minimum_reached,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+}
+
+// Calculates the rewards of the staking events from the tracked participations
+pub(crate) fn staking_rewards_from_overview(
+    events: &HashMap<ParticipationEventId, ParticipationEventWithNodes>,
+    overview: &AccountParticipationOverview,
+    milestone_index: u32,
+) -> Vec<StakingRewards> {
+    let mut staking_rewards = Vec::new();
+    for (event_id, tracked_participations) in &overview.participations {
+        let Some(event_with_nodes) = events.get(event_id) else {
+            continue;
+        };
+        let ParticipationEventPayload::StakingEventPayload(payload) = event_with_nodes.data.payload() else {
+            continue;
+        };
+
+        let mut history = tracked_participations
+            .iter()
+            .map(|(output_id, participation)| {
+                reward_period(
+                    *output_id,
+                    participation,
+                    payload,
+                    *event_with_nodes.data.milestone_index_start(),
+                    (*event_with_nodes.data.milestone_index_end()).min(milestone_index),
+                )
+            })
+            .collect::<Vec<_>>();
+        history.sort_by_key(|period| period.start_milestone_index);
+
+        let rewards = history
+            .iter()
+            .fold(0u64, |rewards, period| rewards.saturating_add(period.rewards));
+        staking_rewards.push(StakingRewards {
+            event_id: *event_id,
+            name: event_with_nodes.data.name().clone(),
+            symbol: payload.symbol().clone(),
+            staked: tracked_participations
+                .values()
+                .filter(|participation| participation.end_milestone_index == 0)
+                .map(|participation| participation.amount)
+                .sum(),
+            rewards,
+            minimum_reached: rewards >= *payload.required_minimum_rewards(),
+            history,
+        });
+    }
+    staking_rewards.sort_by(|a, b| a.name.cmp(&b.name));
+
+    staking_rewards
+}
+
+// The rewards of a staked output between the start of the event or the staking and the end of the event, the staking
+// or `milestone_index_end`
+fn reward_period(
+    output_id: OutputId,
+    participation: &TrackedParticipation,
+    payload: &StakingEventPayload,
+    milestone_index_start: u32,
+    milestone_index_end: u32,
+) -> StakingRewardPeriod {
+    let start_milestone_index = participation.start_milestone_index.max(milestone_index_start);
+    let end_milestone_index = match participation.end_milestone_index {
+        // Still staked
+        0 => milestone_index_end,
+        end_milestone_index => end_milestone_index.min(milestone_index_end),
+    }
+    .max(start_milestone_index);
+
+    StakingRewardPeriod {
+        output_id,
+        amount: participation.amount,
+        start_milestone_index,
+        end_milestone_index,
+        rewards: rewards(
+            participation.amount,
+            end_milestone_index - start_milestone_index,
+            payload,
+        ),
+    }
+}
+
+// Every milestone earns `amount * numerator / denominator`
+fn rewards(amount: u64, milestones: u32, payload: &StakingEventPayload) -> u64 {
+    if *payload.denominator() == 0 {
+        return 0;
+    }
+    u128::from(amount)
+        .checked_mul(u128::from(milestones))
+        .and_then(|rewards| rewards.checked_mul(u128::from(*payload.numerator())))
+        .map_or(u64::MAX, |rewards| {
+            u64::try_from(rewards / u128::from(*payload.denominator())).unwrap_or(u64::MAX)
+        })
+}
@@ -0,0 +1,180 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::HashMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use iota_client::api_types::plugins::participation::{
+    responses::TrackedParticipation,
+    types::{ParticipationEventData, ParticipationEventId, ParticipationEventStatus, Participations},
+};
+use serde::{Deserialize, Serialize};
+
+use super::staking::{staking_rewards_from_overview, StakingRewards};
+use crate::account::handle::AccountHandle;
+
+/// A registered participation event that is commencing or holding, so it can be participated in.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActiveParticipationEvent {
+    /// The event id.
+    pub id: ParticipationEventId,
+    /// Information about a voting or staking event.
+    pub data: ParticipationEventData,
+    /// The latest status of the event.
+    pub status: ParticipationEventStatus,
+}
+
+/// The participation of an account, stored with every update so it's available across syncs.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParticipationState {
+    /// Latest milestone index when the state was updated.
+    pub milestone_index: u32,
+    /// Unix timestamp in milliseconds when the state was updated.
+    pub updated_at: u128,
+    /// The amount of the voting output.
+    pub voting_power: u64,
+    /// The participation for every event the account participated in.
+    pub events: HashMap<ParticipationEventId, EventParticipation>,
+    /// The staked amounts and rewards for the registered staking events.
+    #[serde(default)]
+    pub staking_rewards: Vec<StakingRewards>,
+}
+
+/// The participation of an account in a single event.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventParticipation {
+    /// The name of the event, if it's registered.
+    pub name: Option<String>,
+    /// The answers the voting output currently participates with, `None` if it doesn't participate in the event.
+    pub answers: Option<Vec<u8>>,
+    /// The amount that currently participates in the event.
+    pub amount: u64,
+    /// The participating amount summed up over every milestone of the event it participated in, the base for the
+    /// votes and rewards of the event.
+    pub accumulated: u64,
+}
+
+impl AccountHandle {
+    /// Returns the registered participation events that are commencing or holding, with their latest status.
+    pub async fn get_active_participation_events(&self) -> crate::Result<Vec<ActiveParticipationEvent>> {
+        log::debug!("[get_active_participation_events]");
+        let mut active_events = Vec::new();
+        for (id, event_with_nodes) in self.get_participation_events().await? {
+            let status = self.get_participation_event_status(&id).await?;
+            if status.status() == "commencing" || status.status() == "holding" {
+                active_events.push(ActiveParticipationEvent {
+                    id,
+                    data: event_with_nodes.data,
+                    status,
+                });
+            }
+        }
+        active_events.sort_by_key(|event| *event.data.milestone_index_start());
+
+        Ok(active_events)
+    }
+
+    /// Returns the participation state stored with the last update, `None` if it was never updated.
+    pub async fn get_participation_state(&self) -> crate::Result<Option<ParticipationState>> {
+        let account_index = self.read().await.index;
+        self.storage_manager
+            .lock()
+            .await
+            .get_participation_state(account_index)
+            .await
+    }
+
+    /// Calculates the voting power and the participation in the registered events and stores it, so it's available
+    /// with [`AccountHandle::get_participation_state()`]. Also done when syncing with `sync_participation`, the staking
+    /// rewards of the stored state are included in the balance.
+    pub async fn update_participation_state(&self) -> crate::Result<ParticipationState> {
+        log::debug!("[update_participation_state]");
+        let milestone_index = self.client().get_info().await?.node_info.status.latest_milestone.index;
+        let events = self.get_participation_events().await?;
+        let overview = self.get_participation_overview(None).await?;
+        let voting_output = self.get_voting_output().await?;
+
+        let current_participations = voting_output
+            .as_ref()
+            .and_then(|output_data| output_data.output.features().and_then(|f| f.metadata()))
+            .and_then(|metadata| Participations::from_bytes(&mut metadata.data()).ok())
+            .map(|participations| participations.participations)
+            .unwrap_or_default();
+
+        let staking_rewards = staking_rewards_from_overview(&events, &overview, milestone_index);
+        let mut state_events = HashMap::new();
+        for (event_id, tracked_participations) in overview.participations {
+            let event_data = events.get(&event_id).map(|event_with_nodes| &event_with_nodes.data);
+            let mut event_participation = EventParticipation {
+                name: event_data.map(|data| data.name().clone()),
+                ..Default::default()
+            };
+            for tracked_participation in tracked_participations.values() {
+                if tracked_participation.end_milestone_index == 0 {
+                    event_participation.amount += tracked_participation.amount;
+                }
+                if let Some(event_data) = event_data {
+                    let accumulated = accumulated_participation(tracked_participation, event_data, milestone_index);
+                    event_participation.accumulated = event_participation.accumulated.saturating_add(accumulated);
+                }
+            }
+            state_events.insert(event_id, event_participation);
+        }
+        for participation in current_participations {
+            state_events
+                .entry(participation.event_id)
+                .or_insert_with(|| EventParticipation {
+                    name: events
+                        .get(&participation.event_id)
+                        .map(|event_with_nodes| event_with_nodes.data.name().clone()),
+                    ..Default::default()
+                })
+                .answers = Some(participation.answers);
+        }
+
+        let state = ParticipationState {
+            milestone_index,
+            updated_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("time went backwards")
+                .as_millis(),
+            voting_power: voting_output.map_or(0, |output_data| output_data.output.amount()),
+            events: state_events,
+            staking_rewards,
+        };
+
+        let account_index = self.read().await.index;
+        self.storage_manager
+            .lock()
+            .await
+            .set_participation_state(account_index, &state)
+            .await?;
+
+        Ok(state)
+    }
+}
+
+// The amount of the participation multiplied by the milestones it participated in the holding phase of the event
+fn accumulated_participation(
+    participation: &TrackedParticipation,
+    event_data: &ParticipationEventData,
+    milestone_index: u32,
+) -> u64 {
+    let start = participation
+        .start_milestone_index
+        .max(*event_data.milestone_index_start());
+    let end = match participation.end_milestone_index {
+        // Still participating
+        0 => milestone_index,
+        end_milestone_index => end_milestone_index,
+    }
+    .min(*event_data.milestone_index_end());
+
+    participation
+        .amount
+        .saturating_mul(u64::from(end.saturating_sub(start)))
+}
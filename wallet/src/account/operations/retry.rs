@@ -1,15 +1,27 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashSet;
+
 use iota_client::{
     api_types::core::dto::LedgerInclusionStateDto,
     block::{
-        payload::{transaction::TransactionId, Payload},
+        address::Address,
+        input::Input,
+        output::Output,
+        payload::{
+            transaction::{TransactionEssence, TransactionId},
+            Payload,
+        },
         Block, BlockId,
     },
 };
 
-use crate::account::{handle::AccountHandle, types::InclusionState};
+use crate::account::{
+    handle::AccountHandle,
+    types::{InclusionState, Transaction},
+    TransactionOptions,
+};
 
 const DEFAULT_RETRY_UNTIL_INCLUDED_INTERVAL: u64 = 1;
 const DEFAULT_RETRY_UNTIL_INCLUDED_MAX_AMOUNT: u64 = 40;
@@ -51,6 +63,7 @@ impl AccountHandle {
 
             if transaction.inclusion_state == InclusionState::Conflicting
                 || transaction.inclusion_state == InclusionState::UnknownPruned
+                || transaction.inclusion_state == InclusionState::Expired
             {
                 return Err(iota_client::Error::TangleInclusion(format!(
                     "transaction id: {} inclusion state: {:?}",
@@ -123,4 +136,116 @@ impl AccountHandle {
             Err(crate::Error::TransactionNotFound(*transaction_id))
         }
     }
+
+    /// Retries a sent transaction that conflicted or expired, by sending its outputs again with new inputs. Outputs to
+    /// the account's own addresses, like the remainder, are created again by the input selection if needed, so only
+    /// transactions that send basic outputs to other addresses can be retried. The tagged data payload and the note are
+    /// sent again, the old and the new transaction are linked with [`Transaction::retried_by`] and
+    /// [`Transaction::retry_of`]. Expired transactions were only given up locally and could still be confirmed, so
+    /// their inputs are used again, the new transaction conflicts with the old one and the outputs are only sent once.
+    /// Returns the new transaction.
+    pub async fn retry_transaction(&self, transaction_id: &TransactionId) -> crate::Result<Transaction> {
+        log::debug!("[retry_transaction] {transaction_id}");
+
+        let account = self.read().await;
+        let transaction = account
+            .transactions
+            .get(transaction_id)
+            .cloned()
+            .ok_or(crate::Error::TransactionNotFound(*transaction_id))?;
+        let account_addresses: HashSet<Address> = account
+            .public_addresses
+            .iter()
+            .chain(account.internal_addresses.iter())
+            .map(|address| address.address.inner)
+            .collect();
+        drop(account);
+
+        if transaction.retried_by.is_some() {
+            return Err(crate::Error::TransactionNotRetryable(
+                *transaction_id,
+                "it was already retried",
+            ));
+        }
+        if !matches!(
+            transaction.inclusion_state,
+            InclusionState::Conflicting | InclusionState::Expired
+        ) {
+            return Err(crate::Error::TransactionNotRetryable(
+                *transaction_id,
+                "only conflicting or expired transactions can be retried",
+            ));
+        }
+
+        let TransactionEssence::Regular(essence) = transaction.payload.essence();
+        let outputs: Vec<Output> = essence
+            .outputs()
+            .iter()
+            .filter(|output| {
+                !output
+                    .unlock_conditions()
+                    .and_then(|unlock_conditions| unlock_conditions.address())
+                    .map_or(false, |address_unlock_condition| {
+                        account_addresses.contains(address_unlock_condition.address())
+                    })
+            })
+            .cloned()
+            .collect();
+        if outputs.is_empty() {
+            return Err(crate::Error::TransactionNotRetryable(
+                *transaction_id,
+                "it only has outputs to the account",
+            ));
+        }
+        if outputs.iter().any(|output| !matches!(output, Output::Basic(_))) {
+            return Err(crate::Error::TransactionNotRetryable(
+                *transaction_id,
+                "only basic outputs to other addresses can be sent again",
+            ));
+        }
+        // Both transactions spend the same outputs, so at most one of them can be confirmed
+        let mandatory_inputs = (transaction.inclusion_state == InclusionState::Expired).then(|| {
+            essence
+                .inputs()
+                .iter()
+                .filter_map(|input| {
+                    if let Input::Utxo(input) = input {
+                        Some(*input.output_id())
+                    } else {
+                        None
+                    }
+                })
+                .collect()
+        });
+        let tagged_data_payload = match essence.payload() {
+            Some(Payload::TaggedData(tagged_data_payload)) => Some(*tagged_data_payload.clone()),
+            _ => None,
+        };
+
+        let mut new_transaction = self
+            .finish_transaction(
+                outputs,
+                Some(TransactionOptions {
+                    tagged_data_payload,
+                    mandatory_inputs,
+                    note: transaction.note.clone(),
+                    ..Default::default()
+                }),
+            )
+            .await?;
+        new_transaction.retry_of = Some(*transaction_id);
+        new_transaction.labels = transaction.labels;
+
+        let mut account = self.write().await;
+        if let Some(old_transaction) = account.transactions.get_mut(transaction_id) {
+            old_transaction.retried_by = Some(new_transaction.transaction_id);
+        }
+        account
+            .transactions
+            .insert(new_transaction.transaction_id, new_transaction.clone());
+        #[cfg(feature = "storage")]
+        self.save(Some(&account)).await?;
+
+        Ok(new_transaction)
+    }
 }
@@ -0,0 +1,217 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+
+use iota_client::block::{
+    address::Address,
+    output::{FoundryId, NftId, Output, OutputId, TokenId},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::account::{handle::AccountHandle, types::OutputData, Account};
+
+/// Allow and deny lists and heuristics for unwanted NFTs and native tokens. Outputs that hold flagged assets are
+/// quarantined: they are left out of the balance and can't be claimed with
+/// [`OutputsToClaim`](crate::account::OutputsToClaim), until their assets are allowed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpamFilter {
+    /// Quarantine NFTs and native tokens that were sent with a storage deposit return or an expiration unlock
+    /// condition, so they need to be reviewed before they're claimed.
+    #[serde(default)]
+    pub flag_unsolicited: bool,
+    /// Bech32 encoded issuers whose NFTs and native tokens are quarantined. The issuer of an NFT is its immutable
+    /// issuer feature, the issuer of a native token is the alias that controls its foundry.
+    #[serde(default)]
+    pub denied_issuers: HashSet<String>,
+    /// Native tokens that are quarantined.
+    #[serde(default)]
+    pub denied_token_ids: HashSet<TokenId>,
+    /// NFTs that are quarantined.
+    #[serde(default)]
+    pub denied_nft_ids: HashSet<NftId>,
+    /// Native tokens that are never quarantined, takes precedence over all other rules.
+    #[serde(default)]
+    pub allowed_token_ids: HashSet<TokenId>,
+    /// NFTs that are never quarantined, takes precedence over all other rules.
+    #[serde(default)]
+    pub allowed_nft_ids: HashSet<NftId>,
+}
+
+/// Why an output was quarantined, the first matching rule of the [`SpamFilter`] in this order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SpamReason {
+    /// The NFT is on the deny list
+    DeniedNft,
+    /// A native token is on the deny list
+    DeniedToken,
+    /// The NFT or a native token was issued by a denied issuer
+    DeniedIssuer,
+    /// The assets were sent with a storage deposit return or an expiration unlock condition
+    Unsolicited,
+}
+
+/// An output that holds NFTs or native tokens flagged by the [`SpamFilter`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuarantinedOutput {
+    /// The id of the output
+    pub output_id: OutputId,
+    /// The amount of the output
+    pub amount: u64,
+    /// The NFT of the output, if it isn't allowed
+    pub nft_id: Option<NftId>,
+    /// The native tokens of the output that aren't allowed
+    pub token_ids: Vec<TokenId>,
+    /// The reason the output was quarantined
+    pub reason: SpamReason,
+}
+
+impl SpamFilter {
+    /// Checks an output of the account, returns `None` if it doesn't hold any flagged NFT or native token.
+    pub(crate) fn check(
+        &self,
+        output_data: &OutputData,
+        account_addresses: &HashSet<Address>,
+    ) -> Option<QuarantinedOutput> {
+        let output = &output_data.output;
+        // Alias and foundry outputs can only be created by the account itself
+        let (nft_id, nft_issuer) = match output {
+            Output::Basic(_) => (None, None),
+            Output::Nft(nft_output) => (
+                Some(nft_output.nft_id_non_null(&output_data.output_id))
+                    .filter(|nft_id| !self.allowed_nft_ids.contains(nft_id)),
+                nft_output.immutable_features().issuer().map(|issuer| *issuer.address()),
+            ),
+            _ => return None,
+        };
+        let token_ids: Vec<TokenId> = output
+            .native_tokens()
+            .into_iter()
+            .flat_map(|native_tokens| native_tokens.iter())
+            .map(|native_token| *native_token.token_id())
+            .filter(|token_id| !self.allowed_token_ids.contains(token_id))
+            .collect();
+        if nft_id.is_none() && token_ids.is_empty() {
+            return None;
+        }
+
+        let denied_issuers: HashSet<Address> = self
+            .denied_issuers
+            .iter()
+            .filter_map(|issuer| Address::try_from_bech32(issuer).ok())
+            .map(|(_, address)| address)
+            .collect();
+        let mut issuers = nft_id.and(nft_issuer).into_iter().chain(
+            token_ids
+                .iter()
+                .map(|token_id| Address::Alias(FoundryId::from(*token_id).alias_address())),
+        );
+
+        let reason = if nft_id.map_or(false, |nft_id| self.denied_nft_ids.contains(&nft_id)) {
+            SpamReason::DeniedNft
+        } else if token_ids
+            .iter()
+            .any(|token_id| self.denied_token_ids.contains(token_id))
+        {
+            SpamReason::DeniedToken
+        } else if issuers.any(|issuer| denied_issuers.contains(&issuer)) {
+            SpamReason::DeniedIssuer
+        } else if self.flag_unsolicited && is_unsolicited(output_data, account_addresses) {
+            SpamReason::Unsolicited
+        } else {
+            return None;
+        };
+
+        Some(QuarantinedOutput {
+            output_id: output_data.output_id,
+            amount: output.amount(),
+            nft_id,
+            token_ids,
+            reason,
+        })
+    }
+}
+
+impl AccountHandle {
+    /// Returns the spam filter of the account.
+    pub async fn spam_filter(&self) -> SpamFilter {
+        self.read().await.spam_filter.clone()
+    }
+
+    /// Sets the spam filter of the account, it's applied to the balance and the output claiming from now on.
+    pub async fn set_spam_filter(&self, spam_filter: SpamFilter) -> crate::Result<()> {
+        for issuer in &spam_filter.denied_issuers {
+            Address::try_from_bech32(issuer)?;
+        }
+        let mut account = self.write().await;
+        account.spam_filter = spam_filter;
+        #[cfg(feature = "storage")]
+        self.save(Some(&account)).await?;
+        Ok(())
+    }
+
+    /// Returns the unspent outputs that are quarantined by the spam filter, so they can be reviewed.
+    pub async fn quarantined_outputs(&self) -> Vec<QuarantinedOutput> {
+        quarantined_outputs(&*self.read().await)
+    }
+
+    /// Allows a quarantined native token, it's removed from the deny list and added to the balance from now on.
+    pub async fn allow_token(&self, token_id: TokenId) -> crate::Result<()> {
+        let mut account = self.write().await;
+        account.spam_filter.denied_token_ids.remove(&token_id);
+        account.spam_filter.allowed_token_ids.insert(token_id);
+        #[cfg(feature = "storage")]
+        self.save(Some(&account)).await?;
+        Ok(())
+    }
+
+    /// Allows a quarantined NFT, it's removed from the deny list and added to the balance from now on.
+    pub async fn allow_nft(&self, nft_id: NftId) -> crate::Result<()> {
+        let mut account = self.write().await;
+        account.spam_filter.denied_nft_ids.remove(&nft_id);
+        account.spam_filter.allowed_nft_ids.insert(nft_id);
+        #[cfg(feature = "storage")]
+        self.save(Some(&account)).await?;
+        Ok(())
+    }
+}
+
+/// The unspent outputs of the account that are quarantined by its spam filter.
+pub(crate) fn quarantined_outputs(account: &Account) -> Vec<QuarantinedOutput> {
+    let account_addresses = account_addresses(account);
+    account
+        .unspent_outputs
+        .values()
+        .filter_map(|output_data| account.spam_filter.check(output_data, &account_addresses))
+        .collect()
+}
+
+pub(crate) fn account_addresses(account: &Account) -> HashSet<Address> {
+    account
+        .public_addresses
+        .iter()
+        .chain(account.internal_addresses.iter())
+        .map(|address| *address.address().as_ref())
+        .collect()
+}
+
+// Outputs that need to be claimed before their storage deposit or the whole output goes back to someone else. The
+// account didn't ask for them if it doesn't own the return address.
+fn is_unsolicited(output_data: &OutputData, account_addresses: &HashSet<Address>) -> bool {
+    let Some(unlock_conditions) = output_data.output.unlock_conditions() else {
+        return false;
+    };
+    unlock_conditions
+        .storage_deposit_return()
+        .map(|storage_deposit_return| storage_deposit_return.return_address())
+        .into_iter()
+        .chain(
+            unlock_conditions
+                .expiration()
+                .map(|expiration| expiration.return_address()),
+        )
+        .any(|return_address| !account_addresses.contains(return_address))
+}
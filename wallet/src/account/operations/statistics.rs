@@ -0,0 +1,204 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+};
+
+use iota_client::block::{address::Address, output::Output, payload::transaction::TransactionEssence};
+
+use crate::account::{
+    constants::TOP_COUNTERPARTS_AMOUNT,
+    handle::AccountHandle,
+    operations::helpers::output::output_address,
+    types::{AccountStatistics, CounterpartStatistics, InclusionState, Transaction, TransactionKind},
+};
+
+impl AccountHandle {
+    /// Returns statistics over the stored transactions in the time range, as unix timestamps in milliseconds.
+    /// Conflicting transactions are ignored. Amounts of received transactions are attributed to the address of the
+    /// first input, if the inputs are known.
+    pub async fn statistics(&self, period: Range<u64>) -> crate::Result<AccountStatistics> {
+        let (mut statistics, counterparts) = self.statistics_with_counterparts(period, false).await?;
+        let bech32_hrp = match self.read().await.public_addresses.first() {
+            Some(address) => address.address().bech32_hrp().to_string(),
+            None => return Ok(AccountStatistics::default()),
+        };
+        statistics.top_counterparts = top_counterparts(counterparts, &bech32_hrp);
+
+        Ok(statistics)
+    }
+
+    // Statistics without the top counterparts, and the statistics of all counterparts. With
+    // `exclude_internal_transfers`, transfers between accounts of the wallet are only counted as transaction kind of
+    // the sending account, so the statistics of all accounts can be summed up.
+    pub(crate) async fn statistics_with_counterparts(
+        &self,
+        period: Range<u64>,
+        exclude_internal_transfers: bool,
+    ) -> crate::Result<(AccountStatistics, HashMap<Address, CounterpartStatistics>)> {
+        let account = self.read().await;
+
+        let account_addresses: HashSet<Address> = account
+            .public_addresses
+            .iter()
+            .chain(account.internal_addresses.iter())
+            .map(|address| *address.address().as_ref())
+            .collect();
+
+        let period = period.start as u128..period.end as u128;
+        let is_in_period = |transaction: &&Transaction| {
+            period.contains(&transaction.timestamp)
+                && !matches!(
+                    transaction.inclusion_state,
+                    InclusionState::Conflicting | InclusionState::Expired
+                )
+        };
+
+        let mut statistics = AccountStatistics::default();
+        let mut counterparts: HashMap<Address, CounterpartStatistics> = HashMap::new();
+
+        for transaction in account.transactions().values().filter(is_in_period) {
+            let TransactionEssence::Regular(essence) = transaction.payload.essence();
+            let internal_transfer = account.internal_transfers.contains_key(&transaction.transaction_id);
+            if internal_transfer && exclude_internal_transfers {
+                *statistics
+                    .transaction_kinds
+                    .entry(TransactionKind::InternalTransfer)
+                    .or_default() += 1;
+                continue;
+            }
+            let mut transaction_counterparts = HashSet::new();
+
+            for output in essence.outputs().iter() {
+                if let Some(address) = output_address(output) {
+                    if !account_addresses.contains(&address) {
+                        statistics.sent += output.amount();
+                        counterparts.entry(address).or_default().sent += output.amount();
+                        transaction_counterparts.insert(address);
+                    }
+                }
+            }
+
+            for address in transaction_counterparts {
+                counterparts.entry(address).or_default().transaction_count += 1;
+            }
+            statistics.sent_transactions += 1;
+            let transaction_kind = if internal_transfer {
+                TransactionKind::InternalTransfer
+            } else {
+                transaction_kind(essence.outputs(), false, &account_addresses)
+            };
+            *statistics.transaction_kinds.entry(transaction_kind).or_default() += 1;
+        }
+
+        let mut token_supply = None;
+        for transaction in account.incoming_transactions().values().filter(is_in_period) {
+            let TransactionEssence::Regular(essence) = transaction.payload.essence();
+            let internal_transfer = account.internal_transfers.contains_key(&transaction.transaction_id);
+            if internal_transfer && exclude_internal_transfers {
+                continue;
+            }
+
+            let received_amount: u64 = essence
+                .outputs()
+                .iter()
+                .filter(|output| output_address(output).map_or(false, |address| account_addresses.contains(&address)))
+                .map(|output| output.amount())
+                .sum();
+            statistics.received += received_amount;
+
+            if let Some(input) = transaction.inputs.first() {
+                let token_supply = match token_supply {
+                    Some(token_supply) => token_supply,
+                    None => *token_supply.insert(self.client.get_token_supply().await?),
+                };
+                let input = Output::try_from_dto(&input.output, token_supply)?;
+                if let Some(address) = output_address(&input) {
+                    if !account_addresses.contains(&address) {
+                        let counterpart = counterparts.entry(address).or_default();
+                        counterpart.received += received_amount;
+                        counterpart.transaction_count += 1;
+                    }
+                }
+            }
+
+            statistics.received_transactions += 1;
+            let transaction_kind = if internal_transfer {
+                TransactionKind::InternalTransfer
+            } else {
+                transaction_kind(essence.outputs(), true, &account_addresses)
+            };
+            *statistics.transaction_kinds.entry(transaction_kind).or_default() += 1;
+        }
+
+        Ok((statistics, counterparts))
+    }
+}
+
+// The counterparts with the most transactions and the highest amounts
+pub(crate) fn top_counterparts(
+    counterparts: HashMap<Address, CounterpartStatistics>,
+    bech32_hrp: &str,
+) -> Vec<CounterpartStatistics> {
+    let mut top_counterparts: Vec<CounterpartStatistics> = counterparts
+        .into_iter()
+        .map(|(address, counterpart)| CounterpartStatistics {
+            address: address.to_bech32(bech32_hrp),
+            ..counterpart
+        })
+        .collect();
+    top_counterparts.sort_by(|a, b| {
+        b.transaction_count
+            .cmp(&a.transaction_count)
+            .then((b.sent + b.received).cmp(&(a.sent + a.received)))
+            .then(a.address.cmp(&b.address))
+    });
+    top_counterparts.truncate(TOP_COUNTERPARTS_AMOUNT);
+    top_counterparts
+}
+
+// Determine the kind of a transaction from its outputs, the transferred outputs are the ones to the account for
+// incoming transactions and the ones to other addresses for sent transactions
+fn transaction_kind(outputs: &[Output], incoming: bool, account_addresses: &HashSet<Address>) -> TransactionKind {
+    if outputs.iter().any(|output| matches!(output, Output::Foundry(_))) {
+        return TransactionKind::Foundry;
+    }
+    if outputs
+        .iter()
+        .any(|output| matches!(output, Output::Nft(nft_output) if nft_output.nft_id().is_null()))
+    {
+        return TransactionKind::NftMint;
+    }
+    if outputs
+        .iter()
+        .any(|output| matches!(output, Output::Alias(alias_output) if alias_output.alias_id().is_null()))
+    {
+        return TransactionKind::AliasCreation;
+    }
+
+    let transferred_outputs: Vec<&Output> = outputs
+        .iter()
+        .filter(|output| {
+            output_address(output).map_or(false, |address| account_addresses.contains(&address)) == incoming
+        })
+        .collect();
+
+    if transferred_outputs.is_empty() {
+        TransactionKind::Internal
+    } else if transferred_outputs
+        .iter()
+        .any(|output| matches!(output, Output::Nft(_)))
+    {
+        TransactionKind::NftTransfer
+    } else if transferred_outputs.iter().any(|output| {
+        output
+            .native_tokens()
+            .map_or(false, |native_tokens| !native_tokens.is_empty())
+    }) {
+        TransactionKind::NativeTokenTransfer
+    } else {
+        TransactionKind::BaseCoinTransfer
+    }
+}
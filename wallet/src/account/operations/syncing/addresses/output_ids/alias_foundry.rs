@@ -14,6 +14,7 @@ use iota_client::{
     node_api::indexer::query_parameters::QueryParameter,
 };
 
+use super::with_created_after;
 use crate::{
     account::{handle::AccountHandle, SyncOptions},
     task,
@@ -25,6 +26,7 @@ impl AccountHandle {
         &self,
         bech32_address: &str,
         sync_options: SyncOptions,
+        created_after: Option<u32>,
     ) -> crate::Result<Vec<OutputId>> {
         log::debug!("[SYNC] get_alias_and_foundry_output_ids");
         let client = self.client();
@@ -35,13 +37,19 @@ impl AccountHandle {
         {
             output_ids.extend(
                 client
-                    .alias_output_ids(vec![QueryParameter::Governor(bech32_address.to_string())])
+                    .alias_output_ids(with_created_after(
+                        vec![QueryParameter::Governor(bech32_address.to_string())],
+                        created_after,
+                    ))
                     .await?
                     .items,
             );
             output_ids.extend(
                 client
-                    .alias_output_ids(vec![QueryParameter::StateController(bech32_address.to_string())])
+                    .alias_output_ids(with_created_after(
+                        vec![QueryParameter::StateController(bech32_address.to_string())],
+                        created_after,
+                    ))
                     .await?
                     .items,
             );
@@ -56,7 +64,10 @@ impl AccountHandle {
                     let client = client.clone();
                     task::spawn(async move {
                         client
-                            .alias_output_ids(vec![QueryParameter::Governor(bech32_address_)])
+                            .alias_output_ids(with_created_after(
+                                vec![QueryParameter::Governor(bech32_address_)],
+                                created_after,
+                            ))
                             .await
                             .map_err(From::from)
                     })
@@ -69,7 +80,10 @@ impl AccountHandle {
                     let client = client.clone();
                     task::spawn(async move {
                         client
-                            .alias_output_ids(vec![QueryParameter::StateController(bech32_address_)])
+                            .alias_output_ids(with_created_after(
+                                vec![QueryParameter::StateController(bech32_address_)],
+                                created_after,
+                            ))
                             .await
                             .map_err(From::from)
                     })
@@ -10,6 +10,7 @@ use futures::FutureExt;
 use iota_client::api_types::plugins::indexer::OutputIdsResponse;
 use iota_client::{block::output::OutputId, node_api::indexer::query_parameters::QueryParameter};
 
+use super::with_created_after;
 use crate::account::handle::AccountHandle;
 
 impl AccountHandle {
@@ -17,16 +18,20 @@ impl AccountHandle {
     pub(crate) async fn get_basic_output_ids_with_address_unlock_condition_only(
         &self,
         bech32_address: String,
+        created_after: Option<u32>,
     ) -> iota_client::Result<Vec<OutputId>> {
         // Only request basic outputs with `AddressUnlockCondition` only
         Ok(self
             .client
-            .basic_output_ids(vec![
-                QueryParameter::Address(bech32_address),
-                QueryParameter::HasExpiration(false),
-                QueryParameter::HasTimelock(false),
-                QueryParameter::HasStorageDepositReturn(false),
-            ])
+            .basic_output_ids(with_created_after(
+                vec![
+                    QueryParameter::Address(bech32_address),
+                    QueryParameter::HasExpiration(false),
+                    QueryParameter::HasTimelock(false),
+                    QueryParameter::HasStorageDepositReturn(false),
+                ],
+                created_after,
+            ))
             .await?
             .items)
     }
@@ -36,6 +41,7 @@ impl AccountHandle {
     pub(crate) async fn get_basic_output_ids_with_any_unlock_condition(
         &self,
         bech32_address: &str,
+        created_after: Option<u32>,
     ) -> crate::Result<Vec<OutputId>> {
         // aliases and foundries
         #[cfg(target_family = "wasm")]
@@ -43,23 +49,28 @@ impl AccountHandle {
             let mut output_ids = vec![];
             output_ids.extend(
                 self.client()
-                    .basic_output_ids(vec![QueryParameter::Address(bech32_address.to_string())])
+                    .basic_output_ids(with_created_after(
+                        vec![QueryParameter::Address(bech32_address.to_string())],
+                        created_after,
+                    ))
                     .await?
                     .items,
             );
             output_ids.extend(
                 self.client()
-                    .basic_output_ids(vec![QueryParameter::StorageDepositReturnAddress(
-                        bech32_address.to_string(),
-                    )])
+                    .basic_output_ids(with_created_after(
+                        vec![QueryParameter::StorageDepositReturnAddress(bech32_address.to_string())],
+                        created_after,
+                    ))
                     .await?
                     .items,
             );
             output_ids.extend(
                 self.client()
-                    .basic_output_ids(vec![QueryParameter::ExpirationReturnAddress(
-                        bech32_address.to_string(),
-                    )])
+                    .basic_output_ids(with_created_after(
+                        vec![QueryParameter::ExpirationReturnAddress(bech32_address.to_string())],
+                        created_after,
+                    ))
                     .await?
                     .items,
             );
@@ -77,7 +88,10 @@ impl AccountHandle {
                     let client = client.clone();
                     tokio::spawn(async move {
                         client
-                            .basic_output_ids(vec![QueryParameter::Address(bech32_address)])
+                            .basic_output_ids(with_created_after(
+                                vec![QueryParameter::Address(bech32_address)],
+                                created_after,
+                            ))
                             .await
                             .map_err(From::from)
                     })
@@ -90,7 +104,10 @@ impl AccountHandle {
                     let client = client.clone();
                     tokio::spawn(async move {
                         client
-                            .basic_output_ids(vec![QueryParameter::StorageDepositReturnAddress(bech32_address)])
+                            .basic_output_ids(with_created_after(
+                                vec![QueryParameter::StorageDepositReturnAddress(bech32_address)],
+                                created_after,
+                            ))
                             .await
                             .map_err(From::from)
                     })
@@ -103,7 +120,10 @@ impl AccountHandle {
                     let client = client.clone();
                     tokio::spawn(async move {
                         client
-                            .basic_output_ids(vec![QueryParameter::ExpirationReturnAddress(bech32_address)])
+                            .basic_output_ids(with_created_after(
+                                vec![QueryParameter::ExpirationReturnAddress(bech32_address)],
+                                created_after,
+                            ))
                             .await
                             .map_err(From::from)
                     })
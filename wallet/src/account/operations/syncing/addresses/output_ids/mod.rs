@@ -5,33 +5,38 @@ mod alias_foundry;
 mod basic;
 mod nft;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 #[cfg(not(target_family = "wasm"))]
 use futures::FutureExt;
 use instant::Instant;
-use iota_client::block::{address::Address, output::OutputId};
+use iota_client::{
+    block::{address::Address, output::OutputId},
+    node_api::indexer::query_parameters::QueryParameter,
+};
 
 use crate::account::{
-    constants::PARALLEL_REQUESTS_AMOUNT, handle::AccountHandle, operations::syncing::SyncOptions,
+    handle::AccountHandle,
+    operations::syncing::{cancellation::CancellationToken, SyncOptions},
     types::address::AddressWithUnspentOutputs,
 };
 
 impl AccountHandle {
     /// Returns output ids for outputs that are directly (Ed25519 address in AddressUnlockCondition) or indirectly
     /// (alias/nft address in AddressUnlockCondition and the alias/nft output is controlled with the Ed25519 address)
-    /// connected to
+    /// connected to. With `created_after`, only outputs created after this milestone timestamp are returned.
     pub(crate) async fn get_output_ids_for_address(
         &self,
         address: Address,
         sync_options: &SyncOptions,
+        created_after: Option<u32>,
     ) -> crate::Result<Vec<OutputId>> {
         let bech32_hrp = self.client.get_bech32_hrp().await?;
         let bech32_address = &address.to_bech32(bech32_hrp);
 
         if sync_options.sync_only_most_basic_outputs {
             let output_ids = self
-                .get_basic_output_ids_with_address_unlock_condition_only(bech32_address.to_string())
+                .get_basic_output_ids_with_address_unlock_condition_only(bech32_address.to_string(), created_after)
                 .await?;
             return Ok(output_ids);
         }
@@ -50,7 +55,7 @@ impl AccountHandle {
             #[cfg(target_family = "wasm")]
             {
                 results.push(
-                    self.get_basic_output_ids_with_any_unlock_condition(&bech32_address)
+                    self.get_basic_output_ids_with_any_unlock_condition(&bech32_address, created_after)
                         .await,
                 )
             }
@@ -63,7 +68,7 @@ impl AccountHandle {
                         let bech32_address = bech32_address.clone();
                         tokio::spawn(async move {
                             account_handle
-                                .get_basic_output_ids_with_any_unlock_condition(&bech32_address)
+                                .get_basic_output_ids_with_any_unlock_condition(&bech32_address, created_after)
                                 .await
                         })
                         .await
@@ -80,7 +85,10 @@ impl AccountHandle {
             // nfts
             #[cfg(target_family = "wasm")]
             {
-                results.push(self.get_nft_output_ids_with_any_unlock_condition(&bech32_address).await)
+                results.push(
+                    self.get_nft_output_ids_with_any_unlock_condition(&bech32_address, created_after)
+                        .await,
+                )
             }
 
             #[cfg(not(target_family = "wasm"))]
@@ -91,7 +99,7 @@ impl AccountHandle {
                         let account_handle = self.clone();
                         tokio::spawn(async move {
                             account_handle
-                                .get_nft_output_ids_with_any_unlock_condition(&bech32_address_)
+                                .get_nft_output_ids_with_any_unlock_condition(&bech32_address_, created_after)
                                 .await
                         })
                         .await
@@ -109,7 +117,7 @@ impl AccountHandle {
             #[cfg(target_family = "wasm")]
             {
                 results.push(
-                    self.get_alias_and_foundry_output_ids(&bech32_address, sync_options.clone())
+                    self.get_alias_and_foundry_output_ids(&bech32_address, sync_options.clone(), created_after)
                         .await,
                 )
             }
@@ -123,7 +131,7 @@ impl AccountHandle {
                         let account_handle = self.clone();
                         tokio::spawn(async move {
                             account_handle
-                                .get_alias_and_foundry_output_ids(&bech32_address, sync_options)
+                                .get_alias_and_foundry_output_ids(&bech32_address, sync_options, created_after)
                                 .await
                         })
                         .await
@@ -147,30 +155,52 @@ impl AccountHandle {
     }
 
     /// Get the current output ids for provided addresses and only returns addresses that have unspent outputs and
-    /// return spent outputs separated
+    /// return spent outputs separated. Addresses with a cursor only get the outputs requested that were created after
+    /// it, their known outputs are kept if they aren't spent.
     pub(crate) async fn get_output_ids_for_addresses(
         &self,
         options: &SyncOptions,
         addresses_with_unspent_outputs: Vec<AddressWithUnspentOutputs>,
+        address_cursors: &HashMap<String, u32>,
+        cancellation: &CancellationToken,
     ) -> crate::Result<(Vec<AddressWithUnspentOutputs>, Vec<OutputId>)> {
         log::debug!("[SYNC] start get_output_ids_for_addresses");
         let address_output_ids_start_time = Instant::now();
 
+        // Known outputs of addresses with a cursor aren't returned again, so we need to check if they got spent
+        let spent_known_output_ids = self
+            .get_spent_output_ids(
+                addresses_with_unspent_outputs
+                    .iter()
+                    .filter(|address| address_cursors.contains_key(&address.address.to_bech32()))
+                    .flat_map(|address| address.output_ids.iter().copied())
+                    .collect(),
+            )
+            .await?;
+
         let mut addresses_with_outputs = Vec::new();
         // spent outputs or alias/nft/foundries that don't get synced anymore, because of other sync options
         let mut spent_or_not_anymore_synced_outputs = Vec::new();
-        // We split the addresses into chunks so we don't get timeouts if we have thousands
-        for addresses_chunk in &mut addresses_with_unspent_outputs
-            .chunks(PARALLEL_REQUESTS_AMOUNT)
-            .map(|x: &[AddressWithUnspentOutputs]| x.to_vec())
-        {
+        // We split the addresses into chunks so we don't get timeouts if we have thousands, the chunk size adapts to
+        // the node
+        let mut addresses_to_request = addresses_with_unspent_outputs;
+        while !addresses_to_request.is_empty() {
+            cancellation.check()?;
+            let chunk_size = self.request_limiter.acquire(addresses_to_request.len()).await;
+            let addresses_chunk: Vec<AddressWithUnspentOutputs> = addresses_to_request.drain(..chunk_size).collect();
+            #[cfg(feature = "events")]
+            let addresses_scanned = addresses_chunk.len();
             let results;
             #[cfg(target_family = "wasm")]
             {
                 let mut tasks = Vec::new();
-                for address in addresses_chunk {
-                    let output_ids = self.get_output_ids_for_address(address.address.inner, &options).await?;
-                    tasks.push(crate::Result::Ok((address, output_ids)));
+                for address in addresses_chunk.clone() {
+                    let created_after = address_cursors.get(&address.address.to_bech32()).copied();
+                    tasks.push(
+                        self.get_output_ids_for_address(address.address.inner, &options, created_after)
+                            .await
+                            .map(|output_ids| (address, created_after, output_ids)),
+                    );
                 }
                 results = tasks;
             }
@@ -178,15 +208,16 @@ impl AccountHandle {
             #[cfg(not(target_family = "wasm"))]
             {
                 let mut tasks = Vec::new();
-                for address in addresses_chunk {
+                for address in addresses_chunk.clone() {
                     let account_handle = self.clone();
                     let sync_options = options.clone();
+                    let created_after = address_cursors.get(&address.address.to_bech32()).copied();
                     tasks.push(async move {
                         tokio::spawn(async move {
                             let output_ids = account_handle
-                                .get_output_ids_for_address(address.address.inner, &sync_options)
+                                .get_output_ids_for_address(address.address.inner, &sync_options, created_after)
                                 .await?;
-                            crate::Result::Ok((address, output_ids))
+                            crate::Result::Ok((address, created_after, output_ids))
                         })
                         .await
                     });
@@ -195,8 +226,32 @@ impl AccountHandle {
                 results = futures::future::try_join_all(tasks).await?;
             }
 
-            for res in results {
-                let (mut address, output_ids): (AddressWithUnspentOutputs, Vec<OutputId>) = res?;
+            let results = match results.into_iter().collect::<crate::Result<Vec<_>>>() {
+                Ok(results) => {
+                    self.request_limiter.record_success();
+                    results
+                }
+                Err(err) => {
+                    // Request the chunk again if the node was only overloaded
+                    self.request_limiter.record_failure(err).await?;
+                    addresses_to_request.splice(0..0, addresses_chunk);
+                    continue;
+                }
+            };
+            for (mut address, created_after, mut output_ids) in results {
+                // Only new outputs were requested, so add the known outputs that aren't spent
+                if created_after.is_some() {
+                    let new_output_ids: HashSet<OutputId> = output_ids.drain(..).collect();
+                    output_ids = address
+                        .output_ids
+                        .iter()
+                        .filter(|output_id| {
+                            !spent_known_output_ids.contains(output_id) && !new_output_ids.contains(output_id)
+                        })
+                        .copied()
+                        .chain(new_output_ids)
+                        .collect();
+                }
                 // only return addresses with outputs
                 if !output_ids.is_empty() {
                     // outputs we had before, but now not anymore, got spent or are alias/nft/foundries that don't get
@@ -214,6 +269,9 @@ impl AccountHandle {
                     spent_or_not_anymore_synced_outputs.extend(address.output_ids.into_iter());
                 }
             }
+            #[cfg(feature = "events")]
+            self.update_sync_progress(|sync_progress| sync_progress.addresses_scanned += addresses_scanned)
+                .await;
         }
 
         log::debug!(
@@ -226,4 +284,31 @@ impl AccountHandle {
         );
         Ok((addresses_with_outputs, spent_or_not_anymore_synced_outputs))
     }
+
+    // Returns the output ids of outputs that are spent or that the node doesn't know anymore
+    async fn get_spent_output_ids(&self, output_ids: Vec<OutputId>) -> crate::Result<HashSet<OutputId>> {
+        if output_ids.is_empty() {
+            return Ok(HashSet::new());
+        }
+
+        let mut unspent_output_ids = HashSet::new();
+        for output_metadata_response in self.client.try_get_outputs_metadata(output_ids.clone()).await? {
+            if !output_metadata_response.is_spent {
+                unspent_output_ids.insert(output_metadata_response.output_id()?);
+            }
+        }
+
+        Ok(output_ids
+            .into_iter()
+            .filter(|output_id| !unspent_output_ids.contains(output_id))
+            .collect())
+    }
+}
+
+// Adds the `CreatedAfter` query parameter, if only outputs created after a milestone timestamp should be returned
+fn with_created_after(mut query_parameters: Vec<QueryParameter>, created_after: Option<u32>) -> Vec<QueryParameter> {
+    if let Some(created_after) = created_after {
+        query_parameters.push(QueryParameter::CreatedAfter(created_after));
+    }
+    query_parameters
 }
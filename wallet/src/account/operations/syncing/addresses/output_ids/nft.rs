@@ -10,6 +10,7 @@ use futures::FutureExt;
 use iota_client::api_types::plugins::indexer::OutputIdsResponse;
 use iota_client::{block::output::OutputId, node_api::indexer::query_parameters::QueryParameter};
 
+use super::with_created_after;
 use crate::account::handle::AccountHandle;
 
 impl AccountHandle {
@@ -17,29 +18,35 @@ impl AccountHandle {
     pub(crate) async fn get_nft_output_ids_with_any_unlock_condition(
         &self,
         bech32_address: &str,
+        created_after: Option<u32>,
     ) -> crate::Result<Vec<OutputId>> {
         #[cfg(target_family = "wasm")]
         {
             let mut output_ids = vec![];
             output_ids.extend(
                 self.client()
-                    .nft_output_ids(vec![QueryParameter::Address(bech32_address.to_string())])
+                    .nft_output_ids(with_created_after(
+                        vec![QueryParameter::Address(bech32_address.to_string())],
+                        created_after,
+                    ))
                     .await?
                     .items,
             );
             output_ids.extend(
                 self.client()
-                    .nft_output_ids(vec![QueryParameter::StorageDepositReturnAddress(
-                        bech32_address.to_string(),
-                    )])
+                    .nft_output_ids(with_created_after(
+                        vec![QueryParameter::StorageDepositReturnAddress(bech32_address.to_string())],
+                        created_after,
+                    ))
                     .await?
                     .items,
             );
             output_ids.extend(
                 self.client()
-                    .nft_output_ids(vec![QueryParameter::ExpirationReturnAddress(
-                        bech32_address.to_string(),
-                    )])
+                    .nft_output_ids(with_created_after(
+                        vec![QueryParameter::ExpirationReturnAddress(bech32_address.to_string())],
+                        created_after,
+                    ))
                     .await?
                     .items,
             );
@@ -56,7 +63,10 @@ impl AccountHandle {
                     tokio::spawn(async move {
                         // Get nft outputs where the address is in the address unlock condition
                         client
-                            .nft_output_ids(vec![QueryParameter::Address(bech32_address_)])
+                            .nft_output_ids(with_created_after(
+                                vec![QueryParameter::Address(bech32_address_)],
+                                created_after,
+                            ))
                             .await
                             .map_err(From::from)
                     })
@@ -69,7 +79,10 @@ impl AccountHandle {
                     tokio::spawn(async move {
                         // Get outputs where the address is in the storage deposit return unlock condition
                         client
-                            .nft_output_ids(vec![QueryParameter::StorageDepositReturnAddress(bech32_address_)])
+                            .nft_output_ids(with_created_after(
+                                vec![QueryParameter::StorageDepositReturnAddress(bech32_address_)],
+                                created_after,
+                            ))
                             .await
                             .map_err(From::from)
                     })
@@ -82,7 +95,10 @@ impl AccountHandle {
                     tokio::spawn(async move {
                         // Get outputs where the address is in the expiration unlock condition
                         client
-                            .nft_output_ids(vec![QueryParameter::ExpirationReturnAddress(bech32_address_)])
+                            .nft_output_ids(with_created_after(
+                                vec![QueryParameter::ExpirationReturnAddress(bech32_address_)],
+                                created_after,
+                            ))
                             .await
                             .map_err(From::from)
                     })
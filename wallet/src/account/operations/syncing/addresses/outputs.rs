@@ -3,10 +3,12 @@
 
 use instant::Instant;
 
+#[cfg(feature = "events")]
+use crate::events::types::SyncStep;
 use crate::{
     account::{
-        constants::PARALLEL_REQUESTS_AMOUNT, handle::AccountHandle, types::address::AddressWithUnspentOutputs,
-        OutputData,
+        handle::AccountHandle, operations::syncing::cancellation::CancellationToken,
+        types::address::AddressWithUnspentOutputs, OutputData,
     },
     task,
 };
@@ -16,20 +18,35 @@ impl AccountHandle {
     pub(crate) async fn get_outputs_from_address_output_ids(
         &self,
         addresses_with_unspent_outputs: Vec<AddressWithUnspentOutputs>,
+        cancellation: &CancellationToken,
     ) -> crate::Result<(Vec<AddressWithUnspentOutputs>, Vec<OutputData>)> {
         log::debug!("[SYNC] start get_outputs_from_address_output_ids");
         let address_outputs_start_time = Instant::now();
+        #[cfg(feature = "events")]
+        {
+            let outputs_total = addresses_with_unspent_outputs
+                .iter()
+                .map(|address| address.output_ids.len())
+                .sum();
+            self.update_sync_progress(|sync_progress| {
+                sync_progress.step = SyncStep::FetchingOutputs;
+                sync_progress.outputs_total = outputs_total;
+            })
+            .await;
+        }
 
         let mut addresses_with_outputs = Vec::new();
         let mut outputs_data = Vec::new();
 
-        // We split the addresses into chunks so we don't get timeouts if we have thousands
-        for addresses_chunk in &mut addresses_with_unspent_outputs
-            .chunks(PARALLEL_REQUESTS_AMOUNT)
-            .map(|x: &[AddressWithUnspentOutputs]| x.to_vec())
-        {
+        // We split the addresses into chunks so we don't get timeouts if we have thousands, the chunk size adapts to
+        // the node
+        let mut addresses_to_request = addresses_with_unspent_outputs;
+        while !addresses_to_request.is_empty() {
+            cancellation.check()?;
+            let chunk_size = self.request_limiter.acquire(addresses_to_request.len()).await;
+            let addresses_chunk: Vec<AddressWithUnspentOutputs> = addresses_to_request.drain(..chunk_size).collect();
             let mut tasks = Vec::new();
-            for address in addresses_chunk {
+            for address in addresses_chunk.clone() {
                 let account_handle = self.clone();
                 tasks.push(async move {
                     task::spawn(async move {
@@ -43,12 +60,34 @@ impl AccountHandle {
                     .await
                 });
             }
-            let results = futures::future::try_join_all(tasks).await?;
-            for res in results {
-                let (address, outputs): (AddressWithUnspentOutputs, Vec<OutputData>) = res?;
+            let results = match futures::future::try_join_all(tasks)
+                .await?
+                .into_iter()
+                .collect::<crate::Result<Vec<_>>>()
+            {
+                Ok(results) => {
+                    self.request_limiter.record_success();
+                    results
+                }
+                Err(err) => {
+                    // Request the chunk again if the node was only overloaded
+                    self.request_limiter.record_failure(err).await?;
+                    addresses_to_request.splice(0..0, addresses_chunk);
+                    continue;
+                }
+            };
+            #[cfg(feature = "events")]
+            let outputs_data_before = outputs_data.len();
+            for (address, outputs) in results {
                 addresses_with_outputs.push(address);
                 outputs_data.extend(outputs.into_iter());
             }
+            #[cfg(feature = "events")]
+            {
+                let outputs_fetched = outputs_data.len() - outputs_data_before;
+                self.update_sync_progress(|sync_progress| sync_progress.outputs_fetched += outputs_fetched)
+                    .await;
+            }
         }
         log::debug!(
             "[SYNC] finished get_outputs_from_address_output_ids in {:.2?}",
@@ -0,0 +1,200 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+
+use iota_client::{block::output::OutputId, node_api::indexer::query_parameters::QueryParameter, Client};
+use serde::{Deserialize, Serialize};
+
+use crate::account::{
+    handle::AccountHandle,
+    operations::syncing::{cancellation::CancellationToken, SyncOptions},
+};
+#[cfg(feature = "events")]
+use crate::events::types::{SyncDiscrepancyEvent, WalletEvent};
+
+/// Options to verify the outputs of a sync with other nodes than the ones of the client.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrossVerificationOptions {
+    /// Urls of the nodes that are queried in addition to the node of the client.
+    pub nodes: Vec<String>,
+    /// How many nodes, including the node of the client, need to report an unspent output so it's accepted. The
+    /// majority of the nodes if not set.
+    #[serde(default)]
+    pub quorum: Option<usize>,
+}
+
+/// An unspent output that wasn't reported by all queried nodes.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncDiscrepancy {
+    /// The output id.
+    pub output_id: OutputId,
+    /// The urls of the nodes that reported the output as unspent.
+    pub reported_by: Vec<String>,
+    /// The urls of the nodes that didn't report the output.
+    pub missing_from: Vec<String>,
+    /// If enough nodes reported the output to reach the quorum.
+    pub accepted: bool,
+}
+
+impl AccountHandle {
+    // Request the unspent outputs of the synced addresses from the node of the client and the nodes of the options and
+    // compare them. Outputs that aren't reported by all nodes are emitted as `SyncDiscrepancy` event with the `events`
+    // feature. If the node of the client disagrees with the quorum, the sync fails, so the account isn't used with the
+    // data of a single malicious or lagging node.
+    pub(crate) async fn cross_verify_outputs(
+        &self,
+        options: &SyncOptions,
+        cross_verification: &CrossVerificationOptions,
+        cancellation: &CancellationToken,
+    ) -> crate::Result<()> {
+        log::debug!("[SYNC] cross verifying outputs with {:?}", cross_verification.nodes);
+        let bech32_hrp = self.client.get_bech32_hrp().await?;
+        let bech32_addresses: Vec<String> = self
+            .get_addresses_to_sync(options)
+            .await?
+            .into_iter()
+            .map(|address| address.address.inner.to_bech32(bech32_hrp.clone()))
+            .collect();
+
+        let client_node_url = self.client.get_info().await?.url;
+        let mut reported_output_ids = vec![(
+            client_node_url.clone(),
+            request_unspent_output_ids(&self.client, &bech32_addresses).await?,
+        )];
+        for node_url in &cross_verification.nodes {
+            cancellation.check()?;
+            let client = Client::builder()
+                .with_ignore_node_health()
+                .with_node(node_url)?
+                .finish()?;
+            reported_output_ids.push((
+                node_url.clone(),
+                request_unspent_output_ids(&client, &bech32_addresses).await?,
+            ));
+        }
+
+        let quorum = cross_verification.quorum.unwrap_or(reported_output_ids.len() / 2 + 1);
+        let discrepancies = find_discrepancies(&reported_output_ids, quorum);
+        if discrepancies.is_empty() {
+            return Ok(());
+        }
+        log::warn!("[SYNC] nodes reported different outputs: {discrepancies:?}");
+
+        // The node of the client disagrees with the quorum if it reported an output that wasn't accepted or didn't
+        // report an accepted output
+        let rejected = discrepancies
+            .iter()
+            .filter(|discrepancy| discrepancy.accepted != discrepancy.reported_by.contains(&client_node_url))
+            .count();
+
+        #[cfg(feature = "events")]
+        {
+            let account_index = *self.read().await.index();
+            self.event_emitter.lock().await.emit(
+                account_index,
+                WalletEvent::SyncDiscrepancy(SyncDiscrepancyEvent { discrepancies, quorum }),
+            );
+        }
+
+        if rejected > 0 {
+            return Err(crate::Error::CrossVerificationFailed(rejected));
+        }
+        Ok(())
+    }
+}
+
+// Request the ids of the unspent outputs that are owned by the addresses through the address unlock condition or as
+// state controller or governor of an alias output
+async fn request_unspent_output_ids(client: &Client, bech32_addresses: &[String]) -> crate::Result<HashSet<OutputId>> {
+    let mut output_ids = HashSet::new();
+    for bech32_address in bech32_addresses {
+        output_ids.extend(
+            client
+                .basic_output_ids(vec![QueryParameter::Address(bech32_address.clone())])
+                .await?
+                .items,
+        );
+        output_ids.extend(
+            client
+                .nft_output_ids(vec![QueryParameter::Address(bech32_address.clone())])
+                .await?
+                .items,
+        );
+        output_ids.extend(
+            client
+                .alias_output_ids(vec![QueryParameter::StateController(bech32_address.clone())])
+                .await?
+                .items,
+        );
+        output_ids.extend(
+            client
+                .alias_output_ids(vec![QueryParameter::Governor(bech32_address.clone())])
+                .await?
+                .items,
+        );
+    }
+    Ok(output_ids)
+}
+
+// Returns the outputs that weren't reported by all nodes
+fn find_discrepancies(reported_output_ids: &[(String, HashSet<OutputId>)], quorum: usize) -> Vec<SyncDiscrepancy> {
+    let all_output_ids: HashSet<OutputId> = reported_output_ids
+        .iter()
+        .flat_map(|(_, output_ids)| output_ids.iter().copied())
+        .collect();
+
+    let mut discrepancies = Vec::new();
+    for output_id in all_output_ids {
+        let (reported_by, missing_from): (Vec<_>, Vec<_>) = reported_output_ids
+            .iter()
+            .partition(|(_, output_ids)| output_ids.contains(&output_id));
+        if missing_from.is_empty() {
+            continue;
+        }
+        discrepancies.push(SyncDiscrepancy {
+            output_id,
+            accepted: reported_by.len() >= quorum,
+            reported_by: reported_by.into_iter().map(|(node_url, _)| node_url.clone()).collect(),
+            missing_from: missing_from.into_iter().map(|(node_url, _)| node_url.clone()).collect(),
+        });
+    }
+    discrepancies
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn discrepancies_with_quorum() {
+        let output_id_0 =
+            OutputId::from_str("0x52fdfc072182654f163f5f0f9a621d729566c74d10037c4d7bbb0407d1e2c6490000").unwrap();
+        let output_id_1 =
+            OutputId::from_str("0x52fdfc072182654f163f5f0f9a621d729566c74d10037c4d7bbb0407d1e2c6490100").unwrap();
+        let reported_output_ids = vec![
+            ("node_0".to_string(), HashSet::from([output_id_0, output_id_1])),
+            ("node_1".to_string(), HashSet::from([output_id_0])),
+            ("node_2".to_string(), HashSet::from([output_id_0])),
+        ];
+
+        let discrepancies = find_discrepancies(&reported_output_ids, 2);
+        assert_eq!(
+            discrepancies,
+            vec![SyncDiscrepancy {
+                output_id: output_id_1,
+                reported_by: vec!["node_0".to_string()],
+                missing_from: vec!["node_1".to_string(), "node_2".to_string()],
+                accepted: false,
+            }]
+        );
+
+        let discrepancies = find_discrepancies(&reported_output_ids, 1);
+        assert!(discrepancies[0].accepted);
+        assert!(find_discrepancies(&reported_output_ids[1..], 2).is_empty());
+    }
+}
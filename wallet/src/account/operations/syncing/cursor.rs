@@ -0,0 +1,129 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use iota_client::{block::address::Address, NodeInfoWrapper};
+
+use crate::{
+    account::{constants::ADDRESS_SYNC_CURSOR_OVERLAP, handle::AccountHandle, operations::syncing::SyncOptions},
+    storage::sync_cursor::{AddressSyncCursors, SyncCursor},
+};
+
+impl AccountHandle {
+    /// Returns the stored state of the last completed sync, `None` if the account wasn't synced yet.
+    pub async fn sync_cursor(&self) -> crate::Result<Option<SyncCursor>> {
+        let account_index = *self.read().await.index();
+        self.storage_manager.lock().await.get_sync_cursor(account_index).await
+    }
+
+    // Store the state after a completed sync, so it's available after a restart
+    pub(crate) async fn store_sync_cursor(
+        &self,
+        options: &SyncOptions,
+        node_info: &NodeInfoWrapper,
+        synced_at: u128,
+    ) -> crate::Result<()> {
+        let status = &node_info.node_info.status;
+
+        let account = self.read().await;
+        let account_index = account.index;
+        let address_indexes = options.addresses.is_empty().then(|| {
+            (
+                account
+                    .public_addresses
+                    .iter()
+                    .map(|a| a.key_index)
+                    .max()
+                    .unwrap_or_default(),
+                account
+                    .internal_addresses
+                    .iter()
+                    .map(|a| a.key_index)
+                    .max()
+                    .unwrap_or_default(),
+            )
+        });
+        drop(account);
+
+        let mut storage_manager = self.storage_manager.lock().await;
+        let previous_sync_cursor = storage_manager
+            .get_sync_cursor(account_index)
+            .await?
+            .unwrap_or_default();
+        // If only specific addresses were synced, the address indexes from the previous sync stay the same
+        let (address_index, internal_address_index) = address_indexes.unwrap_or((
+            previous_sync_cursor.address_index,
+            previous_sync_cursor.internal_address_index,
+        ));
+
+        storage_manager
+            .set_sync_cursor(
+                account_index,
+                &SyncCursor {
+                    milestone_index: status.latest_milestone.index,
+                    milestone_timestamp: status.latest_milestone.timestamp,
+                    ledger_index: status.confirmed_milestone.index,
+                    address_index,
+                    internal_address_index,
+                    synced_at: synced_at as u64,
+                    node_url: Some(node_info.url.clone()),
+                },
+            )
+            .await
+    }
+    // Get the milestone timestamps by bech32 address after which outputs need to be requested, empty if all outputs
+    // need to be requested
+    pub(crate) async fn get_address_sync_cursors(&self, options: &SyncOptions) -> crate::Result<HashMap<String, u32>> {
+        if options.full_sync {
+            return Ok(HashMap::new());
+        }
+        let account_index = *self.read().await.index();
+        let address_sync_cursors = self
+            .storage_manager
+            .lock()
+            .await
+            .get_address_sync_cursors(account_index)
+            .await?;
+
+        Ok(match address_sync_cursors {
+            // Cursors from syncs with other options are useless, because other outputs were requested
+            Some(address_sync_cursors) if address_sync_cursors.sync_options.syncs_same_outputs(options) => {
+                address_sync_cursors.cursors
+            }
+            _ => HashMap::new(),
+        })
+    }
+
+    // Store the milestone timestamp from before the outputs were requested as cursor for the synced addresses
+    pub(crate) async fn store_address_sync_cursors(
+        &self,
+        options: &SyncOptions,
+        synced_addresses: Vec<Address>,
+        milestone_timestamp: u32,
+    ) -> crate::Result<()> {
+        let bech32_hrp = self.client.get_bech32_hrp().await?;
+        let account_index = *self.read().await.index();
+        let cursor = milestone_timestamp.saturating_sub(ADDRESS_SYNC_CURSOR_OVERLAP);
+
+        let mut storage_manager = self.storage_manager.lock().await;
+        let mut address_sync_cursors = match storage_manager.get_address_sync_cursors(account_index).await? {
+            Some(address_sync_cursors) if address_sync_cursors.sync_options.syncs_same_outputs(options) => {
+                address_sync_cursors
+            }
+            _ => AddressSyncCursors {
+                sync_options: options.clone(),
+                cursors: HashMap::new(),
+            },
+        };
+        for address in synced_addresses {
+            address_sync_cursors
+                .cursors
+                .insert(address.to_bech32(bech32_hrp.clone()), cursor);
+        }
+
+        storage_manager
+            .set_address_sync_cursors(account_index, &address_sync_cursors)
+            .await
+    }
+}
@@ -0,0 +1,65 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_client::block::output::dto::NativeTokenDto;
+
+use crate::{
+    account::handle::AccountHandle,
+    events::types::{DepositReceivedEvent, WalletEvent},
+};
+
+impl AccountHandle {
+    // Emits a DepositReceived event for every unconfirmed deposit whose confirmations changed since the last sync.
+    // Deposits are tracked until they have `deposit_confirmations` or got spent.
+    pub(crate) async fn update_unconfirmed_deposits(
+        &self,
+        confirmed_milestone_index: u32,
+        deposit_confirmations: u32,
+    ) -> crate::Result<()> {
+        let mut account = self.write().await;
+        if account.unconfirmed_deposits.is_empty() {
+            return Ok(());
+        }
+        let bech32_hrp = self.client.get_bech32_hrp().await?;
+
+        let mut deposit_events = Vec::new();
+        for (output_id, reported_confirmations) in std::mem::take(&mut account.unconfirmed_deposits) {
+            // Spent before it was confirmed
+            let Some(output_data) = account.unspent_outputs.get(&output_id) else {
+                continue;
+            };
+            let confirmations = confirmed_milestone_index.saturating_sub(output_data.metadata.milestone_index_booked);
+            let confirmed = confirmations >= deposit_confirmations;
+            if reported_confirmations != Some(confirmations) {
+                deposit_events.push(DepositReceivedEvent {
+                    output_id,
+                    address: output_data.address.to_bech32(&bech32_hrp),
+                    amount: output_data.output.amount(),
+                    native_tokens: output_data
+                        .output
+                        .native_tokens()
+                        .map(|native_tokens| native_tokens.iter().map(NativeTokenDto::from).collect())
+                        .unwrap_or_default(),
+                    milestone_index_booked: output_data.metadata.milestone_index_booked,
+                    confirmations,
+                    confirmed,
+                });
+            }
+            if !confirmed {
+                account.unconfirmed_deposits.insert(output_id, Some(confirmations));
+            }
+        }
+        deposit_events.sort_by_key(|event| event.milestone_index_booked);
+
+        #[cfg(feature = "storage")]
+        self.save(Some(&account)).await?;
+        let account_index = account.index;
+        drop(account);
+
+        let event_emitter = self.event_emitter.lock().await;
+        for deposit_event in deposit_events {
+            event_emitter.emit(account_index, WalletEvent::DepositReceived(Box::new(deposit_event)));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,116 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use instant::SystemTime;
+use iota_client::NodeInfoWrapper;
+use serde::{Deserialize, Serialize};
+
+use crate::account::handle::AccountHandle;
+
+/// How up to date the data of an account is, from the last completed sync.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncFreshness {
+    /// Unix timestamp in milliseconds when the last sync finished, `None` if the account wasn't synced yet.
+    pub last_synced_at: Option<u64>,
+    /// The url of the node the synced data is from.
+    pub node_url: Option<String>,
+    /// The confirmed milestone index of the node, the ledger state the synced outputs reflect.
+    pub milestone_index: Option<u32>,
+    /// The timestamp of the confirmed milestone.
+    pub milestone_timestamp: Option<u32>,
+}
+
+impl SyncFreshness {
+    pub(crate) fn new(node_info: &NodeInfoWrapper, synced_at: u64) -> Self {
+        Self {
+            last_synced_at: Some(synced_at),
+            node_url: Some(node_info.url.clone()),
+            milestone_index: Some(node_info.node_info.status.confirmed_milestone.index),
+            milestone_timestamp: node_info.node_info.status.confirmed_milestone.timestamp,
+        }
+    }
+
+    /// Returns true if the last sync finished longer ago than the threshold, or if there was no sync yet.
+    pub fn is_stale(&self, threshold: Duration) -> bool {
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .expect("time went backwards")
+            .as_millis() as u64;
+        self.last_synced_at.map_or(true, |last_synced_at| {
+            now.saturating_sub(last_synced_at) > threshold.as_millis() as u64
+        })
+    }
+
+    // Keep the freshness of the data that was synced first, for balances that are added together
+    pub(crate) fn oldest(self, other: Self) -> Self {
+        match (self.last_synced_at, other.last_synced_at) {
+            (Some(last_synced_at), Some(other_last_synced_at)) if last_synced_at <= other_last_synced_at => self,
+            (Some(_), Some(_)) | (Some(_), None) => other,
+            (None, _) => self,
+        }
+    }
+}
+
+impl AccountHandle {
+    /// Returns when the account was synced the last time, from which node and which ledger state the data reflects.
+    /// With the `storage` feature the data of the last sync before a restart is returned until the account is synced
+    /// again.
+    pub async fn sync_freshness(&self) -> crate::Result<SyncFreshness> {
+        let mut sync_freshness = self.sync_freshness.lock().await;
+        #[cfg(feature = "storage")]
+        if sync_freshness.last_synced_at.is_none() {
+            let account_index = *self.read().await.index();
+            if let Some(sync_cursor) = self.storage_manager.lock().await.get_sync_cursor(account_index).await? {
+                *sync_freshness = SyncFreshness {
+                    last_synced_at: Some(sync_cursor.synced_at),
+                    node_url: sync_cursor.node_url,
+                    milestone_index: Some(sync_cursor.ledger_index),
+                    milestone_timestamp: sync_cursor.milestone_timestamp,
+                };
+            }
+        }
+        Ok(sync_freshness.clone())
+    }
+
+    /// Returns true if the last sync of the account finished longer ago than the threshold, or if it wasn't synced yet.
+    pub async fn is_stale(&self, threshold: Duration) -> crate::Result<bool> {
+        Ok(self.sync_freshness().await?.is_stale(threshold))
+    }
+
+    // Record the state of a completed sync
+    pub(crate) async fn set_sync_freshness(&self, node_info: &NodeInfoWrapper, synced_at: u128) {
+        *self.sync_freshness.lock().await = SyncFreshness::new(node_info, synced_at as u64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stale_and_oldest() {
+        let never_synced = SyncFreshness::default();
+        assert!(never_synced.is_stale(Duration::from_secs(60)));
+
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        let synced = SyncFreshness {
+            last_synced_at: Some(now),
+            ..Default::default()
+        };
+        assert!(!synced.is_stale(Duration::from_secs(60)));
+
+        let synced_before = SyncFreshness {
+            last_synced_at: Some(now - 120_000),
+            ..Default::default()
+        };
+        assert!(synced_before.is_stale(Duration::from_secs(60)));
+        assert_eq!(synced.clone().oldest(synced_before.clone()), synced_before);
+        assert_eq!(synced.oldest(never_synced.clone()), never_synced);
+    }
+}
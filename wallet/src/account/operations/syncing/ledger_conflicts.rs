@@ -0,0 +1,161 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{HashMap, HashSet};
+
+use iota_client::block::{input::Input, output::OutputId, payload::transaction::TransactionEssence};
+
+use crate::account::{
+    handle::AccountHandle,
+    operations::syncing::{cancellation::CancellationToken, SyncOptions},
+    types::{InclusionState, LedgerConflict},
+};
+#[cfg(feature = "events")]
+use crate::events::types::{LedgerConflictEvent, WalletEvent};
+
+impl AccountHandle {
+    // Sync all outputs again without the address cursors and check the recorded spends of the account, when ledger
+    // conflicts were found. With the `events` feature a `LedgerConflict` event is emitted with all found conflicts.
+    pub(crate) async fn reconcile_ledger_conflicts(
+        &self,
+        options: &SyncOptions,
+        cancellation: &CancellationToken,
+        mut ledger_conflicts: Vec<LedgerConflict>,
+    ) -> crate::Result<()> {
+        if ledger_conflicts.is_empty() {
+            return Ok(());
+        }
+        log::warn!("[SYNC] ledger conflicts {ledger_conflicts:?}, reconciling the account");
+
+        let reconciliation = self.reconcile_ledger(options, cancellation).await;
+        if let Ok(found_ledger_conflicts) = &reconciliation {
+            for ledger_conflict in found_ledger_conflicts {
+                if !ledger_conflicts.contains(ledger_conflict) {
+                    ledger_conflicts.push(ledger_conflict.clone());
+                }
+            }
+        }
+        log::debug!("[SYNC] ledger conflicts after the reconciliation {ledger_conflicts:?}");
+
+        #[cfg(feature = "events")]
+        {
+            let account_index = *self.read().await.index();
+            self.event_emitter.lock().await.emit(
+                account_index,
+                WalletEvent::LedgerConflict(LedgerConflictEvent {
+                    conflicts: ledger_conflicts,
+                    reconciled: reconciliation.is_ok(),
+                }),
+            );
+        }
+
+        reconciliation.map(|_| ())
+    }
+
+    async fn reconcile_ledger(
+        &self,
+        options: &SyncOptions,
+        cancellation: &CancellationToken,
+    ) -> crate::Result<Vec<LedgerConflict>> {
+        let full_sync_options = SyncOptions {
+            full_sync: true,
+            ..options.clone()
+        };
+        let mut ledger_conflicts = self.sync_internal(&full_sync_options, cancellation).await?;
+        cancellation.check()?;
+        ledger_conflicts.extend(self.verify_recorded_spends().await?);
+        Ok(ledger_conflicts)
+    }
+
+    // Compare the spent outputs of the account that can't be pruned yet with the ledger and update their metadata
+    async fn verify_recorded_spends(&self) -> crate::Result<Vec<LedgerConflict>> {
+        let network_id = self.client.get_network_id().await?;
+        let pruning_index = self.client.get_info().await?.node_info.status.pruning_index;
+
+        let account = self.read().await;
+        // The transaction that spent the output, from the output metadata or the confirmed transactions of the account
+        let mut recorded_spends: HashMap<OutputId, Option<String>> = account
+            .outputs
+            .values()
+            .filter(|output_data| {
+                output_data.is_spent
+                    && output_data.network_id == network_id
+                    && output_data.metadata.milestone_index_booked > pruning_index
+            })
+            .map(|output_data| (output_data.output_id, output_data.metadata.transaction_id_spent.clone()))
+            .collect();
+        for transaction in account
+            .transactions
+            .values()
+            .filter(|transaction| transaction.inclusion_state == InclusionState::Confirmed)
+        {
+            let TransactionEssence::Regular(essence) = transaction.payload.essence();
+            for input in essence.inputs() {
+                if let Input::Utxo(input) = input {
+                    if let Some(spent_by @ None) = recorded_spends.get_mut(input.output_id()) {
+                        *spent_by = Some(transaction.transaction_id.to_string());
+                    }
+                }
+            }
+        }
+        drop(account);
+        if recorded_spends.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let output_metadata_responses = self
+            .client
+            .try_get_outputs_metadata(recorded_spends.keys().copied().collect())
+            .await?;
+
+        let mut ledger_conflicts = Vec::new();
+        let mut known_output_ids = HashSet::new();
+        let mut account = self.write().await;
+        for output_metadata_response in output_metadata_responses {
+            let output_id = output_metadata_response.output_id()?;
+            known_output_ids.insert(output_id);
+            let recorded_transaction_id = recorded_spends.get(&output_id).cloned().flatten();
+
+            if !output_metadata_response.is_spent {
+                ledger_conflicts.push(LedgerConflict::SpendReverted { output_id });
+            } else if let Some(recorded_transaction_id) = recorded_transaction_id {
+                if Some(&recorded_transaction_id) == output_metadata_response.transaction_id_spent.as_ref() {
+                    continue;
+                }
+                ledger_conflicts.push(LedgerConflict::SpentByOtherTransaction {
+                    output_id,
+                    recorded_transaction_id,
+                    transaction_id: output_metadata_response
+                        .transaction_id_spent
+                        .clone()
+                        .unwrap_or_default(),
+                });
+            } else {
+                continue;
+            }
+
+            let unspent_output_data = account.outputs.get_mut(&output_id).and_then(|output_data| {
+                output_data.is_spent = output_metadata_response.is_spent;
+                output_data.metadata = output_metadata_response;
+                (!output_data.is_spent).then(|| output_data.clone())
+            });
+            if let Some(output_data) = unspent_output_data {
+                account.unspent_outputs.insert(output_id, output_data);
+            }
+        }
+        ledger_conflicts.extend(
+            recorded_spends
+                .into_keys()
+                .filter(|output_id| !known_output_ids.contains(output_id))
+                .map(|output_id| LedgerConflict::OutputMissing { output_id }),
+        );
+
+        #[cfg(feature = "storage")]
+        if !ledger_conflicts.is_empty() {
+            log::debug!("[SYNC] storing account {} with reconciled outputs", account.alias());
+            self.save(Some(&account)).await?;
+        }
+
+        Ok(ledger_conflicts)
+    }
+}
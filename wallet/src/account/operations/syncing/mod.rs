@@ -2,9 +2,21 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub(crate) mod addresses;
+pub(crate) mod cancellation;
+pub(crate) mod cross_verification;
+#[cfg(feature = "storage")]
+pub(crate) mod cursor;
+#[cfg(feature = "events")]
+pub(crate) mod deposits;
 pub(crate) mod foundries;
+pub(crate) mod freshness;
+pub(crate) mod ledger_conflicts;
 pub(crate) mod options;
 pub(crate) mod outputs;
+pub(crate) mod preview;
+#[cfg(feature = "events")]
+pub(crate) mod progress;
+pub(crate) mod request_limiter;
 pub(crate) mod transactions;
 
 use std::collections::{HashMap, HashSet};
@@ -16,18 +28,53 @@ use iota_client::block::{
 };
 
 pub use self::options::SyncOptions;
+use self::{cancellation::CancellationToken, options::SyncScope};
 use crate::account::{
     constants::MIN_SYNC_INTERVAL,
     handle::AccountHandle,
-    types::{AddressWithUnspentOutputs, OutputData},
+    types::{AddressWithUnspentOutputs, LedgerConflict, OutputData},
     AccountBalance,
 };
+#[cfg(feature = "events")]
+use crate::events::types::{AlertCode, SyncProgressEvent, SyncStep};
 
 impl AccountHandle {
     /// Sync the account by fetching new information from the nodes. Will also retry pending transactions
     /// if necessary.
     pub async fn sync(&self, options: Option<SyncOptions>) -> crate::Result<AccountBalance> {
-        let options = options.unwrap_or_default();
+        self.sync_with_cancellation(options, CancellationToken::new()).await
+    }
+
+    /// Sync the account like [`AccountHandle::sync()`], but stop with [`Error::Cancelled`](crate::Error::Cancelled)
+    /// once the token is cancelled. Requests run until the next check, outputs are only written to the account if the
+    /// token wasn't cancelled before, so the stored account stays consistent.
+    pub async fn sync_with_cancellation(
+        &self,
+        options: Option<SyncOptions>,
+        cancellation: CancellationToken,
+    ) -> crate::Result<AccountBalance> {
+        let result = self.sync_with_options(options, cancellation).await;
+        #[cfg(feature = "events")]
+        if let Err(err) = &result {
+            if !matches!(err, crate::Error::Cancelled) {
+                // Distinguish a node that can't be reached from other failures, so apps can alert accordingly
+                let code = match self.client.get_info().await {
+                    Ok(_) => AlertCode::SyncFailed,
+                    Err(_) => AlertCode::NodeUnreachable,
+                };
+                let account_index = self.read().await.index;
+                self.emit_alert(account_index, code, err.to_string()).await;
+            }
+        }
+        result
+    }
+
+    async fn sync_with_options(
+        &self,
+        options: Option<SyncOptions>,
+        cancellation: CancellationToken,
+    ) -> crate::Result<AccountBalance> {
+        let options = options.unwrap_or_default().with_scope_applied();
         log::debug!("[SYNC] start syncing with {:?}", options);
         let syc_start_time = Instant::now();
 
@@ -48,41 +95,118 @@ impl AccountHandle {
             return self.balance().await;
         }
 
-        self.sync_internal(&options).await?;
+        if options.scope != SyncScope::PendingTransactions {
+            let ledger_conflicts = self.sync_internal(&options, &cancellation).await?;
+            self.reconcile_ledger_conflicts(&options, &cancellation, ledger_conflicts)
+                .await?;
+        }
 
         // Sync transactions after updating account with outputs, so we can use them to check the transaction
         // status
         if options.sync_pending_transactions {
-            let confirmed_tx_with_unknown_output = self.sync_pending_transactions().await?;
+            cancellation.check()?;
+            #[cfg(feature = "events")]
+            self.update_sync_progress(|sync_progress| sync_progress.step = SyncStep::SyncingTransactions)
+                .await;
+            let confirmed_tx_with_unknown_output = self.sync_pending_transactions(&options.reattachment).await?;
             // Sync again if we don't know the output yet, to prevent having no unspent outputs after syncing
             if confirmed_tx_with_unknown_output {
                 log::debug!("[SYNC] a transaction for which no output is known got confirmed, syncing outputs again");
-                self.sync_internal(&options).await?;
+                let ledger_conflicts = self.sync_internal(&options, &cancellation).await?;
+                self.reconcile_ledger_conflicts(&options, &cancellation, ledger_conflicts)
+                    .await?;
             }
         };
 
-        let account_balance = self.balance().await?;
-        // Update last_synced mutex
         let time_now = SystemTime::now()
             .duration_since(SystemTime::UNIX_EPOCH)
             .expect("time went backwards")
             .as_millis();
+        // The freshness and the cursor describe the state of the synced outputs
+        if options.scope != SyncScope::PendingTransactions {
+            if let Some(cross_verification) = &options.cross_verification {
+                self.cross_verify_outputs(&options, cross_verification, &cancellation)
+                    .await?;
+            }
+            let node_info = self.client.get_info().await?;
+            #[cfg(feature = "events")]
+            self.update_unconfirmed_deposits(
+                node_info.node_info.status.confirmed_milestone.index,
+                options.deposit_confirmations,
+            )
+            .await?;
+            self.set_sync_freshness(&node_info, time_now).await;
+            #[cfg(feature = "storage")]
+            self.store_sync_cursor(&options, &node_info, time_now).await?;
+        }
+        // Before the balance, so it includes the updated staking rewards
+        #[cfg(feature = "participation")]
+        if options.sync_participation && options.scope != SyncScope::PendingTransactions {
+            self.update_participation_state().await?;
+        }
+        let account_balance = self.balance().await?;
+        // Update last_synced mutex
         *last_synced = time_now;
+        #[cfg(feature = "storage")]
+        {
+            self.store_balance_snapshot(&account_balance, time_now).await?;
+            self.store_balance_summary(&account_balance).await?;
+        }
+        #[cfg(feature = "events")]
+        self.update_sync_progress(|sync_progress| sync_progress.step = SyncStep::Finished)
+            .await;
         log::debug!("[SYNC] finished syncing in {:.2?}", syc_start_time.elapsed());
         Ok(account_balance)
     }
 
-    async fn sync_internal(&self, options: &SyncOptions) -> crate::Result<()> {
+    // Returns the ledger conflicts that were found while updating the account
+    async fn sync_internal(
+        &self,
+        options: &SyncOptions,
+        cancellation: &CancellationToken,
+    ) -> crate::Result<Vec<LedgerConflict>> {
         log::debug!("[SYNC] sync_internal");
+        cancellation.check()?;
+
+        // Get the milestone timestamp before requesting outputs, so outputs created during the sync are requested
+        // again with the next sync
+        #[cfg(feature = "storage")]
+        let milestone_timestamp = self
+            .client
+            .get_info()
+            .await?
+            .node_info
+            .status
+            .confirmed_milestone
+            .timestamp;
+        #[cfg(feature = "storage")]
+        let address_cursors = self.get_address_sync_cursors(options).await?;
+        #[cfg(not(feature = "storage"))]
+        let address_cursors = HashMap::new();
 
         let addresses_to_sync = self.get_addresses_to_sync(options).await?;
         log::debug!("[SYNC] addresses_to_sync {}", addresses_to_sync.len());
+        #[cfg(feature = "events")]
+        {
+            let addresses_total = addresses_to_sync.len();
+            self.update_sync_progress(|sync_progress| {
+                *sync_progress = SyncProgressEvent {
+                    addresses_total,
+                    ..Default::default()
+                }
+            })
+            .await;
+        }
+        #[cfg(feature = "storage")]
+        let synced_addresses: Vec<Address> = addresses_to_sync.iter().map(|address| address.address.inner).collect();
 
         let (spent_or_not_synced_output_ids, addresses_with_unspent_outputs, outputs_data): (
             Vec<OutputId>,
             Vec<AddressWithUnspentOutputs>,
             Vec<OutputData>,
-        ) = self.request_outputs_recursively(addresses_to_sync, options).await?;
+        ) = self
+            .request_outputs_recursively(addresses_to_sync, options, &address_cursors, cancellation)
+            .await?;
 
         // Request possible spent outputs
         log::debug!("[SYNC] spent_or_not_synced_outputs: {spent_or_not_synced_output_ids:?}");
@@ -124,14 +248,44 @@ impl AccountHandle {
             self.request_and_store_foundry_outputs(native_token_foundry_ids).await?;
         }
 
+        #[cfg(feature = "storage")]
+        let synced_addresses = synced_addresses
+            .into_iter()
+            .chain(
+                outputs_data
+                    .iter()
+                    .filter_map(|output_data| alias_or_nft_address(&output_data.output_id, &output_data.output)),
+            )
+            .collect();
+
+        #[cfg(feature = "events")]
+        let outputs_processed = outputs_data.len();
+        #[cfg(feature = "events")]
+        self.update_sync_progress(|sync_progress| sync_progress.step = SyncStep::ProcessingOutputs)
+            .await;
+
+        // Last point to stop, after it the account gets updated
+        cancellation.check()?;
         // Updates account with balances, output ids, outputs
-        self.update_account(
-            addresses_with_unspent_outputs,
-            outputs_data,
-            spent_or_unsynced_output_metadata_map,
-            options,
-        )
-        .await
+        let ledger_conflicts = self
+            .update_account(
+                addresses_with_unspent_outputs,
+                outputs_data,
+                spent_or_unsynced_output_metadata_map,
+                options,
+            )
+            .await?;
+        #[cfg(feature = "events")]
+        self.update_sync_progress(|sync_progress| sync_progress.outputs_processed = outputs_processed)
+            .await;
+
+        #[cfg(feature = "storage")]
+        if let Some(milestone_timestamp) = milestone_timestamp {
+            self.store_address_sync_cursors(options, synced_addresses, milestone_timestamp)
+                .await?;
+        }
+
+        Ok(ledger_conflicts)
     }
 
     // First request all outputs directly related to the ed25519 addresses, then for each nft and alias output we got,
@@ -141,6 +295,8 @@ impl AccountHandle {
         &self,
         addresses_to_sync: Vec<AddressWithUnspentOutputs>,
         options: &SyncOptions,
+        address_cursors: &HashMap<String, u32>,
+        cancellation: &CancellationToken,
     ) -> crate::Result<(Vec<OutputId>, Vec<AddressWithUnspentOutputs>, Vec<OutputData>)> {
         // Cache the alias and nft address with the related ed2559 address, so we can update the account address with
         // the new output ids
@@ -152,12 +308,12 @@ impl AccountHandle {
             let new_outputs_data = if new_alias_and_nft_addresses.is_empty() {
                 // Get outputs for addresses and add them also the the addresses_with_unspent_outputs
                 let (addresses_with_output_ids, spent_or_not_synced_output_ids_inner) = self
-                    .get_output_ids_for_addresses(options, addresses_to_sync.clone())
+                    .get_output_ids_for_addresses(options, addresses_to_sync.clone(), address_cursors, cancellation)
                     .await?;
                 spent_or_not_synced_output_ids = spent_or_not_synced_output_ids_inner;
                 // Get outputs for addresses and add them also the the addresses_with_unspent_outputs
                 let (addresses_with_unspent_outputs_inner, outputs_data_inner) = self
-                    .get_outputs_from_address_output_ids(addresses_with_output_ids)
+                    .get_outputs_from_address_output_ids(addresses_with_output_ids, cancellation)
                     .await?;
                 addresses_with_unspent_outputs = addresses_with_unspent_outputs_inner;
                 outputs_data.extend(outputs_data_inner.clone().into_iter());
@@ -166,8 +322,7 @@ impl AccountHandle {
                 let bech32_hrp = self.client().get_bech32_hrp().await?;
                 let mut new_outputs_data = Vec::new();
                 for (alias_or_nft_address, ed25519_address) in new_alias_and_nft_addresses {
-                    let output_ids = self.get_output_ids_for_address(alias_or_nft_address, options).await?;
-
+                    cancellation.check()?;
                     // Update address with unspent outputs
                     let address_with_unspent_outputs = addresses_with_unspent_outputs
                         .iter_mut()
@@ -175,6 +330,19 @@ impl AccountHandle {
                         .ok_or_else(|| {
                             crate::Error::AddressNotFoundInAccount(ed25519_address.to_bech32(bech32_hrp.clone()))
                         })?;
+
+                    // Only request new outputs if the known outputs of the ed25519 address were kept
+                    let created_after = address_cursors
+                        .get(&ed25519_address.to_bech32(bech32_hrp.clone()))
+                        .and(address_cursors.get(&alias_or_nft_address.to_bech32(bech32_hrp.clone())))
+                        .copied();
+                    let output_ids: Vec<OutputId> = self
+                        .get_output_ids_for_address(alias_or_nft_address, options, created_after)
+                        .await?
+                        .into_iter()
+                        // Known outputs are already part of the address
+                        .filter(|output_id| !address_with_unspent_outputs.output_ids.contains(output_id))
+                        .collect();
                     address_with_unspent_outputs.output_ids.extend(output_ids.clone());
 
                     let new_outputs_data_inner = self.get_outputs(output_ids).await?;
@@ -183,6 +351,17 @@ impl AccountHandle {
                         .output_response_to_output_data(new_outputs_data_inner, address_with_unspent_outputs)
                         .await?;
 
+                    #[cfg(feature = "events")]
+                    {
+                        let outputs_fetched = outputs_data_inner.len();
+                        self.update_sync_progress(|sync_progress| {
+                            sync_progress.addresses_total += 1;
+                            sync_progress.addresses_scanned += 1;
+                            sync_progress.outputs_total += outputs_fetched;
+                            sync_progress.outputs_fetched += outputs_fetched;
+                        })
+                        .await;
+                    }
                     outputs_data.extend(outputs_data_inner.clone().into_iter());
                     new_outputs_data.extend(outputs_data_inner);
                 }
@@ -228,3 +407,14 @@ impl AccountHandle {
         ))
     }
 }
+
+// Get the alias or nft address of an alias or nft output
+fn alias_or_nft_address(output_id: &OutputId, output: &Output) -> Option<Address> {
+    match output {
+        Output::Alias(alias_output) => Some(Address::Alias(AliasAddress::from(
+            alias_output.alias_id_non_null(output_id),
+        ))),
+        Output::Nft(nft_output) => Some(Address::Nft(NftAddress::from(nft_output.nft_id_non_null(output_id)))),
+        _ => None,
+    }
+}
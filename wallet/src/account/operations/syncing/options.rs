@@ -3,12 +3,17 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::cross_verification::CrossVerificationOptions;
+
 const DEFAULT_ADDRESS_START_INDEX: u32 = 0;
 const DEFAULT_FORCE_SYNCING: bool = false;
+const DEFAULT_FULL_SYNC: bool = false;
 const DEFAULT_SYNC_INCOMING_TRANSACTIONS: bool = false;
 const DEFAULT_SYNC_ONLY_MOST_BASIC_OUTPUTS: bool = false;
 const DEFAULT_SYNC_PENDING_TRANSACTIONS: bool = true;
 const DEFAULT_SYNC_NATIVE_TOKEN_FOUNDRIES: bool = false;
+const DEFAULT_DEPOSIT_CONFIRMATIONS: u32 = 0;
+const DEFAULT_REATTACH_AFTER_MILLISECONDS: u64 = 30000;
 
 /// The synchronization options
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
@@ -30,6 +35,11 @@ pub struct SyncOptions {
     /// When this to true, we will sync anyways, even if it's called 0ms after the las sync finished.
     #[serde(rename = "forceSyncing", default)]
     pub force_syncing: bool,
+    /// Addresses that were synced before only get the outputs requested that were created since the last sync, the
+    /// known outputs are checked for being spent. When this is set to true, all outputs of the addresses are requested
+    /// again.
+    #[serde(rename = "fullSync", default)]
+    pub full_sync: bool,
     /// Try to sync transactions from incoming outputs with their inputs. Some data may not be obtained if it has been
     /// pruned.
     #[serde(rename = "syncIncomingTransactions", default = "default_sync_incoming_transactions")]
@@ -37,6 +47,9 @@ pub struct SyncOptions {
     /// Checks pending transactions and promotes/reattaches them if necessary.
     #[serde(rename = "syncPendingTransactions", default = "default_sync_pending_transactions")]
     pub sync_pending_transactions: bool,
+    /// When the pending transactions are promoted or reattached and when they're given up.
+    #[serde(default)]
+    pub reattachment: ReattachmentOptions,
     /// Specifies what outputs should be synced for the ed25519 addresses from the account.
     #[serde(default)]
     pub account: AccountSyncOptions,
@@ -56,6 +69,23 @@ pub struct SyncOptions {
     /// Sync native token foundries, so their metadata can be returned in the balance.
     #[serde(rename = "syncNativeTokenFoundries", default = "default_sync_native_token_foundries")]
     pub sync_native_token_foundries: bool,
+    /// Restricts the sync to a subset of the outputs or to the pending transactions, will overwrite `account`,
+    /// `alias` and `nft` options.
+    #[serde(default)]
+    pub scope: SyncScope,
+    /// Verify the synced outputs with other nodes, the sync fails if the node of the client disagrees with the quorum.
+    #[serde(rename = "crossVerification", default)]
+    pub cross_verification: Option<CrossVerificationOptions>,
+    /// Amount of milestones after the one that confirmed a received output, until which a
+    /// [`DepositReceived`](crate::events::types::WalletEvent::DepositReceived) event is emitted for it after every
+    /// sync. Default is 0, so every deposit is reported once.
+    #[serde(rename = "depositConfirmations", default = "default_deposit_confirmations")]
+    pub deposit_confirmations: u32,
+    /// Update the participation state of the account after syncing, with the participation in the registered events.
+    #[cfg(feature = "participation")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "participation")))]
+    #[serde(rename = "syncParticipation", default)]
+    pub sync_participation: bool,
 }
 
 fn default_address_start_index() -> u32 {
@@ -66,6 +96,10 @@ fn default_force_syncing() -> bool {
     DEFAULT_FORCE_SYNCING
 }
 
+fn default_full_sync() -> bool {
+    DEFAULT_FULL_SYNC
+}
+
 fn default_sync_incoming_transactions() -> bool {
     DEFAULT_SYNC_INCOMING_TRANSACTIONS
 }
@@ -82,6 +116,10 @@ fn default_sync_native_token_foundries() -> bool {
     DEFAULT_SYNC_NATIVE_TOKEN_FOUNDRIES
 }
 
+fn default_deposit_confirmations() -> u32 {
+    DEFAULT_DEPOSIT_CONFIRMATIONS
+}
+
 impl Default for SyncOptions {
     fn default() -> Self {
         Self {
@@ -90,13 +128,68 @@ impl Default for SyncOptions {
             address_start_index_internal: default_address_start_index(),
             sync_incoming_transactions: default_sync_incoming_transactions(),
             sync_pending_transactions: default_sync_pending_transactions(),
+            reattachment: ReattachmentOptions::default(),
             account: AccountSyncOptions::default(),
             alias: AliasSyncOptions::default(),
             nft: NftSyncOptions::default(),
             sync_only_most_basic_outputs: default_sync_only_most_basic_outputs(),
             sync_native_token_foundries: default_sync_native_token_foundries(),
             force_syncing: default_force_syncing(),
+            full_sync: default_full_sync(),
+            scope: SyncScope::default(),
+            cross_verification: None,
+            deposit_confirmations: default_deposit_confirmations(),
+            #[cfg(feature = "participation")]
+            sync_participation: false,
+        }
+    }
+}
+
+impl SyncOptions {
+    // Overwrite the output options with the ones of the scope
+    pub(crate) fn with_scope_applied(mut self) -> Self {
+        match self.scope {
+            SyncScope::All | SyncScope::PendingTransactions => {}
+            SyncScope::BasicOutputs => {
+                self.account = AccountSyncOptions {
+                    basic_outputs: true,
+                    nft_outputs: false,
+                    alias_outputs: false,
+                };
+                self.alias = AliasSyncOptions {
+                    basic_outputs: false,
+                    nft_outputs: false,
+                    alias_outputs: false,
+                    foundry_outputs: false,
+                };
+                self.nft = NftSyncOptions::default();
+            }
+            SyncScope::NftOutputs => {
+                self.account = AccountSyncOptions {
+                    basic_outputs: false,
+                    nft_outputs: true,
+                    alias_outputs: false,
+                };
+                self.alias = AliasSyncOptions {
+                    basic_outputs: false,
+                    nft_outputs: false,
+                    alias_outputs: false,
+                    foundry_outputs: false,
+                };
+                self.nft = NftSyncOptions::default();
+                self.sync_only_most_basic_outputs = false;
+            }
         }
+        self
+    }
+
+    // Returns true if both options request the same output types, so outputs synced with one of them don't need to be
+    // requested again with the other one
+    pub(crate) fn syncs_same_outputs(&self, other: &Self) -> bool {
+        self.account == other.account
+            && self.alias == other.alias
+            && self.nft == other.nft
+            && self.sync_only_most_basic_outputs == other.sync_only_most_basic_outputs
     }
 }
 
@@ -149,3 +242,48 @@ pub struct NftSyncOptions {
     pub nft_outputs: bool,
     pub alias_outputs: bool,
 }
+
+/// Thresholds for promoting and reattaching the pending transactions while syncing
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ReattachmentOptions {
+    /// Time in milliseconds after the transaction was created or reattached the last time, before it gets reattached
+    /// if it's still not included.
+    pub reattach_after_milliseconds: u64,
+    /// Promote the block of a pending transaction if the node suggests it.
+    pub promote: bool,
+    /// Number of reattachments after which a transaction is given up, it gets the inclusion state `Expired` and its
+    /// unspent inputs are available again. Transactions are reattached until they're included if not set.
+    pub max_reattachments: Option<u32>,
+    /// Time in milliseconds after the transaction was created, after which a pending transaction is given up like
+    /// after `max_reattachments`. Transactions with their own maximum, set with
+    /// [`AccountHandle::set_transaction_max_pending()`](crate::account::handle::AccountHandle::set_transaction_max_pending),
+    /// use that instead.
+    pub max_pending_milliseconds: Option<u64>,
+}
+
+impl Default for ReattachmentOptions {
+    fn default() -> Self {
+        Self {
+            reattach_after_milliseconds: DEFAULT_REATTACH_AFTER_MILLISECONDS,
+            promote: true,
+            max_reattachments: None,
+            max_pending_milliseconds: None,
+        }
+    }
+}
+
+/// The part of an account that gets synced
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum SyncScope {
+    /// Sync the outputs selected by the other options and the pending transactions.
+    #[default]
+    All,
+    /// Only sync basic outputs of the account addresses, for base coin and native token balances.
+    BasicOutputs,
+    /// Only sync nft outputs of the account addresses.
+    NftOutputs,
+    /// Only check the pending transactions, outputs are only requested if a transaction got confirmed and its outputs
+    /// aren't known yet.
+    PendingTransactions,
+}
@@ -0,0 +1,165 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{HashMap, HashSet};
+
+use iota_client::{
+    api_types::core::response::OutputWithMetadataResponse,
+    block::{
+        address::Address,
+        output::{Output, OutputId, TokenId},
+    },
+};
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+
+use super::alias_or_nft_address;
+use crate::account::{
+    handle::AccountHandle,
+    operations::syncing::{cancellation::CancellationToken, SyncOptions},
+};
+
+/// The difference between the outputs the node knows for the account and the outputs stored in the account.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncPreview {
+    /// Unspent outputs that aren't known to the account yet.
+    pub new_outputs: Vec<OutputWithMetadataResponse>,
+    /// Outputs that are unspent in the account, but were spent or pruned on the node.
+    pub spent_output_ids: Vec<OutputId>,
+    /// The balance change a sync would apply.
+    pub balance_delta: BalanceDelta,
+}
+
+/// The amounts that a sync would add to and remove from the balance of the account.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceDelta {
+    /// Base coin amount of the new outputs.
+    pub base_coin_received: u64,
+    /// Base coin amount of the spent outputs.
+    pub base_coin_spent: u64,
+    /// Native token amounts of the new outputs.
+    pub native_tokens_received: HashMap<TokenId, U256>,
+    /// Native token amounts of the spent outputs.
+    pub native_tokens_spent: HashMap<TokenId, U256>,
+}
+
+impl AccountHandle {
+    /// Request the current outputs of the account addresses from the node and compare them with the outputs stored in
+    /// the account, without changing the account. All outputs of the addresses are requested, like with
+    /// [`SyncOptions::full_sync`], so the preview doesn't depend on the last sync.
+    pub async fn sync_preview(&self, options: Option<SyncOptions>) -> crate::Result<SyncPreview> {
+        let options = options.unwrap_or_default().with_scope_applied();
+        log::debug!("[SYNC] sync_preview with {options:?}");
+        let token_supply = self.client.get_token_supply().await?;
+
+        let addresses_to_sync = self.get_addresses_to_sync(&options).await?;
+        let synced_addresses: HashSet<Address> =
+            addresses_to_sync.iter().map(|address| address.address.inner).collect();
+        let (addresses_with_output_ids, _) = self
+            .get_output_ids_for_addresses(&options, addresses_to_sync, &HashMap::new(), &CancellationToken::new())
+            .await?;
+        let mut node_output_ids: HashSet<OutputId> = addresses_with_output_ids
+            .into_iter()
+            .flat_map(|address| address.output_ids)
+            .collect();
+
+        let account = self.read().await;
+        let known_outputs: HashMap<OutputId, Output> = account
+            .outputs
+            .iter()
+            .map(|(output_id, output_data)| (*output_id, output_data.output.clone()))
+            .collect();
+        let local_unspent_outputs: HashMap<OutputId, Output> = account
+            .unspent_outputs
+            .values()
+            .filter(|output_data| synced_addresses.contains(&output_data.address))
+            .map(|output_data| (output_data.output_id, output_data.output.clone()))
+            .collect();
+        drop(account);
+
+        let mut sync_preview = SyncPreview::default();
+        // Outputs can also be owned by the alias and nft outputs of the account, so their outputs are requested until
+        // no new alias or nft output is found
+        let mut checked_addresses = HashSet::new();
+        let mut output_ids_to_check: Vec<OutputId> = node_output_ids.iter().copied().collect();
+        while !output_ids_to_check.is_empty() {
+            let mut outputs: Vec<(OutputId, Output)> = Vec::new();
+            let mut unknown_output_ids = Vec::new();
+            for output_id in output_ids_to_check {
+                match known_outputs.get(&output_id) {
+                    Some(output) => outputs.push((output_id, output.clone())),
+                    None => unknown_output_ids.push(output_id),
+                }
+            }
+            if !unknown_output_ids.is_empty() {
+                for output_response in self.client.get_outputs(unknown_output_ids).await? {
+                    let output = Output::try_from_dto(&output_response.output, token_supply)?;
+                    add_amounts(
+                        &mut sync_preview.balance_delta.base_coin_received,
+                        &mut sync_preview.balance_delta.native_tokens_received,
+                        &output,
+                    );
+                    outputs.push((output_response.metadata.output_id()?, output));
+                    sync_preview.new_outputs.push(output_response);
+                }
+            }
+
+            output_ids_to_check = Vec::new();
+            for (output_id, output) in outputs {
+                if let Some(address) = alias_or_nft_address(&output_id, &output) {
+                    if checked_addresses.insert(address) {
+                        for owned_output_id in self.get_output_ids_for_address(address, &options, None).await? {
+                            if node_output_ids.insert(owned_output_id) {
+                                output_ids_to_check.push(owned_output_id);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // Outputs that the node didn't return could also be excluded by the sync options, so only the ones that are
+        // spent or pruned are reported
+        let missing_output_ids: Vec<OutputId> = local_unspent_outputs
+            .keys()
+            .filter(|output_id| !node_output_ids.contains(output_id))
+            .copied()
+            .collect();
+        let mut unspent_output_ids = HashSet::new();
+        if !missing_output_ids.is_empty() {
+            for output_metadata_response in self.client.try_get_outputs_metadata(missing_output_ids.clone()).await? {
+                if !output_metadata_response.is_spent {
+                    unspent_output_ids.insert(output_metadata_response.output_id()?);
+                }
+            }
+        }
+        for output_id in missing_output_ids {
+            if unspent_output_ids.contains(&output_id) {
+                continue;
+            }
+            if let Some(output) = local_unspent_outputs.get(&output_id) {
+                add_amounts(
+                    &mut sync_preview.balance_delta.base_coin_spent,
+                    &mut sync_preview.balance_delta.native_tokens_spent,
+                    output,
+                );
+            }
+            sync_preview.spent_output_ids.push(output_id);
+        }
+
+        log::debug!("[SYNC] sync preview {sync_preview:?}");
+        Ok(sync_preview)
+    }
+}
+
+// Add the base coin and native token amounts of the output
+fn add_amounts(base_coin: &mut u64, native_tokens: &mut HashMap<TokenId, U256>, output: &Output) {
+    *base_coin += output.amount();
+    if let Some(output_native_tokens) = output.native_tokens() {
+        for native_token in output_native_tokens.iter() {
+            *native_tokens.entry(*native_token.token_id()).or_default() += native_token.amount();
+        }
+    }
+}
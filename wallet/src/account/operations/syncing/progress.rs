@@ -0,0 +1,72 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    account::handle::AccountHandle,
+    events::types::{SyncProgressEvent, SyncStep, WalletEvent},
+};
+
+impl AccountHandle {
+    // Update the progress of the running sync and emit it. Must not be called while the account is locked.
+    pub(crate) async fn update_sync_progress(&self, update: impl FnOnce(&mut SyncProgressEvent) + Send) {
+        let account_index = *self.read().await.index();
+        let mut sync_progress = self.sync_progress.lock().await;
+        update(&mut sync_progress);
+        sync_progress.percent = progress_percent(&sync_progress);
+        self.event_emitter
+            .lock()
+            .await
+            .emit(account_index, WalletEvent::SyncProgress(*sync_progress));
+    }
+}
+
+// Estimate the progress, requesting the output ids and the outputs takes most of the time
+fn progress_percent(sync_progress: &SyncProgressEvent) -> u8 {
+    // Share of `range` percent that is done
+    let part = |done: usize, total: usize, range: usize| {
+        if total == 0 {
+            range
+        } else {
+            done.min(total) * range / total
+        }
+    };
+    let percent = match sync_progress.step {
+        SyncStep::ScanningAddresses => part(sync_progress.addresses_scanned, sync_progress.addresses_total, 40),
+        SyncStep::FetchingOutputs => 40 + part(sync_progress.outputs_fetched, sync_progress.outputs_total, 40),
+        SyncStep::ProcessingOutputs => 80 + part(sync_progress.outputs_processed, sync_progress.outputs_total, 10),
+        SyncStep::SyncingTransactions => 90,
+        SyncStep::Finished => 100,
+    };
+    percent as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::progress_percent;
+    use crate::events::types::{SyncProgressEvent, SyncStep};
+
+    #[test]
+    fn sync_progress_percent() {
+        let mut sync_progress = SyncProgressEvent {
+            addresses_total: 4,
+            ..Default::default()
+        };
+        assert_eq!(progress_percent(&sync_progress), 0);
+        sync_progress.addresses_scanned = 2;
+        assert_eq!(progress_percent(&sync_progress), 20);
+
+        sync_progress.step = SyncStep::FetchingOutputs;
+        sync_progress.addresses_scanned = 4;
+        sync_progress.outputs_total = 10;
+        sync_progress.outputs_fetched = 5;
+        assert_eq!(progress_percent(&sync_progress), 60);
+
+        // Without outputs the step is done
+        sync_progress.step = SyncStep::ProcessingOutputs;
+        sync_progress.outputs_total = 0;
+        assert_eq!(progress_percent(&sync_progress), 90);
+
+        sync_progress.step = SyncStep::Finished;
+        assert_eq!(progress_percent(&sync_progress), 100);
+    }
+}
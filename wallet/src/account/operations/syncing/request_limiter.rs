@@ -0,0 +1,184 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{sync::Mutex, time::Duration};
+
+use instant::Instant;
+use serde::{Deserialize, Serialize};
+
+use crate::account::constants::PARALLEL_REQUESTS_AMOUNT;
+
+/// How often a chunk of requests is sent again after the node was overloaded, before the sync fails
+const MAX_OVERLOADED_RETRIES: u32 = 5;
+/// The delay before a chunk of requests is sent again after the node was overloaded the first time, it doubles with
+/// every retry
+const OVERLOADED_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// Limits for the requests to the nodes while syncing. An account with its own client options has its own budget,
+/// all other accounts share the budget of the account manager client.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestLimits {
+    /// Maximum amount of addresses that are requested in parallel. The amount is halved when the node responds with
+    /// 429 or times out and grows again with successful requests.
+    pub max_parallel_requests: usize,
+    /// Maximum amount of address requests per second, unlimited if not set.
+    pub max_requests_per_second: Option<usize>,
+}
+
+impl Default for RequestLimits {
+    fn default() -> Self {
+        Self {
+            max_parallel_requests: PARALLEL_REQUESTS_AMOUNT,
+            max_requests_per_second: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RequestLimiterState {
+    limits: RequestLimits,
+    // The current amount of parallel requests, adapted to the node responses
+    parallel_requests: usize,
+    // Start and amount of requests of the current one second window for the budget
+    window_start: Instant,
+    window_requests: usize,
+    // Overloaded responses in a row
+    overloaded_retries: u32,
+}
+
+/// Adapts the amount of parallel requests during syncing to the node and keeps the request budget.
+#[derive(Debug)]
+pub(crate) struct RequestLimiter(Mutex<RequestLimiterState>);
+
+impl Default for RequestLimiter {
+    fn default() -> Self {
+        Self::new(RequestLimits::default())
+    }
+}
+
+impl RequestLimiter {
+    pub(crate) fn new(limits: RequestLimits) -> Self {
+        Self(Mutex::new(RequestLimiterState {
+            limits,
+            parallel_requests: limits.max_parallel_requests.max(1),
+            window_start: Instant::now(),
+            window_requests: 0,
+            overloaded_retries: 0,
+        }))
+    }
+
+    pub(crate) fn limits(&self) -> RequestLimits {
+        self.0.lock().expect("request limiter poisoned").limits
+    }
+
+    pub(crate) fn set_limits(&self, limits: RequestLimits) {
+        let mut state = self.0.lock().expect("request limiter poisoned");
+        state.limits = limits;
+        state.parallel_requests = state.parallel_requests.clamp(1, limits.max_parallel_requests.max(1));
+    }
+
+    /// Returns how many of the remaining requests can be sent now, waits until the budget allows at least one.
+    pub(crate) async fn acquire(&self, remaining_requests: usize) -> usize {
+        loop {
+            let wait = {
+                let mut state = self.0.lock().expect("request limiter poisoned");
+                let elapsed = state.window_start.elapsed();
+                if elapsed >= Duration::from_secs(1) {
+                    state.window_start = Instant::now();
+                    state.window_requests = 0;
+                }
+                let mut requests = state.parallel_requests.min(remaining_requests);
+                if let Some(max_requests_per_second) = state.limits.max_requests_per_second {
+                    requests = requests.min(max_requests_per_second.saturating_sub(state.window_requests));
+                }
+                if requests > 0 || remaining_requests == 0 {
+                    state.window_requests += requests;
+                    return requests;
+                }
+                Duration::from_secs(1).saturating_sub(elapsed)
+            };
+            log::debug!("[SYNC] request budget used, waiting {wait:?}");
+            sleep(wait).await;
+        }
+    }
+
+    /// Increases the amount of parallel requests again after a successful chunk.
+    pub(crate) fn record_success(&self) {
+        let mut state = self.0.lock().expect("request limiter poisoned");
+        state.overloaded_retries = 0;
+        let step = (state.parallel_requests / 10).max(1);
+        state.parallel_requests = (state.parallel_requests + step).min(state.limits.max_parallel_requests.max(1));
+    }
+
+    /// Halves the amount of parallel requests and waits before the chunk is sent again if the node was overloaded,
+    /// returns the error if the chunk shouldn't be sent again.
+    pub(crate) async fn record_failure(&self, error: crate::Error) -> crate::Result<()> {
+        if !is_overloaded(&error) {
+            return Err(error);
+        }
+        let delay = {
+            let mut state = self.0.lock().expect("request limiter poisoned");
+            if state.overloaded_retries >= MAX_OVERLOADED_RETRIES {
+                state.overloaded_retries = 0;
+                return Err(error);
+            }
+            state.overloaded_retries += 1;
+            state.parallel_requests = (state.parallel_requests / 2).max(1);
+            log::debug!(
+                "[SYNC] node overloaded: {error}, reducing parallel requests to {}",
+                state.parallel_requests
+            );
+            OVERLOADED_RETRY_DELAY.saturating_mul(2u32.saturating_pow(state.overloaded_retries - 1))
+        };
+        sleep(delay).await;
+        Ok(())
+    }
+}
+
+// If the node responded with too many requests or didn't respond in time
+fn is_overloaded(error: &crate::Error) -> bool {
+    match error {
+        crate::Error::Client(error) => match error.as_ref() {
+            iota_client::Error::ResponseError { code, .. } => matches!(code, 429 | 503 | 504),
+            iota_client::Error::Reqwest(error) => error.is_timeout(),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+async fn sleep(duration: Duration) {
+    #[cfg(target_family = "wasm")]
+    gloo_timers::future::TimeoutFuture::new(duration.as_millis() as u32).await;
+    #[cfg(not(target_family = "wasm"))]
+    tokio::time::sleep(duration).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn budget_and_parallel_requests() {
+        let limiter = RequestLimiter::new(RequestLimits {
+            max_parallel_requests: 8,
+            max_requests_per_second: Some(10),
+        });
+        assert_eq!(limiter.acquire(20).await, 8);
+        // Only 2 requests are left in the budget of this second
+        assert_eq!(limiter.acquire(20).await, 2);
+
+        let overloaded = crate::Error::Client(Box::new(iota_client::Error::ResponseError {
+            code: 429,
+            text: String::new(),
+            url: String::new(),
+        }));
+        limiter.record_failure(overloaded).await.unwrap();
+        assert_eq!(limiter.0.lock().unwrap().parallel_requests, 4);
+        limiter.record_success();
+        assert_eq!(limiter.0.lock().unwrap().parallel_requests, 5);
+
+        assert!(limiter.record_failure(crate::Error::Cancelled).await.is_err());
+    }
+}
@@ -12,9 +12,12 @@ use iota_client::{
 
 use crate::account::{
     handle::AccountHandle,
+    operations::syncing::options::ReattachmentOptions,
     types::{InclusionState, Transaction},
     Account,
 };
+#[cfg(feature = "events")]
+use crate::events::types::{TransactionReattachmentEvent, WalletEvent};
 
 // ignore outputs and transactions from other networks
 // check if outputs are unspent, rebroadcast, reattach...
@@ -22,11 +25,12 @@ use crate::account::{
 // confirmed, then they should get removed
 
 impl AccountHandle {
-    /// Sync transactions and reattach them if unconfirmed. Returns the transaction with updated metadata and spent
-    /// output ids that don't need to be locked anymore
+    /// Sync transactions and promote or reattach them if unconfirmed, transactions that were reattached too often are
+    /// given up. Returns the transaction with updated metadata and spent output ids that don't need to be locked
+    /// anymore
     /// Return true if a transaction got confirmed for which we don't have an output already, based on this outputs will
     /// be synced again
-    pub(crate) async fn sync_pending_transactions(&self) -> crate::Result<bool> {
+    pub(crate) async fn sync_pending_transactions(&self, options: &ReattachmentOptions) -> crate::Result<bool> {
         log::debug!("[SYNC] sync pending transactions");
         let account = self.read().await;
 
@@ -46,6 +50,7 @@ impl AccountHandle {
         // are available again
         let mut output_ids_to_unlock = Vec::new();
         let mut transactions_to_reattach = Vec::new();
+        let mut blocks_to_promote = Vec::new();
 
         for transaction_id in &account.pending_transactions {
             log::debug!("[SYNC] sync pending transaction {}", transaction_id);
@@ -148,26 +153,28 @@ impl AccountHandle {
                                     )
                                 }
                             }
-                        } else {
+                        } else if input_got_spent {
                             // no need to reattach if one input got spent
-                            if input_got_spent {
-                                process_transaction_with_unknown_state(
-                                    &account,
-                                    transaction,
-                                    &mut updated_transactions,
-                                    &mut output_ids_to_unlock,
-                                )?;
-                            } else {
-                                let time_now = SystemTime::now()
-                                    .duration_since(SystemTime::UNIX_EPOCH)
-                                    .expect("time went backwards")
-                                    .as_millis();
-                                // Reattach if older than 30 seconds
-                                if transaction.timestamp + 30000 < time_now {
-                                    // only reattach if inputs are still unspent
-                                    transactions_to_reattach.push(transaction);
-                                }
-                            }
+                            process_transaction_with_unknown_state(
+                                &account,
+                                transaction,
+                                &mut updated_transactions,
+                                &mut output_ids_to_unlock,
+                            )?;
+                        } else if options.promote
+                            && metadata.should_promote.unwrap_or(false)
+                            && !is_past_deadline(&transaction, options)
+                        {
+                            blocks_to_promote.push(block_id);
+                        } else {
+                            // only reattach if inputs are still unspent
+                            reattach_or_expire(
+                                transaction,
+                                options,
+                                &mut transactions_to_reattach,
+                                &mut updated_transactions,
+                                &mut output_ids_to_unlock,
+                            );
                         }
                     }
                     Err(ClientError::NotFound(_)) => {
@@ -180,15 +187,14 @@ impl AccountHandle {
                                 &mut output_ids_to_unlock,
                             )?;
                         } else {
-                            let time_now = SystemTime::now()
-                                .duration_since(SystemTime::UNIX_EPOCH)
-                                .expect("time went backwards")
-                                .as_millis();
-                            // Reattach if older than 30 seconds
-                            if transaction.timestamp + 30000 < time_now {
-                                // only reattach if inputs are still unspent
-                                transactions_to_reattach.push(transaction);
-                            }
+                            // only reattach if inputs are still unspent
+                            reattach_or_expire(
+                                transaction,
+                                options,
+                                &mut transactions_to_reattach,
+                                &mut updated_transactions,
+                                &mut output_ids_to_unlock,
+                            );
                         }
                     }
                     Err(e) => return Err(e.into()),
@@ -197,18 +203,39 @@ impl AccountHandle {
                 // transaction wasn't submitted yet, so we have to send it again
                 // no need to reattach if one input got spent
                 if input_got_spent {
+                } else if is_past_deadline(&transaction, options) {
+                    expire_transaction(transaction, &mut updated_transactions, &mut output_ids_to_unlock);
                 } else {
                     // only reattach if inputs are still unspent
                     transactions_to_reattach.push(transaction);
                 }
             }
         }
+        #[cfg(feature = "events")]
+        let account_index = account.index;
         drop(account);
 
+        for block_id in blocks_to_promote {
+            log::debug!("[SYNC] promote block {block_id}");
+            self.client.promote_unchecked(&block_id).await?;
+        }
+
         for mut transaction in transactions_to_reattach {
             log::debug!("[SYNC] reattach transaction");
             let reattached_block = self.submit_transaction_payload(transaction.payload.clone()).await?;
-            transaction.block_id.replace(reattached_block);
+            // A transaction without a block wasn't submitted before, so it's not a reattachment
+            if transaction.block_id.replace(reattached_block).is_some() {
+                transaction.reattachments += 1;
+                #[cfg(feature = "events")]
+                self.event_emitter.lock().await.emit(
+                    account_index,
+                    WalletEvent::TransactionReattachment(TransactionReattachmentEvent {
+                        transaction_id: transaction.transaction_id,
+                        block_id: reattached_block,
+                        reattachments: transaction.reattachments,
+                    }),
+                );
+            }
             updated_transactions.push(transaction);
         }
 
@@ -240,6 +267,79 @@ fn updated_transaction_and_outputs(
     updated_transactions.push(transaction);
 }
 
+// Reattach the transaction if the time for its next reattachment passed, or give it up if it was reattached too often
+// or is pending for longer than allowed
+fn reattach_or_expire(
+    transaction: Transaction,
+    options: &ReattachmentOptions,
+    transactions_to_reattach: &mut Vec<Transaction>,
+    updated_transactions: &mut Vec<Transaction>,
+    output_ids_to_unlock: &mut Vec<OutputId>,
+) {
+    if is_past_deadline(&transaction, options) {
+        log::debug!(
+            "[SYNC] transaction {} expired, it's pending for too long",
+            transaction.transaction_id
+        );
+        expire_transaction(transaction, updated_transactions, output_ids_to_unlock);
+        return;
+    }
+
+    let next_reattachment =
+        transaction.timestamp + options.reattach_after_milliseconds as u128 * (transaction.reattachments as u128 + 1);
+    if next_reattachment >= time_now() {
+        return;
+    }
+
+    if options.max_reattachments.map_or(false, |max_reattachments| {
+        transaction.reattachments >= max_reattachments
+    }) {
+        log::debug!(
+            "[SYNC] transaction {} expired after {} reattachments",
+            transaction.transaction_id,
+            transaction.reattachments
+        );
+        expire_transaction(transaction, updated_transactions, output_ids_to_unlock);
+    } else {
+        transactions_to_reattach.push(transaction);
+    }
+}
+
+// Give the transaction up, its inputs are unspent, otherwise it wouldn't be reattached, so they're available again
+fn expire_transaction(
+    mut transaction: Transaction,
+    updated_transactions: &mut Vec<Transaction>,
+    output_ids_to_unlock: &mut Vec<OutputId>,
+) {
+    let TransactionEssence::Regular(essence) = transaction.payload.essence();
+    for input in essence.inputs() {
+        if let Input::Utxo(input) = input {
+            output_ids_to_unlock.push(*input.output_id());
+        }
+    }
+    transaction.inclusion_state = InclusionState::Expired;
+    updated_transactions.push(transaction);
+}
+
+// If the transaction is pending for longer than its own maximum or the one of the options
+fn is_past_deadline(transaction: &Transaction, options: &ReattachmentOptions) -> bool {
+    transaction
+        .expires_at
+        .or_else(|| {
+            options
+                .max_pending_milliseconds
+                .map(|max_pending_milliseconds| transaction.timestamp + max_pending_milliseconds as u128)
+        })
+        .map_or(false, |expires_at| expires_at < time_now())
+}
+
+fn time_now() -> u128 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("time went backwards")
+        .as_millis()
+}
+
 // When a transaction got pruned, the inputs and outputs are also not available, then this could mean that it was
 // confirmed and the created outputs got also already spent and pruned or the inputs got spent in another transaction
 fn process_transaction_with_unknown_state(
@@ -0,0 +1,134 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_client::{
+    api::PreparedTransactionData,
+    block::{output::Output, payload::transaction::TransactionEssence},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::account::handle::AccountHandle;
+#[cfg(feature = "events")]
+use crate::events::types::{TransactionProgressEvent, WalletEvent};
+
+/// Thresholds for outputs that are too small to be spent economically, so sends don't fragment the wallet into dust.
+/// Only basic outputs without native tokens are checked, other outputs usually only hold their storage deposit.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DustPolicy {
+    /// Minimum amount of a created output.
+    pub min_output_amount: Option<u64>,
+    /// Minimum amount of the remainder that goes back to the account.
+    pub min_remainder_amount: Option<u64>,
+    /// Fail transactions that create dust, instead of only warning about them. Can be overridden for a single
+    /// transaction with [`TransactionOptions::allow_dust`](crate::account::TransactionOptions::allow_dust).
+    #[serde(default)]
+    pub block: bool,
+}
+
+/// An output of a prepared transaction that is below a threshold of the [`DustPolicy`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum DustWarning {
+    /// A created output is below the minimum output amount.
+    #[serde(rename_all = "camelCase")]
+    Output {
+        /// The index of the output in the transaction.
+        output_index: u16,
+        /// The amount of the output.
+        amount: u64,
+        /// The minimum output amount of the policy.
+        minimum: u64,
+    },
+    /// The remainder is below the minimum remainder amount.
+    #[serde(rename_all = "camelCase")]
+    Remainder {
+        /// The amount of the remainder.
+        amount: u64,
+        /// The minimum remainder amount of the policy.
+        minimum: u64,
+    },
+}
+
+impl DustPolicy {
+    // The outputs of the transaction that are below a threshold
+    fn check(&self, prepared_transaction_data: &PreparedTransactionData) -> Vec<DustWarning> {
+        let TransactionEssence::Regular(essence) = &prepared_transaction_data.essence;
+        let remainder = prepared_transaction_data
+            .remainder
+            .as_ref()
+            .map(|remainder_data| &remainder_data.output);
+
+        let mut warnings = Vec::new();
+        for (output_index, output) in essence.outputs().iter().enumerate() {
+            if !is_plain_basic_output(output) {
+                continue;
+            }
+            if remainder == Some(output) {
+                if let Some(minimum) = self.min_remainder_amount {
+                    if output.amount() < minimum {
+                        warnings.push(DustWarning::Remainder {
+                            amount: output.amount(),
+                            minimum,
+                        });
+                    }
+                }
+            } else if let Some(minimum) = self.min_output_amount {
+                if output.amount() < minimum {
+                    warnings.push(DustWarning::Output {
+                        output_index: output_index as u16,
+                        amount: output.amount(),
+                        minimum,
+                    });
+                }
+            }
+        }
+        warnings
+    }
+}
+
+impl AccountHandle {
+    /// Returns the outputs of a prepared transaction that are below the thresholds of the dust policy.
+    pub async fn dust_warnings(&self, prepared_transaction_data: &PreparedTransactionData) -> Vec<DustWarning> {
+        self.signing_guard
+            .read()
+            .await
+            .dust_policy
+            .check(prepared_transaction_data)
+    }
+
+    // Fails if the transaction creates dust and the policy blocks it, otherwise the dust is only logged and emitted
+    pub(crate) async fn check_dust_policy(
+        &self,
+        prepared_transaction_data: &PreparedTransactionData,
+        allow_dust: bool,
+    ) -> crate::Result<()> {
+        let block = self.signing_guard.read().await.dust_policy.block;
+        let warnings = self.dust_warnings(prepared_transaction_data).await;
+        if warnings.is_empty() {
+            return Ok(());
+        }
+        if block && !allow_dust {
+            return Err(crate::Error::DustPolicyViolation(format!("{warnings:?}")));
+        }
+
+        log::warn!("[TRANSACTION] transaction creates dust: {warnings:?}");
+        #[cfg(feature = "events")]
+        {
+            let account_index = self.read().await.index;
+            self.event_emitter.lock().await.emit(
+                account_index,
+                WalletEvent::TransactionProgress(TransactionProgressEvent::DustWarning(warnings)),
+            );
+        }
+        Ok(())
+    }
+}
+
+// Basic outputs that only hold base coins, so their amount isn't just the storage deposit of something else
+fn is_plain_basic_output(output: &Output) -> bool {
+    match output {
+        Output::Basic(basic_output) => basic_output.native_tokens().is_empty(),
+        _ => false,
+    }
+}
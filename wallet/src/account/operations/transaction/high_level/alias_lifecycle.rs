@@ -0,0 +1,155 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_client::{
+    api::PreparedTransactionData,
+    block::{
+        address::Address,
+        output::{
+            unlock_condition::{
+                GovernorAddressUnlockCondition, StateControllerAddressUnlockCondition, UnlockCondition,
+            },
+            AliasId, AliasOutput, AliasOutputBuilder, Output, Rent,
+        },
+    },
+};
+
+use crate::{
+    account::{
+        handle::AccountHandle,
+        types::{AliasRecord, Transaction},
+        TransactionOptions,
+    },
+    Error,
+};
+
+impl AccountHandle {
+    /// Returns the aliases the account controls or controlled, with their last known state. The records are updated
+    /// when the account is synced, aliases are created with
+    /// [AccountHandle.create_alias_output()](crate::account::handle::AccountHandle.create_alias_output).
+    pub async fn aliases(&self) -> Vec<AliasRecord> {
+        self.read().await.aliases().values().cloned().collect()
+    }
+
+    /// Change the state controller of an alias with a governance transition, signed by the governor. The new state
+    /// controller needs to be a Bech32 encoded address.
+    pub async fn set_alias_state_controller(
+        &self,
+        alias_id: AliasId,
+        state_controller: String,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<Transaction> {
+        let simulate = options.as_ref().map_or(false, |options| options.simulate);
+        let prepared_transaction = self
+            .prepare_set_alias_controllers(alias_id, Some(state_controller), None, options)
+            .await?;
+        self.sign_and_submit_or_simulate(prepared_transaction, simulate).await
+    }
+
+    /// Change the governor of an alias with a governance transition, signed by the current governor. The new
+    /// governor needs to be a Bech32 encoded address, it can destroy the alias and change its controllers afterwards.
+    pub async fn set_alias_governor(
+        &self,
+        alias_id: AliasId,
+        governor: String,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<Transaction> {
+        let simulate = options.as_ref().map_or(false, |options| options.simulate);
+        let prepared_transaction = self
+            .prepare_set_alias_controllers(alias_id, None, Some(governor), options)
+            .await?;
+        self.sign_and_submit_or_simulate(prepared_transaction, simulate).await
+    }
+
+    /// Publish new state metadata of an alias with a state transition, signed by the state controller. The state
+    /// index is increased and the storage deposit of the alias output is increased if the metadata needs more.
+    pub async fn update_alias_state_metadata(
+        &self,
+        alias_id: AliasId,
+        state_metadata: Vec<u8>,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<Transaction> {
+        let simulate = options.as_ref().map_or(false, |options| options.simulate);
+        let prepared_transaction = self
+            .prepare_update_alias_state_metadata(alias_id, state_metadata, options)
+            .await?;
+        self.sign_and_submit_or_simulate(prepared_transaction, simulate).await
+    }
+
+    /// Function to prepare the transaction for
+    /// [AccountHandle.set_alias_state_controller()](crate::account::handle::AccountHandle.set_alias_state_controller)
+    /// and [AccountHandle.set_alias_governor()](crate::account::handle::AccountHandle.set_alias_governor)
+    pub(crate) async fn prepare_set_alias_controllers(
+        &self,
+        alias_id: AliasId,
+        state_controller: Option<String>,
+        governor: Option<String>,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<PreparedTransactionData> {
+        log::debug!("[TRANSACTION] prepare_set_alias_controllers");
+        let token_supply = self.client.get_token_supply().await?;
+        let alias_output = self.unspent_alias_output(alias_id).await?;
+
+        let state_controller = match state_controller {
+            Some(state_controller) => Address::try_from_bech32(state_controller)?.1,
+            None => *alias_output.state_controller_address(),
+        };
+        let governor = match governor {
+            Some(governor) => Address::try_from_bech32(governor)?.1,
+            None => *alias_output.governor_address(),
+        };
+
+        // The state index stays the same, so the input selection unlocks the alias with the governor
+        let outputs = vec![
+            AliasOutputBuilder::from(&alias_output)
+                .with_alias_id(alias_id)
+                .with_unlock_conditions([
+                    UnlockCondition::StateControllerAddress(StateControllerAddressUnlockCondition::new(
+                        state_controller,
+                    )),
+                    UnlockCondition::GovernorAddress(GovernorAddressUnlockCondition::new(governor)),
+                ])
+                .finish_output(token_supply)?,
+        ];
+
+        self.prepare_transaction(outputs, options).await
+    }
+
+    /// Function to prepare the transaction for
+    /// [AccountHandle.update_alias_state_metadata()](crate::account::handle::AccountHandle.update_alias_state_metadata)
+    pub(crate) async fn prepare_update_alias_state_metadata(
+        &self,
+        alias_id: AliasId,
+        state_metadata: Vec<u8>,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<PreparedTransactionData> {
+        log::debug!("[TRANSACTION] prepare_update_alias_state_metadata");
+        let rent_structure = self.client.get_rent_structure().await?;
+        let token_supply = self.client.get_token_supply().await?;
+        let alias_output = self.unspent_alias_output(alias_id).await?;
+
+        let alias_output_builder = AliasOutputBuilder::from(&alias_output)
+            .with_alias_id(alias_id)
+            .with_state_index(alias_output.state_index() + 1)
+            .with_state_metadata(state_metadata);
+        let mut output = alias_output_builder.clone().finish_output(token_supply)?;
+        if output.rent_cost(&rent_structure) > output.amount() {
+            output = alias_output_builder
+                .with_minimum_storage_deposit(rent_structure)
+                .finish_output(token_supply)?;
+        }
+
+        self.prepare_transaction(vec![output], options).await
+    }
+
+    // Get the unspent output of an alias of the account
+    async fn unspent_alias_output(&self, alias_id: AliasId) -> crate::Result<AliasOutput> {
+        match self.get_alias_output(Some(alias_id)).await {
+            Some((_, output_data)) => match output_data.output {
+                Output::Alias(alias_output) => Ok(alias_output),
+                _ => unreachable!("get_alias_output only returns alias outputs"),
+            },
+            None => Err(Error::AliasNotFoundInUnspentOutputs(alias_id)),
+        }
+    }
+}
@@ -0,0 +1,592 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{HashMap, HashSet};
+
+use iota_client::{
+    api::{
+        transaction::validate_regular_transaction_essence_length, PreparedTransactionData, PreparedTransactionDataDto,
+        SignedTransactionData, SignedTransactionDataDto,
+    },
+    block::{
+        address::Address,
+        input::{Input, UtxoInput},
+        output::{InputsCommitment, NativeToken, NftId, Output, Rent, RentStructure, TokenId},
+        payload::transaction::{RegularTransactionEssence, TransactionEssence, TransactionPayload},
+        protocol::ProtocolParameters,
+        unlock::Unlocks,
+    },
+};
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    account::{
+        handle::AccountHandle,
+        operations::transaction::prepare_output::{Assets, OutputOptions, ReturnStrategy, StorageDeposit},
+        types::Transaction,
+        TransactionOptions,
+    },
+    Error,
+};
+
+/// The version of the [`SwapOffer`] and [`SwapAcceptance`] format.
+pub const SWAP_OFFER_VERSION: u8 = 1;
+
+/// The NFTs and native tokens one side of a swap sends.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapAssets {
+    /// NFTs that are sent.
+    #[serde(default)]
+    pub nft_ids: Vec<NftId>,
+    /// Native tokens that are sent.
+    #[serde(default)]
+    pub native_tokens: Vec<NativeToken>,
+}
+
+impl SwapAssets {
+    /// Returns `true` if no assets are sent.
+    pub fn is_empty(&self) -> bool {
+        self.nft_ids.is_empty() && self.native_tokens.is_empty()
+    }
+}
+
+/// An offer to swap assets, created by the maker with [`AccountHandle::create_swap_offer()`] for a taker that accepts
+/// it with [`AccountHandle::accept_swap_offer()`]. It contains the half of the transaction that sends the offered
+/// assets from the maker to the taker, the inputs of that half are locked in the account of the maker until the swap
+/// is completed or canceled.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapOffer {
+    /// The version of the format.
+    pub version: u8,
+    /// Bech32 encoded address of the maker, which receives the requested assets.
+    pub maker_address: String,
+    /// Bech32 encoded address of the taker, which receives the offered assets.
+    pub taker_address: String,
+    /// The assets the maker sends.
+    pub offered: SwapAssets,
+    /// The assets the taker needs to send.
+    pub requested: SwapAssets,
+    /// The half of the transaction of the maker.
+    pub maker_half: PreparedTransactionDataDto,
+}
+
+impl SwapOffer {
+    /// Serializes the offer to JSON.
+    pub fn to_json(&self) -> crate::Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Deserializes an offer from JSON, fails if it has an unsupported version.
+    pub fn from_json(json: &str) -> crate::Result<Self> {
+        let offer: Self = serde_json::from_str(json)?;
+        check_version(offer.version)?;
+        Ok(offer)
+    }
+
+    fn maker_half(&self, protocol_parameters: &ProtocolParameters) -> crate::Result<PreparedTransactionData> {
+        Ok(PreparedTransactionData::try_from_dto(
+            &self.maker_half,
+            protocol_parameters,
+        )?)
+    }
+}
+
+/// A [`SwapOffer`] accepted by the taker, returned to the maker to complete the swap with
+/// [`AccountHandle::complete_swap()`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapAcceptance {
+    /// The version of the format.
+    pub version: u8,
+    /// The transaction with the inputs and outputs of both halves, signed for the inputs of the taker. The unlocks of
+    /// the inputs of the maker are replaced when the maker signs it.
+    pub signed_transaction_data: SignedTransactionDataDto,
+}
+
+impl SwapAcceptance {
+    /// Serializes the acceptance to JSON.
+    pub fn to_json(&self) -> crate::Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Deserializes an acceptance from JSON, fails if it has an unsupported version.
+    pub fn from_json(json: &str) -> crate::Result<Self> {
+        let acceptance: Self = serde_json::from_str(json)?;
+        check_version(acceptance.version)?;
+        Ok(acceptance)
+    }
+}
+
+impl AccountHandle {
+    /// Offer assets of the account in exchange for assets of the counterparty, which needs to be a Bech32 encoded
+    /// address. The half of the transaction that sends the offered assets is prepared and its inputs are locked, until
+    /// the swap is completed with [`AccountHandle::complete_swap()`] or canceled with
+    /// [`AccountHandle::cancel_swap_offer()`]. The storage deposits of the sent assets are paid by their sender.
+    /// ```ignore
+    /// let offer = account
+    ///     .create_swap_offer(
+    ///         "rms1qpszqzadsym6wpppd6z037dvlejmjuke7s24hm95s9fg9vpua7vluaw60xu".to_string(),
+    ///         SwapAssets { nft_ids: vec![nft_id], native_tokens: Vec::new() },
+    ///         SwapAssets { nft_ids: Vec::new(), native_tokens: vec![NativeToken::new(token_id, U256::from(100))?] },
+    ///         None,
+    ///     )
+    ///     .await?;
+    /// // Send `offer.to_json()?` to the counterparty
+    /// ```
+    pub async fn create_swap_offer(
+        &self,
+        counterparty: String,
+        offered: SwapAssets,
+        requested: SwapAssets,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<SwapOffer> {
+        log::debug!("[TRANSACTION] create_swap_offer");
+        if offered.is_empty() || requested.is_empty() {
+            return Err(Error::InvalidSwap("both sides need to send assets".to_string()));
+        }
+        let counterparty_address = Address::try_from_bech32(&counterparty)?.1;
+        if self.own_addresses().await.contains(&counterparty_address) {
+            return Err(Error::InvalidSwap(
+                "the counterparty is an address of the account".to_string(),
+            ));
+        }
+        let maker_address = self
+            .addresses()
+            .await?
+            .first()
+            .ok_or(Error::FailedToGetRemainder)?
+            .address()
+            .to_bech32();
+
+        let outputs = self.swap_outputs(&offered, &counterparty, &options).await?;
+        let maker_half = self.prepare_transaction(outputs, options).await?;
+
+        Ok(SwapOffer {
+            version: SWAP_OFFER_VERSION,
+            maker_address,
+            taker_address: counterparty,
+            offered,
+            requested,
+            maker_half: PreparedTransactionDataDto::from(&maker_half),
+        })
+    }
+
+    /// Accept a swap offer for an address of the account. The half of the maker is validated, its inputs need to be
+    /// unspent and it needs to send the offered assets to the taker. The half of the taker is prepared and its inputs
+    /// are signed, the returned [`SwapAcceptance`] needs to be sent back to the maker. The inputs of the taker stay
+    /// locked until the swap transaction is confirmed or [`AccountHandle::cancel_swap_acceptance()`] is called.
+    pub async fn accept_swap_offer(
+        &self,
+        offer: &SwapOffer,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<SwapAcceptance> {
+        log::debug!("[TRANSACTION] accept_swap_offer");
+        check_version(offer.version)?;
+        let protocol_parameters = self.client.get_protocol_parameters().await?;
+        let rent_structure = self.client.get_rent_structure().await?;
+        let token_supply = self.client.get_token_supply().await?;
+        let own_addresses = self.own_addresses().await;
+
+        let taker_address = Address::try_from_bech32(&offer.taker_address)?.1;
+        if !own_addresses.contains(&taker_address) {
+            return Err(Error::InvalidSwap(
+                "the offer isn't for an address of the account".to_string(),
+            ));
+        }
+        let maker_address = Address::try_from_bech32(&offer.maker_address)?.1;
+
+        let maker_half = offer.maker_half(&protocol_parameters)?;
+        // The swap transaction can only be confirmed if the inputs of the maker are still unspent
+        for input in &maker_half.inputs_data {
+            let output_response = self.client.get_output(input.output_id()).await?;
+            if output_response.metadata.is_spent
+                || Output::try_from_dto(&output_response.output, token_supply)? != input.output
+            {
+                return Err(Error::InvalidSwap(format!(
+                    "input {} of the maker is spent or doesn't match the ledger",
+                    input.output_id()
+                )));
+            }
+        }
+
+        let outputs = self
+            .swap_outputs(&offer.requested, &offer.maker_address, &options)
+            .await?;
+        let taker_half = self.prepare_transaction(outputs, options).await?;
+        let taker_inputs = taker_half.inputs_data.clone();
+
+        let result: crate::Result<SignedTransactionData> = async {
+            let prepared_transaction_data = combine_halves(maker_half, taker_half, &protocol_parameters)?;
+            validate_swap(
+                &prepared_transaction_data,
+                &own_addresses,
+                &maker_address,
+                &offer.requested,
+                &offer.offered,
+                &rent_structure,
+            )?;
+            self.sign_transaction(&prepared_transaction_data).await
+        }
+        .await;
+
+        match result {
+            Ok(signed_transaction_data) => Ok(SwapAcceptance {
+                version: SWAP_OFFER_VERSION,
+                signed_transaction_data: SignedTransactionDataDto::from(&signed_transaction_data),
+            }),
+            Err(err) => {
+                // unlock outputs so they are available for a new transaction
+                self.unlock_inputs(taker_inputs).await?;
+                Err(err)
+            }
+        }
+    }
+
+    /// Complete a swap with the acceptance of the taker. The transaction is validated against the original offer, so
+    /// the account only sends the offered assets and receives the requested assets, then the inputs of the maker are
+    /// signed and the transaction is submitted.
+    pub async fn complete_swap(&self, offer: &SwapOffer, acceptance: &SwapAcceptance) -> crate::Result<Transaction> {
+        log::debug!("[TRANSACTION] complete_swap");
+        check_version(acceptance.version)?;
+        let protocol_parameters = self.client.get_protocol_parameters().await?;
+        let rent_structure = self.client.get_rent_structure().await?;
+        let own_addresses = self.own_addresses().await;
+
+        let maker_half = offer.maker_half(&protocol_parameters)?;
+        let accepted = SignedTransactionData::try_from_dto(&acceptance.signed_transaction_data, &protocol_parameters)?;
+        let maker_inputs_count = maker_half.inputs_data.len();
+        let maker_inputs_match = accepted.inputs_data.len() > maker_inputs_count
+            && maker_half
+                .inputs_data
+                .iter()
+                .zip(&accepted.inputs_data)
+                .all(|(offered, accepted)| offered.output_id() == accepted.output_id());
+        if !maker_inputs_match {
+            return Err(Error::InvalidSwap(
+                "the acceptance doesn't use the inputs of the offer".to_string(),
+            ));
+        }
+
+        let prepared_transaction_data = PreparedTransactionData {
+            essence: accepted.transaction_payload.essence().clone(),
+            inputs_data: accepted.inputs_data,
+            remainder: None,
+        };
+        let TransactionEssence::Regular(essence) = &prepared_transaction_data.essence;
+        let essence_inputs_match = essence.inputs().iter().zip(&prepared_transaction_data.inputs_data).all(
+            |(input, input_data)| matches!(input, Input::Utxo(utxo) if utxo.output_id() == input_data.output_id()),
+        );
+        if !essence_inputs_match {
+            return Err(Error::InvalidSwap(
+                "the inputs of the transaction don't match their data".to_string(),
+            ));
+        }
+        validate_swap(
+            &prepared_transaction_data,
+            &own_addresses,
+            &Address::try_from_bech32(&offer.taker_address)?.1,
+            &offer.offered,
+            &offer.requested,
+            &rent_structure,
+        )?;
+
+        let signed_transaction_data = self.sign_transaction(&prepared_transaction_data).await?;
+        // The inputs of the maker are at the start, the ones of the taker after them
+        let unlocks = signed_transaction_data
+            .transaction_payload
+            .unlocks()
+            .iter()
+            .take(maker_inputs_count)
+            .chain(accepted.transaction_payload.unlocks().iter().skip(maker_inputs_count))
+            .cloned()
+            .collect::<Vec<_>>();
+        let transaction_payload =
+            TransactionPayload::new(prepared_transaction_data.essence.clone(), Unlocks::new(unlocks)?)?;
+
+        self.submit_transaction(SignedTransactionData {
+            transaction_payload,
+            inputs_data: prepared_transaction_data.inputs_data,
+        })
+        .await
+    }
+
+    /// Cancel a swap offer of the account, its inputs are unlocked so they can be used for other transactions again.
+    /// The swap can't be completed afterwards.
+    pub async fn cancel_swap_offer(&self, offer: &SwapOffer) -> crate::Result<()> {
+        log::debug!("[TRANSACTION] cancel_swap_offer");
+        let protocol_parameters = self.client.get_protocol_parameters().await?;
+        self.discard_prepared_transaction(offer.maker_half(&protocol_parameters)?)
+            .await
+    }
+
+    /// Unlock the inputs the account added to an accepted swap, if the maker doesn't complete it. The inputs of the
+    /// counterparty aren't affected.
+    pub async fn cancel_swap_acceptance(&self, acceptance: &SwapAcceptance) -> crate::Result<()> {
+        log::debug!("[TRANSACTION] cancel_swap_acceptance");
+        let protocol_parameters = self.client.get_protocol_parameters().await?;
+        let signed_transaction_data =
+            SignedTransactionData::try_from_dto(&acceptance.signed_transaction_data, &protocol_parameters)?;
+        self.unlock_inputs(signed_transaction_data.inputs_data).await?;
+        #[cfg(feature = "storage")]
+        self.save(None).await?;
+        Ok(())
+    }
+
+    // The outputs that send the assets of one side to the other side, the sender pays their storage deposits
+    async fn swap_outputs(
+        &self,
+        assets: &SwapAssets,
+        recipient: &str,
+        options: &Option<TransactionOptions>,
+    ) -> crate::Result<Vec<Output>> {
+        let output_options = |assets| OutputOptions {
+            recipient_address: recipient.to_string(),
+            amount: 0,
+            assets: Some(assets),
+            features: None,
+            unlocks: None,
+            storage_deposit: Some(StorageDeposit {
+                return_strategy: Some(ReturnStrategy::Gift),
+                use_excess_if_low: None,
+            }),
+        };
+
+        let mut outputs = Vec::new();
+        for nft_id in &assets.nft_ids {
+            let nft_assets = Assets {
+                native_tokens: None,
+                nft_id: Some(*nft_id),
+            };
+            outputs.push(self.prepare_output(output_options(nft_assets), options.clone()).await?);
+        }
+        if !assets.native_tokens.is_empty() {
+            let native_token_assets = Assets {
+                native_tokens: Some(assets.native_tokens.clone()),
+                nft_id: None,
+            };
+            outputs.push(
+                self.prepare_output(output_options(native_token_assets), options.clone())
+                    .await?,
+            );
+        }
+        Ok(outputs)
+    }
+
+    // The public and internal addresses of the account
+    async fn own_addresses(&self) -> HashSet<Address> {
+        let account = self.read().await;
+        account
+            .public_addresses
+            .iter()
+            .chain(account.internal_addresses.iter())
+            .map(|address| address.address.inner)
+            .collect()
+    }
+}
+
+fn check_version(version: u8) -> crate::Result<()> {
+    if version != SWAP_OFFER_VERSION {
+        return Err(Error::InvalidSwap(format!("unsupported version {version}")));
+    }
+    Ok(())
+}
+
+// Builds one transaction from both halves, the inputs and outputs of the maker come first
+fn combine_halves(
+    maker_half: PreparedTransactionData,
+    taker_half: PreparedTransactionData,
+    protocol_parameters: &ProtocolParameters,
+) -> crate::Result<PreparedTransactionData> {
+    let TransactionEssence::Regular(maker_essence) = &maker_half.essence;
+    let TransactionEssence::Regular(taker_essence) = &taker_half.essence;
+    let outputs: Vec<Output> = maker_essence
+        .outputs()
+        .iter()
+        .chain(taker_essence.outputs().iter())
+        .cloned()
+        .collect();
+    let inputs_data = [maker_half.inputs_data, taker_half.inputs_data].concat();
+    let inputs: Vec<Input> = inputs_data
+        .iter()
+        .map(|input| Input::Utxo(UtxoInput::from(*input.output_id())))
+        .collect();
+
+    let inputs_commitment = InputsCommitment::new(inputs_data.iter().map(|input| &input.output));
+    let essence = RegularTransactionEssence::builder(protocol_parameters.network_id(), inputs_commitment)
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .finish(protocol_parameters)?;
+    validate_regular_transaction_essence_length(&essence)?;
+
+    Ok(PreparedTransactionData {
+        essence: TransactionEssence::Regular(essence),
+        inputs_data,
+        remainder: None,
+    })
+}
+
+// The address of an output that can be unlocked with the address unlock condition alone
+fn unconditional_address(output: &Output) -> Option<Address> {
+    match output {
+        Output::Basic(_) | Output::Nft(_) => {
+            let unlock_conditions = output.unlock_conditions()?;
+            if unlock_conditions.len() != 1 {
+                return None;
+            }
+            unlock_conditions.address().map(|address| *address.address())
+        }
+        _ => None,
+    }
+}
+
+fn add_native_tokens(balance: &mut HashMap<TokenId, U256>, output: &Output) {
+    for native_token in output
+        .native_tokens()
+        .into_iter()
+        .flat_map(|native_tokens| native_tokens.iter())
+    {
+        *balance.entry(*native_token.token_id()).or_default() += *native_token.amount();
+    }
+}
+
+// Checks from the view of one side that the transaction only takes the given assets from its addresses, with the
+// storage deposits of their outputs, and that it receives the assets it gets without further unlock conditions
+fn validate_swap(
+    prepared_transaction_data: &PreparedTransactionData,
+    own_addresses: &HashSet<Address>,
+    counterparty: &Address,
+    give: &SwapAssets,
+    get: &SwapAssets,
+    rent_structure: &RentStructure,
+) -> crate::Result<()> {
+    let TransactionEssence::Regular(essence) = &prepared_transaction_data.essence;
+
+    let mut base_coin_in = 0;
+    let mut nfts_in = HashMap::new();
+    let mut native_tokens_in = HashMap::new();
+    for input in &prepared_transaction_data.inputs_data {
+        let owner = input
+            .output
+            .unlock_conditions()
+            .and_then(|unlock_conditions| unlock_conditions.address())
+            .map(|address| *address.address());
+        if !owner.map_or(false, |owner| own_addresses.contains(&owner)) {
+            continue;
+        }
+        base_coin_in += input.output.amount();
+        if let Output::Nft(nft_output) = &input.output {
+            nfts_in.insert(nft_output.nft_id_non_null(input.output_id()), input.output.amount());
+        }
+        add_native_tokens(&mut native_tokens_in, &input.output);
+    }
+
+    let mut base_coin_out = 0;
+    let mut nfts_out = HashSet::new();
+    let mut native_tokens_out = HashMap::new();
+    let mut delivered_nfts = HashSet::new();
+    let mut storage_deposit_allowance = 0;
+    for output in essence.outputs().iter() {
+        let nft_id = match output {
+            Output::Nft(nft_output) => Some(*nft_output.nft_id()),
+            _ => None,
+        };
+        match unconditional_address(output) {
+            Some(address) if own_addresses.contains(&address) => {
+                base_coin_out += output.amount();
+                nfts_out.extend(nft_id);
+                add_native_tokens(&mut native_tokens_out, output);
+            }
+            Some(address) if &address == counterparty => {
+                let gives_nft = nft_id.map_or(false, |nft_id| give.nft_ids.contains(&nft_id));
+                let gives_native_tokens = output.native_tokens().map_or(false, |native_tokens| {
+                    native_tokens.iter().any(|native_token| {
+                        give.native_tokens
+                            .iter()
+                            .any(|given| given.token_id() == native_token.token_id())
+                    })
+                });
+                if gives_nft || gives_native_tokens {
+                    // An NFT can keep the storage deposit it had before
+                    let nft_amount = nft_id
+                        .and_then(|nft_id| nfts_in.get(&nft_id))
+                        .copied()
+                        .unwrap_or_default();
+                    storage_deposit_allowance += output.amount().min(output.rent_cost(rent_structure).max(nft_amount));
+                }
+                if gives_nft {
+                    delivered_nfts.extend(nft_id);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for nft_id in nfts_in.keys() {
+        if !nfts_out.contains(nft_id) && !give.nft_ids.contains(nft_id) {
+            return Err(Error::InvalidSwap(format!("the transaction takes NFT {nft_id}")));
+        }
+    }
+    for nft_id in &give.nft_ids {
+        if !delivered_nfts.contains(nft_id) {
+            return Err(Error::InvalidSwap(format!(
+                "NFT {nft_id} isn't sent to the counterparty"
+            )));
+        }
+    }
+    for nft_id in &get.nft_ids {
+        if !nfts_out.contains(nft_id) || nfts_in.contains_key(nft_id) {
+            return Err(Error::InvalidSwap(format!("NFT {nft_id} isn't received")));
+        }
+    }
+
+    let token_ids: HashSet<TokenId> = native_tokens_in
+        .keys()
+        .chain(native_tokens_out.keys())
+        .copied()
+        .collect();
+    for token_id in token_ids {
+        let amount_in = native_tokens_in.get(&token_id).copied().unwrap_or_default();
+        let amount_out = native_tokens_out.get(&token_id).copied().unwrap_or_default();
+        let given = give
+            .native_tokens
+            .iter()
+            .filter(|native_token| native_token.token_id() == &token_id)
+            .fold(U256::zero(), |sum, native_token| sum + *native_token.amount());
+        let got = get
+            .native_tokens
+            .iter()
+            .filter(|native_token| native_token.token_id() == &token_id)
+            .fold(U256::zero(), |sum, native_token| sum + *native_token.amount());
+        if amount_in.saturating_sub(amount_out) > given {
+            return Err(Error::InvalidSwap(format!(
+                "the transaction takes more of native token {token_id} than offered"
+            )));
+        }
+        if amount_out.saturating_sub(amount_in) < got {
+            return Err(Error::InvalidSwap(format!(
+                "the transaction doesn't send enough of native token {token_id}"
+            )));
+        }
+    }
+    for native_token in &get.native_tokens {
+        if !native_tokens_out.contains_key(native_token.token_id()) {
+            return Err(Error::InvalidSwap(format!(
+                "native token {} isn't received",
+                native_token.token_id()
+            )));
+        }
+    }
+
+    let base_coin_sent = base_coin_in.saturating_sub(base_coin_out);
+    if base_coin_sent > storage_deposit_allowance {
+        return Err(Error::InvalidSwap(format!(
+            "the transaction takes {base_coin_sent} base coins, but the storage deposits of the sent assets are only \
+             {storage_deposit_allowance}"
+        )));
+    }
+
+    Ok(())
+}
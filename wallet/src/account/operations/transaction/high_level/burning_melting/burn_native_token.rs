@@ -7,7 +7,7 @@ use std::{
 };
 
 use iota_client::{
-    api::input_selection::Burn,
+    api::{input_selection::Burn, PreparedTransactionData},
     block::{
         input::INPUT_COUNT_MAX,
         output::{
@@ -18,6 +18,8 @@ use iota_client::{
 };
 use primitive_types::U256;
 
+#[cfg(feature = "events")]
+use crate::account::operations::transaction::high_level::native_token_operations::NativeTokenOperation;
 use crate::account::{
     handle::AccountHandle, operations::transaction::Transaction, types::OutputData, TransactionOptions,
 };
@@ -56,7 +58,33 @@ impl AccountHandle {
         burn_amount: U256,
         options: Option<TransactionOptions>,
     ) -> crate::Result<Transaction> {
-        log::debug!("[TRANSACTION] burn_native_token");
+        let simulate = options.as_ref().map_or(false, |options| options.simulate);
+        let prepared_transaction = self.prepare_burn_native_token(token_id, burn_amount, options).await?;
+        let transaction = self.sign_and_submit_or_simulate(prepared_transaction, simulate).await?;
+
+        #[cfg(feature = "events")]
+        if !simulate {
+            self.emit_native_token_operation(
+                NativeTokenOperation::Burn,
+                token_id,
+                burn_amount,
+                transaction.transaction_id,
+            )
+            .await;
+        }
+
+        Ok(transaction)
+    }
+
+    /// Function to prepare the transaction for
+    /// [AccountHandle.burn_native_token()](crate::account::handle::AccountHandle.burn_native_token)
+    pub async fn prepare_burn_native_token(
+        &self,
+        token_id: TokenId,
+        burn_amount: U256,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<PreparedTransactionData> {
+        log::debug!("[TRANSACTION] prepare_burn_native_token");
 
         let StrippedOutputAggregate {
             custom_inputs,
@@ -78,7 +106,8 @@ impl AccountHandle {
             }),
         };
 
-        self.send(outputs, options).await
+        self.verify_storage_deposits(&outputs).await?;
+        self.prepare_transaction(outputs, options).await
     }
 
     // Get inputs with the required native token amount and create new outputs, just with the to be burned native token
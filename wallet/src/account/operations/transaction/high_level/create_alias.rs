@@ -99,8 +99,9 @@ impl AccountHandle {
         alias_output_options: Option<AliasOutputOptions>,
         options: Option<TransactionOptions>,
     ) -> crate::Result<Transaction> {
+        let simulate = options.as_ref().map_or(false, |options| options.simulate);
         let prepared_transaction = self.prepare_create_alias_output(alias_output_options, options).await?;
-        self.sign_and_submit_transaction(prepared_transaction).await
+        self.sign_and_submit_or_simulate(prepared_transaction, simulate).await
     }
 
     pub(crate) async fn prepare_create_alias_output(
@@ -0,0 +1,150 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_client::{
+    api::{input_selection::Burn, PreparedTransactionData},
+    block::output::{
+        feature::Feature,
+        unlock_condition::{AddressUnlockCondition, UnlockCondition},
+        BasicOutputBuilder, NftOutputBuilder, Output, OutputId,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    account::{
+        handle::AccountHandle,
+        operations::{output_claiming::sdr_not_expired, transaction::Transaction},
+        TransactionOptions,
+    },
+    Error,
+};
+
+/// What happens with the NFT and the native tokens of a declined output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeclinedAssets {
+    /// Return them to the sender in an additional output, its storage deposit is paid by the account.
+    Return,
+    /// Burn them, so the account doesn't need to pay any storage deposit.
+    Burn,
+}
+
+impl Default for DeclinedAssets {
+    fn default() -> Self {
+        Self::Return
+    }
+}
+
+impl AccountHandle {
+    /// Declines an incoming output with a
+    /// [`StorageDepositReturnUnlockCondition`](iota_client::block::output::unlock_condition::StorageDepositReturnUnlockCondition)
+    /// instead of waiting for it to expire, so unwanted NFTs and native tokens don't stay in the account. The whole
+    /// amount of the output is sent back to the return address, the NFT and the native tokens are returned as well or
+    /// burned.
+    /// ```ignore
+    /// let transaction = account.decline_output(output_id, DeclinedAssets::Burn, None).await?;
+    /// ```
+    pub async fn decline_output(
+        &self,
+        output_id: OutputId,
+        declined_assets: DeclinedAssets,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<Transaction> {
+        let simulate = options.as_ref().map_or(false, |options| options.simulate);
+        let prepared_transaction = self.prepare_decline_output(output_id, declined_assets, options).await?;
+        self.sign_and_submit_or_simulate(prepared_transaction, simulate).await
+    }
+
+    /// Prepares the transaction for [`AccountHandle::decline_output()`].
+    pub async fn prepare_decline_output(
+        &self,
+        output_id: OutputId,
+        declined_assets: DeclinedAssets,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<PreparedTransactionData> {
+        log::debug!("[TRANSACTION] prepare_decline_output");
+        let rent_structure = self.client.get_rent_structure().await?;
+        let token_supply = self.client.get_token_supply().await?;
+        let current_time = self.client.get_time_checked().await?;
+
+        let output = self
+            .read()
+            .await
+            .unspent_outputs()
+            .get(&output_id)
+            .map(|output_data| output_data.output.clone())
+            .ok_or(Error::OutputNotDeclinable(
+                output_id,
+                "not an unspent output of the account",
+            ))?;
+        let unlock_conditions = output.unlock_conditions();
+        if unlock_conditions.map_or(false, |unlock_conditions| {
+            unlock_conditions.is_time_locked(current_time)
+        }) {
+            return Err(Error::OutputNotDeclinable(output_id, "output is timelocked"));
+        }
+        let return_address = sdr_not_expired(&output, current_time)
+            .map(|storage_deposit_return| *storage_deposit_return.return_address())
+            .ok_or(Error::OutputNotDeclinable(
+                output_id,
+                "no storage deposit return or already expired",
+            ))?;
+        let return_unlock_condition = UnlockCondition::Address(AddressUnlockCondition::new(return_address));
+
+        // Only an output without native tokens and features fulfills the storage deposit return
+        let return_output = BasicOutputBuilder::new_with_amount(output.amount())?
+            .add_unlock_condition(return_unlock_condition.clone())
+            .finish_output(token_supply)?;
+        let mut outputs = vec![return_output];
+        let mut burn = Burn::new();
+        match (&output, declined_assets) {
+            (Output::Nft(nft_output), DeclinedAssets::Return) => {
+                // The sender feature can only be set by the address that unlocks the inputs
+                let features = nft_output
+                    .features()
+                    .iter()
+                    .filter(|feature| !matches!(feature, Feature::Sender(_)))
+                    .cloned()
+                    .collect::<Vec<Feature>>();
+                outputs.push(
+                    NftOutputBuilder::from(nft_output)
+                        .with_nft_id(nft_output.nft_id_non_null(&output_id))
+                        .with_unlock_conditions([return_unlock_condition])
+                        .with_features(features)
+                        .with_minimum_storage_deposit(rent_structure)
+                        .finish_output(token_supply)?,
+                );
+            }
+            (Output::Nft(nft_output), DeclinedAssets::Burn) => {
+                burn = burn.add_nft(nft_output.nft_id_non_null(&output_id));
+                for native_token in nft_output.native_tokens().iter() {
+                    burn = burn.add_native_token(*native_token.token_id(), *native_token.amount());
+                }
+            }
+            (Output::Basic(basic_output), DeclinedAssets::Return) if !basic_output.native_tokens().is_empty() => {
+                outputs.push(
+                    BasicOutputBuilder::new_with_minimum_storage_deposit(rent_structure)?
+                        .add_unlock_condition(return_unlock_condition)
+                        .with_native_tokens(basic_output.native_tokens().clone())
+                        .finish_output(token_supply)?,
+                );
+            }
+            (Output::Basic(basic_output), DeclinedAssets::Burn) => {
+                for native_token in basic_output.native_tokens().iter() {
+                    burn = burn.add_native_token(*native_token.token_id(), *native_token.amount());
+                }
+            }
+            _ => {}
+        }
+
+        // Additional inputs are only needed for the storage deposit of the returned NFT or native tokens
+        let mut options = options.unwrap_or_default();
+        options.custom_inputs = None;
+        options.mandatory_inputs = Some(vec![output_id]);
+        if declined_assets == DeclinedAssets::Burn {
+            options.burn = Some(burn);
+        }
+
+        self.prepare_transaction(outputs, Some(options)).await
+    }
+}
@@ -0,0 +1,172 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_client::block::{
+    address::Address,
+    output::{
+        unlock_condition::{AddressUnlockCondition, UnlockCondition},
+        BasicOutputBuilder, Output, Rent, RentStructure,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    account::{handle::AccountHandle, RemainderValueStrategy, TransactionOptions},
+    Error,
+};
+
+/// The storage deposits and amounts of a send, calculated before any transaction is prepared.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SendEstimate {
+    /// The amount that should be sent.
+    pub amount: u64,
+    /// The base coin amount that can be used as inputs, from the custom inputs, the address bucket or the available
+    /// balance.
+    pub available: u64,
+    /// The minimum amount of the output to the address.
+    pub required_storage_deposit: u64,
+    /// The amount that would be left over and needs to be sent back in a remainder output.
+    pub remainder: u64,
+    /// The minimum amount of the remainder output.
+    pub remainder_storage_deposit: u64,
+    /// If there is no remainder or the remainder covers its storage deposit.
+    pub remainder_viable: bool,
+    /// The highest amount that can be sent to the address, 0 if the available amount doesn't cover the storage
+    /// deposit.
+    pub max_sendable: u64,
+    /// If the amount can be sent with the available amount.
+    pub sendable: bool,
+}
+
+impl AccountHandle {
+    /// Calculate the storage deposits of sending an amount to an address with the options, the remainder that would be
+    /// left and the highest amount that can be sent, without preparing or signing a transaction. Outputs with native
+    /// tokens, nfts and aliases keep their storage deposit, so only the available base coin balance is used.
+    /// ```ignore
+    /// let estimate = account
+    ///     .estimate_send(1_000_000, "rms1qpszqzadsym6wpppd6z037dvlejmjuke7s24hm95s9fg9vpua7vluaw60xu", None)
+    ///     .await?;
+    /// if !estimate.sendable {
+    ///     println!("Can send at most {}", estimate.max_sendable);
+    /// }
+    /// ```
+    pub async fn estimate_send(
+        &self,
+        amount: u64,
+        address: &str,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<SendEstimate> {
+        log::debug!("[TRANSACTION] estimate_send");
+        let (_bech32_hrp, address) = Address::try_from_bech32(address)?;
+        let rent_structure = self.client.get_rent_structure().await?;
+        let token_supply = self.client.get_token_supply().await?;
+
+        let available = match options.as_ref() {
+            Some(TransactionOptions {
+                custom_inputs: Some(custom_inputs),
+                ..
+            }) => {
+                let account = self.read().await;
+                custom_inputs
+                    .iter()
+                    .filter(|output_id| !account.locked_outputs.contains(output_id))
+                    .filter_map(|output_id| account.unspent_outputs.get(output_id))
+                    .map(|output_data| output_data.output.amount())
+                    .sum()
+            }
+            Some(TransactionOptions {
+                address_bucket: Some(address_bucket),
+                ..
+            }) => self.address_bucket_balance(address_bucket).await?.base_coin.available,
+            _ => self.balance().await?.base_coin.available,
+        };
+
+        let remainder_address = match options.as_ref().map(|options| &options.remainder_value_strategy) {
+            Some(RemainderValueStrategy::CustomAddress(account_address)) => account_address.address.inner,
+            _ => {
+                self.addresses()
+                    .await?
+                    .first()
+                    .ok_or(Error::FailedToGetRemainder)?
+                    .address
+                    .inner
+            }
+        };
+
+        let required_storage_deposit = basic_output_storage_deposit(&rent_structure, address, token_supply)?;
+        let remainder_storage_deposit = basic_output_storage_deposit(&rent_structure, remainder_address, token_supply)?;
+
+        Ok(estimate(
+            amount,
+            available,
+            required_storage_deposit,
+            remainder_storage_deposit,
+        ))
+    }
+}
+
+// The storage deposit of a basic output that only has an address unlock condition
+fn basic_output_storage_deposit(
+    rent_structure: &RentStructure,
+    address: Address,
+    token_supply: u64,
+) -> crate::Result<u64> {
+    // The amount isn't important, only the size of the output
+    Ok(BasicOutputBuilder::new_with_amount(Output::AMOUNT_MIN)?
+        .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(address)))
+        .finish_output(token_supply)?
+        .rent_cost(rent_structure))
+}
+
+fn estimate(
+    amount: u64,
+    available: u64,
+    required_storage_deposit: u64,
+    remainder_storage_deposit: u64,
+) -> SendEstimate {
+    let remainder = available.saturating_sub(amount);
+    let remainder_viable = remainder == 0 || remainder >= remainder_storage_deposit;
+    // Sending everything doesn't need a remainder output
+    let max_sendable = if available >= required_storage_deposit {
+        available
+    } else {
+        0
+    };
+
+    SendEstimate {
+        amount,
+        available,
+        required_storage_deposit,
+        remainder,
+        remainder_storage_deposit,
+        remainder_viable,
+        max_sendable,
+        sendable: amount >= required_storage_deposit && amount <= available && remainder_viable,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimate_remainder() {
+        let sendable = estimate(1_000_000, 2_000_000, 42_600, 42_600);
+        assert!(sendable.sendable);
+        assert_eq!(sendable.remainder, 1_000_000);
+        assert_eq!(sendable.max_sendable, 2_000_000);
+
+        // The remainder wouldn't cover its storage deposit
+        let remainder_too_small = estimate(1_990_000, 2_000_000, 42_600, 42_600);
+        assert!(!remainder_too_small.remainder_viable);
+        assert!(!remainder_too_small.sendable);
+
+        let below_storage_deposit = estimate(1_000, 2_000_000, 42_600, 42_600);
+        assert!(!below_storage_deposit.sendable);
+
+        let insufficient = estimate(1_000_000, 10_000, 42_600, 42_600);
+        assert!(!insufficient.sendable);
+        assert_eq!(insufficient.max_sendable, 0);
+    }
+}
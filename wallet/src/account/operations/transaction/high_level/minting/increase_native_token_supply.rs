@@ -1,12 +1,15 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use iota_client::block::output::{
-    AliasOutputBuilder, FoundryOutputBuilder, Output, SimpleTokenScheme, TokenId, TokenScheme,
+use iota_client::{
+    api::PreparedTransactionData,
+    block::output::{AliasOutputBuilder, FoundryOutputBuilder, Output, SimpleTokenScheme, TokenId, TokenScheme},
 };
 use primitive_types::U256;
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "events")]
+use crate::account::operations::transaction::high_level::native_token_operations::NativeTokenOperation;
 use crate::{
     account::{
         handle::AccountHandle, operations::transaction::high_level::minting::mint_native_token::MintTokenTransaction,
@@ -50,10 +53,39 @@ impl AccountHandle {
         &self,
         token_id: TokenId,
         mint_amount: U256,
-        _increase_native_token_supply_options: Option<IncreaseNativeTokenSupplyOptions>,
+        increase_native_token_supply_options: Option<IncreaseNativeTokenSupplyOptions>,
         options: Option<TransactionOptions>,
     ) -> crate::Result<MintTokenTransaction> {
-        log::debug!("[TRANSACTION] increase_native_token_supply");
+        let simulate = options.as_ref().map_or(false, |options| options.simulate);
+        let prepared_transaction = self
+            .prepare_increase_native_token_supply(token_id, mint_amount, increase_native_token_supply_options, options)
+            .await?;
+        let transaction = self.sign_and_submit_or_simulate(prepared_transaction, simulate).await?;
+
+        #[cfg(feature = "events")]
+        if !simulate {
+            self.emit_native_token_operation(
+                NativeTokenOperation::Mint,
+                token_id,
+                mint_amount,
+                transaction.transaction_id,
+            )
+            .await;
+        }
+
+        Ok(MintTokenTransaction { token_id, transaction })
+    }
+
+    /// Function to prepare the transaction for
+    /// [AccountHandle.increase_native_token_supply()](crate::account::handle::AccountHandle.increase_native_token_supply)
+    pub async fn prepare_increase_native_token_supply(
+        &self,
+        token_id: TokenId,
+        mint_amount: U256,
+        _increase_native_token_supply_options: Option<IncreaseNativeTokenSupplyOptions>,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<PreparedTransactionData> {
+        log::debug!("[TRANSACTION] prepare_increase_native_token_supply");
 
         let account = self.read().await;
         let token_supply = self.client.get_token_supply().await?;
@@ -130,8 +162,7 @@ impl AccountHandle {
             // Native Tokens will be added automatically in the remainder output in try_select_inputs()
         ];
 
-        self.send(outputs, options)
-            .await
-            .map(|transaction| MintTokenTransaction { token_id, transaction })
+        self.verify_storage_deposits(&outputs).await?;
+        self.prepare_transaction(outputs, options).await
     }
 }
@@ -1,20 +1,26 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use iota_client::block::{
-    address::AliasAddress,
-    dto::U256Dto,
-    output::{
-        dto::AliasIdDto,
-        feature::{Feature, MetadataFeature},
-        unlock_condition::{ImmutableAliasAddressUnlockCondition, UnlockCondition},
-        AliasId, AliasOutputBuilder, FoundryId, FoundryOutputBuilder, Output, SimpleTokenScheme, TokenId, TokenScheme,
+use iota_client::{
+    api::{PreparedTransactionData, PreparedTransactionDataDto},
+    block::{
+        address::AliasAddress,
+        dto::U256Dto,
+        output::{
+            dto::AliasIdDto,
+            feature::{Feature, MetadataFeature},
+            unlock_condition::{ImmutableAliasAddressUnlockCondition, UnlockCondition},
+            AliasId, AliasOutputBuilder, FoundryId, FoundryOutputBuilder, Output, SimpleTokenScheme, TokenId,
+            TokenScheme,
+        },
+        DtoError,
     },
-    DtoError,
 };
 use primitive_types::U256;
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "events")]
+use crate::account::operations::transaction::high_level::native_token_operations::NativeTokenOperation;
 use crate::account::{
     handle::AccountHandle,
     types::{Transaction, TransactionDto},
@@ -103,6 +109,30 @@ impl From<&MintTokenTransaction> for MintTokenTransactionDto {
     }
 }
 
+/// A prepared minting native token transaction, that still needs to be signed and submitted
+#[derive(Debug, Clone)]
+pub struct PreparedMintTokenTransaction {
+    pub token_id: TokenId,
+    pub transaction: PreparedTransactionData,
+}
+
+/// Dto for PreparedMintTokenTransaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreparedMintTokenTransactionDto {
+    pub token_id: TokenId,
+    pub transaction: PreparedTransactionDataDto,
+}
+
+impl From<&PreparedMintTokenTransaction> for PreparedMintTokenTransactionDto {
+    fn from(value: &PreparedMintTokenTransaction) -> Self {
+        Self {
+            token_id: value.token_id,
+            transaction: PreparedTransactionDataDto::from(&value.transaction),
+        }
+    }
+}
+
 impl AccountHandle {
     /// Function to create a new foundry output with minted native tokens.
     /// Calls [AccountHandle.send()](crate::account::handle::AccountHandle.send) internally, the options can define the
@@ -127,7 +157,35 @@ impl AccountHandle {
         native_token_options: NativeTokenOptions,
         options: Option<TransactionOptions>,
     ) -> crate::Result<MintTokenTransaction> {
-        log::debug!("[TRANSACTION] mint_native_token");
+        let simulate = options.as_ref().map_or(false, |options| options.simulate);
+        #[cfg(feature = "events")]
+        let circulating_supply = native_token_options.circulating_supply;
+        let PreparedMintTokenTransaction { token_id, transaction } =
+            self.prepare_mint_native_token(native_token_options, options).await?;
+        let transaction = self.sign_and_submit_or_simulate(transaction, simulate).await?;
+
+        #[cfg(feature = "events")]
+        if !simulate {
+            self.emit_native_token_operation(
+                NativeTokenOperation::Create,
+                token_id,
+                circulating_supply,
+                transaction.transaction_id,
+            )
+            .await;
+        }
+
+        Ok(MintTokenTransaction { token_id, transaction })
+    }
+
+    /// Function to prepare the transaction for
+    /// [AccountHandle.mint_native_token()](crate::account::handle::AccountHandle.mint_native_token)
+    pub async fn prepare_mint_native_token(
+        &self,
+        native_token_options: NativeTokenOptions,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<PreparedMintTokenTransaction> {
+        log::debug!("[TRANSACTION] prepare_mint_native_token");
         let rent_structure = self.client.get_rent_structure().await?;
         let token_supply = self.client.get_token_supply().await?;
 
@@ -175,9 +233,10 @@ impl AccountHandle {
                     foundry_builder.finish_output(token_supply)?
                 }, // Native Tokens will be added automatically in the remainder output in try_select_inputs()
             ];
-            self.send(outputs, options)
+            self.verify_storage_deposits(&outputs).await?;
+            self.prepare_transaction(outputs, options)
                 .await
-                .map(|transaction| MintTokenTransaction { token_id, transaction })
+                .map(|transaction| PreparedMintTokenTransaction { token_id, transaction })
         } else {
             unreachable!("We checked if it's an alias output before")
         }
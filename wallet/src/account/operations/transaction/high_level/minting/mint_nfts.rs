@@ -4,12 +4,13 @@
 use iota_client::{
     api::PreparedTransactionData,
     block::{
-        address::Address,
+        address::{Address, NftAddress},
         output::{
             feature::{Feature, IssuerFeature, MetadataFeature, SenderFeature, TagFeature},
             unlock_condition::{AddressUnlockCondition, UnlockCondition},
-            NftId, NftOutputBuilder,
+            NftId, NftOutputBuilder, Output, OutputId,
         },
+        payload::transaction::TransactionEssence,
         DtoError,
     },
 };
@@ -115,8 +116,24 @@ impl AccountHandle {
         nfts_options: Vec<NftOptions>,
         options: Option<TransactionOptions>,
     ) -> crate::Result<Transaction> {
+        let simulate = options.as_ref().map_or(false, |options| options.simulate);
         let prepared_transaction = self.prepare_mint_nfts(nfts_options, options).await?;
-        self.sign_and_submit_transaction(prepared_transaction).await
+        let transaction = self.sign_and_submit_or_simulate(prepared_transaction, simulate).await?;
+
+        if !simulate {
+            self.record_minted_nfts(minted_nft_ids(&transaction)).await?;
+        }
+
+        Ok(transaction)
+    }
+
+    // Record the minted NFTs, so they can be listed after they were sent
+    pub(crate) async fn record_minted_nfts(&self, nft_ids: Vec<NftId>) -> crate::Result<()> {
+        let mut account = self.write().await;
+        account.minted_nfts.extend(nft_ids);
+        #[cfg(feature = "storage")]
+        self.save(Some(&account)).await?;
+        Ok(())
     }
 
     /// Function to prepare the transaction for
@@ -127,6 +144,57 @@ impl AccountHandle {
         options: Option<TransactionOptions>,
     ) -> crate::Result<PreparedTransactionData> {
         log::debug!("[TRANSACTION] prepare_mint_nfts");
+        let outputs = self.nft_outputs(nfts_options).await?;
+        self.prepare_transaction(outputs, options).await
+    }
+
+    /// Function to prepare the transaction for
+    /// [AccountHandle.mint_collection_nfts()](crate::account::handle::AccountHandle.mint_collection_nfts), the
+    /// collection NFT is transitioned in the same transaction and set as issuer of the new NFTs.
+    pub(crate) async fn prepare_mint_collection_nfts(
+        &self,
+        collection_id: NftId,
+        nfts_options: Vec<NftOptions>,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<PreparedTransactionData> {
+        log::debug!("[TRANSACTION] prepare_mint_collection_nfts");
+        let token_supply = self.client.get_token_supply().await?;
+        let bech32_hrp = self.client.get_bech32_hrp().await?;
+
+        let collection_output = self
+            .unspent_outputs(None)
+            .await?
+            .into_iter()
+            .find_map(|output_data| match output_data.output {
+                Output::Nft(nft_output) if nft_output.nft_id_non_null(&output_data.output_id) == collection_id => {
+                    Some(nft_output)
+                }
+                _ => None,
+            })
+            .ok_or(Error::NftNotFoundInUnspentOutputs)?;
+
+        // The issuer feature can only be set to the collection NFT if it's unlocked in the same transaction
+        let issuer = Address::Nft(NftAddress::from(collection_id)).to_bech32(&bech32_hrp);
+        let nfts_options = nfts_options
+            .into_iter()
+            .map(|nft_options| NftOptions {
+                issuer: Some(issuer.clone()),
+                ..nft_options
+            })
+            .collect();
+
+        let mut outputs = vec![
+            NftOutputBuilder::from(&collection_output)
+                .with_nft_id(collection_id)
+                .finish_output(token_supply)?,
+        ];
+        outputs.extend(self.nft_outputs(nfts_options).await?);
+
+        self.prepare_transaction(outputs, options).await
+    }
+
+    // Build the outputs of the NFTs that are minted
+    async fn nft_outputs(&self, nfts_options: Vec<NftOptions>) -> crate::Result<Vec<Output>> {
         let rent_structure = self.client.get_rent_structure().await?;
         let token_supply = self.client.get_token_supply().await?;
         let account_addresses = self.addresses().await?;
@@ -177,6 +245,24 @@ impl AccountHandle {
             outputs.push(nft_builder.finish_output(token_supply)?);
         }
 
-        self.prepare_transaction(outputs, options).await
+        Ok(outputs)
     }
 }
+
+// The ids of the NFTs that are created by the transaction
+pub(crate) fn minted_nft_ids(transaction: &Transaction) -> Vec<NftId> {
+    let TransactionEssence::Regular(essence) = transaction.payload.essence();
+    essence
+        .outputs()
+        .iter()
+        .enumerate()
+        .filter_map(|(index, output)| match output {
+            Output::Nft(nft_output) if nft_output.nft_id().is_null() => {
+                OutputId::new(transaction.transaction_id, index as u16)
+                    .ok()
+                    .map(|output_id| nft_output.nft_id_non_null(&output_id))
+            }
+            _ => None,
+        })
+        .collect()
+}
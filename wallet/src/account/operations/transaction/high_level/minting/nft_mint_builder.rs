@@ -0,0 +1,286 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use iota_client::block::{
+    address::Address,
+    output::{NftId, OUTPUT_COUNT_MAX},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    account::{
+        handle::AccountHandle,
+        operations::transaction::high_level::minting::mint_nfts::minted_nft_ids,
+        types::{Transaction, TransactionDto},
+        TransactionOptions,
+    },
+    Error, NftOptions,
+};
+
+const IRC_27_VERSION: &str = "v1.0";
+
+/// NFT metadata following the IRC-27 standard, stored as immutable metadata feature of the NFT.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "standard", rename = "IRC27", rename_all = "camelCase")]
+pub struct Irc27Metadata {
+    /// The version of the standard, `v1.0`.
+    pub version: String,
+    /// The media type (MIME) of the asset, like `image/png` or `video/mp4`.
+    #[serde(rename = "type")]
+    pub media_type: String,
+    /// URI pointing to the NFT file location.
+    pub uri: String,
+    /// The human-readable name of the NFT.
+    pub name: String,
+    /// The human-readable name of the collection.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collection_name: Option<String>,
+    /// Bech32 encoded royalty payment addresses mapped to their share of the payout, between 0 and 1.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub royalties: BTreeMap<String, f64>,
+    /// The human-readable name of the creator.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issuer_name: Option<String>,
+    /// The human-readable description of the NFT.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Additional attributes of the NFT.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attributes: Vec<Irc27Attribute>,
+}
+
+/// An attribute of [`Irc27Metadata`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Irc27Attribute {
+    /// The name of the trait.
+    pub trait_type: String,
+    /// The value of the trait.
+    pub value: serde_json::Value,
+    /// How the trait is displayed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_type: Option<String>,
+}
+
+impl Irc27Metadata {
+    /// Create metadata with the required fields.
+    pub fn new(media_type: impl Into<String>, uri: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            version: IRC_27_VERSION.to_string(),
+            media_type: media_type.into(),
+            uri: uri.into(),
+            name: name.into(),
+            collection_name: None,
+            royalties: BTreeMap::new(),
+            issuer_name: None,
+            description: None,
+            attributes: Vec::new(),
+        }
+    }
+
+    /// Check the fields of the metadata.
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.version != IRC_27_VERSION {
+            return Err(Error::InvalidIrc27Metadata(format!(
+                "unsupported version {}",
+                self.version
+            )));
+        }
+        if self.name.trim().is_empty() {
+            return Err(Error::InvalidIrc27Metadata("name is empty".to_string()));
+        }
+        match self.media_type.split_once('/') {
+            Some((kind, subtype)) if !kind.is_empty() && !subtype.is_empty() && !subtype.contains('/') => {}
+            _ => {
+                return Err(Error::InvalidIrc27Metadata(format!(
+                    "invalid media type {}",
+                    self.media_type
+                )));
+            }
+        }
+        match self.uri.split_once("://") {
+            Some((scheme, location)) if !scheme.is_empty() && !location.is_empty() && !self.uri.contains(' ') => {}
+            _ => return Err(Error::InvalidIrc27Metadata(format!("invalid uri {}", self.uri))),
+        }
+        for (address, share) in &self.royalties {
+            Address::try_from_bech32(address)?;
+            if !(0.0..=1.0).contains(share) {
+                return Err(Error::InvalidIrc27Metadata(format!(
+                    "royalty share {share} of {address} isn't between 0 and 1"
+                )));
+            }
+        }
+        if self.royalties.values().sum::<f64>() > 1.0 {
+            return Err(Error::InvalidIrc27Metadata(
+                "royalty shares sum up to more than 1".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Validate the metadata and serialize it to the bytes of a metadata feature.
+    pub fn to_bytes(&self) -> crate::Result<Vec<u8>> {
+        self.validate()?;
+        Ok(serde_json::to_vec(self)?)
+    }
+}
+
+/// An NFT with IRC-27 metadata for [`NftMintBuilder::add_nft()`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Irc27Nft {
+    /// The metadata of the NFT.
+    pub metadata: Irc27Metadata,
+    /// Bech32 encoded address to which the NFT is minted, the first address of the account if not set.
+    #[serde(default)]
+    pub address: Option<String>,
+}
+
+/// Builds the NFTs that are minted together in one transaction with [`AccountHandle::mint_nfts_with_builder()`].
+/// ```ignore
+/// let builder = NftMintBuilder::new()
+///     .add_nft(Irc27Metadata::new("image/png", "https://mywebsite.com/nft-1.png", "My NFT #1"), None)?
+///     .add_nft(Irc27Metadata::new("image/png", "https://mywebsite.com/nft-2.png", "My NFT #2"), None)?;
+/// let minted = account.mint_nfts_with_builder(builder, None).await?;
+/// println!("{:?}", minted.nft_ids);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct NftMintBuilder {
+    nfts: Vec<NftOptions>,
+}
+
+impl NftMintBuilder {
+    /// Create an empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an NFT with IRC-27 metadata as immutable metadata, minted to the bech32 encoded address or to the first
+    /// address of the account. The metadata is validated before it's added.
+    pub fn add_nft(mut self, metadata: Irc27Metadata, address: Option<String>) -> crate::Result<Self> {
+        self.check_batch_size()?;
+        self.nfts.push(NftOptions {
+            address,
+            sender: None,
+            metadata: None,
+            tag: None,
+            issuer: None,
+            immutable_metadata: Some(metadata.to_bytes()?),
+        });
+        Ok(self)
+    }
+
+    /// Add an NFT with custom options.
+    pub fn add_nft_options(mut self, nft_options: NftOptions) -> crate::Result<Self> {
+        self.check_batch_size()?;
+        self.nfts.push(nft_options);
+        Ok(self)
+    }
+
+    /// Set the bech32 encoded issuer of all NFTs added so far.
+    pub fn with_issuer(mut self, issuer: impl Into<String>) -> Self {
+        let issuer = issuer.into();
+        for nft in &mut self.nfts {
+            nft.issuer = Some(issuer.clone());
+        }
+        self
+    }
+
+    /// Returns the options of the NFTs.
+    pub fn finish(self) -> crate::Result<Vec<NftOptions>> {
+        if self.nfts.is_empty() {
+            return Err(Error::MintingFailed("no NFTs to mint".to_string()));
+        }
+        Ok(self.nfts)
+    }
+
+    // All NFTs are minted in one transaction, which also needs room for a remainder output
+    fn check_batch_size(&self) -> crate::Result<()> {
+        if self.nfts.len() + 1 >= OUTPUT_COUNT_MAX as usize {
+            return Err(Error::MintingFailed(format!(
+                "can't mint more than {} NFTs in one transaction",
+                OUTPUT_COUNT_MAX - 1
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// The result of minting NFTs with [`AccountHandle::mint_nfts_with_builder()`].
+#[derive(Debug, Clone)]
+pub struct MintNftsTransaction {
+    /// The ids of the minted NFTs, in the order they were added to the builder.
+    pub nft_ids: Vec<NftId>,
+    pub transaction: Transaction,
+}
+
+/// Dto for MintNftsTransaction
+#[derive(Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MintNftsTransactionDto {
+    pub nft_ids: Vec<NftId>,
+    pub transaction: TransactionDto,
+}
+
+impl From<&MintNftsTransaction> for MintNftsTransactionDto {
+    fn from(value: &MintNftsTransaction) -> Self {
+        Self {
+            nft_ids: value.nft_ids.clone(),
+            transaction: TransactionDto::from(&value.transaction),
+        }
+    }
+}
+
+impl AccountHandle {
+    /// Mint the NFTs of the builder in one transaction. The ids of the minted NFTs are returned and recorded in the
+    /// account, so they can be listed with [`AccountHandle::minted_nfts()`].
+    pub async fn mint_nfts_with_builder(
+        &self,
+        builder: NftMintBuilder,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<MintNftsTransaction> {
+        log::debug!("[TRANSACTION] mint_nfts_with_builder");
+        let transaction = self.mint_nfts(builder.finish()?, options).await?;
+        Ok(MintNftsTransaction {
+            nft_ids: minted_nft_ids(&transaction),
+            transaction,
+        })
+    }
+
+    /// Mint the NFTs of the builder as children of a collection NFT the account owns. The collection NFT is unlocked
+    /// in the same transaction and set as immutable issuer of the new NFTs, so everyone can verify that they belong to
+    /// the collection, for example with [`AccountHandle::nfts()`]. Issuers that were set on the builder are replaced.
+    pub async fn mint_collection_nfts(
+        &self,
+        collection_id: NftId,
+        builder: NftMintBuilder,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<MintNftsTransaction> {
+        log::debug!("[TRANSACTION] mint_collection_nfts {collection_id}");
+        let nfts_options = builder.finish()?;
+        // The collection NFT and the remainder also need an output
+        if nfts_options.len() + 2 > OUTPUT_COUNT_MAX as usize {
+            return Err(Error::MintingFailed(format!(
+                "can't mint more than {} NFTs of a collection in one transaction",
+                OUTPUT_COUNT_MAX - 2
+            )));
+        }
+
+        let simulate = options.as_ref().map_or(false, |options| options.simulate);
+        let prepared_transaction = self
+            .prepare_mint_collection_nfts(collection_id, nfts_options, options)
+            .await?;
+        let transaction = self.sign_and_submit_or_simulate(prepared_transaction, simulate).await?;
+        let nft_ids = minted_nft_ids(&transaction);
+        if !simulate {
+            self.record_minted_nfts(nft_ids.clone()).await?;
+        }
+
+        Ok(MintNftsTransaction { nft_ids, transaction })
+    }
+
+    /// Returns the ids of the NFTs that were minted by the account, also the ones that were sent or burned since.
+    pub async fn minted_nfts(&self) -> Vec<NftId> {
+        self.read().await.minted_nfts.clone()
+    }
+}
@@ -1,11 +1,21 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+pub(crate) mod alias_lifecycle;
+pub(crate) mod atomic_swap;
 pub(crate) mod burning_melting;
 pub(crate) mod create_alias;
+pub(crate) mod decline_output;
+pub(crate) mod estimate_send;
 pub(crate) mod minimum_storage_deposit;
 pub(crate) mod minting;
+pub(crate) mod native_token_operations;
+pub(crate) mod send_all;
 pub(crate) mod send_amount;
+pub(crate) mod send_batch;
 pub(crate) mod send_micro_transaction;
 pub(crate) mod send_native_tokens;
 pub(crate) mod send_nft;
+pub(crate) mod send_with_conditions;
+pub(crate) mod sweep;
+pub(crate) mod vesting;
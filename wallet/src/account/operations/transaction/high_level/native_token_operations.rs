@@ -0,0 +1,170 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+#[cfg(feature = "events")]
+use iota_client::block::{dto::U256Dto, payload::transaction::TransactionId};
+use iota_client::{
+    api::PreparedTransactionData,
+    block::{
+        output::{Output, Rent, TokenId},
+        payload::transaction::TransactionEssence,
+    },
+};
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+
+use crate::account::{
+    handle::AccountHandle,
+    operations::transaction::high_level::minting::{
+        increase_native_token_supply::IncreaseNativeTokenSupplyOptions, mint_native_token::NativeTokenOptions,
+    },
+    TransactionOptions,
+};
+#[cfg(feature = "events")]
+use crate::events::types::{NativeTokenOperationEvent, WalletEvent};
+
+/// A stage in the lifecycle of a native token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NativeTokenOperation {
+    /// A foundry was created and the initial supply minted.
+    Create,
+    /// The supply of a foundry was increased.
+    Mint,
+    /// Tokens were melted with their foundry.
+    Melt,
+    /// Tokens were burned without their foundry.
+    Burn,
+}
+
+/// The storage deposits of a native token operation, calculated from a prepared transaction. Transactions don't have
+/// fees, the base coins only need to cover the storage deposit of the outputs.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NativeTokenEstimate {
+    /// Amount of inputs of the transaction.
+    pub input_count: usize,
+    /// Amount of outputs of the transaction.
+    pub output_count: usize,
+    /// The base coin amount of the inputs.
+    pub inputs_amount: u64,
+    /// The minimum base coin amount that the outputs of the transaction need for their storage deposit.
+    pub required_storage_deposit: u64,
+    /// The base coin amount that is additionally locked in alias and foundry outputs and isn't available anymore,
+    /// for example the storage deposit of a new foundry.
+    pub additional_storage_deposit: u64,
+}
+
+impl AccountHandle {
+    /// Estimate the storage deposits of creating a foundry with
+    /// [AccountHandle.mint_native_token()](crate::account::handle::AccountHandle.mint_native_token). The transaction is
+    /// prepared and discarded again, so the estimate uses the same inputs.
+    pub async fn estimate_mint_native_token(
+        &self,
+        native_token_options: NativeTokenOptions,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<NativeTokenEstimate> {
+        log::debug!("[TRANSACTION] estimate_mint_native_token");
+        let prepared_transaction = self.prepare_mint_native_token(native_token_options, options).await?;
+        self.estimate_and_discard(prepared_transaction.transaction).await
+    }
+
+    /// Estimate the storage deposits of
+    /// [AccountHandle.increase_native_token_supply()](crate::account::handle::AccountHandle.increase_native_token_supply).
+    pub async fn estimate_increase_native_token_supply(
+        &self,
+        token_id: TokenId,
+        mint_amount: U256,
+        increase_native_token_supply_options: Option<IncreaseNativeTokenSupplyOptions>,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<NativeTokenEstimate> {
+        log::debug!("[TRANSACTION] estimate_increase_native_token_supply");
+        let prepared_transaction = self
+            .prepare_increase_native_token_supply(token_id, mint_amount, increase_native_token_supply_options, options)
+            .await?;
+        self.estimate_and_discard(prepared_transaction).await
+    }
+
+    /// Estimate the storage deposits of
+    /// [AccountHandle.decrease_native_token_supply()](crate::account::handle::AccountHandle.decrease_native_token_supply).
+    pub async fn estimate_decrease_native_token_supply(
+        &self,
+        token_id: TokenId,
+        melt_amount: U256,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<NativeTokenEstimate> {
+        log::debug!("[TRANSACTION] estimate_decrease_native_token_supply");
+        let prepared_transaction = self
+            .prepare_decrease_native_token_supply(token_id, melt_amount, options)
+            .await?;
+        self.estimate_and_discard(prepared_transaction).await
+    }
+
+    /// Estimate the storage deposits of
+    /// [AccountHandle.burn_native_token()](crate::account::handle::AccountHandle.burn_native_token).
+    pub async fn estimate_burn_native_token(
+        &self,
+        token_id: TokenId,
+        burn_amount: U256,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<NativeTokenEstimate> {
+        log::debug!("[TRANSACTION] estimate_burn_native_token");
+        let prepared_transaction = self.prepare_burn_native_token(token_id, burn_amount, options).await?;
+        self.estimate_and_discard(prepared_transaction).await
+    }
+
+    // Calculate the estimate of a prepared transaction and unlock its inputs again
+    async fn estimate_and_discard(
+        &self,
+        prepared_transaction_data: PreparedTransactionData,
+    ) -> crate::Result<NativeTokenEstimate> {
+        let rent_structure = self.client.get_rent_structure().await?;
+        let TransactionEssence::Regular(essence) = &prepared_transaction_data.essence;
+        let inputs: Vec<&Output> = prepared_transaction_data
+            .inputs_data
+            .iter()
+            .map(|input| &input.output)
+            .collect();
+        let outputs: Vec<&Output> = essence.outputs().iter().collect();
+
+        let estimate = NativeTokenEstimate {
+            input_count: inputs.len(),
+            output_count: outputs.len(),
+            inputs_amount: inputs.iter().map(|output| output.amount()).sum(),
+            required_storage_deposit: outputs.iter().map(|output| output.rent_cost(&rent_structure)).sum(),
+            additional_storage_deposit: alias_and_foundry_amount(&outputs)
+                .saturating_sub(alias_and_foundry_amount(&inputs)),
+        };
+
+        self.unlock_inputs(prepared_transaction_data.inputs_data).await?;
+        Ok(estimate)
+    }
+
+    // Emit an event for a native token operation that was submitted
+    #[cfg(feature = "events")]
+    pub(crate) async fn emit_native_token_operation(
+        &self,
+        operation: NativeTokenOperation,
+        token_id: TokenId,
+        amount: U256,
+        transaction_id: TransactionId,
+    ) {
+        let account_index = *self.read().await.index();
+        self.event_emitter.lock().await.emit(
+            account_index,
+            WalletEvent::NativeTokenOperation(NativeTokenOperationEvent {
+                operation,
+                token_id,
+                amount: U256Dto::from(&amount),
+                transaction_id,
+            }),
+        );
+    }
+}
+
+fn alias_and_foundry_amount(outputs: &[&Output]) -> u64 {
+    outputs
+        .iter()
+        .filter(|output| matches!(output, Output::Alias(_) | Output::Foundry(_)))
+        .map(|output| output.amount())
+        .sum()
+}
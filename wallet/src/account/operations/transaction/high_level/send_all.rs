@@ -0,0 +1,175 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{cmp::Reverse, collections::HashSet};
+
+use iota_client::{
+    api::{input_selection::minimum_storage_deposit_basic_output, PreparedTransactionData},
+    block::{
+        address::Address,
+        input::INPUT_COUNT_MAX,
+        output::{
+            unlock_condition::{AddressUnlockCondition, UnlockCondition},
+            BasicOutputBuilder, NativeTokens, NativeTokensBuilder, Output, OutputId,
+        },
+    },
+};
+
+use crate::{
+    account::{
+        handle::AccountHandle,
+        operations::{
+            output_claiming::get_new_native_token_count, spam_filter::account_addresses,
+            transaction::high_level::sweep::can_be_swept,
+        },
+        types::{OutputData, Transaction},
+        TransactionOptions,
+    },
+    Error,
+};
+
+impl AccountHandle {
+    /// Sends the highest amount that can be transferred in a single transaction to an address, so it doesn't need to
+    /// be guessed from the balance. The basic outputs that can be unlocked now are used as inputs, the largest ones
+    /// first if there are more than fit into a transaction. Without `include_native_tokens` the native tokens stay in
+    /// the account in a remainder with exactly their minimum storage deposit, everything else is sent. Nft, alias and
+    /// foundry outputs, quarantined outputs and the voting output aren't used.
+    /// ```ignore
+    /// let transaction = account
+    ///     .send_all("rms1qpszqzadsym6wpppd6z037dvlejmjuke7s24hm95s9fg9vpua7vluaw60xu", false, None)
+    ///     .await?;
+    /// ```
+    pub async fn send_all(
+        &self,
+        address: &str,
+        include_native_tokens: bool,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<Transaction> {
+        let simulate = options.as_ref().map_or(false, |options| options.simulate);
+        let idempotency_key = options.as_ref().and_then(|options| options.idempotency_key.clone());
+        self.send_idempotent(
+            idempotency_key,
+            simulate,
+            self.prepare_send_all(address, include_native_tokens, options),
+        )
+        .await
+    }
+
+    /// Prepares the transaction for [`AccountHandle::send_all()`].
+    pub async fn prepare_send_all(
+        &self,
+        address: &str,
+        include_native_tokens: bool,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<PreparedTransactionData> {
+        log::debug!("[TRANSACTION] prepare_send_all");
+        let (_bech32_hrp, address) = Address::try_from_bech32(address)?;
+        let rent_structure = self.client.get_rent_structure().await?;
+        let token_supply = self.client.get_token_supply().await?;
+        let mut options = options.unwrap_or_default();
+
+        let inputs = self.get_send_all_inputs(options.address_bucket.as_deref()).await?;
+        let amount: u64 = inputs.iter().map(|output_data| output_data.output.amount()).sum();
+        let mut native_tokens_builder = NativeTokensBuilder::new();
+        for output_data in &inputs {
+            if let Some(native_tokens) = output_data.output.native_tokens() {
+                native_tokens_builder.add_native_tokens(native_tokens.clone())?;
+            }
+        }
+        let native_tokens = native_tokens_builder.finish()?;
+
+        // Native tokens that aren't sent keep only the storage deposit they need, the input selection adds the
+        // remainder for them
+        let mut output_builder = BasicOutputBuilder::new_with_minimum_storage_deposit(rent_structure.clone())?
+            .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(address)));
+        let remainder_amount = if include_native_tokens || native_tokens.is_empty() {
+            output_builder = output_builder.with_native_tokens(native_tokens);
+            0
+        } else {
+            minimum_storage_deposit_basic_output(&rent_structure, &Some(native_tokens), token_supply)?
+        };
+        let required = output_builder.clone().finish_output(token_supply)?.amount() + remainder_amount;
+        if amount < required {
+            return Err(Error::InsufficientFunds {
+                available: amount,
+                required,
+            });
+        }
+        let output = output_builder
+            .with_amount(amount - remainder_amount)?
+            .finish_output(token_supply)?;
+
+        options.custom_inputs = Some(inputs.iter().map(|output_data| output_data.output_id).collect());
+        options.mandatory_inputs = None;
+        self.prepare_transaction(vec![output], Some(options)).await
+    }
+
+    // The basic outputs that can be sent, the largest first, limited by the inputs and native tokens that fit into a
+    // transaction
+    async fn get_send_all_inputs(&self, address_bucket: Option<&str>) -> crate::Result<Vec<OutputData>> {
+        #[cfg(feature = "participation")]
+        let voting_output = self.get_voting_output().await?;
+        let current_time = self.client.get_time_checked().await?;
+        let account = self.read().await;
+
+        let bucket_addresses: Option<HashSet<Address>> = address_bucket
+            .map(|address_bucket| {
+                account
+                    .address_buckets
+                    .get(address_bucket)
+                    .map(|addresses| addresses.iter().map(|address| address.inner).collect())
+                    .ok_or_else(|| Error::AddressBucketNotFound(address_bucket.to_string()))
+            })
+            .transpose()?;
+        let all_account_addresses = account_addresses(&account);
+
+        let mut candidates = Vec::new();
+        for (output_id, output_data) in account.unspent_outputs() {
+            if account.locked_outputs.contains(output_id)
+                || !matches!(output_data.output, Output::Basic(_))
+                || bucket_addresses.as_ref().map_or(false, |bucket_addresses| {
+                    !bucket_addresses.contains(&output_data.address)
+                })
+                || account.spam_filter.check(output_data, &all_account_addresses).is_some()
+            {
+                continue;
+            }
+            #[cfg(feature = "participation")]
+            if voting_output
+                .as_ref()
+                .map_or(false, |voting_output| voting_output.output_id == *output_id)
+            {
+                continue;
+            }
+            if can_be_swept(output_data, current_time, &account.addresses_with_unspent_outputs)? {
+                candidates.push(output_data);
+            }
+        }
+        candidates.sort_by_key(|output_data| (Reverse(output_data.output.amount()), output_data.output_id));
+
+        let mut inputs = Vec::new();
+        let mut native_tokens = NativeTokensBuilder::new();
+        for output_data in candidates {
+            if inputs.len() >= INPUT_COUNT_MAX.into() {
+                break;
+            }
+            if let Some(output_native_tokens) = output_data.output.native_tokens() {
+                // The native tokens need to fit into a single output
+                if get_new_native_token_count(&native_tokens, output_native_tokens)? > NativeTokens::COUNT_MAX.into() {
+                    continue;
+                }
+                native_tokens.add_native_tokens(output_native_tokens.clone())?;
+            }
+            inputs.push(output_data.clone());
+        }
+        log::debug!(
+            "[TRANSACTION] send all inputs: {:?}",
+            inputs
+                .iter()
+                .map(|output_data| output_data.output_id)
+                .collect::<Vec<OutputId>>()
+        );
+
+        Ok(inputs)
+    }
+}
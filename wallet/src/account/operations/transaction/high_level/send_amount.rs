@@ -46,8 +46,14 @@ impl AccountHandle {
         addresses_with_amount: Vec<AddressWithAmount>,
         options: Option<TransactionOptions>,
     ) -> crate::Result<Transaction> {
-        let prepared_transaction = self.prepare_send_amount(addresses_with_amount, options).await?;
-        self.sign_and_submit_transaction(prepared_transaction).await
+        let simulate = options.as_ref().map_or(false, |options| options.simulate);
+        let idempotency_key = options.as_ref().and_then(|options| options.idempotency_key.clone());
+        self.send_idempotent(
+            idempotency_key,
+            simulate,
+            self.prepare_send_amount(addresses_with_amount, options),
+        )
+        .await
     }
 
     /// Function to prepare the transaction for
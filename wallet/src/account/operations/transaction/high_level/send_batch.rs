@@ -0,0 +1,196 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_client::block::{
+    address::Address,
+    output::{
+        feature::{Feature, MetadataFeature, TagFeature},
+        unlock_condition::{AddressUnlockCondition, UnlockCondition},
+        BasicOutputBuilder, Output, OutputId, OUTPUT_COUNT_MAX,
+    },
+    payload::transaction::TransactionEssence,
+    DtoError,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::account::{
+    handle::AccountHandle,
+    types::{Transaction, TransactionDto},
+    TransactionOptions,
+};
+
+/// A recipient for [`AccountHandle::send_batch()`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchRecipient {
+    /// Bech32 encoded address
+    pub address: String,
+    /// Amount
+    pub amount: u64,
+    /// Hex encoded tag feature of the output
+    #[serde(default)]
+    pub tag: Option<String>,
+    /// Hex encoded metadata feature of the output
+    #[serde(default)]
+    pub metadata: Option<String>,
+}
+
+/// The result of a batch send.
+#[derive(Debug, Clone, Default)]
+pub struct BatchSend {
+    /// The transactions that were sent.
+    pub transactions: Vec<Transaction>,
+    /// The result for each recipient, in the order of the recipients.
+    pub recipients: Vec<RecipientResult>,
+}
+
+/// Dto for the result of a batch send.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchSendDto {
+    /// The transactions that were sent.
+    pub transactions: Vec<TransactionDto>,
+    /// The result for each recipient, in the order of the recipients.
+    pub recipients: Vec<RecipientResult>,
+}
+
+impl From<&BatchSend> for BatchSendDto {
+    fn from(value: &BatchSend) -> Self {
+        Self {
+            transactions: value.transactions.iter().map(TransactionDto::from).collect(),
+            recipients: value.recipients.clone(),
+        }
+    }
+}
+
+/// The output that was created for a recipient, or the error why it wasn't sent.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecipientResult {
+    /// The id of the output for the recipient.
+    pub output_id: Option<OutputId>,
+    /// The error of the output or of its transaction.
+    pub error: Option<String>,
+}
+
+impl AccountHandle {
+    /// Send individual amounts with optional tag and metadata features to many recipients. The outputs are split into
+    /// as many transactions as needed, a transaction is only sent after the previous one was included, so its
+    /// remainder can be used as input. Recipients that fail, because their output is invalid or their transaction
+    /// failed, don't stop the other transactions. The options are used for every transaction.
+    /// ```ignore
+    /// let batch = account
+    ///     .send_batch(
+    ///         vec![BatchRecipient {
+    ///             address: "rms1qpszqzadsym6wpppd6z037dvlejmjuke7s24hm95s9fg9vpua7vluaw60xu".to_string(),
+    ///             amount: 1_000_000,
+    ///             tag: Some(prefix_hex::encode(b"payroll")),
+    ///             metadata: None,
+    ///         }],
+    ///         None,
+    ///     )
+    ///     .await?;
+    /// println!("{:?}", batch.recipients[0]);
+    /// ```
+    pub async fn send_batch(
+        &self,
+        recipients: Vec<BatchRecipient>,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<BatchSend> {
+        log::debug!("[TRANSACTION] send_batch to {} recipients", recipients.len());
+        let token_supply = self.client.get_token_supply().await?;
+        let simulate = options.as_ref().map_or(false, |options| options.simulate);
+
+        let mut batch_send = BatchSend {
+            transactions: Vec::new(),
+            recipients: vec![RecipientResult::default(); recipients.len()],
+        };
+
+        // Recipients with an invalid output are reported directly
+        let mut outputs = Vec::new();
+        for (index, recipient) in recipients.iter().enumerate() {
+            match build_output(recipient, token_supply) {
+                Ok(output) => outputs.push((index, output)),
+                Err(error) => batch_send.recipients[index].error = Some(error.to_string()),
+            }
+        }
+
+        // Leave room for a remainder output
+        let chunks: Vec<&[(usize, Output)]> = outputs.chunks(OUTPUT_COUNT_MAX as usize - 1).collect();
+        let chunk_count = chunks.len();
+        for (chunk_index, chunk) in chunks.into_iter().enumerate() {
+            let chunk_outputs = chunk.iter().map(|(_, output)| output.clone()).collect();
+            // Every transaction of the batch gets its own idempotency key, so a retried batch only sends the missing
+            // transactions
+            let mut chunk_options = options.clone();
+            if let Some(options) = chunk_options.as_mut() {
+                options.idempotency_key = options
+                    .idempotency_key
+                    .take()
+                    .map(|idempotency_key| format!("{idempotency_key}/{chunk_index}"));
+            }
+            let result = match self.finish_transaction(chunk_outputs, chunk_options).await {
+                // Wait until the transaction is included and synced, so the next one can use its remainder
+                Ok(transaction) if !simulate && chunk_index + 1 < chunk_count => self
+                    .retry_transaction_until_included(&transaction.transaction_id, None, None)
+                    .await
+                    .map(|_| transaction),
+                result => result,
+            };
+            match result {
+                Ok(transaction) => {
+                    assign_output_ids(&transaction, chunk, &mut batch_send.recipients)?;
+                    batch_send.transactions.push(transaction);
+                    if !simulate && chunk_index + 1 < chunk_count {
+                        self.sync(None).await?;
+                    }
+                }
+                Err(error) => {
+                    log::warn!("[TRANSACTION] send_batch transaction failed: {error}");
+                    for (index, _) in chunk {
+                        batch_send.recipients[*index].error = Some(error.to_string());
+                    }
+                }
+            }
+        }
+
+        Ok(batch_send)
+    }
+}
+
+fn build_output(recipient: &BatchRecipient, token_supply: u64) -> crate::Result<Output> {
+    let (_bech32_hrp, address) = Address::try_from_bech32(&recipient.address)?;
+    let mut output_builder = BasicOutputBuilder::new_with_amount(recipient.amount)?
+        .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(address)));
+    if let Some(tag) = &recipient.tag {
+        output_builder = output_builder.add_feature(Feature::Tag(TagFeature::new(
+            prefix_hex::decode(tag).map_err(|_| DtoError::InvalidField("tag"))?,
+        )?));
+    }
+    if let Some(metadata) = &recipient.metadata {
+        output_builder = output_builder.add_feature(Feature::Metadata(MetadataFeature::new(
+            prefix_hex::decode(metadata).map_err(|_| DtoError::InvalidField("metadata"))?,
+        )?));
+    }
+    Ok(output_builder.finish_output(token_supply)?)
+}
+
+// Find the outputs of the recipients in the transaction, equal outputs are assigned in order
+fn assign_output_ids(
+    transaction: &Transaction,
+    chunk: &[(usize, Output)],
+    recipients: &mut [RecipientResult],
+) -> crate::Result<()> {
+    let TransactionEssence::Regular(essence) = transaction.payload.essence();
+    let transaction_outputs = essence.outputs();
+    let mut assigned = vec![false; transaction_outputs.len()];
+    for (index, output) in chunk {
+        if let Some(output_index) =
+            (0..transaction_outputs.len()).find(|i| !assigned[*i] && &transaction_outputs[*i] == output)
+        {
+            assigned[output_index] = true;
+            recipients[*index].output_id = Some(OutputId::new(transaction.transaction_id, output_index as u16)?);
+        }
+    }
+    Ok(())
+}
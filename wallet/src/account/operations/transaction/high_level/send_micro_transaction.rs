@@ -70,10 +70,14 @@ impl AccountHandle {
         options: Option<TransactionOptions>,
     ) -> crate::Result<Transaction> {
         log::debug!("[TRANSACTION] send_micro_transaction");
-        let prepared_transaction = self
-            .prepare_send_micro_transaction(addresses_with_micro_amount, options)
-            .await?;
-        self.sign_and_submit_transaction(prepared_transaction).await
+        let simulate = options.as_ref().map_or(false, |options| options.simulate);
+        let idempotency_key = options.as_ref().and_then(|options| options.idempotency_key.clone());
+        self.send_idempotent(
+            idempotency_key,
+            simulate,
+            self.prepare_send_micro_transaction(addresses_with_micro_amount, options),
+        )
+        .await
     }
 
     /// Function to prepare the transaction for
@@ -0,0 +1,473 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use iota_client::{
+    api::PreparedTransactionData,
+    block::{
+        address::Address,
+        output::{
+            unlock_condition::{
+                AddressUnlockCondition, ExpirationUnlockCondition, StorageDepositReturnUnlockCondition,
+                TimelockUnlockCondition, UnlockCondition,
+            },
+            BasicOutputBuilder, NftOutputBuilder, Output, Rent,
+        },
+    },
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    account::{
+        handle::AccountHandle,
+        operations::transaction::high_level::minimum_storage_deposit::minimum_storage_deposit_basic_native_tokens,
+        types::{Transaction, TransactionDto},
+        TransactionOptions,
+    },
+    AddressAndNftId, AddressWithAmount, Error,
+};
+
+/// Conditions that are added to the outputs sent with [`AccountHandle::send_with_conditions()`] and
+/// [`AccountHandle::send_nft_with_conditions()`].
+/// ```ignore
+/// let send_options = SendOptions::new()
+///     .expires_in(Duration::from_secs(60 * 60 * 24))
+///     .return_storage_deposit();
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SendOptions {
+    /// Unix timestamp in seconds after which the outputs can only be unlocked by the return address, if they weren't
+    /// claimed by the recipients before.
+    #[serde(default)]
+    pub expiration_unix_time: Option<u32>,
+    /// Seconds after the current time of the node after which the outputs expire, ignored if `expiration_unix_time`
+    /// is set.
+    #[serde(default)]
+    pub expires_in_seconds: Option<u32>,
+    /// Unix timestamp in seconds before which the recipients can't unlock the outputs.
+    #[serde(default)]
+    pub timelock_unix_time: Option<u32>,
+    /// Seconds after the current time of the node before which the recipients can't unlock the outputs, ignored if
+    /// `timelock_unix_time` is set.
+    #[serde(default)]
+    pub locked_for_seconds: Option<u32>,
+    /// Return the storage deposit of the outputs with a storage deposit return unlock condition, so the recipients
+    /// only keep the sent amount. The storage deposit is always returned if the amount doesn't cover it.
+    #[serde(default)]
+    pub return_storage_deposit: bool,
+    /// Bech32 encoded address that receives the storage deposit and the expired outputs, the first address of the
+    /// account if not set.
+    #[serde(default)]
+    pub return_address: Option<String>,
+}
+
+impl SendOptions {
+    /// Create options without conditions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The outputs expire at the unix timestamp in seconds.
+    pub fn expires_at(mut self, unix_time: u32) -> Self {
+        self.expiration_unix_time = Some(unix_time);
+        self
+    }
+
+    /// The outputs expire after the duration, starting from the current time of the node when they're prepared.
+    pub fn expires_in(mut self, duration: Duration) -> Self {
+        self.expires_in_seconds = Some(u32::try_from(duration.as_secs()).unwrap_or(u32::MAX));
+        self
+    }
+
+    /// The recipients can unlock the outputs from the unix timestamp in seconds on.
+    pub fn locked_until(mut self, unix_time: u32) -> Self {
+        self.timelock_unix_time = Some(unix_time);
+        self
+    }
+
+    /// The recipients can unlock the outputs after the duration, starting from the current time of the node when
+    /// they're prepared.
+    pub fn locked_for(mut self, duration: Duration) -> Self {
+        self.locked_for_seconds = Some(u32::try_from(duration.as_secs()).unwrap_or(u32::MAX));
+        self
+    }
+
+    /// Return the storage deposit of the outputs to the return address when they're claimed.
+    pub fn return_storage_deposit(mut self) -> Self {
+        self.return_storage_deposit = true;
+        self
+    }
+
+    /// Set the bech32 encoded address that receives the storage deposit and the expired outputs.
+    pub fn with_return_address(mut self, return_address: impl Into<String>) -> Self {
+        self.return_address = Some(return_address.into());
+        self
+    }
+}
+
+/// What the recipient of a conditional output needs to know to claim it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SendWarning {
+    /// The output returns to the return address if the recipient doesn't claim it before it expires.
+    ClaimBeforeDeadline {
+        /// Bech32 encoded address of the recipient.
+        recipient: String,
+        /// Unix timestamp in seconds when the output expires.
+        #[serde(rename = "expiresAt")]
+        expires_at: u32,
+        /// Seconds until the output expires.
+        #[serde(rename = "timeToExpiry")]
+        time_to_expiry: u32,
+    },
+    /// The recipient has to send the amount back to the return address to claim the output.
+    StorageDepositReturn {
+        /// Bech32 encoded address of the recipient.
+        recipient: String,
+        /// The amount that has to be returned.
+        amount: u64,
+    },
+    /// The recipient can't unlock the output before the timelock expired.
+    LockedUntil {
+        /// Bech32 encoded address of the recipient.
+        recipient: String,
+        /// Unix timestamp in seconds from which the output can be unlocked.
+        #[serde(rename = "unlocksAt")]
+        unlocks_at: u32,
+    },
+    /// The storage deposit stays locked until the recipient claims the output, because it doesn't expire.
+    NoExpiration {
+        /// Bech32 encoded address of the recipient.
+        recipient: String,
+    },
+}
+
+/// The result of [`AccountHandle::send_with_conditions()`] and [`AccountHandle::send_nft_with_conditions()`].
+#[derive(Debug, Clone)]
+pub struct ConditionalSend {
+    /// The sent transaction.
+    pub transaction: Transaction,
+    /// What the recipients need to know to claim the outputs.
+    pub warnings: Vec<SendWarning>,
+}
+
+/// Dto for ConditionalSend
+#[derive(Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConditionalSendDto {
+    /// The sent transaction.
+    pub transaction: TransactionDto,
+    /// What the recipients need to know to claim the outputs.
+    pub warnings: Vec<SendWarning>,
+}
+
+impl From<&ConditionalSend> for ConditionalSendDto {
+    fn from(value: &ConditionalSend) -> Self {
+        Self {
+            transaction: TransactionDto::from(&value.transaction),
+            warnings: value.warnings.clone(),
+        }
+    }
+}
+
+// The resolved send options of a transaction
+struct Conditions {
+    current_time: u32,
+    expiration: Option<u32>,
+    timelock: Option<u32>,
+    return_storage_deposit: bool,
+    return_address: Address,
+}
+
+impl AccountHandle {
+    /// Send base coins with an expiration and/or a storage deposit return unlock condition. The recipients have to
+    /// claim the outputs before they expire, the returned warnings tell what they need to do, so it can be displayed
+    /// to them.
+    /// ```ignore
+    /// let outputs = vec![AddressWithAmount {
+    ///     address: "rms1qpszqzadsym6wpppd6z037dvlejmjuke7s24hm95s9fg9vpua7vluaw60xu".to_string(),
+    ///     amount: 1_000_000,
+    /// }];
+    /// let send_options = SendOptions::new().expires_in(Duration::from_secs(60 * 60 * 24));
+    ///
+    /// let conditional_send = account.send_with_conditions(outputs, send_options, None).await?;
+    /// println!("{:?}", conditional_send.warnings);
+    /// ```
+    pub async fn send_with_conditions(
+        &self,
+        addresses_with_amount: Vec<AddressWithAmount>,
+        send_options: SendOptions,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<ConditionalSend> {
+        log::debug!("[TRANSACTION] send_with_conditions");
+        let simulate = options.as_ref().map_or(false, |options| options.simulate);
+        let idempotency_key = options.as_ref().and_then(|options| options.idempotency_key.clone());
+        // A transaction that was already sent with the idempotency key is returned without warnings
+        let mut warnings = Vec::new();
+        let transaction = self
+            .send_idempotent(idempotency_key, simulate, async {
+                let (prepared_transaction, send_warnings) = self
+                    .prepare_send_with_conditions(addresses_with_amount, send_options, options)
+                    .await?;
+                warnings = send_warnings;
+                Ok(prepared_transaction)
+            })
+            .await?;
+        Ok(ConditionalSend { transaction, warnings })
+    }
+
+    /// Send NFTs with an expiration and/or a storage deposit return unlock condition, like
+    /// [`AccountHandle::send_with_conditions()`]. The storage deposit of an NFT is returned in full, so the recipient
+    /// has to cover it when claiming the NFT.
+    pub async fn send_nft_with_conditions(
+        &self,
+        addresses_nft_ids: Vec<AddressAndNftId>,
+        send_options: SendOptions,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<ConditionalSend> {
+        log::debug!("[TRANSACTION] send_nft_with_conditions");
+        let simulate = options.as_ref().map_or(false, |options| options.simulate);
+        let idempotency_key = options.as_ref().and_then(|options| options.idempotency_key.clone());
+        // A transaction that was already sent with the idempotency key is returned without warnings
+        let mut warnings = Vec::new();
+        let transaction = self
+            .send_idempotent(idempotency_key, simulate, async {
+                let (prepared_transaction, send_warnings) = self
+                    .prepare_send_nft_with_conditions(addresses_nft_ids, send_options, options)
+                    .await?;
+                warnings = send_warnings;
+                Ok(prepared_transaction)
+            })
+            .await?;
+        Ok(ConditionalSend { transaction, warnings })
+    }
+
+    /// Function to prepare the transaction for
+    /// [AccountHandle.send_with_conditions()](crate::account::handle::AccountHandle.send_with_conditions)
+    async fn prepare_send_with_conditions(
+        &self,
+        addresses_with_amount: Vec<AddressWithAmount>,
+        send_options: SendOptions,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<(PreparedTransactionData, Vec<SendWarning>)> {
+        let rent_structure = self.client.get_rent_structure().await?;
+        let token_supply = self.client.get_token_supply().await?;
+        let conditions = self.resolve_send_options(send_options).await?;
+
+        let mut outputs = Vec::new();
+        for address_with_amount in &addresses_with_amount {
+            let address = Address::try_from_bech32(&address_with_amount.address)?.1;
+            let storage_deposit_amount = minimum_storage_deposit_basic_native_tokens(
+                &rent_structure,
+                &address,
+                &conditions.return_address,
+                None,
+                token_supply,
+            )?;
+            let output_builder = BasicOutputBuilder::new_with_amount(address_with_amount.amount)?
+                .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(address)));
+
+            let output = if conditions.return_storage_deposit || address_with_amount.amount < storage_deposit_amount {
+                // Add the storage deposit, so the recipient can keep the amount after returning it
+                output_builder
+                    .with_amount(address_with_amount.amount + storage_deposit_amount)?
+                    .add_unlock_condition(UnlockCondition::StorageDepositReturn(
+                        StorageDepositReturnUnlockCondition::new(
+                            conditions.return_address,
+                            storage_deposit_amount,
+                            token_supply,
+                        )?,
+                    ))
+            } else {
+                output_builder
+            };
+            let output = match conditions.expiration {
+                Some(expiration_time) => output.add_unlock_condition(UnlockCondition::Expiration(
+                    ExpirationUnlockCondition::new(conditions.return_address, expiration_time)?,
+                )),
+                None => output,
+            };
+            let output = match conditions.timelock {
+                Some(timelock) => {
+                    output.add_unlock_condition(UnlockCondition::Timelock(TimelockUnlockCondition::new(timelock)?))
+                }
+                None => output,
+            };
+            outputs.push(output.finish_output(token_supply)?);
+        }
+
+        let warnings = send_warnings(&outputs, &addresses_with_amount, &conditions)?;
+        Ok((self.prepare_transaction(outputs, options).await?, warnings))
+    }
+
+    /// Function to prepare the transaction for
+    /// [AccountHandle.send_nft_with_conditions()](crate::account::handle::AccountHandle.send_nft_with_conditions)
+    async fn prepare_send_nft_with_conditions(
+        &self,
+        addresses_nft_ids: Vec<AddressAndNftId>,
+        send_options: SendOptions,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<(PreparedTransactionData, Vec<SendWarning>)> {
+        let rent_structure = self.client.get_rent_structure().await?;
+        let token_supply = self.client.get_token_supply().await?;
+        let conditions = self.resolve_send_options(send_options).await?;
+        let unspent_outputs = self.unspent_outputs(None).await?;
+
+        let mut outputs = Vec::new();
+        let mut recipients = Vec::new();
+        for address_and_nft_id in addresses_nft_ids {
+            let address = Address::try_from_bech32(&address_and_nft_id.address)?.1;
+            let nft_output = unspent_outputs
+                .iter()
+                .find_map(|output_data| match &output_data.output {
+                    Output::Nft(nft_output)
+                        if nft_output.nft_id_non_null(&output_data.output_id) == address_and_nft_id.nft_id =>
+                    {
+                        Some(nft_output)
+                    }
+                    _ => None,
+                })
+                .ok_or(Error::NftNotFoundInUnspentOutputs)?;
+
+            let mut unlock_conditions = vec![UnlockCondition::Address(AddressUnlockCondition::new(address))];
+            if let Some(expiration_time) = conditions.expiration {
+                unlock_conditions.push(UnlockCondition::Expiration(ExpirationUnlockCondition::new(
+                    conditions.return_address,
+                    expiration_time,
+                )?));
+            }
+            if let Some(timelock) = conditions.timelock {
+                unlock_conditions.push(UnlockCondition::Timelock(TimelockUnlockCondition::new(timelock)?));
+            }
+            let output_builder = NftOutputBuilder::from(nft_output)
+                .with_nft_id(address_and_nft_id.nft_id)
+                .with_unlock_conditions(unlock_conditions);
+            let output = output_builder.clone().finish_output(token_supply)?;
+
+            let output = if conditions.return_storage_deposit {
+                // The storage deposit return unlock condition itself needs storage, so it's added before the amount
+                // is calculated
+                let with_return = output_builder
+                    .clone()
+                    .add_unlock_condition(UnlockCondition::StorageDepositReturn(
+                        StorageDepositReturnUnlockCondition::new(
+                            conditions.return_address,
+                            output.amount(),
+                            token_supply,
+                        )?,
+                    ));
+                let amount = output.amount().max(
+                    with_return
+                        .clone()
+                        .finish_output(token_supply)?
+                        .rent_cost(&rent_structure),
+                );
+                output_builder
+                    .with_amount(amount)?
+                    .add_unlock_condition(UnlockCondition::StorageDepositReturn(
+                        StorageDepositReturnUnlockCondition::new(conditions.return_address, amount, token_supply)?,
+                    ))
+                    .finish_output(token_supply)?
+            } else if output.rent_cost(&rent_structure) > output.amount() {
+                output_builder
+                    .with_minimum_storage_deposit(rent_structure.clone())
+                    .finish_output(token_supply)?
+            } else {
+                output
+            };
+            outputs.push(output);
+            recipients.push(AddressWithAmount {
+                address: address_and_nft_id.address,
+                amount: 0,
+            });
+        }
+
+        let warnings = send_warnings(&outputs, &recipients, &conditions)?;
+        Ok((self.prepare_transaction(outputs, options).await?, warnings))
+    }
+
+    // Resolve the expiration time, the timelock and the return address of the send options
+    async fn resolve_send_options(&self, send_options: SendOptions) -> crate::Result<Conditions> {
+        let return_address = match send_options.return_address {
+            Some(return_address) => Address::try_from_bech32(return_address)?.1,
+            None => {
+                self.addresses()
+                    .await?
+                    .first()
+                    .ok_or(Error::FailedToGetRemainder)?
+                    .address
+                    .inner
+            }
+        };
+
+        let current_time = self.client.get_time_checked().await?;
+        let expiration = send_options.expiration_unix_time.or_else(|| {
+            send_options
+                .expires_in_seconds
+                .map(|expires_in_seconds| current_time.saturating_add(expires_in_seconds))
+        });
+        let timelock = send_options.timelock_unix_time.or_else(|| {
+            send_options
+                .locked_for_seconds
+                .map(|locked_for_seconds| current_time.saturating_add(locked_for_seconds))
+        });
+        if let Some(expiration_time) = expiration {
+            // The recipient needs time to claim the output after the timelock expired
+            if expiration_time <= timelock.unwrap_or(current_time) {
+                return Err(Error::InvalidExpiration(expiration_time));
+            }
+        }
+
+        Ok(Conditions {
+            current_time,
+            expiration,
+            timelock,
+            return_storage_deposit: send_options.return_storage_deposit,
+            return_address,
+        })
+    }
+}
+
+// The warnings for the recipients of the outputs
+fn send_warnings(
+    outputs: &[Output],
+    recipients: &[AddressWithAmount],
+    conditions: &Conditions,
+) -> crate::Result<Vec<SendWarning>> {
+    let mut warnings = Vec::new();
+    for (output, recipient) in outputs.iter().zip(recipients) {
+        let unlock_conditions = output
+            .unlock_conditions()
+            .ok_or(Error::MissingParameter("unlock_conditions"))?;
+        if let Some(timelock) = conditions.timelock {
+            warnings.push(SendWarning::LockedUntil {
+                recipient: recipient.address.clone(),
+                unlocks_at: timelock,
+            });
+        }
+        if let Some(expiration_time) = conditions.expiration {
+            log::warn!(
+                "[TRANSACTION] {} has to claim the output before {expiration_time}",
+                recipient.address
+            );
+            warnings.push(SendWarning::ClaimBeforeDeadline {
+                recipient: recipient.address.clone(),
+                expires_at: expiration_time,
+                time_to_expiry: expiration_time - conditions.current_time,
+            });
+        }
+        if let Some(storage_deposit_return) = unlock_conditions.storage_deposit_return() {
+            warnings.push(SendWarning::StorageDepositReturn {
+                recipient: recipient.address.clone(),
+                amount: storage_deposit_return.amount(),
+            });
+            if conditions.expiration.is_none() {
+                warnings.push(SendWarning::NoExpiration {
+                    recipient: recipient.address.clone(),
+                });
+            }
+        }
+    }
+    Ok(warnings)
+}
@@ -0,0 +1,240 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_client::block::{
+    address::Address,
+    input::INPUT_COUNT_MAX,
+    output::{
+        unlock_condition::{AddressUnlockCondition, UnlockCondition},
+        BasicOutputBuilder, NativeTokens, NativeTokensBuilder, NftOutputBuilder, Output, OutputId, OUTPUT_COUNT_MAX,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+use crate::account::{
+    handle::AccountHandle,
+    operations::{helpers::time::can_output_be_unlocked_now, output_claiming::get_new_native_token_count},
+    types::{OutputData, Transaction, TransactionDto},
+    AddressWithUnspentOutputs, TransactionOptions,
+};
+
+/// Options for [`AccountHandle::sweep()`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SweepOptions {
+    /// Also sweep basic outputs with native tokens, otherwise they stay in the account.
+    #[serde(default)]
+    pub include_native_tokens: bool,
+    /// Also send the nft outputs that can be unlocked now to the address.
+    #[serde(default)]
+    pub include_nfts: bool,
+}
+
+/// The result of sweeping an account.
+#[derive(Debug, Clone, Default)]
+pub struct Sweep {
+    /// The transactions that were sent.
+    pub transactions: Vec<Transaction>,
+    /// The outputs of the transactions that failed, with the error.
+    pub failed: Vec<FailedSweep>,
+}
+
+/// Dto for the result of sweeping an account.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SweepDto {
+    /// The transactions that were sent.
+    pub transactions: Vec<TransactionDto>,
+    /// The outputs of the transactions that failed, with the error.
+    pub failed: Vec<FailedSweep>,
+}
+
+impl From<&Sweep> for SweepDto {
+    fn from(value: &Sweep) -> Self {
+        Self {
+            transactions: value.transactions.iter().map(TransactionDto::from).collect(),
+            failed: value.failed.clone(),
+        }
+    }
+}
+
+/// A sweep transaction that couldn't be sent, its outputs stay in the account.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FailedSweep {
+    /// The outputs that should have been swept with the transaction.
+    pub output_ids: Vec<OutputId>,
+    /// The error of the transaction.
+    pub error: String,
+}
+
+impl AccountHandle {
+    /// Send all outputs of the account that can be unlocked now to an address, with as many transactions as needed.
+    /// Basic outputs are merged into one output per transaction, nft outputs keep their features and are only sent
+    /// with [`SweepOptions::include_nfts`]. Outputs with a timelock or a storage deposit return that didn't expire,
+    /// alias and foundry outputs stay in the account. A failed transaction doesn't stop the sweep, its outputs are
+    /// reported in [`Sweep::failed`].
+    /// ```ignore
+    /// let sweep = account
+    ///     .sweep(
+    ///         "rms1qpszqzadsym6wpppd6z037dvlejmjuke7s24hm95s9fg9vpua7vluaw60xu",
+    ///         Some(SweepOptions {
+    ///             include_native_tokens: true,
+    ///             include_nfts: true,
+    ///         }),
+    ///     )
+    ///     .await?;
+    /// println!("Sent {} transactions", sweep.transactions.len());
+    /// ```
+    pub async fn sweep(&self, address: &str, options: Option<SweepOptions>) -> crate::Result<Sweep> {
+        log::debug!("[TRANSACTION] sweep to {address}");
+        let options = options.unwrap_or_default();
+        let (_bech32_hrp, address) = Address::try_from_bech32(address)?;
+        let token_supply = self.client.get_token_supply().await?;
+
+        let mut sweep = Sweep::default();
+        for outputs_to_sweep in self.get_outputs_to_sweep(&options).await? {
+            let output_ids: Vec<OutputId> = outputs_to_sweep
+                .iter()
+                .map(|output_data| output_data.output_id)
+                .collect();
+            let result = match sweep_outputs(&outputs_to_sweep, address, token_supply) {
+                Ok(outputs) => {
+                    self.finish_transaction(
+                        outputs,
+                        Some(TransactionOptions {
+                            custom_inputs: Some(output_ids.clone()),
+                            ..Default::default()
+                        }),
+                    )
+                    .await
+                }
+                Err(error) => Err(error),
+            };
+            match result {
+                Ok(transaction) => {
+                    log::debug!("[TRANSACTION] sweep transaction {}", transaction.transaction_id);
+                    sweep.transactions.push(transaction)
+                }
+                Err(error) => {
+                    log::warn!("[TRANSACTION] sweep transaction failed: {error}");
+                    sweep.failed.push(FailedSweep {
+                        output_ids,
+                        error: error.to_string(),
+                    })
+                }
+            }
+        }
+
+        Ok(sweep)
+    }
+
+    // Returns the outputs that can be swept, split into chunks that fit into a transaction
+    async fn get_outputs_to_sweep(&self, options: &SweepOptions) -> crate::Result<Vec<Vec<OutputData>>> {
+        #[cfg(feature = "participation")]
+        let voting_output = self.get_voting_output().await?;
+        let current_time = self.client.get_time_checked().await?;
+        let account = self.read().await;
+
+        let mut chunks = Vec::new();
+        let mut chunk: Vec<OutputData> = Vec::new();
+        let mut chunk_native_tokens = NativeTokensBuilder::new();
+        for (output_id, output_data) in account.unspent_outputs() {
+            if account.locked_outputs.contains(output_id) {
+                continue;
+            }
+            #[cfg(feature = "participation")]
+            if let Some(ref voting_output) = voting_output {
+                // The voting output keeps its features, it has to be spent explicitly
+                if output_data.output_id == voting_output.output_id {
+                    continue;
+                }
+            }
+            let sweepable = match &output_data.output {
+                Output::Basic(basic_output) => options.include_native_tokens || basic_output.native_tokens().is_empty(),
+                Output::Nft(_) => options.include_nfts,
+                _ => false,
+            };
+            if !sweepable || !can_be_swept(output_data, current_time, &account.addresses_with_unspent_outputs)? {
+                continue;
+            }
+
+            let native_tokens = match &output_data.output {
+                Output::Basic(basic_output) => Some(basic_output.native_tokens()),
+                _ => None,
+            };
+            // Start a new transaction if the inputs, the nft outputs or the native tokens of the basic output wouldn't
+            // fit into the current one
+            let chunk_full = chunk.len() >= INPUT_COUNT_MAX.into()
+                || chunk.len() >= (OUTPUT_COUNT_MAX - 1).into()
+                || match native_tokens {
+                    Some(native_tokens) => {
+                        get_new_native_token_count(&chunk_native_tokens, native_tokens)?
+                            > NativeTokens::COUNT_MAX.into()
+                    }
+                    None => false,
+                };
+            if chunk_full {
+                chunks.push(std::mem::take(&mut chunk));
+                chunk_native_tokens = NativeTokensBuilder::new();
+            }
+            if let Some(native_tokens) = native_tokens {
+                chunk_native_tokens.add_native_tokens(native_tokens.clone())?;
+            }
+            chunk.push(output_data.clone());
+        }
+        if !chunk.is_empty() {
+            chunks.push(chunk);
+        }
+
+        Ok(chunks)
+    }
+}
+
+// Outputs that don't have a timelock or an unexpired storage deposit return and can be unlocked by the account now
+pub(crate) fn can_be_swept(
+    output_data: &OutputData,
+    current_time: u32,
+    account_addresses: &[AddressWithUnspentOutputs],
+) -> crate::Result<bool> {
+    if let Some(unlock_conditions) = output_data.output.unlock_conditions() {
+        if unlock_conditions.is_time_locked(current_time) {
+            return Ok(false);
+        }
+        if unlock_conditions.storage_deposit_return().is_some() && !unlock_conditions.is_expired(current_time) {
+            return Ok(false);
+        }
+    }
+    can_output_be_unlocked_now(account_addresses, &[], output_data, current_time, None)
+}
+
+// Merge the basic outputs into one output and send the nft outputs to the address
+fn sweep_outputs(outputs_to_sweep: &[OutputData], address: Address, token_supply: u64) -> crate::Result<Vec<Output>> {
+    let mut outputs = Vec::new();
+    let mut amount = 0;
+    let mut native_tokens = NativeTokensBuilder::new();
+    for output_data in outputs_to_sweep {
+        match &output_data.output {
+            Output::Basic(basic_output) => {
+                amount += basic_output.amount();
+                native_tokens.add_native_tokens(basic_output.native_tokens().clone())?;
+            }
+            Output::Nft(nft_output) => outputs.push(
+                NftOutputBuilder::from(nft_output)
+                    .with_nft_id(nft_output.nft_id_non_null(&output_data.output_id))
+                    .with_unlock_conditions(vec![UnlockCondition::Address(AddressUnlockCondition::new(address))])
+                    .finish_output(token_supply)?,
+            ),
+            _ => {}
+        }
+    }
+    if amount > 0 {
+        outputs.push(
+            BasicOutputBuilder::new_with_amount(amount)?
+                .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(address)))
+                .with_native_tokens(native_tokens.finish()?)
+                .finish_output(token_supply)?,
+        );
+    }
+    Ok(outputs)
+}
@@ -0,0 +1,174 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use iota_client::{
+    api::PreparedTransactionData,
+    block::{
+        address::Address,
+        output::{
+            unlock_condition::{AddressUnlockCondition, TimelockUnlockCondition, UnlockCondition},
+            BasicOutputBuilder, Rent, OUTPUT_COUNT_MAX,
+        },
+    },
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    account::{handle::AccountHandle, types::Transaction, TransactionOptions},
+    Error,
+};
+
+const SECONDS_PER_MONTH: u32 = 60 * 60 * 24 * 30;
+
+/// Splits an amount into timelocked outputs that unlock one after another, sent with
+/// [`AccountHandle::send_vesting()`].
+/// ```ignore
+/// // 12 outputs, the first one can be unlocked in 30 days
+/// let schedule = VestingSchedule::monthly(12);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VestingSchedule {
+    /// Unix timestamp in seconds from which the periods are counted, the current time of the node if not set.
+    #[serde(default)]
+    pub start_unix_time: Option<u32>,
+    /// Seconds between two unlocks.
+    pub interval_seconds: u32,
+    /// Number of outputs the amount is split into, one unlocks at the end of every period.
+    pub periods: u32,
+}
+
+/// One output of a [`VestingSchedule`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VestingUnlock {
+    /// Unix timestamp in seconds from which the output can be unlocked.
+    pub unlocks_at: u32,
+    /// The amount of the output.
+    pub amount: u64,
+}
+
+impl VestingSchedule {
+    /// Create a schedule with `periods` unlocks, one after every `interval`.
+    pub fn new(interval: Duration, periods: u32) -> Self {
+        Self {
+            start_unix_time: None,
+            interval_seconds: u32::try_from(interval.as_secs()).unwrap_or(u32::MAX),
+            periods,
+        }
+    }
+
+    /// Create a schedule with an unlock every 30 days.
+    pub fn monthly(periods: u32) -> Self {
+        Self {
+            start_unix_time: None,
+            interval_seconds: SECONDS_PER_MONTH,
+            periods,
+        }
+    }
+
+    /// Count the periods from the unix timestamp in seconds instead of the current time.
+    pub fn starting_at(mut self, unix_time: u32) -> Self {
+        self.start_unix_time = Some(unix_time);
+        self
+    }
+
+    /// Split the amount over the periods, starting at `start_unix_time` or at `current_time`. The amount that can't be
+    /// split evenly is added to the last unlock.
+    pub fn unlocks(&self, amount: u64, current_time: u32) -> crate::Result<Vec<VestingUnlock>> {
+        if self.periods == 0 || self.interval_seconds == 0 {
+            return Err(Error::InvalidVestingSchedule(
+                "periods and interval need to be greater than 0".to_string(),
+            ));
+        }
+        // Every unlock is an output, the remainder also needs one
+        if self.periods >= OUTPUT_COUNT_MAX as u32 {
+            return Err(Error::InvalidVestingSchedule(format!(
+                "can't have more than {} periods",
+                OUTPUT_COUNT_MAX - 1
+            )));
+        }
+
+        let start = self.start_unix_time.unwrap_or(current_time);
+        let amount_per_period = amount / u64::from(self.periods);
+        (1..=self.periods)
+            .map(|period| {
+                let unlocks_at = self
+                    .interval_seconds
+                    .checked_mul(period)
+                    .and_then(|offset| start.checked_add(offset))
+                    .ok_or_else(|| Error::InvalidVestingSchedule("the last unlock is too far away".to_string()))?;
+                let amount = if period == self.periods {
+                    amount - amount_per_period * u64::from(self.periods - 1)
+                } else {
+                    amount_per_period
+                };
+                Ok(VestingUnlock { unlocks_at, amount })
+            })
+            .collect()
+    }
+}
+
+impl AccountHandle {
+    /// Send an amount to an address as timelocked outputs following the vesting schedule, so the recipient can only
+    /// unlock a part of it after every period. The recipient can see the upcoming unlocks with
+    /// [`AccountHandle::upcoming_unlocks()`]. Every output needs to cover its storage deposit.
+    /// ```ignore
+    /// let transaction = account
+    ///     .send_vesting(
+    ///         "rms1qpszqzadsym6wpppd6z037dvlejmjuke7s24hm95s9fg9vpua7vluaw60xu".to_string(),
+    ///         12_000_000,
+    ///         VestingSchedule::monthly(12),
+    ///         None,
+    ///     )
+    ///     .await?;
+    /// ```
+    pub async fn send_vesting(
+        &self,
+        address: String,
+        amount: u64,
+        schedule: VestingSchedule,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<Transaction> {
+        log::debug!("[TRANSACTION] send_vesting");
+        let simulate = options.as_ref().map_or(false, |options| options.simulate);
+        let prepared_transaction = self.prepare_send_vesting(address, amount, schedule, options).await?;
+        self.sign_and_submit_or_simulate(prepared_transaction, simulate).await
+    }
+
+    /// Function to prepare the transaction for
+    /// [AccountHandle.send_vesting()](crate::account::handle::AccountHandle.send_vesting)
+    async fn prepare_send_vesting(
+        &self,
+        address: String,
+        amount: u64,
+        schedule: VestingSchedule,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<PreparedTransactionData> {
+        let rent_structure = self.client.get_rent_structure().await?;
+        let token_supply = self.client.get_token_supply().await?;
+        let current_time = self.client.get_time_checked().await?;
+        let address = Address::try_from_bech32(address)?.1;
+
+        let mut outputs = Vec::new();
+        for unlock in schedule.unlocks(amount, current_time)? {
+            let output = BasicOutputBuilder::new_with_amount(unlock.amount)?
+                .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(address)))
+                .add_unlock_condition(UnlockCondition::Timelock(TimelockUnlockCondition::new(
+                    unlock.unlocks_at,
+                )?))
+                .finish_output(token_supply)?;
+            let required_storage_deposit = output.rent_cost(&rent_structure);
+            if output.amount() < required_storage_deposit {
+                return Err(Error::InvalidVestingSchedule(format!(
+                    "the amount of every period needs to be at least {required_storage_deposit}"
+                )));
+            }
+            outputs.push(output);
+        }
+
+        self.prepare_transaction(outputs, options).await
+    }
+}
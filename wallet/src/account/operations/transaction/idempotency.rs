@@ -0,0 +1,106 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{future::Future, sync::PoisonError, time::Duration};
+
+use instant::SystemTime;
+use iota_client::{api::PreparedTransactionData, block::payload::transaction::TransactionId};
+
+use crate::account::{handle::AccountHandle, types::Transaction, Account};
+
+/// How long the idempotency key of a sent transaction is kept, a send with the key after that sends again
+pub(crate) const IDEMPOTENCY_KEY_EXPIRY: Duration = Duration::from_secs(60 * 60 * 24 * 30);
+
+// Releases an idempotency key that is in flight, also when the send future is dropped before it completes
+struct InFlightKey<'a> {
+    account_handle: &'a AccountHandle,
+    idempotency_key: String,
+}
+
+impl Drop for InFlightKey<'_> {
+    fn drop(&mut self) {
+        self.account_handle
+            .idempotency_keys_in_flight
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&self.idempotency_key);
+    }
+}
+
+impl AccountHandle {
+    /// Returns the transaction that was sent with an idempotency key, see
+    /// [`TransactionOptions::idempotency_key`](crate::account::TransactionOptions::idempotency_key).
+    pub async fn get_transaction_by_idempotency_key(&self, idempotency_key: &str) -> Option<Transaction> {
+        let account = self.read().await;
+        account
+            .idempotency_keys
+            .get(idempotency_key)
+            .and_then(|transaction_id| account.transactions.get(transaction_id))
+            .cloned()
+    }
+
+    // Sends a transaction only once per idempotency key. If a transaction was already sent with the key it's returned
+    // instead, and a send with a key that is still in flight fails, so retried calls never spend twice. The key is
+    // stored in the same write as the transaction. Simulated transactions aren't stored, so their key stays unused.
+    pub(crate) async fn send_idempotent(
+        &self,
+        idempotency_key: Option<String>,
+        simulate: bool,
+        prepare: impl Future<Output = crate::Result<PreparedTransactionData>>,
+    ) -> crate::Result<Transaction> {
+        let Some(idempotency_key) = idempotency_key else {
+            return self.sign_and_submit_or_simulate(prepare.await?, simulate).await;
+        };
+
+        if let Some(transaction) = self.get_transaction_by_idempotency_key(&idempotency_key).await {
+            log::debug!(
+                "[TRANSACTION] idempotency key {idempotency_key} was used for {}",
+                transaction.transaction_id
+            );
+            return Ok(transaction);
+        }
+        let _in_flight_key = {
+            let mut idempotency_keys = self
+                .idempotency_keys_in_flight
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            if !idempotency_keys.insert(idempotency_key.clone()) {
+                return Err(crate::Error::IdempotencyKeyInUse(idempotency_key));
+            }
+            InFlightKey {
+                account_handle: self,
+                idempotency_key: idempotency_key.clone(),
+            }
+        };
+        // A send that finished while the key was checked
+        if let Some(transaction) = self.get_transaction_by_idempotency_key(&idempotency_key).await {
+            return Ok(transaction);
+        }
+
+        let prepared_transaction_data = prepare.await?;
+        if simulate {
+            self.simulate_transaction(prepared_transaction_data).await
+        } else {
+            self.sign_and_submit_idempotent(prepared_transaction_data, Some(idempotency_key))
+                .await
+        }
+    }
+}
+
+impl Account {
+    // Adds the idempotency key of a sent transaction and removes the keys of transactions that are older than
+    // [`IDEMPOTENCY_KEY_EXPIRY`] or were removed from the account, so the keys don't grow without limit
+    pub(crate) fn insert_idempotency_key(&mut self, idempotency_key: String, transaction_id: TransactionId) {
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .expect("time went backwards")
+            .as_millis();
+        let transactions = &self.transactions;
+        self.idempotency_keys.retain(|_, transaction_id| {
+            transactions.get(transaction_id).map_or(false, |transaction| {
+                now.saturating_sub(transaction.timestamp) < IDEMPOTENCY_KEY_EXPIRY.as_millis()
+            })
+        });
+        self.idempotency_keys.insert(idempotency_key, transaction_id);
+    }
+}
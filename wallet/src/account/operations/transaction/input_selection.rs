@@ -27,6 +27,7 @@ impl AccountHandle {
         mandatory_inputs: Option<HashSet<OutputId>>,
         remainder_address: Option<Address>,
         burn: Option<&Burn>,
+        address_bucket: Option<&str>,
     ) -> crate::Result<Selected> {
         log::debug!("[TRANSACTION] select_inputs");
         // Voting output needs to be requested before to prevent a deadlock
@@ -43,9 +44,33 @@ impl AccountHandle {
         );
 
         let current_time = self.client.get_time_checked().await?;
-        #[allow(unused_mut)]
         let mut forbidden_inputs = account.locked_outputs.clone();
 
+        // Inputs of in-progress transactions of other account managers that share the database
+        #[cfg(feature = "storage")]
+        let reserved_inputs = self.update_input_reservations(&account).await?;
+        #[cfg(not(feature = "storage"))]
+        let reserved_inputs: HashSet<OutputId> = HashSet::new();
+        forbidden_inputs.extend(reserved_inputs.iter());
+
+        // Only use outputs on addresses of the bucket
+        if let Some(address_bucket) = address_bucket {
+            let bucket_addresses: HashSet<Address> = account
+                .address_buckets
+                .get(address_bucket)
+                .ok_or_else(|| crate::Error::AddressBucketNotFound(address_bucket.to_string()))?
+                .iter()
+                .map(|address| address.inner)
+                .collect();
+            forbidden_inputs.extend(
+                account
+                    .unspent_outputs
+                    .values()
+                    .filter(|output_data| !bucket_addresses.contains(&output_data.address))
+                    .map(|output_data| output_data.output_id),
+            );
+        }
+
         let addresses = account
             .public_addresses()
             .iter()
@@ -81,13 +106,37 @@ impl AccountHandle {
         if let Some(custom_inputs) = custom_inputs {
             // Check that no input got already locked
             for input in custom_inputs.iter() {
-                if account.locked_outputs.contains(input) {
+                if account.locked_outputs.contains(input) || reserved_inputs.contains(input) {
                     return Err(crate::Error::CustomInput(format!(
                         "provided custom input {input} is already used in another transaction",
                     )));
                 }
+                if !account.unspent_outputs.contains_key(input) {
+                    return Err(crate::Error::CustomInput(format!(
+                        "provided custom input {input} is not an unspent output of the account",
+                    )));
+                }
+            }
+
+            // Fail early if the selected inputs can't cover the outputs, before input selection is attempted
+            let available = custom_inputs
+                .iter()
+                .filter_map(|input| account.unspent_outputs.get(input))
+                .map(|output_data| output_data.output.amount())
+                .sum::<u64>();
+            let required = outputs.iter().map(|output| output.amount()).sum::<u64>();
+            if available < required {
+                return Err(crate::Error::InsufficientFunds { available, required });
             }
 
+            // Only the selected inputs are used
+            forbidden_inputs.extend(
+                account
+                    .unspent_outputs
+                    .keys()
+                    .filter(|output_id| !custom_inputs.contains(output_id)),
+            );
+
             let mut input_selection = InputSelection::new(
                 available_outputs_signing_data,
                 outputs,
@@ -111,12 +160,16 @@ impl AccountHandle {
             for output in &selected_transaction_data.inputs {
                 account.locked_outputs.insert(*output.output_id());
             }
+            account.update_cached_balance_for_inputs(&selected_transaction_data.inputs, true);
+            #[cfg(feature = "storage")]
+            self.reserve_selected_inputs(&mut account, &selected_transaction_data.inputs)
+                .await?;
 
             return Ok(selected_transaction_data);
         } else if let Some(mandatory_inputs) = mandatory_inputs {
             // Check that no input got already locked
             for input in mandatory_inputs.iter() {
-                if account.locked_outputs.contains(input) {
+                if account.locked_outputs.contains(input) || reserved_inputs.contains(input) {
                     return Err(crate::Error::CustomInput(format!(
                         "provided custom input {input} is already used in another transaction",
                     )));
@@ -151,6 +204,10 @@ impl AccountHandle {
             for output in &selected_transaction_data.inputs {
                 account.locked_outputs.insert(*output.output_id());
             }
+            account.update_cached_balance_for_inputs(&selected_transaction_data.inputs, true);
+            #[cfg(feature = "storage")]
+            self.reserve_selected_inputs(&mut account, &selected_transaction_data.inputs)
+                .await?;
 
             return Ok(selected_transaction_data);
         }
@@ -193,6 +250,10 @@ impl AccountHandle {
             log::debug!("[TRANSACTION] locking: {}", output.output_id());
             account.locked_outputs.insert(*output.output_id());
         }
+        account.update_cached_balance_for_inputs(&selected_transaction_data.inputs, true);
+        #[cfg(feature = "storage")]
+        self.reserve_selected_inputs(&mut account, &selected_transaction_data.inputs)
+            .await?;
 
         Ok(selected_transaction_data)
     }
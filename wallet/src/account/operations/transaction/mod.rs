@@ -2,12 +2,16 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod build_transaction;
+pub(crate) mod dust_policy;
 pub(crate) mod high_level;
+mod idempotency;
 mod input_selection;
 mod options;
 pub(crate) mod prepare_output;
 mod prepare_transaction;
+pub(crate) mod sign_request;
 mod sign_transaction;
+pub(crate) mod signing_policy;
 pub(crate) mod submit_transaction;
 
 use instant::SystemTime;
@@ -21,11 +25,14 @@ use iota_client::{
         },
         payload::transaction::TransactionPayload,
         semantic::ConflictReason,
+        BlockId,
     },
     secret::types::InputSigningData,
 };
 
 pub use self::options::{RemainderValueStrategy, TransactionOptions, TransactionOptionsDto};
+#[cfg(feature = "storage")]
+use crate::storage::audit_log::AuditOperation;
 use crate::{
     account::{
         handle::AccountHandle,
@@ -61,17 +68,23 @@ impl AccountHandle {
     pub async fn send(&self, outputs: Vec<Output>, options: Option<TransactionOptions>) -> crate::Result<Transaction> {
         // here to check before syncing, how to prevent duplicated verification (also in prepare_transaction())?
         // Checking it also here is good to return earlier if something is invalid
+        self.verify_storage_deposits(&outputs).await?;
+
+        self.finish_transaction(outputs, options).await
+    }
+
+    // Check if the outputs have enough amount to cover the storage deposit
+    pub(crate) async fn verify_storage_deposits(&self, outputs: &[Output]) -> crate::Result<()> {
         let protocol_parameters = self.client.get_protocol_parameters().await?;
 
-        // Check if the outputs have enough amount to cover the storage deposit
-        for output in &outputs {
+        for output in outputs {
             output.verify_storage_deposit(
                 protocol_parameters.rent_structure().clone(),
                 protocol_parameters.token_supply(),
             )?;
         }
 
-        self.finish_transaction(outputs, options).await
+        Ok(())
     }
 
     /// Separated function from send, so syncing isn't called recursively with the consolidation function, which sends
@@ -83,19 +96,29 @@ impl AccountHandle {
     ) -> crate::Result<Transaction> {
         log::debug!("[TRANSACTION] finish_transaction");
 
-        let prepared_transaction_data = self.prepare_transaction(outputs, options).await?;
-
-        self.sign_and_submit_transaction(prepared_transaction_data).await
+        let simulate = options.as_ref().map_or(false, |options| options.simulate);
+        let idempotency_key = options.as_ref().and_then(|options| options.idempotency_key.clone());
+        self.send_idempotent(idempotency_key, simulate, self.prepare_transaction(outputs, options))
+            .await
     }
 
-    /// Sign a transaction, submit it to a node and store it in the account
+    /// Sign a transaction with [`AccountHandle::sign_transaction()`] and submit it with
+    /// [`AccountHandle::submit_transaction()`]
     pub async fn sign_and_submit_transaction(
         &self,
         prepared_transaction_data: PreparedTransactionData,
     ) -> crate::Result<Transaction> {
         log::debug!("[TRANSACTION] sign_and_submit_transaction");
+        self.sign_and_submit_idempotent(prepared_transaction_data, None).await
+    }
 
-        let signed_transaction_data = match self.sign_transaction_essence(&prepared_transaction_data).await {
+    // Sign and submit a transaction, the idempotency key is stored together with the transaction
+    pub(crate) async fn sign_and_submit_idempotent(
+        &self,
+        prepared_transaction_data: PreparedTransactionData,
+        idempotency_key: Option<String>,
+    ) -> crate::Result<Transaction> {
+        let signed_transaction_data = match self.sign_transaction(&prepared_transaction_data).await {
             Ok(res) => res,
             Err(err) => {
                 // unlock outputs so they are available for a new transaction
@@ -104,16 +127,110 @@ impl AccountHandle {
             }
         };
 
-        self.submit_and_store_transaction(signed_transaction_data).await
+        self.submit_idempotent(signed_transaction_data, idempotency_key).await
+    }
+
+    // Sign and submit the transaction, or only simulate it if [`TransactionOptions::simulate`] was set
+    pub(crate) async fn sign_and_submit_or_simulate(
+        &self,
+        prepared_transaction_data: PreparedTransactionData,
+        simulate: bool,
+    ) -> crate::Result<Transaction> {
+        if simulate {
+            self.simulate_transaction(prepared_transaction_data).await
+        } else {
+            self.sign_and_submit_transaction(prepared_transaction_data).await
+        }
+    }
+
+    /// Sign a prepared transaction and validate it like a node would, without submitting or storing it. The inputs
+    /// are unlocked afterwards, so the returned transaction, with its id, inputs and outputs, only shows what would be
+    /// sent. With a Ledger Nano the transaction still needs to be confirmed on the device.
+    pub async fn simulate_transaction(
+        &self,
+        prepared_transaction_data: PreparedTransactionData,
+    ) -> crate::Result<Transaction> {
+        log::debug!("[TRANSACTION] simulate_transaction");
+        let result = self.simulate_transaction_internal(&prepared_transaction_data).await;
+        self.unlock_inputs(prepared_transaction_data.inputs_data).await?;
+        result
+    }
+
+    async fn simulate_transaction_internal(
+        &self,
+        prepared_transaction_data: &PreparedTransactionData,
+    ) -> crate::Result<Transaction> {
+        let signed_transaction_data = self.sign_transaction(prepared_transaction_data).await?;
+
+        let local_time = self.client.get_time_checked().await?;
+        let conflict = verify_semantic(
+            &signed_transaction_data.inputs_data,
+            &signed_transaction_data.transaction_payload,
+            local_time,
+        )?;
+        if conflict != ConflictReason::None {
+            log::debug!("[TRANSACTION] simulated transaction conflict: {conflict:?}");
+            return Err(Error::TransactionSemantic(conflict).into());
+        }
+
+        let network_id = self.client.get_network_id().await?;
+        Ok(transaction_from_signed(signed_transaction_data, None, network_id))
+    }
+
+    /// Validate the transaction, submit it to a node and store it in the account. The transaction can be signed by
+    /// another system, as long as the inputs of the [`PreparedTransactionData`] are used.
+    pub async fn submit_transaction(
+        &self,
+        signed_transaction_data: SignedTransactionData,
+    ) -> crate::Result<Transaction> {
+        log::debug!("[TRANSACTION] submit_transaction");
+        self.submit_idempotent(signed_transaction_data, None).await
+    }
+
+    async fn submit_idempotent(
+        &self,
+        signed_transaction_data: SignedTransactionData,
+        idempotency_key: Option<String>,
+    ) -> crate::Result<Transaction> {
+        #[cfg(feature = "storage")]
+        let transaction_id = signed_transaction_data.transaction_payload.id();
+
+        let result = self
+            .submit_transaction_internal(signed_transaction_data, idempotency_key)
+            .await;
+
+        #[cfg(feature = "storage")]
+        {
+            let account_index = *self.read().await.index();
+            // The transaction could already be submitted, it must not be reported as failed and be sent again
+            if let Err(err) = self
+                .storage_manager
+                .lock()
+                .await
+                .audit(Some(account_index), AuditOperation::Send, &transaction_id, &result)
+                .await
+            {
+                log::debug!("[TRANSACTION] audit log error: {}", err);
+            }
+        }
+
+        result
     }
 
     /// Validate the transaction, submit it to a node and store it in the account
+    #[deprecated(since = "1.0.0-rc.7", note = "use `submit_transaction()` instead")]
     pub async fn submit_and_store_transaction(
         &self,
         signed_transaction_data: SignedTransactionData,
     ) -> crate::Result<Transaction> {
-        log::debug!("[TRANSACTION] submit_and_store_transaction");
+        self.submit_transaction(signed_transaction_data).await
+    }
 
+    async fn submit_transaction_internal(
+        &self,
+        signed_transaction_data: SignedTransactionData,
+        idempotency_key: Option<String>,
+    ) -> crate::Result<Transaction> {
         // Validate transaction before sending and storing it
         let local_time = self.client.get_time_checked().await?;
 
@@ -149,35 +266,16 @@ impl AccountHandle {
 
         // store transaction payload to account (with db feature also store the account to the db)
         let network_id = self.client.get_network_id().await?;
-
-        let inputs = signed_transaction_data
-            .inputs_data
-            .into_iter()
-            .map(|input| OutputWithMetadataResponse {
-                metadata: OutputMetadataDto::from(&input.output_metadata),
-                output: OutputDto::from(&input.output),
-            })
-            .collect();
-
-        let transaction = Transaction {
-            transaction_id,
-            payload: signed_transaction_data.transaction_payload,
-            block_id,
-            network_id,
-            timestamp: SystemTime::now()
-                .duration_since(SystemTime::UNIX_EPOCH)
-                .expect("time went backwards")
-                .as_millis(),
-            inclusion_state: InclusionState::Pending,
-            incoming: false,
-            note: None,
-            inputs,
-        };
+        let transaction = transaction_from_signed(signed_transaction_data, block_id, network_id);
 
         let mut account = self.write().await;
 
         account.transactions.insert(transaction_id, transaction.clone());
         account.pending_transactions.insert(transaction_id);
+        // Stored with the transaction, so the key isn't lost if the wallet stops after the transaction was sent
+        if let Some(idempotency_key) = idempotency_key {
+            account.insert_idempotency_key(idempotency_key, transaction_id);
+        }
         #[cfg(feature = "storage")]
         {
             log::debug!("[TRANSACTION] storing account {}", account.index());
@@ -187,8 +285,21 @@ impl AccountHandle {
         Ok(transaction)
     }
 
+    /// Unlock the inputs of a prepared transaction that won't be signed or submitted, so they can be used for other
+    /// transactions again.
+    pub async fn discard_prepared_transaction(
+        &self,
+        prepared_transaction_data: PreparedTransactionData,
+    ) -> crate::Result<()> {
+        log::debug!("[TRANSACTION] discard_prepared_transaction");
+        self.unlock_inputs(prepared_transaction_data.inputs_data).await?;
+        #[cfg(feature = "storage")]
+        self.save(None).await?;
+        Ok(())
+    }
+
     // unlock outputs
-    async fn unlock_inputs(&self, inputs: Vec<InputSigningData>) -> crate::Result<()> {
+    pub(crate) async fn unlock_inputs(&self, inputs: Vec<InputSigningData>) -> crate::Result<()> {
         let mut account = self.write().await;
         for input_signing_data in &inputs {
             let output_id = input_signing_data.output_id();
@@ -198,6 +309,45 @@ impl AccountHandle {
                 output_id
             );
         }
+        account.update_cached_balance_for_inputs(&inputs, false);
+        #[cfg(feature = "storage")]
+        self.update_input_reservations(&account).await?;
         Ok(())
     }
 }
+
+// Create a pending transaction from the signed transaction data
+fn transaction_from_signed(
+    signed_transaction_data: SignedTransactionData,
+    block_id: Option<BlockId>,
+    network_id: u64,
+) -> Transaction {
+    let inputs = signed_transaction_data
+        .inputs_data
+        .into_iter()
+        .map(|input| OutputWithMetadataResponse {
+            metadata: OutputMetadataDto::from(&input.output_metadata),
+            output: OutputDto::from(&input.output),
+        })
+        .collect();
+
+    Transaction {
+        transaction_id: signed_transaction_data.transaction_payload.id(),
+        payload: signed_transaction_data.transaction_payload,
+        block_id,
+        network_id,
+        timestamp: SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .expect("time went backwards")
+            .as_millis(),
+        inclusion_state: InclusionState::Pending,
+        incoming: false,
+        note: None,
+        labels: Vec::new(),
+        reattachments: 0,
+        retry_of: None,
+        retried_by: None,
+        expires_at: None,
+        inputs,
+    }
+}
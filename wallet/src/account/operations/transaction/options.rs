@@ -28,6 +28,19 @@ pub struct TransactionOptions {
     pub mandatory_inputs: Option<Vec<OutputId>>,
     pub burn: Option<Burn>,
     pub note: Option<String>,
+    // If set, only outputs on addresses of this bucket are used as inputs
+    #[serde(rename = "addressBucket", default)]
+    pub address_bucket: Option<String>,
+    // If set, the transaction is signed and validated, but not submitted or stored
+    #[serde(default)]
+    pub simulate: bool,
+    // If set, the transaction isn't blocked when it creates dust
+    #[serde(rename = "allowDust", default)]
+    pub allow_dust: bool,
+    // If set, the transaction is only sent once for this key, retried sends in the next 30 days return the transaction
+    // that was sent first
+    #[serde(rename = "idempotencyKey", default)]
+    pub idempotency_key: Option<String>,
 }
 
 impl TransactionOptions {
@@ -44,6 +57,10 @@ impl TransactionOptions {
             mandatory_inputs: value.mandatory_inputs.clone(),
             burn: value.burn.as_ref().map(Burn::try_from).transpose()?,
             note: value.note.clone(),
+            address_bucket: value.address_bucket.clone(),
+            simulate: value.simulate,
+            allow_dust: value.allow_dust,
+            idempotency_key: value.idempotency_key.clone(),
         })
     }
 }
@@ -63,6 +80,19 @@ pub struct TransactionOptionsDto {
     pub mandatory_inputs: Option<Vec<OutputId>>,
     pub burn: Option<BurnDto>,
     pub note: Option<String>,
+    // If set, only outputs on addresses of this bucket are used as inputs
+    #[serde(rename = "addressBucket", default)]
+    pub address_bucket: Option<String>,
+    // If set, the transaction is signed and validated, but not submitted or stored
+    #[serde(default)]
+    pub simulate: bool,
+    // If set, the transaction isn't blocked when it creates dust
+    #[serde(rename = "allowDust", default)]
+    pub allow_dust: bool,
+    // If set, the transaction is only sent once for this key, retried sends in the next 30 days return the transaction
+    // that was sent first
+    #[serde(rename = "idempotencyKey", default)]
+    pub idempotency_key: Option<String>,
 }
 
 #[allow(clippy::enum_variant_names)]
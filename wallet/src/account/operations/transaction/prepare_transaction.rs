@@ -21,7 +21,9 @@ use crate::account::{
 use crate::events::types::{AddressData, TransactionProgressEvent, WalletEvent};
 
 impl AccountHandle {
-    /// Get inputs and build the transaction essence
+    /// Get inputs and build the transaction essence. The selected inputs are locked until the transaction is signed
+    /// with [`AccountHandle::sign_transaction()`] and submitted with [`AccountHandle::submit_transaction()`], or
+    /// discarded with [`AccountHandle::discard_prepared_transaction()`].
     pub async fn prepare_transaction(
         &self,
         outputs: Vec<Output>,
@@ -104,9 +106,11 @@ impl AccountHandle {
                     .map(|inputs| HashSet::from_iter(inputs.clone())),
                 remainder_address,
                 options.as_ref().and_then(|options| options.burn.as_ref()),
+                options.as_ref().and_then(|options| options.address_bucket.as_deref()),
             )
             .await?;
 
+        let allow_dust = options.as_ref().map_or(false, |options| options.allow_dust);
         let prepared_transaction_data = match self
             .build_transaction_essence(selected_transaction_data.clone(), options)
             .await
@@ -118,6 +122,10 @@ impl AccountHandle {
                 return Err(err);
             }
         };
+        if let Err(err) = self.check_dust_policy(&prepared_transaction_data, allow_dust).await {
+            self.unlock_inputs(selected_transaction_data.inputs).await?;
+            return Err(err);
+        }
 
         log::debug!(
             "[TRANSACTION] finished prepare_transaction in {:.2?}",
@@ -0,0 +1,312 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use crypto::hashes::{blake2b::Blake2b256, Digest};
+use iota_client::{
+    api::{PreparedTransactionData, PreparedTransactionDataDto, SignedTransactionData, SignedTransactionDataDto},
+    block::protocol::ProtocolParameters,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::account::{handle::AccountHandle, types::Transaction};
+
+/// The version of the [`SignRequest`] and [`SignResponse`] format.
+pub const SIGN_REQUEST_VERSION: u8 = 1;
+
+const SIGN_REQUEST_KIND: &str = "iotaSignRequest";
+const SIGN_RESPONSE_KIND: &str = "iotaSignResponse";
+
+/// A prepared transaction that is exported by an online, watch-only account to be signed on an offline device.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignRequest {
+    /// The version of the format.
+    pub version: u8,
+    /// The index of the account that needs to sign the transaction.
+    pub account_index: u32,
+    /// The protocol parameters of the network, so the offline device can verify the transaction.
+    pub protocol_parameters: ProtocolParameters,
+    /// The prepared transaction.
+    pub prepared_transaction_data: PreparedTransactionDataDto,
+}
+
+impl SignRequest {
+    /// Creates a sign request for a prepared transaction of the account.
+    pub fn new(
+        account_index: u32,
+        prepared_transaction_data: &PreparedTransactionData,
+        protocol_parameters: ProtocolParameters,
+    ) -> Self {
+        Self {
+            version: SIGN_REQUEST_VERSION,
+            account_index,
+            protocol_parameters,
+            prepared_transaction_data: PreparedTransactionDataDto::from(prepared_transaction_data),
+        }
+    }
+
+    /// Returns the prepared transaction, verified against the protocol parameters of the request.
+    pub fn prepared_transaction_data(&self) -> crate::Result<PreparedTransactionData> {
+        Ok(PreparedTransactionData::try_from_dto(
+            &self.prepared_transaction_data,
+            &self.protocol_parameters,
+        )?)
+    }
+
+    /// Serializes the request to JSON.
+    pub fn to_json(&self) -> crate::Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Deserializes a request from JSON, fails if it has an unsupported version.
+    pub fn from_json(json: &str) -> crate::Result<Self> {
+        let sign_request: Self = serde_json::from_str(json)?;
+        check_version(sign_request.version)?;
+        Ok(sign_request)
+    }
+
+    /// Splits the JSON of the request into chunks of at most `max_chunk_length` data characters, so it can be
+    /// transferred with multiple QR codes.
+    pub fn to_chunks(&self, max_chunk_length: usize) -> crate::Result<Vec<String>> {
+        to_chunks(SIGN_REQUEST_KIND, &self.to_json()?, max_chunk_length)
+    }
+
+    /// Joins chunks created with [`SignRequest::to_chunks()`], they can be provided in any order.
+    pub fn from_chunks(chunks: &[String]) -> crate::Result<Self> {
+        Self::from_json(&from_chunks(SIGN_REQUEST_KIND, chunks)?)
+    }
+}
+
+/// A transaction signed on an offline device, to be submitted by the online account that created the
+/// [`SignRequest`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignResponse {
+    /// The version of the format.
+    pub version: u8,
+    /// The index of the account that signed the transaction.
+    pub account_index: u32,
+    /// The signed transaction.
+    pub signed_transaction_data: SignedTransactionDataDto,
+}
+
+impl SignResponse {
+    /// Creates a sign response for a signed transaction of the account.
+    pub fn new(account_index: u32, signed_transaction_data: &SignedTransactionData) -> Self {
+        Self {
+            version: SIGN_REQUEST_VERSION,
+            account_index,
+            signed_transaction_data: SignedTransactionDataDto::from(signed_transaction_data),
+        }
+    }
+
+    /// Returns the signed transaction, verified against the protocol parameters.
+    pub fn signed_transaction_data(
+        &self,
+        protocol_parameters: &ProtocolParameters,
+    ) -> crate::Result<SignedTransactionData> {
+        Ok(SignedTransactionData::try_from_dto(
+            &self.signed_transaction_data,
+            protocol_parameters,
+        )?)
+    }
+
+    /// Serializes the response to JSON.
+    pub fn to_json(&self) -> crate::Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Deserializes a response from JSON, fails if it has an unsupported version.
+    pub fn from_json(json: &str) -> crate::Result<Self> {
+        let sign_response: Self = serde_json::from_str(json)?;
+        check_version(sign_response.version)?;
+        Ok(sign_response)
+    }
+
+    /// Splits the JSON of the response into chunks of at most `max_chunk_length` data characters, so it can be
+    /// transferred with multiple QR codes.
+    pub fn to_chunks(&self, max_chunk_length: usize) -> crate::Result<Vec<String>> {
+        to_chunks(SIGN_RESPONSE_KIND, &self.to_json()?, max_chunk_length)
+    }
+
+    /// Joins chunks created with [`SignResponse::to_chunks()`], they can be provided in any order.
+    pub fn from_chunks(chunks: &[String]) -> crate::Result<Self> {
+        Self::from_json(&from_chunks(SIGN_RESPONSE_KIND, chunks)?)
+    }
+}
+
+impl AccountHandle {
+    /// Creates a [`SignRequest`] for a transaction prepared by this account, to sign it on an offline device.
+    pub async fn create_sign_request(
+        &self,
+        prepared_transaction_data: &PreparedTransactionData,
+    ) -> crate::Result<SignRequest> {
+        log::debug!("[TRANSACTION] create_sign_request");
+        Ok(SignRequest::new(
+            self.read().await.index,
+            prepared_transaction_data,
+            self.client.get_protocol_parameters().await?,
+        ))
+    }
+
+    /// Signs a [`SignRequest`] with the secret manager of this account, doesn't need a connection to a node.
+    pub async fn sign_request(&self, sign_request: &SignRequest) -> crate::Result<SignResponse> {
+        log::debug!("[TRANSACTION] sign_request");
+        let account_index = self.read().await.index;
+        if sign_request.account_index != account_index {
+            return Err(crate::Error::AirGappedSigning(format!(
+                "request is for account {}, not for account {account_index}",
+                sign_request.account_index
+            )));
+        }
+
+        let signed_transaction_data = self
+            .sign_transaction(&sign_request.prepared_transaction_data()?)
+            .await?;
+        Ok(SignResponse::new(account_index, &signed_transaction_data))
+    }
+
+    /// Submits the transaction of a [`SignResponse`] and stores it in the account.
+    pub async fn submit_sign_response(&self, sign_response: &SignResponse) -> crate::Result<Transaction> {
+        log::debug!("[TRANSACTION] submit_sign_response");
+        let account_index = self.read().await.index;
+        if sign_response.account_index != account_index {
+            return Err(crate::Error::AirGappedSigning(format!(
+                "response is from account {}, not from account {account_index}",
+                sign_response.account_index
+            )));
+        }
+
+        let signed_transaction_data =
+            sign_response.signed_transaction_data(&self.client.get_protocol_parameters().await?)?;
+        self.submit_transaction(signed_transaction_data).await
+    }
+}
+
+fn check_version(version: u8) -> crate::Result<()> {
+    if version != SIGN_REQUEST_VERSION {
+        return Err(crate::Error::AirGappedSigning(format!("unsupported version {version}")));
+    }
+    Ok(())
+}
+
+// A short hash of the whole JSON is added to every chunk, so chunks of different requests can't be mixed up
+fn checksum(json: &str) -> String {
+    prefix_hex::encode(&Blake2b256::digest(json.as_bytes())[..4])
+}
+
+// Chunks have the format `<kind>:<checksum>:<index>/<count>:<data>`, the index starts at 1
+fn to_chunks(kind: &str, json: &str, max_chunk_length: usize) -> crate::Result<Vec<String>> {
+    if max_chunk_length == 0 {
+        return Err(crate::Error::AirGappedSigning("chunk length can't be 0".to_string()));
+    }
+    let checksum = checksum(json);
+    let characters = json.chars().collect::<Vec<_>>();
+    let parts = characters.chunks(max_chunk_length).collect::<Vec<_>>();
+    let count = parts.len();
+
+    Ok(parts
+        .into_iter()
+        .enumerate()
+        .map(|(index, part)| {
+            format!(
+                "{kind}:{checksum}:{}/{count}:{}",
+                index + 1,
+                part.iter().collect::<String>()
+            )
+        })
+        .collect())
+}
+
+fn from_chunks(kind: &str, chunks: &[String]) -> crate::Result<String> {
+    let invalid_chunk = |reason: &str| crate::Error::AirGappedSigning(format!("invalid chunk: {reason}"));
+
+    let mut expected_checksum = None;
+    let mut count = None;
+    let mut parts = BTreeMap::new();
+    for chunk in chunks {
+        let mut fields = chunk.splitn(4, ':');
+        let (chunk_kind, chunk_checksum, position, data) =
+            match (fields.next(), fields.next(), fields.next(), fields.next()) {
+                (Some(chunk_kind), Some(chunk_checksum), Some(position), Some(data)) => {
+                    (chunk_kind, chunk_checksum, position, data)
+                }
+                _ => return Err(invalid_chunk("missing fields")),
+            };
+        if chunk_kind != kind {
+            return Err(invalid_chunk("wrong kind"));
+        }
+        let (index, chunk_count) = position
+            .split_once('/')
+            .and_then(|(index, count)| Some((index.parse::<usize>().ok()?, count.parse::<usize>().ok()?)))
+            .ok_or_else(|| invalid_chunk("invalid position"))?;
+
+        if *expected_checksum.get_or_insert(chunk_checksum) != chunk_checksum
+            || *count.get_or_insert(chunk_count) != chunk_count
+        {
+            return Err(invalid_chunk("chunks belong to different requests"));
+        }
+        parts.insert(index, data);
+    }
+
+    let count = count.ok_or_else(|| invalid_chunk("no chunks"))?;
+    if parts.keys().copied().ne(1..=count) {
+        return Err(invalid_chunk("chunks are missing"));
+    }
+    let json = parts.into_values().collect::<String>();
+    if expected_checksum != Some(checksum(&json).as_str()) {
+        return Err(invalid_chunk("checksum mismatch"));
+    }
+
+    Ok(json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk_error(chunks: &[String]) -> String {
+        match from_chunks(SIGN_REQUEST_KIND, chunks) {
+            Err(crate::Error::AirGappedSigning(error)) => error,
+            result => panic!("unexpected result {result:?}"),
+        }
+    }
+
+    #[test]
+    fn chunks() {
+        let json = r#"{"version":1,"note":"Grüße"}"#;
+        let chunks = to_chunks(SIGN_REQUEST_KIND, json, 10).unwrap();
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks[0].starts_with(&format!("{SIGN_REQUEST_KIND}:{}:1/3:", checksum(json))));
+        assert_eq!(from_chunks(SIGN_REQUEST_KIND, &chunks).unwrap(), json);
+
+        // The order of the chunks doesn't matter
+        let reordered_chunks = vec![chunks[2].clone(), chunks[0].clone(), chunks[1].clone()];
+        assert_eq!(from_chunks(SIGN_REQUEST_KIND, &reordered_chunks).unwrap(), json);
+
+        assert_eq!(
+            chunk_error(&[chunks[0].clone(), chunks[2].clone()]),
+            "invalid chunk: chunks are missing"
+        );
+        assert_eq!(chunk_error(&[]), "invalid chunk: no chunks");
+        assert!(from_chunks(SIGN_RESPONSE_KIND, &chunks).is_err());
+
+        // The checksum of all chunks was changed
+        let corrupted_chunks = chunks
+            .iter()
+            .map(|chunk| chunk.replace(&checksum(json), "0x00000000"))
+            .collect::<Vec<_>>();
+        assert_eq!(chunk_error(&corrupted_chunks), "invalid chunk: checksum mismatch");
+        // The checksum of a single chunk was changed
+        let mut corrupted_chunks = chunks.clone();
+        corrupted_chunks[1] = corrupted_chunks[1].replace(&checksum(json), "0x00000000");
+        assert_eq!(
+            chunk_error(&corrupted_chunks),
+            "invalid chunk: chunks belong to different requests"
+        );
+
+        assert!(to_chunks(SIGN_REQUEST_KIND, json, 0).is_err());
+    }
+}
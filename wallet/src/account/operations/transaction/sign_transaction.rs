@@ -1,37 +1,91 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(any(feature = "ledger_nano", all(feature = "events", feature = "stronghold")))]
+use iota_client::secret::SecretManager;
 use iota_client::{
     api::{transaction::validate_transaction_payload_length, PreparedTransactionData, SignedTransactionData},
     secret::SecretManageExt,
 };
 #[cfg(all(feature = "events", feature = "ledger_nano"))]
-use {
-    iota_client::api::PreparedTransactionDataDto, iota_client::secret::ledger_nano::needs_blind_signing,
-    iota_client::secret::SecretManager,
-};
+use {iota_client::api::PreparedTransactionDataDto, iota_client::secret::ledger_nano::needs_blind_signing};
 
 use crate::account::{handle::AccountHandle, operations::transaction::TransactionPayload};
+#[cfg(feature = "ledger_nano")]
+use crate::account_manager::{LedgerNanoCapabilities, LedgerNanoOperation};
+#[cfg(all(feature = "events", feature = "stronghold"))]
+use crate::events::types::AlertCode;
 #[cfg(feature = "events")]
 use crate::events::types::{TransactionProgressEvent, WalletEvent};
+#[cfg(feature = "storage")]
+use crate::storage::seed_fingerprint::verify_unverified_seed_fingerprint;
 
 impl AccountHandle {
-    /// Function to sign a transaction essence
-    pub async fn sign_transaction_essence(
+    /// Sign the essence of a prepared transaction, doesn't submit it
+    pub async fn sign_transaction(
         &self,
         prepared_transaction_data: &PreparedTransactionData,
     ) -> crate::Result<SignedTransactionData> {
-        log::debug!("[TRANSACTION] sign_transaction_essence");
+        log::debug!("[TRANSACTION] sign_transaction");
         log::debug!("[TRANSACTION] prepared_transaction_data {prepared_transaction_data:?}");
+        if self.read().await.watch_only {
+            return Err(crate::Error::WatchOnlyAccount);
+        }
+        #[cfg(feature = "storage")]
+        verify_unverified_seed_fingerprint(
+            &self.storage_manager,
+            &self.secret_manager,
+            #[cfg(feature = "ledger_nano")]
+            &self.ledger_nano_queue,
+        )
+        .await?;
+        // Enforced here so it also applies to transactions that were prepared separately
+        self.check_signing_policy(prepared_transaction_data).await?;
+
+        let result = self.sign_prepared_transaction(prepared_transaction_data).await;
+        if result.is_err() {
+            // The signing error is returned, not a failure to release the reservation
+            if let Err(err) = self.release_spending_reservation(prepared_transaction_data).await {
+                log::debug!("[TRANSACTION] releasing the spending reservation failed: {err}");
+            }
+        }
+        result
+    }
+
+    /// Sign the essence of a prepared transaction, doesn't submit it
+    #[deprecated(since = "1.0.0-rc.7", note = "use `sign_transaction()` instead")]
+    pub async fn sign_transaction_essence(
+        &self,
+        prepared_transaction_data: &PreparedTransactionData,
+    ) -> crate::Result<SignedTransactionData> {
+        self.sign_transaction(prepared_transaction_data).await
+    }
+
+    async fn sign_prepared_transaction(
+        &self,
+        prepared_transaction_data: &PreparedTransactionData,
+    ) -> crate::Result<SignedTransactionData> {
         #[cfg(feature = "events")]
         self.event_emitter.lock().await.emit(
             self.read().await.index,
             WalletEvent::TransactionProgress(TransactionProgressEvent::SigningTransaction),
         );
 
-        #[cfg(all(feature = "events", feature = "ledger_nano"))]
+        #[cfg(feature = "ledger_nano")]
         if let SecretManager::LedgerNano(ledger) = &*self.secret_manager.read().await {
-            let ledger_nano_status = ledger.get_ledger_nano_status().await;
+            let ledger_nano_status = self
+                .ledger_nano_queue
+                .get_status(
+                    ledger,
+                    #[cfg(feature = "events")]
+                    Some((&*self.event_emitter, self.read().await.index)),
+                )
+                .await?;
+            // Fail early instead of letting the device reject the transaction
+            LedgerNanoCapabilities::try_from_status(&ledger_nano_status)?
+                .check_transaction(prepared_transaction_data)?;
+
+            #[cfg(feature = "events")]
             if let Some(buffer_size) = ledger_nano_status.buffer_size() {
                 if needs_blind_signing(prepared_transaction_data, buffer_size) {
                     self.event_emitter.lock().await.emit(
@@ -51,12 +105,40 @@ impl AccountHandle {
             }
         }
 
-        let unlocks = self
-            .secret_manager
-            .read()
-            .await
-            .sign_transaction_essence(prepared_transaction_data, None)
-            .await?;
+        let unlocks = match &*self.secret_manager.read().await {
+            #[cfg(feature = "ledger_nano")]
+            secret_manager @ SecretManager::LedgerNano(_) => {
+                self.ledger_nano_queue
+                    .run(
+                        LedgerNanoOperation::SignTransaction,
+                        #[cfg(feature = "events")]
+                        Some((&*self.event_emitter, self.read().await.index)),
+                        async {
+                            secret_manager
+                                .sign_transaction_essence(prepared_transaction_data, None)
+                                .await
+                                .map_err(Into::into)
+                        },
+                    )
+                    .await?
+            }
+            secret_manager => match secret_manager
+                .sign_transaction_essence(prepared_transaction_data, None)
+                .await
+            {
+                Ok(unlocks) => unlocks,
+                Err(err) => {
+                    #[cfg(all(feature = "events", feature = "stronghold"))]
+                    if let SecretManager::Stronghold(stronghold) = secret_manager {
+                        if !stronghold.is_key_available().await {
+                            self.emit_alert(self.read().await.index, AlertCode::SecretManagerLocked, err.to_string())
+                                .await;
+                        }
+                    }
+                    return Err(err.into());
+                }
+            },
+        };
 
         let transaction_payload = TransactionPayload::new(prepared_transaction_data.essence.clone(), unlocks)?;
 
@@ -0,0 +1,455 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{Debug, Formatter},
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+};
+
+use instant::SystemTime;
+use iota_client::{
+    api::PreparedTransactionData,
+    block::{
+        address::Address,
+        output::{Output, TokenId},
+        payload::transaction::TransactionEssence,
+    },
+};
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+
+use crate::account::{
+    handle::AccountHandle,
+    operations::{helpers::output::output_address, transaction::dust_policy::DustPolicy},
+    types::InclusionState,
+    Account,
+};
+
+// Window for the daily amount limit in milliseconds
+const DAY_IN_MILLIS: u128 = 24 * 60 * 60 * 1000;
+
+/// Limits and address lists that every transaction needs to comply with before it gets signed. Amounts only include
+/// outputs that don't belong to the account, so remainders and transactions to own addresses aren't counted. A
+/// transaction counts towards the daily limits from the moment it's signed, also if it's queued or was signed for a
+/// sign request and isn't submitted yet.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SigningPolicy {
+    /// Maximum amount that can be sent with a single transaction.
+    pub max_transaction_amount: Option<u64>,
+    /// Maximum amount that can be sent by an account in the last 24 hours, including the new transaction.
+    pub max_daily_amount: Option<u64>,
+    /// Maximum amounts of native tokens that can be sent by an account in the last 24 hours, including the new
+    /// transaction. Native tokens that aren't listed aren't limited.
+    #[serde(default)]
+    pub max_daily_native_token_amounts: HashMap<TokenId, U256>,
+    /// Maximum amount of NFTs that can be sent by an account in the last 24 hours, including the new transaction.
+    pub max_daily_nfts: Option<usize>,
+    /// Bech32 addresses that can receive funds, if set all other addresses are denied.
+    pub allowed_addresses: Option<HashSet<String>>,
+    /// Bech32 addresses that can't receive funds.
+    #[serde(default)]
+    pub denied_addresses: HashSet<String>,
+}
+
+/// The assets of a transaction that was signed by an account, stored with the account for 24 hours, so signed
+/// transactions that aren't in the account yet count towards the daily limits of the signing policy.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpendingReservation {
+    /// The hash of the signed transaction essence.
+    pub essence_hash: String,
+    /// Unix timestamp in milliseconds when the transaction was signed.
+    pub timestamp: u128,
+    /// The amount that leaves the account.
+    pub amount: u64,
+    /// The native token amounts that leave the account.
+    pub native_tokens: HashMap<TokenId, U256>,
+    /// The amount of NFTs that leave the account.
+    pub nfts: usize,
+}
+
+/// A transaction that is about to be signed, passed to every registered confirmation callback.
+#[derive(Debug, Clone)]
+pub struct SigningRequest<'a> {
+    /// The index of the account that signs the transaction.
+    pub account_index: u32,
+    /// The amount that leaves the account.
+    pub amount: u64,
+    /// The addresses outside of the account that receive outputs.
+    pub recipients: Vec<Address>,
+    /// The prepared transaction.
+    pub prepared_transaction_data: &'a PreparedTransactionData,
+}
+
+/// A transaction that complies with the signing policy and is about to be signed, passed to the transaction approver.
+#[derive(Debug, Clone)]
+pub struct TransactionApprovalRequest {
+    /// The index of the account that signs the transaction.
+    pub account_index: u32,
+    /// The amount that leaves the account.
+    pub amount: u64,
+    /// The addresses outside of the account that receive outputs.
+    pub recipients: Vec<Address>,
+    /// The prepared transaction with the essence that will be signed.
+    pub prepared_transaction_data: PreparedTransactionData,
+}
+
+/// The decision of the transaction approver.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransactionApproval {
+    /// The transaction can be signed.
+    Approved,
+    /// The transaction is rejected with a reason.
+    Rejected(String),
+}
+
+type Confirmation = Box<dyn Fn(&SigningRequest<'_>) -> bool + Send + Sync + 'static>;
+type ApprovalFuture = Pin<Box<dyn Future<Output = TransactionApproval> + Send>>;
+type Approver = Arc<dyn Fn(TransactionApprovalRequest) -> ApprovalFuture + Send + Sync + 'static>;
+
+/// The signing policy, the dust policy, the confirmation callbacks and the transaction approver, shared by the account
+/// manager and all accounts.
+#[derive(Default)]
+pub struct SigningGuard {
+    pub(crate) policy: SigningPolicy,
+    pub(crate) dust_policy: DustPolicy,
+    confirmations: Vec<Confirmation>,
+    approver: Option<Approver>,
+}
+
+impl SigningGuard {
+    pub(crate) fn add_confirmation<F>(&mut self, confirmation: F)
+    where
+        F: Fn(&SigningRequest<'_>) -> bool + Send + Sync + 'static,
+    {
+        self.confirmations.push(Box::new(confirmation));
+    }
+
+    pub(crate) fn clear_confirmations(&mut self) {
+        self.confirmations.clear();
+    }
+
+    pub(crate) fn set_approver<F, Fut>(&mut self, approver: F)
+    where
+        F: Fn(TransactionApprovalRequest) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = TransactionApproval> + Send + 'static,
+    {
+        let approver: Approver = Arc::new(move |request| -> ApprovalFuture { Box::pin(approver(request)) });
+        self.approver = Some(approver);
+    }
+
+    pub(crate) fn clear_approver(&mut self) {
+        self.approver = None;
+    }
+
+    // Checks the transaction against the policy and asks all confirmation callbacks, returns the assets that leave the
+    // account
+    fn check(
+        &self,
+        account: &Account,
+        prepared_transaction_data: &PreparedTransactionData,
+        essence_hash: &str,
+    ) -> crate::Result<Outgoing> {
+        let outgoing = outgoing(account, &prepared_transaction_data.essence);
+        let amount = outgoing.amount;
+
+        if let Some(max_transaction_amount) = self.policy.max_transaction_amount {
+            if amount > max_transaction_amount {
+                return Err(crate::Error::SigningPolicyViolation(format!(
+                    "transaction amount {amount} exceeds the limit of {max_transaction_amount}"
+                )));
+            }
+        }
+
+        if self.policy.max_daily_amount.is_some()
+            || !self.policy.max_daily_native_token_amounts.is_empty()
+            || self.policy.max_daily_nfts.is_some()
+        {
+            let sent = sent_in_last_day(account, essence_hash);
+            if let Some(max_daily_amount) = self.policy.max_daily_amount {
+                let sent_amount = sent.amount + amount;
+                if sent_amount > max_daily_amount {
+                    return Err(crate::Error::SigningPolicyViolation(format!(
+                        "daily amount {sent_amount} exceeds the limit of {max_daily_amount}"
+                    )));
+                }
+            }
+            for (token_id, max_daily_amount) in &self.policy.max_daily_native_token_amounts {
+                if let Some(token_amount) = outgoing.native_tokens.get(token_id) {
+                    let sent_amount = sent
+                        .native_tokens
+                        .get(token_id)
+                        .copied()
+                        .unwrap_or_default()
+                        .saturating_add(*token_amount);
+                    if sent_amount > *max_daily_amount {
+                        return Err(crate::Error::SigningPolicyViolation(format!(
+                            "daily amount {sent_amount} of native token {token_id} exceeds the limit of \
+                             {max_daily_amount}"
+                        )));
+                    }
+                }
+            }
+            if let Some(max_daily_nfts) = self.policy.max_daily_nfts {
+                let sent_nfts = sent.nfts + outgoing.nfts;
+                if outgoing.nfts > 0 && sent_nfts > max_daily_nfts {
+                    return Err(crate::Error::SigningPolicyViolation(format!(
+                        "daily amount of {sent_nfts} NFTs exceeds the limit of {max_daily_nfts}"
+                    )));
+                }
+            }
+        }
+
+        let allowed_addresses = self
+            .policy
+            .allowed_addresses
+            .as_ref()
+            .map(|addresses| parse_addresses(addresses))
+            .transpose()?;
+        let denied_addresses = parse_addresses(&self.policy.denied_addresses)?;
+        let bech32_hrp = account
+            .public_addresses()
+            .first()
+            .map(|address| address.address().bech32_hrp().to_string())
+            .unwrap_or_default();
+        for recipient in &outgoing.recipients {
+            let allowed = allowed_addresses
+                .as_ref()
+                .map_or(true, |addresses| addresses.contains(recipient));
+            if !allowed || denied_addresses.contains(recipient) {
+                return Err(crate::Error::SigningPolicyViolation(format!(
+                    "recipient {} is not allowed",
+                    recipient.to_bech32(&bech32_hrp)
+                )));
+            }
+        }
+
+        if !self.confirmations.is_empty() {
+            let signing_request = SigningRequest {
+                account_index: *account.index(),
+                amount,
+                recipients: outgoing.recipients.clone(),
+                prepared_transaction_data,
+            };
+            if !self
+                .confirmations
+                .iter()
+                .all(|confirmation| confirmation(&signing_request))
+            {
+                return Err(crate::Error::SigningPolicyViolation(
+                    "transaction was not confirmed".to_string(),
+                ));
+            }
+        }
+
+        Ok(outgoing)
+    }
+}
+
+impl Debug for SigningGuard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SigningGuard")
+            .field("policy", &self.policy)
+            .field("dust_policy", &self.dust_policy)
+            .field("confirmations", &self.confirmations.len())
+            .field("approver", &self.approver.is_some())
+            .finish()
+    }
+}
+
+impl AccountHandle {
+    /// Checks if the transaction complies with the signing policy and asks the transaction approver, fails if it
+    /// doesn't comply or gets rejected. The assets of the transaction are reserved under the account lock, so
+    /// concurrent transactions can't exceed the daily limits together, a transaction that doesn't get signed needs to
+    /// release them with [`AccountHandle::release_spending_reservation()`].
+    pub(crate) async fn check_signing_policy(
+        &self,
+        prepared_transaction_data: &PreparedTransactionData,
+    ) -> crate::Result<()> {
+        log::debug!("[TRANSACTION] check_signing_policy");
+        let essence_hash = prefix_hex::encode(prepared_transaction_data.essence.hash());
+        let (approver, approval_request) = {
+            let mut account = self.write().await;
+            let signing_guard = self.signing_guard.read().await;
+            let outgoing = signing_guard.check(&account, prepared_transaction_data, &essence_hash)?;
+
+            let now = now_millis();
+            account.spending_reservations.retain(|spending_reservation| {
+                spending_reservation.essence_hash != essence_hash
+                    && now.saturating_sub(spending_reservation.timestamp) < DAY_IN_MILLIS
+            });
+            account.spending_reservations.push(SpendingReservation {
+                essence_hash,
+                timestamp: now,
+                amount: outgoing.amount,
+                native_tokens: outgoing.native_tokens,
+                nfts: outgoing.nfts,
+            });
+            #[cfg(feature = "storage")]
+            self.save(Some(&account)).await?;
+
+            let Some(approver) = signing_guard.approver.clone() else {
+                return Ok(());
+            };
+            let approval_request = TransactionApprovalRequest {
+                account_index: *account.index(),
+                amount: outgoing.amount,
+                recipients: outgoing.recipients,
+                prepared_transaction_data: prepared_transaction_data.clone(),
+            };
+            (approver, approval_request)
+        };
+
+        // The locks are released, the approval can wait for a manual review without blocking other accounts
+        match approver(approval_request).await {
+            TransactionApproval::Approved => Ok(()),
+            TransactionApproval::Rejected(reason) => {
+                if let Err(err) = self.release_spending_reservation(prepared_transaction_data).await {
+                    log::debug!("[TRANSACTION] releasing the spending reservation failed: {err}");
+                }
+                Err(crate::Error::TransactionRejected(reason))
+            }
+        }
+    }
+
+    /// Removes the reservation of a transaction that passed the signing policy, but wasn't signed.
+    pub(crate) async fn release_spending_reservation(
+        &self,
+        prepared_transaction_data: &PreparedTransactionData,
+    ) -> crate::Result<()> {
+        let essence_hash = prefix_hex::encode(prepared_transaction_data.essence.hash());
+        let mut account = self.write().await;
+        account
+            .spending_reservations
+            .retain(|spending_reservation| spending_reservation.essence_hash != essence_hash);
+        #[cfg(feature = "storage")]
+        self.save(Some(&account)).await?;
+        Ok(())
+    }
+}
+
+fn parse_addresses(addresses: &HashSet<String>) -> crate::Result<HashSet<Address>> {
+    addresses
+        .iter()
+        .map(|address| Ok(Address::try_from_bech32(address)?.1))
+        .collect()
+}
+
+// The address that controls an output
+fn controlling_address(output: &Output) -> Option<Address> {
+    match output {
+        Output::Alias(alias_output) => Some(*alias_output.state_controller_address()),
+        Output::Foundry(foundry_output) => Some(Address::Alias(*foundry_output.alias_address())),
+        _ => output_address(output),
+    }
+}
+
+// The assets and the addresses of the outputs that don't belong to the account
+#[derive(Default)]
+struct Outgoing {
+    amount: u64,
+    native_tokens: HashMap<TokenId, U256>,
+    nfts: usize,
+    recipients: Vec<Address>,
+}
+
+impl Outgoing {
+    fn add(&mut self, amount: u64, native_tokens: &HashMap<TokenId, U256>, nfts: usize) {
+        self.amount += amount;
+        for (token_id, token_amount) in native_tokens {
+            let sent_amount = self.native_tokens.entry(*token_id).or_default();
+            *sent_amount = sent_amount.saturating_add(*token_amount);
+        }
+        self.nfts += nfts;
+    }
+}
+
+fn outgoing(account: &Account, essence: &TransactionEssence) -> Outgoing {
+    let TransactionEssence::Regular(essence) = essence;
+    let account_addresses = account
+        .public_addresses()
+        .iter()
+        .chain(account.internal_addresses())
+        .map(|address| address.address().inner)
+        .collect::<HashSet<_>>();
+
+    let mut outgoing = Outgoing::default();
+    for output in essence.outputs() {
+        match controlling_address(output) {
+            Some(address) if account_addresses.contains(&address) => {}
+            address => {
+                let native_tokens = output
+                    .native_tokens()
+                    .map(|native_tokens| {
+                        native_tokens
+                            .iter()
+                            .map(|native_token| (*native_token.token_id(), *native_token.amount()))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                outgoing.add(
+                    output.amount(),
+                    &native_tokens,
+                    usize::from(matches!(output, Output::Nft(_))),
+                );
+                if let Some(address) = address {
+                    if !outgoing.recipients.contains(&address) {
+                        outgoing.recipients.push(address);
+                    }
+                }
+            }
+        }
+    }
+    outgoing
+}
+
+// The assets sent by the account in the last 24 hours: transactions that were created in the last 24 hours and didn't
+// conflict, and signed transactions that aren't in the account yet, like queued transactions or transactions signed for
+// a sign request. The reservation of the checked transaction isn't included.
+fn sent_in_last_day(account: &Account, essence_hash: &str) -> Outgoing {
+    let now = now_millis();
+    let mut sent = Outgoing::default();
+    // Transactions are created after they were signed, so older transactions don't have a reservation anymore
+    let mut transaction_essence_hashes = HashSet::new();
+    for transaction in account
+        .transactions()
+        .values()
+        .filter(|transaction| !transaction.incoming && now.saturating_sub(transaction.timestamp) < DAY_IN_MILLIS)
+    {
+        transaction_essence_hashes.insert(prefix_hex::encode(transaction.payload.essence().hash()));
+        if !matches!(
+            transaction.inclusion_state,
+            InclusionState::Conflicting | InclusionState::Expired
+        ) {
+            let transaction_outgoing = outgoing(account, transaction.payload.essence());
+            sent.add(
+                transaction_outgoing.amount,
+                &transaction_outgoing.native_tokens,
+                transaction_outgoing.nfts,
+            );
+        }
+    }
+    for spending_reservation in account.spending_reservations.iter().filter(|spending_reservation| {
+        spending_reservation.essence_hash != essence_hash
+            && now.saturating_sub(spending_reservation.timestamp) < DAY_IN_MILLIS
+            && !transaction_essence_hashes.contains(&spending_reservation.essence_hash)
+    }) {
+        sent.add(
+            spending_reservation.amount,
+            &spending_reservation.native_tokens,
+            spending_reservation.nfts,
+        );
+    }
+    sent
+}
+
+fn now_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("time went backwards")
+        .as_millis()
+}
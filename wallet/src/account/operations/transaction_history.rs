@@ -0,0 +1,220 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{cmp::Ordering, str::FromStr};
+
+use iota_client::block::{address::Address, output::TokenId, payload::transaction::TransactionId};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    account::{
+        handle::AccountHandle,
+        types::{InclusionState, Transaction, TransactionDirection, TransactionDto},
+    },
+    storage::transaction_index::{transaction_index, TransactionIndexEntry},
+    Error,
+};
+
+/// Filter for [`AccountHandle::transactions_paged()`], every set field needs to match.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionHistoryFilter {
+    /// Only sent or only incoming transactions
+    pub direction: Option<TransactionDirection>,
+    /// Unix timestamp in milliseconds from which transactions are included
+    pub start_time: Option<u64>,
+    /// Unix timestamp in milliseconds before which transactions are included
+    pub end_time: Option<u64>,
+    /// Minimum base coin amount sent to other addresses, or received by the account for incoming transactions
+    pub min_amount: Option<u64>,
+    /// Maximum base coin amount sent to other addresses, or received by the account for incoming transactions
+    pub max_amount: Option<u64>,
+    /// Hex encoded tag of the tagged data payload or of an output
+    pub tag: Option<String>,
+    /// Native token in one of the outputs
+    pub token_id: Option<TokenId>,
+    /// Bech32 encoded address of an output or its sender
+    pub address: Option<String>,
+    /// Inclusion state of the transactions
+    pub inclusion_state: Option<InclusionState>,
+}
+
+/// A page of the transaction history, returned from [`AccountHandle::transactions_paged()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionPage {
+    /// The transactions, newest first
+    pub transactions: Vec<Transaction>,
+    /// Cursor to request the next page, `None` if this is the last page
+    pub next_cursor: Option<String>,
+}
+
+/// Dto for [`TransactionPage`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionPageDto {
+    /// The transactions, newest first
+    pub transactions: Vec<TransactionDto>,
+    /// Cursor to request the next page, `None` if this is the last page
+    pub next_cursor: Option<String>,
+}
+
+impl From<&TransactionPage> for TransactionPageDto {
+    fn from(value: &TransactionPage) -> Self {
+        Self {
+            transactions: value.transactions.iter().map(TransactionDto::from).collect(),
+            next_cursor: value.next_cursor.clone(),
+        }
+    }
+}
+
+impl AccountHandle {
+    /// Returns a page of the sent and incoming transactions, newest first, that match the filter. The transactions
+    /// are looked up in the transaction index that is stored with the account. Pass the `next_cursor` of a page to get
+    /// the following one, the cursor stays valid when new transactions are added.
+    /// ```ignore
+    /// let mut cursor = None;
+    /// loop {
+    ///     let page = account.transactions_paged(None, cursor, 50).await?;
+    ///     println!("{:?}", page.transactions);
+    ///     match page.next_cursor {
+    ///         Some(next_cursor) => cursor = Some(next_cursor),
+    ///         None => break,
+    ///     }
+    /// }
+    /// ```
+    pub async fn transactions_paged(
+        &self,
+        filter: Option<TransactionHistoryFilter>,
+        cursor: Option<String>,
+        limit: usize,
+    ) -> crate::Result<TransactionPage> {
+        log::debug!("[transactions_paged]");
+        let filter = filter.unwrap_or_default();
+        let address = filter
+            .address
+            .as_ref()
+            .map(|address| Address::try_from_bech32(address).map(|(_, address)| address))
+            .transpose()?;
+        let tag = filter
+            .tag
+            .as_ref()
+            .map(|tag| {
+                prefix_hex::decode::<Vec<u8>>(tag)
+                    .map_err(|_| Error::InvalidTransactionFilter(format!("invalid tag {tag}")))
+            })
+            .transpose()?;
+        let cursor = cursor.as_deref().map(parse_cursor).transpose()?;
+
+        let account_index = self.read().await.index;
+        let stored_transaction_index = self
+            .storage_manager
+            .lock()
+            .await
+            .get_transaction_index(account_index)
+            .await?;
+        let account = self.read().await;
+        // Accounts stored by older versions don't have an index yet
+        let transaction_index = stored_transaction_index.unwrap_or_else(|| transaction_index(&account));
+
+        let mut matching_entries = transaction_index
+            .iter()
+            // The index is sorted, so the transactions after the cursor are the next ones
+            .filter(|entry| {
+                cursor.as_ref().map_or(true, |(timestamp, transaction_id)| {
+                    compare_entry(entry, *timestamp, transaction_id) == Ordering::Greater
+                })
+            })
+            .filter(|entry| matches_filter(entry, &filter, address.as_ref(), tag.as_deref()));
+
+        let mut transactions = Vec::new();
+        let mut last_entry = None;
+        for entry in matching_entries.by_ref().take(limit) {
+            let transaction = if entry.incoming {
+                account.incoming_transactions.get(&entry.transaction_id)
+            } else {
+                account.transactions.get(&entry.transaction_id)
+            };
+            if let Some(transaction) = transaction {
+                transactions.push(transaction.clone());
+            }
+            last_entry = Some(entry);
+        }
+        let next_cursor = match (last_entry, matching_entries.next()) {
+            (Some(last_entry), Some(_)) => Some(format!("{}-{}", last_entry.timestamp, last_entry.transaction_id)),
+            _ => None,
+        };
+
+        Ok(TransactionPage {
+            transactions,
+            next_cursor,
+        })
+    }
+}
+
+// The cursor is the timestamp and the transaction id of the last transaction of the previous page
+fn parse_cursor(cursor: &str) -> crate::Result<(u128, TransactionId)> {
+    let invalid_cursor = || Error::InvalidTransactionFilter(format!("invalid cursor {cursor}"));
+    let (timestamp, transaction_id) = cursor.split_once('-').ok_or_else(invalid_cursor)?;
+
+    Ok((
+        timestamp.parse().map_err(|_| invalid_cursor())?,
+        TransactionId::from_str(transaction_id).map_err(|_| invalid_cursor())?,
+    ))
+}
+
+// Compares an entry with a position in the index, which is sorted by the newest timestamp first and then by the
+// transaction id
+fn compare_entry(entry: &TransactionIndexEntry, timestamp: u128, transaction_id: &TransactionId) -> Ordering {
+    timestamp
+        .cmp(&entry.timestamp)
+        .then(entry.transaction_id.cmp(transaction_id))
+}
+
+fn matches_filter(
+    entry: &TransactionIndexEntry,
+    filter: &TransactionHistoryFilter,
+    address: Option<&Address>,
+    tag: Option<&[u8]>,
+) -> bool {
+    if let Some(direction) = filter.direction {
+        if entry.incoming != (direction == TransactionDirection::Incoming) {
+            return false;
+        }
+    }
+    if let Some(start_time) = filter.start_time {
+        if entry.timestamp < u128::from(start_time) {
+            return false;
+        }
+    }
+    if let Some(end_time) = filter.end_time {
+        if entry.timestamp >= u128::from(end_time) {
+            return false;
+        }
+    }
+    if filter.min_amount.map_or(false, |min_amount| entry.amount < min_amount)
+        || filter.max_amount.map_or(false, |max_amount| entry.amount > max_amount)
+    {
+        return false;
+    }
+    if let Some(tag) = tag {
+        if !entry.tags.iter().any(|entry_tag| entry_tag == tag) {
+            return false;
+        }
+    }
+    if let Some(token_id) = &filter.token_id {
+        if !entry.token_ids.contains(token_id) {
+            return false;
+        }
+    }
+    if let Some(address) = address {
+        if !entry.addresses.contains(address) {
+            return false;
+        }
+    }
+    if let Some(inclusion_state) = filter.inclusion_state {
+        if entry.inclusion_state != inclusion_state {
+            return false;
+        }
+    }
+    true
+}
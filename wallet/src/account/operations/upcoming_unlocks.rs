@@ -0,0 +1,75 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use iota_client::block::output::{Output, OutputId};
+use serde::{Deserialize, Serialize};
+
+use crate::account::{handle::AccountHandle, types::OutputData};
+
+/// A timelocked output of the account that can't be unlocked yet, like the outputs of a vesting schedule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpcomingUnlock {
+    /// The id of the output.
+    pub output_id: OutputId,
+    /// The base coin amount of the output.
+    pub amount: u64,
+    /// Unix timestamp in seconds from which the output can be unlocked.
+    pub unlocks_at: u32,
+    /// Seconds until the output can be unlocked.
+    pub time_to_unlock: u32,
+    /// Bech32 encoded address of the sender, if the output has a sender feature.
+    pub sender: Option<String>,
+}
+
+impl AccountHandle {
+    /// Returns the timelocked outputs of the account that can't be unlocked yet, ordered by the time they unlock. With
+    /// `within` only the outputs that unlock in this period are returned.
+    pub async fn upcoming_unlocks(&self, within: Option<Duration>) -> crate::Result<Vec<UpcomingUnlock>> {
+        log::debug!("[upcoming_unlocks]");
+        let current_time = self.client.get_time_checked().await?;
+        let account = self.read().await;
+
+        let bech32_hrp = match account.public_addresses.first() {
+            Some(address) => address.address.bech32_hrp.clone(),
+            None => return Ok(Vec::new()),
+        };
+
+        let mut upcoming_unlocks: Vec<UpcomingUnlock> = account
+            .unspent_outputs
+            .values()
+            .filter_map(|output_data| upcoming_unlock(output_data, current_time, &bech32_hrp))
+            .filter(|upcoming_unlock| {
+                within.map_or(true, |within| {
+                    u64::from(upcoming_unlock.time_to_unlock) <= within.as_secs()
+                })
+            })
+            .collect();
+        upcoming_unlocks.sort_by_key(|upcoming_unlock| upcoming_unlock.unlocks_at);
+
+        Ok(upcoming_unlocks)
+    }
+}
+
+// Returns the output if its timelock didn't expire yet
+fn upcoming_unlock(output_data: &OutputData, current_time: u32, bech32_hrp: &str) -> Option<UpcomingUnlock> {
+    let (unlock_conditions, features) = match &output_data.output {
+        Output::Basic(basic_output) => (basic_output.unlock_conditions(), basic_output.features()),
+        Output::Nft(nft_output) => (nft_output.unlock_conditions(), nft_output.features()),
+        _ => return None,
+    };
+    let timelock = unlock_conditions.timelock()?;
+    if current_time >= timelock.timestamp() {
+        return None;
+    }
+
+    Some(UpcomingUnlock {
+        output_id: output_data.output_id,
+        amount: output_data.output.amount(),
+        unlocks_at: timelock.timestamp(),
+        time_to_unlock: timelock.timestamp() - current_time,
+        sender: features.sender().map(|sender| sender.address().to_bech32(bech32_hrp)),
+    })
+}
@@ -0,0 +1,55 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_client::block::output::{AliasId, AliasOutput, OutputId};
+use serde::{Deserialize, Serialize};
+
+use crate::account::types::address::AddressWrapper;
+
+/// An alias that is or was controlled by the account, updated when the account is synced.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AliasRecord {
+    /// The id of the alias.
+    pub alias_id: AliasId,
+    /// The output that holds the alias, the last known one if the account doesn't control the alias anymore.
+    pub output_id: OutputId,
+    /// The index of the state, increased with every state transition.
+    pub state_index: u32,
+    /// The amount of foundries that were created by the alias.
+    pub foundry_counter: u32,
+    /// The address that can transition the state of the alias.
+    pub state_controller: AddressWrapper,
+    /// The address that can change the controllers of the alias and destroy it.
+    pub governor: AddressWrapper,
+    /// The metadata of the current state.
+    pub state_metadata: Vec<u8>,
+    /// If the account still controls the alias.
+    pub status: AliasStatus,
+}
+
+impl AliasRecord {
+    pub(crate) fn new(alias_id: AliasId, output_id: OutputId, alias_output: &AliasOutput, bech32_hrp: &str) -> Self {
+        Self {
+            alias_id,
+            output_id,
+            state_index: alias_output.state_index(),
+            foundry_counter: alias_output.foundry_counter(),
+            state_controller: AddressWrapper::new(*alias_output.state_controller_address(), bech32_hrp.to_string()),
+            governor: AddressWrapper::new(*alias_output.governor_address(), bech32_hrp.to_string()),
+            state_metadata: alias_output.state_metadata().to_vec(),
+            status: AliasStatus::Controlled,
+        }
+    }
+}
+
+/// The status of an [`AliasRecord`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum AliasStatus {
+    /// The alias is held by an unspent output of the account.
+    Controlled,
+    /// Both controllers of the alias were changed to addresses of someone else.
+    Transferred,
+    /// The alias was destroyed by a transaction of the account.
+    Destroyed,
+}
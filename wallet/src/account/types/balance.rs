@@ -3,6 +3,8 @@
 
 use std::collections::HashMap;
 
+#[cfg(feature = "participation")]
+use iota_client::api_types::plugins::participation::types::ParticipationEventId;
 use iota_client::block::{
     dto::U256Dto,
     output::{dto::TokenIdDto, feature::MetadataFeature, AliasId, FoundryId, NftId, OutputId, TokenId},
@@ -10,6 +12,8 @@ use iota_client::block::{
 use primitive_types::U256;
 use serde::{Deserialize, Serialize};
 
+use crate::account::operations::{spam_filter::QuarantinedOutput, syncing::freshness::SyncFreshness};
+
 /// The balance of an account, returned from [`crate::account::handle::AccountHandle::sync()`] and
 /// [`crate::account::handle::AccountHandle::balance()`].
 #[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
@@ -35,6 +39,17 @@ pub struct AccountBalance {
     /// change at any time
     #[serde(rename = "potentiallyLockedOutputs")]
     pub potentially_locked_outputs: HashMap<OutputId, bool>,
+    /// Outputs with NFTs or native tokens flagged by the spam filter, they aren't part of the other fields
+    #[serde(default)]
+    pub quarantined: Vec<QuarantinedOutput>,
+    /// When the account was synced the last time, so outdated balances can be recognized
+    #[serde(default)]
+    pub freshness: SyncFreshness,
+    /// Staked amounts and rewards of the staking events, from the last participation state update
+    #[cfg(feature = "participation")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "participation")))]
+    #[serde(rename = "stakingRewards", default)]
+    pub staking_rewards: Vec<StakingRewardsBalance>,
 }
 
 /// Dto for the balance of an account, returned from [`crate::account::handle::AccountHandle::sync()`] and
@@ -62,6 +77,17 @@ pub struct AccountBalanceDto {
     /// change at any time
     #[serde(rename = "potentiallyLockedOutputs")]
     pub potentially_locked_outputs: HashMap<OutputId, bool>,
+    /// Outputs with NFTs or native tokens flagged by the spam filter, they aren't part of the other fields
+    #[serde(default)]
+    pub quarantined: Vec<QuarantinedOutput>,
+    /// When the account was synced the last time, so outdated balances can be recognized
+    #[serde(default)]
+    pub freshness: SyncFreshness,
+    /// Staked amounts and rewards of the staking events, from the last participation state update
+    #[cfg(feature = "participation")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "participation")))]
+    #[serde(rename = "stakingRewards", default)]
+    pub staking_rewards: Vec<StakingRewardsBalanceDto>,
 }
 
 impl From<&AccountBalance> for AccountBalanceDto {
@@ -78,6 +104,14 @@ impl From<&AccountBalance> for AccountBalanceDto {
             aliases: value.aliases.clone(),
             foundries: value.foundries.clone(),
             potentially_locked_outputs: value.potentially_locked_outputs.clone(),
+            quarantined: value.quarantined.clone(),
+            freshness: value.freshness.clone(),
+            #[cfg(feature = "participation")]
+            staking_rewards: value
+                .staking_rewards
+                .iter()
+                .map(StakingRewardsBalanceDto::from)
+                .collect::<_>(),
         }
     }
 }
@@ -217,3 +251,54 @@ impl From<&NativeTokensBalance> for NativeTokensBalanceDto {
         }
     }
 }
+
+/// Staking rewards fields for [`AccountBalance`]
+#[cfg(feature = "participation")]
+#[cfg_attr(docsrs, doc(cfg(feature = "participation")))]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct StakingRewardsBalance {
+    /// Staking event id
+    #[serde(rename = "eventId")]
+    pub event_id: ParticipationEventId,
+    /// Symbol of the rewards
+    pub symbol: String,
+    /// Amount that is currently staked
+    pub staked: u64,
+    /// Rewards earned in the event
+    pub rewards: u64,
+    /// If the rewards reach the required minimum of the event
+    #[serde(rename = "minimumReached")]
+    pub minimum_reached: bool,
+}
+
+/// Staking rewards fields for [`AccountBalanceDto`]
+#[cfg(feature = "participation")]
+#[cfg_attr(docsrs, doc(cfg(feature = "participation")))]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct StakingRewardsBalanceDto {
+    /// Staking event id
+    #[serde(rename = "eventId")]
+    pub event_id: ParticipationEventId,
+    /// Symbol of the rewards
+    pub symbol: String,
+    /// Amount that is currently staked
+    pub staked: String,
+    /// Rewards earned in the event
+    pub rewards: String,
+    /// If the rewards reach the required minimum of the event
+    #[serde(rename = "minimumReached")]
+    pub minimum_reached: bool,
+}
+
+#[cfg(feature = "participation")]
+impl From<&StakingRewardsBalance> for StakingRewardsBalanceDto {
+    fn from(value: &StakingRewardsBalance) -> Self {
+        Self {
+            event_id: value.event_id,
+            symbol: value.symbol.clone(),
+            staked: value.staked.to_string(),
+            rewards: value.rewards.to_string(),
+            minimum_reached: value.minimum_reached,
+        }
+    }
+}
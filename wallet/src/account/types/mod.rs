@@ -5,9 +5,12 @@
 pub(crate) mod address;
 /// Custom de/serialization for [`address::AddressWrapper`]
 pub(crate) mod address_serde;
+/// The record of an alias controlled by the account
+pub(crate) mod alias;
 pub(crate) mod balance;
 #[cfg(feature = "participation")]
 pub mod participation;
+pub(crate) mod statistics;
 
 use std::str::FromStr;
 
@@ -29,11 +32,15 @@ use serde::{Deserialize, Deserializer, Serialize};
 
 pub use self::{
     address::{AccountAddress, AddressWithUnspentOutputs, AddressWrapper},
+    alias::{AliasRecord, AliasStatus},
     balance::{
         AccountBalance, AccountBalanceDto, BaseCoinBalance, BaseCoinBalanceDto, NativeTokensBalance,
         NativeTokensBalanceDto, RequiredStorageDeposit,
     },
+    statistics::{AccountStatistics, CounterpartStatistics, TransactionKind},
 };
+#[cfg(feature = "participation")]
+pub use self::balance::{StakingRewardsBalance, StakingRewardsBalanceDto};
 use crate::account::Account;
 
 /// An output with metadata
@@ -157,6 +164,21 @@ pub struct Transaction {
     // set if the transaction was created by the wallet or if it was sent by someone else and is incoming
     pub incoming: bool,
     pub note: Option<String>,
+    /// Local labels of the transaction, they're never sent
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// How often the transaction was reattached while syncing
+    #[serde(default)]
+    pub reattachments: u32,
+    /// The conflicting or expired transaction that was retried with this one
+    #[serde(rename = "retryOf", default)]
+    pub retry_of: Option<TransactionId>,
+    /// The transaction that retried this one
+    #[serde(rename = "retriedBy", default)]
+    pub retried_by: Option<TransactionId>,
+    /// Unix timestamp in milliseconds after which the transaction is given up while syncing, if it's still pending
+    #[serde(rename = "expiresAt", default)]
+    pub expires_at: Option<u128>,
     /// Outputs that are used as input in the transaction. May not be all, because some may have already been deleted
     /// from the node.
     // serde(default) is needed so it doesn't break with old dbs
@@ -185,6 +207,19 @@ pub struct TransactionDto {
     /// If the transaction was created by the wallet or if it was sent by someone else and is incoming
     pub incoming: bool,
     pub note: Option<String>,
+    /// Local labels of the transaction
+    pub labels: Vec<String>,
+    /// How often the transaction was reattached while syncing
+    pub reattachments: u32,
+    /// The conflicting or expired transaction that was retried with this one
+    #[serde(rename = "retryOf")]
+    pub retry_of: Option<TransactionId>,
+    /// The transaction that retried this one
+    #[serde(rename = "retriedBy")]
+    pub retried_by: Option<TransactionId>,
+    /// Unix timestamp in milliseconds after which the transaction is given up while syncing, if it's still pending
+    #[serde(rename = "expiresAt")]
+    pub expires_at: Option<String>,
     pub inputs: Vec<OutputWithMetadataResponse>,
 }
 
@@ -199,6 +234,11 @@ impl From<&Transaction> for TransactionDto {
             network_id: value.network_id.to_string(),
             incoming: value.incoming,
             note: value.note.clone(),
+            labels: value.labels.clone(),
+            reattachments: value.reattachments,
+            retry_of: value.retry_of,
+            retried_by: value.retried_by,
+            expires_at: value.expires_at.map(|expires_at| expires_at.to_string()),
             inputs: value.inputs.clone(),
         }
     }
@@ -211,6 +251,66 @@ pub enum InclusionState {
     Confirmed,
     Conflicting,
     UnknownPruned,
+    // Not included after the maximum number of reattachments or the maximum pending time, the inputs were made
+    // available again
+    Expired,
+}
+
+/// The direction of a transaction, seen from the account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransactionDirection {
+    /// Sent by the account
+    Outgoing,
+    /// Received from someone else
+    Incoming,
+}
+
+/// A transfer between two accounts of the same account manager, recorded on both accounts, so the statistics of the
+/// whole wallet don't count it as spent and received.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InternalTransfer {
+    /// The index of the sending account
+    pub from_account: u32,
+    /// The index of the receiving account
+    pub to_account: u32,
+    /// The transferred base coin amount
+    pub amount: u64,
+}
+
+/// A difference between the recorded and the current ledger state of an output, because the ledger was rolled back
+/// or the node follows another ledger.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum LedgerConflict {
+    /// A confirmed unspent output is unknown to the node, but it was booked after the pruning index, so it can't have
+    /// been pruned.
+    OutputMissing {
+        #[serde(rename = "outputId")]
+        output_id: OutputId,
+    },
+    /// An output that was recorded as spent is unspent again.
+    SpendReverted {
+        #[serde(rename = "outputId")]
+        output_id: OutputId,
+    },
+    /// An output was spent by another transaction than recorded.
+    SpentByOtherTransaction {
+        #[serde(rename = "outputId")]
+        output_id: OutputId,
+        #[serde(rename = "recordedTransactionId")]
+        recorded_transaction_id: String,
+        #[serde(rename = "transactionId")]
+        transaction_id: String,
+    },
+    /// An output was booked in another block than recorded.
+    BookedInOtherBlock {
+        #[serde(rename = "outputId")]
+        output_id: OutputId,
+        #[serde(rename = "recordedBlockId")]
+        recorded_block_id: String,
+        #[serde(rename = "blockId")]
+        block_id: String,
+    },
 }
 
 /// The output kind enum.
@@ -0,0 +1,60 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Statistics over the transactions of an account, returned from
+/// [`crate::account::handle::AccountHandle::statistics()`].
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountStatistics {
+    /// Total amount of the base coin sent to other addresses
+    pub sent: u64,
+    /// Total amount of the base coin received from other addresses
+    pub received: u64,
+    /// Amount of sent transactions
+    pub sent_transactions: usize,
+    /// Amount of received transactions
+    pub received_transactions: usize,
+    /// Amount of transactions by their kind
+    pub transaction_kinds: HashMap<TransactionKind, usize>,
+    /// The addresses with the most transactions
+    pub top_counterparts: Vec<CounterpartStatistics>,
+}
+
+/// The kind of a transaction, determined by its outputs.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum TransactionKind {
+    /// Only base coins were transferred
+    BaseCoinTransfer,
+    /// Native tokens were transferred
+    NativeTokenTransfer,
+    /// An NFT was transferred
+    NftTransfer,
+    /// An NFT was minted
+    NftMint,
+    /// An alias was created
+    AliasCreation,
+    /// A foundry was created or native tokens were minted or melted
+    Foundry,
+    /// All outputs are owned by the account, like for output consolidation
+    Internal,
+    /// Base coins were transferred to or from another account of the wallet
+    InternalTransfer,
+}
+
+/// Statistics over the transactions with a single address.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CounterpartStatistics {
+    /// The bech32 encoded address
+    pub address: String,
+    /// Amount of the base coin sent to this address
+    pub sent: u64,
+    /// Amount of the base coin received from this address
+    pub received: u64,
+    /// Amount of transactions with this address
+    pub transaction_count: usize,
+}
@@ -1,18 +1,27 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
 
 use iota_client::{
-    block::output::{dto::OutputMetadataDto, OutputId},
+    block::{
+        output::{dto::OutputMetadataDto, Output, OutputId},
+        payload::transaction::{TransactionEssence, TransactionId},
+    },
     Client,
 };
 
 use crate::account::{
     handle::AccountHandle,
     operations::syncing::options::SyncOptions,
-    types::{address::AddressWithUnspentOutputs, InclusionState, OutputData, Transaction},
-    AccountAddress,
+    types::{
+        address::{AddressWithUnspentOutputs, AddressWrapper},
+        AliasRecord, AliasStatus, InclusionState, InternalTransfer, LedgerConflict, OutputData, Transaction,
+    },
+    Account, AccountAddress,
 };
 #[cfg(feature = "events")]
 use crate::{
@@ -23,10 +32,141 @@ use crate::{
 };
 
 impl AccountHandle {
-    // Set the alias for the account
+    /// Set the alias for the account. Aliases are unique case-insensitively, the account and the alias index are
+    /// updated in a single storage write.
     pub async fn set_alias(&self, alias: &str) -> crate::Result<()> {
         let mut account = self.write().await;
-        account.alias = alias.to_string();
+        #[cfg(feature = "storage")]
+        let previous_alias = std::mem::replace(&mut account.alias, alias.to_string());
+        #[cfg(not(feature = "storage"))]
+        {
+            account.alias = alias.to_string();
+        }
+        #[cfg(feature = "storage")]
+        if let Err(err) = self.storage_manager.lock().await.save_account_alias(&account).await {
+            account.alias = previous_alias;
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    /// Set the metadata of the application for the account, replacing the previous metadata
+    pub async fn set_metadata(&self, metadata: HashMap<String, String>) -> crate::Result<()> {
+        let mut account = self.write().await;
+        account.metadata = metadata;
+        #[cfg(feature = "storage")]
+        {
+            self.save(Some(&account)).await?;
+            self.storage_manager
+                .lock()
+                .await
+                .update_balance_summary_metadata(account.index, &account.metadata)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Move addresses of the account into a bucket, the bucket is created if it doesn't exist. An address can only be
+    /// in a single bucket, so it's removed from its previous bucket.
+    pub async fn add_addresses_to_bucket(&self, bucket: &str, addresses: &[AddressWrapper]) -> crate::Result<()> {
+        let mut account = self.write().await;
+        for address in addresses {
+            if !account
+                .public_addresses
+                .iter()
+                .chain(account.internal_addresses.iter())
+                .any(|account_address| account_address.address.inner == address.inner)
+            {
+                return Err(crate::Error::AddressNotFoundInAccount(address.to_bech32()));
+            }
+        }
+
+        for bucket_addresses in account.address_buckets.values_mut() {
+            bucket_addresses
+                .retain(|bucket_address| !addresses.iter().any(|address| address.inner == bucket_address.inner));
+        }
+        account
+            .address_buckets
+            .retain(|name, bucket_addresses| name == bucket || !bucket_addresses.is_empty());
+        account
+            .address_buckets
+            .entry(bucket.to_string())
+            .or_default()
+            .extend(addresses.iter().cloned());
+        #[cfg(feature = "storage")]
+        self.save(Some(&account)).await?;
+        Ok(())
+    }
+
+    /// Remove a bucket, its addresses stay in the account
+    pub async fn remove_address_bucket(&self, bucket: &str) -> crate::Result<()> {
+        let mut account = self.write().await;
+        if account.address_buckets.remove(bucket).is_none() {
+            return Err(crate::Error::AddressBucketNotFound(bucket.to_string()));
+        }
+        #[cfg(feature = "storage")]
+        self.save(Some(&account)).await?;
+        Ok(())
+    }
+
+    /// Set or remove the local note of a sent or incoming transaction. Notes are stored with the account and never
+    /// sent.
+    pub async fn set_transaction_note(
+        &self,
+        transaction_id: &TransactionId,
+        note: Option<String>,
+    ) -> crate::Result<()> {
+        let mut account = self.write().await;
+        get_transaction_mut(&mut account, transaction_id)?.note = note;
+        #[cfg(feature = "storage")]
+        self.save(Some(&account)).await?;
+        Ok(())
+    }
+
+    /// Replace the local labels of a sent or incoming transaction, duplicates are removed.
+    pub async fn set_transaction_labels(
+        &self,
+        transaction_id: &TransactionId,
+        mut labels: Vec<String>,
+    ) -> crate::Result<()> {
+        labels.sort();
+        labels.dedup();
+        let mut account = self.write().await;
+        get_transaction_mut(&mut account, transaction_id)?.labels = labels;
+        #[cfg(feature = "storage")]
+        self.save(Some(&account)).await?;
+        Ok(())
+    }
+
+    /// Set or remove the maximum time in milliseconds a sent transaction can be pending, counted from when it was
+    /// created. If it's still pending afterwards, it's given up while syncing, like after
+    /// [`ReattachmentOptions::max_reattachments`](crate::account::ReattachmentOptions::max_reattachments), and its
+    /// inputs are available again. Without a maximum the one of the sync options is used.
+    pub async fn set_transaction_max_pending(
+        &self,
+        transaction_id: &TransactionId,
+        max_pending_milliseconds: Option<u64>,
+    ) -> crate::Result<()> {
+        let mut account = self.write().await;
+        if !account.pending_transactions.contains(transaction_id) {
+            return Err(crate::Error::TransactionNotPending(*transaction_id));
+        }
+        let transaction = get_transaction_mut(&mut account, transaction_id)?;
+        transaction.expires_at = max_pending_milliseconds
+            .map(|max_pending_milliseconds| transaction.timestamp + max_pending_milliseconds as u128);
+        #[cfg(feature = "storage")]
+        self.save(Some(&account)).await?;
+        Ok(())
+    }
+
+    /// Record a transaction to or from another account of the wallet
+    pub(crate) async fn record_internal_transfer(
+        &self,
+        transaction_id: TransactionId,
+        internal_transfer: InternalTransfer,
+    ) -> crate::Result<()> {
+        let mut account = self.write().await;
+        account.internal_transfers.insert(transaction_id, internal_transfer);
         #[cfg(feature = "storage")]
         self.save(Some(&account)).await?;
         Ok(())
@@ -39,10 +179,17 @@ impl AccountHandle {
         unspent_outputs: Vec<OutputData>,
         spent_or_unsynced_output_metadata_map: HashMap<OutputId, Option<OutputMetadataDto>>,
         options: &SyncOptions,
-    ) -> crate::Result<()> {
+    ) -> crate::Result<Vec<LedgerConflict>> {
         log::debug!("[SYNC] Update account with new synced transactions");
 
         let network_id = self.client.get_network_id().await?;
+        // Outputs booked after the pruning index can't be unknown to the node because of pruning
+        let pruning_index = if spent_or_unsynced_output_metadata_map.values().any(Option::is_none) {
+            Some(self.client.get_info().await?.node_info.status.pruning_index)
+        } else {
+            None
+        };
+        let mut ledger_conflicts = Vec::new();
         let mut account = self.write().await;
         #[cfg(feature = "events")]
         let account_index = account.index;
@@ -97,9 +244,26 @@ impl AccountHandle {
 
         // Update spent outputs
         for (output_id, output_metadata_response_opt) in spent_or_unsynced_output_metadata_map {
+            let metadata_missing = output_metadata_response_opt.is_none();
             // If we got the output response and it's still unspent, skip it
             if let Some(output_metadata_response) = output_metadata_response_opt {
                 if output_metadata_response.is_spent {
+                    if let Some(recorded_transaction_id) = account
+                        .outputs
+                        .get(&output_id)
+                        .and_then(|output_data| output_data.metadata.transaction_id_spent.clone())
+                    {
+                        if Some(&recorded_transaction_id) != output_metadata_response.transaction_id_spent.as_ref() {
+                            ledger_conflicts.push(LedgerConflict::SpentByOtherTransaction {
+                                output_id,
+                                recorded_transaction_id,
+                                transaction_id: output_metadata_response
+                                    .transaction_id_spent
+                                    .clone()
+                                    .unwrap_or_default(),
+                            });
+                        }
+                    }
                     account.unspent_outputs.remove(&output_id);
                     if let Some(output_data) = account.outputs.get_mut(&output_id) {
                         output_data.metadata = output_metadata_response;
@@ -113,6 +277,14 @@ impl AccountHandle {
             if let Some(output) = account.outputs.get(&output_id) {
                 // Could also be outputs from other networks after we switched the node, so we check that first
                 if output.network_id == network_id {
+                    if metadata_missing
+                        && pruning_index.map_or(false, |pruning_index| {
+                            output.metadata.milestone_index_booked > pruning_index
+                        })
+                    {
+                        log::warn!("[SYNC] Confirmed output {output_id} is unknown to the node");
+                        ledger_conflicts.push(LedgerConflict::OutputMissing { output_id });
+                    }
                     log::debug!("[SYNC] Spent output {}", output_id);
                     account.locked_outputs.remove(&output_id);
                     account.unspent_outputs.remove(&output_id);
@@ -136,6 +308,20 @@ impl AccountHandle {
 
         // Add new synced outputs
         for output_data in unspent_outputs {
+            if let Some(recorded_output_data) = account.outputs.get(&output_data.output_id) {
+                if recorded_output_data.is_spent && !output_data.is_spent {
+                    ledger_conflicts.push(LedgerConflict::SpendReverted {
+                        output_id: output_data.output_id,
+                    });
+                }
+                if recorded_output_data.metadata.block_id != output_data.metadata.block_id {
+                    ledger_conflicts.push(LedgerConflict::BookedInOtherBlock {
+                        output_id: output_data.output_id,
+                        recorded_block_id: recorded_output_data.metadata.block_id.clone(),
+                        block_id: output_data.metadata.block_id.clone(),
+                    });
+                }
+            }
             // Insert output, if it's unknown emit the NewOutputEvent
             if account
                 .outputs
@@ -161,6 +347,15 @@ impl AccountHandle {
                             }),
                         })),
                     );
+                    // Outputs that weren't created by transactions of the account are deposits
+                    if !output_data.is_spent
+                        && !output_data.remainder
+                        && !account
+                            .transactions
+                            .contains_key(output_data.output_id.transaction_id())
+                    {
+                        account.unconfirmed_deposits.insert(output_data.output_id, None);
+                    }
                 }
             };
             if !output_data.is_spent {
@@ -168,12 +363,14 @@ impl AccountHandle {
             }
         }
 
+        update_alias_records(&mut account);
+
         #[cfg(feature = "storage")]
         {
             log::debug!("[SYNC] storing account {} with new synced data", account.alias());
             self.save(Some(&account)).await?;
         }
-        Ok(())
+        Ok(ledger_conflicts)
     }
 
     /// Update account with newly synced transactions
@@ -189,7 +386,10 @@ impl AccountHandle {
 
         for transaction in updated_transactions {
             match transaction.inclusion_state {
-                InclusionState::Confirmed | InclusionState::Conflicting | InclusionState::UnknownPruned => {
+                InclusionState::Confirmed
+                | InclusionState::Conflicting
+                | InclusionState::UnknownPruned
+                | InclusionState::Expired => {
                     let transaction_id = transaction.payload.id();
                     account.pending_transactions.remove(&transaction_id);
                     log::debug!(
@@ -298,3 +498,61 @@ impl AccountHandle {
         Ok(())
     }
 }
+
+fn get_transaction_mut<'a>(
+    account: &'a mut Account,
+    transaction_id: &TransactionId,
+) -> crate::Result<&'a mut Transaction> {
+    match account.transactions.get_mut(transaction_id) {
+        Some(transaction) => Ok(transaction),
+        None => account
+            .incoming_transactions
+            .get_mut(transaction_id)
+            .ok_or(crate::Error::TransactionNotFound(*transaction_id)),
+    }
+}
+
+// Update the records of the aliases from the unspent outputs, an alias that isn't in the unspent outputs anymore was
+// destroyed if the transaction that spent its last output doesn't have it in its outputs
+fn update_alias_records(account: &mut Account) {
+    let bech32_hrp = match account.public_addresses.first() {
+        Some(address) => address.address.bech32_hrp.clone(),
+        None => return,
+    };
+
+    let mut controlled_aliases = HashSet::new();
+    for output_data in account.unspent_outputs.values() {
+        if let Output::Alias(alias_output) = &output_data.output {
+            let alias_id = alias_output.alias_id_non_null(&output_data.output_id);
+            controlled_aliases.insert(alias_id);
+            account.aliases.insert(
+                alias_id,
+                AliasRecord::new(alias_id, output_data.output_id, alias_output, &bech32_hrp),
+            );
+        }
+    }
+
+    for alias_record in account.aliases.values_mut() {
+        if alias_record.status != AliasStatus::Controlled || controlled_aliases.contains(&alias_record.alias_id) {
+            continue;
+        }
+        let destroyed = account
+            .outputs
+            .get(&alias_record.output_id)
+            .and_then(|output_data| output_data.metadata.transaction_id_spent.as_ref())
+            .and_then(|transaction_id| TransactionId::from_str(transaction_id).ok())
+            .and_then(|transaction_id| account.transactions.get(&transaction_id))
+            .map_or(false, |transaction| {
+                let TransactionEssence::Regular(essence) = transaction.payload.essence();
+                !essence.outputs().iter().any(|output| match output {
+                    Output::Alias(alias_output) => *alias_output.alias_id() == alias_record.alias_id,
+                    _ => false,
+                })
+            });
+        alias_record.status = if destroyed {
+            AliasStatus::Destroyed
+        } else {
+            AliasStatus::Transferred
+        };
+    }
+}
@@ -6,21 +6,46 @@ use std::sync::{
     Arc,
 };
 #[cfg(feature = "storage")]
-use std::{path::PathBuf, sync::atomic::Ordering};
+use std::{
+    path::PathBuf,
+    sync::atomic::{AtomicBool, Ordering},
+};
 
-use iota_client::secret::SecretManager;
+use iota_client::secret::{mnemonic::MnemonicSecretManager, SecretManager};
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "events")]
 use tokio::sync::Mutex;
 use tokio::sync::RwLock;
+use zeroize::Zeroize;
 
+#[cfg(all(feature = "events", feature = "storage"))]
+use crate::account_manager::operations::event_log::EventLogSender;
+#[cfg(feature = "ledger_nano")]
+use crate::account_manager::LedgerNanoQueue;
 #[cfg(feature = "events")]
 use crate::events::EventEmitter;
 #[cfg(all(feature = "storage", not(feature = "jammdb")))]
 use crate::storage::adapter::memory::Memory;
+use crate::{
+    account::{
+        builder::get_first_public_address,
+        handle::AccountHandle,
+        operations::{syncing::request_limiter::RequestLimiter, transaction::signing_policy::SigningGuard},
+    },
+    account_manager::{operations::background_syncing::BackgroundSyncSchedule, AccountManager},
+    ClientOptions,
+};
 #[cfg(feature = "storage")]
-use crate::storage::{constants::default_storage_path, manager::ManagerStorage};
-use crate::{account::handle::AccountHandle, account_manager::AccountManager, ClientOptions};
+use crate::{
+    encryption::{Password, PasswordEncryptedData},
+    storage::{
+        adapter::{profile::ProfileStorageAdapter, StorageAdapter},
+        constants::default_storage_path,
+        manager::{ManagerStorage, StorageManagerHandle},
+        quota::StorageQuota,
+        seed_fingerprint::is_secret_manager_available,
+    },
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 /// Builder for the account manager.
@@ -29,6 +54,23 @@ pub struct AccountManagerBuilder {
     coin_type: Option<u32>,
     #[cfg(feature = "storage")]
     storage_options: Option<StorageOptions>,
+    #[cfg(feature = "storage")]
+    #[serde(default, skip_serializing, skip_deserializing)]
+    storage_quota: Option<StorageQuota>,
+    #[cfg(feature = "storage")]
+    #[serde(default, skip_serializing, skip_deserializing)]
+    encrypted_mnemonic: Option<PasswordEncryptedData>,
+    #[cfg(feature = "storage")]
+    #[serde(default, skip_serializing, skip_deserializing)]
+    mnemonic_passphrase: Option<Password>,
+    #[cfg(feature = "storage")]
+    #[serde(default, skip_serializing, skip_deserializing)]
+    bip39_passphrase: Option<Password>,
+    #[cfg(feature = "storage")]
+    #[serde(default, skip_serializing, skip_deserializing)]
+    profile: Option<Profile>,
+    #[serde(default, skip_serializing, skip_deserializing)]
+    verify_first_address: bool,
     #[serde(default, skip_serializing, skip_deserializing)]
     pub(crate) secret_manager: Option<Arc<RwLock<SecretManager>>>,
 }
@@ -43,6 +85,16 @@ pub(crate) struct StorageOptions {
     pub(crate) manager_store: ManagerStorage,
 }
 
+/// A named profile that stores its records in the storage of another account manager.
+#[derive(Debug, Clone)]
+#[cfg(feature = "storage")]
+pub(crate) struct Profile {
+    pub(crate) name: String,
+    pub(crate) storage_manager: StorageManagerHandle,
+    // Used if no client options are provided and none are stored for the profile yet
+    pub(crate) client_options: ClientOptions,
+}
+
 #[cfg(feature = "storage")]
 impl Default for StorageOptions {
     fn default() -> Self {
@@ -89,6 +141,51 @@ impl AccountManagerBuilder {
         self
     }
 
+    /// Set a mnemonic secret manager with the seed derived from the mnemonic and an optional BIP39 passphrase. The
+    /// addresses of stored accounts are verified, so a wrong mnemonic or passphrase is detected.
+    pub fn with_mnemonic(mut self, mut mnemonic: String, bip39_passphrase: Option<String>) -> crate::Result<Self> {
+        let secret_manager = mnemonic_secret_manager(&mnemonic, bip39_passphrase);
+        mnemonic.zeroize();
+
+        self.secret_manager
+            .replace(Arc::new(RwLock::new(SecretManager::Mnemonic(secret_manager?))));
+        self.verify_first_address = true;
+        Ok(self)
+    }
+
+    /// Set a mnemonic secret manager and store the mnemonic encrypted with the passphrase, so the secret manager can be
+    /// restored with [`AccountManagerBuilder::with_mnemonic_passphrase()`] after a restart. The optional BIP39
+    /// passphrase is not stored.
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    pub fn with_encrypted_mnemonic(
+        mut self,
+        mut mnemonic: String,
+        mut passphrase: String,
+        bip39_passphrase: Option<String>,
+    ) -> crate::Result<Self> {
+        let secret_manager = mnemonic_secret_manager(&mnemonic, bip39_passphrase);
+        let encrypted_mnemonic = PasswordEncryptedData::encrypt(&passphrase, mnemonic.as_bytes());
+        mnemonic.zeroize();
+        passphrase.zeroize();
+
+        self.encrypted_mnemonic.replace(encrypted_mnemonic?);
+        self.secret_manager
+            .replace(Arc::new(RwLock::new(SecretManager::Mnemonic(secret_manager?))));
+        self.verify_first_address = true;
+        Ok(self)
+    }
+
+    /// Set the passphrase and the optional BIP39 passphrase to restore a mnemonic secret manager that was stored with
+    /// [`AccountManagerBuilder::with_encrypted_mnemonic()`]. Only used if no secret manager is provided.
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    pub fn with_mnemonic_passphrase(mut self, passphrase: String, bip39_passphrase: Option<String>) -> Self {
+        self.mnemonic_passphrase.replace(Password::new(passphrase));
+        self.bip39_passphrase = bip39_passphrase.map(Password::new);
+        self
+    }
+
     /// Set the storage path to be used.
     #[cfg(feature = "storage")]
     #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
@@ -100,6 +197,22 @@ impl AccountManagerBuilder {
         self
     }
 
+    /// Set limits for the storage, writes that would exceed them return [`crate::Error::StorageQuotaExceeded`].
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    pub fn with_storage_quota(mut self, quota: StorageQuota) -> Self {
+        self.storage_quota.replace(quota);
+        self
+    }
+
+    /// Set the profile, so the records are stored in the storage of another account manager.
+    #[cfg(feature = "storage")]
+    pub(crate) fn with_profile(mut self, profile: Profile, storage_options: StorageOptions) -> Self {
+        self.profile.replace(profile);
+        self.storage_options.replace(storage_options);
+        self
+    }
+
     /// Builds the account manager
     #[allow(unreachable_code, unused_mut)]
     pub async fn finish(mut self) -> crate::Result<AccountManager> {
@@ -110,7 +223,7 @@ impl AccountManagerBuilder {
         #[cfg(feature = "storage")]
         // Check if the db exists and if not, return an error if one parameter is missing, because otherwise the db
         // would be created with an empty parameter which just leads to errors later
-        if !storage_options.storage_path.is_dir() {
+        if self.profile.is_none() && !storage_options.storage_path.is_dir() {
             if self.client_options.is_none() {
                 return Err(crate::Error::MissingParameter("client_options"));
             }
@@ -121,24 +234,69 @@ impl AccountManagerBuilder {
                 return Err(crate::Error::MissingParameter("secret_manager"));
             }
         }
-        #[cfg(all(feature = "jammdb", feature = "storage"))]
-        let storage =
-            crate::storage::adapter::jammdb::JammdbStorageAdapter::new(storage_options.storage_path.clone())?;
-        #[cfg(all(not(feature = "jammdb"), feature = "storage"))]
-        let storage = Memory::default();
+        #[cfg(feature = "storage")]
+        let storage = match &self.profile {
+            // The storage of the other account manager is already opened, so it's shared
+            Some(profile) => Box::new(ProfileStorageAdapter::new(
+                profile.storage_manager.clone(),
+                &profile.name,
+            )) as Box<dyn StorageAdapter + Send + Sync>,
+            #[cfg(feature = "jammdb")]
+            None => Box::new(crate::storage::adapter::jammdb::JammdbStorageAdapter::new(
+                storage_options.storage_path.clone(),
+            )?),
+            #[cfg(not(feature = "jammdb"))]
+            None => Box::new(Memory::default()),
+        };
+
+        #[cfg(feature = "storage")]
+        let storage_manager = crate::storage::manager::new_storage_manager(None, storage).await?;
+
+        #[cfg(feature = "storage")]
+        if let Some(storage_quota) = self.storage_quota {
+            storage_manager.lock().await.set_quota(storage_quota).await?;
+        }
+
+        #[cfg(feature = "storage")]
+        if let Some(encrypted_mnemonic) = &self.encrypted_mnemonic {
+            storage_manager
+                .lock()
+                .await
+                .save_encrypted_mnemonic(encrypted_mnemonic)
+                .await?;
+        }
 
         #[cfg(feature = "storage")]
-        let storage_manager = crate::storage::manager::new_storage_manager(
-            None,
-            Box::new(storage) as Box<dyn crate::storage::adapter::StorageAdapter + Send + Sync>,
-        )
-        .await?;
+        if let (None, Some(mnemonic_passphrase)) = (&self.secret_manager, &self.mnemonic_passphrase) {
+            if let Some(encrypted_mnemonic) = storage_manager.lock().await.get_encrypted_mnemonic().await? {
+                let mnemonic = encrypted_mnemonic.decrypt_string(mnemonic_passphrase.as_str())?;
+                let secret_manager = mnemonic_secret_manager(
+                    &mnemonic,
+                    self.bip39_passphrase
+                        .as_ref()
+                        .map(|bip39_passphrase| bip39_passphrase.as_str().to_string()),
+                );
+                self.secret_manager
+                    .replace(Arc::new(RwLock::new(SecretManager::Mnemonic(secret_manager?))));
+                self.verify_first_address = true;
+            }
+        }
+
+        // Remember if the secret manager was set, because only then it could have another seed than the stored one
+        #[cfg(feature = "storage")]
+        let secret_manager_provided = self.secret_manager.is_some();
 
         #[cfg(feature = "storage")]
         let read_manager_builder = storage_manager.lock().await.get_account_manager_data().await?;
         #[cfg(not(feature = "storage"))]
         let read_manager_builder: Option<AccountManagerBuilder> = None;
 
+        // A new profile uses the client options of the account manager it was created from
+        #[cfg(feature = "storage")]
+        if let (None, None, Some(profile)) = (&self.client_options, &read_manager_builder, &self.profile) {
+            self.client_options.replace(profile.client_options.clone());
+        }
+
         // prioritise provided client_options and secret_manager over stored ones
         let new_provided_client_options = if self.client_options.is_none() {
             let loaded_client_options = read_manager_builder
@@ -172,9 +330,51 @@ impl AccountManagerBuilder {
             self.coin_type.replace(coin_type);
         }
 
+        #[cfg(feature = "ledger_nano")]
+        let ledger_nano_queue = Arc::new(LedgerNanoQueue::default());
+
+        // Fail before the secret manager gets stored if it has another seed than the one the storage was created with.
+        // A locked Stronghold or a Ledger Nano is verified before it's used the first time instead, so building the
+        // account manager doesn't need the password or the device.
+        #[cfg(feature = "storage")]
+        if secret_manager_provided || storage_manager.lock().await.is_seed_fingerprint_unverified().await? {
+            let seed_fingerprint = storage_manager.lock().await.get_seed_fingerprint().await?;
+            if let (Some(seed_fingerprint), Some(secret_manager)) = (seed_fingerprint, &self.secret_manager) {
+                if is_secret_manager_available(secret_manager).await {
+                    seed_fingerprint
+                        .verify(
+                            secret_manager,
+                            #[cfg(feature = "ledger_nano")]
+                            &ledger_nano_queue,
+                        )
+                        .await?;
+                    storage_manager
+                        .lock()
+                        .await
+                        .set_seed_fingerprint_unverified(false)
+                        .await?;
+                } else {
+                    storage_manager
+                        .lock()
+                        .await
+                        .set_seed_fingerprint_unverified(true)
+                        .await?;
+                }
+            }
+        }
+
         // Store account manager data in storage
         #[cfg(feature = "storage")]
         storage_manager.lock().await.save_account_manager_data(&self).await?;
+        #[cfg(feature = "storage")]
+        if let Some(profile) = &self.profile {
+            profile
+                .storage_manager
+                .lock()
+                .await
+                .add_profile_name(&profile.name)
+                .await?;
+        }
 
         let client = self
             .client_options
@@ -184,39 +384,123 @@ impl AccountManagerBuilder {
 
         #[cfg(feature = "events")]
         let event_emitter = Arc::new(Mutex::new(EventEmitter::new()));
+        #[cfg(all(feature = "events", feature = "storage"))]
+        event_emitter
+            .lock()
+            .await
+            .set_event_log(EventLogSender::spawn(storage_manager.clone()));
+        let signing_guard = Arc::new(RwLock::new(SigningGuard::default()));
+        let request_limiter = Arc::new(RequestLimiter::default());
 
         #[cfg(feature = "storage")]
         let accounts = storage_manager.lock().await.get_accounts().await.unwrap_or_default();
         #[cfg(not(feature = "storage"))]
         let accounts = Vec::new();
-        let mut account_handles: Vec<AccountHandle> = accounts
-            .into_iter()
-            .map(|a| {
+        let mut account_handles: Vec<AccountHandle> = Vec::new();
+        for account in accounts {
+            // Accounts can use nodes of another network than the account manager
+            #[cfg(feature = "storage")]
+            let account_client_options = storage_manager
+                .lock()
+                .await
+                .get_account_client_options(*account.index())
+                .await?;
+            #[cfg(not(feature = "storage"))]
+            let account_client_options: Option<ClientOptions> = None;
+            let account_client = match &account_client_options {
+                Some(client_options) => client_options.clone().finish()?,
+                None => client.clone(),
+            };
+            account_handles.push(
                 AccountHandle::new(
-                    a,
-                    client.clone(),
+                    account,
+                    account_client,
                     self.secret_manager
                         .clone()
                         .expect("secret_manager needs to be provided"),
+                    signing_guard.clone(),
+                    request_limiter.clone(),
+                    #[cfg(feature = "ledger_nano")]
+                    ledger_nano_queue.clone(),
                     #[cfg(feature = "events")]
                     event_emitter.clone(),
                     #[cfg(feature = "storage")]
                     storage_manager.clone(),
                 )
-            })
-            .collect::<_>();
+                .with_client_options(account_client_options),
+            );
+        }
+
+        // A wrong mnemonic or BIP39 passphrase results in different addresses, so we fail early instead of showing an
+        // empty balance
+        if self.verify_first_address {
+            let mut first_account = None;
+            for account_handle in &account_handles {
+                // Watch-only accounts have addresses of other seeds
+                if !*account_handle.read().await.watch_only() {
+                    first_account.replace(account_handle);
+                    break;
+                }
+            }
+            if let Some(account_handle) = first_account {
+                let account = account_handle.read().await;
+                let first_public_address = get_first_public_address(
+                    &account_handle.secret_manager,
+                    #[cfg(feature = "ledger_nano")]
+                    &ledger_nano_queue,
+                    *account.coin_type(),
+                    *account.index(),
+                )
+                .await?;
+                if account
+                    .public_addresses()
+                    .first()
+                    .map(|address| address.address().inner)
+                    != Some(first_public_address)
+                {
+                    return Err(crate::Error::InvalidMnemonic(
+                        "first account address doesn't match, wrong mnemonic or BIP39 passphrase".to_string(),
+                    ));
+                }
+            }
+        }
 
         // If the manager builder is not set, it means the user provided it and we need to update the addresses.
         // In the other case it was loaded from the database and addresses are up to date.
         if new_provided_client_options {
-            for account in account_handles.iter_mut() {
+            for account in account_handles
+                .iter_mut()
+                .filter(|account| account.client_options.is_none())
+            {
                 account.update_account_with_new_client(client.clone()).await?;
             }
         }
 
+        #[cfg(all(feature = "stronghold", feature = "storage"))]
+        let stronghold_snapshot_generations = storage_manager
+            .lock()
+            .await
+            .get_stronghold_snapshot_generations()
+            .await?;
+        #[cfg(all(feature = "stronghold", not(feature = "storage")))]
+        let stronghold_snapshot_generations = 0;
+
         Ok(AccountManager {
             accounts: Arc::new(RwLock::new(account_handles)),
             background_syncing_status: Arc::new(AtomicUsize::new(0)),
+            background_syncing_schedule: Arc::new(RwLock::new(BackgroundSyncSchedule::default())),
+            #[cfg(feature = "storage")]
+            background_compaction_status: Arc::new(AtomicUsize::new(0)),
+            #[cfg(feature = "mqtt")]
+            mqtt_syncing_status: Arc::new(AtomicUsize::new(0)),
+            #[cfg(feature = "event_bridge")]
+            event_bridge_status: Arc::new(AtomicUsize::new(0)),
+            #[cfg(feature = "webhooks")]
+            webhook_dispatcher_status: Arc::new(AtomicUsize::new(0)),
+            #[cfg(feature = "storage")]
+            scheduled_backups_status: Arc::new(AtomicUsize::new(0)),
+            #[cfg(feature = "storage")]
+            offline_mode: Arc::new(AtomicBool::new(false)),
             client_options: Arc::new(RwLock::new(
                 self.client_options
                     .ok_or(crate::Error::MissingParameter("client_options"))?,
@@ -228,6 +512,12 @@ impl AccountManagerBuilder {
             secret_manager: self
                 .secret_manager
                 .ok_or(crate::Error::MissingParameter("secret_manager"))?,
+            signing_guard,
+            request_limiter,
+            #[cfg(feature = "ledger_nano")]
+            ledger_nano_queue,
+            #[cfg(feature = "stronghold")]
+            stronghold_snapshot_generations: Arc::new(AtomicUsize::new(stronghold_snapshot_generations)),
             #[cfg(feature = "events")]
             event_emitter,
             #[cfg(feature = "storage")]
@@ -243,7 +533,31 @@ impl AccountManagerBuilder {
             client_options: Some(account_manager.client_options.read().await.clone()),
             coin_type: Some(account_manager.coin_type.load(Ordering::Relaxed)),
             storage_options: Some(account_manager.storage_options.clone()),
+            storage_quota: None,
+            encrypted_mnemonic: None,
+            mnemonic_passphrase: None,
+            bip39_passphrase: None,
+            profile: None,
+            verify_first_address: false,
             secret_manager: Some(account_manager.secret_manager.clone()),
         }
     }
 }
+
+// Creates a mnemonic secret manager with the seed derived from the mnemonic and the optional BIP39 passphrase
+fn mnemonic_secret_manager(mnemonic: &str, bip39_passphrase: Option<String>) -> crate::Result<MnemonicSecretManager> {
+    // first we check if the mnemonic is valid to give meaningful errors
+    crypto::keys::bip39::wordlist::verify(mnemonic, &crypto::keys::bip39::wordlist::ENGLISH)
+        .map_err(|e| crate::Error::InvalidMnemonic(format!("{e:?}")))?;
+
+    let mut bip39_passphrase = bip39_passphrase.unwrap_or_default();
+    let mut seed = [0u8; 64];
+    crypto::keys::bip39::mnemonic_to_seed(mnemonic, &bip39_passphrase, &mut seed);
+    bip39_passphrase.zeroize();
+    let mut hex_seed = prefix_hex::encode(seed);
+    seed.zeroize();
+    let secret_manager = MnemonicSecretManager::try_from_hex_seed(&hex_seed);
+    hex_seed.zeroize();
+
+    Ok(secret_manager?)
+}
@@ -4,6 +4,8 @@
 pub(crate) mod builder;
 pub(crate) mod operations;
 
+#[cfg(feature = "storage")]
+use std::sync::atomic::AtomicBool;
 use std::sync::{
     atomic::{AtomicU32, AtomicUsize, Ordering},
     Arc,
@@ -14,13 +16,35 @@ use iota_client::{secret::SecretManager, Client};
 use tokio::sync::Mutex;
 use tokio::sync::RwLock;
 
-use self::builder::AccountManagerBuilder;
 #[cfg(feature = "storage")]
 use self::builder::StorageOptions;
+#[cfg(feature = "storage")]
+pub use self::operations::backup_verification::{BackupAccountSummary, BackupVerificationReport};
+#[cfg(feature = "ledger_nano")]
+use self::operations::ledger_nano::LedgerNanoQueue;
+#[cfg(feature = "ledger_nano")]
+pub use self::operations::ledger_nano::{
+    LedgerNanoCapabilities, LedgerNanoOperation, LedgerNanoOperationStatus, LedgerNanoTimeouts,
+};
+#[cfg(feature = "storage")]
+pub use self::operations::portable_backup::{
+    PortableBackup, PortableBackupOptions, PortableRestoreOptions, PORTABLE_BACKUP_VERSION,
+};
+#[cfg(feature = "storage")]
+pub use self::operations::scheduled_payments::SchedulePaymentOptions;
+#[cfg(feature = "stronghold")]
+pub use self::operations::stronghold_snapshot_generations::StrongholdSnapshotGeneration;
+#[cfg(feature = "webhooks")]
+pub use self::operations::webhooks::{Webhook, WebhookPayload, WEBHOOK_SIGNATURE_HEADER};
+pub use self::operations::{
+    account_recovery::{AddressGapReport, RecoverAccountsOptions, RecoveryReport},
+    derivation_verification::{DerivationMismatch, DerivationReport},
+};
+use self::{builder::AccountManagerBuilder, operations::background_syncing::BackgroundSyncSchedule};
 #[cfg(feature = "events")]
 use crate::events::{
-    types::{Event, WalletEventType},
-    EventEmitter,
+    types::{Event, EventFilter, WalletEventType},
+    EventEmitter, EventStream,
 };
 #[cfg(feature = "storage")]
 use crate::storage::manager::StorageManagerHandle;
@@ -28,8 +52,12 @@ use crate::{
     account::{
         builder::AccountBuilder,
         handle::AccountHandle,
-        operations::{balance::add_balances, syncing::SyncOptions},
-        types::AccountBalance,
+        operations::{
+            balance::add_balances,
+            syncing::{request_limiter::RequestLimiter, SyncOptions},
+            transaction::signing_policy::SigningGuard,
+        },
+        types::{address::parse_bech32_address, AccountAddress, AccountBalance},
     },
     ClientOptions,
 };
@@ -42,9 +70,39 @@ pub struct AccountManager {
     pub(crate) accounts: Arc<RwLock<Vec<AccountHandle>>>,
     // 0 = not running, 1 = running, 2 = stopping
     pub(crate) background_syncing_status: Arc<AtomicUsize>,
+    // Per-account intervals and paused accounts of the background syncing
+    pub(crate) background_syncing_schedule: Arc<RwLock<BackgroundSyncSchedule>>,
+    // 0 = not running, 1 = running, 2 = stopping
+    #[cfg(feature = "storage")]
+    pub(crate) background_compaction_status: Arc<AtomicUsize>,
+    // 0 = not running, 1 = running, 2 = stopping
+    #[cfg(feature = "mqtt")]
+    pub(crate) mqtt_syncing_status: Arc<AtomicUsize>,
+    // 0 = not running, 1 = running, 2 = stopping
+    #[cfg(feature = "event_bridge")]
+    pub(crate) event_bridge_status: Arc<AtomicUsize>,
+    // 0 = not running, 1 = running, 2 = stopping
+    #[cfg(feature = "webhooks")]
+    pub(crate) webhook_dispatcher_status: Arc<AtomicUsize>,
+    // 0 = not running, 1 = running, 2 = stopping
+    #[cfg(feature = "storage")]
+    pub(crate) scheduled_backups_status: Arc<AtomicUsize>,
+    // If the background syncing skips the accounts and queued transactions aren't submitted
+    #[cfg(feature = "storage")]
+    pub(crate) offline_mode: Arc<AtomicBool>,
     pub(crate) client_options: Arc<RwLock<ClientOptions>>,
     pub(crate) coin_type: Arc<AtomicU32>,
     pub(crate) secret_manager: Arc<RwLock<SecretManager>>,
+    // Signing policy and confirmation callbacks, shared with all accounts
+    pub(crate) signing_guard: Arc<RwLock<SigningGuard>>,
+    // Adapts the parallel requests during syncing and keeps the request budget of the client
+    pub(crate) request_limiter: Arc<RequestLimiter>,
+    // Runs the operations on a Ledger Nano one after another
+    #[cfg(feature = "ledger_nano")]
+    pub(crate) ledger_nano_queue: Arc<LedgerNanoQueue>,
+    // Amount of Stronghold snapshot copies to keep
+    #[cfg(feature = "stronghold")]
+    pub(crate) stronghold_snapshot_generations: Arc<AtomicUsize>,
     #[cfg(feature = "events")]
     pub(crate) event_emitter: Arc<Mutex<EventEmitter>>,
     #[cfg(feature = "storage")]
@@ -67,6 +125,10 @@ impl AccountManager {
             self.client_options.clone(),
             self.coin_type.load(Ordering::Relaxed),
             self.secret_manager.clone(),
+            self.signing_guard.clone(),
+            self.request_limiter.clone(),
+            #[cfg(feature = "ledger_nano")]
+            self.ledger_nano_queue.clone(),
             #[cfg(feature = "events")]
             self.event_emitter.clone(),
             #[cfg(feature = "storage")]
@@ -74,6 +136,36 @@ impl AccountManager {
         )
     }
 
+    /// Create a watch-only account that syncs the balance and the transactions of bech32 addresses that are controlled
+    /// externally, for example by an offline device. The account can't generate addresses or sign transactions.
+    /// Extended public keys aren't supported, because Ed25519 keys can only be derived with hardened indexes.
+    /// Watch-only accounts get indexes starting at 2^31, so they don't take the account indexes of the seed.
+    pub async fn create_watch_account(
+        &self,
+        addresses: Vec<String>,
+        alias: Option<String>,
+    ) -> crate::Result<AccountHandle> {
+        log::debug!("creating watch account");
+        let addresses = addresses
+            .iter()
+            .enumerate()
+            .map(|(key_index, address)| {
+                Ok(AccountAddress {
+                    address: parse_bech32_address(address)?,
+                    key_index: key_index as u32,
+                    internal: false,
+                    used: false,
+                })
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        let mut builder = self.create_account().with_addresses(addresses).with_watch_only();
+        if let Some(alias) = alias {
+            builder = builder.with_alias(alias);
+        }
+        builder.finish().await
+    }
+
     /// Get all accounts
     pub async fn get_accounts(&self) -> crate::Result<Vec<AccountHandle>> {
         Ok(self.accounts.read().await.clone())
@@ -158,6 +250,16 @@ impl AccountManager {
         emitter.on(events, handler);
     }
 
+    /// Subscribe to the wallet events that match the filter, for example only the events of one account. Returns a
+    /// stream of the events, dropping it ends the subscription. The stream buffers up to `capacity` events, default is
+    /// 1000, if it isn't polled fast enough the oldest events are dropped and
+    /// [`StreamEvent::Lagged`](crate::events::StreamEvent::Lagged) is returned.
+    #[cfg(feature = "events")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "events")))]
+    pub async fn subscribe(&self, filter: EventFilter, capacity: Option<usize>) -> EventStream {
+        self.event_emitter.lock().await.subscribe(filter, capacity)
+    }
+
     /// Remove wallet event listeners, empty vec will remove all listeners
     #[cfg(feature = "events")]
     #[cfg_attr(docsrs, doc(cfg(feature = "events")))]
@@ -0,0 +1,79 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{account::handle::AccountHandle, account_manager::AccountManager};
+
+impl AccountManager {
+    /// Archives an account instead of deleting it. The account isn't loaded anymore, but it can be restored with
+    /// [`AccountManager::restore_account()`].
+    pub async fn archive_account(&self, account_index: u32) -> crate::Result<()> {
+        let mut accounts = self.accounts.write().await;
+
+        let mut position = None;
+        for (i, account_handle) in accounts.iter().enumerate() {
+            if *account_handle.read().await.index() == account_index {
+                position.replace(i);
+                break;
+            }
+        }
+        let position = position.ok_or_else(|| crate::Error::AccountNotFound(account_index.to_string()))?;
+
+        self.storage_manager.lock().await.archive_account(account_index).await?;
+        accounts.remove(position);
+
+        Ok(())
+    }
+
+    /// Restores an archived account.
+    pub async fn restore_account(&self, account_index: u32) -> crate::Result<AccountHandle> {
+        let mut accounts = self.accounts.write().await;
+        let mut aliases = Vec::new();
+        for account_handle in accounts.iter() {
+            aliases.push(account_handle.read().await.alias().to_lowercase());
+        }
+
+        let mut storage_manager = self.storage_manager.lock().await;
+        // The alias could have been used for a new account in the meantime
+        if let Some(archived_account) = storage_manager.get_archived_account(account_index).await? {
+            if aliases.contains(&archived_account.alias().to_lowercase()) {
+                return Err(crate::Error::AccountAliasAlreadyExists(
+                    archived_account.alias().to_string(),
+                ));
+            }
+        }
+        let account = storage_manager.restore_account(account_index).await?;
+        drop(storage_manager);
+
+        let (client, account_client_options) = self.stored_account_client(account_index).await?;
+        let account_handle = AccountHandle::new(
+            account,
+            client,
+            self.secret_manager.clone(),
+            self.signing_guard.clone(),
+            self.request_limiter.clone(),
+            #[cfg(feature = "ledger_nano")]
+            self.ledger_nano_queue.clone(),
+            #[cfg(feature = "events")]
+            self.event_emitter.clone(),
+            self.storage_manager.clone(),
+        )
+        .with_client_options(account_client_options);
+
+        let mut indexed_accounts = Vec::new();
+        for account_handle in accounts.drain(..).chain(std::iter::once(account_handle.clone())) {
+            indexed_accounts.push((*account_handle.read().await.index(), account_handle));
+        }
+        indexed_accounts.sort_by_key(|(index, _)| *index);
+        *accounts = indexed_accounts
+            .into_iter()
+            .map(|(_, account_handle)| account_handle)
+            .collect();
+
+        Ok(account_handle)
+    }
+
+    /// Returns the indexes of the archived accounts.
+    pub async fn archived_account_indexes(&self) -> crate::Result<Vec<u32>> {
+        self.storage_manager.lock().await.archived_account_indexes().await
+    }
+}
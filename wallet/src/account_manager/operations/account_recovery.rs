@@ -1,14 +1,91 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashMap;
+
 use instant::Instant;
+use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "events")]
+use crate::events::types::WalletEvent;
 use crate::{
-    account::handle::AccountHandle,
+    account::{handle::AccountHandle, CancellationToken},
     account_manager::{AccountManager, SyncOptions},
     task,
 };
 
+/// Options for [`AccountManager::recover_accounts_with_options()`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoverAccountsOptions {
+    /// The index of the first account to search for.
+    pub account_start_index: u32,
+    /// The number of accounts to search for, after the last account with unspent outputs.
+    pub account_gap_limit: u32,
+    /// The number of addresses to search for, after the last address with unspent outputs, in each account.
+    pub address_gap_limit: u32,
+    /// Larger address gap limits for single accounts by their index, for accounts with addresses far apart.
+    #[serde(default)]
+    pub account_address_gap_limits: HashMap<u32, u32>,
+    /// Optional parameter to specify the sync options. The `address_start_index` and `force_syncing` fields will be
+    /// overwritten to skip existing addresses.
+    pub sync_options: Option<SyncOptions>,
+}
+
+impl RecoverAccountsOptions {
+    // The address gap limit for an account, a configured limit for the account can only extend the default one
+    fn address_gap_limit_for(&self, account_index: u32) -> u32 {
+        self.account_address_gap_limits
+            .get(&account_index)
+            .map_or(self.address_gap_limit, |limit| (*limit).max(self.address_gap_limit))
+    }
+}
+
+/// Up to which indexes accounts and addresses were searched, to see which gaps ended the discovery.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveryReport {
+    /// The account gap limit that was used.
+    pub account_gap_limit: u32,
+    /// The highest account index that was searched, the discovery ended after it.
+    pub highest_searched_account_index: Option<u32>,
+    /// The highest account index with unspent outputs.
+    pub highest_account_index_with_outputs: Option<u32>,
+    /// The searched addresses of every account, also of the removed accounts without outputs.
+    pub address_gaps: Vec<AddressGapReport>,
+}
+
+/// Up to which indexes the addresses of an account were searched.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressGapReport {
+    /// The index of the account.
+    pub account_index: u32,
+    /// The address gap limit that was used for the account.
+    pub address_gap_limit: u32,
+    /// The highest public address index that was searched.
+    pub highest_searched_address_index: u32,
+    /// The highest internal address index that was searched.
+    pub highest_searched_address_index_internal: Option<u32>,
+    /// The highest public address index with unspent outputs.
+    pub highest_address_index_with_outputs: Option<u32>,
+    /// The highest internal address index with unspent outputs.
+    pub highest_address_index_with_outputs_internal: Option<u32>,
+    /// The amount of unspent outputs of the account.
+    pub outputs_count: usize,
+}
+
+// The progress of a recovery, to continue it after it was interrupted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct RecoveryCheckpoint {
+    // Only a recovery with the same options continues from the checkpoint
+    #[cfg_attr(not(feature = "storage"), allow(dead_code))]
+    options: RecoverAccountsOptions,
+    // Accounts that were created by the recovery are removed again if they have no outputs
+    existing_account_indexes: Vec<u32>,
+    report: RecoveryReport,
+}
+
 impl AccountManager {
     /// Find accounts with unspent outputs.
     ///
@@ -31,62 +108,148 @@ impl AccountManager {
         address_gap_limit: u32,
         sync_options: Option<SyncOptions>,
     ) -> crate::Result<Vec<AccountHandle>> {
+        let (accounts, _) = self
+            .recover_accounts_with_options(RecoverAccountsOptions {
+                account_start_index,
+                account_gap_limit,
+                address_gap_limit,
+                account_address_gap_limits: HashMap::new(),
+                sync_options,
+            })
+            .await?;
+        Ok(accounts)
+    }
+
+    /// Find accounts with unspent outputs like [`AccountManager::recover_accounts()`], with larger address gap limits
+    /// for single accounts. Returns the accounts and a report up to which indexes accounts and addresses were searched.
+    /// An [`AccountRecoveryProgress`](crate::events::types::WalletEvent::AccountRecoveryProgress) event is emitted
+    /// after the addresses of each account were searched. If the recovery fails, the empty accounts it created are
+    /// removed again and a checkpoint is kept, so calling it again with the same options doesn't search the kept
+    /// accounts again.
+    pub async fn recover_accounts_with_options(
+        &self,
+        options: RecoverAccountsOptions,
+    ) -> crate::Result<(Vec<AccountHandle>, RecoveryReport)> {
+        self.recover_accounts_with_cancellation(options, CancellationToken::new())
+            .await
+    }
+
+    /// Find accounts with unspent outputs like [`AccountManager::recover_accounts_with_options()`], but stop with
+    /// [`Error::Cancelled`](crate::Error::Cancelled) once the token is cancelled. A checkpoint is kept like for other
+    /// failures, so the recovery can be continued later.
+    pub async fn recover_accounts_with_cancellation(
+        &self,
+        options: RecoverAccountsOptions,
+        cancellation: CancellationToken,
+    ) -> crate::Result<(Vec<AccountHandle>, RecoveryReport)> {
         log::debug!("[recover_accounts]");
         let start_time = Instant::now();
         let mut max_account_index_to_keep = None;
 
+        let account_handles = self.accounts.read().await.clone();
+        let mut existing_account_indexes = Vec::new();
+        for account_handle in &account_handles {
+            existing_account_indexes.push(*account_handle.read().await.index());
+        }
+        let mut checkpoint = RecoveryCheckpoint {
+            options: options.clone(),
+            existing_account_indexes,
+            report: RecoveryReport {
+                account_gap_limit: options.account_gap_limit,
+                ..Default::default()
+            },
+        };
+        #[cfg(feature = "storage")]
+        {
+            let mut storage_manager = self.storage_manager.lock().await;
+            if let Some(stored_checkpoint) = storage_manager.get_recovery_checkpoint().await? {
+                if stored_checkpoint.options == options {
+                    log::debug!("[recover_accounts] continue from checkpoint");
+                    checkpoint = stored_checkpoint;
+                } else {
+                    // The progress of a recovery with other options can't be used
+                    storage_manager.remove_recovery_checkpoint().await?;
+                }
+            }
+        }
+
         // Search for addresses in current accounts
-        for account_handle in self.accounts.read().await.iter() {
-            // If the gap limit is 0, there is no need to search for funds
-            if address_gap_limit > 0 {
-                account_handle
-                    .search_addresses_with_outputs(address_gap_limit, sync_options.clone())
-                    .await?;
+        for account_handle in &account_handles {
+            let (account_index, watch_only) = {
+                let account = account_handle.read().await;
+                (*account.index(), *account.watch_only())
+            };
+            // Watch-only accounts have their own index range and are always kept
+            if watch_only {
+                continue;
             }
-            let account_index = *account_handle.read().await.index();
-            match max_account_index_to_keep {
-                Some(max_account_index) => {
-                    if account_index > max_account_index {
-                        max_account_index_to_keep = Some(account_index);
+            let searched_outputs_count = checkpoint
+                .report
+                .address_gaps
+                .iter()
+                .find(|address_gap_report| address_gap_report.account_index == account_index)
+                .map(|address_gap_report| address_gap_report.outputs_count);
+            let outputs_count = match searched_outputs_count {
+                // Already searched before the recovery was interrupted
+                Some(outputs_count) => outputs_count,
+                None => {
+                    let address_gap_limit = options.address_gap_limit_for(account_index);
+                    // If the gap limit is 0, there is no need to search for funds
+                    if address_gap_limit > 0 {
+                        let address_gap_report = account_handle
+                            .search_addresses_with_outputs(
+                                address_gap_limit,
+                                options.sync_options.clone(),
+                                &cancellation,
+                            )
+                            .await?;
+                        let outputs_count = address_gap_report.outputs_count;
+                        self.add_recovery_progress(&mut checkpoint, address_gap_report).await?;
+                        outputs_count
+                    } else {
+                        0
                     }
                 }
-                None => max_account_index_to_keep = Some(account_index),
+            };
+            // Accounts that existed before the recovery are always kept, accounts created by an interrupted recovery
+            // only if they have outputs
+            if checkpoint.existing_account_indexes.contains(&account_index) || outputs_count != 0 {
+                match max_account_index_to_keep {
+                    Some(max_account_index) => {
+                        if account_index > max_account_index {
+                            max_account_index_to_keep = Some(account_index);
+                        }
+                    }
+                    None => max_account_index_to_keep = Some(account_index),
+                }
             }
         }
 
         // Create accounts below account_start_index, because we don't want to have gaps in the accounts, but we also
         // don't want to sync them
-        for _ in max_account_index_to_keep.unwrap_or(0)..account_start_index {
+        for _ in max_account_index_to_keep.unwrap_or(0)..options.account_start_index {
             // Don't return possible errors here, because we could then still have empty accounts
             let _ = self.create_account().finish().await;
         }
 
         // Don't return possible errors here already, because we would then still have empty accounts
         let new_accounts_discovery_result = self
-            .search_new_accounts(
-                account_gap_limit,
-                address_gap_limit,
-                &mut max_account_index_to_keep,
-                sync_options.clone(),
-            )
+            .search_new_accounts(&options, &mut max_account_index_to_keep, &mut checkpoint, &cancellation)
             .await;
 
-        // remove accounts without outputs
+        // remove accounts without outputs, also if the discovery failed. The checkpoint has the accounts that existed
+        // before the first attempt of the recovery, so empty accounts of an interrupted attempt are removed too
         let mut new_accounts = Vec::new();
         let mut accounts = self.accounts.write().await;
 
         for account_handle in accounts.iter() {
             let account_index = *account_handle.read().await.index();
-            let mut keep_account = false;
-
-            if let Some(max_account_index_to_keep) = max_account_index_to_keep {
-                if account_index <= max_account_index_to_keep {
-                    new_accounts.push((account_index, account_handle.clone()));
-                    keep_account = true;
-                }
-            }
+            let keep_account = checkpoint.existing_account_indexes.contains(&account_index)
+                || max_account_index_to_keep.map_or(false, |max_account_index| account_index <= max_account_index);
 
-            if !keep_account {
+            if keep_account {
+                new_accounts.push((account_index, account_handle.clone()));
+            } else {
                 // accounts are stored during syncing, delete the empty accounts again
                 #[cfg(feature = "storage")]
                 {
@@ -99,49 +262,78 @@ impl AccountManager {
         *accounts = new_accounts.into_iter().map(|(_, acc)| acc).collect();
         drop(accounts);
 
-        // Handle result after cleaning up the empty accounts
+        // Handle result after cleaning up the empty accounts, the checkpoint is kept to continue the recovery
         new_accounts_discovery_result?;
 
+        #[cfg(feature = "storage")]
+        self.storage_manager.lock().await.remove_recovery_checkpoint().await?;
+
         log::debug!("[recover_accounts] finished in {:?}", start_time.elapsed());
-        Ok(self.accounts.read().await.clone())
+        Ok((self.accounts.read().await.clone(), checkpoint.report))
     }
 
     /// Generate new accounts and search for unspent outputs
     async fn search_new_accounts(
         &self,
-        account_gap_limit: u32,
-        address_gap_limit: u32,
+        options: &RecoverAccountsOptions,
         max_account_index_to_keep: &mut Option<u32>,
-        sync_options: Option<SyncOptions>,
+        checkpoint: &mut RecoveryCheckpoint,
+        cancellation: &CancellationToken,
     ) -> crate::Result<()> {
-        let mut updated_account_gap_limit = account_gap_limit;
+        let account_gap_limit = options.account_gap_limit;
+
+        // Empty accounts of an interrupted recovery count to the gap
+        let mut highest_account_index = None;
+        for account_handle in self.accounts.read().await.iter() {
+            let account = account_handle.read().await;
+            if !*account.watch_only() {
+                highest_account_index = highest_account_index.max(Some(*account.index()));
+            }
+        }
+        let empty_accounts_in_row = match (highest_account_index, *max_account_index_to_keep) {
+            (Some(highest_account_index), Some(max_account_index_to_keep)) => {
+                highest_account_index - max_account_index_to_keep
+            }
+            (Some(highest_account_index), None) => highest_account_index + 1,
+            (None, _) => 0,
+        };
+        let mut updated_account_gap_limit = account_gap_limit.saturating_sub(empty_accounts_in_row);
+
         loop {
+            cancellation.check()?;
             log::debug!("[recover_accounts] generating {updated_account_gap_limit} new accounts");
 
             // Generate account with addresses and get their outputs in parallel
             let mut tasks = Vec::new();
             for _ in 0..updated_account_gap_limit {
                 let mut new_account = self.create_account();
-                let sync_options_ = sync_options.clone();
+                let options_ = options.clone();
+                let cancellation_ = cancellation.clone();
                 tasks.push(async move {
                     task::spawn(async move {
                         let new_account = new_account.finish().await?;
-                        let account_outputs_count = new_account
-                            .search_addresses_with_outputs(address_gap_limit, sync_options_)
-                            .await?;
                         let account_index = *new_account.read().await.index();
-                        Ok((account_index, account_outputs_count))
+                        new_account
+                            .search_addresses_with_outputs(
+                                options_.address_gap_limit_for(account_index),
+                                options_.sync_options,
+                                &cancellation_,
+                            )
+                            .await
                     })
                     .await
                 });
             }
 
-            let results: Vec<crate::Result<(u32, usize)>> = futures::future::try_join_all(tasks).await?;
+            let results: Vec<crate::Result<AddressGapReport>> = futures::future::try_join_all(tasks).await?;
 
             let mut new_accounts_with_outputs = 0;
             let mut highest_account_index = 0;
             for res in results {
-                let (account_index, outputs_count): (u32, usize) = res?;
+                let address_gap_report = res?;
+                let account_index = address_gap_report.account_index;
+                let outputs_count = address_gap_report.outputs_count;
+                self.add_recovery_progress(checkpoint, address_gap_report).await?;
                 if outputs_count != 0 {
                     new_accounts_with_outputs += 1;
 
@@ -176,4 +368,39 @@ impl AccountManager {
 
         Ok(())
     }
+
+    // Adds the searched addresses of an account to the report, emits a progress event and stores the checkpoint
+    async fn add_recovery_progress(
+        &self,
+        checkpoint: &mut RecoveryCheckpoint,
+        address_gap_report: AddressGapReport,
+    ) -> crate::Result<()> {
+        #[cfg(feature = "events")]
+        self.event_emitter.lock().await.emit(
+            address_gap_report.account_index,
+            WalletEvent::AccountRecoveryProgress(address_gap_report.clone()),
+        );
+        checkpoint.report.add_address_gap_report(address_gap_report);
+        #[cfg(feature = "storage")]
+        self.storage_manager
+            .lock()
+            .await
+            .save_recovery_checkpoint(checkpoint)
+            .await?;
+        Ok(())
+    }
+}
+
+impl RecoveryReport {
+    fn add_address_gap_report(&mut self, address_gap_report: AddressGapReport) {
+        let account_index = Some(address_gap_report.account_index);
+        self.highest_searched_account_index = self.highest_searched_account_index.max(account_index);
+        if address_gap_report.outputs_count != 0 {
+            self.highest_account_index_with_outputs = self.highest_account_index_with_outputs.max(account_index);
+        }
+        // Empty accounts of a failed attempt are removed and searched again when the recovery is continued
+        self.address_gaps
+            .retain(|report| report.account_index != address_gap_report.account_index);
+        self.address_gaps.push(address_gap_report);
+    }
 }
@@ -9,8 +9,12 @@ use iota_client::{
 };
 
 use crate::account_manager::AccountManager;
+#[cfg(feature = "ledger_nano")]
+use crate::account_manager::LedgerNanoOperation;
 #[cfg(all(feature = "events", feature = "ledger_nano"))]
 use crate::events::types::{AddressData, WalletEvent};
+#[cfg(feature = "storage")]
+use crate::storage::derivation_cache::get_verified_cached_addresses;
 
 impl AccountManager {
     /// Generate an address without storing it
@@ -31,68 +35,113 @@ impl AccountManager {
         address_index: u32,
         options: Option<GenerateAddressOptions>,
     ) -> crate::Result<Address> {
+        // The coin type of the account, accounts created before the coin type of the manager was changed keep theirs
+        let mut coin_type = self.coin_type.load(Ordering::Relaxed);
+        for account in self.accounts.read().await.iter() {
+            let account = account.read().await;
+            if *account.index() == account_index {
+                coin_type = *account.coin_type();
+                break;
+            }
+        }
+
+        // Addresses that were derived before don't need to be derived again, unless they should be shown on a device
+        #[cfg(feature = "storage")]
+        if !options.as_ref().map_or(false, |o| o.ledger_nano_prompt) {
+            if let Some(addresses) = get_verified_cached_addresses(
+                &self.storage_manager,
+                &self.secret_manager,
+                #[cfg(feature = "ledger_nano")]
+                &self.ledger_nano_queue,
+                account_index,
+                coin_type,
+                internal,
+                address_index..address_index + 1,
+            )
+            .await?
+            {
+                return Ok(addresses[0]);
+            }
+        }
+
         let address = match &*self.secret_manager.read().await {
             #[cfg(feature = "ledger_nano")]
             SecretManager::LedgerNano(ledger_nano) => {
-                // If we don't sync, then we want to display the prompt on the ledger with the address. But the user
-                // needs to have it visible on the computer first, so we need to generate it without the
-                // prompt first
-                if options.as_ref().map_or(false, |o| o.ledger_nano_prompt) {
-                    #[cfg(feature = "events")]
-                    {
-                        let changed_options = options.clone().map(|mut options| {
-                            // Change options so ledger will not show the prompt the first time
-                            options.ledger_nano_prompt = false;
-                            options
-                        });
-                        // Generate without prompt to be able to display it
-                        let address = ledger_nano
-                            .generate_addresses(
-                                self.coin_type.load(Ordering::Relaxed),
-                                account_index,
-                                address_index..address_index + 1,
-                                internal,
-                                changed_options,
-                            )
-                            .await?;
+                let operation = if options.as_ref().map_or(false, |o| o.ledger_nano_prompt) {
+                    LedgerNanoOperation::ShowAddresses
+                } else {
+                    LedgerNanoOperation::GenerateAddresses
+                };
+                self.ledger_nano_queue
+                    .run(
+                        operation,
+                        #[cfg(feature = "events")]
+                        Some((&*self.event_emitter, account_index)),
+                        async {
+                            // If we don't sync, then we want to display the prompt on the ledger with the address.
+                            // But the user needs to have it visible on the computer first, so we need to generate it
+                            // without the prompt first
+                            if options.as_ref().map_or(false, |o| o.ledger_nano_prompt) {
+                                #[cfg(feature = "events")]
+                                {
+                                    let changed_options = options.clone().map(|mut options| {
+                                        // Change options so ledger will not show the prompt the first time
+                                        options.ledger_nano_prompt = false;
+                                        options
+                                    });
+                                    // Generate without prompt to be able to display it
+                                    let address = ledger_nano
+                                        .generate_addresses(
+                                            coin_type,
+                                            account_index,
+                                            address_index..address_index + 1,
+                                            internal,
+                                            changed_options,
+                                        )
+                                        .await?;
 
-                        let bech32_hrp = self.get_bech32_hrp().await?;
+                                    let bech32_hrp = self.get_bech32_hrp().await?;
 
-                        self.event_emitter.lock().await.emit(
-                            account_index,
-                            WalletEvent::LedgerAddressGeneration(AddressData {
-                                address: address[0].to_bech32(bech32_hrp),
-                            }),
-                        );
-                    }
+                                    self.event_emitter.lock().await.emit(
+                                        account_index,
+                                        WalletEvent::LedgerAddressGeneration(AddressData {
+                                            address: address[0].to_bech32(bech32_hrp),
+                                        }),
+                                    );
+                                }
 
-                    // Generate with prompt so the user can verify
-                    ledger_nano
-                        .generate_addresses(
-                            self.coin_type.load(Ordering::Relaxed),
-                            account_index,
-                            address_index..address_index + 1,
-                            internal,
-                            options,
-                        )
-                        .await?
-                } else {
-                    ledger_nano
-                        .generate_addresses(
-                            self.coin_type.load(Ordering::Relaxed),
-                            account_index,
-                            address_index..address_index + 1,
-                            internal,
-                            options,
-                        )
-                        .await?
-                }
+                                // Generate with prompt so the user can verify
+                                ledger_nano
+                                    .generate_addresses(
+                                        coin_type,
+                                        account_index,
+                                        address_index..address_index + 1,
+                                        internal,
+                                        options,
+                                    )
+                                    .await
+                                    .map_err(Into::into)
+                            } else {
+                                ledger_nano
+                                    .generate_addresses(
+                                        coin_type,
+                                        account_index,
+                                        address_index..address_index + 1,
+                                        internal,
+                                        options,
+                                    )
+                                    .await
+                                    .map_err(Into::into)
+                            }
+                        },
+                    )
+                    .await?
             }
             #[cfg(feature = "stronghold")]
             SecretManager::Stronghold(stronghold) => {
                 stronghold
                     .generate_addresses(
-                        self.coin_type.load(Ordering::Relaxed),
+                        coin_type,
                         account_index,
                         address_index..address_index + 1,
                         internal,
@@ -103,7 +152,7 @@ impl AccountManager {
             SecretManager::Mnemonic(mnemonic) => {
                 mnemonic
                     .generate_addresses(
-                        self.coin_type.load(Ordering::Relaxed),
+                        coin_type,
                         account_index,
                         address_index..address_index + 1,
                         internal,
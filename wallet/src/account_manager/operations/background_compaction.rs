@@ -0,0 +1,94 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{sync::atomic::Ordering, time::Duration};
+
+use tokio::time::sleep;
+
+use crate::account_manager::AccountManager;
+
+/// The default interval for checking if the storage should be compacted
+pub(crate) const DEFAULT_BACKGROUND_COMPACTION_INTERVAL: Duration = Duration::from_secs(60 * 10);
+/// The default amount of removed records after which the storage gets compacted
+pub(crate) const DEFAULT_COMPACTION_REMOVAL_THRESHOLD: usize = 100;
+
+impl AccountManager {
+    /// Start a background process that compacts the storage once more than `removal_threshold` records got removed
+    /// since the last compaction. Checks every `interval`, default interval is 10 minutes and default threshold is 100
+    /// removals.
+    pub async fn start_background_compaction(
+        &self,
+        interval: Option<Duration>,
+        removal_threshold: Option<usize>,
+    ) -> crate::Result<()> {
+        log::debug!("[start_background_compaction]");
+        let background_compaction_status = self.background_compaction_status.clone();
+        // stop existing process if running
+        if background_compaction_status.load(Ordering::Relaxed) == 1 {
+            background_compaction_status.store(2, Ordering::Relaxed);
+        };
+        while background_compaction_status.load(Ordering::Relaxed) == 2 {
+            log::debug!("[background_compaction]: waiting for the old process to stop");
+            sleep(Duration::from_secs(1)).await;
+        }
+
+        background_compaction_status.store(1, Ordering::Relaxed);
+        let storage_manager = self.storage_manager.clone();
+        let removal_threshold = removal_threshold.unwrap_or(DEFAULT_COMPACTION_REMOVAL_THRESHOLD);
+        let _background_compaction = std::thread::spawn(move || {
+            #[cfg(not(target_family = "wasm"))]
+            let runtime = tokio::runtime::Builder::new_multi_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            #[cfg(target_family = "wasm")]
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            runtime.block_on(async {
+                'outer: loop {
+                    let mut storage_manager = storage_manager.lock().await;
+                    if storage_manager.removals_since_compaction() >= removal_threshold {
+                        log::debug!("[background_compaction]: compacting storage");
+                        if let Err(err) = storage_manager.compact().await {
+                            log::debug!("[background_compaction] error: {}", err);
+                        }
+                    }
+                    drop(storage_manager);
+                    // split interval to seconds so stopping the process doesn't have to wait long
+                    let seconds = interval.unwrap_or(DEFAULT_BACKGROUND_COMPACTION_INTERVAL).as_secs();
+                    for _ in 0..seconds {
+                        if background_compaction_status.load(Ordering::Relaxed) == 2 {
+                            log::debug!("[background_compaction]: stopping");
+                            break 'outer;
+                        }
+                        sleep(Duration::from_secs(1)).await;
+                    }
+                }
+                background_compaction_status.store(0, Ordering::Relaxed);
+                log::debug!("[background_compaction]: stopped");
+            });
+        });
+        Ok(())
+    }
+
+    /// Stop the background compaction of the storage
+    pub async fn stop_background_compaction(&self) -> crate::Result<()> {
+        log::debug!("[stop_background_compaction]");
+        // immediately return if not running
+        if self.background_compaction_status.load(Ordering::Relaxed) == 0 {
+            return Ok(());
+        }
+        // send stop request
+        self.background_compaction_status.store(2, Ordering::Relaxed);
+        // wait until it stopped
+        while self.background_compaction_status.load(Ordering::Relaxed) != 0 {
+            #[cfg(target_family = "wasm")]
+            gloo_timers::future::TimeoutFuture::new(10).await;
+            #[cfg(not(target_family = "wasm"))]
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        Ok(())
+    }
+}
@@ -1,17 +1,71 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{sync::atomic::Ordering, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::atomic::Ordering,
+    time::Duration,
+};
 
+use crypto::utils::rand;
+use instant::Instant;
+#[cfg(feature = "events")]
+use iota_client::block::output::OutputId;
 use tokio::time::sleep;
 
-use crate::{account::operations::syncing::SyncOptions, account_manager::AccountManager};
+#[cfg(feature = "storage")]
+use crate::account_manager::operations::scheduled_payments::execute_scheduled_payments;
+use crate::{
+    account::{handle::AccountHandle, operations::syncing::SyncOptions, ConsolidationPolicy, OutputsToClaim},
+    account_manager::AccountManager,
+};
+#[cfg(feature = "events")]
+use crate::{
+    account::{types::AccountBalanceDto, ExpiringOutput},
+    events::types::{BalanceChangeEvent, ExpiringOutputsEvent, WalletEvent},
+};
 
 /// The default interval for background syncing
 pub(crate) const DEFAULT_BACKGROUNDSYNCING_INTERVAL: Duration = Duration::from_secs(7);
+/// The maximum delay before syncing an account again after the node returned errors
+pub(crate) const MAX_BACKGROUNDSYNCING_BACKOFF: Duration = Duration::from_secs(60 * 5);
+/// The maximum deviation of the time between background syncs from the interval, in percent
+pub(crate) const BACKGROUNDSYNCING_JITTER_PERCENT: u64 = 10;
+/// The default period before the expiration of an output in which an event is emitted for it
+#[cfg(feature = "events")]
+pub(crate) const DEFAULT_EXPIRATION_WARNING_PERIOD: Duration = Duration::from_secs(60 * 60 * 24);
+
+/// Settings of the background syncing that can be changed while it's running.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct BackgroundSyncSchedule {
+    // Intervals of the accounts that don't use the interval the background syncing was started with
+    pub(crate) account_intervals: HashMap<u32, Duration>,
+    // Accounts that are skipped
+    pub(crate) paused_accounts: HashSet<u32>,
+    // If all accounts are skipped
+    pub(crate) paused: bool,
+    // Accounts whose outputs are consolidated after they were synced
+    pub(crate) consolidation_policies: HashMap<u32, ConsolidationPolicy>,
+    // Accounts whose unlockable outputs with additional unlock conditions are claimed after they were synced
+    pub(crate) auto_claims: HashMap<u32, OutputsToClaim>,
+    // Accounts that don't use the default period before the expiration of an output in which it's reported
+    #[cfg(feature = "events")]
+    pub(crate) expiration_warning_periods: HashMap<u32, Duration>,
+}
 
 impl AccountManager {
-    /// Start the background syncing process for all accounts, default interval is 7 seconds
+    /// Start the background syncing process for all accounts, default interval is 7 seconds. The interval can be set
+    /// for each account with [`AccountManager::set_background_syncing_interval()`] and deviates randomly by up to 10%,
+    /// so accounts don't get synced at the same time. If the node returns errors, the delay for the account doubles
+    /// with every failed sync, up to 5 minutes. With the `events` feature a
+    /// [`BalanceChange`](crate::events::types::WalletEvent::BalanceChange) event is emitted when the balance of an
+    /// account changed between two background syncs. While the offline mode is enabled, all accounts are skipped,
+    /// otherwise the queued transactions of an account are submitted after it was synced. Accounts with a
+    /// [`ConsolidationPolicy`] get their outputs consolidated after they were synced, accounts with an automatic claim
+    /// claim their outputs as soon as they can be unlocked. With the `events` feature an
+    /// [`ExpiringOutputs`](crate::events::types::WalletEvent::ExpiringOutputs) event is emitted for outputs that will
+    /// expire back to their sender within a day, or the period set with
+    /// [`AccountManager::set_expiration_warning_period()`].
     pub async fn start_background_syncing(
         &self,
         options: Option<SyncOptions>,
@@ -30,6 +84,10 @@ impl AccountManager {
 
         background_syncing_status.store(1, Ordering::Relaxed);
         let accounts = self.accounts.clone();
+        let background_syncing_schedule = self.background_syncing_schedule.clone();
+        #[cfg(feature = "storage")]
+        let offline_mode = self.offline_mode.clone();
+        let interval = interval.unwrap_or(DEFAULT_BACKGROUNDSYNCING_INTERVAL);
         let _background_syncing = std::thread::spawn(move || {
             #[cfg(not(target_family = "wasm"))]
             let runtime = tokio::runtime::Builder::new_multi_thread()
@@ -42,28 +100,138 @@ impl AccountManager {
                 .build()
                 .unwrap();
             runtime.block_on(async {
+                // When each account should be synced next and how many syncs failed in a row because of node errors
+                let mut next_syncs: HashMap<u32, Instant> = HashMap::new();
+                let mut failed_syncs: HashMap<u32, u32> = HashMap::new();
+                let mut last_consolidations: HashMap<u32, Instant> = HashMap::new();
+                #[cfg(feature = "events")]
+                let mut balances = HashMap::new();
+                // Expiring outputs that were already reported
+                #[cfg(feature = "events")]
+                let mut reported_expiring_outputs: HashMap<u32, HashSet<OutputId>> = HashMap::new();
+
                 'outer: loop {
-                    log::debug!("[background_syncing]: syncing accounts");
-                    for account in accounts.read().await.iter() {
-                        // Check if the process should stop before syncing each account so it stops faster
-                        if background_syncing_status.load(Ordering::Relaxed) == 2 {
-                            log::debug!("[background_syncing]: stopping");
-                            break 'outer;
+                    // Clone, so the schedule can be changed while accounts are syncing
+                    let schedule = background_syncing_schedule.read().await.clone();
+                    #[cfg(feature = "storage")]
+                    let paused = schedule.paused || offline_mode.load(Ordering::Relaxed);
+                    #[cfg(not(feature = "storage"))]
+                    let paused = schedule.paused;
+                    if !paused {
+                        for account in accounts.read().await.iter() {
+                            // Check if the process should stop before syncing each account so it stops faster
+                            if background_syncing_status.load(Ordering::Relaxed) == 2 {
+                                log::debug!("[background_syncing]: stopping");
+                                break 'outer;
+                            }
+                            let account_index = *account.read().await.index();
+                            let now = Instant::now();
+                            if schedule.paused_accounts.contains(&account_index)
+                                || next_syncs
+                                    .get(&account_index)
+                                    .map_or(false, |next_sync| *next_sync > now)
+                            {
+                                continue;
+                            }
+                            let account_interval = schedule
+                                .account_intervals
+                                .get(&account_index)
+                                .copied()
+                                .unwrap_or(interval);
+
+                            log::debug!("[background_syncing]: syncing account {account_index}");
+                            match account.sync(options.clone()).await {
+                                Ok(_balance) => {
+                                    failed_syncs.remove(&account_index);
+                                    next_syncs.insert(account_index, now + with_jitter(account_interval));
+                                    // The node is reachable again, so transactions from the offline mode can be sent
+                                    #[cfg(feature = "storage")]
+                                    if let Err(err) = account.submit_queued_transactions().await {
+                                        log::debug!(
+                                            "[background_syncing] submitting queued transactions failed: {err}"
+                                        );
+                                    }
+                                    #[cfg(feature = "storage")]
+                                    if let Err(err) = execute_scheduled_payments(&account).await {
+                                        log::debug!("[background_syncing] scheduled payments failed: {err}");
+                                    }
+                                    if let Some(policy) = schedule.consolidation_policies.get(&account_index) {
+                                        let consolidate = last_consolidations.get(&account_index).map_or(
+                                            true,
+                                            |last_consolidation| {
+                                                last_consolidation.elapsed()
+                                                    >= Duration::from_millis(policy.interval_in_milliseconds)
+                                            },
+                                        );
+                                        if consolidate {
+                                            match account.consolidate_outputs_with_policy(policy).await {
+                                                Ok(_) => {
+                                                    last_consolidations.insert(account_index, Instant::now());
+                                                }
+                                                Err(crate::Error::NoOutputsToConsolidate { .. }) => {}
+                                                Err(err) => {
+                                                    log::debug!("[background_syncing] consolidation failed: {err}")
+                                                }
+                                            }
+                                        }
+                                    }
+                                    if let Some(outputs_to_claim) = schedule.auto_claims.get(&account_index) {
+                                        if let Err(err) = auto_claim(account, *outputs_to_claim).await {
+                                            log::debug!("[background_syncing] claiming outputs failed: {err}");
+                                        }
+                                    }
+                                    #[cfg(feature = "events")]
+                                    if let Some(mut previous_balance) = balances.insert(account_index, _balance.clone())
+                                    {
+                                        // Only the amounts are compared, the freshness changes with every sync
+                                        let freshness = previous_balance.freshness;
+                                        previous_balance.freshness = _balance.freshness.clone();
+                                        if previous_balance != _balance {
+                                            previous_balance.freshness = freshness;
+                                            account.event_emitter.lock().await.emit(
+                                                account_index,
+                                                WalletEvent::BalanceChange(Box::new(BalanceChangeEvent {
+                                                    previous_balance: AccountBalanceDto::from(&previous_balance),
+                                                    balance: AccountBalanceDto::from(&_balance),
+                                                })),
+                                            );
+                                        }
+                                    }
+                                    #[cfg(feature = "events")]
+                                    {
+                                        let warning_period = schedule
+                                            .expiration_warning_periods
+                                            .get(&account_index)
+                                            .copied()
+                                            .unwrap_or(DEFAULT_EXPIRATION_WARNING_PERIOD);
+                                        let reported = reported_expiring_outputs.entry(account_index).or_default();
+                                        if let Err(err) = warn_expiring_outputs(account, warning_period, reported).await
+                                        {
+                                            log::debug!("[background_syncing] checking expiring outputs failed: {err}");
+                                        }
+                                    }
+                                }
+                                Err(err) => {
+                                    let delay = if matches!(err, crate::Error::Client(_)) {
+                                        let failed = failed_syncs.entry(account_index).or_default();
+                                        *failed += 1;
+                                        backoff(account_interval, *failed)
+                                    } else {
+                                        account_interval
+                                    };
+                                    log::debug!("[background_syncing] error: {}, syncing again in {:?}", err, delay);
+                                    next_syncs.insert(account_index, now + delay);
+                                }
+                            }
                         }
-                        match account.sync(options.clone()).await {
-                            Ok(_) => {}
-                            Err(err) => log::debug!("[background_syncing] error: {}", err),
-                        };
                     }
-                    // split interval syncing to seconds so stopping the process doesn't have to wait long
-                    let seconds = interval.unwrap_or(DEFAULT_BACKGROUNDSYNCING_INTERVAL).as_secs();
-                    for _ in 0..seconds {
-                        if background_syncing_status.load(Ordering::Relaxed) == 2 {
-                            log::debug!("[background_syncing]: stopping");
-                            break 'outer;
-                        }
-                        sleep(Duration::from_secs(1)).await;
+                    // check every second which accounts need to be synced, so stopping the process doesn't have to
+                    // wait long
+                    if background_syncing_status.load(Ordering::Relaxed) == 2 {
+                        log::debug!("[background_syncing]: stopping");
+                        break 'outer;
                     }
+                    sleep(Duration::from_secs(1)).await;
                 }
                 background_syncing_status.store(0, Ordering::Relaxed);
                 log::debug!("[background_syncing]: stopped");
@@ -90,4 +258,190 @@ impl AccountManager {
         }
         Ok(())
     }
+    /// Pause the background syncing of an account, or of all accounts if no account index is provided. The background
+    /// syncing process keeps running and continues with the account once it's resumed.
+    pub async fn pause_background_syncing(&self, account_index: Option<u32>) -> crate::Result<()> {
+        log::debug!("[pause_background_syncing] {account_index:?}");
+        let mut background_syncing_schedule = self.background_syncing_schedule.write().await;
+        match account_index {
+            Some(account_index) => {
+                self.get_account(account_index).await?;
+                background_syncing_schedule.paused_accounts.insert(account_index);
+            }
+            None => background_syncing_schedule.paused = true,
+        }
+        Ok(())
+    }
+
+    /// Resume the background syncing of an account, or of all accounts if no account index is provided.
+    pub async fn resume_background_syncing(&self, account_index: Option<u32>) -> crate::Result<()> {
+        log::debug!("[resume_background_syncing] {account_index:?}");
+        let mut background_syncing_schedule = self.background_syncing_schedule.write().await;
+        match account_index {
+            Some(account_index) => {
+                background_syncing_schedule.paused_accounts.remove(&account_index);
+            }
+            None => {
+                background_syncing_schedule.paused = false;
+                background_syncing_schedule.paused_accounts.clear();
+            }
+        }
+        Ok(())
+    }
+
+    /// Set the policy for the automatic consolidation of the outputs of an account by the background syncing, `None`
+    /// disables it.
+    pub async fn set_consolidation_policy(
+        &self,
+        account_index: u32,
+        policy: Option<ConsolidationPolicy>,
+    ) -> crate::Result<()> {
+        log::debug!("[set_consolidation_policy] {account_index} {policy:?}");
+        self.get_account(account_index).await?;
+        let mut background_syncing_schedule = self.background_syncing_schedule.write().await;
+        match policy {
+            Some(policy) => background_syncing_schedule
+                .consolidation_policies
+                .insert(account_index, policy),
+            None => background_syncing_schedule
+                .consolidation_policies
+                .remove(&account_index),
+        };
+        Ok(())
+    }
+
+    /// Returns the consolidation policy of an account.
+    pub async fn get_consolidation_policy(&self, account_index: u32) -> Option<ConsolidationPolicy> {
+        self.background_syncing_schedule
+            .read()
+            .await
+            .consolidation_policies
+            .get(&account_index)
+            .copied()
+    }
+
+    /// Claim the outputs of an account that match `outputs_to_claim` automatically in the background syncing, as soon
+    /// as their timelock expired and they can be unlocked. `None` disables it.
+    pub async fn set_auto_claim(
+        &self,
+        account_index: u32,
+        outputs_to_claim: Option<OutputsToClaim>,
+    ) -> crate::Result<()> {
+        log::debug!("[set_auto_claim] {account_index} {outputs_to_claim:?}");
+        self.get_account(account_index).await?;
+        let mut background_syncing_schedule = self.background_syncing_schedule.write().await;
+        match outputs_to_claim {
+            Some(outputs_to_claim) => background_syncing_schedule
+                .auto_claims
+                .insert(account_index, outputs_to_claim),
+            None => background_syncing_schedule.auto_claims.remove(&account_index),
+        };
+        Ok(())
+    }
+
+    /// Set the period before the expiration of an output of an account in which the background syncing emits an
+    /// [`ExpiringOutputs`](crate::events::types::WalletEvent::ExpiringOutputs) event for it, `None` resets it to one
+    /// day and a zero duration disables the events.
+    #[cfg(feature = "events")]
+    pub async fn set_expiration_warning_period(
+        &self,
+        account_index: u32,
+        period: Option<Duration>,
+    ) -> crate::Result<()> {
+        log::debug!("[set_expiration_warning_period] {account_index} {period:?}");
+        self.get_account(account_index).await?;
+        let mut background_syncing_schedule = self.background_syncing_schedule.write().await;
+        match period {
+            Some(period) => background_syncing_schedule
+                .expiration_warning_periods
+                .insert(account_index, period),
+            None => background_syncing_schedule
+                .expiration_warning_periods
+                .remove(&account_index),
+        };
+        Ok(())
+    }
+
+    /// Set the background syncing interval of an account, `None` resets it to the interval the background syncing was
+    /// started with.
+    pub async fn set_background_syncing_interval(
+        &self,
+        account_index: u32,
+        interval: Option<Duration>,
+    ) -> crate::Result<()> {
+        log::debug!("[set_background_syncing_interval] {account_index} {interval:?}");
+        self.get_account(account_index).await?;
+        let mut background_syncing_schedule = self.background_syncing_schedule.write().await;
+        match interval {
+            Some(interval) => background_syncing_schedule
+                .account_intervals
+                .insert(account_index, interval),
+            None => background_syncing_schedule.account_intervals.remove(&account_index),
+        };
+        Ok(())
+    }
+}
+
+// Claim the outputs that can be unlocked now
+async fn auto_claim(account: &AccountHandle, outputs_to_claim: OutputsToClaim) -> crate::Result<()> {
+    let output_ids = account
+        .get_unlockable_outputs_with_additional_unlock_conditions(outputs_to_claim)
+        .await?;
+    if !output_ids.is_empty() {
+        let claim_batch = account.claim_outputs_in_batches(output_ids).await?;
+        if let Some(failed_claim) = claim_batch.failed.first() {
+            log::debug!("[background_syncing] claiming outputs failed: {}", failed_claim.error);
+        }
+    }
+    Ok(())
+}
+
+// Emit an event for the outputs that expire within the warning period and weren't reported yet
+#[cfg(feature = "events")]
+async fn warn_expiring_outputs(
+    account: &AccountHandle,
+    warning_period: Duration,
+    reported: &mut HashSet<OutputId>,
+) -> crate::Result<()> {
+    if warning_period.is_zero() {
+        return Ok(());
+    }
+    let expiring_outputs = account.expiring_outputs(Some(warning_period)).await?;
+    // Forget outputs that were claimed or expired, the set only contains outputs that are still expiring
+    reported.retain(|output_id| {
+        expiring_outputs
+            .iter()
+            .any(|expiring_output| expiring_output.output_id == *output_id)
+    });
+    let outputs: Vec<ExpiringOutput> = expiring_outputs
+        .into_iter()
+        .filter(|expiring_output| reported.insert(expiring_output.output_id))
+        .collect();
+    if !outputs.is_empty() {
+        let account_index = *account.read().await.index();
+        account.event_emitter.lock().await.emit(
+            account_index,
+            WalletEvent::ExpiringOutputs(ExpiringOutputsEvent { outputs }),
+        );
+    }
+    Ok(())
+}
+
+// Shift the interval randomly by up to BACKGROUNDSYNCING_JITTER_PERCENT
+fn with_jitter(interval: Duration) -> Duration {
+    let interval_millis = interval.as_millis() as u64;
+    let max_jitter = interval_millis * BACKGROUNDSYNCING_JITTER_PERCENT / 100;
+    let mut random_bytes = [0u8; 8];
+    if max_jitter == 0 || rand::fill(&mut random_bytes).is_err() {
+        return interval;
+    }
+    let jitter = u64::from_le_bytes(random_bytes) % (2 * max_jitter + 1);
+    Duration::from_millis(interval_millis + jitter - max_jitter)
+}
+
+// Double the delay for every failed sync, up to MAX_BACKGROUNDSYNCING_BACKOFF
+fn backoff(interval: Duration, failed_syncs: u32) -> Duration {
+    interval
+        .saturating_mul(2u32.saturating_pow(failed_syncs))
+        .min(MAX_BACKGROUNDSYNCING_BACKOFF)
 }
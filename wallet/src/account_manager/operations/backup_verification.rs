@@ -0,0 +1,138 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::HashSet, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    account_manager::{AccountManager, PortableBackup},
+    storage::{constants::*, manager::account_keys, seed_fingerprint::SeedFingerprint},
+};
+
+/// The result of opening a portable backup without restoring it, returned from
+/// [`AccountManager::verify_backup()`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupVerificationReport {
+    /// The version of the backup format.
+    pub version: u8,
+    /// Unix timestamp in milliseconds when the backup was created.
+    pub created_at: u64,
+    /// The database schema version the backup was created with, records of older versions are migrated on restore.
+    pub database_schema_version: u8,
+    /// The accounts that can be restored.
+    pub accounts: Vec<BackupAccountSummary>,
+    /// If the seed fingerprint of the backup matches the secret manager, `None` if the backup has no fingerprint.
+    pub seed_fingerprint_matches: Option<bool>,
+    /// Keys of records that can't be read or don't belong to an account of the backup.
+    pub invalid_records: Vec<String>,
+}
+
+impl BackupVerificationReport {
+    /// Returns true if all records are valid and the backup can be restored with the current secret manager.
+    pub fn is_valid(&self) -> bool {
+        self.invalid_records.is_empty() && self.seed_fingerprint_matches != Some(false)
+    }
+}
+
+/// An account in a portable backup.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupAccountSummary {
+    /// The account index.
+    pub account_index: u32,
+    /// The account alias.
+    pub alias: String,
+    /// The coin type of the account.
+    pub coin_type: u32,
+    /// Amount of public and internal addresses.
+    pub addresses: usize,
+    /// Amount of outputs.
+    pub outputs: usize,
+    /// Amount of transactions.
+    pub transactions: usize,
+}
+
+impl AccountManager {
+    /// Opens a backup created with [`AccountManager::backup_portable()`] without restoring it and checks that it can
+    /// be restored: the records of all accounts are read and the seed fingerprint is compared with the current secret
+    /// manager. Returns an error if the backup can't be decrypted, its version isn't supported or its records can't be
+    /// migrated to the current database schema.
+    pub async fn verify_backup(
+        &self,
+        path: impl AsRef<Path>,
+        password: &str,
+    ) -> crate::Result<BackupVerificationReport> {
+        self.verify_backup_bytes(&std::fs::read(path)?, password).await
+    }
+
+    /// Verifies a backup returned by [`AccountManager::backup_portable_bytes()`], like
+    /// [`AccountManager::verify_backup()`].
+    pub async fn verify_backup_bytes(&self, bytes: &[u8], password: &str) -> crate::Result<BackupVerificationReport> {
+        log::debug!("[verify_backup]");
+        let backup = PortableBackup::decrypt(bytes, password)?;
+        let bundle = &backup.bundle;
+        let mut report = BackupVerificationReport {
+            version: backup.version,
+            created_at: backup.created_at,
+            database_schema_version: PortableBackup::database_schema_version(bytes)?,
+            ..Default::default()
+        };
+
+        let account_indexes = bundle.account_indexes().unwrap_or_else(|_| {
+            report.invalid_records.push(ACCOUNTS_INDEXATION_KEY.to_string());
+            Vec::new()
+        });
+        let mut known_keys: HashSet<String> = [
+            ACCOUNTS_INDEXATION_KEY,
+            ACCOUNT_MANAGER_INDEXATION_KEY,
+            SEED_FINGERPRINT_KEY,
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect();
+        for account_index in account_indexes {
+            known_keys.extend(account_keys(account_index));
+            match bundle.account_package(account_index).account() {
+                Ok(account) if *account.index() == account_index => report.accounts.push(BackupAccountSummary {
+                    account_index,
+                    alias: account.alias().clone(),
+                    coin_type: *account.coin_type(),
+                    addresses: account.public_addresses().len() + account.internal_addresses().len(),
+                    outputs: account.outputs().len(),
+                    transactions: account.transactions().len(),
+                }),
+                _ => report
+                    .invalid_records
+                    .push(format!("{ACCOUNT_INDEXATION_KEY}{account_index}")),
+            }
+        }
+        // Records of accounts that aren't listed in the backup
+        report
+            .invalid_records
+            .extend(bundle.records.keys().filter(|key| !known_keys.contains(*key)).cloned());
+
+        if let Some(seed_fingerprint) = bundle.records.get(SEED_FINGERPRINT_KEY) {
+            match serde_json::from_value::<SeedFingerprint>(seed_fingerprint.clone()) {
+                Ok(seed_fingerprint) => {
+                    report.seed_fingerprint_matches = match seed_fingerprint
+                        .verify(
+                            &self.secret_manager,
+                            #[cfg(feature = "ledger_nano")]
+                            &self.ledger_nano_queue,
+                        )
+                        .await
+                    {
+                        Ok(()) => Some(true),
+                        Err(crate::Error::SeedFingerprintMismatch) => Some(false),
+                        Err(err) => return Err(err),
+                    }
+                }
+                Err(_) => report.invalid_records.push(SEED_FINGERPRINT_KEY.to_string()),
+            }
+        }
+
+        Ok(report)
+    }
+}
@@ -0,0 +1,45 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use crate::{account_manager::AccountManager, storage::balance_summary::BalanceSummary};
+
+impl AccountManager {
+    /// Returns the summed balance of all accounts, as recorded after their last sync. Accounts that were never synced
+    /// are not included. Use [`AccountManager::balance()`] to calculate the current balance instead.
+    pub async fn total_balance(&self) -> crate::Result<BalanceSummary> {
+        let mut total_balance = BalanceSummary::default();
+        for balance_summary in self.storage_manager.lock().await.get_balance_summaries().await? {
+            total_balance.add(&balance_summary.balance);
+        }
+        Ok(total_balance)
+    }
+
+    /// Returns the summed balances of the accounts grouped by their coin type, as recorded after their last sync.
+    pub async fn total_balance_by_coin_type(&self) -> crate::Result<HashMap<u32, BalanceSummary>> {
+        let mut total_balances: HashMap<u32, BalanceSummary> = HashMap::new();
+        for balance_summary in self.storage_manager.lock().await.get_balance_summaries().await? {
+            total_balances
+                .entry(balance_summary.coin_type)
+                .or_default()
+                .add(&balance_summary.balance);
+        }
+        Ok(total_balances)
+    }
+
+    /// Returns the summed balances of the accounts grouped by the value of a metadata key, as recorded after their
+    /// last sync. Accounts without the metadata key are not included.
+    pub async fn total_balance_by_metadata(&self, key: &str) -> crate::Result<HashMap<String, BalanceSummary>> {
+        let mut total_balances: HashMap<String, BalanceSummary> = HashMap::new();
+        for balance_summary in self.storage_manager.lock().await.get_balance_summaries().await? {
+            if let Some(value) = balance_summary.metadata.get(key) {
+                total_balances
+                    .entry(value.clone())
+                    .or_default()
+                    .add(&balance_summary.balance);
+            }
+        }
+        Ok(total_balances)
+    }
+}
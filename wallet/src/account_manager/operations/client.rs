@@ -1,7 +1,7 @@
 // Copyright 2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::HashSet;
+use std::{collections::HashSet, sync::Arc};
 
 use iota_client::{
     node_manager::node::{Node, NodeAuth, NodeDto},
@@ -9,6 +9,7 @@ use iota_client::{
 };
 
 use crate::{
+    account::{operations::syncing::request_limiter::RequestLimiter, RequestLimits},
     account_manager::{builder::AccountManagerBuilder, AccountManager},
     ClientOptions,
 };
@@ -24,7 +25,14 @@ impl AccountManager {
 
         let new_client = options.clone().finish()?;
 
-        for account in self.accounts.write().await.iter_mut() {
+        // Accounts with their own client options keep using them
+        for account in self
+            .accounts
+            .write()
+            .await
+            .iter_mut()
+            .filter(|account| account.client_options.is_none())
+        {
             account.update_account_with_new_client(new_client.clone()).await?;
         }
 
@@ -45,6 +53,83 @@ impl AccountManager {
         Ok(())
     }
 
+    /// Sets the client options for a single account, so it can use nodes of another network than the other accounts.
+    /// With `None` the account uses the client options of the account manager again.
+    pub async fn set_account_client_options(
+        &self,
+        account_index: u32,
+        options: Option<ClientOptions>,
+    ) -> crate::Result<()> {
+        log::debug!("[set_account_client_options] {account_index}");
+        let new_client = match &options {
+            Some(options) => options.clone().finish()?,
+            None => self.client_options.read().await.clone().finish()?,
+        };
+
+        let mut accounts = self.accounts.write().await;
+        let mut account_found = false;
+        for account in accounts.iter_mut() {
+            if *account.read().await.index() == account_index {
+                account.update_account_with_new_client(new_client).await?;
+                account.client_options = options.clone();
+                // Other nodes get their own request budget
+                account.request_limiter = match options {
+                    Some(_) => Arc::new(RequestLimiter::new(self.request_limiter.limits())),
+                    None => self.request_limiter.clone(),
+                };
+                account_found = true;
+                break;
+            }
+        }
+        if !account_found {
+            return Err(crate::Error::AccountNotFound(account_index.to_string()));
+        }
+
+        #[cfg(feature = "storage")]
+        self.storage_manager
+            .lock()
+            .await
+            .save_account_client_options(account_index, options.as_ref())
+            .await?;
+
+        Ok(())
+    }
+
+    /// Returns the client and the client options of an account that's restored from storage, it uses the client
+    /// options of the account manager if none are stored for the account.
+    #[cfg(feature = "storage")]
+    pub(crate) async fn stored_account_client(
+        &self,
+        account_index: u32,
+    ) -> crate::Result<(iota_client::Client, Option<ClientOptions>)> {
+        let account_client_options = self
+            .storage_manager
+            .lock()
+            .await
+            .get_account_client_options(account_index)
+            .await?;
+        let client = match &account_client_options {
+            Some(client_options) => client_options.clone().finish()?,
+            None => self.client_options.read().await.clone().finish()?,
+        };
+        Ok((client, account_client_options))
+    }
+
+    /// Sets the limits for the requests to the nodes while syncing. Accounts with their own client options get the
+    /// same limits for their nodes.
+    pub async fn set_request_limits(&self, limits: RequestLimits) {
+        log::debug!("[set_request_limits] {limits:?}");
+        self.request_limiter.set_limits(limits);
+        for account in self.accounts.read().await.iter() {
+            account.request_limiter.set_limits(limits);
+        }
+    }
+
+    /// Returns the limits for the requests to the nodes while syncing.
+    pub fn get_request_limits(&self) -> RequestLimits {
+        self.request_limiter.limits()
+    }
+
     /// Get the used client options.
     pub async fn get_client_options(&self) -> ClientOptions {
         self.client_options.read().await.clone()
@@ -54,8 +139,9 @@ impl AccountManager {
     pub async fn get_node_info(&self) -> crate::Result<NodeInfoWrapper> {
         let accounts = self.accounts.read().await;
 
-        // Try to get the Client from the first account and only build the Client if we have no account
-        let node_info_wrapper = match &accounts.first() {
+        // Try to get the Client from the first account that uses the client options of the account manager and only
+        // build the Client if we have no such account
+        let node_info_wrapper = match accounts.iter().find(|account| account.client_options.is_none()) {
             Some(account) => account.client.get_info().await?,
             None => self.client_options.read().await.clone().finish()?.get_info().await?,
         };
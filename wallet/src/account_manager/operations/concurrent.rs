@@ -0,0 +1,55 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::future::Future;
+
+use futures::{stream, StreamExt};
+
+use crate::{account::handle::AccountHandle, account_manager::AccountManager};
+
+impl AccountManager {
+    /// Runs an operation for all accounts, with at most `limit` operations running at the same time, so the nodes
+    /// aren't overloaded. Returns the results in the order of the accounts, or
+    /// [`Error::AccountOperationsFailed`](crate::Error::AccountOperationsFailed) with the errors of all accounts for
+    /// which the operation failed.
+    /// ```ignore
+    /// let balances = account_manager
+    ///     .for_each_account_concurrent(5, |account| async move { account.sync(None).await })
+    ///     .await?;
+    /// ```
+    pub async fn for_each_account_concurrent<F, Fut, T>(&self, limit: usize, operation: F) -> crate::Result<Vec<T>>
+    where
+        F: Fn(AccountHandle) -> Fut,
+        Fut: Future<Output = crate::Result<T>>,
+    {
+        let accounts = self.accounts.read().await.clone();
+
+        let mut results: Vec<(u32, crate::Result<T>)> = stream::iter(accounts)
+            .map(|account| {
+                let operation = &operation;
+                async move {
+                    let account_index = *account.read().await.index();
+                    (account_index, operation(account).await)
+                }
+            })
+            .buffer_unordered(limit.max(1))
+            .collect()
+            .await;
+        results.sort_by_key(|(account_index, _)| *account_index);
+
+        let mut values = Vec::new();
+        let mut errors = Vec::new();
+        for (account_index, result) in results {
+            match result {
+                Ok(value) => values.push(value),
+                Err(error) => errors.push((account_index, error)),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(values)
+        } else {
+            Err(crate::Error::AccountOperationsFailed(errors))
+        }
+    }
+}
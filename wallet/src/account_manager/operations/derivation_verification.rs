@@ -0,0 +1,127 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_client::secret::{SecretManage, SecretManager};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ledger_nano")]
+use crate::account_manager::{LedgerNanoOperation, LedgerNanoQueue};
+use crate::{account::types::AccountAddress, account_manager::AccountManager};
+
+/// The result of re-deriving the addresses of all accounts, returned from
+/// [`AccountManager::verify_derivations()`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DerivationReport {
+    /// Amount of addresses that were derived and compared.
+    pub verified_addresses: usize,
+    /// Indexes of watch-only accounts, their addresses don't belong to the seed and are skipped.
+    pub skipped_accounts: Vec<u32>,
+    /// Addresses that differ from the derived ones.
+    pub mismatches: Vec<DerivationMismatch>,
+}
+
+impl DerivationReport {
+    /// Returns true if all derived addresses match the stored ones.
+    pub fn is_valid(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+/// A stored address that differs from the address derived with the current secret manager.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DerivationMismatch {
+    /// The account index.
+    pub account_index: u32,
+    /// The address index.
+    pub key_index: u32,
+    /// If the address is an internal one.
+    pub internal: bool,
+    /// The bech32 encoded stored address.
+    pub stored_address: String,
+    /// The bech32 encoded derived address.
+    pub derived_address: Option<String>,
+}
+
+impl AccountManager {
+    /// Derives all addresses of all accounts with the current secret manager again and reports the ones that don't
+    /// match the stored addresses. Useful as safety check after restoring a backup or replacing the secret manager.
+    pub async fn verify_derivations(&self) -> crate::Result<DerivationReport> {
+        log::debug!("[verify_derivations]");
+        let secret_manager = self.secret_manager.read().await;
+        let mut report = DerivationReport::default();
+
+        for account_handle in self.accounts.read().await.iter() {
+            let account = account_handle.read().await;
+            if *account.watch_only() {
+                report.skipped_accounts.push(*account.index());
+                continue;
+            }
+            for (addresses, internal) in [
+                (account.public_addresses(), false),
+                (account.internal_addresses(), true),
+            ] {
+                report.verified_addresses += addresses.len();
+                report.mismatches.extend(
+                    find_derivation_mismatches(
+                        &secret_manager,
+                        #[cfg(feature = "ledger_nano")]
+                        &self.ledger_nano_queue,
+                        *account.coin_type(),
+                        *account.index(),
+                        addresses,
+                        internal,
+                    )
+                    .await?,
+                );
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+// Derives the addresses with the secret manager and returns the ones that differ from the addresses stored in the
+// account, through the queue if the secret manager is a Ledger Nano
+pub(crate) async fn find_derivation_mismatches(
+    secret_manager: &SecretManager,
+    #[cfg(feature = "ledger_nano")] ledger_nano_queue: &LedgerNanoQueue,
+    coin_type: u32,
+    account_index: u32,
+    addresses: &[AccountAddress],
+    internal: bool,
+) -> crate::Result<Vec<DerivationMismatch>> {
+    let address_count = match addresses.iter().map(|address| *address.key_index()).max() {
+        Some(highest_key_index) => highest_key_index + 1,
+        None => return Ok(Vec::new()),
+    };
+    let generate_addresses = async {
+        secret_manager
+            .generate_addresses(coin_type, account_index, 0..address_count, internal, None)
+            .await
+            .map_err(crate::Error::from)
+    };
+    #[cfg(feature = "ledger_nano")]
+    let generate_addresses = ledger_nano_queue.run_for(
+        secret_manager,
+        LedgerNanoOperation::GenerateAddresses,
+        generate_addresses,
+    );
+    let derived_addresses = generate_addresses.await?;
+
+    Ok(addresses
+        .iter()
+        .filter_map(|address| {
+            let derived_address = derived_addresses.get(*address.key_index() as usize);
+            (derived_address != Some(&address.address().inner)).then(|| DerivationMismatch {
+                account_index,
+                key_index: *address.key_index(),
+                internal,
+                stored_address: address.address().to_bech32(),
+                derived_address: derived_address
+                    .map(|derived_address| derived_address.to_bech32(address.address().bech32_hrp())),
+            })
+        })
+        .collect())
+}
@@ -0,0 +1,222 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use futures::StreamExt;
+use tokio::{
+    io::{AsyncWrite, AsyncWriteExt},
+    sync::Mutex,
+    task::JoinHandle,
+    time::timeout,
+};
+
+use crate::{
+    account_manager::AccountManager,
+    events::{types::EventFilter, EventEmitter, EventStream},
+};
+
+/// How long writing an event to a client can take, before the client is disconnected
+const EVENT_BRIDGE_WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+impl AccountManager {
+    /// Start a background process that publishes the events that match the filter to other processes, so a companion
+    /// app can observe the wallet without linking the library or opening the database. On Unix `path` is the path of
+    /// a socket that is only accessible by the current user. On Windows it's the name of a named pipe
+    /// (`\\.\pipe\<name>`) that only accepts local clients, but has the default security of the process, so other
+    /// users of the machine can read the events too. Every connected client receives the events that were emitted
+    /// after it connected, as JSON serialized [`StreamEvent`](crate::events::StreamEvent)s, one per line. Clients that
+    /// don't read the events in time are disconnected. A running bridge is replaced.
+    pub async fn start_event_bridge(&self, path: String, filter: EventFilter) -> crate::Result<()> {
+        log::debug!("[start_event_bridge]");
+        // stop existing process if running
+        self.stop_event_bridge().await?;
+
+        // Bound before the process is started, so errors are returned
+        #[cfg(unix)]
+        let listener = bind_socket(&path)?;
+
+        let event_bridge_status = self.event_bridge_status.clone();
+        event_bridge_status.store(1, Ordering::Relaxed);
+        let event_emitter = self.event_emitter.clone();
+        let _event_bridge = std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            runtime.block_on(async {
+                #[cfg(unix)]
+                let result = accept_unix_clients(listener, &event_emitter, &event_bridge_status, &filter).await;
+                #[cfg(windows)]
+                let result = accept_pipe_clients(&path, &event_emitter, &event_bridge_status, &filter).await;
+                if let Err(err) = result {
+                    log::debug!("[event_bridge] error: {}", err);
+                }
+                #[cfg(unix)]
+                std::fs::remove_file(&path).ok();
+                event_bridge_status.store(0, Ordering::Relaxed);
+                log::debug!("[event_bridge]: stopped");
+            });
+        });
+        Ok(())
+    }
+
+    /// Stop the event bridge, disconnects all clients
+    pub async fn stop_event_bridge(&self) -> crate::Result<()> {
+        log::debug!("[stop_event_bridge]");
+        // immediately return if not running
+        if self.event_bridge_status.load(Ordering::Relaxed) == 0 {
+            return Ok(());
+        }
+        // send stop request
+        self.event_bridge_status.store(2, Ordering::Relaxed);
+        // wait until it stopped
+        while self.event_bridge_status.load(Ordering::Relaxed) != 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+fn bind_socket(path: &str) -> crate::Result<std::os::unix::net::UnixListener> {
+    use std::os::unix::fs::{DirBuilderExt, FileTypeExt, PermissionsExt};
+
+    // Remove the socket of a previous run, other files aren't touched
+    if let Ok(metadata) = std::fs::symlink_metadata(path) {
+        if !metadata.file_type().is_socket() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                format!("{path} exists and isn't a socket"),
+            )
+            .into());
+        }
+        std::fs::remove_file(path)?;
+    }
+    // The socket is bound in a directory that only the current user can access and moved to the path after its
+    // permissions are restricted, so other users can't connect in between
+    let directory = format!("{path}.{}", std::process::id());
+    let socket_path = format!("{directory}/socket");
+    // Left over if a previous process with the same id crashed while binding
+    std::fs::remove_file(&socket_path).ok();
+    std::fs::remove_dir(&directory).ok();
+    std::fs::DirBuilder::new().mode(0o700).create(&directory)?;
+    let result = std::os::unix::net::UnixListener::bind(&socket_path).and_then(|listener| {
+        std::fs::set_permissions(&socket_path, std::fs::Permissions::from_mode(0o600))?;
+        std::fs::rename(&socket_path, path)?;
+        listener.set_nonblocking(true)?;
+        Ok(listener)
+    });
+    std::fs::remove_file(&socket_path).ok();
+    std::fs::remove_dir(&directory)?;
+    Ok(result?)
+}
+
+#[cfg(unix)]
+async fn accept_unix_clients(
+    listener: std::os::unix::net::UnixListener,
+    event_emitter: &Arc<Mutex<EventEmitter>>,
+    event_bridge_status: &Arc<AtomicUsize>,
+    filter: &EventFilter,
+) -> crate::Result<()> {
+    let listener = tokio::net::UnixListener::from_std(listener)?;
+    let mut clients: Vec<JoinHandle<()>> = Vec::new();
+    while event_bridge_status.load(Ordering::Relaxed) != 2 {
+        // wait at most a second for the next client, so stopping the process doesn't have to wait long
+        match timeout(Duration::from_secs(1), listener.accept()).await {
+            Ok(Ok((stream, _))) => {
+                let events = event_emitter.lock().await.subscribe(filter.clone(), None);
+                clients.retain(|client| !client.is_finished());
+                clients.push(tokio::spawn(publish_events(
+                    stream,
+                    events,
+                    event_bridge_status.clone(),
+                )));
+            }
+            Ok(Err(err)) => log::debug!("[event_bridge] failed to accept a client: {}", err),
+            Err(_) => continue,
+        }
+    }
+    futures::future::join_all(clients).await;
+    Ok(())
+}
+
+#[cfg(windows)]
+async fn accept_pipe_clients(
+    path: &str,
+    event_emitter: &Arc<Mutex<EventEmitter>>,
+    event_bridge_status: &Arc<AtomicUsize>,
+    filter: &EventFilter,
+) -> crate::Result<()> {
+    use tokio::net::windows::named_pipe::ServerOptions;
+
+    let mut server = ServerOptions::new()
+        .first_pipe_instance(true)
+        .reject_remote_clients(true)
+        .create(path)?;
+    let mut clients: Vec<JoinHandle<()>> = Vec::new();
+    while event_bridge_status.load(Ordering::Relaxed) != 2 {
+        // wait at most a second for the next client, so stopping the process doesn't have to wait long
+        match timeout(Duration::from_secs(1), server.connect()).await {
+            Ok(Ok(())) => {
+                // The connected instance is handed to the client, the next client connects to a new one
+                let connected = std::mem::replace(
+                    &mut server,
+                    ServerOptions::new().reject_remote_clients(true).create(path)?,
+                );
+                let events = event_emitter.lock().await.subscribe(filter.clone(), None);
+                clients.retain(|client| !client.is_finished());
+                clients.push(tokio::spawn(publish_events(
+                    connected,
+                    events,
+                    event_bridge_status.clone(),
+                )));
+            }
+            Ok(Err(err)) => log::debug!("[event_bridge] failed to accept a client: {}", err),
+            Err(_) => continue,
+        }
+    }
+    futures::future::join_all(clients).await;
+    Ok(())
+}
+
+// Writes the events as JSON lines until the client disconnects, doesn't read them in time or the bridge is stopped
+async fn publish_events(
+    mut client: impl AsyncWrite + Unpin,
+    mut events: EventStream,
+    event_bridge_status: Arc<AtomicUsize>,
+) {
+    while event_bridge_status.load(Ordering::Relaxed) != 2 {
+        let event = match timeout(Duration::from_secs(1), events.next()).await {
+            Ok(Some(event)) => event,
+            Ok(None) => break,
+            Err(_) => continue,
+        };
+        let mut line = match serde_json::to_vec(&event) {
+            Ok(line) => line,
+            Err(err) => {
+                log::debug!("[event_bridge] error: {}", err);
+                continue;
+            }
+        };
+        line.push(b'\n');
+        match timeout(EVENT_BRIDGE_WRITE_TIMEOUT, client.write_all(&line)).await {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => {
+                log::debug!("[event_bridge] client disconnected: {}", err);
+                break;
+            }
+            // A client that doesn't read would block the bridge and buffer all events in memory
+            Err(_) => {
+                log::debug!("[event_bridge] disconnecting a client that doesn't read the events");
+                break;
+            }
+        }
+    }
+}
@@ -0,0 +1,78 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use tokio::sync::{mpsc, oneshot};
+
+use crate::{
+    account_manager::AccountManager,
+    events::types::Event,
+    storage::{event_log::EventLogEntry, manager::StorageManagerHandle},
+};
+
+#[derive(Debug)]
+enum EventLogMessage {
+    Append(Event),
+    // Answered once all events that were sent before are written
+    Flush(oneshot::Sender<()>),
+}
+
+/// Sends the emitted events to a background process that appends them to the event log in the storage, in the
+/// order in which they were emitted. The process stops when all senders are dropped.
+#[derive(Debug, Clone)]
+pub(crate) struct EventLogSender(mpsc::UnboundedSender<EventLogMessage>);
+
+impl EventLogSender {
+    /// Starts the background process that writes the events to the storage.
+    pub(crate) fn spawn(storage_manager: StorageManagerHandle) -> Self {
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let _event_log = std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            runtime.block_on(async {
+                while let Some(message) = receiver.recv().await {
+                    match message {
+                        EventLogMessage::Append(event) => {
+                            if let Err(err) = storage_manager.lock().await.append_event(event).await {
+                                log::debug!("[event_log] error: {}", err);
+                            }
+                        }
+                        EventLogMessage::Flush(flushed) => {
+                            flushed.send(()).ok();
+                        }
+                    }
+                }
+            });
+        });
+        Self(sender)
+    }
+
+    /// Queues an event to be appended to the event log.
+    pub(crate) fn append(&self, event: Event) {
+        // Only fails if the background process stopped, then there is nothing to log to anymore
+        self.0.send(EventLogMessage::Append(event)).ok();
+    }
+
+    /// Waits until the queued events are written.
+    async fn flush(&self) {
+        let (sender, receiver) = oneshot::channel();
+        if self.0.send(EventLogMessage::Flush(sender)).is_ok() {
+            receiver.await.ok();
+        }
+    }
+}
+
+impl AccountManager {
+    /// Returns the emitted events with a sequence number greater than `sequence`, oldest first, so an app that wasn't
+    /// listening can replay the events it missed. Start with `0` and continue with the sequence number of the last
+    /// received entry. Only the last 10000 events are kept, if the returned first sequence number is greater than
+    /// `sequence + 1`, older events were removed already.
+    pub async fn events_since(&self, sequence: u64) -> crate::Result<Vec<EventLogEntry>> {
+        let event_log = self.event_emitter.lock().await.event_log().cloned();
+        if let Some(event_log) = event_log {
+            event_log.flush().await;
+        }
+        self.storage_manager.lock().await.get_events_since(sequence).await
+    }
+}
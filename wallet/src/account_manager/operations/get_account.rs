@@ -33,4 +33,25 @@ impl AccountManager {
 
         Err(crate::Error::AccountNotFound(serde_json::to_string(&account_id)?))
     }
+
+    /// Get an account by its alias, aliases are compared case-insensitively
+    pub async fn get_account_by_alias(&self, alias: &str) -> crate::Result<AccountHandle> {
+        #[cfg(feature = "storage")]
+        {
+            let account_index = self.storage_manager.lock().await.get_account_index_by_alias(alias);
+            if let Some(account_index) = account_index {
+                return self.get_account(account_index).await;
+            }
+        }
+
+        let accounts = self.accounts.read().await;
+        for account_handle in accounts.iter() {
+            let account = account_handle.read().await;
+            if account.alias().to_lowercase() == alias.to_lowercase() {
+                return Ok(account_handle.clone());
+            }
+        }
+
+        Err(crate::Error::AccountNotFound(alias.to_string()))
+    }
 }
@@ -0,0 +1,61 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    account::{
+        types::{InternalTransfer, Transaction},
+        TransactionOptions,
+    },
+    account_manager::AccountManager,
+    AddressWithAmount, Error,
+};
+
+impl AccountManager {
+    /// Sends an amount from one account to another account of the wallet, to the first public address of the
+    /// receiving account. The transaction is recorded as internal transfer on both accounts, so
+    /// [`AccountManager::statistics()`] doesn't count it as spent and received.
+    pub async fn internal_transfer(
+        &self,
+        from_index: u32,
+        to_index: u32,
+        amount: u64,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<Transaction> {
+        log::debug!("[internal_transfer] {from_index} -> {to_index}: {amount}");
+        if from_index == to_index {
+            return Err(Error::InvalidInternalTransfer(
+                "sending and receiving account are the same".to_string(),
+            ));
+        }
+        let from_account = self.get_account(from_index).await?;
+        let to_account = self.get_account(to_index).await?;
+        let address = to_account
+            .read()
+            .await
+            .public_addresses()
+            .first()
+            .map(|address| address.address().to_bech32())
+            .ok_or_else(|| Error::InvalidInternalTransfer("receiving account has no address".to_string()))?;
+
+        let simulate = options.as_ref().map_or(false, |options| options.simulate);
+        let transaction = from_account
+            .send_amount(vec![AddressWithAmount { address, amount }], options)
+            .await?;
+
+        if !simulate {
+            let internal_transfer = InternalTransfer {
+                from_account: from_index,
+                to_account: to_index,
+                amount,
+            };
+            from_account
+                .record_internal_transfer(transaction.transaction_id, internal_transfer)
+                .await?;
+            to_account
+                .record_internal_transfer(transaction.transaction_id, internal_transfer)
+                .await?;
+        }
+
+        Ok(transaction)
+    }
+}
@@ -1,17 +1,324 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use iota_client::secret::{LedgerNanoStatus, SecretManager};
+use std::{future::Future, time::Duration};
+
+use iota_client::{
+    api::PreparedTransactionData,
+    block::{
+        output::{AliasOutput, BasicOutput, FoundryOutput, NftOutput},
+        payload::transaction::TransactionEssence,
+    },
+    secret::{
+        ledger_nano::{needs_blind_signing, LedgerSecretManager},
+        LedgerDeviceType, LedgerNanoStatus, SecretManager,
+    },
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Mutex, RwLock};
 
 use crate::account_manager::AccountManager;
+#[cfg(feature = "events")]
+use crate::events::{
+    types::{LedgerNanoOperationEvent, WalletEvent},
+    EventEmitter,
+};
+
+/// An operation on the Ledger Nano.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LedgerNanoOperation {
+    /// Generating addresses without showing them.
+    GenerateAddresses,
+    /// Generating addresses and showing them on the device, needs a confirmation.
+    ShowAddresses,
+    /// Signing a transaction, needs a confirmation.
+    SignTransaction,
+    /// Getting the status of the device and the opened app.
+    GetStatus,
+}
+
+impl LedgerNanoOperation {
+    /// If the user needs to confirm the operation on the device.
+    pub fn needs_confirmation(&self) -> bool {
+        matches!(self, Self::ShowAddresses | Self::SignTransaction)
+    }
+}
+
+/// The state of an operation on the Ledger Nano.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LedgerNanoOperationStatus {
+    /// Waiting for previous operations to finish.
+    Queued,
+    /// Sent to the device.
+    Processing,
+    /// Sent to the device, waiting for the user to confirm it on the device.
+    AwaitingConfirmation,
+    /// Finished, successfully or with an error.
+    Finished,
+    /// Didn't finish in time.
+    TimedOut,
+}
+
+/// How long operations on the Ledger Nano can take before they fail with [`crate::Error::LedgerNanoTimeout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LedgerNanoTimeouts {
+    /// Timeout for operations without a confirmation on the device.
+    pub processing: Duration,
+    /// Timeout for operations that need a confirmation on the device, includes the time the user needs.
+    pub confirmation: Duration,
+}
+
+impl Default for LedgerNanoTimeouts {
+    fn default() -> Self {
+        Self {
+            processing: Duration::from_secs(60),
+            confirmation: Duration::from_secs(300),
+        }
+    }
+}
+
+/// Runs the operations on the Ledger Nano one after another, so concurrent syncs or transactions of different
+/// accounts don't interleave their requests to the device.
+#[derive(Debug, Default)]
+pub struct LedgerNanoQueue {
+    lock: Mutex<()>,
+    timeouts: RwLock<LedgerNanoTimeouts>,
+}
+
+impl LedgerNanoQueue {
+    /// Waits until all previous operations finished and runs the operation with its timeout. The events are only
+    /// emitted for operations of an account, not for internal checks of the account manager.
+    pub(crate) async fn run<T>(
+        &self,
+        operation: LedgerNanoOperation,
+        #[cfg(feature = "events")] events: Option<(&Mutex<EventEmitter>, u32)>,
+        future: impl Future<Output = crate::Result<T>>,
+    ) -> crate::Result<T> {
+        #[cfg(feature = "events")]
+        let emit = |status| async move {
+            if let Some((event_emitter, account_index)) = events {
+                event_emitter.lock().await.emit(
+                    account_index,
+                    WalletEvent::LedgerNanoOperation(LedgerNanoOperationEvent { operation, status }),
+                );
+            }
+        };
+
+        #[cfg(feature = "events")]
+        emit(LedgerNanoOperationStatus::Queued).await;
+        let _lock = self.lock.lock().await;
+        let timeouts = *self.timeouts.read().await;
+        let timeout = if operation.needs_confirmation() {
+            timeouts.confirmation
+        } else {
+            timeouts.processing
+        };
+        log::debug!("[LEDGER NANO] {operation:?} with a timeout of {timeout:?}");
+        #[cfg(feature = "events")]
+        emit(if operation.needs_confirmation() {
+            LedgerNanoOperationStatus::AwaitingConfirmation
+        } else {
+            LedgerNanoOperationStatus::Processing
+        })
+        .await;
+
+        match tokio::time::timeout(timeout, future).await {
+            Ok(result) => {
+                #[cfg(feature = "events")]
+                emit(LedgerNanoOperationStatus::Finished).await;
+                result
+            }
+            Err(_) => {
+                #[cfg(feature = "events")]
+                emit(LedgerNanoOperationStatus::TimedOut).await;
+                Err(crate::Error::LedgerNanoTimeout(operation))
+            }
+        }
+    }
+
+    /// Runs an operation of the secret manager without events, through the queue if it's a Ledger Nano.
+    pub(crate) async fn run_for<T>(
+        &self,
+        secret_manager: &SecretManager,
+        operation: LedgerNanoOperation,
+        future: impl Future<Output = crate::Result<T>>,
+    ) -> crate::Result<T> {
+        match secret_manager {
+            SecretManager::LedgerNano(_) => {
+                self.run(
+                    operation,
+                    #[cfg(feature = "events")]
+                    None,
+                    future,
+                )
+                .await
+            }
+            _ => future.await,
+        }
+    }
+
+    /// Gets the status of the Ledger Nano.
+    pub(crate) async fn get_status(
+        &self,
+        ledger: &LedgerSecretManager,
+        #[cfg(feature = "events")] events: Option<(&Mutex<EventEmitter>, u32)>,
+    ) -> crate::Result<LedgerNanoStatus> {
+        self.run(
+            LedgerNanoOperation::GetStatus,
+            #[cfg(feature = "events")]
+            events,
+            async { Ok(ledger.get_ledger_nano_status().await) },
+        )
+        .await
+    }
+}
+
+/// What the connected Ledger Nano and the opened app support.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerNanoCapabilities {
+    /// Name of the opened app.
+    pub app_name: String,
+    /// Version of the opened app.
+    pub app_version: String,
+    /// The device type.
+    pub device: Option<LedgerDeviceType>,
+    /// With blind signing transactions that can't be shown on the device are signed by their essence hash.
+    pub blind_signing_enabled: bool,
+    /// The maximum size in bytes of a transaction essence, including the BIP32 indices of the inputs, that can be
+    /// shown on the device.
+    pub max_essence_size: Option<usize>,
+    /// The output kinds that can be signed. Without blind signing only basic outputs to an Ed25519 address without
+    /// further unlock conditions or features can be shown on the device.
+    pub supported_output_kinds: Vec<u8>,
+}
+
+impl LedgerNanoCapabilities {
+    /// Creates the capabilities from the status, fails if the device can't be used.
+    pub fn try_from_status(status: &LedgerNanoStatus) -> crate::Result<Self> {
+        if !status.connected() {
+            return Err(crate::Error::LedgerNanoNotReady("not connected"));
+        }
+        if status.locked() == Some(true) {
+            return Err(crate::Error::LedgerNanoNotReady("locked"));
+        }
+        let app = status.app().ok_or(crate::Error::LedgerNanoNotReady("no app opened"))?;
+
+        let supported_output_kinds = if status.blind_signing_enabled() {
+            vec![
+                BasicOutput::KIND,
+                AliasOutput::KIND,
+                FoundryOutput::KIND,
+                NftOutput::KIND,
+            ]
+        } else {
+            vec![BasicOutput::KIND]
+        };
+
+        Ok(Self {
+            app_name: app.name().clone(),
+            app_version: app.version().clone(),
+            device: status.device(),
+            blind_signing_enabled: status.blind_signing_enabled(),
+            max_essence_size: status.buffer_size(),
+            supported_output_kinds,
+        })
+    }
+
+    /// Checks if the transaction can be signed, so it doesn't get rejected by the device.
+    pub fn check_transaction(&self, prepared_transaction_data: &PreparedTransactionData) -> crate::Result<()> {
+        let TransactionEssence::Regular(essence) = &prepared_transaction_data.essence;
+        if let Some(output) = essence
+            .outputs()
+            .iter()
+            .find(|output| !self.supported_output_kinds.contains(&output.kind()))
+        {
+            // Without blind signing only basic outputs are supported, all other outputs need it
+            return Err(if self.blind_signing_enabled {
+                crate::Error::LedgerNanoUnsupportedOutputKind(output.kind())
+            } else {
+                crate::Error::LedgerNanoBlindSigningRequired
+            });
+        }
+        if let (false, Some(max_essence_size)) = (self.blind_signing_enabled, self.max_essence_size) {
+            if needs_blind_signing(prepared_transaction_data, max_essence_size) {
+                return Err(crate::Error::LedgerNanoBlindSigningRequired);
+            }
+        }
+        Ok(())
+    }
+}
 
 impl AccountManager {
     /// Get the ledger nano status
     pub async fn get_ledger_nano_status(&self) -> crate::Result<LedgerNanoStatus> {
         if let SecretManager::LedgerNano(ledger) = &*self.secret_manager.read().await {
-            Ok(ledger.get_ledger_nano_status().await)
+            self.ledger_nano_queue
+                .get_status(
+                    ledger,
+                    #[cfg(feature = "events")]
+                    Some((&*self.event_emitter, 0)),
+                )
+                .await
         } else {
             Err(iota_client::Error::SecretManagerMismatch.into())
         }
     }
+
+    /// Sets how long operations on the ledger nano can take, before they fail.
+    pub async fn set_ledger_nano_timeouts(&self, timeouts: LedgerNanoTimeouts) {
+        *self.ledger_nano_queue.timeouts.write().await = timeouts;
+    }
+
+    /// Returns how long operations on the ledger nano can take.
+    pub async fn get_ledger_nano_timeouts(&self) -> LedgerNanoTimeouts {
+        *self.ledger_nano_queue.timeouts.read().await
+    }
+
+    /// Get the capabilities of the ledger nano and the opened app, fails if the device is not connected, locked or no
+    /// app is opened
+    pub async fn get_ledger_nano_capabilities(&self) -> crate::Result<LedgerNanoCapabilities> {
+        LedgerNanoCapabilities::try_from_status(&self.get_ledger_nano_status().await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use iota_client::secret::placeholder::PlaceholderSecretManager;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn operations_are_serialized() {
+        let queue = LedgerNanoQueue::default();
+        let (running, max_running) = (AtomicUsize::new(0), AtomicUsize::new(0));
+        let (queue, running, max_running) = (&queue, &running, &max_running);
+
+        let operations = (0..4).map(move |_| {
+            queue.run(
+                LedgerNanoOperation::GenerateAddresses,
+                #[cfg(feature = "events")]
+                None,
+                async move {
+                    max_running.fetch_max(running.fetch_add(1, Ordering::SeqCst) + 1, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    Ok(())
+                },
+            )
+        });
+        futures::future::try_join_all(operations).await.unwrap();
+        assert_eq!(max_running.load(Ordering::SeqCst), 1);
+
+        // Requests of a Ledger Nano wait for the running operation, other secret managers don't use the queue
+        let _running_operation = queue.lock.lock().await;
+        let ledger_nano = SecretManager::LedgerNano(LedgerSecretManager::new(true));
+        let request = queue.run_for(&ledger_nano, LedgerNanoOperation::GetStatus, async { Ok(()) });
+        assert!(tokio::time::timeout(Duration::from_millis(50), request).await.is_err());
+        let placeholder = SecretManager::Placeholder(PlaceholderSecretManager);
+        let request = queue.run_for(&placeholder, LedgerNanoOperation::GenerateAddresses, async { Ok(()) });
+        assert!(tokio::time::timeout(Duration::from_millis(50), request).await.is_ok());
+    }
 }
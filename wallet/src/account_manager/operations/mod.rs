@@ -1,16 +1,52 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(feature = "storage")]
+pub(crate) mod account_archive;
 pub(crate) mod account_recovery;
 pub(crate) mod address_generation;
+#[cfg(feature = "storage")]
+pub(crate) mod background_compaction;
 pub(crate) mod background_syncing;
+#[cfg(feature = "storage")]
+pub(crate) mod backup_verification;
+#[cfg(feature = "storage")]
+pub(crate) mod balance_summary;
 pub(crate) mod client;
+pub(crate) mod concurrent;
+pub(crate) mod derivation_verification;
+#[cfg(feature = "event_bridge")]
+pub(crate) mod event_bridge;
+#[cfg(all(feature = "events", feature = "storage"))]
+pub(crate) mod event_log;
 pub(crate) mod get_account;
+pub(crate) mod internal_transfer;
 #[cfg(feature = "ledger_nano")]
 pub(crate) mod ledger_nano;
+#[cfg(feature = "mqtt")]
+pub(crate) mod mqtt_syncing;
+#[cfg(feature = "storage")]
+pub(crate) mod offline_mode;
+#[cfg(feature = "storage")]
+pub(crate) mod portable_backup;
+#[cfg(feature = "storage")]
+pub(crate) mod profile;
+#[cfg(feature = "storage")]
+pub(crate) mod scheduled_backups;
+#[cfg(feature = "storage")]
+pub(crate) mod scheduled_payments;
+pub(crate) mod secret_manager_migration;
+pub(crate) mod signing_policy;
+pub(crate) mod statistics;
+#[cfg(feature = "storage")]
+pub(crate) mod storage;
 #[cfg(feature = "stronghold")]
 pub(crate) mod stronghold;
 #[cfg(feature = "stronghold")]
 pub(crate) mod stronghold_backup;
+#[cfg(feature = "stronghold")]
+pub(crate) mod stronghold_snapshot_generations;
 #[cfg(debug_assertions)]
 pub(crate) mod verify_integrity;
+#[cfg(feature = "webhooks")]
+pub(crate) mod webhooks;
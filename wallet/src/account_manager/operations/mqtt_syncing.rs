@@ -0,0 +1,218 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::atomic::Ordering,
+    time::Duration,
+};
+
+use instant::Instant;
+use iota_client::{MqttEvent, Topic, TopicEvent};
+use tokio::{sync::mpsc, time::sleep};
+
+use crate::{
+    account::{handle::AccountHandle, operations::syncing::SyncOptions},
+    account_manager::AccountManager,
+};
+
+/// The default interval for polling the accounts while the MQTT connection is down
+pub(crate) const DEFAULT_MQTT_SYNCING_FALLBACK_INTERVAL: Duration = Duration::from_secs(30);
+
+impl AccountManager {
+    /// Start syncing the accounts based on MQTT notifications from the node. The wallet subscribes to the output topics
+    /// of all account addresses and syncs only the address that got a new or spent output. Addresses generated after
+    /// the start get subscribed within a second. While the MQTT connection is down, the accounts are polled with the
+    /// fallback interval instead, default is 30 seconds.
+    pub async fn start_mqtt_syncing(
+        &self,
+        options: Option<SyncOptions>,
+        fallback_interval: Option<Duration>,
+    ) -> crate::Result<()> {
+        log::debug!("[start_mqtt_syncing]");
+        let mqtt_syncing_status = self.mqtt_syncing_status.clone();
+        // stop existing process if running
+        if mqtt_syncing_status.load(Ordering::Relaxed) == 1 {
+            mqtt_syncing_status.store(2, Ordering::Relaxed);
+        };
+        while mqtt_syncing_status.load(Ordering::Relaxed) == 2 {
+            log::debug!("[mqtt_syncing]: waiting for the old process to stop");
+            sleep(Duration::from_secs(1)).await;
+        }
+
+        mqtt_syncing_status.store(1, Ordering::Relaxed);
+        let accounts = self.accounts.clone();
+        let options = options.unwrap_or_default();
+        let fallback_interval = fallback_interval.unwrap_or(DEFAULT_MQTT_SYNCING_FALLBACK_INTERVAL);
+        let _mqtt_syncing = std::thread::spawn(move || {
+            #[cfg(not(target_family = "wasm"))]
+            let runtime = tokio::runtime::Builder::new_multi_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            #[cfg(target_family = "wasm")]
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            runtime.block_on(async {
+                // The callbacks of the subscriptions send the account index and bech32 address of a notification
+                let (sender, mut receiver) = mpsc::unbounded_channel::<(u32, String)>();
+                // Subscribed addresses per account, when the accounts were polled the last time and which accounts
+                // had their connection down
+                let mut subscriptions: HashMap<u32, (AccountHandle, HashSet<String>)> = HashMap::new();
+                let mut last_polls: HashMap<u32, Instant> = HashMap::new();
+                let mut disconnected_accounts: HashSet<u32> = HashSet::new();
+
+                loop {
+                    if mqtt_syncing_status.load(Ordering::Relaxed) == 2 {
+                        log::debug!("[mqtt_syncing]: stopping");
+                        break;
+                    }
+
+                    for account in accounts.read().await.iter() {
+                        let account_index = *account.read().await.index();
+                        let subscribed_addresses = &mut subscriptions
+                            .entry(account_index)
+                            .or_insert_with(|| (account.clone(), HashSet::new()))
+                            .1;
+                        if let Err(err) =
+                            subscribe_new_addresses(account, account_index, subscribed_addresses, sender.clone()).await
+                        {
+                            log::debug!("[mqtt_syncing] subscribing account {account_index} failed: {err}");
+                        }
+
+                        // Poll accounts that were never synced by this process, all accounts while the connection is
+                        // down and once after it's back, because notifications could have been missed
+                        let disconnected = matches!(
+                            *account.client().mqtt_event_receiver().borrow(),
+                            MqttEvent::Disconnected
+                        );
+                        let reconnected = if disconnected {
+                            disconnected_accounts.insert(account_index);
+                            false
+                        } else {
+                            disconnected_accounts.remove(&account_index)
+                        };
+                        let poll_due = reconnected
+                            || last_polls.get(&account_index).map_or(true, |last_poll| {
+                                disconnected && last_poll.elapsed() >= fallback_interval
+                            });
+                        if poll_due {
+                            log::debug!("[mqtt_syncing]: polling account {account_index}");
+                            if let Err(err) = account.sync(Some(options.clone())).await {
+                                log::debug!("[mqtt_syncing] error: {err}");
+                            }
+                            last_polls.insert(account_index, Instant::now());
+                        }
+                    }
+
+                    // Collect the notifications, so every address is only synced once
+                    let mut notified_addresses: HashMap<u32, HashSet<String>> = HashMap::new();
+                    while let Ok((account_index, bech32_address)) = receiver.try_recv() {
+                        notified_addresses
+                            .entry(account_index)
+                            .or_default()
+                            .insert(bech32_address);
+                    }
+                    for (account_index, addresses) in notified_addresses {
+                        if let Some((account, _)) = subscriptions.get(&account_index) {
+                            log::debug!("[mqtt_syncing]: syncing {addresses:?} of account {account_index}");
+                            let address_options = SyncOptions {
+                                addresses: addresses.into_iter().collect(),
+                                force_syncing: true,
+                                ..options.clone()
+                            };
+                            if let Err(err) = account.sync(Some(address_options)).await {
+                                log::debug!("[mqtt_syncing] error: {err}");
+                            }
+                        }
+                    }
+
+                    sleep(Duration::from_secs(1)).await;
+                }
+
+                for (account, subscribed_addresses) in subscriptions.into_values() {
+                    if let Err(err) = account
+                        .client()
+                        .subscriber()
+                        .with_topics(address_topics(subscribed_addresses.iter()))
+                        .unsubscribe()
+                        .await
+                    {
+                        log::debug!("[mqtt_syncing] unsubscribing failed: {err}");
+                    }
+                }
+                mqtt_syncing_status.store(0, Ordering::Relaxed);
+                log::debug!("[mqtt_syncing]: stopped");
+            });
+        });
+        Ok(())
+    }
+
+    /// Stop the MQTT based syncing of the accounts and unsubscribe from the topics
+    pub async fn stop_mqtt_syncing(&self) -> crate::Result<()> {
+        log::debug!("[stop_mqtt_syncing]");
+        // immediately return if not running
+        if self.mqtt_syncing_status.load(Ordering::Relaxed) == 0 {
+            return Ok(());
+        }
+        // send stop request
+        self.mqtt_syncing_status.store(2, Ordering::Relaxed);
+        // wait until it stopped
+        while self.mqtt_syncing_status.load(Ordering::Relaxed) != 0 {
+            #[cfg(target_family = "wasm")]
+            gloo_timers::future::TimeoutFuture::new(10).await;
+            #[cfg(not(target_family = "wasm"))]
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        Ok(())
+    }
+}
+
+// Subscribe to the output topics of the account addresses that aren't subscribed yet
+async fn subscribe_new_addresses(
+    account: &AccountHandle,
+    account_index: u32,
+    subscribed_addresses: &mut HashSet<String>,
+    sender: mpsc::UnboundedSender<(u32, String)>,
+) -> crate::Result<()> {
+    let new_addresses = account
+        .addresses()
+        .await?
+        .into_iter()
+        .map(|address| address.address().to_bech32())
+        .filter(|bech32_address| !subscribed_addresses.contains(bech32_address))
+        .collect::<Vec<String>>();
+    if new_addresses.is_empty() {
+        return Ok(());
+    }
+
+    account
+        .client()
+        .subscriber()
+        .with_topics(address_topics(new_addresses.iter()))
+        .subscribe(move |event: &TopicEvent| {
+            // Topics are "outputs/unlock/address/{bech32}" and "outputs/unlock/address/{bech32}/spent"
+            if let Some(bech32_address) = event.topic.split('/').nth(3) {
+                // Only fails if the process stopped
+                sender.send((account_index, bech32_address.to_string())).ok();
+            }
+        })
+        .await?;
+    subscribed_addresses.extend(new_addresses);
+    Ok(())
+}
+
+// The topics for new and spent outputs that can be unlocked by the addresses
+fn address_topics<'a>(bech32_addresses: impl Iterator<Item = &'a String>) -> Vec<Topic> {
+    bech32_addresses
+        .flat_map(|bech32_address| {
+            [
+                format!("outputs/unlock/address/{bech32_address}"),
+                format!("outputs/unlock/address/{bech32_address}/spent"),
+            ]
+        })
+        .filter_map(|topic| Topic::try_from(topic).ok())
+        .collect()
+}
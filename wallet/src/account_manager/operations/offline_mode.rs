@@ -0,0 +1,46 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::HashMap, sync::atomic::Ordering};
+
+use crate::{account::QueueSubmission, account_manager::AccountManager};
+
+impl AccountManager {
+    /// Enable or disable the offline mode. While offline, the background syncing skips all accounts and transactions
+    /// can be prepared, signed and queued with
+    /// [`queue_transaction()`](crate::account::AccountHandle::queue_transaction) and
+    /// [`queue_claim_outputs()`](crate::account::AccountHandle::queue_claim_outputs). When the offline mode is
+    /// disabled, the queued transactions of all accounts are submitted, the background syncing also submits them after
+    /// every successful sync of an account.
+    pub async fn set_offline_mode(&self, offline: bool) -> crate::Result<()> {
+        log::debug!("[set_offline_mode] {offline}");
+        self.offline_mode.store(offline, Ordering::Relaxed);
+        if !offline {
+            self.submit_queued_transactions().await?;
+        }
+        Ok(())
+    }
+
+    /// Returns true if the offline mode is enabled.
+    pub fn is_offline_mode(&self) -> bool {
+        self.offline_mode.load(Ordering::Relaxed)
+    }
+
+    /// Submit the queued transactions of all accounts, returns the submissions of the accounts that had queued
+    /// transactions by account index. Fails if the offline mode is enabled.
+    pub async fn submit_queued_transactions(&self) -> crate::Result<HashMap<u32, QueueSubmission>> {
+        log::debug!("[submit_queued_transactions]");
+        if self.is_offline_mode() {
+            return Err(crate::Error::OfflineMode);
+        }
+        let mut queue_submissions = HashMap::new();
+        for account in self.accounts.read().await.iter() {
+            let account_index = *account.read().await.index();
+            let queue_submission = account.submit_queued_transactions().await?;
+            if !queue_submission.submitted.is_empty() || !queue_submission.conflicting.is_empty() {
+                queue_submissions.insert(account_index, queue_submission);
+            }
+        }
+        Ok(queue_submissions)
+    }
+}
@@ -0,0 +1,399 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{path::Path, sync::atomic::Ordering};
+
+use instant::SystemTime;
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use crate::{
+    account::handle::AccountHandle,
+    account_manager::{builder::AccountManagerBuilder, AccountManager},
+    encryption::PasswordEncryptedData,
+    storage::{
+        audit_log::AuditOperation, constants::*, export::StorageBundle, manager::StorageManagerHandle,
+        seed_fingerprint::SeedFingerprint,
+    },
+    ClientOptions,
+};
+
+/// The version of the portable backup format.
+pub const PORTABLE_BACKUP_VERSION: u8 = 1;
+// Identifies portable backup files, so other JSON files are rejected with a meaningful error
+const PORTABLE_BACKUP_FORMAT: &str = "iota-wallet-portable-backup";
+
+/// The decrypted content of a portable backup: the accounts with their addresses, outputs and transactions, and the
+/// settings of the account manager. Secret managers and seeds are never included.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortableBackup {
+    /// The version of the backup format.
+    pub version: u8,
+    /// Unix timestamp in milliseconds when the backup was created.
+    pub created_at: u64,
+    /// The records of the accounts and the account manager.
+    pub bundle: StorageBundle,
+}
+
+/// Options for [`AccountManager::backup_portable()`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortableBackupOptions {
+    /// The indexes of the accounts that are included, all accounts if `None`.
+    #[serde(default)]
+    pub account_indexes: Option<Vec<u32>>,
+}
+
+/// Options for [`AccountManager::restore_portable_backup()`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortableRestoreOptions {
+    /// The indexes of the accounts that are restored, all accounts of the backup if `None`.
+    #[serde(default)]
+    pub account_indexes: Option<Vec<u32>>,
+    /// Adds the accounts to the existing accounts instead of requiring an empty account manager. The client options
+    /// and the coin type of the account manager are kept. Fails without restoring anything if an account has the
+    /// index or the alias of an existing account.
+    #[serde(default)]
+    pub merge: bool,
+}
+
+// The content of a portable backup file, only the versions are readable without the password
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PortableBackupFile {
+    format: String,
+    version: u8,
+    database_schema_version: u8,
+    encrypted: PasswordEncryptedData,
+}
+
+impl PortableBackupFile {
+    fn read(bytes: &[u8]) -> crate::Result<Self> {
+        let file: Self = serde_json::from_slice(bytes)?;
+        if file.format != PORTABLE_BACKUP_FORMAT {
+            return Err(crate::Error::Backup("not a portable backup"));
+        }
+        if file.version != PORTABLE_BACKUP_VERSION {
+            return Err(crate::Error::Storage(format!(
+                "unsupported portable backup version {}",
+                file.version
+            )));
+        }
+        Ok(file)
+    }
+}
+
+impl PortableBackup {
+    /// Decrypts a portable backup, fails if the password is wrong, the data was modified or the version isn't
+    /// supported. Records of older database schema versions are migrated to the current one, so backups of older
+    /// versions of the wallet can be restored.
+    pub fn decrypt(bytes: &[u8], password: &str) -> crate::Result<Self> {
+        let file = PortableBackupFile::read(bytes)?;
+        let mut backup: Self = serde_json::from_slice(&file.encrypted.decrypt(password)?)?;
+        // The versions are also part of the encrypted data, so they can't be modified
+        if backup.version != file.version || backup.bundle.database_schema_version != file.database_schema_version {
+            return Err(crate::Error::Backup("portable backup version was modified"));
+        }
+        backup.bundle.migrate()?;
+        Ok(backup)
+    }
+
+    /// Returns the database schema version the backup was created with, without decrypting it.
+    pub fn database_schema_version(bytes: &[u8]) -> crate::Result<u8> {
+        Ok(PortableBackupFile::read(bytes)?.database_schema_version)
+    }
+
+    fn encrypt(&self, password: &str) -> crate::Result<Vec<u8>> {
+        let file = PortableBackupFile {
+            format: PORTABLE_BACKUP_FORMAT.to_string(),
+            version: self.version,
+            database_schema_version: self.bundle.database_schema_version,
+            encrypted: PasswordEncryptedData::encrypt(password, &serde_json::to_vec(self)?)?,
+        };
+        Ok(serde_json::to_vec_pretty(&file)?)
+    }
+}
+
+impl AccountManager {
+    /// Writes a portable backup, encrypted with the password, to a file. Unlike [`AccountManager::backup()`] it doesn't
+    /// need Stronghold and contains no secrets, the seed has to be restored separately. The backup can be restored on
+    /// every platform, including wasm, with [`AccountManager::restore_portable_backup()`].
+    pub async fn backup_portable(
+        &self,
+        path: impl AsRef<Path>,
+        password: &str,
+        options: Option<PortableBackupOptions>,
+    ) -> crate::Result<()> {
+        let result = create_portable_backup(
+            &self.accounts,
+            &self.storage_manager,
+            password,
+            &options.unwrap_or_default(),
+        )
+        .await
+        .and_then(|backup| Ok(std::fs::write(&path, backup)?));
+
+        if let Err(err) = self
+            .storage_manager
+            .lock()
+            .await
+            .audit(None, AuditOperation::PortableBackup, &path.as_ref(), &result)
+            .await
+        {
+            log::debug!("[backup_portable] audit log error: {}", err);
+        }
+
+        result
+    }
+
+    /// Returns a portable backup encrypted with the password, like [`AccountManager::backup_portable()`], for
+    /// platforms without a file system.
+    pub async fn backup_portable_bytes(
+        &self,
+        password: &str,
+        options: Option<PortableBackupOptions>,
+    ) -> crate::Result<Vec<u8>> {
+        let result = create_portable_backup(
+            &self.accounts,
+            &self.storage_manager,
+            password,
+            &options.unwrap_or_default(),
+        )
+        .await;
+
+        if let Err(err) = self
+            .storage_manager
+            .lock()
+            .await
+            .audit(None, AuditOperation::PortableBackup, &(), &result)
+            .await
+        {
+            log::debug!("[backup_portable_bytes] audit log error: {}", err);
+        }
+
+        result
+    }
+
+    /// Restores a backup created with [`AccountManager::backup_portable()`]. The accounts, the client options and the
+    /// coin type are replaced, the secret manager is kept and has to use the seed of the backup, which is checked with
+    /// the seed fingerprint of the backup or the first address of every account. Returns an error if accounts were
+    /// already created, unless [`PortableRestoreOptions::merge`] is set.
+    pub async fn restore_portable_backup(
+        &self,
+        path: impl AsRef<Path>,
+        password: &str,
+        options: Option<PortableRestoreOptions>,
+    ) -> crate::Result<()> {
+        let result = match std::fs::read(&path) {
+            Ok(bytes) => {
+                self.restore_portable_backup_internal(&bytes, password, options.unwrap_or_default())
+                    .await
+            }
+            Err(err) => Err(err.into()),
+        };
+
+        if let Err(err) = self
+            .storage_manager
+            .lock()
+            .await
+            .audit(None, AuditOperation::RestorePortableBackup, &path.as_ref(), &result)
+            .await
+        {
+            log::debug!("[restore_portable_backup] audit log error: {}", err);
+        }
+
+        result
+    }
+
+    /// Restores a backup returned by [`AccountManager::backup_portable_bytes()`], like
+    /// [`AccountManager::restore_portable_backup()`].
+    pub async fn restore_portable_backup_bytes(
+        &self,
+        bytes: &[u8],
+        password: &str,
+        options: Option<PortableRestoreOptions>,
+    ) -> crate::Result<()> {
+        let result = self
+            .restore_portable_backup_internal(bytes, password, options.unwrap_or_default())
+            .await;
+
+        if let Err(err) = self
+            .storage_manager
+            .lock()
+            .await
+            .audit(None, AuditOperation::RestorePortableBackup, &(), &result)
+            .await
+        {
+            log::debug!("[restore_portable_backup_bytes] audit log error: {}", err);
+        }
+
+        result
+    }
+
+    async fn restore_portable_backup_internal(
+        &self,
+        bytes: &[u8],
+        password: &str,
+        options: PortableRestoreOptions,
+    ) -> crate::Result<()> {
+        log::debug!("[restore_portable_backup]");
+        let mut bundle = PortableBackup::decrypt(bytes, password)?.bundle;
+        if let Some(account_indexes) = &options.account_indexes {
+            bundle.retain_accounts(account_indexes)?;
+        }
+
+        let mut accounts = self.accounts.write().await;
+        // We don't want to overwrite possible existing accounts
+        if !options.merge && !accounts.is_empty() {
+            return Err(crate::Error::Backup(
+                "can't restore backup when there are already accounts",
+            ));
+        }
+
+        let seed_fingerprint = match bundle.records.get(SEED_FINGERPRINT_KEY) {
+            Some(seed_fingerprint) => {
+                let seed_fingerprint: SeedFingerprint = serde_json::from_value(seed_fingerprint.clone())?;
+                seed_fingerprint
+                    .verify(
+                        &self.secret_manager,
+                        #[cfg(feature = "ledger_nano")]
+                        &self.ledger_nano_queue,
+                    )
+                    .await?;
+                Some(seed_fingerprint)
+            }
+            // Backups of wallets without a seed fingerprint are checked account by account
+            None => {
+                for account_index in bundle.account_indexes()? {
+                    self.verify_account_seed(&bundle.account_package(account_index).account()?)
+                        .await?;
+                }
+                None
+            }
+        };
+
+        if options.merge {
+            return self
+                .merge_portable_backup(&mut accounts, bundle, seed_fingerprint)
+                .await;
+        }
+
+        // Settings of the account manager
+        if let Some(account_manager_data) = bundle.records.get(ACCOUNT_MANAGER_INDEXATION_KEY) {
+            if let Some(client_options) = account_manager_data
+                .get("client_options")
+                .filter(|client_options| !client_options.is_null())
+            {
+                *self.client_options.write().await = serde_json::from_value::<ClientOptions>(client_options.clone())?;
+            }
+            if let Some(coin_type) = account_manager_data
+                .get("coin_type")
+                .and_then(serde_json::Value::as_u64)
+            {
+                self.coin_type.store(coin_type as u32, Ordering::Relaxed);
+            }
+            let account_manager_builder = AccountManagerBuilder::from_account_manager(self).await;
+            self.storage_manager
+                .lock()
+                .await
+                .save_account_manager_data(&account_manager_builder)
+                .await?;
+        }
+
+        self.storage_manager.lock().await.import_bundle(bundle).await?;
+        self.load_imported_accounts(&mut accounts).await
+    }
+
+    // Adds the accounts of the bundle to the existing accounts, all collisions are checked before the accounts are
+    // written in a single write
+    async fn merge_portable_backup(
+        &self,
+        accounts: &mut Vec<AccountHandle>,
+        bundle: StorageBundle,
+        seed_fingerprint: Option<SeedFingerprint>,
+    ) -> crate::Result<()> {
+        let coin_type = self.coin_type.load(Ordering::Relaxed);
+        let packages = bundle
+            .account_indexes()?
+            .into_iter()
+            .map(|account_index| bundle.account_package(account_index))
+            .collect::<Vec<_>>();
+
+        let mut storage_manager = self.storage_manager.lock().await;
+        let archived_account_indexes = storage_manager.archived_account_indexes().await?;
+        let mut collisions = Vec::new();
+        for package in &packages {
+            let account = package.account()?;
+            if *account.coin_type() != coin_type && !package.has_client_options() {
+                return Err(crate::Error::InvalidCoinType {
+                    new_coin_type: *account.coin_type(),
+                    existing_coin_type: coin_type,
+                });
+            }
+            if storage_manager.account_indexes.contains(account.index())
+                || archived_account_indexes.contains(account.index())
+            {
+                collisions.push(format!("index {}", account.index()));
+            }
+            if storage_manager
+                .account_aliases
+                .contains_key(&account.alias().to_lowercase())
+            {
+                collisions.push(format!("alias {}", account.alias()));
+            }
+        }
+        if !collisions.is_empty() {
+            return Err(crate::Error::RestoreCollision(collisions.join(", ")));
+        }
+
+        let imported_accounts = storage_manager
+            .import_account_packages(packages, seed_fingerprint.as_ref())
+            .await?;
+        drop(storage_manager);
+
+        for account in imported_accounts {
+            self.insert_imported_account(accounts, account).await?;
+        }
+        Ok(())
+    }
+}
+
+// Creates an encrypted portable backup, shared with the scheduled backups
+pub(crate) async fn create_portable_backup(
+    accounts: &RwLock<Vec<AccountHandle>>,
+    storage_manager: &StorageManagerHandle,
+    password: &str,
+    options: &PortableBackupOptions,
+) -> crate::Result<Vec<u8>> {
+    log::debug!("[backup_portable]");
+    // Save first, so the backup contains the current state
+    for account in accounts.read().await.iter() {
+        account.save(None).await?;
+    }
+
+    let storage_manager = storage_manager.lock().await;
+    let mut bundle = storage_manager.export_bundle().await?;
+    if let Some(account_indexes) = &options.account_indexes {
+        bundle.retain_accounts(account_indexes)?;
+    }
+    // Allows to check on restore that the secret manager has the seed of the accounts
+    if let Some(seed_fingerprint) = storage_manager.get_seed_fingerprint().await? {
+        bundle.records.insert(
+            SEED_FINGERPRINT_KEY.to_string(),
+            serde_json::to_value(seed_fingerprint)?,
+        );
+    }
+    drop(storage_manager);
+
+    PortableBackup {
+        version: PORTABLE_BACKUP_VERSION,
+        created_at: SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .expect("time went backwards")
+            .as_millis() as u64,
+        bundle,
+    }
+    .encrypt(password)
+}
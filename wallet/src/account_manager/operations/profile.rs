@@ -0,0 +1,45 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::account_manager::{
+    builder::{AccountManagerBuilder, Profile},
+    AccountManager,
+};
+
+impl AccountManager {
+    /// Returns a builder for the account manager of a named profile, which has its own secret manager, coin type and
+    /// accounts, but stores them in the storage of this account manager. The secret manager and the coin type need to
+    /// be provided when the profile is created, the client options of this account manager are used if none are
+    /// provided. Afterwards the profile can be loaded again like an account manager from its storage.
+    /// ```ignore
+    /// let savings = manager
+    ///     .profile("savings")
+    ///     .await?
+    ///     .with_secret_manager(secret_manager)
+    ///     .with_coin_type(SHIMMER_COIN_TYPE)
+    ///     .finish()
+    ///     .await?;
+    /// let account = savings.create_account().finish().await?;
+    /// ```
+    pub async fn profile(&self, name: &str) -> crate::Result<AccountManagerBuilder> {
+        log::debug!("[profile] {name}");
+        // The name is part of the storage keys, so it can't contain the separator
+        if name.is_empty() || name.contains(':') {
+            return Err(crate::Error::InvalidProfileName(name.to_string()));
+        }
+
+        Ok(AccountManagerBuilder::new().with_profile(
+            Profile {
+                name: name.to_string(),
+                storage_manager: self.storage_manager.clone(),
+                client_options: self.client_options.read().await.clone(),
+            },
+            self.storage_options.clone(),
+        ))
+    }
+
+    /// Returns the names of all profiles that were created in the storage of this account manager.
+    pub async fn get_profile_names(&self) -> crate::Result<Vec<String>> {
+        self.storage_manager.lock().await.get_profile_names().await
+    }
+}
@@ -0,0 +1,188 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    sync::{atomic::Ordering, Arc},
+    time::Duration,
+};
+
+use instant::SystemTime;
+use tokio::{sync::RwLock, time::sleep};
+use zeroize::Zeroizing;
+
+#[cfg(feature = "events")]
+use crate::events::types::{BackupEvent, WalletEvent};
+use crate::{
+    account::handle::AccountHandle,
+    account_manager::{
+        operations::portable_backup::{create_portable_backup, PortableBackupOptions},
+        AccountManager,
+    },
+    storage::{audit_log::AuditOperation, backup_target::BackupTarget, manager::StorageManagerHandle},
+};
+
+/// The default interval between scheduled backups
+pub(crate) const DEFAULT_SCHEDULED_BACKUP_INTERVAL: Duration = Duration::from_secs(60 * 60 * 24);
+/// The default amount of scheduled backups that are kept
+pub(crate) const DEFAULT_SCHEDULED_BACKUP_RETENTION: usize = 7;
+// Prefix of the names of scheduled backups, followed by the stored id of the wallet. Other backups in the target,
+// including the ones of other wallets that share it, are never removed
+const SCHEDULED_BACKUP_PREFIX: &str = "iota-wallet-backup-";
+
+impl AccountManager {
+    /// Start a background process that writes a portable backup of all accounts, encrypted with the password, to the
+    /// target every `interval` and removes the oldest scheduled backups, so only the last `retention` are kept. The
+    /// first backup is created immediately, default interval is 24 hours and default retention is 7 backups. The
+    /// names contain an id that is stored with the wallet and only its own backups are removed, also the ones of
+    /// previous runs, so multiple wallets can share a target. The backups can be restored with [`AccountManager::restore_portable_backup_bytes()`].
+    /// With the `events` feature a [`WalletEvent::Backup`](crate::events::types::WalletEvent::Backup) is emitted
+    /// after every backup.
+    pub async fn start_scheduled_backups(
+        &self,
+        target: impl BackupTarget + 'static,
+        password: String,
+        interval: Option<Duration>,
+        retention: Option<usize>,
+    ) -> crate::Result<()> {
+        log::debug!("[start_scheduled_backups]");
+        // stop existing process if running
+        self.stop_scheduled_backups().await?;
+
+        let prefix = format!(
+            "{SCHEDULED_BACKUP_PREFIX}{}-",
+            self.storage_manager.lock().await.get_or_create_wallet_id().await?
+        );
+        let scheduled_backups_status = self.scheduled_backups_status.clone();
+        scheduled_backups_status.store(1, Ordering::Relaxed);
+        let accounts = self.accounts.clone();
+        let storage_manager = self.storage_manager.clone();
+        #[cfg(feature = "events")]
+        let event_emitter = self.event_emitter.clone();
+        let password = Zeroizing::new(password);
+        // At least the latest backup is kept
+        let retention = retention.unwrap_or(DEFAULT_SCHEDULED_BACKUP_RETENTION).max(1);
+        let _scheduled_backups = std::thread::spawn(move || {
+            #[cfg(not(target_family = "wasm"))]
+            let runtime = tokio::runtime::Builder::new_multi_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            #[cfg(target_family = "wasm")]
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            runtime.block_on(async {
+                'outer: loop {
+                    let name = format!(
+                        "{prefix}{:020}.json",
+                        SystemTime::now()
+                            .duration_since(SystemTime::UNIX_EPOCH)
+                            .expect("time went backwards")
+                            .as_millis()
+                    );
+                    log::debug!("[scheduled_backups]: creating {}", name);
+                    let result = create_scheduled_backup(
+                        &accounts,
+                        &storage_manager,
+                        &target,
+                        &password,
+                        &prefix,
+                        &name,
+                        retention,
+                    )
+                    .await;
+                    if let Err(err) = &result {
+                        log::debug!("[scheduled_backups] error: {}", err);
+                    }
+                    if let Err(err) = storage_manager
+                        .lock()
+                        .await
+                        .audit(None, AuditOperation::PortableBackup, &name, &result)
+                        .await
+                    {
+                        log::debug!("[scheduled_backups] error: {}", err);
+                    }
+                    #[cfg(feature = "events")]
+                    event_emitter.lock().await.emit(
+                        0,
+                        WalletEvent::Backup(match result {
+                            Ok(removed) => BackupEvent {
+                                name,
+                                removed,
+                                error: None,
+                            },
+                            Err(err) => BackupEvent {
+                                name,
+                                removed: Vec::new(),
+                                error: Some(err.to_string()),
+                            },
+                        }),
+                    );
+                    // split interval to seconds so stopping the process doesn't have to wait long
+                    let seconds = interval.unwrap_or(DEFAULT_SCHEDULED_BACKUP_INTERVAL).as_secs();
+                    for _ in 0..seconds {
+                        if scheduled_backups_status.load(Ordering::Relaxed) == 2 {
+                            break 'outer;
+                        }
+                        sleep(Duration::from_secs(1)).await;
+                    }
+                    if scheduled_backups_status.load(Ordering::Relaxed) == 2 {
+                        break;
+                    }
+                }
+                scheduled_backups_status.store(0, Ordering::Relaxed);
+                log::debug!("[scheduled_backups]: stopped");
+            });
+        });
+        Ok(())
+    }
+
+    /// Stop the scheduled backups
+    pub async fn stop_scheduled_backups(&self) -> crate::Result<()> {
+        log::debug!("[stop_scheduled_backups]");
+        // immediately return if not running
+        if self.scheduled_backups_status.load(Ordering::Relaxed) == 0 {
+            return Ok(());
+        }
+        // send stop request
+        self.scheduled_backups_status.store(2, Ordering::Relaxed);
+        // wait until it stopped
+        while self.scheduled_backups_status.load(Ordering::Relaxed) != 0 {
+            #[cfg(target_family = "wasm")]
+            gloo_timers::future::TimeoutFuture::new(10).await;
+            #[cfg(not(target_family = "wasm"))]
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        Ok(())
+    }
+}
+
+// Writes a backup to the target and removes the oldest scheduled backups with the prefix, returns the names of the
+// removed backups
+async fn create_scheduled_backup(
+    accounts: &Arc<RwLock<Vec<AccountHandle>>>,
+    storage_manager: &StorageManagerHandle,
+    target: &impl BackupTarget,
+    password: &str,
+    prefix: &str,
+    name: &str,
+    retention: usize,
+) -> crate::Result<Vec<String>> {
+    let backup = create_portable_backup(accounts, storage_manager, password, &PortableBackupOptions::default()).await?;
+    target.put(name, backup).await?;
+
+    let mut names = target
+        .list()
+        .await?
+        .into_iter()
+        .filter(|name| name.starts_with(prefix))
+        .collect::<Vec<_>>();
+    // The timestamps in the names have a fixed width, so they are sorted from the oldest to the latest
+    names.sort();
+    let removed = names[..names.len().saturating_sub(retention)].to_vec();
+    for name in &removed {
+        target.remove(name).await?;
+    }
+    Ok(removed)
+}
@@ -0,0 +1,247 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use instant::SystemTime;
+use iota_client::block::{address::Address, DtoError};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "events")]
+use crate::events::types::{ScheduledPaymentEvent, WalletEvent};
+use crate::{
+    account::handle::AccountHandle, account_manager::AccountManager, storage::scheduled_payments::ScheduledPayment,
+    AddressWithAmount,
+};
+
+/// The delay before a scheduled payment that failed is executed again
+pub(crate) const SCHEDULED_PAYMENT_RETRY_DELAY: Duration = Duration::from_secs(60 * 10);
+
+/// Options to schedule a payment with [`AccountManager::schedule_payment()`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchedulePaymentOptions {
+    /// Bech32 encoded address of the recipient.
+    pub address: String,
+    /// The amount that is sent with every execution.
+    pub amount: u64,
+    /// Unix timestamp in milliseconds of the first execution, the payment is due immediately if not set.
+    #[serde(default)]
+    pub start_time: Option<u64>,
+    /// The time between two executions in milliseconds, the payment is only executed once if not set.
+    #[serde(default)]
+    pub interval_in_milliseconds: Option<u64>,
+    /// Unix timestamp in milliseconds after which the payment isn't executed anymore.
+    #[serde(default)]
+    pub expires_at: Option<u64>,
+}
+
+impl AccountManager {
+    /// Schedule a future-dated or recurring payment of an account. Due payments are sent by the background syncing
+    /// after the account was synced, with the `events` feature a
+    /// [`ScheduledPayment`](crate::events::types::WalletEvent::ScheduledPayment) event is emitted for every execution.
+    /// A failed payment is retried after 10 minutes, executions of a recurring payment that were missed while the
+    /// background syncing wasn't running are skipped. An execution is stored before the payment is sent, so a payment
+    /// isn't sent twice if the wallet stops while sending. Scheduled payments are stored with the account.
+    pub async fn schedule_payment(
+        &self,
+        account_index: u32,
+        options: SchedulePaymentOptions,
+    ) -> crate::Result<ScheduledPayment> {
+        log::debug!("[schedule_payment] {account_index} {options:?}");
+        self.get_account(account_index).await?;
+        Address::try_from_bech32(&options.address)?;
+        if options.interval_in_milliseconds == Some(0) {
+            return Err(DtoError::InvalidField("intervalInMilliseconds").into());
+        }
+
+        let mut storage_manager = self.storage_manager.lock().await;
+        let mut scheduled_payments = storage_manager.get_scheduled_payments(account_index).await?;
+        let scheduled_payment = ScheduledPayment {
+            id: scheduled_payments
+                .iter()
+                .map(|scheduled_payment| scheduled_payment.id + 1)
+                .max()
+                .unwrap_or_default(),
+            address: options.address,
+            amount: options.amount,
+            next_execution: options.start_time.unwrap_or_else(now),
+            interval_in_milliseconds: options.interval_in_milliseconds,
+            expires_at: options.expires_at,
+            executions: 0,
+            last_error: None,
+        };
+        scheduled_payments.push(scheduled_payment.clone());
+        storage_manager
+            .save_scheduled_payments(account_index, &scheduled_payments)
+            .await?;
+        Ok(scheduled_payment)
+    }
+
+    /// Returns the scheduled payments of an account that weren't completed, cancelled or expired yet.
+    pub async fn scheduled_payments(&self, account_index: u32) -> crate::Result<Vec<ScheduledPayment>> {
+        self.get_account(account_index).await?;
+        self.storage_manager
+            .lock()
+            .await
+            .get_scheduled_payments(account_index)
+            .await
+    }
+
+    /// Cancel a scheduled payment, so it isn't executed anymore.
+    pub async fn cancel_scheduled_payment(&self, account_index: u32, id: u32) -> crate::Result<()> {
+        log::debug!("[cancel_scheduled_payment] {account_index} {id}");
+        self.get_account(account_index).await?;
+        let mut storage_manager = self.storage_manager.lock().await;
+        let mut scheduled_payments = storage_manager.get_scheduled_payments(account_index).await?;
+        let count = scheduled_payments.len();
+        scheduled_payments.retain(|scheduled_payment| scheduled_payment.id != id);
+        if scheduled_payments.len() == count {
+            return Err(crate::Error::ScheduledPaymentNotFound(id));
+        }
+        storage_manager
+            .save_scheduled_payments(account_index, &scheduled_payments)
+            .await
+    }
+}
+
+// Send the due scheduled payments of the account, called by the background syncing after the account was synced
+pub(crate) async fn execute_scheduled_payments(account: &AccountHandle) -> crate::Result<()> {
+    let account_index = *account.read().await.index();
+    let due_payment_ids: Vec<u32> = account
+        .storage_manager
+        .lock()
+        .await
+        .get_scheduled_payments(account_index)
+        .await?
+        .into_iter()
+        .filter(|scheduled_payment| scheduled_payment.next_execution <= now())
+        .map(|scheduled_payment| scheduled_payment.id)
+        .collect();
+
+    for id in due_payment_ids {
+        // The execution is stored before the payment is sent, so it isn't sent again if the wallet stops before the
+        // result is stored or the payments are executed concurrently
+        let Some(scheduled_payment) = claim_scheduled_payment(account, account_index, id).await? else {
+            continue;
+        };
+
+        log::debug!("[scheduled_payments] executing {scheduled_payment:?}");
+        let result = account
+            .send_amount(
+                vec![AddressWithAmount {
+                    address: scheduled_payment.address.clone(),
+                    amount: scheduled_payment.amount,
+                }],
+                None,
+            )
+            .await;
+        #[cfg(feature = "events")]
+        let event = ScheduledPaymentEvent {
+            id: scheduled_payment.id,
+            transaction_id: result.as_ref().ok().map(|transaction| transaction.transaction_id),
+            error: result.as_ref().err().map(|error| error.to_string()),
+        };
+
+        if let Err(error) = result {
+            log::debug!("[scheduled_payments] payment {} failed: {error}", scheduled_payment.id);
+            retry_scheduled_payment(account, account_index, scheduled_payment, error.to_string()).await?;
+        }
+
+        #[cfg(feature = "events")]
+        account
+            .event_emitter
+            .lock()
+            .await
+            .emit(account_index, WalletEvent::ScheduledPayment(event));
+    }
+    Ok(())
+}
+
+// Advances a due payment to its next execution and stores it, completed and expired payments are removed. Returns the
+// payment as it was before, or `None` if it isn't due anymore or expired.
+async fn claim_scheduled_payment(
+    account: &AccountHandle,
+    account_index: u32,
+    id: u32,
+) -> crate::Result<Option<ScheduledPayment>> {
+    let is_expired = |scheduled_payment: &ScheduledPayment| {
+        scheduled_payment
+            .expires_at
+            .map_or(false, |expires_at| scheduled_payment.next_execution > expires_at)
+    };
+
+    let mut storage_manager = account.storage_manager.lock().await;
+    let mut scheduled_payments = storage_manager.get_scheduled_payments(account_index).await?;
+    let Some(position) = scheduled_payments
+        .iter()
+        .position(|scheduled_payment| scheduled_payment.id == id && scheduled_payment.next_execution <= now())
+    else {
+        return Ok(None);
+    };
+
+    let scheduled_payment = scheduled_payments[position].clone();
+    let due = !is_expired(&scheduled_payment);
+    let mut executed_payment = scheduled_payment.clone();
+    executed_payment.executions += 1;
+    executed_payment.last_error = None;
+    let completed = !due
+        || match executed_payment.interval_in_milliseconds {
+            Some(interval) => {
+                executed_payment.next_execution = next_execution(executed_payment.next_execution, interval);
+                is_expired(&executed_payment)
+            }
+            None => true,
+        };
+    if completed {
+        scheduled_payments.remove(position);
+    } else {
+        scheduled_payments[position] = executed_payment;
+    }
+    storage_manager
+        .save_scheduled_payments(account_index, &scheduled_payments)
+        .await?;
+
+    Ok(due.then_some(scheduled_payment))
+}
+
+// Stores a payment that failed as it was before it was claimed, so it's executed again after the retry delay
+async fn retry_scheduled_payment(
+    account: &AccountHandle,
+    account_index: u32,
+    mut scheduled_payment: ScheduledPayment,
+    error: String,
+) -> crate::Result<()> {
+    scheduled_payment.last_error = Some(error);
+    scheduled_payment.next_execution = now() + SCHEDULED_PAYMENT_RETRY_DELAY.as_millis() as u64;
+
+    let mut storage_manager = account.storage_manager.lock().await;
+    let mut scheduled_payments = storage_manager.get_scheduled_payments(account_index).await?;
+    match scheduled_payments
+        .iter_mut()
+        .find(|stored_payment| stored_payment.id == scheduled_payment.id)
+    {
+        Some(stored_payment) => *stored_payment = scheduled_payment,
+        // A completed payment was removed when it was claimed
+        None => {
+            scheduled_payments.push(scheduled_payment);
+            scheduled_payments.sort_by_key(|scheduled_payment| scheduled_payment.id);
+        }
+    }
+    storage_manager
+        .save_scheduled_payments(account_index, &scheduled_payments)
+        .await
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("time went backwards")
+        .as_millis() as u64
+}
+
+// The first execution after now, executions that were missed are skipped
+fn next_execution(last_execution: u64, interval: u64) -> u64 {
+    let missed_intervals = now().saturating_sub(last_execution) / interval;
+    last_execution + (missed_intervals + 1) * interval
+}
@@ -0,0 +1,64 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_client::secret::SecretManager;
+
+use crate::account_manager::{operations::derivation_verification::find_derivation_mismatches, AccountManager};
+
+impl AccountManager {
+    /// Replaces the current secret manager with another one that holds the same seed, for example to move from a
+    /// mnemonic to a Stronghold snapshot or from a Stronghold snapshot to a Ledger Nano. All addresses of all accounts
+    /// are derived with the new secret manager first, nothing is changed if any of them differs. With storage the new
+    /// secret manager is also stored, so it's loaded again if no secret manager is provided to the builder, and a
+    /// stored encrypted mnemonic is removed.
+    pub async fn migrate_secret_manager(&self, secret_manager: SecretManager) -> crate::Result<()> {
+        log::debug!("[migrate_secret_manager]");
+        let accounts = self.accounts.read().await;
+        for account_handle in accounts.iter() {
+            let account = account_handle.read().await;
+            // The addresses of watch-only accounts don't belong to the seed
+            if *account.watch_only() {
+                continue;
+            }
+            for (addresses, internal) in [
+                (account.public_addresses(), false),
+                (account.internal_addresses(), true),
+            ] {
+                if let Some(mismatch) = find_derivation_mismatches(
+                    &secret_manager,
+                    #[cfg(feature = "ledger_nano")]
+                    &self.ledger_nano_queue,
+                    *account.coin_type(),
+                    *account.index(),
+                    addresses,
+                    internal,
+                )
+                .await?
+                .into_iter()
+                .next()
+                {
+                    return Err(crate::Error::SecretManagerMigration {
+                        account_index: mismatch.account_index,
+                        address: mismatch.stored_address,
+                    });
+                }
+            }
+        }
+
+        // Hold the lock while replacing, so nothing can be signed with the previous secret manager in between
+        let mut current_secret_manager = self.secret_manager.write().await;
+        #[cfg(feature = "storage")]
+        {
+            let mut storage_manager = self.storage_manager.lock().await;
+            storage_manager.save_secret_manager(&secret_manager).await?;
+            // Without accounts nothing was verified, so the seed might be another one
+            if accounts.is_empty() {
+                storage_manager.remove_seed_fingerprint().await?;
+                storage_manager.clear_derivation_cache().await?;
+            }
+        }
+        *current_secret_manager = secret_manager;
+
+        Ok(())
+    }
+}
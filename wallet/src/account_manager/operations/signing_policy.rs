@@ -0,0 +1,83 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::future::Future;
+
+use crate::{
+    account::{DustPolicy, SigningPolicy, SigningRequest, TransactionApproval, TransactionApprovalRequest},
+    account_manager::AccountManager,
+};
+
+impl AccountManager {
+    /// Sets the policy every transaction of all accounts needs to comply with before it gets signed, independent of
+    /// the secret manager. Transactions that don't comply fail with [`crate::Error::SigningPolicyViolation`].
+    pub async fn set_signing_policy(&self, signing_policy: SigningPolicy) {
+        self.signing_guard.write().await.policy = signing_policy;
+    }
+
+    /// Returns the current signing policy.
+    pub async fn get_signing_policy(&self) -> SigningPolicy {
+        self.signing_guard.read().await.policy.clone()
+    }
+
+    /// Sets the dust policy for the transactions of all accounts. Transactions that create dust are logged and emitted
+    /// as [`TransactionProgressEvent::DustWarning`](crate::events::types::TransactionProgressEvent::DustWarning), or
+    /// fail with [`crate::Error::DustPolicyViolation`] if the policy blocks them.
+    pub async fn set_dust_policy(&self, dust_policy: DustPolicy) {
+        self.signing_guard.write().await.dust_policy = dust_policy;
+    }
+
+    /// Returns the current dust policy.
+    pub async fn get_dust_policy(&self) -> DustPolicy {
+        self.signing_guard.read().await.dust_policy.clone()
+    }
+
+    /// Registers a callback that needs to confirm every transaction before it gets signed, the transaction is rejected
+    /// if any callback returns false.
+    /// ```ignore
+    /// account_manager
+    ///     .add_signing_confirmation(|request| request.amount < 1_000_000 || ask_operator(request))
+    ///     .await;
+    /// ```
+    pub async fn add_signing_confirmation<F>(&self, confirmation: F)
+    where
+        F: Fn(&SigningRequest<'_>) -> bool + Send + Sync + 'static,
+    {
+        self.signing_guard.write().await.add_confirmation(confirmation);
+    }
+
+    /// Removes all registered signing confirmation callbacks.
+    pub async fn clear_signing_confirmations(&self) {
+        self.signing_guard.write().await.clear_confirmations();
+    }
+
+    /// Sets the approver that needs to approve every transaction of all accounts before it gets signed, after it
+    /// complied with the signing policy and the confirmation callbacks. The approver receives the prepared essence and
+    /// can wait for a second factor, a policy engine or a manual review. A rejected transaction fails with
+    /// [`crate::Error::TransactionRejected`].
+    /// ```ignore
+    /// account_manager
+    ///     .set_transaction_approver(move |request| {
+    ///         let review_queue = review_queue.clone();
+    ///         async move {
+    ///             match review_queue.submit(request).await {
+    ///                 true => TransactionApproval::Approved,
+    ///                 false => TransactionApproval::Rejected("declined by the reviewer".to_string()),
+    ///             }
+    ///         }
+    ///     })
+    ///     .await;
+    /// ```
+    pub async fn set_transaction_approver<F, Fut>(&self, approver: F)
+    where
+        F: Fn(TransactionApprovalRequest) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = TransactionApproval> + Send + 'static,
+    {
+        self.signing_guard.write().await.set_approver(approver);
+    }
+
+    /// Removes the transaction approver, transactions only need to comply with the signing policy again.
+    pub async fn clear_transaction_approver(&self) {
+        self.signing_guard.write().await.clear_approver();
+    }
+}
@@ -0,0 +1,59 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::HashMap, ops::Range};
+
+use iota_client::block::address::Address;
+
+use crate::{
+    account::{
+        operations::statistics::top_counterparts,
+        types::{AccountStatistics, CounterpartStatistics},
+    },
+    account_manager::AccountManager,
+};
+
+impl AccountManager {
+    /// Returns the summed statistics of all accounts in the time range, as unix timestamps in milliseconds.
+    /// Transfers between the accounts with [`AccountManager::internal_transfer()`] aren't counted as sent or received,
+    /// only as [`TransactionKind::InternalTransfer`](crate::account::types::TransactionKind::InternalTransfer).
+    pub async fn statistics(&self, period: Range<u64>) -> crate::Result<AccountStatistics> {
+        let mut total_statistics = AccountStatistics::default();
+        let mut total_counterparts: HashMap<Address, CounterpartStatistics> = HashMap::new();
+        let mut bech32_hrp = None;
+
+        for account_handle in self.accounts.read().await.iter() {
+            let (statistics, counterparts) = account_handle
+                .statistics_with_counterparts(period.clone(), true)
+                .await?;
+            if bech32_hrp.is_none() {
+                bech32_hrp = account_handle
+                    .read()
+                    .await
+                    .public_addresses()
+                    .first()
+                    .map(|address| address.address().bech32_hrp().to_string());
+            }
+
+            total_statistics.sent += statistics.sent;
+            total_statistics.received += statistics.received;
+            total_statistics.sent_transactions += statistics.sent_transactions;
+            total_statistics.received_transactions += statistics.received_transactions;
+            for (transaction_kind, count) in statistics.transaction_kinds {
+                *total_statistics.transaction_kinds.entry(transaction_kind).or_default() += count;
+            }
+            for (address, counterpart) in counterparts {
+                let total_counterpart = total_counterparts.entry(address).or_default();
+                total_counterpart.sent += counterpart.sent;
+                total_counterpart.received += counterpart.received;
+                total_counterpart.transaction_count += counterpart.transaction_count;
+            }
+        }
+
+        if let Some(bech32_hrp) = bech32_hrp {
+            total_statistics.top_counterparts = top_counterparts(total_counterparts, &bech32_hrp);
+        }
+
+        Ok(total_statistics)
+    }
+}
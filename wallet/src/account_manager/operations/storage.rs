@@ -0,0 +1,247 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{path::Path, sync::atomic::Ordering, time::Duration};
+
+use crate::{
+    account::{builder::get_first_public_address, handle::AccountHandle, Account},
+    account_manager::AccountManager,
+    storage::{
+        audit_log::{AuditLogEntry, AuditLogFilter},
+        hooks::StorageEvent,
+        maintenance::DiskUsage,
+        manager::StorageManager,
+        quota::StorageQuota,
+    },
+};
+
+impl AccountManager {
+    /// Registers a hook that gets called after every set or remove on the storage with the key, the size of the
+    /// written record and the duration of the operation. Can be used for external replication, audit logging or
+    /// cache invalidation. The hooks run after the storage lock is released, so they can use the account manager.
+    /// ```ignore
+    /// account_manager
+    ///     .add_storage_hook(|event| println!("{:?} {} ({} bytes)", event.operation, event.key, event.size))
+    ///     .await;
+    /// ```
+    pub async fn add_storage_hook<F>(&self, hook: F)
+    where
+        F: Fn(&StorageEvent) + Send + Sync + 'static,
+    {
+        self.storage_manager.lock().await.add_hook(hook);
+    }
+
+    /// Removes all registered storage hooks.
+    pub async fn clear_storage_hooks(&self) {
+        self.storage_manager.lock().await.clear_hooks();
+    }
+
+    /// Returns the on-disk size of the storage and the size of the stored records per domain.
+    pub async fn storage_disk_usage(&self) -> crate::Result<DiskUsage> {
+        self.storage_manager.lock().await.disk_usage().await
+    }
+
+    /// Removes the addresses that were cached to not derive them again. Only needed if the secret manager was replaced
+    /// outside of the account manager, restoring a backup clears them itself.
+    pub async fn clear_derivation_cache(&self) -> crate::Result<()> {
+        self.storage_manager.lock().await.clear_derivation_cache().await
+    }
+
+    /// Compacts the storage to reclaim the space of removed records.
+    pub async fn compact_storage(&self) -> crate::Result<()> {
+        self.storage_manager.lock().await.compact().await
+    }
+
+    /// Returns the keys of the storage records that [`AccountManager::remove_latest_account()`] would remove, without
+    /// removing anything.
+    pub async fn remove_latest_account_dry_run(&self) -> crate::Result<Vec<String>> {
+        let mut largest_account_index = None;
+        for account in self.accounts.read().await.iter() {
+            largest_account_index = largest_account_index.max(Some(*account.read().await.index()));
+        }
+
+        match largest_account_index {
+            Some(account_index) => {
+                self.storage_manager
+                    .lock()
+                    .await
+                    .remove_account_records(account_index, true)
+                    .await
+            }
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Sets limits for the storage, writes that would exceed them return [`crate::Error::StorageQuotaExceeded`].
+    pub async fn set_storage_quota(&self, quota: StorageQuota) -> crate::Result<()> {
+        self.storage_manager.lock().await.set_quota(quota).await
+    }
+
+    /// Returns the current storage limits.
+    pub async fn get_storage_quota(&self) -> StorageQuota {
+        self.storage_manager.lock().await.quota()
+    }
+
+    /// Exports all accounts and their records, but not the secret manager, as JSON file with a stable key order. If a
+    /// password is provided, the file content will be encrypted with it.
+    pub async fn export_storage_json(&self, path: impl AsRef<Path>, password: Option<&str>) -> crate::Result<()> {
+        self.storage_manager.lock().await.export_json(path, password).await
+    }
+
+    /// Imports the accounts from a file created with [`AccountManager::export_storage_json()`]. Returns an error if
+    /// accounts were already created.
+    pub async fn import_storage_json(&self, path: impl AsRef<Path>, password: Option<&str>) -> crate::Result<()> {
+        log::debug!("[import_storage_json]");
+        let mut accounts = self.accounts.write().await;
+        // We don't want to overwrite possible existing accounts
+        if !accounts.is_empty() {
+            return Err(crate::Error::Backup(
+                "can't import storage when there are already accounts",
+            ));
+        }
+
+        self.storage_manager.lock().await.import_json(path, password).await?;
+        self.load_imported_accounts(&mut accounts).await
+    }
+
+    // Creates the account handles for the accounts that were written to the storage by an import
+    pub(crate) async fn load_imported_accounts(&self, accounts: &mut Vec<AccountHandle>) -> crate::Result<()> {
+        let imported_accounts = self.storage_manager.lock().await.get_accounts().await?;
+
+        for account in imported_accounts {
+            let (client, account_client_options) = self.stored_account_client(*account.index()).await?;
+            accounts.push(
+                AccountHandle::new(
+                    account,
+                    client,
+                    self.secret_manager.clone(),
+                    self.signing_guard.clone(),
+                    self.request_limiter.clone(),
+                    #[cfg(feature = "ledger_nano")]
+                    self.ledger_nano_queue.clone(),
+                    #[cfg(feature = "events")]
+                    self.event_emitter.clone(),
+                    self.storage_manager.clone(),
+                )
+                .with_client_options(account_client_options),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Imports an account from a file created with [`AccountHandle::export()`]. Fails if the account has another coin
+    /// type without own client options, wasn't created with the seed of this account manager, or if an account with
+    /// the same index or alias exists.
+    pub async fn import_account(&self, path: impl AsRef<Path>, password: &str) -> crate::Result<AccountHandle> {
+        log::debug!("[import_account]");
+        let package = StorageManager::read_account_package(path, password)?;
+        let account = package.account()?;
+
+        let coin_type = self.coin_type.load(Ordering::Relaxed);
+        if *account.coin_type() != coin_type && !package.has_client_options() {
+            return Err(crate::Error::InvalidCoinType {
+                new_coin_type: *account.coin_type(),
+                existing_coin_type: coin_type,
+            });
+        }
+        self.verify_account_seed(&account).await?;
+
+        let mut accounts = self.accounts.write().await;
+        for account_handle in accounts.iter() {
+            if account_handle.read().await.alias().to_lowercase() == account.alias().to_lowercase() {
+                return Err(crate::Error::AccountAliasAlreadyExists(account.alias().to_string()));
+            }
+        }
+        let account = self
+            .storage_manager
+            .lock()
+            .await
+            .import_account_package(package)
+            .await?;
+
+        self.insert_imported_account(&mut accounts, account).await
+    }
+
+    // Checks that an imported account was created with the seed of the secret manager
+    pub(crate) async fn verify_account_seed(&self, account: &Account) -> crate::Result<()> {
+        // Watch-only accounts have addresses of other seeds
+        if *account.watch_only() {
+            return Ok(());
+        }
+        let first_public_address = get_first_public_address(
+            &self.secret_manager,
+            #[cfg(feature = "ledger_nano")]
+            &self.ledger_nano_queue,
+            *account.coin_type(),
+            *account.index(),
+        )
+        .await?;
+        if account
+            .public_addresses()
+            .first()
+            .map(|address| address.address().inner)
+            != Some(first_public_address)
+        {
+            return Err(crate::Error::Backup(
+                "account wasn't created with the seed of the account manager",
+            ));
+        }
+        Ok(())
+    }
+
+    // Creates the handle for an account that was imported to the storage and inserts it sorted by the account index
+    pub(crate) async fn insert_imported_account(
+        &self,
+        accounts: &mut Vec<AccountHandle>,
+        account: Account,
+    ) -> crate::Result<AccountHandle> {
+        let account_index = *account.index();
+        let (client, account_client_options) = self.stored_account_client(account_index).await?;
+        let account_handle = AccountHandle::new(
+            account,
+            client,
+            self.secret_manager.clone(),
+            self.signing_guard.clone(),
+            self.request_limiter.clone(),
+            #[cfg(feature = "ledger_nano")]
+            self.ledger_nano_queue.clone(),
+            #[cfg(feature = "events")]
+            self.event_emitter.clone(),
+            self.storage_manager.clone(),
+        )
+        .with_client_options(account_client_options);
+
+        let position = {
+            let mut position = accounts.len();
+            for (i, existing_account_handle) in accounts.iter().enumerate() {
+                if *existing_account_handle.read().await.index() > account_index {
+                    position = i;
+                    break;
+                }
+            }
+            position
+        };
+        accounts.insert(position, account_handle.clone());
+
+        Ok(account_handle)
+    }
+
+    /// Sets how long the balance snapshots of the accounts are kept, older snapshots are removed when a new one is
+    /// recorded. Defaults to
+    /// [`DEFAULT_BALANCE_HISTORY_RETENTION`](crate::storage::balance_history::DEFAULT_BALANCE_HISTORY_RETENTION).
+    pub async fn set_balance_history_retention(&self, retention: Duration) -> crate::Result<()> {
+        self.storage_manager
+            .lock()
+            .await
+            .set_balance_history_retention(retention)
+            .await
+    }
+
+    /// Returns the entries of the audit log that match the filter, oldest first. Sends, address generation and
+    /// backups are recorded with a hash of their parameters and their result. The oldest entries are dropped once the
+    /// log holds more than 10000 entries.
+    pub async fn audit_log(&self, filter: &AuditLogFilter) -> crate::Result<Vec<AuditLogEntry>> {
+        self.storage_manager.lock().await.get_audit_log(filter).await
+    }
+}
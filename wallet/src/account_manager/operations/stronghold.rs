@@ -1,7 +1,7 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::time::Duration;
+use std::{fs, path::PathBuf, time::Duration};
 
 use iota_client::secret::SecretManager;
 
@@ -17,10 +17,33 @@ impl AccountManager {
     }
 
     /// Change the Stronghold password to another one and also re-encrypt the values in the loaded snapshot with it.
+    /// The key derived from the current password is replaced in memory. If re-encrypting fails, the snapshot file and
+    /// the current password are restored.
     pub async fn change_stronghold_password(&self, current_password: &str, new_password: &str) -> crate::Result<()> {
+        log::debug!("[change_stronghold_password]");
+        // Keep the write lock until the end, so the Stronghold can't be used while the password changes
         if let SecretManager::Stronghold(stronghold) = &mut *self.secret_manager.write().await {
             stronghold.set_password(current_password).await?;
-            stronghold.change_password(new_password).await?;
+
+            // Write the snapshot with the current password and keep a copy of it to roll back to
+            stronghold.write_stronghold_snapshot(None).await?;
+            let snapshot_path = stronghold.snapshot_path.clone();
+            let mut rollback_path = snapshot_path.clone().into_os_string();
+            rollback_path.push(".rollback");
+            let rollback_path = PathBuf::from(rollback_path);
+            fs::copy(&snapshot_path, &rollback_path)?;
+
+            if let Err(err) = stronghold.change_password(new_password).await {
+                log::debug!("[change_stronghold_password] rolling back: {err}");
+                fs::rename(&rollback_path, &snapshot_path)?;
+                stronghold.clear_key().await;
+                stronghold.set_password(current_password).await?;
+                stronghold.read_stronghold_snapshot().await?;
+                return Err(err.into());
+            }
+
+            fs::remove_file(&rollback_path)?;
+            self.rotate_stronghold_snapshot(&snapshot_path)?;
         }
         Ok(())
     }
@@ -37,6 +60,7 @@ impl AccountManager {
     pub async fn store_mnemonic(&self, mnemonic: String) -> crate::Result<()> {
         if let SecretManager::Stronghold(stronghold) = &mut *self.secret_manager.write().await {
             stronghold.store_mnemonic(mnemonic).await?;
+            self.rotate_stronghold_snapshot(&stronghold.snapshot_path)?;
         }
         Ok(())
     }
@@ -9,14 +9,37 @@ use iota_client::secret::{stronghold::StrongholdSecretManager, SecretManager, Se
 use zeroize::Zeroize;
 
 use self::stronghold_snapshot::{read_data_from_stronghold_snapshot, store_data_to_stronghold};
-#[cfg(feature = "storage")]
-use crate::account_manager::AccountManagerBuilder;
 use crate::account_manager::{AccountHandle, AccountManager};
+#[cfg(feature = "storage")]
+use crate::{
+    account_manager::AccountManagerBuilder,
+    storage::{audit_log::AuditOperation, seed_fingerprint::SeedFingerprint},
+};
 
 impl AccountManager {
     /// Backup the account manager data in a Stronghold file
     /// stronghold_password must be the current one when Stronghold is used as SecretManager.
-    pub async fn backup(&self, backup_path: PathBuf, mut stronghold_password: String) -> crate::Result<()> {
+    pub async fn backup(&self, backup_path: PathBuf, stronghold_password: String) -> crate::Result<()> {
+        #[cfg(feature = "storage")]
+        let parameters = backup_path.clone();
+
+        let result = self.backup_internal(backup_path, stronghold_password).await;
+
+        #[cfg(feature = "storage")]
+        if let Err(err) = self
+            .storage_manager
+            .lock()
+            .await
+            .audit(None, AuditOperation::Backup, &parameters, &result)
+            .await
+        {
+            log::debug!("[backup] audit log error: {}", err);
+        }
+
+        result
+    }
+
+    async fn backup_internal(&self, backup_path: PathBuf, mut stronghold_password: String) -> crate::Result<()> {
         log::debug!("[backup] creating a stronghold backup");
         let mut secret_manager = self.secret_manager.write().await;
 
@@ -59,6 +82,33 @@ impl AccountManager {
     /// if ignore_if_coin_type_mismatch == Some(true), client options coin type and accounts will not be restored if the
     /// cointype doesn't match
     pub async fn restore_backup(
+        &self,
+        backup_path: PathBuf,
+        stronghold_password: String,
+        ignore_if_coin_type_mismatch: Option<bool>,
+    ) -> crate::Result<()> {
+        #[cfg(feature = "storage")]
+        let parameters = (backup_path.clone(), ignore_if_coin_type_mismatch);
+
+        let result = self
+            .restore_backup_internal(backup_path, stronghold_password, ignore_if_coin_type_mismatch)
+            .await;
+
+        #[cfg(feature = "storage")]
+        if let Err(err) = self
+            .storage_manager
+            .lock()
+            .await
+            .audit(None, AuditOperation::RestoreBackup, &parameters, &result)
+            .await
+        {
+            log::debug!("[restore_backup] audit log error: {}", err);
+        }
+
+        result
+    }
+
+    async fn restore_backup_internal(
         &self,
         backup_path: PathBuf,
         mut stronghold_password: String,
@@ -120,6 +170,8 @@ impl AccountManager {
             }
         }
 
+        #[cfg(feature = "storage")]
+        let restored_secret_manager = read_secret_manager.is_some();
         if let Some(mut read_secret_manager) = read_secret_manager {
             // We have to replace the snapshot path with the current one, when building stronghold
             if let SecretManagerDto::Stronghold(stronghold_dto) = &mut read_secret_manager {
@@ -131,7 +183,8 @@ impl AccountManager {
 
             if let SecretManager::Stronghold(stronghold) = &mut restored_secret_manager {
                 // Copy Stronghold file so the seed is available in the new location
-                fs::copy(backup_path, new_snapshot_path)?;
+                fs::copy(backup_path, &new_snapshot_path)?;
+                self.rotate_stronghold_snapshot(&new_snapshot_path)?;
 
                 // Set password to restored secret manager
                 stronghold.set_password(&stronghold_password).await?;
@@ -151,6 +204,10 @@ impl AccountManager {
                         account,
                         client.clone(),
                         self.secret_manager.clone(),
+                        self.signing_guard.clone(),
+                        self.request_limiter.clone(),
+                        #[cfg(feature = "ledger_nano")]
+                        self.ledger_nano_queue.clone(),
                         #[cfg(feature = "events")]
                         self.event_emitter.clone(),
                         #[cfg(feature = "storage")]
@@ -184,9 +241,41 @@ impl AccountManager {
                 .await
                 .save_account_manager_data(&account_manager_builder)
                 .await?;
+            // Cached addresses were derived by the replaced secret manager
+            if restored_secret_manager {
+                self.storage_manager.lock().await.clear_derivation_cache().await?;
+            }
             // also save account to db
             for account in accounts.iter() {
                 account.save(None).await?;
+                // The address sync cursors of the replaced accounts would skip outputs of the restored accounts
+                if !ignore_backup_values {
+                    let account_index = *account.read().await.index();
+                    self.storage_manager
+                        .lock()
+                        .await
+                        .remove_address_sync_cursors(account_index)
+                        .await?;
+                }
+            }
+            // The stored seed fingerprint belongs to the replaced secret manager
+            if restored_secret_manager {
+                if accounts.is_empty() {
+                    self.storage_manager.lock().await.remove_seed_fingerprint().await?;
+                } else {
+                    let seed_fingerprint = SeedFingerprint::new(
+                        &self.secret_manager,
+                        #[cfg(feature = "ledger_nano")]
+                        &self.ledger_nano_queue,
+                        self.coin_type.load(Ordering::Relaxed),
+                    )
+                    .await?;
+                    self.storage_manager
+                        .lock()
+                        .await
+                        .save_seed_fingerprint(&seed_fingerprint)
+                        .await?;
+                }
             }
         }
 
@@ -0,0 +1,136 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::atomic::Ordering,
+    time::SystemTime,
+};
+
+use iota_client::secret::{stronghold::StrongholdSecretManager, SecretManager};
+use serde::{Deserialize, Serialize};
+
+use crate::account_manager::AccountManager;
+
+/// A timestamped copy of the Stronghold snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StrongholdSnapshotGeneration {
+    /// Unix timestamp in milliseconds when the copy was created.
+    pub timestamp: u128,
+    /// Path of the copy, next to the snapshot.
+    pub path: PathBuf,
+}
+
+impl AccountManager {
+    /// Sets how many timestamped copies of the Stronghold snapshot are kept, 0 disables them. A copy is created every
+    /// time the account manager writes the snapshot, the oldest copies are removed. The setting is stored, so it's
+    /// kept when the account manager is created again.
+    pub async fn set_stronghold_snapshot_generations(&self, generations: usize) -> crate::Result<()> {
+        #[cfg(feature = "storage")]
+        self.storage_manager
+            .lock()
+            .await
+            .set_stronghold_snapshot_generations(generations)
+            .await?;
+        self.stronghold_snapshot_generations
+            .store(generations, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Returns the copies of the Stronghold snapshot, newest first.
+    pub async fn stronghold_snapshot_generations(&self) -> crate::Result<Vec<StrongholdSnapshotGeneration>> {
+        match &*self.secret_manager.read().await {
+            SecretManager::Stronghold(stronghold) => snapshot_generations(&stronghold.snapshot_path),
+            _ => Err(iota_client::Error::SecretManagerMismatch.into()),
+        }
+    }
+
+    /// Replaces the Stronghold snapshot with the copy created at the timestamp and loads it. The password must be the
+    /// one that was used when the copy was created.
+    pub async fn restore_stronghold_snapshot_generation(&self, timestamp: u128, password: &str) -> crate::Result<()> {
+        log::debug!("[restore_stronghold_snapshot_generation] {timestamp}");
+        let mut secret_manager = self.secret_manager.write().await;
+        match &mut *secret_manager {
+            SecretManager::Stronghold(stronghold) => {
+                let generation = snapshot_generations(&stronghold.snapshot_path)?
+                    .into_iter()
+                    .find(|generation| generation.timestamp == timestamp)
+                    .ok_or(crate::Error::Backup("snapshot generation doesn't exist"))?;
+
+                // Make sure the copy can be loaded before it replaces the snapshot
+                StrongholdSecretManager::builder()
+                    .password(password)
+                    .build(generation.path.clone())?;
+
+                fs::copy(&generation.path, &stronghold.snapshot_path)?;
+                stronghold.clear_key().await;
+                stronghold.set_password(password).await?;
+                stronghold.read_stronghold_snapshot().await?;
+                Ok(())
+            }
+            _ => Err(iota_client::Error::SecretManagerMismatch.into()),
+        }
+    }
+
+    // Copies the snapshot that was just written and removes the oldest copies, if generations are enabled
+    pub(crate) fn rotate_stronghold_snapshot(&self, snapshot_path: &Path) -> crate::Result<()> {
+        let generations = self.stronghold_snapshot_generations.load(Ordering::Relaxed);
+        if generations == 0 || !snapshot_path.is_file() {
+            return Ok(());
+        }
+
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .expect("time went backwards")
+            .as_millis();
+        fs::copy(snapshot_path, generation_path(snapshot_path, timestamp))?;
+
+        for generation in snapshot_generations(snapshot_path)?.into_iter().skip(generations) {
+            log::debug!("[rotate_stronghold_snapshot] removing {:?}", generation.path);
+            fs::remove_file(generation.path)?;
+        }
+        Ok(())
+    }
+}
+
+fn generation_path(snapshot_path: &Path, timestamp: u128) -> PathBuf {
+    let mut path = snapshot_path.as_os_str().to_owned();
+    path.push(format!(".{timestamp}"));
+    PathBuf::from(path)
+}
+
+// The copies are stored next to the snapshot with the timestamp as additional extension
+fn snapshot_generations(snapshot_path: &Path) -> crate::Result<Vec<StrongholdSnapshotGeneration>> {
+    let (directory, file_name) = match (snapshot_path.parent(), snapshot_path.file_name()) {
+        (Some(directory), Some(file_name)) => (directory, file_name.to_string_lossy()),
+        _ => return Ok(Vec::new()),
+    };
+    let directory = if directory.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        directory
+    };
+    if !directory.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut generations = Vec::new();
+    for entry in fs::read_dir(directory)? {
+        let entry_name = entry?.file_name();
+        if let Some(timestamp) = entry_name
+            .to_string_lossy()
+            .strip_prefix(&format!("{file_name}."))
+            .and_then(|timestamp| timestamp.parse().ok())
+        {
+            generations.push(StrongholdSnapshotGeneration {
+                timestamp,
+                path: generation_path(snapshot_path, timestamp),
+            });
+        }
+    }
+    generations.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+
+    Ok(generations)
+}
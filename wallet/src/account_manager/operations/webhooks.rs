@@ -0,0 +1,256 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    fmt::{Debug, Formatter, Result},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use crypto::{macs::hmac::HMAC_SHA256, utils::rand};
+use futures::{
+    future::{select, Either},
+    StreamExt,
+};
+use instant::SystemTime;
+use iota_client::node_manager::node::Url;
+use serde::{Deserialize, Serialize};
+use tokio::time::{sleep, timeout};
+
+use crate::{
+    account_manager::AccountManager,
+    events::{
+        types::{Event, EventFilter},
+        EventStream, StreamEvent,
+    },
+    storage::{manager::StorageManagerHandle, webhook_dead_letters::WebhookDeadLetter},
+};
+
+/// The header with the hex encoded HMAC-SHA256 of the posted body, keyed with the secret of the webhook.
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "X-Wallet-Signature";
+// Amount of delivery attempts before a payload is stored as dead letter
+const WEBHOOK_MAX_ATTEMPTS: u32 = 5;
+// Delay before the first retry, doubled for every further retry
+const WEBHOOK_RETRY_DELAY: Duration = Duration::from_secs(1);
+const WEBHOOK_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+// Interval in which a delivery checks if the dispatcher is stopped, while it waits for a response or the next retry
+const WEBHOOK_STOP_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// An HTTPS endpoint to which the wallet events that match the filter are posted.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Webhook {
+    /// The url to which the events are posted, needs to use https.
+    pub url: String,
+    /// The secret with which the payloads are signed, see [`WEBHOOK_SIGNATURE_HEADER`].
+    pub secret: String,
+    /// The events that are posted.
+    #[serde(default)]
+    pub filter: EventFilter,
+}
+
+// Custom Debug implementation to not log the secret
+impl Debug for Webhook {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "Webhook{{ url: {}, secret: <omitted>, filter: {:?} }}",
+            self.url, self.filter
+        )
+    }
+}
+
+/// The JSON body that is posted to a webhook.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookPayload {
+    /// Random hex encoded id, the same for all delivery attempts, so receivers can ignore duplicates.
+    pub id: String,
+    /// Unix timestamp in milliseconds when the event was received by the dispatcher.
+    pub timestamp: u64,
+    /// The event.
+    pub event: Event,
+}
+
+impl WebhookPayload {
+    fn new(event: Event) -> crate::Result<Self> {
+        let mut id = [0u8; 16];
+        rand::fill(&mut id)?;
+        Ok(Self {
+            id: prefix_hex::encode(id),
+            timestamp: SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .expect("time went backwards")
+                .as_millis() as u64,
+            event,
+        })
+    }
+}
+
+impl AccountManager {
+    /// Start a background process that posts the events that match the filters of the webhooks as signed JSON
+    /// payloads. Failed deliveries are retried with an increasing delay, payloads that can't be delivered are stored
+    /// and can be read with [`AccountManager::webhook_dead_letters`]. A running dispatcher is replaced.
+    pub async fn start_webhook_dispatcher(&self, webhooks: Vec<Webhook>) -> crate::Result<()> {
+        log::debug!("[start_webhook_dispatcher]");
+        for webhook in &webhooks {
+            let url = Url::parse(&webhook.url)
+                .map_err(|e| crate::Error::InvalidWebhook(format!("invalid url {}: {e}", webhook.url)))?;
+            if url.scheme() != "https" {
+                return Err(crate::Error::InvalidWebhook(format!(
+                    "{} doesn't use https",
+                    webhook.url
+                )));
+            }
+            if webhook.secret.is_empty() {
+                return Err(crate::Error::InvalidWebhook(format!("no secret for {}", webhook.url)));
+            }
+        }
+        // stop existing process if running
+        self.stop_webhook_dispatcher().await?;
+
+        let mut subscriptions = Vec::new();
+        for webhook in webhooks {
+            let events = self.subscribe(webhook.filter.clone(), None).await;
+            subscriptions.push((webhook, events));
+        }
+        let webhook_dispatcher_status = self.webhook_dispatcher_status.clone();
+        webhook_dispatcher_status.store(1, Ordering::Relaxed);
+        let storage_manager = self.storage_manager.clone();
+        let _webhook_dispatcher = std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            runtime.block_on(async {
+                let client = reqwest::Client::new();
+                futures::future::join_all(subscriptions.into_iter().map(|(webhook, events)| {
+                    dispatch_events(&client, &storage_manager, &webhook_dispatcher_status, webhook, events)
+                }))
+                .await;
+                webhook_dispatcher_status.store(0, Ordering::Relaxed);
+                log::debug!("[webhook_dispatcher]: stopped");
+            });
+        });
+        Ok(())
+    }
+
+    /// Stop the webhook dispatcher
+    pub async fn stop_webhook_dispatcher(&self) -> crate::Result<()> {
+        log::debug!("[stop_webhook_dispatcher]");
+        // immediately return if not running
+        if self.webhook_dispatcher_status.load(Ordering::Relaxed) == 0 {
+            return Ok(());
+        }
+        // send stop request
+        self.webhook_dispatcher_status.store(2, Ordering::Relaxed);
+        // wait until it stopped
+        while self.webhook_dispatcher_status.load(Ordering::Relaxed) != 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        Ok(())
+    }
+
+    /// Returns the webhook payloads that couldn't be delivered, oldest first.
+    pub async fn webhook_dead_letters(&self) -> crate::Result<Vec<WebhookDeadLetter>> {
+        self.storage_manager.lock().await.get_webhook_dead_letters().await
+    }
+
+    /// Removes the stored webhook payloads that couldn't be delivered.
+    pub async fn clear_webhook_dead_letters(&self) -> crate::Result<()> {
+        self.storage_manager.lock().await.clear_webhook_dead_letters().await
+    }
+}
+
+// Posts the events of a webhook one after another, until the dispatcher is stopped
+async fn dispatch_events(
+    client: &reqwest::Client,
+    storage_manager: &StorageManagerHandle,
+    webhook_dispatcher_status: &AtomicUsize,
+    webhook: Webhook,
+    mut events: EventStream,
+) {
+    while webhook_dispatcher_status.load(Ordering::Relaxed) != 2 {
+        // wait at most a second for the next event, so stopping the process doesn't have to wait long
+        let event = match timeout(Duration::from_secs(1), events.next()).await {
+            Ok(Some(StreamEvent::Event(event))) => event,
+            Ok(Some(StreamEvent::Lagged(dropped))) => {
+                log::debug!("[webhook_dispatcher] {dropped} events for {} were dropped", webhook.url);
+                continue;
+            }
+            Ok(None) => break,
+            Err(_) => continue,
+        };
+        if let Err(err) = deliver(client, storage_manager, webhook_dispatcher_status, &webhook, event).await {
+            log::debug!("[webhook_dispatcher] error: {}", err);
+        }
+    }
+}
+
+// Posts an event until it's accepted, stores it as dead letter if all attempts failed or the dispatcher is stopped
+async fn deliver(
+    client: &reqwest::Client,
+    storage_manager: &StorageManagerHandle,
+    webhook_dispatcher_status: &AtomicUsize,
+    webhook: &Webhook,
+    event: Event,
+) -> crate::Result<()> {
+    let payload = WebhookPayload::new(event)?;
+    let body = serde_json::to_vec(&payload)?;
+    let mut signature = [0u8; 32];
+    HMAC_SHA256(&body, webhook.secret.as_bytes(), &mut signature);
+    let signature = prefix_hex::encode(signature);
+
+    let mut attempts = 0;
+    let mut delay = WEBHOOK_RETRY_DELAY;
+    let error = loop {
+        attempts += 1;
+        let request = client
+            .post(&webhook.url)
+            .header("Content-Type", "application/json")
+            .header(WEBHOOK_SIGNATURE_HEADER, &signature)
+            .timeout(WEBHOOK_REQUEST_TIMEOUT)
+            .body(body.clone())
+            .send();
+        let response = match select(Box::pin(request), Box::pin(stop_requested(webhook_dispatcher_status))).await {
+            Either::Left((response, _)) => response.and_then(|response| response.error_for_status()),
+            // The request is cancelled
+            Either::Right(_) => break "webhook dispatcher was stopped".to_string(),
+        };
+        let error = match response {
+            Ok(_) => return Ok(()),
+            Err(error) => error.to_string(),
+        };
+        log::debug!(
+            "[webhook_dispatcher] attempt {attempts} to post {} to {} failed: {error}",
+            payload.id,
+            webhook.url
+        );
+        if attempts == WEBHOOK_MAX_ATTEMPTS {
+            break error;
+        }
+        // Stopping the dispatcher doesn't wait for the retry
+        if timeout(delay, stop_requested(webhook_dispatcher_status)).await.is_ok() {
+            break error;
+        }
+        delay *= 2;
+    };
+
+    storage_manager
+        .lock()
+        .await
+        .add_webhook_dead_letter(WebhookDeadLetter {
+            url: webhook.url.clone(),
+            payload,
+            error,
+            attempts,
+        })
+        .await
+}
+
+// Returns when the dispatcher is requested to stop
+async fn stop_requested(webhook_dispatcher_status: &AtomicUsize) {
+    while webhook_dispatcher_status.load(Ordering::Relaxed) != 2 {
+        sleep(WEBHOOK_STOP_CHECK_INTERVAL).await;
+    }
+}
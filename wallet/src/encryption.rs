@@ -0,0 +1,137 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt::{Debug, Formatter, Result};
+
+use crypto::{ciphers::chacha, utils::rand};
+use serde::{Deserialize, Serialize};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+const SALT_LENGTH: usize = 32;
+// 1 GiB, more than any device this runs on should use for the key derivation
+const MAX_ARGON2ID_MEMORY_COST: u32 = 1024 * 1024;
+
+/// A password that gets zeroized on drop and isn't shown in debug output.
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+pub(crate) struct Password(String);
+
+impl Password {
+    pub(crate) fn new(password: String) -> Self {
+        Self(password)
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Debug for Password {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "Password(..)")
+    }
+}
+
+/// Data encrypted with a key derived from a password.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PasswordEncryptedData {
+    /// Hex encoded salt for the key derivation.
+    salt: String,
+    /// Argon2id parameters used for the key derivation.
+    argon2id: Argon2idParams,
+    /// Hex encoded XChaCha20-Poly1305 ciphertext including nonce and tag.
+    ciphertext: String,
+}
+
+/// The parameters of an Argon2id key derivation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Argon2idParams {
+    /// Memory size in KiB.
+    memory_cost: u32,
+    /// Number of iterations.
+    time_cost: u32,
+    /// Degree of parallelism.
+    parallelism: u32,
+}
+
+impl Default for Argon2idParams {
+    // The parameters recommended by OWASP, which are also feasible on mobile devices
+    fn default() -> Self {
+        Self {
+            memory_cost: 19 * 1024,
+            time_cost: 2,
+            parallelism: 1,
+        }
+    }
+}
+
+impl PasswordEncryptedData {
+    /// Encrypts the plaintext with a key derived from the password.
+    pub(crate) fn encrypt(password: &str, plaintext: &[u8]) -> crate::Result<Self> {
+        let mut salt = [0u8; SALT_LENGTH];
+        rand::fill(&mut salt)?;
+        let params = Argon2idParams::default();
+        let key = derive_argon2id_key(password, &salt, params)?;
+        let ciphertext = chacha::aead_encrypt(&*key, plaintext);
+
+        Ok(Self {
+            salt: prefix_hex::encode(salt),
+            argon2id: params,
+            ciphertext: prefix_hex::encode(ciphertext?),
+        })
+    }
+
+    /// Decrypts the data, fails if the password is wrong or the data was modified.
+    pub(crate) fn decrypt(&self, password: &str) -> crate::Result<Zeroizing<Vec<u8>>> {
+        let salt: Vec<u8> =
+            prefix_hex::decode(&self.salt).map_err(|_| crate::Error::Decryption("invalid salt".to_string()))?;
+        let ciphertext: Vec<u8> = prefix_hex::decode(&self.ciphertext)
+            .map_err(|_| crate::Error::Decryption("invalid ciphertext".to_string()))?;
+        let key = derive_argon2id_key(password, &salt, self.argon2id)?;
+
+        chacha::aead_decrypt(&*key, &ciphertext)
+            .map(Zeroizing::new)
+            .map_err(|_| crate::Error::Decryption("wrong password or corrupted data".to_string()))
+    }
+
+    /// Decrypts a UTF-8 encoded text, like a mnemonic, that is zeroized on drop.
+    pub(crate) fn decrypt_string(&self, password: &str) -> crate::Result<Zeroizing<String>> {
+        let mut plaintext = self.decrypt(password)?;
+        String::from_utf8(std::mem::take(&mut *plaintext))
+            .map(Zeroizing::new)
+            .map_err(|err| {
+                err.into_bytes().zeroize();
+                crate::Error::Decryption("invalid UTF-8".to_string())
+            })
+    }
+}
+
+fn derive_argon2id_key(password: &str, salt: &[u8], params: Argon2idParams) -> crate::Result<Zeroizing<[u8; 32]>> {
+    // Stored parameters could be modified to exhaust the memory
+    if params.memory_cost > MAX_ARGON2ID_MEMORY_COST {
+        return Err(crate::Error::Decryption(
+            "invalid key derivation parameters".to_string(),
+        ));
+    }
+    let argon2_params = argon2::Params::new(params.memory_cost, params.time_cost, params.parallelism, Some(32))
+        .map_err(|err| crate::Error::Decryption(format!("invalid key derivation parameters: {err}")))?;
+    let mut key = Zeroizing::new([0u8; 32]);
+    argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, argon2_params)
+        .hash_password_into(password.as_bytes(), salt, &mut *key)
+        .map_err(|err| crate::Error::Decryption(format!("key derivation failed: {err}")))?;
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_and_decrypt() {
+        let encrypted = PasswordEncryptedData::encrypt("password", b"mnemonic").unwrap();
+        assert_eq!(encrypted.argon2id, Argon2idParams::default());
+        assert_eq!(encrypted.decrypt_string("password").unwrap().as_str(), "mnemonic");
+        assert!(encrypted.decrypt("wrong password").is_err());
+    }
+}
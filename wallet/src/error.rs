@@ -3,7 +3,10 @@
 
 use std::fmt::Debug;
 
-use iota_client::block::payload::transaction::TransactionId;
+use iota_client::block::{
+    output::{AliasId, OutputId},
+    payload::transaction::TransactionId,
+};
 use serde::{
     ser::{SerializeMap, Serializer},
     Serialize,
@@ -13,17 +16,32 @@ use serde::{
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     /// Account alias must be unique.
-    #[error("can't create account: account alias {0} already exists")]
+    #[error("account alias {0} already exists")]
     AccountAliasAlreadyExists(String),
     /// Account not found
     #[error("account {0} not found")]
     AccountNotFound(String),
+    /// An operation failed for some accounts, with the account index and the error
+    #[error("operation failed for accounts: {}", format_account_errors(.0))]
+    AccountOperationsFailed(Vec<(u32, Error)>),
+    /// Address bucket not found
+    #[error("address bucket {0} not found")]
+    AddressBucketNotFound(String),
     /// Address not found in account
     #[error("address {0} not found in account")]
     AddressNotFoundInAccount(String),
+    /// Invalid air-gapped sign request or response
+    #[error("air-gapped signing failed: {0}")]
+    AirGappedSigning(String),
+    /// Alias not found in the unspent outputs of the account
+    #[error("alias {0} not found in unspent outputs")]
+    AliasNotFoundInUnspentOutputs(AliasId),
     /// Errors during backup creation or restoring
     #[error("backup failed {0}")]
     Backup(&'static str),
+    /// Errors of a backup target
+    #[error("backup target error: {0}")]
+    BackupTarget(String),
     /// Error from block crate.
     #[error("{0}")]
     Block(Box<iota_client::block::Error>),
@@ -33,21 +51,39 @@ pub enum Error {
     /// Burning or melting failed
     #[error("burning or melting failed: {0}")]
     BurningOrMeltingFailed(String),
+    /// An operation was cancelled with its cancellation token
+    #[error("operation cancelled")]
+    Cancelled,
     /// Client error.
     #[error("`{0}`")]
     Client(Box<iota_client::Error>),
     /// Funds are spread over too many outputs
     #[error("funds are spread over too many outputs {output_count}/{output_count_max}, consolidation required")]
     ConsolidationRequired { output_count: usize, output_count_max: u16 },
+    /// The node of the client disagreed with the quorum of the nodes that were queried to cross verify a sync
+    #[error("cross verification failed, the node disagreed with the quorum on {0} outputs")]
+    CrossVerificationFailed(usize),
     /// Crypto.rs error
     #[error("{0}")]
     Crypto(#[from] crypto::Error),
     /// Custom input error
     #[error("custom input error {0}")]
     CustomInput(String),
+    /// Decrypting data with a password failed
+    #[error("decryption failed: {0}")]
+    Decryption(String),
+    /// Transaction creates dust and the dust policy blocks it
+    #[error("dust policy violation: {0}")]
+    DustPolicyViolation(String),
     /// Failed to get remainder
     #[error("failed to get remainder address")]
     FailedToGetRemainder,
+    /// A transaction with the idempotency key is already being sent
+    #[error("a transaction with idempotency key {0} is already being sent")]
+    IdempotencyKeyInUse(String),
+    /// Inputs are reserved by another account manager that shares the database
+    #[error("inputs {0:?} are reserved by another account manager")]
+    InputsReserved(Vec<OutputId>),
     /// Insufficient funds to send transaction.
     #[error("insufficient funds {available}/{required} available")]
     InsufficientFunds { available: u64, required: u64 },
@@ -57,18 +93,62 @@ pub enum Error {
         new_coin_type: u32,
         existing_coin_type: u32,
     },
+    /// Expiration time that isn't in the future or not after the timelock
+    #[error("invalid expiration time {0}, it needs to be in the future and after the timelock")]
+    InvalidExpiration(u32),
+    /// Internal transfer between accounts that can't be sent
+    #[error("invalid internal transfer: {0}")]
+    InvalidInternalTransfer(String),
+    /// Invalid IRC-27 NFT metadata
+    #[error("invalid IRC-27 metadata: {0}")]
+    InvalidIrc27Metadata(String),
     /// Invalid mnemonic error
     #[error("invalid mnemonic: {0}")]
     InvalidMnemonic(String),
     /// Invalid output kind.
     #[error("invalid output kind: {0}")]
     InvalidOutputKind(String),
+    /// Invalid profile name
+    #[error("invalid profile name: {0}")]
+    InvalidProfileName(String),
+    /// Invalid atomic swap
+    #[error("invalid swap: {0}")]
+    InvalidSwap(String),
+    /// Invalid transaction filter or cursor
+    #[error("invalid transaction filter: {0}")]
+    InvalidTransactionFilter(String),
+    /// Invalid vesting schedule
+    #[error("invalid vesting schedule: {0}")]
+    InvalidVestingSchedule(String),
+    /// Invalid webhook
+    #[error("invalid webhook: {0}")]
+    InvalidWebhook(String),
     /// IO error. (storage, backup, restore)
     #[error("`{0}`")]
     Io(#[from] std::io::Error),
     /// serde_json error.
     #[error("`{0}`")]
     Json(#[from] serde_json::error::Error),
+    /// The transaction can't be shown on the Ledger Nano and blind signing is disabled
+    #[cfg(feature = "ledger_nano")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ledger_nano")))]
+    #[error("transaction can't be shown on the ledger nano, blind signing needs to be enabled in the app")]
+    LedgerNanoBlindSigningRequired,
+    /// The Ledger Nano can't be used
+    #[cfg(feature = "ledger_nano")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ledger_nano")))]
+    #[error("ledger nano not ready: {0}")]
+    LedgerNanoNotReady(&'static str),
+    /// An operation on the Ledger Nano didn't finish in time
+    #[cfg(feature = "ledger_nano")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ledger_nano")))]
+    #[error("ledger nano operation timed out: {0:?}")]
+    LedgerNanoTimeout(crate::account_manager::LedgerNanoOperation),
+    /// The Ledger Nano app can't sign outputs of this kind
+    #[cfg(feature = "ledger_nano")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ledger_nano")))]
+    #[error("ledger nano can't sign outputs of kind {0}")]
+    LedgerNanoUnsupportedOutputKind(u8),
     /// Minting failed
     #[error("minting failed {0}")]
     MintingFailed(String),
@@ -99,6 +179,35 @@ pub enum Error {
         /// The consolidation threshold.
         consolidation_threshold: usize,
     },
+    /// Not possible while the offline mode is enabled
+    #[error("not possible in offline mode")]
+    OfflineMode,
+    /// Output can't be declined
+    #[error("output {0} can't be declined: {1}")]
+    OutputNotDeclinable(OutputId, &'static str),
+    /// Transaction not found in the offline queue
+    #[error("transaction {0} not found in the offline queue")]
+    QueuedTransactionNotFound(TransactionId),
+    /// Accounts of a backup can't be restored next to the existing accounts
+    #[error("can't restore accounts, they collide with existing accounts: {0}")]
+    RestoreCollision(String),
+    /// Scheduled payment not found in the account
+    #[error("scheduled payment {0} not found")]
+    ScheduledPaymentNotFound(u32),
+    /// The new secret manager doesn't derive the addresses of the accounts
+    #[error("can't migrate secret manager: address {address} of account {account_index} isn't derived")]
+    SecretManagerMigration {
+        /// The index of the account.
+        account_index: u32,
+        /// The bech32 address that wasn't derived by the new secret manager.
+        address: String,
+    },
+    /// The secret manager has another seed than the one the storage was created with
+    #[error("secret manager doesn't match the seed fingerprint of the storage, wrong mnemonic or Stronghold snapshot")]
+    SeedFingerprintMismatch,
+    /// The transaction doesn't comply with the signing policy
+    #[error("signing policy violation: {0}")]
+    SigningPolicyViolation(String),
     /// Storage access error.
     #[error("error accessing storage: {0}")]
     Storage(String),
@@ -107,12 +216,42 @@ pub enum Error {
         "can't perform operation while storage is encrypted; use AccountManager::set_storage_password to decrypt storage"
     )]
     StorageIsEncrypted,
+    /// A storage quota would be exceeded
+    #[error("storage quota exceeded: {required} {quota} required, {allowed} allowed")]
+    StorageQuotaExceeded {
+        /// The exceeded quota, `accounts` or `bytes`.
+        quota: &'static str,
+        /// The amount that would be required for the operation.
+        required: u64,
+        /// The amount allowed by the quota.
+        allowed: u64,
+    },
     /// Tokio task join error
     #[error("{0}")]
     TaskJoin(#[from] tokio::task::JoinError),
     /// Transaction not found
     #[error("transaction {0} not found")]
     TransactionNotFound(TransactionId),
+    /// Transaction isn't pending
+    #[error("transaction {0} isn't pending")]
+    TransactionNotPending(TransactionId),
+    /// Transaction can't be retried
+    #[error("transaction {0} can't be retried: {1}")]
+    TransactionNotRetryable(TransactionId, &'static str),
+    /// Transaction was rejected by the transaction approver
+    #[error("transaction rejected: {0}")]
+    TransactionRejected(String),
+    /// Watch-only accounts can't generate addresses or sign transactions
+    #[error("not possible with a watch-only account")]
+    WatchOnlyAccount,
+}
+
+fn format_account_errors(errors: &[(u32, Error)]) -> String {
+    errors
+        .iter()
+        .map(|(account_index, error)| format!("{account_index}: {error}"))
+        .collect::<Vec<String>>()
+        .join(", ")
 }
 
 // Serialize type with Display error
@@ -1,6 +1,7 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+mod stream;
 pub mod types;
 
 use std::{
@@ -8,12 +9,22 @@ use std::{
     fmt::{Debug, Formatter, Result},
 };
 
-use self::types::{Event, WalletEvent, WalletEventType};
+pub use self::stream::{EventStream, StreamEvent, DEFAULT_EVENT_STREAM_CAPACITY};
+use self::{
+    stream::Subscription,
+    types::{Event, EventFilter, WalletEvent, WalletEventType},
+};
+#[cfg(feature = "storage")]
+use crate::account_manager::operations::event_log::EventLogSender;
 
 type Handler<T> = Box<dyn Fn(&T) + Send + Sync + 'static>;
 
 pub struct EventEmitter {
     handlers: HashMap<WalletEventType, Vec<Handler<Event>>>,
+    subscriptions: Vec<Subscription>,
+    // Passes the emitted events to the task that appends them to the event log in the storage
+    #[cfg(feature = "storage")]
+    event_log: Option<EventLogSender>,
 }
 
 impl EventEmitter {
@@ -21,9 +32,24 @@ impl EventEmitter {
     pub fn new() -> Self {
         Self {
             handlers: HashMap::new(),
+            subscriptions: Vec::new(),
+            #[cfg(feature = "storage")]
+            event_log: None,
         }
     }
 
+    /// Sets the sender through which every emitted event is added to the event log.
+    #[cfg(feature = "storage")]
+    pub(crate) fn set_event_log(&mut self, event_log: EventLogSender) {
+        self.event_log.replace(event_log);
+    }
+
+    /// Returns the sender of the event log, if one is set.
+    #[cfg(feature = "storage")]
+    pub(crate) fn event_log(&self) -> Option<&EventLogSender> {
+        self.event_log.as_ref()
+    }
+
     /// Registers function `handler` as a listener for a `WalletEventType`. There may be
     /// multiple listeners for a single event.
     pub fn on<F>(&mut self, events: Vec<WalletEventType>, handler: F)
@@ -36,11 +62,26 @@ impl EventEmitter {
             for event_type in &[
                 WalletEventType::NewOutput,
                 WalletEventType::SpentOutput,
+                WalletEventType::SyncProgress,
                 WalletEventType::TransactionInclusion,
                 WalletEventType::TransactionProgress,
                 WalletEventType::ConsolidationRequired,
+                WalletEventType::Consolidation,
+                WalletEventType::DepositReceived,
+                WalletEventType::AccountRecoveryProgress,
+                WalletEventType::Alert,
+                WalletEventType::Backup,
+                WalletEventType::BalanceChange,
+                WalletEventType::LedgerConflict,
+                WalletEventType::SyncDiscrepancy,
+                WalletEventType::ScheduledPayment,
+                WalletEventType::TransactionReattachment,
+                WalletEventType::ExpiringOutputs,
+                WalletEventType::NativeTokenOperation,
                 #[cfg(feature = "ledger_nano")]
                 WalletEventType::LedgerAddressGeneration,
+                #[cfg(feature = "ledger_nano")]
+                WalletEventType::LedgerNanoOperation,
             ] {
                 let event_handlers = self.handlers.entry(*event_type).or_insert_with(Vec::new);
                 event_handlers.push(Box::new(handler.clone()));
@@ -52,6 +93,17 @@ impl EventEmitter {
         }
     }
 
+    /// Subscribes to the events that match `filter`, the subscription ends when the returned stream is dropped. The
+    /// stream buffers up to `capacity` events, default is [`DEFAULT_EVENT_STREAM_CAPACITY`], if it isn't polled fast
+    /// enough the oldest events are dropped and [`StreamEvent::Lagged`] is returned.
+    pub fn subscribe(&mut self, filter: EventFilter, capacity: Option<usize>) -> EventStream {
+        // remove the subscriptions of dropped streams
+        self.subscriptions.retain(|subscription| !subscription.is_closed());
+        let (subscription, stream) = Subscription::new(filter, capacity.unwrap_or(DEFAULT_EVENT_STREAM_CAPACITY));
+        self.subscriptions.push(subscription);
+        stream
+    }
+
     /// Removes handlers for each given `WalletEventType`.
     /// If no `WalletEventType` is given, handlers will be removed for all event types.
     pub fn clear(&mut self, events: Vec<WalletEventType>) {
@@ -67,16 +119,15 @@ impl EventEmitter {
     /// Invokes all listeners of `event`, passing a reference to `payload` as an
     /// argument to each of them.
     pub fn emit(&self, account_index: u32, event: WalletEvent) {
-        let event_type = match &event {
-            WalletEvent::NewOutput(_) => WalletEventType::NewOutput,
-            WalletEvent::SpentOutput(_) => WalletEventType::SpentOutput,
-            WalletEvent::TransactionInclusion(_) => WalletEventType::TransactionInclusion,
-            WalletEvent::TransactionProgress(_) => WalletEventType::TransactionProgress,
-            WalletEvent::ConsolidationRequired => WalletEventType::ConsolidationRequired,
-            #[cfg(feature = "ledger_nano")]
-            WalletEvent::LedgerAddressGeneration(_) => WalletEventType::LedgerAddressGeneration,
-        };
+        let event_type = event.event_type();
         let event = Event { account_index, event };
+        for subscription in &self.subscriptions {
+            subscription.send(&event);
+        }
+        #[cfg(feature = "storage")]
+        if let Some(event_log) = &self.event_log {
+            event_log.append(event.clone());
+        }
         if let Some(handlers) = self.handlers.get(&event_type) {
             for handler in handlers {
                 handler(&event);
@@ -95,8 +146,9 @@ impl Debug for EventEmitter {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         write!(
             f,
-            "event_types_with_handlers: {:?}",
-            self.handlers.keys().collect::<Vec<&WalletEventType>>()
+            "event_types_with_handlers: {:?}, subscriptions: {}",
+            self.handlers.keys().collect::<Vec<&WalletEventType>>(),
+            self.subscriptions.len()
         )
     }
 }
@@ -109,13 +161,19 @@ mod tests {
             atomic::{AtomicUsize, Ordering},
             Arc,
         },
+        task::{Context, Poll},
     };
 
+    use futures::{task::noop_waker_ref, StreamExt};
+
     use iota_client::block::payload::transaction::TransactionId;
 
     use super::{
-        types::{TransactionInclusionEvent, TransactionProgressEvent, WalletEvent, WalletEventType},
-        EventEmitter,
+        types::{
+            AlertCode, AlertEvent, ConsolidationEvent, Event, EventFilter, EventSeverity, TransactionInclusionEvent,
+            TransactionProgressEvent, WalletEvent, WalletEventType,
+        },
+        EventEmitter, EventStream, StreamEvent,
     };
     use crate::account::types::InclusionState;
 
@@ -204,4 +262,146 @@ mod tests {
         }
         assert_eq!(1_000_003, event_counter.load(Ordering::SeqCst));
     }
+
+    #[test]
+    fn subscriptions() {
+        let mut emitter = EventEmitter::new();
+        let mut all_events = emitter.subscribe(EventFilter::default(), None);
+        let mut filtered_events = emitter.subscribe(
+            EventFilter {
+                accounts: vec![1],
+                kinds: vec![WalletEventType::Consolidation, WalletEventType::ConsolidationRequired],
+                min_amount: Some(1_000_000),
+                min_severity: None,
+            },
+            None,
+        );
+        let consolidation = |amount| {
+            WalletEvent::Consolidation(ConsolidationEvent {
+                transaction_id: TransactionId::from_str(
+                    "0x2289d9981fb23cc5f4f6c2742685eeb480f8476089888aa886a18232bad81989",
+                )
+                .expect("invalid tx id"),
+                consolidated_outputs: 2,
+                amount,
+            })
+        };
+
+        // other account
+        emitter.emit(0, WalletEvent::ConsolidationRequired);
+        // other kind
+        emitter.emit(
+            1,
+            WalletEvent::TransactionProgress(TransactionProgressEvent::SelectingInputs),
+        );
+        // amount below the minimum
+        emitter.emit(1, consolidation(999_999));
+        // events without an amount aren't filtered by it
+        emitter.emit(1, WalletEvent::ConsolidationRequired);
+        emitter.emit(1, consolidation(1_000_000));
+
+        assert_eq!(
+            poll_events(&mut filtered_events),
+            vec![
+                StreamEvent::Event(Event {
+                    account_index: 1,
+                    event: WalletEvent::ConsolidationRequired
+                }),
+                StreamEvent::Event(Event {
+                    account_index: 1,
+                    event: consolidation(1_000_000)
+                }),
+            ]
+        );
+        assert_eq!(poll_events(&mut all_events).len(), 5);
+
+        // dropped streams are removed
+        drop(all_events);
+        let _events = emitter.subscribe(EventFilter::default(), None);
+        assert_eq!(emitter.subscriptions.len(), 2);
+    }
+
+    #[test]
+    fn severity_filter() {
+        let mut emitter = EventEmitter::new();
+        let mut events = emitter.subscribe(
+            EventFilter {
+                min_severity: Some(EventSeverity::Warning),
+                ..Default::default()
+            },
+            None,
+        );
+        let alert = |code: AlertCode| {
+            WalletEvent::Alert(AlertEvent {
+                code,
+                severity: code.severity(),
+                message: "error".to_string(),
+            })
+        };
+
+        emitter.emit(
+            0,
+            WalletEvent::TransactionProgress(TransactionProgressEvent::SelectingInputs),
+        );
+        emitter.emit(0, WalletEvent::ConsolidationRequired);
+        emitter.emit(0, alert(AlertCode::StorageWriteFailed));
+
+        assert_eq!(
+            poll_events(&mut events),
+            vec![
+                StreamEvent::Event(Event {
+                    account_index: 0,
+                    event: WalletEvent::ConsolidationRequired
+                }),
+                StreamEvent::Event(Event {
+                    account_index: 0,
+                    event: alert(AlertCode::StorageWriteFailed)
+                }),
+            ]
+        );
+        assert_eq!(alert(AlertCode::NodeUnreachable).severity(), EventSeverity::Warning);
+        assert!(EventSeverity::Error > EventSeverity::Warning);
+    }
+
+    #[test]
+    fn lagging_subscription() {
+        let mut emitter = EventEmitter::new();
+        let mut events = emitter.subscribe(EventFilter::default(), Some(2));
+
+        for account_index in 0..5 {
+            emitter.emit(account_index, WalletEvent::ConsolidationRequired);
+        }
+        // the oldest events are dropped
+        assert_eq!(
+            poll_events(&mut events),
+            vec![
+                StreamEvent::Lagged(3),
+                StreamEvent::Event(Event {
+                    account_index: 3,
+                    event: WalletEvent::ConsolidationRequired
+                }),
+                StreamEvent::Event(Event {
+                    account_index: 4,
+                    event: WalletEvent::ConsolidationRequired
+                }),
+            ]
+        );
+
+        // the stream ends with the emitter
+        drop(emitter);
+        assert_eq!(
+            events.poll_next_unpin(&mut Context::from_waker(noop_waker_ref())),
+            Poll::Ready(None)
+        );
+    }
+
+    // Returns the events that are ready
+    fn poll_events(events: &mut EventStream) -> Vec<StreamEvent> {
+        let mut context = Context::from_waker(noop_waker_ref());
+        let mut received = Vec::new();
+        while let Poll::Ready(Some(event)) = events.poll_next_unpin(&mut context) {
+            received.push(event);
+        }
+        received
+    }
 }
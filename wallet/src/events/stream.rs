@@ -0,0 +1,126 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    task::{Context, Poll, Waker},
+};
+
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+
+use super::types::{Event, EventFilter};
+
+/// The default amount of events that an [`EventStream`] buffers, before the oldest ones are dropped.
+pub const DEFAULT_EVENT_STREAM_CAPACITY: usize = 1000;
+
+/// An item of an [`EventStream`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StreamEvent {
+    /// An event that matches the filter of the subscription.
+    Event(Event),
+    /// The stream wasn't polled fast enough and its buffer was full, so this amount of the oldest buffered events was
+    /// dropped. The following events are newer than the dropped ones.
+    Lagged(u64),
+}
+
+#[derive(Debug)]
+struct Buffer {
+    events: VecDeque<Event>,
+    capacity: usize,
+    dropped: u64,
+    waker: Option<Waker>,
+    closed: bool,
+}
+
+fn lock(buffer: &Mutex<Buffer>) -> MutexGuard<'_, Buffer> {
+    buffer.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The side of an [`EventStream`] that is held by the event emitter.
+#[derive(Debug)]
+pub(crate) struct Subscription {
+    filter: EventFilter,
+    buffer: Arc<Mutex<Buffer>>,
+}
+
+impl Subscription {
+    /// Creates a subscription and the stream that receives its events, at most `capacity` events are buffered.
+    pub(crate) fn new(filter: EventFilter, capacity: usize) -> (Self, EventStream) {
+        let buffer = Arc::new(Mutex::new(Buffer {
+            events: VecDeque::new(),
+            capacity: capacity.max(1),
+            dropped: 0,
+            waker: None,
+            closed: false,
+        }));
+        (
+            Self {
+                filter,
+                buffer: buffer.clone(),
+            },
+            EventStream { buffer },
+        )
+    }
+
+    /// Buffers the event if it matches the filter, drops the oldest buffered event if the buffer is full.
+    pub(crate) fn send(&self, event: &Event) {
+        if !self.filter.matches(event) {
+            return;
+        }
+        let mut buffer = lock(&self.buffer);
+        if buffer.events.len() >= buffer.capacity {
+            buffer.events.pop_front();
+            buffer.dropped += 1;
+        }
+        buffer.events.push_back(event.clone());
+        if let Some(waker) = buffer.waker.take() {
+            waker.wake();
+        }
+    }
+
+    /// Returns if the stream was dropped.
+    pub(crate) fn is_closed(&self) -> bool {
+        Arc::strong_count(&self.buffer) == 1
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        let mut buffer = lock(&self.buffer);
+        buffer.closed = true;
+        if let Some(waker) = buffer.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// A stream of the events that match the filter of a subscription, see
+/// [`EventEmitter::subscribe`](super::EventEmitter::subscribe). Dropping it ends the subscription, the stream ends
+/// when the event emitter is dropped.
+#[derive(Debug)]
+pub struct EventStream {
+    buffer: Arc<Mutex<Buffer>>,
+}
+
+impl Stream for EventStream {
+    type Item = StreamEvent;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut buffer = lock(&self.buffer);
+        if buffer.dropped > 0 {
+            return Poll::Ready(Some(StreamEvent::Lagged(std::mem::take(&mut buffer.dropped))));
+        }
+        if let Some(event) = buffer.events.pop_front() {
+            return Poll::Ready(Some(StreamEvent::Event(event)));
+        }
+        if buffer.closed {
+            return Poll::Ready(None);
+        }
+        buffer.waker.replace(cx.waker().clone());
+        Poll::Pending
+    }
+}
@@ -5,11 +5,27 @@ use getset::Getters;
 use iota_client::{
     api::PreparedTransactionDataDto,
     api_types::core::response::OutputWithMetadataResponse,
-    block::payload::transaction::{dto::TransactionPayloadDto, TransactionId},
+    block::{
+        dto::U256Dto,
+        output::{
+            dto::{NativeTokenDto, OutputDto},
+            OutputId, TokenId,
+        },
+        payload::transaction::{dto::TransactionPayloadDto, TransactionId},
+        BlockId,
+    },
 };
 use serde::{Deserialize, Serialize};
 
-use crate::account::types::{address::AddressWrapper, InclusionState, OutputDataDto};
+#[cfg(feature = "ledger_nano")]
+use crate::account_manager::{LedgerNanoOperation, LedgerNanoOperationStatus};
+use crate::{
+    account::{
+        types::{address::AddressWrapper, AccountBalanceDto, InclusionState, LedgerConflict, OutputDataDto},
+        DustWarning, ExpiringOutput, NativeTokenOperation, SyncDiscrepancy,
+    },
+    account_manager::AddressGapReport,
+};
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Event {
     /// Associated account index.
@@ -21,26 +37,72 @@ pub struct Event {
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum WalletEvent {
+    /// The addresses of an account were searched during account recovery.
+    AccountRecoveryProgress(AddressGapReport),
+    /// A problem that a supervising application should be alerted about.
+    Alert(AlertEvent),
+    /// A scheduled backup was created or failed.
+    Backup(BackupEvent),
+    /// The balance of an account changed between two background syncs.
+    BalanceChange(Box<BalanceChangeEvent>),
+    /// Outputs of an account were consolidated.
+    Consolidation(ConsolidationEvent),
     ConsolidationRequired,
+    /// An output was received from another wallet, reported again after syncs until it has the required
+    /// confirmations.
+    DepositReceived(Box<DepositReceivedEvent>),
+    /// Outputs of an account will expire back to their sender soon, each output is only reported once.
+    ExpiringOutputs(ExpiringOutputsEvent),
     #[cfg(feature = "ledger_nano")]
     #[cfg_attr(docsrs, doc(cfg(feature = "ledger_nano")))]
     LedgerAddressGeneration(AddressData),
+    #[cfg(feature = "ledger_nano")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ledger_nano")))]
+    LedgerNanoOperation(LedgerNanoOperationEvent),
+    /// The recorded outputs of an account didn't match the ledger during a sync.
+    LedgerConflict(LedgerConflictEvent),
+    /// A transaction that creates a foundry, mints, melts or burns native tokens was submitted.
+    NativeTokenOperation(NativeTokenOperationEvent),
     NewOutput(Box<NewOutputEvent>),
+    /// A scheduled payment of an account was executed or failed.
+    ScheduledPayment(ScheduledPaymentEvent),
     SpentOutput(Box<SpentOutputEvent>),
+    /// The nodes that were queried to cross verify a sync reported different outputs.
+    SyncDiscrepancy(SyncDiscrepancyEvent),
+    /// Progress of an account sync.
+    SyncProgress(SyncProgressEvent),
     TransactionInclusion(TransactionInclusionEvent),
     TransactionProgress(TransactionProgressEvent),
+    /// A pending transaction was reattached while syncing.
+    TransactionReattachment(TransactionReattachmentEvent),
 }
 
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum WalletEventType {
+    AccountRecoveryProgress,
+    Alert,
+    Backup,
+    BalanceChange,
+    Consolidation,
     ConsolidationRequired,
+    DepositReceived,
+    ExpiringOutputs,
     #[cfg(feature = "ledger_nano")]
     #[cfg_attr(docsrs, doc(cfg(feature = "ledger_nano")))]
     LedgerAddressGeneration,
+    #[cfg(feature = "ledger_nano")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ledger_nano")))]
+    LedgerNanoOperation,
+    LedgerConflict,
+    NativeTokenOperation,
     NewOutput,
+    ScheduledPayment,
     SpentOutput,
+    SyncDiscrepancy,
+    SyncProgress,
     TransactionInclusion,
     TransactionProgress,
+    TransactionReattachment,
 }
 
 impl TryFrom<&str> for WalletEventType {
@@ -48,19 +110,275 @@ impl TryFrom<&str> for WalletEventType {
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         let event_type = match value {
+            "AccountRecoveryProgress" => Self::AccountRecoveryProgress,
+            "Alert" => Self::Alert,
+            "Backup" => Self::Backup,
+            "BalanceChange" => Self::BalanceChange,
+            "Consolidation" => Self::Consolidation,
             "ConsolidationRequired" => Self::ConsolidationRequired,
+            "DepositReceived" => Self::DepositReceived,
+            "ExpiringOutputs" => Self::ExpiringOutputs,
             #[cfg(feature = "ledger_nano")]
             "LedgerAddressGeneration" => Self::LedgerAddressGeneration,
+            #[cfg(feature = "ledger_nano")]
+            "LedgerNanoOperation" => Self::LedgerNanoOperation,
+            "LedgerConflict" => Self::LedgerConflict,
+            "NativeTokenOperation" => Self::NativeTokenOperation,
             "NewOutput" => Self::NewOutput,
+            "ScheduledPayment" => Self::ScheduledPayment,
             "SpentOutput" => Self::SpentOutput,
+            "SyncDiscrepancy" => Self::SyncDiscrepancy,
+            "SyncProgress" => Self::SyncProgress,
             "TransactionInclusion" => Self::TransactionInclusion,
             "TransactionProgress" => Self::TransactionProgress,
+            "TransactionReattachment" => Self::TransactionReattachment,
             _ => return Err(format!("invalid event type {value}")),
         };
         Ok(event_type)
     }
 }
 
+impl WalletEvent {
+    /// Returns the type of the event.
+    pub const fn event_type(&self) -> WalletEventType {
+        match self {
+            Self::NewOutput(_) => WalletEventType::NewOutput,
+            Self::SpentOutput(_) => WalletEventType::SpentOutput,
+            Self::SyncProgress(_) => WalletEventType::SyncProgress,
+            Self::TransactionInclusion(_) => WalletEventType::TransactionInclusion,
+            Self::TransactionProgress(_) => WalletEventType::TransactionProgress,
+            Self::ConsolidationRequired => WalletEventType::ConsolidationRequired,
+            Self::Consolidation(_) => WalletEventType::Consolidation,
+            Self::DepositReceived(_) => WalletEventType::DepositReceived,
+            Self::AccountRecoveryProgress(_) => WalletEventType::AccountRecoveryProgress,
+            Self::Alert(_) => WalletEventType::Alert,
+            Self::Backup(_) => WalletEventType::Backup,
+            Self::BalanceChange(_) => WalletEventType::BalanceChange,
+            Self::LedgerConflict(_) => WalletEventType::LedgerConflict,
+            Self::SyncDiscrepancy(_) => WalletEventType::SyncDiscrepancy,
+            Self::ScheduledPayment(_) => WalletEventType::ScheduledPayment,
+            Self::TransactionReattachment(_) => WalletEventType::TransactionReattachment,
+            Self::ExpiringOutputs(_) => WalletEventType::ExpiringOutputs,
+            Self::NativeTokenOperation(_) => WalletEventType::NativeTokenOperation,
+            #[cfg(feature = "ledger_nano")]
+            Self::LedgerAddressGeneration(_) => WalletEventType::LedgerAddressGeneration,
+            #[cfg(feature = "ledger_nano")]
+            Self::LedgerNanoOperation(_) => WalletEventType::LedgerNanoOperation,
+        }
+    }
+
+    /// Returns how severe the event is.
+    pub const fn severity(&self) -> EventSeverity {
+        match self {
+            Self::Alert(alert) => alert.severity,
+            Self::Backup(backup) if backup.error.is_some() => EventSeverity::Error,
+            Self::ConsolidationRequired | Self::LedgerConflict(_) | Self::SyncDiscrepancy(_) => EventSeverity::Warning,
+            _ => EventSeverity::Info,
+        }
+    }
+
+    // The base coin amount that is moved by the event, `None` for events that don't move funds
+    fn amount(&self) -> Option<u64> {
+        match self {
+            Self::NewOutput(event) => output_amount(&event.output.output),
+            Self::SpentOutput(event) => output_amount(&event.output.output),
+            Self::Consolidation(event) => Some(event.amount),
+            Self::DepositReceived(event) => Some(event.amount),
+            Self::BalanceChange(event) => {
+                let previous_total: u64 = event.previous_balance.base_coin.total.parse().ok()?;
+                let total: u64 = event.balance.base_coin.total.parse().ok()?;
+                Some(previous_total.abs_diff(total))
+            }
+            Self::ExpiringOutputs(event) => Some(event.outputs.iter().map(|output| output.amount).sum()),
+            _ => None,
+        }
+    }
+}
+
+fn output_amount(output: &OutputDto) -> Option<u64> {
+    let amount = match output {
+        OutputDto::Treasury(output) => &output.amount,
+        OutputDto::Basic(output) => &output.amount,
+        OutputDto::Alias(output) => &output.amount,
+        OutputDto::Foundry(output) => &output.amount,
+        OutputDto::Nft(output) => &output.amount,
+    };
+    amount.parse().ok()
+}
+
+/// Selects the events of a subscription, empty fields match all events.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventFilter {
+    /// The indexes of the accounts to receive events of, all accounts if empty.
+    #[serde(default)]
+    pub accounts: Vec<u32>,
+    /// The types of events to receive, all types if empty.
+    #[serde(default)]
+    pub kinds: Vec<WalletEventType>,
+    /// The minimum base coin amount of events that move funds: new and spent outputs, balance changes, consolidations
+    /// and expiring outputs. Other events aren't filtered by amount.
+    #[serde(default)]
+    pub min_amount: Option<u64>,
+    /// The minimum severity of the events to receive.
+    #[serde(default)]
+    pub min_severity: Option<EventSeverity>,
+}
+
+impl EventFilter {
+    /// Returns if the event is selected by the filter.
+    pub fn matches(&self, event: &Event) -> bool {
+        if !self.accounts.is_empty() && !self.accounts.contains(&event.account_index) {
+            return false;
+        }
+        if !self.kinds.is_empty() && !self.kinds.contains(&event.event.event_type()) {
+            return false;
+        }
+        if self
+            .min_severity
+            .map_or(false, |min_severity| event.event.severity() < min_severity)
+        {
+            return false;
+        }
+        match (self.min_amount, event.event.amount()) {
+            (Some(min_amount), Some(amount)) => amount >= min_amount,
+            _ => true,
+        }
+    }
+}
+
+/// How severe an event is, ordered from the least to the most severe.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub enum EventSeverity {
+    /// A regular event.
+    Info,
+    /// Something that might need attention, the wallet keeps working.
+    Warning,
+    /// An operation failed in a way that needs attention.
+    Error,
+}
+
+/// Machine-readable code of an [`AlertEvent`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum AlertCode {
+    /// The node of the client couldn't be reached.
+    NodeUnreachable,
+    /// Syncing an account failed.
+    SyncFailed,
+    /// Writing to the storage failed, changes of the account might be lost.
+    StorageWriteFailed,
+    /// A transaction couldn't be signed, because the secret manager is locked.
+    SecretManagerLocked,
+}
+
+impl AlertCode {
+    /// Returns the severity of alerts with this code.
+    pub const fn severity(&self) -> EventSeverity {
+        match self {
+            Self::StorageWriteFailed => EventSeverity::Error,
+            Self::NodeUnreachable | Self::SyncFailed | Self::SecretManagerLocked => EventSeverity::Warning,
+        }
+    }
+}
+
+/// A problem that a supervising application should be alerted about.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AlertEvent {
+    /// The code of the problem.
+    pub code: AlertCode,
+    /// How severe the problem is.
+    pub severity: EventSeverity,
+    /// The error message.
+    pub message: String,
+}
+
+/// A scheduled backup, emitted with the account index 0, because backups contain all accounts.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BackupEvent {
+    /// The name of the backup in the backup target.
+    pub name: String,
+    /// Older backups that were removed, because they exceeded the retention count.
+    pub removed: Vec<String>,
+    /// The error, if the backup failed.
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BalanceChangeEvent {
+    /// The balance after the previous background sync.
+    #[serde(rename = "previousBalance")]
+    pub previous_balance: AccountBalanceDto,
+    /// The new balance.
+    pub balance: AccountBalanceDto,
+}
+
+/// A consolidation transaction that was sent.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ConsolidationEvent {
+    /// The id of the consolidation transaction.
+    #[serde(rename = "transactionId")]
+    pub transaction_id: TransactionId,
+    /// Amount of outputs that were consolidated.
+    #[serde(rename = "consolidatedOutputs")]
+    pub consolidated_outputs: usize,
+    /// The base coin amount of the consolidated outputs.
+    pub amount: u64,
+}
+
+/// An unspent output that was received from another wallet.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositReceivedEvent {
+    /// The id of the output.
+    pub output_id: OutputId,
+    /// Bech32 encoded address of the account that received the output.
+    pub address: String,
+    /// The base coin amount of the output.
+    pub amount: u64,
+    /// The native tokens of the output.
+    pub native_tokens: Vec<NativeTokenDto>,
+    /// The index of the milestone that confirmed the output.
+    pub milestone_index_booked: u32,
+    /// Amount of milestones that were confirmed after the one that confirmed the output.
+    pub confirmations: u32,
+    /// If the output has the confirmations that are required by the sync options, then it isn't reported again.
+    pub confirmed: bool,
+}
+
+/// Outputs that expire back to their sender within the warning period of the background syncing.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ExpiringOutputsEvent {
+    /// The outputs, ordered by their expiration.
+    pub outputs: Vec<ExpiringOutput>,
+}
+
+/// A submitted transaction of the native token lifecycle.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NativeTokenOperationEvent {
+    /// The stage of the native token lifecycle.
+    pub operation: NativeTokenOperation,
+    /// The id of the native token.
+    #[serde(rename = "tokenId")]
+    pub token_id: TokenId,
+    /// The amount that was minted, melted or burned.
+    pub amount: U256Dto,
+    /// The id of the transaction.
+    #[serde(rename = "transactionId")]
+    pub transaction_id: TransactionId,
+}
+
+/// The execution of a scheduled payment.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ScheduledPaymentEvent {
+    /// The id of the scheduled payment.
+    pub id: u32,
+    /// The id of the transaction, if the payment was sent.
+    #[serde(rename = "transactionId")]
+    pub transaction_id: Option<TransactionId>,
+    /// The error, if the payment failed.
+    pub error: Option<String>,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct NewOutputEvent {
     /// The new output.
@@ -73,12 +391,77 @@ pub struct NewOutputEvent {
     pub transaction_inputs: Option<Vec<OutputWithMetadataResponse>>,
 }
 
+/// Progress of an operation on the Ledger Nano.
+#[cfg(feature = "ledger_nano")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ledger_nano")))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct LedgerNanoOperationEvent {
+    /// The operation.
+    pub operation: LedgerNanoOperation,
+    /// The new status of the operation.
+    pub status: LedgerNanoOperationStatus,
+}
+
+/// Ledger conflicts that were found during a sync, the account was synced again without address cursors afterwards.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct LedgerConflictEvent {
+    /// The found conflicts.
+    pub conflicts: Vec<LedgerConflict>,
+    /// If the reconciliation of the account succeeded.
+    pub reconciled: bool,
+}
+
+/// Outputs that weren't reported by all nodes that were queried to cross verify a sync.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SyncDiscrepancyEvent {
+    /// The outputs that weren't reported by all nodes.
+    pub discrepancies: Vec<SyncDiscrepancy>,
+    /// How many nodes needed to report an output so it's accepted.
+    pub quorum: usize,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct SpentOutputEvent {
     /// The spent output.
     pub output: OutputDataDto,
 }
 
+/// Progress of an account sync, the counts include alias and nft addresses and their outputs once they're found.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncProgressEvent {
+    /// The current step of the sync.
+    pub step: SyncStep,
+    /// Amount of addresses for which the output ids were requested.
+    pub addresses_scanned: usize,
+    /// Amount of addresses to scan.
+    pub addresses_total: usize,
+    /// Amount of outputs that were fetched.
+    pub outputs_fetched: usize,
+    /// Amount of outputs to fetch.
+    pub outputs_total: usize,
+    /// Amount of outputs the account was updated with.
+    pub outputs_processed: usize,
+    /// Estimated progress of the sync from 0 to 100.
+    pub percent: u8,
+}
+
+/// A step of an account sync.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum SyncStep {
+    /// Requesting the output ids of the addresses.
+    #[default]
+    ScanningAddresses,
+    /// Requesting the outputs.
+    FetchingOutputs,
+    /// Updating the account with the outputs.
+    ProcessingOutputs,
+    /// Checking pending transactions.
+    SyncingTransactions,
+    /// The sync is finished.
+    Finished,
+}
+
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub struct TransactionInclusionEvent {
     #[serde(rename = "transactionId")]
@@ -87,6 +470,18 @@ pub struct TransactionInclusionEvent {
     pub inclusion_state: InclusionState,
 }
 
+/// A new attachment of a pending transaction.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct TransactionReattachmentEvent {
+    #[serde(rename = "transactionId")]
+    pub transaction_id: TransactionId,
+    /// The block of the new attachment.
+    #[serde(rename = "blockId")]
+    pub block_id: BlockId,
+    /// How often the transaction was reattached.
+    pub reattachments: u32,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum TransactionProgressEvent {
     /// Performing input selection.
@@ -97,6 +492,8 @@ pub enum TransactionProgressEvent {
     PreparedTransaction(Box<PreparedTransactionDataDto>),
     /// Prepared transaction essence hash hex encoded, required for blindsigning with a ledger nano
     PreparedTransactionEssenceHash(String),
+    /// The prepared transaction creates dust, it isn't blocked by the dust policy.
+    DustWarning(Vec<DustWarning>),
     /// Signing the transaction.
     SigningTransaction,
     /// Performing PoW.
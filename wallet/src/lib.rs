@@ -26,6 +26,9 @@ pub mod message_interface;
 /// The ClientOptions to build the iota_client for interactions with the IOTA Tangle.
 pub use iota_client::ClientBuilder as ClientOptions;
 
+/// Password based encryption helpers.
+#[cfg(feature = "storage")]
+pub(crate) mod encryption;
 /// The error module.
 pub mod error;
 /// The event module.
@@ -38,6 +41,8 @@ pub mod events;
 pub mod storage;
 /// The module for spawning tasks on a thread
 pub(crate) mod task;
+/// Date and time helpers.
+pub(crate) mod time;
 
 // Expose for high level functions
 pub use iota_client::{self, secret};
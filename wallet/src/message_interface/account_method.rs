@@ -1,6 +1,8 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashMap;
+
 use iota_client::{
     api::{PreparedTransactionDataDto, SignedTransactionDataDto},
     block::{
@@ -23,11 +25,14 @@ use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "participation")]
 use crate::account::types::participation::ParticipationEventRegistrationOptions;
+#[cfg(feature = "storage")]
+use crate::account::TransactionHistoryFilter;
 use crate::{
     account::{
-        handle::FilterOptions,
+        handle::{FilterOptions, TransactionFilterOptions},
         operations::{
             address_generation::AddressGenerationOptions,
+            claimable_outputs::ClaimableOutputsFilter,
             output_claiming::OutputsToClaim,
             syncing::SyncOptions,
             transaction::{
@@ -37,11 +42,17 @@ use crate::{
                         increase_native_token_supply::IncreaseNativeTokenSupplyOptionsDto,
                         mint_native_token::NativeTokenOptionsDto, mint_nfts::NftOptionsDto,
                     },
+                    send_batch::BatchRecipient,
+                    send_with_conditions::SendOptions,
+                    sweep::SweepOptions,
+                    vesting::VestingSchedule,
                 },
                 prepare_output::OutputOptionsDto,
                 TransactionOptionsDto,
             },
         },
+        DeclinedAssets, HistoryExportFormat, Irc27Nft, SignRequest, SignResponse, SpamFilter, SwapAcceptance,
+        SwapAssets, SwapOffer,
     },
     message_interface::dtos::{AddressWithAmountDto, AddressWithMicroAmountDto},
     AddressAndNftId, AddressNativeTokens,
@@ -164,6 +175,32 @@ pub enum AccountMethod {
         alias_id: AliasIdDto,
         options: Option<TransactionOptionsDto>,
     },
+    /// Change the state controller of an alias, signed by its governor.
+    /// Expected response: [`SentTransaction`](crate::message_interface::Response::SentTransaction)
+    SetAliasStateController {
+        #[serde(rename = "aliasId")]
+        alias_id: AliasIdDto,
+        #[serde(rename = "stateController")]
+        state_controller: String,
+        options: Option<TransactionOptionsDto>,
+    },
+    /// Change the governor of an alias, signed by its current governor.
+    /// Expected response: [`SentTransaction`](crate::message_interface::Response::SentTransaction)
+    SetAliasGovernor {
+        #[serde(rename = "aliasId")]
+        alias_id: AliasIdDto,
+        governor: String,
+        options: Option<TransactionOptionsDto>,
+    },
+    /// Publish new hex encoded state metadata of an alias, signed by its state controller.
+    /// Expected response: [`SentTransaction`](crate::message_interface::Response::SentTransaction)
+    UpdateAliasStateMetadata {
+        #[serde(rename = "aliasId")]
+        alias_id: AliasIdDto,
+        #[serde(rename = "stateMetadata")]
+        state_metadata: String,
+        options: Option<TransactionOptionsDto>,
+    },
     /// Function to destroy a foundry output with a circulating supply of 0.
     /// Native tokens in the foundry (minted by other foundries) will be transacted to the controlling alias
     /// Expected response: [`SentTransaction`](crate::message_interface::Response::SentTransaction)
@@ -178,6 +215,11 @@ pub enum AccountMethod {
         amount: u32,
         options: Option<AddressGenerationOptions>,
     },
+    /// Derive an address of the account again on the ledger nano and show it on the device.
+    /// Expected response: [`AddressVerifiedOnDevice`](crate::message_interface::Response::AddressVerifiedOnDevice)
+    #[cfg(feature = "ledger_nano")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ledger_nano")))]
+    VerifyAddressOnDevice { address: String },
     /// Get the [`OutputData`](crate::account::types::OutputData) of an output stored in the account
     /// Expected response: [`OutputData`](crate::message_interface::Response::OutputData)
     GetOutput {
@@ -196,12 +238,36 @@ pub enum AccountMethod {
         #[serde(rename = "outputsToClaim")]
         outputs_to_claim: OutputsToClaim,
     },
+    /// Get the outputs with additional unlock conditions that the account can claim now or, if the filter includes
+    /// timelocked ones, in the future
+    /// Expected response: [`ClaimableOutputs`](crate::message_interface::Response::ClaimableOutputs)
+    GetClaimableOutputs { filter: Option<ClaimableOutputsFilter> },
+    /// Get the outputs that the account can unlock now, but that expire back to their sender, optionally only the
+    /// ones that expire within the given number of milliseconds
+    /// Expected response: [`ExpiringOutputs`](crate::message_interface::Response::ExpiringOutputs)
+    GetExpiringOutputs {
+        #[serde(rename = "withinMilliseconds")]
+        within_milliseconds: Option<u64>,
+    },
+    /// Get the timelocked outputs of the account that can't be unlocked yet, optionally only the ones that unlock
+    /// within the given number of milliseconds
+    /// Expected response: [`UpcomingUnlocks`](crate::message_interface::Response::UpcomingUnlocks)
+    GetUpcomingUnlocks {
+        #[serde(rename = "withinMilliseconds")]
+        within_milliseconds: Option<u64>,
+    },
     /// Get the [`Transaction`](crate::account::types::Transaction) of a transaction stored in the account
     /// Expected response: [`Transaction`](crate::message_interface::Response::Transaction)
     GetTransaction {
         #[serde(rename = "transactionId")]
         transaction_id: TransactionId,
     },
+    /// Get the transaction that was sent with an idempotency key
+    /// Expected response: [`Transaction`](crate::message_interface::Response::Transaction)
+    GetTransactionByIdempotencyKey {
+        #[serde(rename = "idempotencyKey")]
+        idempotency_key: String,
+    },
     /// Get the transaction with inputs of an incoming transaction stored in the account
     /// List might not be complete, if the node pruned the data already
     /// Expected response: [`IncomingTransactionData`](crate::message_interface::Response::IncomingTransactionData)
@@ -234,6 +300,40 @@ pub enum AccountMethod {
     /// Returns all transaction of the account
     /// Expected response: [`Transactions`](crate::message_interface::Response::Transactions)
     Transactions,
+    /// Returns the transactions of the account that match the filter
+    /// Expected response: [`Transactions`](crate::message_interface::Response::Transactions)
+    FilterTransactions { filter: TransactionFilterOptions },
+    /// Returns a page of the sent and incoming transactions, newest first, that match the filter.
+    /// Expected response: [`TransactionPage`](crate::message_interface::Response::TransactionPage)
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    GetTransactionsPaged {
+        filter: Option<TransactionHistoryFilter>,
+        cursor: Option<String>,
+        limit: usize,
+    },
+    /// Set or remove the local note of a transaction.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    SetTransactionNote {
+        #[serde(rename = "transactionId")]
+        transaction_id: TransactionId,
+        note: Option<String>,
+    },
+    /// Set or remove the maximum time in milliseconds a sent transaction can be pending.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    SetTransactionMaxPending {
+        #[serde(rename = "transactionId")]
+        transaction_id: TransactionId,
+        #[serde(rename = "maxPendingMilliseconds")]
+        max_pending_milliseconds: Option<u64>,
+    },
+    /// Replace the local labels of a transaction.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    SetTransactionLabels {
+        #[serde(rename = "transactionId")]
+        transaction_id: TransactionId,
+        labels: Vec<String>,
+    },
     /// Returns all pending transactions of the account
     /// Expected response: [`Transactions`](crate::message_interface::Response::Transactions)
     PendingTransactions,
@@ -280,9 +380,83 @@ pub enum AccountMethod {
         nfts_options: Vec<NftOptionsDto>,
         options: Option<TransactionOptionsDto>,
     },
+    /// Mint nfts with validated IRC-27 metadata in one transaction, optionally with the same issuer for all of them.
+    /// Expected response: [`MintNftsTransaction`](crate::message_interface::Response::MintNftsTransaction)
+    MintIrc27Nfts {
+        nfts: Vec<Irc27Nft>,
+        issuer: Option<String>,
+        options: Option<TransactionOptionsDto>,
+    },
+    /// Mint nfts with validated IRC-27 metadata as children of a collection nft the account owns.
+    /// Expected response: [`MintNftsTransaction`](crate::message_interface::Response::MintNftsTransaction)
+    MintCollectionNfts {
+        #[serde(rename = "collectionId")]
+        collection_id: NftIdDto,
+        nfts: Vec<Irc27Nft>,
+        options: Option<TransactionOptionsDto>,
+    },
+    /// Get the ids of the nfts that were minted by the account.
+    /// Expected response: [`NftIds`](crate::message_interface::Response::NftIds)
+    GetMintedNfts,
+    /// Get the balance of the outputs on the addresses of a bucket.
+    /// Expected response: [`Balance`](crate::message_interface::Response::Balance)
+    GetAddressBucketBalance { bucket: String },
+    /// Get the aliases the account controls or controlled.
+    /// Expected response: [`Aliases`](crate::message_interface::Response::Aliases)
+    GetAliases,
+    /// Get the nfts of the account with their collection, optionally only the ones of a collection.
+    /// Expected response: [`Nfts`](crate::message_interface::Response::Nfts)
+    GetNfts {
+        #[serde(rename = "collectionId")]
+        collection_id: Option<NftIdDto>,
+    },
+    /// Get the spam filter of the account.
+    /// Expected response: [`SpamFilter`](crate::message_interface::Response::SpamFilter)
+    GetSpamFilter,
+    /// Set the spam filter of the account.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    SetSpamFilter {
+        #[serde(rename = "spamFilter")]
+        spam_filter: SpamFilter,
+    },
+    /// Get the outputs with NFTs or native tokens that are quarantined by the spam filter.
+    /// Expected response: [`QuarantinedOutputs`](crate::message_interface::Response::QuarantinedOutputs)
+    GetQuarantinedOutputs,
+    /// Allow a quarantined native token.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    AllowToken {
+        #[serde(rename = "tokenId")]
+        token_id: TokenIdDto,
+    },
+    /// Allow a quarantined nft.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    AllowNft {
+        #[serde(rename = "nftId")]
+        nft_id: NftIdDto,
+    },
     /// Get account balance information.
     /// Expected response: [`Balance`](crate::message_interface::Response::Balance)
     GetBalance,
+    /// Get the balance snapshots recorded after syncs between two unix timestamps in milliseconds.
+    /// Expected response: [`BalanceHistory`](crate::message_interface::Response::BalanceHistory)
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    GetBalanceHistory {
+        from: u64,
+        to: u64,
+        /// Only return the latest snapshot of each interval of this many milliseconds.
+        resolution: Option<u64>,
+    },
+    /// Get statistics over the transactions between two unix timestamps in milliseconds.
+    /// Expected response: [`AccountStatistics`](crate::message_interface::Response::AccountStatistics)
+    GetStatistics { from: u64, to: u64 },
+    /// Export the transactions between two unix timestamps in milliseconds as CSV or JSON.
+    /// Expected response: [`HistoryExport`](crate::message_interface::Response::HistoryExport)
+    ExportHistory {
+        format: HistoryExportFormat,
+        from: u64,
+        to: u64,
+    },
     /// Prepare an output.
     /// Expected response: [`Output`](crate::message_interface::Response::Output)
     PrepareOutput {
@@ -303,6 +477,58 @@ pub enum AccountMethod {
         addresses_with_amount: Vec<AddressWithAmountDto>,
         options: Option<TransactionOptionsDto>,
     },
+    /// Prepare the transaction for [`MintNativeToken`](crate::message_interface::AccountMethod::MintNativeToken).
+    /// Expected response:
+    /// [`PreparedMintTokenTransaction`](crate::message_interface::Response::PreparedMintTokenTransaction)
+    PrepareMintNativeToken {
+        #[serde(rename = "nativeTokenOptions")]
+        native_token_options: NativeTokenOptionsDto,
+        options: Option<TransactionOptionsDto>,
+    },
+    /// Prepare the transaction for
+    /// [`IncreaseNativeTokenSupply`](crate::message_interface::AccountMethod::IncreaseNativeTokenSupply).
+    /// Expected response: [`PreparedTransaction`](crate::message_interface::Response::PreparedTransaction)
+    PrepareIncreaseNativeTokenSupply {
+        /// Native token id
+        #[serde(rename = "tokenId")]
+        token_id: TokenIdDto,
+        /// To be minted amount
+        #[serde(rename = "mintAmount")]
+        mint_amount: U256Dto,
+        #[serde(rename = "increaseNativeTokenSupplyOptions")]
+        increase_native_token_supply_options: Option<IncreaseNativeTokenSupplyOptionsDto>,
+        options: Option<TransactionOptionsDto>,
+    },
+    /// Prepare the transaction for
+    /// [`DecreaseNativeTokenSupply`](crate::message_interface::AccountMethod::DecreaseNativeTokenSupply).
+    /// Expected response: [`PreparedTransaction`](crate::message_interface::Response::PreparedTransaction)
+    PrepareDecreaseNativeTokenSupply {
+        /// Native token id
+        #[serde(rename = "tokenId")]
+        token_id: TokenIdDto,
+        /// To be melted amount
+        #[serde(rename = "meltAmount")]
+        melt_amount: U256Dto,
+        options: Option<TransactionOptionsDto>,
+    },
+    /// Prepare the transaction for [`BurnNativeToken`](crate::message_interface::AccountMethod::BurnNativeToken).
+    /// Expected response: [`PreparedTransaction`](crate::message_interface::Response::PreparedTransaction)
+    PrepareBurnNativeToken {
+        /// Native token id
+        #[serde(rename = "tokenId")]
+        token_id: TokenIdDto,
+        /// To be burned amount
+        #[serde(rename = "burnAmount")]
+        burn_amount: U256Dto,
+        options: Option<TransactionOptionsDto>,
+    },
+    /// Sends the outputs of a conflicting or expired transaction again with new inputs.
+    /// Expected response: [`SentTransaction`](crate::message_interface::Response::SentTransaction)
+    RetryTransaction {
+        /// Transaction id
+        #[serde(rename = "transactionId")]
+        transaction_id: TransactionId,
+    },
     /// Retries (promotes or reattaches) a transaction sent from the account for a provided transaction id until it's
     /// included (referenced by a milestone). Returns the included block id.
     /// Expected response: [`BlockId`](crate::message_interface::Response::BlockId)
@@ -323,6 +549,15 @@ pub enum AccountMethod {
         /// Sync options
         options: Option<SyncOptions>,
     },
+    /// Get when the account was synced the last time and which node and milestone the data is from.
+    /// Expected response: [`SyncFreshness`](crate::message_interface::Response::SyncFreshness)
+    GetSyncFreshness,
+    /// Compare the outputs the node knows for the account with the stored ones, without changing the account.
+    /// Expected response: [`SyncPreview`](crate::message_interface::Response::SyncPreview)
+    SyncPreview {
+        /// Sync options
+        options: Option<SyncOptions>,
+    },
     /// Send amount.
     /// Expected response: [`SentTransaction`](crate::message_interface::Response::SentTransaction)
     SendAmount {
@@ -351,15 +586,131 @@ pub enum AccountMethod {
         addresses_nft_ids: Vec<AddressAndNftId>,
         options: Option<TransactionOptionsDto>,
     },
+    /// Decline an incoming output with a storage deposit return unlock condition, sending it back to the sender.
+    /// Expected response: [`SentTransaction`](crate::message_interface::Response::SentTransaction)
+    DeclineOutput {
+        #[serde(rename = "outputId")]
+        output_id: OutputId,
+        #[serde(rename = "declinedAssets", default)]
+        declined_assets: DeclinedAssets,
+        options: Option<TransactionOptionsDto>,
+    },
+    /// Send amount with an expiration and/or a storage deposit return unlock condition.
+    /// Expected response: [`ConditionalSend`](crate::message_interface::Response::ConditionalSend)
+    SendWithConditions {
+        #[serde(rename = "addressesWithAmount")]
+        addresses_with_amount: Vec<AddressWithAmountDto>,
+        #[serde(rename = "sendOptions")]
+        send_options: SendOptions,
+        options: Option<TransactionOptionsDto>,
+    },
+    /// Send nft with an expiration and/or a storage deposit return unlock condition.
+    /// Expected response: [`ConditionalSend`](crate::message_interface::Response::ConditionalSend)
+    SendNftWithConditions {
+        #[serde(rename = "addressesAndNftIds")]
+        addresses_nft_ids: Vec<AddressAndNftId>,
+        #[serde(rename = "sendOptions")]
+        send_options: SendOptions,
+        options: Option<TransactionOptionsDto>,
+    },
+    /// Send an amount as timelocked outputs that unlock following a vesting schedule.
+    /// Expected response: [`SentTransaction`](crate::message_interface::Response::SentTransaction)
+    SendVesting {
+        address: String,
+        amount: String,
+        schedule: VestingSchedule,
+        options: Option<TransactionOptionsDto>,
+    },
     /// Set the alias of the account.
     /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
     SetAlias { alias: String },
+    /// Set the metadata of the application for the account.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    SetMetadata { metadata: HashMap<String, String> },
+    /// Move bech32 encoded addresses of the account into a bucket.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    AddAddressesToBucket { bucket: String, addresses: Vec<String> },
+    /// Remove a bucket, its addresses stay in the account.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    RemoveAddressBucket { bucket: String },
     /// Send outputs in a transaction.
     /// Expected response: [`SentTransaction`](crate::message_interface::Response::SentTransaction)
     SendOutputs {
         outputs: Vec<OutputDto>,
         options: Option<TransactionOptionsDto>,
     },
+    /// Send amounts with tag and metadata features to many recipients, in as many transactions as needed.
+    /// Expected response: [`BatchSend`](crate::message_interface::Response::BatchSend)
+    SendBatch {
+        recipients: Vec<BatchRecipient>,
+        options: Option<TransactionOptionsDto>,
+    },
+    /// Calculate the storage deposits and the highest amount that can be sent to an address, without preparing a
+    /// transaction.
+    /// Expected response: [`SendEstimate`](crate::message_interface::Response::SendEstimate)
+    EstimateSend {
+        amount: String,
+        address: String,
+        options: Option<TransactionOptionsDto>,
+    },
+    /// Estimate the storage deposits of [`MintNativeToken`](crate::message_interface::AccountMethod::MintNativeToken).
+    /// Expected response: [`NativeTokenEstimate`](crate::message_interface::Response::NativeTokenEstimate)
+    EstimateMintNativeToken {
+        #[serde(rename = "nativeTokenOptions")]
+        native_token_options: NativeTokenOptionsDto,
+        options: Option<TransactionOptionsDto>,
+    },
+    /// Estimate the storage deposits of
+    /// [`IncreaseNativeTokenSupply`](crate::message_interface::AccountMethod::IncreaseNativeTokenSupply).
+    /// Expected response: [`NativeTokenEstimate`](crate::message_interface::Response::NativeTokenEstimate)
+    EstimateIncreaseNativeTokenSupply {
+        /// Native token id
+        #[serde(rename = "tokenId")]
+        token_id: TokenIdDto,
+        /// To be minted amount
+        #[serde(rename = "mintAmount")]
+        mint_amount: U256Dto,
+        #[serde(rename = "increaseNativeTokenSupplyOptions")]
+        increase_native_token_supply_options: Option<IncreaseNativeTokenSupplyOptionsDto>,
+        options: Option<TransactionOptionsDto>,
+    },
+    /// Estimate the storage deposits of
+    /// [`DecreaseNativeTokenSupply`](crate::message_interface::AccountMethod::DecreaseNativeTokenSupply).
+    /// Expected response: [`NativeTokenEstimate`](crate::message_interface::Response::NativeTokenEstimate)
+    EstimateDecreaseNativeTokenSupply {
+        /// Native token id
+        #[serde(rename = "tokenId")]
+        token_id: TokenIdDto,
+        /// To be melted amount
+        #[serde(rename = "meltAmount")]
+        melt_amount: U256Dto,
+        options: Option<TransactionOptionsDto>,
+    },
+    /// Estimate the storage deposits of [`BurnNativeToken`](crate::message_interface::AccountMethod::BurnNativeToken).
+    /// Expected response: [`NativeTokenEstimate`](crate::message_interface::Response::NativeTokenEstimate)
+    EstimateBurnNativeToken {
+        /// Native token id
+        #[serde(rename = "tokenId")]
+        token_id: TokenIdDto,
+        /// To be burned amount
+        #[serde(rename = "burnAmount")]
+        burn_amount: U256Dto,
+        options: Option<TransactionOptionsDto>,
+    },
+    /// Send the highest amount that can be transferred in a single transaction to an address.
+    /// Expected response: [`SentTransaction`](crate::message_interface::Response::SentTransaction)
+    SendAll {
+        address: String,
+        #[serde(rename = "includeNativeTokens", default)]
+        include_native_tokens: bool,
+        options: Option<TransactionOptionsDto>,
+    },
+    /// Send all outputs that can be unlocked now to an address, with as many transactions as needed.
+    /// Expected response: [`Sweep`](crate::message_interface::Response::Sweep)
+    Sweep {
+        address: String,
+        options: Option<SweepOptions>,
+    },
     /// Sign a prepared transaction.
     /// Expected response: [`SignedTransactionData`](crate::message_interface::Response::SignedTransactionData)
     SignTransactionEssence {
@@ -372,12 +723,107 @@ pub enum AccountMethod {
         #[serde(rename = "signedTransactionData")]
         signed_transaction_data: SignedTransactionDataDto,
     },
+    /// Unlock the inputs of a prepared transaction that won't be signed or submitted.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    DiscardPreparedTransaction {
+        #[serde(rename = "preparedTransactionData")]
+        prepared_transaction_data: PreparedTransactionDataDto,
+    },
+    /// Create a request to sign a prepared transaction on an offline device.
+    /// Expected response: [`SignRequest`](crate::message_interface::Response::SignRequest)
+    CreateSignRequest {
+        #[serde(rename = "preparedTransactionData")]
+        prepared_transaction_data: PreparedTransactionDataDto,
+    },
+    /// Sign a request that was created by an online account, doesn't need a connection to a node.
+    /// Expected response: [`SignResponse`](crate::message_interface::Response::SignResponse)
+    SignRequest {
+        #[serde(rename = "signRequest")]
+        sign_request: SignRequest,
+    },
+    /// Submit a transaction that was signed on an offline device and store it in the account.
+    /// Expected response: [`SentTransaction`](crate::message_interface::Response::SentTransaction)
+    SubmitSignResponse {
+        #[serde(rename = "signResponse")]
+        sign_response: SignResponse,
+    },
+    /// Offer assets of the account in exchange for assets of the counterparty.
+    /// Expected response: [`SwapOffer`](crate::message_interface::Response::SwapOffer)
+    CreateSwapOffer {
+        counterparty: String,
+        offered: SwapAssets,
+        requested: SwapAssets,
+        options: Option<TransactionOptionsDto>,
+    },
+    /// Accept a swap offer for an address of the account and sign the inputs of the account.
+    /// Expected response: [`SwapAcceptance`](crate::message_interface::Response::SwapAcceptance)
+    AcceptSwapOffer {
+        offer: SwapOffer,
+        options: Option<TransactionOptionsDto>,
+    },
+    /// Complete a swap offer of the account with the acceptance of the counterparty and submit the transaction.
+    /// Expected response: [`SentTransaction`](crate::message_interface::Response::SentTransaction)
+    CompleteSwap {
+        offer: SwapOffer,
+        acceptance: SwapAcceptance,
+    },
+    /// Cancel a swap offer of the account and unlock its inputs.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    CancelSwapOffer { offer: SwapOffer },
+    /// Unlock the inputs the account added to an accepted swap.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    CancelSwapAcceptance { acceptance: SwapAcceptance },
     /// Claim outputs.
     /// Expected response: [`SentTransaction`](crate::message_interface::Response::SentTransaction)
     ClaimOutputs {
         #[serde(rename = "outputIdsToClaim")]
         output_ids_to_claim: Vec<OutputId>,
     },
+    /// Claim outputs with as many transactions as needed.
+    /// Expected response: [`ClaimBatch`](crate::message_interface::Response::ClaimBatch)
+    ClaimOutputsInBatches {
+        #[serde(rename = "outputIdsToClaim")]
+        output_ids_to_claim: Vec<OutputId>,
+    },
+    /// Prepare and sign a transaction and store it in the offline queue instead of submitting it.
+    /// Expected response: [`QueuedTransaction`](crate::message_interface::Response::QueuedTransaction)
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    QueueTransaction {
+        outputs: Vec<OutputDto>,
+        options: Option<TransactionOptionsDto>,
+    },
+    /// Prepare and sign a transaction to claim outputs and store it in the offline queue instead of submitting it.
+    /// Expected response: [`QueuedTransaction`](crate::message_interface::Response::QueuedTransaction)
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    QueueClaimOutputs {
+        #[serde(rename = "outputIdsToClaim")]
+        output_ids_to_claim: Vec<OutputId>,
+    },
+    /// Get the transactions in the offline queue.
+    /// Expected response: [`QueuedTransactions`](crate::message_interface::Response::QueuedTransactions)
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    GetQueuedTransactions,
+    /// Remove a transaction from the offline queue and unlock its inputs.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    RemoveQueuedTransaction {
+        #[serde(rename = "transactionId")]
+        transaction_id: TransactionId,
+    },
+    /// Submit the transactions in the offline queue, after checking their inputs for conflicts.
+    /// Expected response: [`QueueSubmission`](crate::message_interface::Response::QueueSubmission)
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    SubmitQueuedTransactions,
+    /// Get the inputs that are reserved by in-progress transactions.
+    /// Expected response: [`InputReservations`](crate::message_interface::Response::InputReservations)
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    GetInputReservations,
     /// Vote for a participation event.
     /// Expected response: [`SentTransaction`](crate::message_interface::Response::SentTransaction)
     #[cfg(feature = "participation")]
@@ -467,6 +913,35 @@ pub enum AccountMethod {
     #[cfg(feature = "participation")]
     #[cfg_attr(docsrs, doc(cfg(feature = "participation")))]
     GetParticipationEvents,
+    /// Returns the registered participation events that are commencing or holding, with their latest status.
+    /// Expected response:
+    /// [`ActiveParticipationEvents`](crate::message_interface::Response::ActiveParticipationEvents)
+    #[cfg(feature = "participation")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "participation")))]
+    GetActiveParticipationEvents,
+    /// Returns the participation state stored with the last update.
+    /// Expected response: [`ParticipationState`](crate::message_interface::Response::ParticipationState)
+    #[cfg(feature = "participation")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "participation")))]
+    GetParticipationState,
+    /// Calculates the voting power and the participation in the registered events and stores it.
+    /// Expected response: [`ParticipationState`](crate::message_interface::Response::ParticipationState)
+    #[cfg(feature = "participation")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "participation")))]
+    UpdateParticipationState,
+    /// Stake the voting power in a staking event.
+    /// Expected response: [`SentTransaction`](crate::message_interface::Response::SentTransaction)
+    #[cfg(feature = "participation")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "participation")))]
+    Stake {
+        #[serde(rename = "eventId")]
+        event_id: ParticipationEventId,
+    },
+    /// Calculates the staked amounts and the rewards for the registered staking events.
+    /// Expected response: [`StakingRewards`](crate::message_interface::Response::StakingRewards)
+    #[cfg(feature = "participation")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "participation")))]
+    GetStakingRewards,
     /// Expected response: [`Faucet`](crate::message_interface::Response::Faucet)
     RequestFundsFromFaucet { url: String, address: String },
 }
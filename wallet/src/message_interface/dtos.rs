@@ -9,14 +9,14 @@ use std::{
 };
 
 use iota_client::block::{
-    output::{dto::FoundryOutputDto, FoundryId, OutputId},
+    output::{dto::FoundryOutputDto, AliasId, FoundryId, NftId, OutputId},
     payload::transaction::TransactionId,
 };
 use serde::{Deserialize, Serialize};
 
 use crate::{
     account::{
-        types::{address::AddressWrapper, AccountAddress, AddressWithUnspentOutputs, TransactionDto},
+        types::{address::AddressWrapper, AccountAddress, AddressWithUnspentOutputs, AliasRecord, TransactionDto},
         Account, OutputDataDto,
     },
     AddressWithAmount, AddressWithMicroAmount,
@@ -138,6 +138,21 @@ pub struct AccountDto {
     /// Foundries for native tokens in outputs
     #[serde(rename = "nativeTokenFoundries", default)]
     pub native_token_foundries: HashMap<FoundryId, FoundryOutputDto>,
+    /// Watch-only accounts can't generate addresses or sign transactions
+    #[serde(rename = "watchOnly", default)]
+    pub watch_only: bool,
+    /// Metadata of the application
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+    /// Named buckets of addresses
+    #[serde(rename = "addressBuckets", default)]
+    pub address_buckets: HashMap<String, HashSet<AddressWrapper>>,
+    /// Ids of the NFTs that were minted by the account
+    #[serde(rename = "mintedNfts", default)]
+    pub minted_nfts: Vec<NftId>,
+    /// The aliases the account controls or controlled
+    #[serde(default)]
+    pub aliases: HashMap<AliasId, AliasRecord>,
 }
 
 impl From<&Account> for AccountDto {
@@ -180,6 +195,11 @@ impl From<&Account> for AccountDto {
                 .iter()
                 .map(|(id, foundry)| (*id, FoundryOutputDto::from(foundry)))
                 .collect(),
+            watch_only: *value.watch_only(),
+            metadata: value.metadata().clone(),
+            address_buckets: value.address_buckets().clone(),
+            minted_nfts: value.minted_nfts().clone(),
+            aliases: value.aliases().clone(),
         }
     }
 }
@@ -11,10 +11,20 @@ use super::account_method::AccountMethod;
 #[cfg(feature = "events")]
 use crate::events::types::{WalletEvent, WalletEventType};
 use crate::{
-    account::{operations::syncing::SyncOptions, types::AccountIdentifier},
-    iota_client::{node_manager::node::NodeAuth, secret::GenerateAddressOptions, Url},
+    account::{
+        operations::syncing::SyncOptions, types::AccountIdentifier, ConsolidationPolicy, DustPolicy, OutputsToClaim,
+        RequestLimits, SigningPolicy, TransactionOptionsDto,
+    },
+    account_manager::RecoverAccountsOptions,
+    iota_client::{
+        node_manager::node::NodeAuth,
+        secret::{GenerateAddressOptions, SecretManagerDto},
+        Url,
+    },
     ClientOptions,
 };
+#[cfg(feature = "storage")]
+use crate::{account_manager::SchedulePaymentOptions, storage::audit_log::AuditLogFilter};
 
 /// The messages that can be sent to the actor.
 #[derive(Clone, Serialize, Deserialize)]
@@ -29,6 +39,20 @@ pub enum Message {
         /// The bech32 HRP.
         #[serde(rename = "bech32Hrp")]
         bech32_hrp: Option<String>,
+        /// The coin type, if it should differ from the one of the account manager.
+        #[serde(rename = "coinType")]
+        coin_type: Option<u32>,
+        /// The client options, if the account should use nodes of another network than the account manager.
+        #[serde(rename = "clientOptions")]
+        client_options: Option<Box<ClientOptions>>,
+    },
+    /// Create a watch-only account for bech32 addresses that are controlled externally.
+    /// Expected response: [`Account`](crate::message_interface::Response::Account)
+    CreateWatchAccount {
+        /// The bech32 addresses to watch.
+        addresses: Vec<String>,
+        /// The account alias.
+        alias: Option<String>,
     },
     /// Read account.
     /// Expected response: [`Account`](crate::message_interface::Response::Account)
@@ -100,6 +124,9 @@ pub enum Message {
         /// fields will be overwritten to skip existing addresses.
         sync_options: Option<SyncOptions>,
     },
+    /// Find accounts with unspent outputs, with larger address gap limits for single accounts
+    /// Expected response: [`RecoveredAccounts`](crate::message_interface::Response::RecoveredAccounts)
+    RecoverAccountsWithOptions { options: RecoverAccountsOptions },
     /// Restore a backup from a Stronghold file
     /// Replaces client_options, coin_type, secret_manager and accounts. Returns an error if accounts were already
     /// created If Stronghold is used as secret_manager, the existing Stronghold file will be overwritten. If a
@@ -120,6 +147,9 @@ pub enum Message {
     /// Removes the latest account (account with the largest account index).
     /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
     RemoveLatestAccount,
+    /// Derives all addresses of all accounts again and reports the ones that don't match the stored addresses.
+    /// Expected response: [`DerivationReport`](crate::message_interface::Response::DerivationReport)
+    VerifyDerivations,
     /// Generates a new mnemonic.
     /// Expected response: [`GeneratedMnemonic`](crate::message_interface::Response::GeneratedMnemonic)
     GenerateMnemonic,
@@ -132,6 +162,23 @@ pub enum Message {
         #[serde(rename = "clientOptions")]
         client_options: Box<ClientOptions>,
     },
+    /// Set the limits for the requests to the nodes while syncing.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    SetRequestLimits { limits: RequestLimits },
+    /// Updates the client options for a single account, with `None` it uses the ones of the account manager again.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    SetAccountClientOptions {
+        #[serde(rename = "accountIndex")]
+        account_index: u32,
+        #[serde(rename = "clientOptions")]
+        client_options: Option<Box<ClientOptions>>,
+    },
+    /// Replaces the secret manager with another one that derives the same addresses for all accounts.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    MigrateSecretManager {
+        #[serde(rename = "secretManager")]
+        secret_manager: SecretManagerDto,
+    },
     /// Generate an address without storing it
     /// Expected response: [`Bech32Address`](crate::message_interface::Response::Bech32Address)
     GenerateAddress {
@@ -149,11 +196,50 @@ pub enum Message {
         #[serde(rename = "bech32Hrp")]
         bech32_hrp: Option<String>,
     },
+    /// Get the entries of the audit log that match the filter
+    /// Expected response: [`AuditLog`](crate::message_interface::Response::AuditLog)
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    GetAuditLog { filter: AuditLogFilter },
+    /// Get the summed balance of all accounts, as recorded after their last sync
+    /// Expected response: [`TotalBalance`](crate::message_interface::Response::TotalBalance)
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    GetTotalBalance,
+    /// Get the summed balances of the accounts grouped by their coin type
+    /// Expected response: [`TotalBalancesByCoinType`](crate::message_interface::Response::TotalBalancesByCoinType)
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    GetTotalBalanceByCoinType,
+    /// Get the summed balances of the accounts grouped by the value of a metadata key
+    /// Expected response: [`TotalBalancesByMetadata`](crate::message_interface::Response::TotalBalancesByMetadata)
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    GetTotalBalanceByMetadata { key: String },
+    /// Get the summed statistics of all accounts between two unix timestamps in milliseconds, without the internal
+    /// transfers between them
+    /// Expected response: [`AccountStatistics`](crate::message_interface::Response::AccountStatistics)
+    GetTotalStatistics { from: u64, to: u64 },
+    /// Send an amount from one account to another account of the wallet, recorded as internal transfer on both
+    /// Expected response: [`SentTransaction`](crate::message_interface::Response::SentTransaction)
+    InternalTransfer {
+        #[serde(rename = "fromAccount")]
+        from_account: u32,
+        #[serde(rename = "toAccount")]
+        to_account: u32,
+        amount: String,
+        options: Option<TransactionOptionsDto>,
+    },
     /// Get the ledger nano status
     /// Expected response: [`LedgerNanoStatus`](crate::message_interface::Response::LedgerNanoStatus)
     #[cfg(feature = "ledger_nano")]
     #[cfg_attr(docsrs, doc(cfg(feature = "ledger_nano")))]
     GetLedgerNanoStatus,
+    /// Get the capabilities of the ledger nano and the opened app
+    /// Expected response: [`LedgerNanoCapabilities`](crate::message_interface::Response::LedgerNanoCapabilities)
+    #[cfg(feature = "ledger_nano")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ledger_nano")))]
+    GetLedgerNanoCapabilities,
     /// Get the node information
     /// Expected response: [`NodeInfo`](crate::message_interface::Response::NodeInfo)
     GetNodeInfo {
@@ -162,6 +248,24 @@ pub enum Message {
         /// Node authentication
         auth: Option<NodeAuth>,
     },
+    /// Get the signing policy.
+    /// Expected response: [`SigningPolicy`](crate::message_interface::Response::SigningPolicy)
+    GetSigningPolicy,
+    /// Set the signing policy every transaction needs to comply with before it gets signed.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    SetSigningPolicy {
+        #[serde(rename = "signingPolicy")]
+        signing_policy: SigningPolicy,
+    },
+    /// Get the dust policy.
+    /// Expected response: [`DustPolicy`](crate::message_interface::Response::DustPolicy)
+    GetDustPolicy,
+    /// Set the dust policy that warns about or blocks transactions creating dust.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    SetDustPolicy {
+        #[serde(rename = "dustPolicy")]
+        dust_policy: DustPolicy,
+    },
     /// Set the stronghold password.
     /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
     #[cfg(feature = "stronghold")]
@@ -192,11 +296,124 @@ pub enum Message {
     /// Stop background syncing.
     /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
     StopBackgroundSync,
+    /// Start syncing the accounts based on MQTT notifications, polling with the fallback interval while the
+    /// connection is down.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    #[cfg(feature = "mqtt")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "mqtt")))]
+    StartMqttSync {
+        /// Sync options
+        options: Option<SyncOptions>,
+        /// Fallback interval in milliseconds
+        #[serde(rename = "fallbackIntervalInMilliseconds")]
+        fallback_interval_in_milliseconds: Option<u64>,
+    },
+    /// Stop syncing the accounts based on MQTT notifications.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    #[cfg(feature = "mqtt")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "mqtt")))]
+    StopMqttSync,
+    /// Pause the background syncing of an account, or of all accounts if no account index is provided.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    PauseBackgroundSync {
+        #[serde(rename = "accountIndex")]
+        account_index: Option<u32>,
+    },
+    /// Resume the background syncing of an account, or of all accounts if no account index is provided.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    ResumeBackgroundSync {
+        #[serde(rename = "accountIndex")]
+        account_index: Option<u32>,
+    },
+    /// Set the background syncing interval of an account, no interval resets it to the interval the background
+    /// syncing was started with.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    SetBackgroundSyncInterval {
+        #[serde(rename = "accountIndex")]
+        account_index: u32,
+        /// Interval in milliseconds
+        #[serde(rename = "intervalInMilliseconds")]
+        interval_in_milliseconds: Option<u64>,
+    },
+    /// Set the policy for the automatic consolidation of the outputs of an account by the background syncing, `None`
+    /// disables it.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    SetConsolidationPolicy {
+        #[serde(rename = "accountIndex")]
+        account_index: u32,
+        policy: Option<ConsolidationPolicy>,
+    },
+    /// Claim the outputs of an account that match `outputsToClaim` automatically in the background syncing, as soon
+    /// as they can be unlocked, `None` disables it.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    SetAutoClaim {
+        #[serde(rename = "accountIndex")]
+        account_index: u32,
+        #[serde(rename = "outputsToClaim")]
+        outputs_to_claim: Option<OutputsToClaim>,
+    },
+    /// Set the period before the expiration of an output of an account in which the background syncing emits an
+    /// `ExpiringOutputs` event for it, `None` resets it to one day and `0` disables the events.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    #[cfg(feature = "events")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "events")))]
+    SetExpirationWarningPeriod {
+        #[serde(rename = "accountIndex")]
+        account_index: u32,
+        #[serde(rename = "periodInMilliseconds")]
+        period_in_milliseconds: Option<u64>,
+    },
+    /// Enable or disable the offline mode, when disabled the queued transactions of all accounts are submitted.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    SetOfflineMode { offline: bool },
+    /// Check if the offline mode is enabled.
+    /// Expected response: [`OfflineMode`](crate::message_interface::Response::OfflineMode)
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    IsOfflineMode,
+    /// Submit the queued transactions of all accounts.
+    /// Expected response: [`QueueSubmissions`](crate::message_interface::Response::QueueSubmissions)
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    SubmitQueuedTransactions,
+    /// Schedule a future-dated or recurring payment of an account, executed by the background syncing.
+    /// Expected response: [`ScheduledPayment`](crate::message_interface::Response::ScheduledPayment)
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    SchedulePayment {
+        #[serde(rename = "accountIndex")]
+        account_index: u32,
+        options: SchedulePaymentOptions,
+    },
+    /// Get the pending scheduled payments of an account.
+    /// Expected response: [`ScheduledPayments`](crate::message_interface::Response::ScheduledPayments)
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    GetScheduledPayments {
+        #[serde(rename = "accountIndex")]
+        account_index: u32,
+    },
+    /// Cancel a scheduled payment of an account.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    CancelScheduledPayment {
+        #[serde(rename = "accountIndex")]
+        account_index: u32,
+        id: u32,
+    },
     /// Emits an event for testing if the event system is working
     /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
     #[cfg(feature = "events")]
     #[cfg_attr(docsrs, doc(cfg(feature = "events")))]
     EmitTestEvent { event: WalletEvent },
+    /// Get the emitted events with a sequence number greater than `sequence`, to replay missed events
+    /// Expected response: [`EventLog`](crate::message_interface::Response::EventLog)
+    #[cfg(all(feature = "events", feature = "storage"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "events", feature = "storage"))))]
+    GetEventsSince { sequence: u64 },
     /// Transforms a bech32 encoded address to hex
     /// Expected response: [`HexAddress`](crate::message_interface::Response::HexAddress)
     Bech32ToHex {
@@ -234,8 +451,17 @@ pub enum Message {
 impl Debug for Message {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         match self {
-            Self::CreateAccount { alias, bech32_hrp } => {
-                write!(f, "CreateAccount{{ alias: {alias:?}, bech32_hrp: {bech32_hrp:?} }}")
+            Self::CreateAccount {
+                alias,
+                bech32_hrp,
+                coin_type,
+                client_options,
+            } => write!(
+                f,
+                "CreateAccount{{ alias: {alias:?}, bech32_hrp: {bech32_hrp:?}, coin_type: {coin_type:?}, client_options: {client_options:?} }}"
+            ),
+            Self::CreateWatchAccount { addresses, alias } => {
+                write!(f, "CreateWatchAccount{{ addresses: {addresses:?}, alias: {alias:?} }}")
             }
             Self::GetAccountIndexes => write!(f, "GetAccountIndexes"),
             Self::GetAccount { account_id } => write!(f, "GetAccount{{ account_id: {account_id:?} }}"),
@@ -270,7 +496,11 @@ impl Debug for Message {
                 f,
                 "RecoverAccounts{{ account_start_index: {account_start_index:?}, account_gap_limit: {account_gap_limit:?}, address_gap_limit: {address_gap_limit:?}, sync_options: {sync_options:?} }}"
             ),
+            Self::RecoverAccountsWithOptions { options } => {
+                write!(f, "RecoverAccountsWithOptions{{ options: {options:?} }}")
+            }
             Self::RemoveLatestAccount => write!(f, "RemoveLatestAccount"),
+            Self::VerifyDerivations => write!(f, "VerifyDerivations"),
             #[cfg(feature = "stronghold")]
             Self::RestoreBackup {
                 source,
@@ -285,8 +515,39 @@ impl Debug for Message {
             Self::SetClientOptions { client_options } => {
                 write!(f, "SetClientOptions{{ client_options: {client_options:?} }}")
             }
+            Self::SetRequestLimits { limits } => write!(f, "SetRequestLimits{{ limits: {limits:?} }}"),
+            Self::SetAccountClientOptions {
+                account_index,
+                client_options,
+            } => write!(
+                f,
+                "SetAccountClientOptions{{ account_index: {account_index}, client_options: {client_options:?} }}"
+            ),
+            Self::MigrateSecretManager { secret_manager: _ } => {
+                write!(f, "MigrateSecretManager{{ secret_manager: <omitted> }}")
+            }
+            #[cfg(feature = "storage")]
+            Self::GetAuditLog { filter } => write!(f, "GetAuditLog{{ filter: {filter:?} }}"),
+            #[cfg(feature = "storage")]
+            Self::GetTotalBalance => write!(f, "GetTotalBalance"),
+            #[cfg(feature = "storage")]
+            Self::GetTotalBalanceByCoinType => write!(f, "GetTotalBalanceByCoinType"),
+            #[cfg(feature = "storage")]
+            Self::GetTotalBalanceByMetadata { key } => write!(f, "GetTotalBalanceByMetadata{{ key: {key:?} }}"),
+            Self::GetTotalStatistics { from, to } => write!(f, "GetTotalStatistics{{ from: {from:?}, to: {to:?} }}"),
+            Self::InternalTransfer {
+                from_account,
+                to_account,
+                amount,
+                options,
+            } => write!(
+                f,
+                "InternalTransfer{{ from_account: {from_account:?}, to_account: {to_account:?}, amount: {amount:?}, options: {options:?} }}"
+            ),
             #[cfg(feature = "ledger_nano")]
             Self::GetLedgerNanoStatus => write!(f, "GetLedgerNanoStatus"),
+            #[cfg(feature = "ledger_nano")]
+            Self::GetLedgerNanoCapabilities => write!(f, "GetLedgerNanoCapabilities"),
             Self::GenerateAddress {
                 account_index,
                 internal,
@@ -298,6 +559,12 @@ impl Debug for Message {
                 "GenerateAddress{{ account_index: {account_index:?}, internal: {internal:?}, address_index: {address_index:?}, options: {options:?}, bech32_hrp: {bech32_hrp:?} }}"
             ),
             Self::GetNodeInfo { url, auth: _ } => write!(f, "GetNodeInfo{{ url: {url:?} }}"),
+            Self::GetSigningPolicy => write!(f, "GetSigningPolicy"),
+            Self::SetSigningPolicy { signing_policy } => {
+                write!(f, "SetSigningPolicy{{ signing_policy: {signing_policy:?} }}")
+            }
+            Self::GetDustPolicy => write!(f, "GetDustPolicy"),
+            Self::SetDustPolicy { dust_policy } => write!(f, "SetDustPolicy{{ dust_policy: {dust_policy:?} }}"),
             #[cfg(feature = "stronghold")]
             Self::SetStrongholdPassword { password: _ } => {
                 write!(f, "SetStrongholdPassword{{  password: <omitted> }}")
@@ -321,8 +588,72 @@ impl Debug for Message {
                 "StartBackgroundSync{{ options: {options:?}, interval: {interval_in_milliseconds:?} }}"
             ),
             Self::StopBackgroundSync => write!(f, "StopBackgroundSync"),
+            #[cfg(feature = "mqtt")]
+            Self::StartMqttSync {
+                options,
+                fallback_interval_in_milliseconds,
+            } => write!(
+                f,
+                "StartMqttSync{{ options: {options:?}, fallback_interval: {fallback_interval_in_milliseconds:?} }}"
+            ),
+            #[cfg(feature = "mqtt")]
+            Self::StopMqttSync => write!(f, "StopMqttSync"),
+            Self::PauseBackgroundSync { account_index } => {
+                write!(f, "PauseBackgroundSync{{ account_index: {account_index:?} }}")
+            }
+            Self::ResumeBackgroundSync { account_index } => {
+                write!(f, "ResumeBackgroundSync{{ account_index: {account_index:?} }}")
+            }
+            Self::SetBackgroundSyncInterval {
+                account_index,
+                interval_in_milliseconds,
+            } => write!(
+                f,
+                "SetBackgroundSyncInterval{{ account_index: {account_index:?}, interval: {interval_in_milliseconds:?} }}"
+            ),
+            Self::SetAutoClaim {
+                account_index,
+                outputs_to_claim,
+            } => write!(
+                f,
+                "SetAutoClaim{{ account_index: {account_index:?}, outputs_to_claim: {outputs_to_claim:?} }}"
+            ),
+            #[cfg(feature = "events")]
+            Self::SetExpirationWarningPeriod {
+                account_index,
+                period_in_milliseconds,
+            } => write!(
+                f,
+                "SetExpirationWarningPeriod{{ account_index: {account_index:?}, period_in_milliseconds: {period_in_milliseconds:?} }}"
+            ),
+            Self::SetConsolidationPolicy { account_index, policy } => write!(
+                f,
+                "SetConsolidationPolicy{{ account_index: {account_index:?}, policy: {policy:?} }}"
+            ),
+            #[cfg(feature = "storage")]
+            Self::SetOfflineMode { offline } => write!(f, "SetOfflineMode{{ offline: {offline} }}"),
+            #[cfg(feature = "storage")]
+            Self::IsOfflineMode => write!(f, "IsOfflineMode"),
+            #[cfg(feature = "storage")]
+            Self::SubmitQueuedTransactions => write!(f, "SubmitQueuedTransactions"),
+            #[cfg(feature = "storage")]
+            Self::SchedulePayment { account_index, options } => write!(
+                f,
+                "SchedulePayment{{ account_index: {account_index:?}, options: {options:?} }}"
+            ),
+            #[cfg(feature = "storage")]
+            Self::GetScheduledPayments { account_index } => {
+                write!(f, "GetScheduledPayments{{ account_index: {account_index:?} }}")
+            }
+            #[cfg(feature = "storage")]
+            Self::CancelScheduledPayment { account_index, id } => write!(
+                f,
+                "CancelScheduledPayment{{ account_index: {account_index:?}, id: {id:?} }}"
+            ),
             #[cfg(feature = "events")]
             Self::EmitTestEvent { event } => write!(f, "EmitTestEvent{{ event: {event:?} }}"),
+            #[cfg(all(feature = "events", feature = "storage"))]
+            Self::GetEventsSince { sequence } => write!(f, "GetEventsSince{{ sequence: {sequence} }}"),
             Self::Bech32ToHex { bech32_address } => write!(f, "Bech32ToHex{{ bech32_address: {bech32_address:?} }}"),
             Self::HexToBech32 { hex, bech32_hrp } => {
                 write!(f, "HexToBech32{{ hex: {hex:?}, bech32_hrp: {bech32_hrp:?} }}")
@@ -3,11 +3,10 @@
 
 #[cfg(feature = "stronghold")]
 use std::path::PathBuf;
-#[cfg(feature = "participation")]
-use std::str::FromStr;
 use std::{
     any::Any,
     panic::{catch_unwind, AssertUnwindSafe},
+    str::FromStr,
     time::Duration,
 };
 
@@ -23,30 +22,40 @@ use iota_client::{
         DtoError,
     },
     constants::SHIMMER_TESTNET_BECH32_HRP,
-    request_funds_from_faucet, utils, Client, NodeInfoWrapper,
+    request_funds_from_faucet,
+    secret::SecretManager,
+    utils, Client, NodeInfoWrapper,
 };
 use primitive_types::U256;
 use zeroize::Zeroize;
 
+#[cfg(feature = "storage")]
+use crate::account::{QueueSubmissionDto, TransactionPageDto};
 #[cfg(feature = "events")]
 use crate::events::types::{Event, WalletEventType};
 use crate::{
     account::{
         operations::transaction::{
-            high_level::{create_alias::AliasOutputOptions, minting::mint_native_token::MintTokenTransactionDto},
+            high_level::{
+                create_alias::AliasOutputOptions, minting::mint_native_token::MintTokenTransactionDto,
+                send_batch::BatchSendDto, send_with_conditions::ConditionalSendDto, sweep::SweepDto,
+            },
             prepare_output::OutputOptions,
             TransactionOptions,
         },
-        types::{AccountBalanceDto, AccountIdentifier, TransactionDto},
-        OutputDataDto,
+        types::{
+            address::{parse_bech32_address, AddressWrapper},
+            AccountBalanceDto, AccountIdentifier, TransactionDto,
+        },
+        ClaimBatchDto, MintNftsTransactionDto, NftMintBuilder, OutputDataDto, PreparedMintTokenTransactionDto,
     },
     account_manager::AccountManager,
     message_interface::{
         account_method::AccountMethod, dtos::AccountDto, message::Message, response::Response,
         AddressWithUnspentOutputsDto,
     },
-    AddressWithAmount, AddressWithMicroAmount, IncreaseNativeTokenSupplyOptions, NativeTokenOptions, NftOptions,
-    Result,
+    AddressWithAmount, AddressWithMicroAmount, ClientOptions, IncreaseNativeTokenSupplyOptions, NativeTokenOptions,
+    NftOptions, Result,
 };
 
 fn panic_to_response_message(panic: Box<dyn Any>) -> Response {
@@ -128,8 +137,25 @@ impl WalletMessageHandler {
         log::debug!("Message: {:?}", message);
 
         let response: Result<Response> = match message {
-            Message::CreateAccount { alias, bech32_hrp } => {
-                convert_async_panics(|| async { self.create_account(alias, bech32_hrp).await }).await
+            Message::CreateAccount {
+                alias,
+                bech32_hrp,
+                coin_type,
+                client_options,
+            } => {
+                convert_async_panics(|| async {
+                    self.create_account(alias, bech32_hrp, coin_type, client_options.map(|options| *options))
+                        .await
+                })
+                .await
+            }
+            Message::CreateWatchAccount { addresses, alias } => {
+                convert_async_panics(|| async {
+                    let account_handle = self.account_manager.create_watch_account(addresses, alias).await?;
+                    let account = account_handle.read().await;
+                    Ok(Response::Account(AccountDto::from(&*account)))
+                })
+                .await
             }
             Message::GetAccount { account_id } => {
                 convert_async_panics(|| async { self.get_account(&account_id).await }).await
@@ -204,6 +230,18 @@ impl WalletMessageHandler {
                 })
                 .await
             }
+            Message::RecoverAccountsWithOptions { options } => {
+                convert_async_panics(|| async {
+                    let (account_handles, report) = self.account_manager.recover_accounts_with_options(options).await?;
+                    let mut accounts = Vec::new();
+                    for account_handle in account_handles {
+                        let account = account_handle.read().await;
+                        accounts.push(AccountDto::from(&*account));
+                    }
+                    Ok(Response::RecoveredAccounts(accounts, report))
+                })
+                .await
+            }
             Message::RemoveLatestAccount => {
                 convert_async_panics(|| async {
                     self.account_manager.remove_latest_account().await?;
@@ -211,6 +249,13 @@ impl WalletMessageHandler {
                 })
                 .await
             }
+            Message::VerifyDerivations => {
+                convert_async_panics(|| async {
+                    let report = self.account_manager.verify_derivations().await?;
+                    Ok(Response::DerivationReport(report))
+                })
+                .await
+            }
             #[cfg(feature = "stronghold")]
             Message::RestoreBackup {
                 source,
@@ -240,6 +285,94 @@ impl WalletMessageHandler {
                 })
                 .await
             }
+            Message::SetRequestLimits { limits } => {
+                convert_async_panics(|| async {
+                    self.account_manager.set_request_limits(limits).await;
+                    Ok(Response::Ok(()))
+                })
+                .await
+            }
+            Message::SetAccountClientOptions {
+                account_index,
+                client_options,
+            } => {
+                convert_async_panics(|| async {
+                    self.account_manager
+                        .set_account_client_options(account_index, client_options.map(|options| *options))
+                        .await?;
+                    Ok(Response::Ok(()))
+                })
+                .await
+            }
+            Message::MigrateSecretManager { secret_manager } => {
+                convert_async_panics(|| async {
+                    let secret_manager = SecretManager::try_from(&secret_manager)?;
+                    self.account_manager.migrate_secret_manager(secret_manager).await?;
+                    Ok(Response::Ok(()))
+                })
+                .await
+            }
+            #[cfg(feature = "storage")]
+            Message::GetAuditLog { filter } => {
+                convert_async_panics(|| async {
+                    let audit_log = self.account_manager.audit_log(&filter).await?;
+                    Ok(Response::AuditLog(audit_log))
+                })
+                .await
+            }
+            #[cfg(feature = "storage")]
+            Message::GetTotalBalance => {
+                convert_async_panics(|| async {
+                    let total_balance = self.account_manager.total_balance().await?;
+                    Ok(Response::TotalBalance(total_balance))
+                })
+                .await
+            }
+            #[cfg(feature = "storage")]
+            Message::GetTotalBalanceByCoinType => {
+                convert_async_panics(|| async {
+                    let total_balances = self.account_manager.total_balance_by_coin_type().await?;
+                    Ok(Response::TotalBalancesByCoinType(total_balances))
+                })
+                .await
+            }
+            #[cfg(feature = "storage")]
+            Message::GetTotalBalanceByMetadata { key } => {
+                convert_async_panics(|| async {
+                    let total_balances = self.account_manager.total_balance_by_metadata(&key).await?;
+                    Ok(Response::TotalBalancesByMetadata(total_balances))
+                })
+                .await
+            }
+            Message::GetTotalStatistics { from, to } => {
+                convert_async_panics(|| async {
+                    let statistics = self.account_manager.statistics(from..to).await?;
+                    Ok(Response::AccountStatistics(statistics))
+                })
+                .await
+            }
+            Message::InternalTransfer {
+                from_account,
+                to_account,
+                amount,
+                options,
+            } => {
+                convert_async_panics(|| async {
+                    let amount =
+                        u64::from_str(&amount).map_err(|_| iota_client::Error::InvalidAmount(amount.clone()))?;
+                    let transaction = self
+                        .account_manager
+                        .internal_transfer(
+                            from_account,
+                            to_account,
+                            amount,
+                            options.as_ref().map(TransactionOptions::try_from_dto).transpose()?,
+                        )
+                        .await?;
+                    Ok(Response::SentTransaction(TransactionDto::from(&transaction)))
+                })
+                .await
+            }
             #[cfg(feature = "ledger_nano")]
             Message::GetLedgerNanoStatus => {
                 convert_async_panics(|| async {
@@ -248,6 +381,14 @@ impl WalletMessageHandler {
                 })
                 .await
             }
+            #[cfg(feature = "ledger_nano")]
+            Message::GetLedgerNanoCapabilities => {
+                convert_async_panics(|| async {
+                    let capabilities = self.account_manager.get_ledger_nano_capabilities().await?;
+                    Ok(Response::LedgerNanoCapabilities(capabilities))
+                })
+                .await
+            }
             Message::GenerateAddress {
                 account_index,
                 internal,
@@ -282,6 +423,32 @@ impl WalletMessageHandler {
                 })
                 .await
             }
+            Message::GetSigningPolicy => {
+                convert_async_panics(|| async {
+                    Ok(Response::SigningPolicy(self.account_manager.get_signing_policy().await))
+                })
+                .await
+            }
+            Message::SetSigningPolicy { signing_policy } => {
+                convert_async_panics(|| async {
+                    self.account_manager.set_signing_policy(signing_policy).await;
+                    Ok(Response::Ok(()))
+                })
+                .await
+            }
+            Message::GetDustPolicy => {
+                convert_async_panics(|| async {
+                    Ok(Response::DustPolicy(self.account_manager.get_dust_policy().await))
+                })
+                .await
+            }
+            Message::SetDustPolicy { dust_policy } => {
+                convert_async_panics(|| async {
+                    self.account_manager.set_dust_policy(dust_policy).await;
+                    Ok(Response::Ok(()))
+                })
+                .await
+            }
             #[cfg(feature = "stronghold")]
             Message::SetStrongholdPassword { mut password } => {
                 convert_async_panics(|| async {
@@ -330,6 +497,136 @@ impl WalletMessageHandler {
                 })
                 .await
             }
+            #[cfg(feature = "mqtt")]
+            Message::StartMqttSync {
+                options,
+                fallback_interval_in_milliseconds,
+            } => {
+                convert_async_panics(|| async {
+                    let duration = fallback_interval_in_milliseconds.map(Duration::from_millis);
+                    self.account_manager.start_mqtt_syncing(options, duration).await?;
+                    Ok(Response::Ok(()))
+                })
+                .await
+            }
+            #[cfg(feature = "mqtt")]
+            Message::StopMqttSync => {
+                convert_async_panics(|| async {
+                    self.account_manager.stop_mqtt_syncing().await?;
+                    Ok(Response::Ok(()))
+                })
+                .await
+            }
+            Message::PauseBackgroundSync { account_index } => {
+                convert_async_panics(|| async {
+                    self.account_manager.pause_background_syncing(account_index).await?;
+                    Ok(Response::Ok(()))
+                })
+                .await
+            }
+            Message::ResumeBackgroundSync { account_index } => {
+                convert_async_panics(|| async {
+                    self.account_manager.resume_background_syncing(account_index).await?;
+                    Ok(Response::Ok(()))
+                })
+                .await
+            }
+            Message::SetBackgroundSyncInterval {
+                account_index,
+                interval_in_milliseconds,
+            } => {
+                convert_async_panics(|| async {
+                    let duration = interval_in_milliseconds.map(Duration::from_millis);
+                    self.account_manager
+                        .set_background_syncing_interval(account_index, duration)
+                        .await?;
+                    Ok(Response::Ok(()))
+                })
+                .await
+            }
+            Message::SetAutoClaim {
+                account_index,
+                outputs_to_claim,
+            } => {
+                convert_async_panics(|| async {
+                    self.account_manager
+                        .set_auto_claim(account_index, outputs_to_claim)
+                        .await?;
+                    Ok(Response::Ok(()))
+                })
+                .await
+            }
+            #[cfg(feature = "events")]
+            Message::SetExpirationWarningPeriod {
+                account_index,
+                period_in_milliseconds,
+            } => {
+                convert_async_panics(|| async {
+                    self.account_manager
+                        .set_expiration_warning_period(account_index, period_in_milliseconds.map(Duration::from_millis))
+                        .await?;
+                    Ok(Response::Ok(()))
+                })
+                .await
+            }
+            Message::SetConsolidationPolicy { account_index, policy } => {
+                convert_async_panics(|| async {
+                    self.account_manager
+                        .set_consolidation_policy(account_index, policy)
+                        .await?;
+                    Ok(Response::Ok(()))
+                })
+                .await
+            }
+            #[cfg(feature = "storage")]
+            Message::SetOfflineMode { offline } => {
+                convert_async_panics(|| async {
+                    self.account_manager.set_offline_mode(offline).await?;
+                    Ok(Response::Ok(()))
+                })
+                .await
+            }
+            #[cfg(feature = "storage")]
+            Message::IsOfflineMode => Ok(Response::OfflineMode(self.account_manager.is_offline_mode())),
+            #[cfg(feature = "storage")]
+            Message::SubmitQueuedTransactions => {
+                convert_async_panics(|| async {
+                    let queue_submissions = self.account_manager.submit_queued_transactions().await?;
+                    Ok(Response::QueueSubmissions(
+                        queue_submissions
+                            .iter()
+                            .map(|(account_index, queue_submission)| {
+                                (*account_index, QueueSubmissionDto::from(queue_submission))
+                            })
+                            .collect(),
+                    ))
+                })
+                .await
+            }
+            #[cfg(feature = "storage")]
+            Message::SchedulePayment { account_index, options } => {
+                convert_async_panics(|| async {
+                    let scheduled_payment = self.account_manager.schedule_payment(account_index, options).await?;
+                    Ok(Response::ScheduledPayment(scheduled_payment))
+                })
+                .await
+            }
+            #[cfg(feature = "storage")]
+            Message::GetScheduledPayments { account_index } => {
+                convert_async_panics(|| async {
+                    let scheduled_payments = self.account_manager.scheduled_payments(account_index).await?;
+                    Ok(Response::ScheduledPayments(scheduled_payments))
+                })
+                .await
+            }
+            #[cfg(feature = "storage")]
+            Message::CancelScheduledPayment { account_index, id } => {
+                convert_async_panics(|| async {
+                    self.account_manager.cancel_scheduled_payment(account_index, id).await?;
+                    Ok(Response::Ok(()))
+                })
+                .await
+            }
             #[cfg(feature = "events")]
             Message::EmitTestEvent { event } => {
                 convert_async_panics(|| async {
@@ -338,6 +635,14 @@ impl WalletMessageHandler {
                 })
                 .await
             }
+            #[cfg(all(feature = "events", feature = "storage"))]
+            Message::GetEventsSince { sequence } => {
+                convert_async_panics(|| async {
+                    let event_log = self.account_manager.events_since(sequence).await?;
+                    Ok(Response::EventLog(event_log))
+                })
+                .await
+            }
             Message::Bech32ToHex { bech32_address } => {
                 convert_panics(|| Ok(Response::HexAddress(utils::bech32_to_hex(&bech32_address)?)))
             }
@@ -577,6 +882,58 @@ impl WalletMessageHandler {
                 })
                 .await
             }
+            AccountMethod::SetAliasStateController {
+                alias_id,
+                state_controller,
+                options,
+            } => {
+                convert_async_panics(|| async {
+                    let transaction = account_handle
+                        .set_alias_state_controller(
+                            AliasId::try_from(&alias_id)?,
+                            state_controller,
+                            options.as_ref().map(TransactionOptions::try_from_dto).transpose()?,
+                        )
+                        .await?;
+                    Ok(Response::SentTransaction(TransactionDto::from(&transaction)))
+                })
+                .await
+            }
+            AccountMethod::SetAliasGovernor {
+                alias_id,
+                governor,
+                options,
+            } => {
+                convert_async_panics(|| async {
+                    let transaction = account_handle
+                        .set_alias_governor(
+                            AliasId::try_from(&alias_id)?,
+                            governor,
+                            options.as_ref().map(TransactionOptions::try_from_dto).transpose()?,
+                        )
+                        .await?;
+                    Ok(Response::SentTransaction(TransactionDto::from(&transaction)))
+                })
+                .await
+            }
+            AccountMethod::UpdateAliasStateMetadata {
+                alias_id,
+                state_metadata,
+                options,
+            } => {
+                convert_async_panics(|| async {
+                    let transaction = account_handle
+                        .update_alias_state_metadata(
+                            AliasId::try_from(&alias_id)?,
+                            prefix_hex::decode(&state_metadata)
+                                .map_err(|_| DtoError::InvalidField("state_metadata"))?,
+                            options.as_ref().map(TransactionOptions::try_from_dto).transpose()?,
+                        )
+                        .await?;
+                    Ok(Response::SentTransaction(TransactionDto::from(&transaction)))
+                })
+                .await
+            }
             AccountMethod::DestroyFoundry { foundry_id, options } => {
                 convert_async_panics(|| async {
                     let transaction = account_handle
@@ -593,12 +950,33 @@ impl WalletMessageHandler {
                 let address = account_handle.generate_addresses(amount, options).await?;
                 Ok(Response::GeneratedAddress(address))
             }
+            #[cfg(feature = "ledger_nano")]
+            AccountMethod::VerifyAddressOnDevice { address } => {
+                let verified = account_handle.verify_address_on_device(&address).await?;
+                Ok(Response::AddressVerifiedOnDevice(verified))
+            }
             AccountMethod::GetOutputsWithAdditionalUnlockConditions { outputs_to_claim } => {
                 let output_ids = account_handle
                     .get_unlockable_outputs_with_additional_unlock_conditions(outputs_to_claim)
                     .await?;
                 Ok(Response::OutputIds(output_ids))
             }
+            AccountMethod::GetClaimableOutputs { filter } => {
+                let claimable_outputs = account_handle.claimable_outputs(filter).await?;
+                Ok(Response::ClaimableOutputs(claimable_outputs))
+            }
+            AccountMethod::GetExpiringOutputs { within_milliseconds } => {
+                let expiring_outputs = account_handle
+                    .expiring_outputs(within_milliseconds.map(Duration::from_millis))
+                    .await?;
+                Ok(Response::ExpiringOutputs(expiring_outputs))
+            }
+            AccountMethod::GetUpcomingUnlocks { within_milliseconds } => {
+                let upcoming_unlocks = account_handle
+                    .upcoming_unlocks(within_milliseconds.map(Duration::from_millis))
+                    .await?;
+                Ok(Response::UpcomingUnlocks(upcoming_unlocks))
+            }
             AccountMethod::GetOutput { output_id } => {
                 let output_data = account_handle.get_output(&output_id).await;
                 Ok(Response::OutputData(
@@ -616,6 +994,14 @@ impl WalletMessageHandler {
                     transaction.as_ref().map(TransactionDto::from).map(Box::new),
                 ))
             }
+            AccountMethod::GetTransactionByIdempotencyKey { idempotency_key } => {
+                let transaction = account_handle
+                    .get_transaction_by_idempotency_key(&idempotency_key)
+                    .await;
+                Ok(Response::Transaction(
+                    transaction.as_ref().map(TransactionDto::from).map(Box::new),
+                ))
+            }
             AccountMethod::GetIncomingTransactionData { transaction_id } => {
                 let transaction = account_handle.get_incoming_transaction_data(&transaction_id).await;
 
@@ -662,6 +1048,34 @@ impl WalletMessageHandler {
                     transactions.iter().map(TransactionDto::from).collect(),
                 ))
             }
+            AccountMethod::FilterTransactions { filter } => {
+                let transactions = account_handle.filter_transactions(filter).await?;
+                Ok(Response::Transactions(
+                    transactions.iter().map(TransactionDto::from).collect(),
+                ))
+            }
+            #[cfg(feature = "storage")]
+            AccountMethod::GetTransactionsPaged { filter, cursor, limit } => {
+                let page = account_handle.transactions_paged(filter, cursor, limit).await?;
+                Ok(Response::TransactionPage(TransactionPageDto::from(&page)))
+            }
+            AccountMethod::SetTransactionNote { transaction_id, note } => {
+                account_handle.set_transaction_note(&transaction_id, note).await?;
+                Ok(Response::Ok(()))
+            }
+            AccountMethod::SetTransactionMaxPending {
+                transaction_id,
+                max_pending_milliseconds,
+            } => {
+                account_handle
+                    .set_transaction_max_pending(&transaction_id, max_pending_milliseconds)
+                    .await?;
+                Ok(Response::Ok(()))
+            }
+            AccountMethod::SetTransactionLabels { transaction_id, labels } => {
+                account_handle.set_transaction_labels(&transaction_id, labels).await?;
+                Ok(Response::Ok(()))
+            }
             AccountMethod::PendingTransactions => {
                 let transactions = account_handle.pending_transactions().await?;
                 Ok(Response::Transactions(
@@ -742,13 +1156,87 @@ impl WalletMessageHandler {
                 })
                 .await
             }
-            AccountMethod::MintNfts { nfts_options, options } => {
+            AccountMethod::MintIrc27Nfts { nfts, issuer, options } => {
                 convert_async_panics(|| async {
+                    let mut builder = NftMintBuilder::new();
+                    for nft in nfts {
+                        builder = builder.add_nft(nft.metadata, nft.address)?;
+                    }
+                    if let Some(issuer) = issuer {
+                        builder = builder.with_issuer(issuer);
+                    }
                     let transaction = account_handle
-                        .mint_nfts(
-                            nfts_options
-                                .iter()
-                                .map(NftOptions::try_from)
+                        .mint_nfts_with_builder(
+                            builder,
+                            options.as_ref().map(TransactionOptions::try_from_dto).transpose()?,
+                        )
+                        .await?;
+                    Ok(Response::MintNftsTransaction(MintNftsTransactionDto::from(
+                        &transaction,
+                    )))
+                })
+                .await
+            }
+            AccountMethod::MintCollectionNfts {
+                collection_id,
+                nfts,
+                options,
+            } => {
+                convert_async_panics(|| async {
+                    let mut builder = NftMintBuilder::new();
+                    for nft in nfts {
+                        builder = builder.add_nft(nft.metadata, nft.address)?;
+                    }
+                    let transaction = account_handle
+                        .mint_collection_nfts(
+                            NftId::try_from(&collection_id)?,
+                            builder,
+                            options.as_ref().map(TransactionOptions::try_from_dto).transpose()?,
+                        )
+                        .await?;
+                    Ok(Response::MintNftsTransaction(MintNftsTransactionDto::from(
+                        &transaction,
+                    )))
+                })
+                .await
+            }
+            AccountMethod::GetNfts { collection_id } => {
+                let collection_id = collection_id.as_ref().map(NftId::try_from).transpose()?;
+                Ok(Response::Nfts(account_handle.nfts(collection_id).await?))
+            }
+            AccountMethod::GetMintedNfts => Ok(Response::NftIds(account_handle.minted_nfts().await)),
+            AccountMethod::GetSpamFilter => Ok(Response::SpamFilter(account_handle.spam_filter().await)),
+            AccountMethod::SetSpamFilter { spam_filter } => {
+                convert_async_panics(|| async {
+                    account_handle.set_spam_filter(spam_filter).await?;
+                    Ok(Response::Ok(()))
+                })
+                .await
+            }
+            AccountMethod::GetQuarantinedOutputs => {
+                Ok(Response::QuarantinedOutputs(account_handle.quarantined_outputs().await))
+            }
+            AccountMethod::AllowToken { token_id } => {
+                convert_async_panics(|| async {
+                    account_handle.allow_token(TokenId::try_from(&token_id)?).await?;
+                    Ok(Response::Ok(()))
+                })
+                .await
+            }
+            AccountMethod::AllowNft { nft_id } => {
+                convert_async_panics(|| async {
+                    account_handle.allow_nft(NftId::try_from(&nft_id)?).await?;
+                    Ok(Response::Ok(()))
+                })
+                .await
+            }
+            AccountMethod::MintNfts { nfts_options, options } => {
+                convert_async_panics(|| async {
+                    let transaction = account_handle
+                        .mint_nfts(
+                            nfts_options
+                                .iter()
+                                .map(NftOptions::try_from)
                                 .collect::<Result<Vec<NftOptions>>>()?,
                             options.as_ref().map(TransactionOptions::try_from_dto).transpose()?,
                         )
@@ -757,9 +1245,23 @@ impl WalletMessageHandler {
                 })
                 .await
             }
+            AccountMethod::GetAliases => Ok(Response::Aliases(account_handle.aliases().await)),
+            AccountMethod::GetAddressBucketBalance { bucket } => Ok(Response::Balance(AccountBalanceDto::from(
+                &account_handle.address_bucket_balance(&bucket).await?,
+            ))),
             AccountMethod::GetBalance => Ok(Response::Balance(AccountBalanceDto::from(
                 &account_handle.balance().await?,
             ))),
+            #[cfg(feature = "storage")]
+            AccountMethod::GetBalanceHistory { from, to, resolution } => Ok(Response::BalanceHistory(
+                account_handle.balance_history(from..to, resolution).await?,
+            )),
+            AccountMethod::GetStatistics { from, to } => {
+                Ok(Response::AccountStatistics(account_handle.statistics(from..to).await?))
+            }
+            AccountMethod::ExportHistory { format, from, to } => Ok(Response::HistoryExport(
+                account_handle.export_history(format, from..to).await?,
+            )),
             AccountMethod::PrepareOutput {
                 options,
                 transaction_options,
@@ -796,6 +1298,79 @@ impl WalletMessageHandler {
                 })
                 .await
             }
+            AccountMethod::PrepareMintNativeToken {
+                native_token_options,
+                options,
+            } => {
+                convert_async_panics(|| async {
+                    let data = account_handle
+                        .prepare_mint_native_token(
+                            NativeTokenOptions::try_from(&native_token_options)?,
+                            options.as_ref().map(TransactionOptions::try_from_dto).transpose()?,
+                        )
+                        .await?;
+                    Ok(Response::PreparedMintTokenTransaction(
+                        PreparedMintTokenTransactionDto::from(&data),
+                    ))
+                })
+                .await
+            }
+            AccountMethod::PrepareIncreaseNativeTokenSupply {
+                token_id,
+                mint_amount,
+                increase_native_token_supply_options,
+                options,
+            } => {
+                convert_async_panics(|| async {
+                    let data = account_handle
+                        .prepare_increase_native_token_supply(
+                            TokenId::try_from(&token_id)?,
+                            U256::try_from(&mint_amount).map_err(|_| DtoError::InvalidField("mint_amount"))?,
+                            increase_native_token_supply_options
+                                .as_ref()
+                                .map(IncreaseNativeTokenSupplyOptions::try_from)
+                                .transpose()?,
+                            options.as_ref().map(TransactionOptions::try_from_dto).transpose()?,
+                        )
+                        .await?;
+                    Ok(Response::PreparedTransaction(PreparedTransactionDataDto::from(&data)))
+                })
+                .await
+            }
+            AccountMethod::PrepareDecreaseNativeTokenSupply {
+                token_id,
+                melt_amount,
+                options,
+            } => {
+                convert_async_panics(|| async {
+                    let data = account_handle
+                        .prepare_decrease_native_token_supply(
+                            TokenId::try_from(&token_id)?,
+                            U256::try_from(&melt_amount).map_err(|_| DtoError::InvalidField("melt_amount"))?,
+                            options.as_ref().map(TransactionOptions::try_from_dto).transpose()?,
+                        )
+                        .await?;
+                    Ok(Response::PreparedTransaction(PreparedTransactionDataDto::from(&data)))
+                })
+                .await
+            }
+            AccountMethod::PrepareBurnNativeToken {
+                token_id,
+                burn_amount,
+                options,
+            } => {
+                convert_async_panics(|| async {
+                    let data = account_handle
+                        .prepare_burn_native_token(
+                            TokenId::try_from(&token_id)?,
+                            U256::try_from(&burn_amount).map_err(|_| DtoError::InvalidField("burn_amount"))?,
+                            options.as_ref().map(TransactionOptions::try_from_dto).transpose()?,
+                        )
+                        .await?;
+                    Ok(Response::PreparedTransaction(PreparedTransactionDataDto::from(&data)))
+                })
+                .await
+            }
             AccountMethod::PrepareTransaction { outputs, options } => {
                 convert_async_panics(|| async {
                     let token_supply = account_handle.client.get_token_supply().await?;
@@ -812,6 +1387,13 @@ impl WalletMessageHandler {
                 })
                 .await
             }
+            AccountMethod::RetryTransaction { transaction_id } => {
+                convert_async_panics(|| async {
+                    let transaction = account_handle.retry_transaction(&transaction_id).await?;
+                    Ok(Response::SentTransaction(TransactionDto::from(&transaction)))
+                })
+                .await
+            }
             AccountMethod::RetryTransactionUntilIncluded {
                 transaction_id,
                 interval,
@@ -828,6 +1410,10 @@ impl WalletMessageHandler {
             AccountMethod::SyncAccount { options } => Ok(Response::Balance(AccountBalanceDto::from(
                 &account_handle.sync(options).await?,
             ))),
+            AccountMethod::GetSyncFreshness => Ok(Response::SyncFreshness(account_handle.sync_freshness().await?)),
+            AccountMethod::SyncPreview { options } => {
+                Ok(Response::SyncPreview(account_handle.sync_preview(options).await?))
+            }
             AccountMethod::SendAmount {
                 addresses_with_amount,
                 options,
@@ -894,6 +1480,60 @@ impl WalletMessageHandler {
                 })
                 .await
             }
+            AccountMethod::DeclineOutput {
+                output_id,
+                declined_assets,
+                options,
+            } => {
+                convert_async_panics(|| async {
+                    let transaction = account_handle
+                        .decline_output(
+                            output_id,
+                            declined_assets,
+                            options.as_ref().map(TransactionOptions::try_from_dto).transpose()?,
+                        )
+                        .await?;
+                    Ok(Response::SentTransaction(TransactionDto::from(&transaction)))
+                })
+                .await
+            }
+            AccountMethod::SendWithConditions {
+                addresses_with_amount,
+                send_options,
+                options,
+            } => {
+                convert_async_panics(|| async {
+                    let conditional_send = account_handle
+                        .send_with_conditions(
+                            addresses_with_amount
+                                .iter()
+                                .map(AddressWithAmount::try_from)
+                                .collect::<Result<Vec<AddressWithAmount>>>()?,
+                            send_options.clone(),
+                            options.as_ref().map(TransactionOptions::try_from_dto).transpose()?,
+                        )
+                        .await?;
+                    Ok(Response::ConditionalSend(ConditionalSendDto::from(&conditional_send)))
+                })
+                .await
+            }
+            AccountMethod::SendNftWithConditions {
+                addresses_nft_ids,
+                send_options,
+                options,
+            } => {
+                convert_async_panics(|| async {
+                    let conditional_send = account_handle
+                        .send_nft_with_conditions(
+                            addresses_nft_ids.clone(),
+                            send_options.clone(),
+                            options.as_ref().map(TransactionOptions::try_from_dto).transpose()?,
+                        )
+                        .await?;
+                    Ok(Response::ConditionalSend(ConditionalSendDto::from(&conditional_send)))
+                })
+                .await
+            }
             AccountMethod::SetAlias { alias } => {
                 convert_async_panics(|| async {
                     account_handle.set_alias(&alias).await?;
@@ -901,6 +1541,31 @@ impl WalletMessageHandler {
                 })
                 .await
             }
+            AccountMethod::SetMetadata { metadata } => {
+                convert_async_panics(|| async {
+                    account_handle.set_metadata(metadata).await?;
+                    Ok(Response::Ok(()))
+                })
+                .await
+            }
+            AccountMethod::AddAddressesToBucket { bucket, addresses } => {
+                convert_async_panics(|| async {
+                    let addresses = addresses
+                        .iter()
+                        .map(parse_bech32_address)
+                        .collect::<Result<Vec<AddressWrapper>>>()?;
+                    account_handle.add_addresses_to_bucket(&bucket, &addresses).await?;
+                    Ok(Response::Ok(()))
+                })
+                .await
+            }
+            AccountMethod::RemoveAddressBucket { bucket } => {
+                convert_async_panics(|| async {
+                    account_handle.remove_address_bucket(&bucket).await?;
+                    Ok(Response::Ok(()))
+                })
+                .await
+            }
             AccountMethod::SendOutputs { outputs, options } => {
                 convert_async_panics(|| async {
                     let token_supply = account_handle.client.get_token_supply().await?;
@@ -917,12 +1582,140 @@ impl WalletMessageHandler {
                 })
                 .await
             }
+            AccountMethod::SendVesting {
+                address,
+                amount,
+                schedule,
+                options,
+            } => {
+                convert_async_panics(|| async {
+                    let amount =
+                        u64::from_str(&amount).map_err(|_| iota_client::Error::InvalidAmount(amount.clone()))?;
+                    let transaction = account_handle
+                        .send_vesting(
+                            address.clone(),
+                            amount,
+                            schedule.clone(),
+                            options.as_ref().map(TransactionOptions::try_from_dto).transpose()?,
+                        )
+                        .await?;
+                    Ok(Response::SentTransaction(TransactionDto::from(&transaction)))
+                })
+                .await
+            }
+            AccountMethod::SendBatch { recipients, options } => {
+                convert_async_panics(|| async {
+                    let batch_send = account_handle
+                        .send_batch(
+                            recipients,
+                            options.as_ref().map(TransactionOptions::try_from_dto).transpose()?,
+                        )
+                        .await?;
+                    Ok(Response::BatchSend(BatchSendDto::from(&batch_send)))
+                })
+                .await
+            }
+            AccountMethod::EstimateSend {
+                amount,
+                address,
+                options,
+            } => {
+                let amount = u64::from_str(&amount).map_err(|_| iota_client::Error::InvalidAmount(amount.clone()))?;
+                Ok(Response::SendEstimate(
+                    account_handle
+                        .estimate_send(
+                            amount,
+                            &address,
+                            options.as_ref().map(TransactionOptions::try_from_dto).transpose()?,
+                        )
+                        .await?,
+                ))
+            }
+            AccountMethod::EstimateMintNativeToken {
+                native_token_options,
+                options,
+            } => Ok(Response::NativeTokenEstimate(
+                account_handle
+                    .estimate_mint_native_token(
+                        NativeTokenOptions::try_from(&native_token_options)?,
+                        options.as_ref().map(TransactionOptions::try_from_dto).transpose()?,
+                    )
+                    .await?,
+            )),
+            AccountMethod::EstimateIncreaseNativeTokenSupply {
+                token_id,
+                mint_amount,
+                increase_native_token_supply_options,
+                options,
+            } => Ok(Response::NativeTokenEstimate(
+                account_handle
+                    .estimate_increase_native_token_supply(
+                        TokenId::try_from(&token_id)?,
+                        U256::try_from(&mint_amount).map_err(|_| DtoError::InvalidField("mint_amount"))?,
+                        increase_native_token_supply_options
+                            .as_ref()
+                            .map(IncreaseNativeTokenSupplyOptions::try_from)
+                            .transpose()?,
+                        options.as_ref().map(TransactionOptions::try_from_dto).transpose()?,
+                    )
+                    .await?,
+            )),
+            AccountMethod::EstimateDecreaseNativeTokenSupply {
+                token_id,
+                melt_amount,
+                options,
+            } => Ok(Response::NativeTokenEstimate(
+                account_handle
+                    .estimate_decrease_native_token_supply(
+                        TokenId::try_from(&token_id)?,
+                        U256::try_from(&melt_amount).map_err(|_| DtoError::InvalidField("melt_amount"))?,
+                        options.as_ref().map(TransactionOptions::try_from_dto).transpose()?,
+                    )
+                    .await?,
+            )),
+            AccountMethod::EstimateBurnNativeToken {
+                token_id,
+                burn_amount,
+                options,
+            } => Ok(Response::NativeTokenEstimate(
+                account_handle
+                    .estimate_burn_native_token(
+                        TokenId::try_from(&token_id)?,
+                        U256::try_from(&burn_amount).map_err(|_| DtoError::InvalidField("burn_amount"))?,
+                        options.as_ref().map(TransactionOptions::try_from_dto).transpose()?,
+                    )
+                    .await?,
+            )),
+            AccountMethod::SendAll {
+                address,
+                include_native_tokens,
+                options,
+            } => {
+                convert_async_panics(|| async {
+                    let transaction = account_handle
+                        .send_all(
+                            &address,
+                            include_native_tokens,
+                            options.as_ref().map(TransactionOptions::try_from_dto).transpose()?,
+                        )
+                        .await?;
+                    Ok(Response::SentTransaction(TransactionDto::from(&transaction)))
+                })
+                .await
+            }
+            AccountMethod::Sweep { address, options } => {
+                convert_async_panics(|| async {
+                    let sweep = account_handle.sweep(&address, options).await?;
+                    Ok(Response::Sweep(SweepDto::from(&sweep)))
+                })
+                .await
+            }
             AccountMethod::SignTransactionEssence {
                 prepared_transaction_data,
             } => {
                 convert_async_panics(|| async {
                     let signed_transaction_data = account_handle
-                        .sign_transaction_essence(&PreparedTransactionData::try_from_dto(
+                        .sign_transaction(&PreparedTransactionData::try_from_dto(
                             &prepared_transaction_data,
                             &account_handle.client.get_protocol_parameters().await?,
                         )?)
@@ -941,13 +1734,105 @@ impl WalletMessageHandler {
                         &signed_transaction_data,
                         &account_handle.client.get_protocol_parameters().await?,
                     )?;
-                    let transaction = account_handle
-                        .submit_and_store_transaction(signed_transaction_data)
+                    let transaction = account_handle.submit_transaction(signed_transaction_data).await?;
+                    Ok(Response::SentTransaction(TransactionDto::from(&transaction)))
+                })
+                .await
+            }
+            AccountMethod::DiscardPreparedTransaction {
+                prepared_transaction_data,
+            } => {
+                convert_async_panics(|| async {
+                    account_handle
+                        .discard_prepared_transaction(PreparedTransactionData::try_from_dto(
+                            &prepared_transaction_data,
+                            &account_handle.client.get_protocol_parameters().await?,
+                        )?)
+                        .await?;
+                    Ok(Response::Ok(()))
+                })
+                .await
+            }
+            AccountMethod::CreateSignRequest {
+                prepared_transaction_data,
+            } => {
+                convert_async_panics(|| async {
+                    let sign_request = account_handle
+                        .create_sign_request(&PreparedTransactionData::try_from_dto(
+                            &prepared_transaction_data,
+                            &account_handle.client.get_protocol_parameters().await?,
+                        )?)
                         .await?;
+                    Ok(Response::SignRequest(sign_request))
+                })
+                .await
+            }
+            AccountMethod::SignRequest { sign_request } => {
+                convert_async_panics(|| async {
+                    let sign_response = account_handle.sign_request(&sign_request).await?;
+                    Ok(Response::SignResponse(sign_response))
+                })
+                .await
+            }
+            AccountMethod::SubmitSignResponse { sign_response } => {
+                convert_async_panics(|| async {
+                    let transaction = account_handle.submit_sign_response(&sign_response).await?;
                     Ok(Response::SentTransaction(TransactionDto::from(&transaction)))
                 })
                 .await
             }
+            AccountMethod::CreateSwapOffer {
+                counterparty,
+                offered,
+                requested,
+                options,
+            } => {
+                convert_async_panics(|| async {
+                    let offer = account_handle
+                        .create_swap_offer(
+                            counterparty.clone(),
+                            offered.clone(),
+                            requested.clone(),
+                            options.as_ref().map(TransactionOptions::try_from_dto).transpose()?,
+                        )
+                        .await?;
+                    Ok(Response::SwapOffer(offer))
+                })
+                .await
+            }
+            AccountMethod::AcceptSwapOffer { offer, options } => {
+                convert_async_panics(|| async {
+                    let acceptance = account_handle
+                        .accept_swap_offer(
+                            &offer,
+                            options.as_ref().map(TransactionOptions::try_from_dto).transpose()?,
+                        )
+                        .await?;
+                    Ok(Response::SwapAcceptance(acceptance))
+                })
+                .await
+            }
+            AccountMethod::CompleteSwap { offer, acceptance } => {
+                convert_async_panics(|| async {
+                    let transaction = account_handle.complete_swap(&offer, &acceptance).await?;
+                    Ok(Response::SentTransaction(TransactionDto::from(&transaction)))
+                })
+                .await
+            }
+            AccountMethod::CancelSwapOffer { offer } => {
+                convert_async_panics(|| async {
+                    account_handle.cancel_swap_offer(&offer).await?;
+                    Ok(Response::Ok(()))
+                })
+                .await
+            }
+            AccountMethod::CancelSwapAcceptance { acceptance } => {
+                convert_async_panics(|| async {
+                    account_handle.cancel_swap_acceptance(&acceptance).await?;
+                    Ok(Response::Ok(()))
+                })
+                .await
+            }
             AccountMethod::ClaimOutputs { output_ids_to_claim } => {
                 convert_async_panics(|| async {
                     let transaction = account_handle.claim_outputs(output_ids_to_claim.to_vec()).await?;
@@ -955,6 +1840,61 @@ impl WalletMessageHandler {
                 })
                 .await
             }
+            AccountMethod::ClaimOutputsInBatches { output_ids_to_claim } => {
+                convert_async_panics(|| async {
+                    let claim_batch = account_handle
+                        .claim_outputs_in_batches(output_ids_to_claim.to_vec())
+                        .await?;
+                    Ok(Response::ClaimBatch(ClaimBatchDto::from(&claim_batch)))
+                })
+                .await
+            }
+            #[cfg(feature = "storage")]
+            AccountMethod::QueueTransaction { outputs, options } => {
+                convert_async_panics(|| async {
+                    let token_supply = account_handle.client.get_token_supply().await?;
+                    let queued_transaction = account_handle
+                        .queue_transaction(
+                            outputs
+                                .iter()
+                                .map(|o| Ok(Output::try_from_dto(o, token_supply)?))
+                                .collect::<crate::Result<Vec<Output>>>()?,
+                            options.as_ref().map(TransactionOptions::try_from_dto).transpose()?,
+                        )
+                        .await?;
+                    Ok(Response::QueuedTransaction(queued_transaction))
+                })
+                .await
+            }
+            #[cfg(feature = "storage")]
+            AccountMethod::QueueClaimOutputs { output_ids_to_claim } => {
+                convert_async_panics(|| async {
+                    let queued_transaction = account_handle.queue_claim_outputs(output_ids_to_claim.to_vec()).await?;
+                    Ok(Response::QueuedTransaction(queued_transaction))
+                })
+                .await
+            }
+            #[cfg(feature = "storage")]
+            AccountMethod::GetQueuedTransactions => Ok(Response::QueuedTransactions(
+                account_handle.queued_transactions().await?,
+            )),
+            #[cfg(feature = "storage")]
+            AccountMethod::RemoveQueuedTransaction { transaction_id } => {
+                account_handle.remove_queued_transaction(&transaction_id).await?;
+                Ok(Response::Ok(()))
+            }
+            #[cfg(feature = "storage")]
+            AccountMethod::SubmitQueuedTransactions => {
+                convert_async_panics(|| async {
+                    let queue_submission = account_handle.submit_queued_transactions().await?;
+                    Ok(Response::QueueSubmission(QueueSubmissionDto::from(&queue_submission)))
+                })
+                .await
+            }
+            #[cfg(feature = "storage")]
+            AccountMethod::GetInputReservations => {
+                Ok(Response::InputReservations(account_handle.input_reservations().await?))
+            }
             #[cfg(feature = "participation")]
             AccountMethod::Vote { event_id, answers } => {
                 convert_async_panics(|| async {
@@ -1059,6 +1999,46 @@ impl WalletMessageHandler {
                 })
                 .await
             }
+            #[cfg(feature = "participation")]
+            AccountMethod::GetActiveParticipationEvents => {
+                convert_async_panics(|| async {
+                    let events = account_handle.get_active_participation_events().await?;
+                    Ok(Response::ActiveParticipationEvents(events))
+                })
+                .await
+            }
+            #[cfg(feature = "participation")]
+            AccountMethod::GetParticipationState => {
+                convert_async_panics(|| async {
+                    let state = account_handle.get_participation_state().await?;
+                    Ok(Response::ParticipationState(state))
+                })
+                .await
+            }
+            #[cfg(feature = "participation")]
+            AccountMethod::UpdateParticipationState => {
+                convert_async_panics(|| async {
+                    let state = account_handle.update_participation_state().await?;
+                    Ok(Response::ParticipationState(Some(state)))
+                })
+                .await
+            }
+            #[cfg(feature = "participation")]
+            AccountMethod::Stake { event_id } => {
+                convert_async_panics(|| async {
+                    let transaction = account_handle.stake(event_id).await?;
+                    Ok(Response::SentTransaction(TransactionDto::from(&transaction)))
+                })
+                .await
+            }
+            #[cfg(feature = "participation")]
+            AccountMethod::GetStakingRewards => {
+                convert_async_panics(|| async {
+                    let staking_rewards = account_handle.staking_rewards().await?;
+                    Ok(Response::StakingRewards(staking_rewards))
+                })
+                .await
+            }
             AccountMethod::RequestFundsFromFaucet { url, address } => {
                 convert_async_panics(|| async {
                     Ok(Response::Faucet(request_funds_from_faucet(&url, &address).await?))
@@ -1069,7 +2049,13 @@ impl WalletMessageHandler {
     }
 
     /// The create account message handler.
-    async fn create_account(&self, alias: Option<String>, bech32_hrp: Option<String>) -> Result<Response> {
+    async fn create_account(
+        &self,
+        alias: Option<String>,
+        bech32_hrp: Option<String>,
+        coin_type: Option<u32>,
+        client_options: Option<ClientOptions>,
+    ) -> Result<Response> {
         let mut builder = self.account_manager.create_account();
 
         if let Some(alias) = alias {
@@ -1080,6 +2066,14 @@ impl WalletMessageHandler {
             builder = builder.with_bech32_hrp(bech32_hrp);
         }
 
+        if let Some(coin_type) = coin_type {
+            builder = builder.with_coin_type(coin_type);
+        }
+
+        if let Some(client_options) = client_options {
+            builder = builder.with_client_options(client_options);
+        }
+
         match builder.finish().await {
             Ok(account_handle) => {
                 let account = account_handle.read().await;
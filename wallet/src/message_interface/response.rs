@@ -1,6 +1,8 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(any(feature = "participation", feature = "storage"))]
+use std::collections::HashMap;
 use std::fmt::{Debug, Formatter, Result};
 
 #[cfg(feature = "ledger_nano")]
@@ -8,7 +10,7 @@ use iota_client::secret::LedgerNanoStatus;
 use iota_client::{
     api::{PreparedTransactionDataDto, SignedTransactionDataDto},
     block::{
-        output::{dto::OutputDto, OutputId},
+        output::{dto::OutputDto, NftId, OutputId},
         payload::transaction::TransactionId,
         BlockId,
     },
@@ -17,17 +19,38 @@ use iota_client::{
 use serde::Serialize;
 #[cfg(feature = "participation")]
 use {
-    crate::account::operations::participation::{AccountParticipationOverview, ParticipationEventWithNodes},
+    crate::account::operations::participation::{
+        AccountParticipationOverview, ActiveParticipationEvent, ParticipationEventWithNodes, ParticipationState,
+        StakingRewards,
+    },
     iota_client::api_types::plugins::participation::types::{ParticipationEventId, ParticipationEventStatus},
-    std::collections::HashMap,
 };
 
+#[cfg(feature = "ledger_nano")]
+use crate::account_manager::LedgerNanoCapabilities;
+#[cfg(all(feature = "events", feature = "storage"))]
+use crate::storage::event_log::EventLogEntry;
+#[cfg(feature = "storage")]
+use crate::{
+    account::{QueueSubmissionDto, TransactionPageDto},
+    storage::{
+        audit_log::AuditLogEntry, balance_history::BalanceSnapshot, balance_summary::BalanceSummary,
+        input_reservations::InputReservation, offline_queue::QueuedTransaction, scheduled_payments::ScheduledPayment,
+    },
+};
 use crate::{
     account::{
-        operations::transaction::high_level::minting::mint_native_token::MintTokenTransactionDto,
-        types::{address::AccountAddress, AccountBalanceDto, TransactionDto},
-        OutputDataDto,
+        operations::transaction::high_level::{
+            minting::mint_native_token::MintTokenTransactionDto, send_batch::BatchSendDto,
+            send_with_conditions::ConditionalSendDto, sweep::SweepDto,
+        },
+        types::{address::AccountAddress, AccountBalanceDto, AccountStatistics, AliasRecord, TransactionDto},
+        AccountNft, ClaimBatchDto, ClaimableOutput, DustPolicy, ExpiringOutput, MintNftsTransactionDto,
+        NativeTokenEstimate, OutputDataDto, PreparedMintTokenTransactionDto, QuarantinedOutput, SendEstimate,
+        SignRequest, SignResponse, SigningPolicy, SpamFilter, SwapAcceptance, SwapOffer, SyncFreshness, SyncPreview,
+        UpcomingUnlock,
     },
+    account_manager::{DerivationReport, RecoveryReport},
     message_interface::dtos::{AccountDto, AddressWithUnspentOutputsDto},
     Error,
 };
@@ -38,12 +61,18 @@ use crate::{
 pub enum Response {
     /// Response for
     /// [`CreateAccount`](crate::message_interface::Message::CreateAccount),
+    /// [`CreateWatchAccount`](crate::message_interface::Message::CreateWatchAccount),
     /// [`GetAccount`](crate::message_interface::Message::GetAccount)
     Account(AccountDto),
     /// Response for [`GetAccountIndexes`](crate::message_interface::Message::GetAccountIndexes)
     AccountIndexes(Vec<u32>),
     /// Response for [`GetAccounts`](crate::message_interface::Message::GetAccounts)
     Accounts(Vec<AccountDto>),
+    /// Response for
+    /// [`RecoverAccountsWithOptions`](crate::message_interface::Message::RecoverAccountsWithOptions)
+    RecoveredAccounts(Vec<AccountDto>, RecoveryReport),
+    /// Response for [`VerifyDerivations`](crate::message_interface::Message::VerifyDerivations)
+    DerivationReport(DerivationReport),
     /// Response for [`Addresses`](crate::message_interface::AccountMethod::Addresses)
     Addresses(Vec<AccountAddress>),
     /// Response for
@@ -66,6 +95,16 @@ pub enum Response {
     /// Response for
     /// [`GetOutputsWithAdditionalUnlockConditions`](crate::message_interface::AccountMethod::GetOutputsWithAdditionalUnlockConditions)
     OutputIds(Vec<OutputId>),
+    /// Response for [`GetMintedNfts`](crate::message_interface::AccountMethod::GetMintedNfts)
+    NftIds(Vec<NftId>),
+    /// Response for [`GetNfts`](crate::message_interface::AccountMethod::GetNfts)
+    Nfts(Vec<AccountNft>),
+    /// Response for [`GetAliases`](crate::message_interface::AccountMethod::GetAliases)
+    Aliases(Vec<AliasRecord>),
+    /// Response for [`GetSpamFilter`](crate::message_interface::AccountMethod::GetSpamFilter)
+    SpamFilter(SpamFilter),
+    /// Response for [`GetQuarantinedOutputs`](crate::message_interface::AccountMethod::GetQuarantinedOutputs)
+    QuarantinedOutputs(Vec<QuarantinedOutput>),
     /// Response for [`GetOutput`](crate::message_interface::AccountMethod::GetOutput)
     OutputData(Option<Box<OutputDataDto>>),
     /// Response for
@@ -74,19 +113,34 @@ pub enum Response {
     OutputsData(Vec<OutputDataDto>),
     /// Response for
     /// [`PrepareSendAmount`](crate::message_interface::AccountMethod::PrepareSendAmount),
-    /// [`PrepareTransaction`](crate::message_interface::AccountMethod::PrepareTransaction)
+    /// [`PrepareTransaction`](crate::message_interface::AccountMethod::PrepareTransaction),
+    /// [`PrepareIncreaseNativeTokenSupply`](crate::message_interface::AccountMethod::PrepareIncreaseNativeTokenSupply),
+    /// [`PrepareDecreaseNativeTokenSupply`](crate::message_interface::AccountMethod::PrepareDecreaseNativeTokenSupply),
+    /// [`PrepareBurnNativeToken`](crate::message_interface::AccountMethod::PrepareBurnNativeToken)
     PreparedTransaction(PreparedTransactionDataDto),
+    /// Response for [`PrepareMintNativeToken`](crate::message_interface::AccountMethod::PrepareMintNativeToken)
+    PreparedMintTokenTransaction(PreparedMintTokenTransactionDto),
     /// Response for
     /// [`GetTransaction`](crate::message_interface::AccountMethod::GetTransaction),
+    /// [`GetTransactionByIdempotencyKey`](crate::message_interface::AccountMethod::GetTransactionByIdempotencyKey)
     Transaction(Option<Box<TransactionDto>>),
     /// Response for
     /// [`Transactions`](crate::message_interface::AccountMethod::Transactions),
+    /// [`FilterTransactions`](crate::message_interface::AccountMethod::FilterTransactions),
     /// [`PendingTransactions`](crate::message_interface::AccountMethod::PendingTransactions)
     Transactions(Vec<TransactionDto>),
     /// Response for
     /// [`SignTransactionEssence`](crate::message_interface::AccountMethod::SignTransactionEssence)
     /// [`SubmitAndStoreTransaction`](crate::message_interface::AccountMethod::SubmitAndStoreTransaction)
     SignedTransactionData(SignedTransactionDataDto),
+    /// Response for [`CreateSignRequest`](crate::message_interface::AccountMethod::CreateSignRequest)
+    SignRequest(SignRequest),
+    /// Response for [`SignRequest`](crate::message_interface::AccountMethod::SignRequest)
+    SignResponse(SignResponse),
+    /// Response for [`CreateSwapOffer`](crate::message_interface::AccountMethod::CreateSwapOffer)
+    SwapOffer(SwapOffer),
+    /// Response for [`AcceptSwapOffer`](crate::message_interface::AccountMethod::AcceptSwapOffer)
+    SwapAcceptance(SwapAcceptance),
     /// GenerateAddress response.
     /// Response for [`GenerateAddresses`](crate::message_interface::AccountMethod::GenerateAddresses)
     GeneratedAddress(Vec<AccountAddress>),
@@ -95,11 +149,50 @@ pub enum Response {
     /// [`SyncAccount`](crate::message_interface::AccountMethod::SyncAccount)
     Balance(AccountBalanceDto),
     /// Response for
+    /// [`GetStatistics`](crate::message_interface::AccountMethod::GetStatistics),
+    /// [`GetTotalStatistics`](crate::message_interface::Message::GetTotalStatistics)
+    AccountStatistics(AccountStatistics),
+    /// Response for [`ExportHistory`](crate::message_interface::AccountMethod::ExportHistory)
+    HistoryExport(String),
+    /// Response for [`SyncPreview`](crate::message_interface::AccountMethod::SyncPreview)
+    SyncPreview(SyncPreview),
+    /// Response for [`GetSyncFreshness`](crate::message_interface::AccountMethod::GetSyncFreshness)
+    SyncFreshness(SyncFreshness),
+    /// Response for [`GetBalanceHistory`](crate::message_interface::AccountMethod::GetBalanceHistory)
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    BalanceHistory(Vec<BalanceSnapshot>),
+    /// Response for [`GetAuditLog`](crate::message_interface::Message::GetAuditLog)
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    AuditLog(Vec<AuditLogEntry>),
+    /// Response for [`GetEventsSince`](crate::message_interface::Message::GetEventsSince)
+    #[cfg(all(feature = "events", feature = "storage"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "events", feature = "storage"))))]
+    EventLog(Vec<EventLogEntry>),
+    /// Response for [`GetTotalBalance`](crate::message_interface::Message::GetTotalBalance)
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    TotalBalance(BalanceSummary),
+    /// Response for [`GetTotalBalanceByCoinType`](crate::message_interface::Message::GetTotalBalanceByCoinType)
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    TotalBalancesByCoinType(HashMap<u32, BalanceSummary>),
+    /// Response for [`GetTotalBalanceByMetadata`](crate::message_interface::Message::GetTotalBalanceByMetadata)
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    TotalBalancesByMetadata(HashMap<String, BalanceSummary>),
+    /// Response for
     /// [`GetLedgerNanoStatus`](crate::message_interface::Message::GetLedgerNanoStatus),
     #[cfg(feature = "ledger_nano")]
     #[cfg_attr(docsrs, doc(cfg(feature = "ledger_nano")))]
     LedgerNanoStatus(LedgerNanoStatus),
     /// Response for
+    /// [`GetLedgerNanoCapabilities`](crate::message_interface::Message::GetLedgerNanoCapabilities),
+    #[cfg(feature = "ledger_nano")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ledger_nano")))]
+    LedgerNanoCapabilities(LedgerNanoCapabilities),
+    /// Response for
     /// [`GetIncomingTransactionData`](crate::message_interface::AccountMethod::GetIncomingTransactionData),
     IncomingTransactionData(Option<Box<(TransactionId, TransactionDto)>>),
     /// Response for
@@ -116,18 +209,93 @@ pub enum Response {
     /// [`SendNativeTokens`](crate::message_interface::AccountMethod::SendNativeTokens),
     /// [`SendNft`](crate::message_interface::AccountMethod::SendNft),
     /// [`SendOutputs`](crate::message_interface::AccountMethod::SendOutputs)
+    /// [`RetryTransaction`](crate::message_interface::AccountMethod::RetryTransaction)
     /// [`SubmitAndStoreTransaction`](crate::message_interface::AccountMethod::SubmitAndStoreTransaction)
     /// [`Vote`](crate::message_interface::AccountMethod::Vote)
     /// [`StopParticipating`](crate::message_interface::AccountMethod::StopParticipating)
     /// [`IncreaseVotingPower`](crate::message_interface::AccountMethod::IncreaseVotingPower)
     /// [`DecreaseVotingPower`](crate::message_interface::AccountMethod::DecreaseVotingPower)
+    /// [`Stake`](crate::message_interface::AccountMethod::Stake)
+    /// [`DeclineOutput`](crate::message_interface::AccountMethod::DeclineOutput)
+    /// [`SendAll`](crate::message_interface::AccountMethod::SendAll)
+    /// [`InternalTransfer`](crate::message_interface::Message::InternalTransfer)
     SentTransaction(TransactionDto),
     /// Response for
     /// [`MintNativeToken`](crate::message_interface::AccountMethod::MintNativeToken),
     MintTokenTransaction(MintTokenTransactionDto),
+    /// Response for [`MintIrc27Nfts`](crate::message_interface::AccountMethod::MintIrc27Nfts)
+    MintNftsTransaction(MintNftsTransactionDto),
+    /// Response for [`SendBatch`](crate::message_interface::AccountMethod::SendBatch)
+    BatchSend(BatchSendDto),
+    /// Response for
+    /// [`SendWithConditions`](crate::message_interface::AccountMethod::SendWithConditions),
+    /// [`SendNftWithConditions`](crate::message_interface::AccountMethod::SendNftWithConditions)
+    ConditionalSend(ConditionalSendDto),
+    /// Response for [`EstimateSend`](crate::message_interface::AccountMethod::EstimateSend)
+    SendEstimate(SendEstimate),
+    /// Response for
+    /// [`EstimateMintNativeToken`](crate::message_interface::AccountMethod::EstimateMintNativeToken),
+    /// [`EstimateIncreaseNativeTokenSupply`](crate::message_interface::AccountMethod::EstimateIncreaseNativeTokenSupply),
+    /// [`EstimateDecreaseNativeTokenSupply`](crate::message_interface::AccountMethod::EstimateDecreaseNativeTokenSupply),
+    /// [`EstimateBurnNativeToken`](crate::message_interface::AccountMethod::EstimateBurnNativeToken)
+    NativeTokenEstimate(NativeTokenEstimate),
+    /// Response for [`Sweep`](crate::message_interface::AccountMethod::Sweep)
+    Sweep(SweepDto),
+    /// Response for [`ClaimOutputsInBatches`](crate::message_interface::AccountMethod::ClaimOutputsInBatches)
+    ClaimBatch(ClaimBatchDto),
+    /// Response for [`GetClaimableOutputs`](crate::message_interface::AccountMethod::GetClaimableOutputs)
+    ClaimableOutputs(Vec<ClaimableOutput>),
+    /// Response for [`GetExpiringOutputs`](crate::message_interface::AccountMethod::GetExpiringOutputs)
+    ExpiringOutputs(Vec<ExpiringOutput>),
+    /// Response for [`GetUpcomingUnlocks`](crate::message_interface::AccountMethod::GetUpcomingUnlocks)
+    UpcomingUnlocks(Vec<UpcomingUnlock>),
     /// Response for
     /// [`IsStrongholdPasswordAvailable`](crate::message_interface::Message::IsStrongholdPasswordAvailable)
     StrongholdPasswordIsAvailable(bool),
+    /// Response for [`IsOfflineMode`](crate::message_interface::Message::IsOfflineMode)
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    OfflineMode(bool),
+    /// Response for
+    /// [`QueueTransaction`](crate::message_interface::AccountMethod::QueueTransaction),
+    /// [`QueueClaimOutputs`](crate::message_interface::AccountMethod::QueueClaimOutputs)
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    QueuedTransaction(QueuedTransaction),
+    /// Response for [`GetQueuedTransactions`](crate::message_interface::AccountMethod::GetQueuedTransactions)
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    QueuedTransactions(Vec<QueuedTransaction>),
+    /// Response for [`SubmitQueuedTransactions`](crate::message_interface::AccountMethod::SubmitQueuedTransactions)
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    QueueSubmission(QueueSubmissionDto),
+    /// Response for [`GetInputReservations`](crate::message_interface::AccountMethod::GetInputReservations)
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    InputReservations(Vec<InputReservation>),
+    /// Response for [`SubmitQueuedTransactions`](crate::message_interface::Message::SubmitQueuedTransactions), by
+    /// account index
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    QueueSubmissions(HashMap<u32, QueueSubmissionDto>),
+    /// Response for [`GetTransactionsPaged`](crate::message_interface::AccountMethod::GetTransactionsPaged)
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    TransactionPage(TransactionPageDto),
+    /// Response for [`SchedulePayment`](crate::message_interface::Message::SchedulePayment)
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    ScheduledPayment(ScheduledPayment),
+    /// Response for [`GetScheduledPayments`](crate::message_interface::Message::GetScheduledPayments)
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    ScheduledPayments(Vec<ScheduledPayment>),
+    /// Response for
+    /// [`VerifyAddressOnDevice`](crate::message_interface::AccountMethod::VerifyAddressOnDevice)
+    #[cfg(feature = "ledger_nano")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ledger_nano")))]
+    AddressVerifiedOnDevice(bool),
     /// An error occurred.
     Error(Error),
     /// A panic occurred.
@@ -136,6 +304,10 @@ pub enum Response {
     GeneratedMnemonic(String),
     /// Response for [`GetNodeInfo`](crate::message_interface::Message::GetNodeInfo)
     NodeInfo(NodeInfoWrapper),
+    /// Response for [`GetSigningPolicy`](crate::message_interface::Message::GetSigningPolicy)
+    SigningPolicy(SigningPolicy),
+    /// Response for [`GetDustPolicy`](crate::message_interface::Message::GetDustPolicy)
+    DustPolicy(DustPolicy),
     /// Response for
     /// [`GetParticipationEvent`](crate::message_interface::AccountMethod::GetParticipationEvent)
     /// [`RegisterParticipationEvent`](crate::message_interface::AccountMethod::RegisterParticipationEvent)
@@ -167,6 +339,22 @@ pub enum Response {
     #[cfg(feature = "participation")]
     #[cfg_attr(docsrs, doc(cfg(feature = "participation")))]
     AccountParticipationOverview(AccountParticipationOverview),
+    /// Response for
+    /// [`GetActiveParticipationEvents`](crate::message_interface::AccountMethod::GetActiveParticipationEvents)
+    #[cfg(feature = "participation")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "participation")))]
+    ActiveParticipationEvents(Vec<ActiveParticipationEvent>),
+    /// Response for
+    /// [`GetParticipationState`](crate::message_interface::AccountMethod::GetParticipationState),
+    /// [`UpdateParticipationState`](crate::message_interface::AccountMethod::UpdateParticipationState)
+    #[cfg(feature = "participation")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "participation")))]
+    ParticipationState(Option<ParticipationState>),
+    /// Response for
+    /// [`GetStakingRewards`](crate::message_interface::AccountMethod::GetStakingRewards)
+    #[cfg(feature = "participation")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "participation")))]
+    StakingRewards(Vec<StakingRewards>),
     /// Response for [`Bech32ToHex`](crate::message_interface::Message::Bech32ToHex)
     HexAddress(String),
     /// Response for [`HexToBech32`](crate::message_interface::Message::HexToBech32)
@@ -181,11 +369,27 @@ pub enum Response {
     /// [`RestoreBackup`](crate::message_interface::Message::RestoreBackup),
     /// [`VerifyMnemonic`](crate::message_interface::Message::VerifyMnemonic),
     /// [`SetClientOptions`](crate::message_interface::Message::SetClientOptions),
+    /// [`SetRequestLimits`](crate::message_interface::Message::SetRequestLimits),
+    /// [`SetOfflineMode`](crate::message_interface::Message::SetOfflineMode),
+    /// [`CancelScheduledPayment`](crate::message_interface::Message::CancelScheduledPayment),
+    /// [`DiscardPreparedTransaction`](crate::message_interface::AccountMethod::DiscardPreparedTransaction),
+    /// [`RemoveQueuedTransaction`](crate::message_interface::AccountMethod::RemoveQueuedTransaction),
+    /// [`SetTransactionNote`](crate::message_interface::AccountMethod::SetTransactionNote),
+    /// [`SetTransactionMaxPending`](crate::message_interface::AccountMethod::SetTransactionMaxPending),
+    /// [`SetTransactionLabels`](crate::message_interface::AccountMethod::SetTransactionLabels),
     /// [`SetStrongholdPassword`](crate::message_interface::Message::SetStrongholdPassword),
     /// [`SetStrongholdPasswordClearInterval`](crate::message_interface::Message::SetStrongholdPasswordClearInterval),
     /// [`StoreMnemonic`](crate::message_interface::Message::StoreMnemonic),
     /// [`StartBackgroundSync`](crate::message_interface::Message::StartBackgroundSync),
     /// [`StopBackgroundSync`](crate::message_interface::Message::StopBackgroundSync),
+    /// [`StartMqttSync`](crate::message_interface::Message::StartMqttSync),
+    /// [`StopMqttSync`](crate::message_interface::Message::StopMqttSync),
+    /// [`PauseBackgroundSync`](crate::message_interface::Message::PauseBackgroundSync),
+    /// [`ResumeBackgroundSync`](crate::message_interface::Message::ResumeBackgroundSync),
+    /// [`SetBackgroundSyncInterval`](crate::message_interface::Message::SetBackgroundSyncInterval),
+    /// [`SetConsolidationPolicy`](crate::message_interface::Message::SetConsolidationPolicy),
+    /// [`SetAutoClaim`](crate::message_interface::Message::SetAutoClaim),
+    /// [`SetExpirationWarningPeriod`](crate::message_interface::Message::SetExpirationWarningPeriod),
     /// [`EmitTestEvent`](crate::message_interface::Message::EmitTestEvent),
     Ok(()),
 }
@@ -197,6 +401,8 @@ impl Debug for Response {
             Self::Account(account) => write!(f, "Account({account:?})"),
             Self::AccountIndexes(account_indexes) => write!(f, "AccountIndexes({account_indexes:?})"),
             Self::Accounts(accounts) => write!(f, "Accounts({accounts:?})"),
+            Self::RecoveredAccounts(accounts, report) => write!(f, "RecoveredAccounts({accounts:?}, {report:?})"),
+            Self::DerivationReport(report) => write!(f, "DerivationReport({report:?})"),
             Self::Addresses(addresses) => write!(f, "Addresses({addresses:?})"),
             Self::AddressesWithUnspentOutputs(addresses) => {
                 write!(f, "AddressesWithUnspentOutputs({addresses:?})")
@@ -205,18 +411,36 @@ impl Debug for Response {
             Self::Output(output) => write!(f, "Output({output:?})"),
             Self::MinimumRequiredStorageDeposit(amount) => write!(f, "MinimumRequiredStorageDeposit({amount:?})"),
             Self::OutputIds(output_ids) => write!(f, "OutputIds({output_ids:?})"),
+            Self::NftIds(nft_ids) => write!(f, "NftIds({nft_ids:?})"),
+            Self::Nfts(nfts) => write!(f, "Nfts({nfts:?})"),
+            Self::Aliases(aliases) => write!(f, "Aliases({aliases:?})"),
+            Self::SpamFilter(spam_filter) => write!(f, "SpamFilter({spam_filter:?})"),
+            Self::QuarantinedOutputs(outputs) => write!(f, "QuarantinedOutputs({outputs:?})"),
             Self::OutputData(output) => write!(f, "OutputData({output:?})"),
             Self::OutputsData(outputs) => write!(f, "OutputsData{outputs:?}"),
             Self::PreparedTransaction(transaction_data) => {
                 write!(f, "PreparedTransaction({transaction_data:?})")
             }
+            Self::PreparedMintTokenTransaction(transaction_data) => {
+                write!(f, "PreparedMintTokenTransaction({transaction_data:?})")
+            }
             Self::Transaction(transaction) => write!(f, "Transaction({transaction:?})"),
             Self::Transactions(transactions) => write!(f, "Transactions({transactions:?})"),
             Self::SignedTransactionData(signed_transaction_data) => {
                 write!(f, "SignedTransactionData({signed_transaction_data:?})")
             }
+            Self::SignRequest(sign_request) => write!(f, "SignRequest({sign_request:?})"),
+            Self::SignResponse(sign_response) => write!(f, "SignResponse({sign_response:?})"),
+            Self::SwapOffer(offer) => write!(f, "SwapOffer({offer:?})"),
+            Self::SwapAcceptance(acceptance) => write!(f, "SwapAcceptance({acceptance:?})"),
             Self::GeneratedAddress(addresses) => write!(f, "GeneratedAddress({addresses:?})"),
             Self::Balance(balance) => write!(f, "Balance({balance:?})"),
+            Self::AccountStatistics(statistics) => write!(f, "AccountStatistics({statistics:?})"),
+            Self::HistoryExport(history_export) => write!(f, "HistoryExport({history_export})"),
+            Self::SyncPreview(sync_preview) => write!(f, "SyncPreview({sync_preview:?})"),
+            Self::SyncFreshness(sync_freshness) => write!(f, "SyncFreshness({sync_freshness:?})"),
+            #[cfg(feature = "storage")]
+            Self::BalanceHistory(balance_history) => write!(f, "BalanceHistory({balance_history:?})"),
             Self::IncomingTransactionData(transaction_data) => {
                 write!(f, "IncomingTransactionData({transaction_data:?})")
             }
@@ -227,15 +451,61 @@ impl Debug for Response {
             Self::MintTokenTransaction(mint_transaction) => {
                 write!(f, "MintTokenTransaction({mint_transaction:?})")
             }
+            Self::MintNftsTransaction(mint_transaction) => {
+                write!(f, "MintNftsTransaction({mint_transaction:?})")
+            }
+            Self::BatchSend(batch_send) => write!(f, "BatchSend({batch_send:?})"),
+            Self::ConditionalSend(conditional_send) => write!(f, "ConditionalSend({conditional_send:?})"),
+            Self::SendEstimate(send_estimate) => write!(f, "SendEstimate({send_estimate:?})"),
+            Self::NativeTokenEstimate(estimate) => write!(f, "NativeTokenEstimate({estimate:?})"),
+            Self::Sweep(sweep) => write!(f, "Sweep({sweep:?})"),
+            Self::ClaimBatch(claim_batch) => write!(f, "ClaimBatch({claim_batch:?})"),
+            Self::ClaimableOutputs(claimable_outputs) => write!(f, "ClaimableOutputs({claimable_outputs:?})"),
+            Self::ExpiringOutputs(expiring_outputs) => write!(f, "ExpiringOutputs({expiring_outputs:?})"),
+            Self::UpcomingUnlocks(upcoming_unlocks) => write!(f, "UpcomingUnlocks({upcoming_unlocks:?})"),
             Self::StrongholdPasswordIsAvailable(is_available) => {
                 write!(f, "StrongholdPasswordIsAvailable({is_available:?})")
             }
+            #[cfg(feature = "storage")]
+            Self::OfflineMode(offline) => write!(f, "OfflineMode({offline:?})"),
+            #[cfg(feature = "storage")]
+            Self::QueuedTransaction(queued_transaction) => write!(f, "QueuedTransaction({queued_transaction:?})"),
+            #[cfg(feature = "storage")]
+            Self::QueuedTransactions(queued_transactions) => write!(f, "QueuedTransactions({queued_transactions:?})"),
+            #[cfg(feature = "storage")]
+            Self::QueueSubmission(queue_submission) => write!(f, "QueueSubmission({queue_submission:?})"),
+            #[cfg(feature = "storage")]
+            Self::InputReservations(input_reservations) => write!(f, "InputReservations({input_reservations:?})"),
+            #[cfg(feature = "storage")]
+            Self::QueueSubmissions(queue_submissions) => write!(f, "QueueSubmissions({queue_submissions:?})"),
+            #[cfg(feature = "storage")]
+            Self::TransactionPage(page) => write!(f, "TransactionPage({page:?})"),
+            #[cfg(feature = "storage")]
+            Self::ScheduledPayment(scheduled_payment) => write!(f, "ScheduledPayment({scheduled_payment:?})"),
+            #[cfg(feature = "storage")]
+            Self::ScheduledPayments(scheduled_payments) => write!(f, "ScheduledPayments({scheduled_payments:?})"),
+            #[cfg(feature = "ledger_nano")]
+            Self::AddressVerifiedOnDevice(verified) => write!(f, "AddressVerifiedOnDevice({verified:?})"),
             Self::Error(error) => write!(f, "Error({error:?})"),
             Self::Panic(panic_msg) => write!(f, "Panic({panic_msg:?})"),
             Self::GeneratedMnemonic(_) => write!(f, "GeneratedMnemonic(<omitted>)"),
+            #[cfg(feature = "storage")]
+            Self::AuditLog(audit_log) => write!(f, "AuditLog({audit_log:?})"),
+            #[cfg(all(feature = "events", feature = "storage"))]
+            Self::EventLog(event_log) => write!(f, "EventLog({event_log:?})"),
+            #[cfg(feature = "storage")]
+            Self::TotalBalance(total_balance) => write!(f, "TotalBalance({total_balance:?})"),
+            #[cfg(feature = "storage")]
+            Self::TotalBalancesByCoinType(total_balances) => write!(f, "TotalBalancesByCoinType({total_balances:?})"),
+            #[cfg(feature = "storage")]
+            Self::TotalBalancesByMetadata(total_balances) => write!(f, "TotalBalancesByMetadata({total_balances:?})"),
             #[cfg(feature = "ledger_nano")]
             Self::LedgerNanoStatus(ledger_nano_status) => write!(f, "LedgerNanoStatus({ledger_nano_status:?})"),
+            #[cfg(feature = "ledger_nano")]
+            Self::LedgerNanoCapabilities(capabilities) => write!(f, "LedgerNanoCapabilities({capabilities:?})"),
             Self::NodeInfo(info) => write!(f, "NodeInfo({info:?})"),
+            Self::SigningPolicy(signing_policy) => write!(f, "SigningPolicy({signing_policy:?})"),
+            Self::DustPolicy(dust_policy) => write!(f, "DustPolicy({dust_policy:?})"),
             Self::HexAddress(hex_address) => write!(f, "Hex encoded address({hex_address:?})"),
             Self::Bech32Address(bech32_address) => write!(f, "Bech32 encoded address({bech32_address:?})"),
             Self::Ok(()) => write!(f, "Ok(())"),
@@ -253,6 +523,12 @@ impl Debug for Response {
             Self::AccountParticipationOverview(overview) => {
                 write!(f, "AccountParticipationOverview({overview:?})")
             }
+            #[cfg(feature = "participation")]
+            Self::ActiveParticipationEvents(events) => write!(f, "ActiveParticipationEvents({events:?})"),
+            #[cfg(feature = "participation")]
+            Self::ParticipationState(state) => write!(f, "ParticipationState({state:?})"),
+            #[cfg(feature = "participation")]
+            Self::StakingRewards(staking_rewards) => write!(f, "StakingRewards({staking_rewards:?})"),
             Self::Faucet(response) => write!(f, "Faucet({response:?})"),
         }
     }
@@ -20,6 +20,7 @@ impl Debug for JammdbStorageAdapter{
 /// Key value storage adapter.
 pub struct JammdbStorageAdapter {
     db: Arc<Mutex<DB>>,
+    path: PathBuf,
 }
 
 impl JammdbStorageAdapter {
@@ -33,18 +34,25 @@ impl JammdbStorageAdapter {
 
         }
         db_path = temp_path;
-        let db = OpenOptions::new().pagesize(4096).num_pages(32).open(db_path)?;
-        // create a default bucket
-        let tx = db.tx(true)?;
-        let bucket = tx.get_or_create_bucket(BUCKET_NAME)?;
-        bucket.put("INITIAL_KEY", "INIT_VALUE")?; // needs some initial value
-        tx.commit()?;
+        let db = open_db(&db_path)?;
         Ok(Self {
             db: Arc::new(Mutex::new(db)),
+            path: db_path,
         })
     }
 }
 
+// Opens the database file and makes sure the default bucket exists
+fn open_db(path: &Path) -> crate::Result<DB> {
+    let db = OpenOptions::new().pagesize(4096).num_pages(32).open(path)?;
+    // create a default bucket
+    let tx = db.tx(true)?;
+    let bucket = tx.get_or_create_bucket(BUCKET_NAME)?;
+    bucket.put("INITIAL_KEY", "INIT_VALUE")?; // needs some initial value
+    tx.commit()?;
+    Ok(db)
+}
+
 #[async_trait::async_trait]
 impl StorageAdapter for JammdbStorageAdapter {
     fn id(&self) -> &'static str {
@@ -90,4 +98,58 @@ impl StorageAdapter for JammdbStorageAdapter {
         tx.commit()?;
         Ok(())
     }
+
+    async fn batch_write(&mut self, records: HashMap<String, String>, removals: Vec<String>) -> crate::Result<()> {
+        let db = self.db.lock().await;
+        let tx = db.tx(true)?;
+        let bucket = tx.get_bucket(BUCKET_NAME)?;
+        for (key, value) in records {
+            bucket.put(key, value)?;
+        }
+        for key in removals {
+            // Deleting a missing key is an error in JammDB
+            if bucket.get(&key).is_some() {
+                bucket.delete(key)?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// JammDB never shrinks its file, so all records are copied into a new file which then replaces the old one.
+    async fn compact(&mut self) -> crate::Result<()> {
+        let mut db = self.db.lock().await;
+        let mut compacted_path = self.path.clone().into_os_string();
+        compacted_path.push(".compact");
+        let compacted_path = PathBuf::from(compacted_path);
+        // Remove leftovers from a previously interrupted compaction
+        if compacted_path.exists() {
+            std::fs::remove_file(&compacted_path)?;
+        }
+
+        {
+            let compacted_db = open_db(&compacted_path)?;
+            let compacted_tx = compacted_db.tx(true)?;
+            let compacted_bucket = compacted_tx.get_bucket(BUCKET_NAME)?;
+            let tx = db.tx(false)?;
+            let bucket = tx.get_bucket(BUCKET_NAME)?;
+            for data in bucket.cursor() {
+                if data.is_kv() {
+                    let kv = data.kv();
+                    compacted_bucket.put(kv.key().to_vec(), kv.value().to_vec())?;
+                }
+            }
+            compacted_tx.commit()?;
+        }
+
+        std::fs::rename(&compacted_path, &self.path)?;
+        *db = open_db(&self.path)?;
+        Ok(())
+    }
+
+    async fn size_on_disk(&self) -> crate::Result<Option<u64>> {
+        // Lock so the size isn't read during a compaction
+        let _db = self.db.lock().await;
+        Ok(Some(std::fs::metadata(&self.path)?.len()))
+    }
 }
@@ -40,4 +40,13 @@ impl StorageAdapter for Memory {
         self.0.remove(key);
         Ok(())
     }
+
+    /// Writes and removes records.
+    async fn batch_write(&mut self, records: HashMap<String, String>, removals: Vec<String>) -> crate::Result<()> {
+        self.0.extend(records.into_iter());
+        for key in removals {
+            self.0.remove(&key);
+        }
+        Ok(())
+    }
 }
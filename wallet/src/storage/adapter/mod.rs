@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod memory;
+/// Storage adapter for the records of a profile.
+pub(crate) mod profile;
 /// RocksDB storage adapter.
 #[cfg(feature = "rocksdb")]
 #[cfg_attr(docsrs, doc(cfg(feature = "rocksdb")))]
@@ -32,4 +34,24 @@ pub trait StorageAdapter: std::fmt::Debug {
 
     /// Removes a record from the storage.
     async fn remove(&mut self, key: &str) -> crate::Result<()>;
+
+    /// Writes and removes records. The default implementation applies the changes one after another, adapters that
+    /// support transactions should override it, so either all or none of the changes are applied.
+    async fn batch_write(&mut self, records: HashMap<String, String>, removals: Vec<String>) -> crate::Result<()> {
+        self.batch_set(records).await?;
+        for key in removals {
+            self.remove(&key).await?;
+        }
+        Ok(())
+    }
+
+    /// Reclaims space that is no longer used after records got removed. Does nothing by default.
+    async fn compact(&mut self) -> crate::Result<()> {
+        Ok(())
+    }
+
+    /// Returns the size of the storage on disk in bytes, if the adapter is able to determine it.
+    async fn size_on_disk(&self) -> crate::Result<Option<u64>> {
+        Ok(None)
+    }
 }
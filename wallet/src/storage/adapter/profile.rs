@@ -0,0 +1,94 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use super::StorageAdapter;
+use crate::storage::{constants::PROFILE_PREFIX, manager::StorageManagerHandle};
+
+// The storage id.
+const STORAGE_ID: &str = "Profile";
+
+/// A storage adapter that stores the records of a profile in the storage of another account manager, with the profile
+/// name as prefix of the keys.
+#[derive(Debug)]
+pub(crate) struct ProfileStorageAdapter {
+    storage_manager: StorageManagerHandle,
+    prefix: String,
+}
+
+impl ProfileStorageAdapter {
+    pub(crate) fn new(storage_manager: StorageManagerHandle, profile_name: &str) -> Self {
+        Self {
+            storage_manager,
+            prefix: format!("{PROFILE_PREFIX}{profile_name}:"),
+        }
+    }
+
+    fn key(&self, key: &str) -> String {
+        format!("{}{key}", self.prefix)
+    }
+}
+
+// The records are already encrypted by the storage of the profile, so they're written without encryption, but with the
+// byte quota and the hooks of the other storage
+#[async_trait::async_trait]
+impl StorageAdapter for ProfileStorageAdapter {
+    fn id(&self) -> &'static str {
+        STORAGE_ID
+    }
+
+    /// Gets the record associated with the given key from the storage.
+    async fn get(&self, key: &str) -> crate::Result<Option<String>> {
+        self.storage_manager
+            .lock()
+            .await
+            .storage
+            .inner
+            .get(&self.key(key))
+            .await
+    }
+
+    /// Saves or updates a record on the storage.
+    async fn set(&mut self, key: &str, record: String) -> crate::Result<()> {
+        self.storage_manager
+            .lock()
+            .await
+            .storage
+            .set_raw(&self.key(key), record)
+            .await
+    }
+
+    /// Batch writes records to the storage.
+    async fn batch_set(&mut self, records: HashMap<String, String>) -> crate::Result<()> {
+        self.batch_write(records, Vec::new()).await
+    }
+
+    /// Removes a record from the storage.
+    async fn remove(&mut self, key: &str) -> crate::Result<()> {
+        self.storage_manager.lock().await.storage.remove(&self.key(key)).await
+    }
+
+    /// Writes and removes records.
+    async fn batch_write(&mut self, records: HashMap<String, String>, removals: Vec<String>) -> crate::Result<()> {
+        let records = records
+            .into_iter()
+            .map(|(key, record)| (self.key(&key), record))
+            .collect();
+        let removals = removals.iter().map(|key| self.key(key)).collect();
+        self.storage_manager
+            .lock()
+            .await
+            .storage
+            .batch_write_raw(records, removals)
+            .await
+    }
+
+    async fn compact(&mut self) -> crate::Result<()> {
+        self.storage_manager.lock().await.storage.inner.compact().await
+    }
+
+    async fn size_on_disk(&self) -> crate::Result<Option<u64>> {
+        self.storage_manager.lock().await.storage.inner.size_on_disk().await
+    }
+}
@@ -68,4 +68,32 @@ impl StorageAdapter for RocksdbStorageAdapter {
         self.db.lock().await.delete(key.as_bytes())?;
         Ok(())
     }
+
+    /// Writes and removes records in a single atomic write batch.
+    async fn batch_write(&mut self, records: HashMap<String, String>, removals: Vec<String>) -> crate::Result<()> {
+        let mut batch = WriteBatch::default();
+        for (key, value) in records {
+            batch.put(key.as_bytes(), value.as_bytes());
+        }
+        for key in removals {
+            batch.delete(key.as_bytes());
+        }
+        self.db.lock().await.write(batch)?;
+        Ok(())
+    }
+
+    /// Compacts the whole key range, so tombstones of removed records get dropped.
+    async fn compact(&mut self) -> crate::Result<()> {
+        self.db.lock().await.compact_range(None::<&[u8]>, None::<&[u8]>);
+        Ok(())
+    }
+
+    /// Returns the total size of the sst files.
+    async fn size_on_disk(&self) -> crate::Result<Option<u64>> {
+        Ok(self
+            .db
+            .lock()
+            .await
+            .property_int_value("rocksdb.total-sst-files-size")?)
+    }
 }
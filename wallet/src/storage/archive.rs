@@ -0,0 +1,140 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use super::manager::{account_keys, StorageManager};
+use crate::{account::Account, storage::constants::*};
+
+impl StorageManager {
+    /// Returns the indexes of the archived accounts.
+    pub async fn archived_account_indexes(&self) -> crate::Result<Vec<u32>> {
+        Ok(self
+            .storage
+            .get(ARCHIVED_ACCOUNTS_INDEXATION_KEY)
+            .await?
+            .unwrap_or_default())
+    }
+
+    /// Returns an archived account.
+    pub async fn get_archived_account(&self, account_index: u32) -> crate::Result<Option<Account>> {
+        self.storage
+            .get(&format!("{ARCHIVED_PREFIX}{ACCOUNT_INDEXATION_KEY}{account_index}"))
+            .await
+    }
+
+    /// Moves all records of an account to the archive, so it's not loaded anymore, but can be restored later.
+    pub async fn archive_account(&mut self, account_index: u32) -> crate::Result<()> {
+        log::debug!("archive_account {account_index}");
+        if !self.account_indexes.contains(&account_index) {
+            return Err(crate::Error::AccountNotFound(account_index.to_string()));
+        }
+
+        let account_indexes = self
+            .account_indexes
+            .iter()
+            .copied()
+            .filter(|index| *index != account_index)
+            .collect::<Vec<_>>();
+        let mut archived_account_indexes = self.archived_account_indexes().await?;
+        // The records of the archived account would be overwritten
+        if archived_account_indexes.contains(&account_index) {
+            return Err(crate::Error::Storage(format!(
+                "can't archive account {account_index}, an archived account with this index already exists"
+            )));
+        }
+        archived_account_indexes.push(account_index);
+        // The alias can be used by other accounts while the account is archived
+        let mut account_aliases = self.account_aliases.clone();
+        account_aliases.retain(|_, index| *index != account_index);
+
+        self.move_account_records(
+            account_index,
+            "",
+            ARCHIVED_PREFIX,
+            &account_indexes,
+            &archived_account_indexes,
+            &account_aliases,
+        )
+        .await?;
+        self.account_indexes = account_indexes;
+        self.account_aliases = account_aliases;
+
+        Ok(())
+    }
+
+    /// Moves the records of an archived account back and returns the account.
+    pub async fn restore_account(&mut self, account_index: u32) -> crate::Result<Account> {
+        log::debug!("restore_account {account_index}");
+        let mut archived_account_indexes = self.archived_account_indexes().await?;
+        if !archived_account_indexes.contains(&account_index) {
+            return Err(crate::Error::AccountNotFound(account_index.to_string()));
+        }
+        // A new account could have been created with the same index in the meantime
+        if self.account_indexes.contains(&account_index) {
+            return Err(crate::Error::Storage(format!(
+                "can't restore account {account_index}, an account with this index already exists"
+            )));
+        }
+        self.check_account_quota(self.account_indexes.len() + 1)?;
+
+        let account = self
+            .get_archived_account(account_index)
+            .await?
+            .ok_or_else(|| crate::Error::AccountNotFound(account_index.to_string()))?;
+
+        let mut account_indexes = self.account_indexes.clone();
+        account_indexes.push(account_index);
+        archived_account_indexes.retain(|index| *index != account_index);
+        let account_aliases = self.account_aliases_with(&account)?;
+
+        self.move_account_records(
+            account_index,
+            ARCHIVED_PREFIX,
+            "",
+            &account_indexes,
+            &archived_account_indexes,
+            &account_aliases,
+        )
+        .await?;
+        self.account_indexes = account_indexes;
+        self.account_aliases = account_aliases;
+
+        Ok(account)
+    }
+
+    // Moves all records of an account from one key prefix to another and updates the account indexes in a single write
+    async fn move_account_records(
+        &mut self,
+        account_index: u32,
+        from_prefix: &str,
+        to_prefix: &str,
+        account_indexes: &[u32],
+        archived_account_indexes: &[u32],
+        account_aliases: &HashMap<String, u32>,
+    ) -> crate::Result<()> {
+        let mut records = HashMap::new();
+        let mut removals = Vec::new();
+        for key in account_keys(account_index) {
+            let from_key = format!("{from_prefix}{key}");
+            if let Some(record) = self.storage.get::<serde_json::Value>(&from_key).await? {
+                records.insert(format!("{to_prefix}{key}"), serde_json::to_string(&record)?);
+                removals.push(from_key);
+            }
+        }
+        records.insert(
+            ACCOUNTS_INDEXATION_KEY.to_string(),
+            serde_json::to_string(account_indexes)?,
+        );
+        records.insert(
+            ARCHIVED_ACCOUNTS_INDEXATION_KEY.to_string(),
+            serde_json::to_string(archived_account_indexes)?,
+        );
+        records.insert(
+            ACCOUNT_ALIASES_INDEXATION_KEY.to_string(),
+            serde_json::to_string(account_aliases)?,
+        );
+
+        self.storage.batch_write(records, removals).await
+    }
+}
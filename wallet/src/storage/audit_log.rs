@@ -0,0 +1,127 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crypto::hashes::{blake2b::Blake2b256, Digest};
+use instant::SystemTime;
+use serde::{Deserialize, Serialize};
+
+use super::{chunked_log::ChunkedLog, manager::StorageManager};
+use crate::storage::constants::{AUDIT_LOG, AUDIT_LOG_CAPACITY, AUDIT_LOG_CHUNK, AUDIT_LOG_CHUNK_SIZE};
+
+/// A sensitive operation that is recorded in the audit log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AuditOperation {
+    /// A transaction was submitted.
+    Send,
+    /// Addresses were generated.
+    GenerateAddresses,
+    /// A Stronghold backup was created.
+    Backup,
+    /// A Stronghold backup was restored.
+    RestoreBackup,
+    /// A portable backup was created.
+    PortableBackup,
+    /// A portable backup was restored.
+    RestorePortableBackup,
+}
+
+/// An entry of the audit log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogEntry {
+    /// Unix timestamp in milliseconds when the operation finished.
+    pub timestamp: u64,
+    /// The account index, if the operation belongs to an account.
+    pub account_index: Option<u32>,
+    /// The operation.
+    pub operation: AuditOperation,
+    /// Hex encoded hash of the parameters of the operation, secrets like passwords are never included.
+    pub parameters_hash: String,
+    /// The error message if the operation failed.
+    pub error: Option<String>,
+}
+
+impl AuditLogEntry {
+    fn new<P: Serialize, T>(
+        account_index: Option<u32>,
+        operation: AuditOperation,
+        parameters: &P,
+        result: &crate::Result<T>,
+    ) -> crate::Result<Self> {
+        Ok(Self {
+            timestamp: SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .expect("time went backwards")
+                .as_millis() as u64,
+            account_index,
+            operation,
+            parameters_hash: prefix_hex::encode(Blake2b256::digest(serde_json::to_vec(parameters)?).to_vec()),
+            error: result.as_ref().err().map(|error| error.to_string()),
+        })
+    }
+}
+
+/// Filter for the entries of the audit log.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogFilter {
+    /// Only return entries of this account.
+    pub account_index: Option<u32>,
+    /// Only return entries of these operations.
+    pub operations: Option<Vec<AuditOperation>>,
+    /// Only return entries with a timestamp greater than or equal to this one.
+    pub from: Option<u64>,
+    /// Only return entries with a timestamp lower than this one.
+    pub to: Option<u64>,
+    /// Only return failed operations.
+    #[serde(default)]
+    pub failed_only: bool,
+}
+
+impl AuditLogFilter {
+    fn matches(&self, entry: &AuditLogEntry) -> bool {
+        self.account_index
+            .map_or(true, |account_index| entry.account_index == Some(account_index))
+            && self
+                .operations
+                .as_ref()
+                .map_or(true, |operations| operations.contains(&entry.operation))
+            && self.from.map_or(true, |from| entry.timestamp >= from)
+            && self.to.map_or(true, |to| entry.timestamp < to)
+            && (!self.failed_only || entry.error.is_some())
+    }
+}
+
+const CHUNKED_AUDIT_LOG: ChunkedLog = ChunkedLog {
+    key: AUDIT_LOG,
+    chunk_key: AUDIT_LOG_CHUNK,
+    chunk_size: AUDIT_LOG_CHUNK_SIZE,
+    capacity: AUDIT_LOG_CAPACITY,
+};
+
+impl StorageManager {
+    /// Returns the entries of the audit log that match the filter, oldest first.
+    pub(crate) async fn get_audit_log(&self, filter: &AuditLogFilter) -> crate::Result<Vec<AuditLogEntry>> {
+        let audit_log: Vec<AuditLogEntry> = CHUNKED_AUDIT_LOG.entries(&self.storage, 1).await?;
+        Ok(audit_log.into_iter().filter(|entry| filter.matches(entry)).collect())
+    }
+
+    /// Returns the keys of the audit log records, with the key prefix of a profile for the audit log of the profile.
+    pub(crate) async fn audit_log_keys(&self, prefix: &str) -> crate::Result<Vec<String>> {
+        CHUNKED_AUDIT_LOG.keys(&self.storage, prefix).await
+    }
+
+    /// Appends an entry with the result of a sensitive operation to the audit log. Entries aren't removed with the
+    /// account they belong to, only the oldest ones are dropped once the log holds more than `AUDIT_LOG_CAPACITY`
+    /// entries.
+    pub(crate) async fn audit<P: Serialize, T>(
+        &mut self,
+        account_index: Option<u32>,
+        operation: AuditOperation,
+        parameters: &P,
+        result: &crate::Result<T>,
+    ) -> crate::Result<()> {
+        let entry = AuditLogEntry::new(account_index, operation, parameters, result)?;
+        CHUNKED_AUDIT_LOG.append(&mut self.storage, |_| entry).await
+    }
+}
@@ -0,0 +1,56 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+
+use super::BackupTarget;
+
+/// A backup target that stores the backups as files in a directory, which is created if it doesn't exist.
+#[derive(Debug, Clone)]
+pub struct FileSystemBackupTarget {
+    directory: PathBuf,
+}
+
+impl FileSystemBackupTarget {
+    /// Creates a backup target for the directory.
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl BackupTarget for FileSystemBackupTarget {
+    async fn put(&self, name: &str, backup: Vec<u8>) -> crate::Result<()> {
+        std::fs::create_dir_all(&self.directory)?;
+        // Written to a temporary file first, so an interrupted write doesn't leave a broken backup
+        let temporary_path = self.directory.join(format!("{name}.tmp"));
+        std::fs::write(&temporary_path, backup)?;
+        std::fs::rename(temporary_path, self.directory.join(name))?;
+        Ok(())
+    }
+
+    async fn list(&self) -> crate::Result<Vec<String>> {
+        if !self.directory.exists() {
+            return Ok(Vec::new());
+        }
+        let mut names = Vec::new();
+        for entry in std::fs::read_dir(&self.directory)? {
+            let entry = entry?;
+            if entry.file_type()?.is_file() {
+                if let Some(name) = entry.file_name().to_str() {
+                    if !name.ends_with(".tmp") {
+                        names.push(name.to_string());
+                    }
+                }
+            }
+        }
+        Ok(names)
+    }
+
+    async fn remove(&self, name: &str) -> crate::Result<()> {
+        std::fs::remove_file(self.directory.join(name))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,23 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+/// Backup target for a local directory.
+pub mod filesystem;
+/// Backup target for S3 compatible object storages.
+#[cfg(feature = "s3_backup")]
+#[cfg_attr(docsrs, doc(cfg(feature = "s3_backup")))]
+pub mod s3;
+
+/// A destination for the encrypted backups of the scheduled backups, see
+/// [`AccountManager::start_scheduled_backups()`](crate::account_manager::AccountManager::start_scheduled_backups).
+#[async_trait::async_trait]
+pub trait BackupTarget: std::fmt::Debug + Send + Sync {
+    /// Stores a backup under the name, an existing backup with the same name is replaced.
+    async fn put(&self, name: &str, backup: Vec<u8>) -> crate::Result<()>;
+
+    /// Returns the names of all stored backups.
+    async fn list(&self) -> crate::Result<Vec<String>>;
+
+    /// Removes a backup.
+    async fn remove(&self, name: &str) -> crate::Result<()>;
+}
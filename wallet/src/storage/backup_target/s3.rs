@@ -0,0 +1,345 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    fmt::{Debug, Formatter},
+    time::Duration,
+};
+
+use crypto::{
+    hashes::{sha::Sha256, Digest},
+    macs::hmac::HMAC_SHA256,
+};
+use instant::SystemTime;
+use reqwest::{Method, Response, Url};
+
+use super::BackupTarget;
+use crate::time::UtcDateTime;
+
+/// The timeout of a single request to the object storage
+const S3_REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A backup target that uploads the backups to a bucket of an S3 compatible object storage, like AWS S3 or MinIO.
+/// Requests are signed with AWS Signature Version 4 and use path-style URLs (`<endpoint>/<bucket>/<key>`).
+#[derive(Clone)]
+pub struct S3BackupTarget {
+    client: reqwest::Client,
+    endpoint: Url,
+    bucket: String,
+    region: String,
+    access_key_id: String,
+    secret_access_key: String,
+    prefix: String,
+}
+
+impl Debug for S3BackupTarget {
+    // The secret access key isn't included
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("S3BackupTarget")
+            .field("endpoint", &self.endpoint.as_str())
+            .field("bucket", &self.bucket)
+            .field("region", &self.region)
+            .field("access_key_id", &self.access_key_id)
+            .field("prefix", &self.prefix)
+            .finish()
+    }
+}
+
+impl S3BackupTarget {
+    /// Creates a backup target for a bucket, `endpoint` is the URL of the object storage, for example
+    /// `https://s3.eu-central-1.amazonaws.com`.
+    pub fn new(
+        endpoint: &str,
+        bucket: impl Into<String>,
+        region: impl Into<String>,
+        access_key_id: impl Into<String>,
+        secret_access_key: impl Into<String>,
+    ) -> crate::Result<Self> {
+        let endpoint = Url::parse(endpoint).map_err(|err| crate::Error::BackupTarget(err.to_string()))?;
+        if endpoint.host_str().is_none() {
+            return Err(crate::Error::BackupTarget(format!("endpoint {endpoint} has no host")));
+        }
+        Ok(Self {
+            client: reqwest::Client::new(),
+            endpoint,
+            bucket: bucket.into(),
+            region: region.into(),
+            access_key_id: access_key_id.into(),
+            secret_access_key: secret_access_key.into(),
+            prefix: String::new(),
+        })
+    }
+
+    /// Stores the backups under a key prefix, like `wallets/alice/`, so a bucket can be shared.
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    // Sends a request signed with AWS Signature Version 4, fails if the response has an error status
+    async fn send(
+        &self,
+        method: Method,
+        key: Option<&str>,
+        query: &[(&str, &str)],
+        body: Vec<u8>,
+    ) -> crate::Result<Response> {
+        let mut path = format!("/{}", uri_encode(&self.bucket, true));
+        if let Some(key) = key {
+            path.push('/');
+            path.push_str(&uri_encode(key, false));
+        }
+        // Encoded and sorted as in the canonical request
+        let mut query = query
+            .iter()
+            .map(|(name, value)| format!("{}={}", uri_encode(name, true), uri_encode(value, true)))
+            .collect::<Vec<_>>();
+        query.sort();
+        let query = query.join("&");
+
+        let base_path = self.endpoint.path().trim_end_matches('/');
+        let mut url = format!("{}://{}{base_path}{path}", self.endpoint.scheme(), host(&self.endpoint));
+        if !query.is_empty() {
+            url.push('?');
+            url.push_str(&query);
+        }
+
+        let seconds = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .expect("time went backwards")
+            .as_secs();
+        let amz_date = amz_date(seconds);
+        let date = &amz_date[..8];
+        let payload_hash = hex(&Sha256::digest(&body));
+        let canonical_request = canonical_request(
+            method.as_str(),
+            &host(&self.endpoint),
+            &format!("{base_path}{path}"),
+            &query,
+            &payload_hash,
+            &amz_date,
+        );
+        let scope = format!("{date}/{}/s3/aws4_request", self.region);
+        let string_to_sign = string_to_sign(&amz_date, &scope, &canonical_request);
+        let signature = signature(&self.secret_access_key, date, &self.region, &string_to_sign);
+
+        self.client
+            .request(method, url)
+            .header("x-amz-content-sha256", payload_hash)
+            .header("x-amz-date", &amz_date)
+            .header(
+                "Authorization",
+                format!(
+                    "AWS4-HMAC-SHA256 Credential={}/{scope}, SignedHeaders=host;x-amz-content-sha256;x-amz-date, \
+                     Signature={signature}",
+                    self.access_key_id
+                ),
+            )
+            .timeout(S3_REQUEST_TIMEOUT)
+            .body(body)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|err| crate::Error::BackupTarget(err.to_string()))
+    }
+}
+
+#[async_trait::async_trait]
+impl BackupTarget for S3BackupTarget {
+    async fn put(&self, name: &str, backup: Vec<u8>) -> crate::Result<()> {
+        self.send(Method::PUT, Some(&format!("{}{name}", self.prefix)), &[], backup)
+            .await?;
+        Ok(())
+    }
+
+    async fn list(&self) -> crate::Result<Vec<String>> {
+        let mut names = Vec::new();
+        let mut continuation_token = None;
+        // The object storage returns at most 1000 keys per request
+        loop {
+            let mut query = vec![("list-type", "2"), ("prefix", self.prefix.as_str())];
+            if let Some(continuation_token) = &continuation_token {
+                query.push(("continuation-token", continuation_token.as_str()));
+            }
+            let body = self
+                .send(Method::GET, None, &query, Vec::new())
+                .await?
+                .text()
+                .await
+                .map_err(|err| crate::Error::BackupTarget(err.to_string()))?;
+            names.extend(
+                xml_elements(&body, "Key")
+                    .into_iter()
+                    .filter_map(|key| key.strip_prefix(&self.prefix).map(String::from)),
+            );
+            continuation_token = xml_elements(&body, "NextContinuationToken").into_iter().next();
+            if continuation_token.is_none() {
+                break;
+            }
+        }
+        Ok(names)
+    }
+
+    async fn remove(&self, name: &str) -> crate::Result<()> {
+        self.send(Method::DELETE, Some(&format!("{}{name}", self.prefix)), &[], Vec::new())
+            .await?;
+        Ok(())
+    }
+}
+
+// The host with the port if it isn't the default port of the scheme
+fn host(url: &Url) -> String {
+    let host = url.host_str().unwrap_or_default();
+    match url.port() {
+        Some(port) => format!("{host}:{port}"),
+        None => host.to_string(),
+    }
+}
+
+fn hmac(key: &[u8], data: &[u8]) -> [u8; 32] {
+    let mut mac = [0u8; 32];
+    HMAC_SHA256(data, key, &mut mac);
+    mac
+}
+
+// Lowercase hex without prefix, as required for signatures
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+// Percent-encodes everything except unreserved characters, slashes are kept in keys
+fn uri_encode(value: &str, encode_slash: bool) -> String {
+    let mut encoded = String::new();
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => encoded.push(byte as char),
+            b'/' if !encode_slash => encoded.push('/'),
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+// The canonical request of AWS Signature Version 4, only the host, content hash and date headers are signed. The path
+// and query have to be encoded and the query parameters sorted.
+fn canonical_request(method: &str, host: &str, path: &str, query: &str, payload_hash: &str, amz_date: &str) -> String {
+    format!(
+        "{method}\n{path}\n{query}\nhost:{host}\nx-amz-content-sha256:{payload_hash}\nx-amz-date:{amz_date}\n\n\
+         host;x-amz-content-sha256;x-amz-date\n{payload_hash}"
+    )
+}
+
+fn string_to_sign(amz_date: &str, scope: &str, canonical_request: &str) -> String {
+    format!(
+        "AWS4-HMAC-SHA256\n{amz_date}\n{scope}\n{}",
+        hex(&Sha256::digest(canonical_request.as_bytes()))
+    )
+}
+
+// The signature with the key derived from the secret access key for the date, region and service
+fn signature(secret_access_key: &str, date: &str, region: &str, string_to_sign: &str) -> String {
+    let mut signing_key = hmac(format!("AWS4{secret_access_key}").as_bytes(), date.as_bytes());
+    for part in [region, "s3", "aws4_request"] {
+        signing_key = hmac(&signing_key, part.as_bytes());
+    }
+    hex(&hmac(&signing_key, string_to_sign.as_bytes()))
+}
+
+// Formats a unix timestamp as `YYYYMMDDTHHMMSSZ`
+fn amz_date(seconds: u64) -> String {
+    let date_time = UtcDateTime::from_unix_seconds(seconds);
+    format!(
+        "{:04}{:02}{:02}T{:02}{:02}{:02}Z",
+        date_time.year, date_time.month, date_time.day, date_time.hour, date_time.minute, date_time.second
+    )
+}
+
+// Returns the unescaped text of all elements with the name, enough for the responses of ListObjectsV2
+fn xml_elements(xml: &str, name: &str) -> Vec<String> {
+    let start_tag = format!("<{name}>");
+    let end_tag = format!("</{name}>");
+    let mut elements = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find(&start_tag) {
+        rest = &rest[start + start_tag.len()..];
+        let Some(end) = rest.find(&end_tag) else {
+            break;
+        };
+        elements.push(
+            rest[..end]
+                .replace("&lt;", "<")
+                .replace("&gt;", ">")
+                .replace("&quot;", "\"")
+                .replace("&apos;", "'")
+                .replace("&amp;", "&"),
+        );
+        rest = &rest[end + end_tag.len()..];
+    }
+    elements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn amz_dates() {
+        assert_eq!(amz_date(0), "19700101T000000Z");
+        assert_eq!(amz_date(1369353600), "20130524T000000Z");
+        assert_eq!(amz_date(1709251199), "20240229T235959Z");
+    }
+
+    // The "GET Bucket (List Objects)" example of the AWS Signature Version 4 documentation
+    #[test]
+    fn signature_version_4() {
+        let payload_hash = hex(&Sha256::digest(b""));
+        assert_eq!(
+            payload_hash,
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        let amz_date = amz_date(1369353600);
+
+        let canonical_request = canonical_request(
+            "GET",
+            "examplebucket.s3.amazonaws.com",
+            "/",
+            "max-keys=2&prefix=J",
+            &payload_hash,
+            &amz_date,
+        );
+        assert_eq!(
+            canonical_request,
+            "GET\n/\nmax-keys=2&prefix=J\nhost:examplebucket.s3.amazonaws.com\n\
+             x-amz-content-sha256:e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855\n\
+             x-amz-date:20130524T000000Z\n\nhost;x-amz-content-sha256;x-amz-date\n\
+             e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+
+        let string_to_sign = string_to_sign(&amz_date, "20130524/us-east-1/s3/aws4_request", &canonical_request);
+        assert_eq!(
+            string_to_sign,
+            "AWS4-HMAC-SHA256\n20130524T000000Z\n20130524/us-east-1/s3/aws4_request\n\
+             df57d21db20da04d7fa30298dd4488ba3a2b47ca3a489c74750e0f1e7df1b9b7"
+        );
+
+        assert_eq!(
+            signature(
+                "wJalrXUtnFEMI/K7MDENG/bPxRfiCYEXAMPLEKEY",
+                "20130524",
+                "us-east-1",
+                &string_to_sign
+            ),
+            "34b48302e7b5fa45bde8084f4b7868a86f0a534bc59db6670ed5711ef69dc6f7"
+        );
+    }
+
+    #[test]
+    fn list_response() {
+        let xml = "<ListBucketResult><Contents><Key>backups/a.json</Key></Contents><Contents>\
+                   <Key>backups/b&amp;c.json</Key></Contents><NextContinuationToken>token</NextContinuationToken>\
+                   </ListBucketResult>";
+        assert_eq!(xml_elements(xml, "Key"), vec!["backups/a.json", "backups/b&c.json"]);
+        assert_eq!(xml_elements(xml, "NextContinuationToken"), vec!["token"]);
+        assert_eq!(uri_encode("backups/b&c.json", false), "backups/b%26c.json");
+    }
+}
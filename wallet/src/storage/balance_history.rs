@@ -0,0 +1,75 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::HashMap, time::Duration};
+
+use iota_client::block::output::TokenId;
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+
+use super::manager::StorageManager;
+use crate::storage::constants::{BALANCE_HISTORY, BALANCE_HISTORY_RETENTION};
+
+/// How long balance snapshots are kept if no retention was set.
+pub const DEFAULT_BALANCE_HISTORY_RETENTION: Duration = Duration::from_secs(365 * 24 * 60 * 60);
+
+/// The total balance of an account after a sync.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceSnapshot {
+    /// Unix timestamp in milliseconds when the sync finished.
+    pub timestamp: u64,
+    /// Total amount of the base coin.
+    pub base_coin: u64,
+    /// Total amounts of the native tokens.
+    pub native_tokens: HashMap<TokenId, U256>,
+}
+
+impl StorageManager {
+    /// Returns the balance snapshots of an account, sorted by their timestamp.
+    pub(crate) async fn get_balance_history(&self, account_index: u32) -> crate::Result<Vec<BalanceSnapshot>> {
+        Ok(self
+            .storage
+            .get(&format!("{BALANCE_HISTORY}{account_index}"))
+            .await?
+            .unwrap_or_default())
+    }
+
+    /// Adds a balance snapshot if the balance changed since the last one and removes snapshots older than the
+    /// retention.
+    pub(crate) async fn add_balance_snapshot(
+        &mut self,
+        account_index: u32,
+        balance_snapshot: BalanceSnapshot,
+    ) -> crate::Result<()> {
+        let mut balance_history = self.get_balance_history(account_index).await?;
+        if let Some(latest_snapshot) = balance_history.last() {
+            if latest_snapshot.base_coin == balance_snapshot.base_coin
+                && latest_snapshot.native_tokens == balance_snapshot.native_tokens
+            {
+                return Ok(());
+            }
+        }
+
+        let retention = self.get_balance_history_retention().await?;
+        let oldest_timestamp = balance_snapshot.timestamp.saturating_sub(retention.as_millis() as u64);
+        balance_history.retain(|snapshot| snapshot.timestamp >= oldest_timestamp);
+        balance_history.push(balance_snapshot);
+
+        self.storage
+            .set(&format!("{BALANCE_HISTORY}{account_index}"), balance_history)
+            .await
+    }
+
+    pub(crate) async fn get_balance_history_retention(&self) -> crate::Result<Duration> {
+        Ok(self
+            .storage
+            .get(BALANCE_HISTORY_RETENTION)
+            .await?
+            .unwrap_or(DEFAULT_BALANCE_HISTORY_RETENTION))
+    }
+
+    pub(crate) async fn set_balance_history_retention(&mut self, retention: Duration) -> crate::Result<()> {
+        self.storage.set(BALANCE_HISTORY_RETENTION, retention).await
+    }
+}
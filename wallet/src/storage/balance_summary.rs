@@ -0,0 +1,82 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use iota_client::block::output::TokenId;
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+
+use super::manager::StorageManager;
+use crate::{account::types::BaseCoinBalance, storage::constants::BALANCE_SUMMARY};
+
+/// The summed balance of accounts, as recorded after their last sync.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceSummary {
+    /// Total and available amount of the base coin.
+    pub base_coin: BaseCoinBalance,
+    /// Total amounts of the native tokens.
+    pub native_tokens: HashMap<TokenId, U256>,
+    /// Amount of accounts that are included.
+    pub accounts: usize,
+}
+
+impl BalanceSummary {
+    /// Adds the balance of another summary.
+    pub(crate) fn add(&mut self, other: &Self) {
+        self.base_coin.total += other.base_coin.total;
+        self.base_coin.available += other.base_coin.available;
+        for (token_id, amount) in &other.native_tokens {
+            *self.native_tokens.entry(*token_id).or_default() += *amount;
+        }
+        self.accounts += other.accounts;
+    }
+}
+
+/// The balance of a single account with the fields accounts can be grouped by, stored separately so aggregating
+/// doesn't require to load the full accounts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AccountBalanceSummary {
+    pub(crate) coin_type: u32,
+    pub(crate) metadata: HashMap<String, String>,
+    pub(crate) balance: BalanceSummary,
+}
+
+impl StorageManager {
+    /// Returns the balance summaries of all accounts that were synced at least once.
+    pub(crate) async fn get_balance_summaries(&self) -> crate::Result<Vec<AccountBalanceSummary>> {
+        let mut balance_summaries = Vec::new();
+        for account_index in &self.account_indexes {
+            if let Some(balance_summary) = self.storage.get(&format!("{BALANCE_SUMMARY}{account_index}")).await? {
+                balance_summaries.push(balance_summary);
+            }
+        }
+        Ok(balance_summaries)
+    }
+
+    pub(crate) async fn save_balance_summary(
+        &mut self,
+        account_index: u32,
+        balance_summary: &AccountBalanceSummary,
+    ) -> crate::Result<()> {
+        self.storage
+            .set(&format!("{BALANCE_SUMMARY}{account_index}"), balance_summary)
+            .await
+    }
+
+    /// Updates the metadata of the balance summary of an account, if the account was synced before.
+    pub(crate) async fn update_balance_summary_metadata(
+        &mut self,
+        account_index: u32,
+        metadata: &HashMap<String, String>,
+    ) -> crate::Result<()> {
+        let key = format!("{BALANCE_SUMMARY}{account_index}");
+        if let Some(mut balance_summary) = self.storage.get::<AccountBalanceSummary>(&key).await? {
+            balance_summary.metadata = metadata.clone();
+            self.storage.set(&key, balance_summary).await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,166 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::Storage;
+
+/// A log that is stored in chunks, so appending an entry doesn't rewrite the whole log. The entries are numbered with
+/// increasing sequence numbers starting at 1 and stored in chunks by `(sequence - 1) / chunk_size`, the oldest chunk
+/// is removed once the log holds more than `capacity` entries.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ChunkedLog {
+    /// The key of the record with the range of sequence numbers.
+    pub(crate) key: &'static str,
+    /// The key prefix of the chunks, followed by the chunk index.
+    pub(crate) chunk_key: &'static str,
+    /// Amount of entries per chunk.
+    pub(crate) chunk_size: u64,
+    /// Amount of entries that are kept at least.
+    pub(crate) capacity: u64,
+}
+
+// The range of sequence numbers in the log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ChunkedLogHead {
+    // The sequence number of the oldest stored entry
+    first_sequence: u64,
+    // The sequence number of the next entry
+    next_sequence: u64,
+}
+
+impl ChunkedLog {
+    /// Returns the entries with a sequence number greater than or equal to `from_sequence`, oldest first.
+    pub(crate) async fn entries<T: for<'de> Deserialize<'de>>(
+        &self,
+        storage: &Storage,
+        from_sequence: u64,
+    ) -> crate::Result<Vec<T>> {
+        let Some(head) = storage.get::<ChunkedLogHead>(self.key).await? else {
+            return Ok(Vec::new());
+        };
+        let from_sequence = from_sequence.max(head.first_sequence);
+        if from_sequence >= head.next_sequence {
+            return Ok(Vec::new());
+        }
+
+        let mut entries = Vec::new();
+        for chunk in self.chunk_index(from_sequence)..=self.chunk_index(head.next_sequence - 1) {
+            let chunk_entries: Vec<T> = storage.get(&self.chunk_key(chunk)).await?.unwrap_or_default();
+            // Chunks always start at a multiple of the chunk size
+            let chunk_first_sequence = chunk * self.chunk_size + 1;
+            entries.extend(
+                (chunk_first_sequence..)
+                    .zip(chunk_entries)
+                    .filter(|(sequence, _)| *sequence >= from_sequence)
+                    .map(|(_, entry)| entry),
+            );
+        }
+        Ok(entries)
+    }
+
+    /// Returns the keys of the records of the log, with the key prefix of a profile for the log of the profile. The
+    /// returned keys don't include the prefix.
+    pub(crate) async fn keys(&self, storage: &Storage, prefix: &str) -> crate::Result<Vec<String>> {
+        let mut keys = vec![self.key.to_string()];
+        if let Some(head) = storage.get::<ChunkedLogHead>(&format!("{prefix}{}", self.key)).await? {
+            if head.next_sequence > head.first_sequence {
+                keys.extend(
+                    (self.chunk_index(head.first_sequence)..=self.chunk_index(head.next_sequence - 1))
+                        .map(|chunk| self.chunk_key(chunk)),
+                );
+            }
+        }
+        Ok(keys)
+    }
+
+    /// Appends the entry that is created with the next sequence number and drops the oldest chunk if the log is full.
+    pub(crate) async fn append<T, F>(&self, storage: &mut Storage, entry: F) -> crate::Result<()>
+    where
+        T: Serialize + for<'de> Deserialize<'de>,
+        F: FnOnce(u64) -> T,
+    {
+        let mut head = storage
+            .get::<ChunkedLogHead>(self.key)
+            .await?
+            .unwrap_or(ChunkedLogHead {
+                first_sequence: 1,
+                next_sequence: 1,
+            });
+        let sequence = head.next_sequence;
+        let chunk_key = self.chunk_key(self.chunk_index(sequence));
+        let mut chunk_entries: Vec<T> = storage.get(&chunk_key).await?.unwrap_or_default();
+        chunk_entries.push(entry(sequence));
+        head.next_sequence += 1;
+
+        let mut removals = Vec::new();
+        if head.next_sequence - head.first_sequence > self.capacity + self.chunk_size {
+            removals.push(self.chunk_key(self.chunk_index(head.first_sequence)));
+            head.first_sequence += self.chunk_size;
+        }
+
+        storage
+            .batch_write(
+                HashMap::from([
+                    (chunk_key, serde_json::to_string(&chunk_entries)?),
+                    (self.key.to_string(), serde_json::to_string(&head)?),
+                ]),
+                removals,
+            )
+            .await
+    }
+
+    const fn chunk_index(&self, sequence: u64) -> u64 {
+        (sequence - 1) / self.chunk_size
+    }
+
+    fn chunk_key(&self, chunk: u64) -> String {
+        format!("{}{chunk}", self.chunk_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{adapter::memory::Memory, hooks::StorageHooks, quota::StorageQuota};
+
+    const LOG: ChunkedLog = ChunkedLog {
+        key: "log",
+        chunk_key: "log-",
+        chunk_size: 2,
+        capacity: 4,
+    };
+
+    #[tokio::test]
+    async fn append_and_drop_oldest_chunk() {
+        let mut storage = Storage {
+            inner: Box::<Memory>::default(),
+            encryption_key: None,
+            hooks: StorageHooks::default(),
+            removals_since_compaction: 0,
+            quota: StorageQuota::default(),
+            used_bytes: 0,
+        };
+        for sequence in 1..=7u64 {
+            LOG.append(&mut storage, |next_sequence| {
+                assert_eq!(next_sequence, sequence);
+                sequence
+            })
+            .await
+            .unwrap();
+        }
+
+        // The log holds more than capacity + chunk size entries, so the first chunk got removed
+        assert_eq!(LOG.entries::<u64>(&storage, 1).await.unwrap(), vec![3, 4, 5, 6, 7]);
+        assert_eq!(LOG.entries::<u64>(&storage, 5).await.unwrap(), vec![5, 6, 7]);
+        assert!(LOG.entries::<u64>(&storage, 8).await.unwrap().is_empty());
+        assert_eq!(
+            LOG.keys(&storage, "").await.unwrap(),
+            vec!["log", "log-1", "log-2", "log-3"]
+        );
+        assert!(storage.get::<Vec<u64>>("log-0").await.unwrap().is_none());
+    }
+}
@@ -18,14 +18,66 @@ pub const fn default_storage_path() -> &'static str {
 pub(crate) const ACCOUNT_MANAGER_INDEXATION_KEY: &str = "iota-wallet-account-manager";
 
 pub(crate) const SECRET_MANAGER_KEY: &str = "secret_manager";
+pub(crate) const ENCRYPTED_MNEMONIC_KEY: &str = "encrypted_mnemonic";
+pub(crate) const SEED_FINGERPRINT_KEY: &str = "seed_fingerprint";
+pub(crate) const SEED_FINGERPRINT_UNVERIFIED_KEY: &str = "seed_fingerprint_unverified";
+pub(crate) const STRONGHOLD_SNAPSHOT_GENERATIONS: &str = "stronghold-snapshot-generations";
+pub(crate) const WALLET_ID: &str = "wallet-id";
 
 pub(crate) const ACCOUNTS_INDEXATION_KEY: &str = "iota-wallet-accounts";
 pub(crate) const ACCOUNT_INDEXATION_KEY: &str = "iota-wallet-account-";
+pub(crate) const ACCOUNT_ALIASES_INDEXATION_KEY: &str = "iota-wallet-account-aliases";
 
-pub(crate) const DATABASE_SCHEMA_VERSION: u8 = 1;
+pub(crate) const SYNC_CURSOR: &str = "sync-cursor-";
+pub(crate) const ADDRESS_SYNC_CURSORS: &str = "address-sync-cursors-";
+pub(crate) const DERIVATION_CACHE: &str = "derivation-cache-";
+pub(crate) const ACCOUNT_CLIENT_OPTIONS: &str = "account-client-options-";
+pub(crate) const BALANCE_HISTORY: &str = "balance-history-";
+pub(crate) const BALANCE_HISTORY_RETENTION: &str = "balance-history-retention";
+pub(crate) const BALANCE_SUMMARY: &str = "balance-summary-";
+pub(crate) const OFFLINE_QUEUE: &str = "offline-queue-";
+pub(crate) const SCHEDULED_PAYMENTS: &str = "scheduled-payments-";
+pub(crate) const TRANSACTION_INDEX: &str = "transaction-index-";
+pub(crate) const INPUT_RESERVATIONS: &str = "input-reservations-";
+/// Time after which an input reservation is released if its account manager didn't renew it, half of it before it's
+/// renewed.
+pub(crate) const INPUT_RESERVATION_TIMEOUT_MILLISECONDS: u64 = 30 * 60 * 1000;
+
+pub(crate) const ARCHIVED_PREFIX: &str = "archived:";
+pub(crate) const ARCHIVED_ACCOUNTS_INDEXATION_KEY: &str = "archived:iota-wallet-accounts";
+
+pub(crate) const ACCOUNT_RECOVERY_CHECKPOINT: &str = "account-recovery-checkpoint";
+pub(crate) const AUDIT_LOG: &str = "audit-log";
+pub(crate) const AUDIT_LOG_CHUNK: &str = "audit-log-";
+/// Amount of entries that are kept in the audit log at least, older entries are removed in chunks.
+pub(crate) const AUDIT_LOG_CAPACITY: u64 = 10_000;
+pub(crate) const AUDIT_LOG_CHUNK_SIZE: u64 = 100;
+#[cfg(feature = "events")]
+pub(crate) const EVENT_LOG: &str = "event-log";
+#[cfg(feature = "events")]
+pub(crate) const EVENT_LOG_CHUNK: &str = "event-log-";
+/// Amount of events that are kept in the event log at least, older events are removed in chunks.
+#[cfg(feature = "events")]
+pub(crate) const EVENT_LOG_CAPACITY: u64 = 10_000;
+#[cfg(feature = "events")]
+pub(crate) const EVENT_LOG_CHUNK_SIZE: u64 = 100;
+#[cfg(feature = "webhooks")]
+pub(crate) const WEBHOOK_DEAD_LETTERS: &str = "webhook-dead-letters";
+/// Amount of undeliverable webhook payloads that are kept, older ones are dropped.
+#[cfg(feature = "webhooks")]
+pub(crate) const WEBHOOK_DEAD_LETTERS_CAPACITY: usize = 1000;
+
+pub(crate) const PROFILE_PREFIX: &str = "profile:";
+pub(crate) const PROFILES_INDEXATION_KEY: &str = "iota-wallet-profiles";
+
+pub(crate) const DATABASE_SCHEMA_VERSION: u8 = 2;
 pub(crate) const DATABASE_SCHEMA_VERSION_KEY: &str = "database-schema-version";
 
 #[cfg(feature = "participation")]
 pub(crate) const PARTICIPATION_EVENTS: &str = "participation-events";
 #[cfg(feature = "participation")]
 pub(crate) const PARTICIPATION_CACHED_OUTPUTS: &str = "participation-cached-outputs";
+#[cfg(feature = "participation")]
+pub(crate) const PARTICIPATION_STATE: &str = "participation-state";
+
+pub(crate) const STORAGE_BUNDLE_VERSION: u8 = 1;
@@ -0,0 +1,204 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::BTreeMap, ops::Range, sync::Arc};
+
+use iota_client::{
+    block::address::{Address, Ed25519Address},
+    secret::{SecretManage, SecretManager},
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use super::manager::{StorageManager, StorageManagerHandle};
+#[cfg(feature = "ledger_nano")]
+use crate::account_manager::{LedgerNanoOperation, LedgerNanoQueue};
+use crate::storage::constants::{ARCHIVED_PREFIX, DERIVATION_CACHE};
+
+/// Addresses derived by the secret manager for an account, so they don't need to be derived again by slow secret
+/// managers like hardware devices.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DerivationCache {
+    /// The coin type the addresses were derived with.
+    coin_type: u32,
+    /// Hex encoded Ed25519 addresses by their key index.
+    public: BTreeMap<u32, String>,
+    /// Hex encoded Ed25519 internal addresses by their key index.
+    internal: BTreeMap<u32, String>,
+}
+
+impl DerivationCache {
+    fn addresses(&self, internal: bool) -> &BTreeMap<u32, String> {
+        if internal {
+            &self.internal
+        } else {
+            &self.public
+        }
+    }
+}
+
+impl StorageManager {
+    /// Returns the cached addresses for the whole range, or None if any of them isn't cached.
+    pub(crate) async fn get_cached_addresses(
+        &self,
+        account_index: u32,
+        coin_type: u32,
+        internal: bool,
+        key_indexes: Range<u32>,
+    ) -> crate::Result<Option<Vec<Address>>> {
+        let cache = match self.get_derivation_cache(account_index).await? {
+            Some(cache) if cache.coin_type == coin_type => cache,
+            _ => return Ok(None),
+        };
+
+        let mut addresses = Vec::new();
+        for key_index in key_indexes {
+            match cache
+                .addresses(internal)
+                .get(&key_index)
+                .and_then(|address| prefix_hex::decode::<[u8; Ed25519Address::LENGTH]>(address).ok())
+            {
+                Some(address) => addresses.push(Address::Ed25519(Ed25519Address::new(address))),
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(addresses))
+    }
+
+    /// Adds derived addresses, starting at the first key index, to the cache of the account.
+    pub(crate) async fn cache_addresses(
+        &mut self,
+        account_index: u32,
+        coin_type: u32,
+        internal: bool,
+        first_key_index: u32,
+        addresses: &[Address],
+    ) -> crate::Result<()> {
+        if addresses.is_empty() {
+            return Ok(());
+        }
+        let mut cache = match self.get_derivation_cache(account_index).await? {
+            Some(cache) if cache.coin_type == coin_type => cache,
+            _ => DerivationCache {
+                coin_type,
+                ..Default::default()
+            },
+        };
+
+        let cached_addresses = if internal {
+            &mut cache.internal
+        } else {
+            &mut cache.public
+        };
+        for (key_index, address) in (first_key_index..).zip(addresses) {
+            if let Address::Ed25519(address) = address {
+                cached_addresses.insert(key_index, prefix_hex::encode(**address));
+            }
+        }
+
+        self.storage
+            .set(&format!("{DERIVATION_CACHE}{account_index}"), cache)
+            .await
+    }
+
+    /// Removes the cached addresses of all accounts, needs to be called when the secret manager changes.
+    pub(crate) async fn clear_derivation_cache(&mut self) -> crate::Result<()> {
+        log::debug!("clear_derivation_cache");
+        let mut keys = self
+            .account_indexes
+            .iter()
+            .map(|account_index| format!("{DERIVATION_CACHE}{account_index}"))
+            .collect::<Vec<_>>();
+        for account_index in self.archived_account_indexes().await? {
+            keys.push(format!("{ARCHIVED_PREFIX}{DERIVATION_CACHE}{account_index}"));
+        }
+
+        for key in keys {
+            // Some adapters fail to remove missing records
+            if self.storage.get::<serde_json::Value>(&key).await?.is_some() {
+                self.storage.remove(&key).await?;
+            }
+        }
+        self.verified_derivation_caches.clear();
+        Ok(())
+    }
+
+    async fn get_derivation_cache(&self, account_index: u32) -> crate::Result<Option<DerivationCache>> {
+        self.storage.get(&format!("{DERIVATION_CACHE}{account_index}")).await
+    }
+}
+
+/// Returns the cached addresses for the whole range like [`StorageManager::get_cached_addresses()`]. The first time the
+/// cache of an account is used in a session, the first address of the range is derived again and compared with the
+/// cached one, if they don't match the cache of the account is removed and None is returned, so the addresses are
+/// derived by the secret manager.
+pub(crate) async fn get_verified_cached_addresses(
+    storage_manager: &StorageManagerHandle,
+    secret_manager: &Arc<RwLock<SecretManager>>,
+    #[cfg(feature = "ledger_nano")] ledger_nano_queue: &LedgerNanoQueue,
+    account_index: u32,
+    coin_type: u32,
+    internal: bool,
+    key_indexes: Range<u32>,
+) -> crate::Result<Option<Vec<Address>>> {
+    let first_key_index = key_indexes.start;
+    let addresses = {
+        let storage_manager = storage_manager.lock().await;
+        match storage_manager
+            .get_cached_addresses(account_index, coin_type, internal, key_indexes)
+            .await?
+        {
+            Some(addresses)
+                if addresses.is_empty() || storage_manager.verified_derivation_caches.contains(&account_index) =>
+            {
+                return Ok(Some(addresses));
+            }
+            Some(addresses) => addresses,
+            None => return Ok(None),
+        }
+    };
+
+    // The storage isn't locked while the address is derived, a Ledger Nano can take a while
+    let derived_address = {
+        let secret_manager = secret_manager.read().await;
+        // Without seed the cache can't be verified
+        if let SecretManager::Placeholder(_) = &*secret_manager {
+            return Ok(Some(addresses));
+        }
+        let generate_address = async {
+            secret_manager
+                .generate_addresses(
+                    coin_type,
+                    account_index,
+                    first_key_index..first_key_index + 1,
+                    internal,
+                    None,
+                )
+                .await
+                .map_err(crate::Error::from)
+        };
+        #[cfg(feature = "ledger_nano")]
+        let generate_address = ledger_nano_queue.run_for(
+            &secret_manager,
+            LedgerNanoOperation::GenerateAddresses,
+            generate_address,
+        );
+        generate_address.await?
+    };
+
+    let mut storage_manager = storage_manager.lock().await;
+    if derived_address.first() == addresses.first() {
+        storage_manager.verified_derivation_caches.insert(account_index);
+        return Ok(Some(addresses));
+    }
+    log::warn!(
+        "[get_verified_cached_addresses] cached addresses of account {} don't match the secret manager",
+        account_index
+    );
+    storage_manager
+        .storage
+        .remove(&format!("{DERIVATION_CACHE}{account_index}"))
+        .await?;
+    Ok(None)
+}
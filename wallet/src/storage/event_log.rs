@@ -0,0 +1,58 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use instant::SystemTime;
+use serde::{Deserialize, Serialize};
+
+use super::{chunked_log::ChunkedLog, manager::StorageManager};
+use crate::{
+    events::types::Event,
+    storage::constants::{EVENT_LOG, EVENT_LOG_CAPACITY, EVENT_LOG_CHUNK, EVENT_LOG_CHUNK_SIZE},
+};
+
+/// An emitted event in the event log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventLogEntry {
+    /// The sequence number of the event, increasing by one for every event, starting at 1.
+    pub sequence: u64,
+    /// Unix timestamp in milliseconds when the event was emitted.
+    pub timestamp: u64,
+    /// The event.
+    pub event: Event,
+}
+
+const CHUNKED_EVENT_LOG: ChunkedLog = ChunkedLog {
+    key: EVENT_LOG,
+    chunk_key: EVENT_LOG_CHUNK,
+    chunk_size: EVENT_LOG_CHUNK_SIZE,
+    capacity: EVENT_LOG_CAPACITY,
+};
+
+impl StorageManager {
+    /// Returns the logged events with a sequence number greater than `sequence`, oldest first. Only the last
+    /// `EVENT_LOG_CAPACITY` events are kept.
+    pub(crate) async fn get_events_since(&self, sequence: u64) -> crate::Result<Vec<EventLogEntry>> {
+        CHUNKED_EVENT_LOG.entries(&self.storage, sequence + 1).await
+    }
+
+    /// Returns the keys of the event log records, with the key prefix of a profile for the event log of the profile.
+    pub(crate) async fn event_log_keys(&self, prefix: &str) -> crate::Result<Vec<String>> {
+        CHUNKED_EVENT_LOG.keys(&self.storage, prefix).await
+    }
+
+    /// Appends an event to the event log and drops the oldest chunk of events if the log is full.
+    pub(crate) async fn append_event(&mut self, event: Event) -> crate::Result<()> {
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .expect("time went backwards")
+            .as_millis() as u64;
+        CHUNKED_EVENT_LOG
+            .append(&mut self.storage, |sequence| EventLogEntry {
+                sequence,
+                timestamp,
+                event,
+            })
+            .await
+    }
+}
@@ -0,0 +1,387 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    manager::{account_keys, StorageManager},
+    migration::migrate_records,
+    seed_fingerprint::SeedFingerprint,
+};
+use crate::{account::Account, encryption::PasswordEncryptedData, storage::constants::*};
+
+/// A versioned dump of all wallet records, without the secret manager.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageBundle {
+    /// The version of the bundle format.
+    pub version: u8,
+    /// The database schema version of the exported records.
+    pub database_schema_version: u8,
+    /// The records by their storage key, sorted so the same data always results in the same output.
+    pub records: BTreeMap<String, serde_json::Value>,
+}
+
+// The content of an exported file
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum StorageBundleFile {
+    Plain(StorageBundle),
+    Encrypted(PasswordEncryptedData),
+}
+
+/// The records of a single account, without any secrets, to move the account to another account manager.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountPackage {
+    /// The version of the bundle format.
+    pub version: u8,
+    /// The database schema version of the exported records.
+    pub database_schema_version: u8,
+    /// The index of the exported account.
+    pub account_index: u32,
+    /// The records of the account by their storage key.
+    pub records: BTreeMap<String, serde_json::Value>,
+}
+
+impl AccountPackage {
+    /// Returns the exported account.
+    pub fn account(&self) -> crate::Result<Account> {
+        let account = self
+            .records
+            .get(&format!("{ACCOUNT_INDEXATION_KEY}{}", self.account_index))
+            .ok_or(crate::Error::Backup("account package doesn't contain the account"))?;
+        Ok(serde_json::from_value(account.clone())?)
+    }
+
+    /// Returns true if the account uses its own client options instead of the ones of the account manager.
+    pub fn has_client_options(&self) -> bool {
+        self.records
+            .contains_key(&format!("{ACCOUNT_CLIENT_OPTIONS}{}", self.account_index))
+    }
+}
+
+impl StorageBundle {
+    /// Returns the indexes of the accounts in the bundle.
+    pub fn account_indexes(&self) -> crate::Result<Vec<u32>> {
+        match self.records.get(ACCOUNTS_INDEXATION_KEY) {
+            Some(account_indexes) => Ok(serde_json::from_value(account_indexes.clone())?),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Removes the records of all accounts that aren't in `account_indexes`, fails if an account isn't in the bundle.
+    pub(crate) fn retain_accounts(&mut self, account_indexes: &[u32]) -> crate::Result<()> {
+        let bundle_account_indexes = self.account_indexes()?;
+        if let Some(account_index) = account_indexes
+            .iter()
+            .find(|account_index| !bundle_account_indexes.contains(account_index))
+        {
+            return Err(crate::Error::AccountNotFound(account_index.to_string()));
+        }
+        for account_index in bundle_account_indexes {
+            if !account_indexes.contains(&account_index) {
+                for key in account_keys(account_index) {
+                    self.records.remove(&key);
+                }
+            }
+        }
+        self.records.insert(
+            ACCOUNTS_INDEXATION_KEY.to_string(),
+            serde_json::to_value(account_indexes)?,
+        );
+        Ok(())
+    }
+
+    /// Returns the records of an account as [`AccountPackage`].
+    pub(crate) fn account_package(&self, account_index: u32) -> AccountPackage {
+        let account_keys = account_keys(account_index);
+        AccountPackage {
+            version: self.version,
+            database_schema_version: self.database_schema_version,
+            account_index,
+            records: self
+                .records
+                .iter()
+                .filter(|(key, _)| account_keys.contains(key))
+                .map(|(key, record)| (key.clone(), record.clone()))
+                .collect(),
+        }
+    }
+
+    /// Checks that the bundle format is supported and migrates the records of older database schema versions to the
+    /// current one.
+    pub(crate) fn migrate(&mut self) -> crate::Result<()> {
+        if self.version != STORAGE_BUNDLE_VERSION {
+            return Err(crate::Error::Storage(format!(
+                "unsupported storage bundle version {}",
+                self.version
+            )));
+        }
+        migrate_records(&mut self.records, self.database_schema_version)?;
+        self.database_schema_version = DATABASE_SCHEMA_VERSION;
+        Ok(())
+    }
+}
+
+impl StorageManager {
+    /// Creates a [`StorageBundle`] with all records except the secret manager.
+    pub async fn export_bundle(&self) -> crate::Result<StorageBundle> {
+        log::debug!("export_bundle");
+        let mut records = BTreeMap::new();
+
+        if let Some(mut account_manager_data) = self
+            .storage
+            .get::<serde_json::Value>(ACCOUNT_MANAGER_INDEXATION_KEY)
+            .await?
+        {
+            // The storage encryption key must not end up in the export
+            if let Some(storage_encryption_key) = account_manager_data
+                .get_mut("storage_options")
+                .and_then(|storage_options| storage_options.get_mut("storage_encryption_key"))
+            {
+                *storage_encryption_key = serde_json::Value::Null;
+            }
+            records.insert(ACCOUNT_MANAGER_INDEXATION_KEY.to_string(), account_manager_data);
+        }
+
+        if let Some(account_indexes) = self.storage.get::<serde_json::Value>(ACCOUNTS_INDEXATION_KEY).await? {
+            records.insert(ACCOUNTS_INDEXATION_KEY.to_string(), account_indexes);
+        }
+
+        for account_index in &self.account_indexes {
+            for key in account_keys(*account_index) {
+                if let Some(record) = self.storage.get::<serde_json::Value>(&key).await? {
+                    records.insert(key, record);
+                }
+            }
+        }
+
+        Ok(StorageBundle {
+            version: STORAGE_BUNDLE_VERSION,
+            database_schema_version: DATABASE_SCHEMA_VERSION,
+            records,
+        })
+    }
+
+    /// Writes all records except the secret manager as JSON to a file. If a password is provided, the content will be
+    /// encrypted with it.
+    pub async fn export_json(&self, path: impl AsRef<Path>, password: Option<&str>) -> crate::Result<()> {
+        log::debug!("export_json");
+        let bundle = self.export_bundle().await?;
+
+        let file = match password {
+            Some(password) => {
+                StorageBundleFile::Encrypted(PasswordEncryptedData::encrypt(password, &serde_json::to_vec(&bundle)?)?)
+            }
+            None => StorageBundleFile::Plain(bundle),
+        };
+
+        std::fs::write(path, serde_json::to_string_pretty(&file)?)?;
+        Ok(())
+    }
+
+    /// Reads a file created with [`StorageManager::export_json()`]. The password is required if the file is
+    /// encrypted. Records of older database schema versions are migrated to the current one.
+    pub fn read_json_bundle(path: impl AsRef<Path>, password: Option<&str>) -> crate::Result<StorageBundle> {
+        let file: StorageBundleFile = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+
+        let mut bundle: StorageBundle = match file {
+            StorageBundleFile::Plain(bundle) => bundle,
+            StorageBundleFile::Encrypted(encrypted) => {
+                let password = password.ok_or(crate::Error::MissingParameter("password"))?;
+                serde_json::from_slice(&encrypted.decrypt(password)?)?
+            }
+        };
+
+        bundle.migrate()?;
+
+        Ok(bundle)
+    }
+
+    /// Writes the account records from a file created with [`StorageManager::export_json()`] to the storage. Existing
+    /// records with the same keys are overwritten. The account manager record is not imported, so the storage options
+    /// and client options of the current storage are kept.
+    pub async fn import_json(&mut self, path: impl AsRef<Path>, password: Option<&str>) -> crate::Result<()> {
+        log::debug!("import_json");
+        let bundle = Self::read_json_bundle(path, password)?;
+        self.import_bundle(bundle).await
+    }
+
+    /// Writes the account records of a [`StorageBundle`] to the storage, like [`StorageManager::import_json()`].
+    pub(crate) async fn import_bundle(&mut self, bundle: StorageBundle) -> crate::Result<()> {
+        if let Some(account_indexes) = bundle.records.get(ACCOUNTS_INDEXATION_KEY) {
+            let account_indexes: Vec<u32> = serde_json::from_value(account_indexes.clone())?;
+            self.check_account_quota(
+                account_indexes
+                    .iter()
+                    .chain(self.account_indexes.iter())
+                    .collect::<HashSet<_>>()
+                    .len(),
+            )?;
+        }
+
+        for (key, record) in bundle.records {
+            // A modified file must not overwrite the secret manager, schema version or storage options
+            if [
+                ACCOUNT_MANAGER_INDEXATION_KEY,
+                SECRET_MANAGER_KEY,
+                DATABASE_SCHEMA_VERSION_KEY,
+            ]
+            .contains(&key.as_str())
+            {
+                continue;
+            }
+            if key == ACCOUNTS_INDEXATION_KEY {
+                let account_indexes: Vec<u32> = serde_json::from_value(record.clone())?;
+                for account_index in account_indexes {
+                    if !self.account_indexes.contains(&account_index) {
+                        self.account_indexes.push(account_index);
+                    }
+                }
+                continue;
+            }
+            self.storage.set(&key, record).await?;
+        }
+        // The imported derivation caches weren't verified yet
+        self.verified_derivation_caches.clear();
+
+        self.storage
+            .set(ACCOUNTS_INDEXATION_KEY, self.account_indexes.clone())
+            .await
+    }
+
+    /// Writes the records of an account, encrypted with the password, to a file.
+    pub async fn export_account(
+        &self,
+        account_index: u32,
+        path: impl AsRef<Path>,
+        password: &str,
+    ) -> crate::Result<()> {
+        log::debug!("export_account {account_index}");
+        if !self.account_indexes.contains(&account_index) {
+            return Err(crate::Error::AccountNotFound(account_index.to_string()));
+        }
+
+        let mut records = BTreeMap::new();
+        for key in account_keys(account_index) {
+            if let Some(record) = self.storage.get::<serde_json::Value>(&key).await? {
+                records.insert(key, record);
+            }
+        }
+        let package = AccountPackage {
+            version: STORAGE_BUNDLE_VERSION,
+            database_schema_version: DATABASE_SCHEMA_VERSION,
+            account_index,
+            records,
+        };
+
+        let encrypted = PasswordEncryptedData::encrypt(password, &serde_json::to_vec(&package)?)?;
+        std::fs::write(path, serde_json::to_string_pretty(&encrypted)?)?;
+        Ok(())
+    }
+
+    /// Reads and decrypts a file created with [`StorageManager::export_account()`], records of older database schema
+    /// versions are migrated to the current one.
+    pub fn read_account_package(path: impl AsRef<Path>, password: &str) -> crate::Result<AccountPackage> {
+        let encrypted: PasswordEncryptedData = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        let mut package: AccountPackage = serde_json::from_slice(&encrypted.decrypt(password)?)?;
+
+        if package.version != STORAGE_BUNDLE_VERSION {
+            return Err(crate::Error::Storage(format!(
+                "unsupported account package version {}",
+                package.version
+            )));
+        }
+        migrate_records(&mut package.records, package.database_schema_version)?;
+        package.database_schema_version = DATABASE_SCHEMA_VERSION;
+        // Only records of the exported account can be imported
+        let account_keys = account_keys(package.account_index);
+        if package.records.keys().any(|key| !account_keys.contains(key)) {
+            return Err(crate::Error::Backup(
+                "account package contains records of other accounts",
+            ));
+        }
+
+        Ok(package)
+    }
+
+    /// Writes the records of an [`AccountPackage`] and adds the account to the indexes in a single write. Fails if an
+    /// account or archived account with the same index or an account with the same alias exists.
+    pub async fn import_account_package(&mut self, package: AccountPackage) -> crate::Result<Account> {
+        log::debug!("import_account_package {}", package.account_index);
+        let mut accounts = self.import_account_packages(vec![package], None).await?;
+        Ok(accounts.remove(0))
+    }
+
+    /// Writes the records of multiple [`AccountPackage`]s like [`StorageManager::import_account_package()`]. All
+    /// accounts are checked first and written in a single write, so either all or none of them are imported. The seed
+    /// fingerprint is written with them if the storage doesn't have one yet.
+    pub(crate) async fn import_account_packages(
+        &mut self,
+        packages: Vec<AccountPackage>,
+        seed_fingerprint: Option<&SeedFingerprint>,
+    ) -> crate::Result<Vec<Account>> {
+        let archived_account_indexes = self.archived_account_indexes().await?;
+        let mut account_indexes = self.account_indexes.clone();
+        let mut account_aliases = self.account_aliases.clone();
+        let mut records = HashMap::new();
+        let mut accounts = Vec::new();
+        for package in packages {
+            let account = package.account()?;
+            if *account.index() != package.account_index {
+                return Err(crate::Error::Backup(
+                    "account package contains an account with another index",
+                ));
+            }
+            if account_indexes.contains(account.index()) || archived_account_indexes.contains(account.index()) {
+                return Err(crate::Error::Storage(format!(
+                    "can't import account {}, an account with this index already exists",
+                    account.index()
+                )));
+            }
+            let alias = account.alias().to_lowercase();
+            if account_aliases.contains_key(&alias) {
+                return Err(crate::Error::AccountAliasAlreadyExists(account.alias().clone()));
+            }
+            account_indexes.push(*account.index());
+            account_aliases.insert(alias, *account.index());
+            for (key, record) in package.records {
+                records.insert(key, serde_json::to_string(&record)?);
+            }
+            accounts.push(account);
+        }
+        self.check_account_quota(account_indexes.len())?;
+
+        records.insert(
+            ACCOUNTS_INDEXATION_KEY.to_string(),
+            serde_json::to_string(&account_indexes)?,
+        );
+        records.insert(
+            ACCOUNT_ALIASES_INDEXATION_KEY.to_string(),
+            serde_json::to_string(&account_aliases)?,
+        );
+        if let Some(seed_fingerprint) = seed_fingerprint {
+            if !self.has_seed_fingerprint().await? {
+                records.insert(
+                    SEED_FINGERPRINT_KEY.to_string(),
+                    serde_json::to_string(seed_fingerprint)?,
+                );
+            }
+        }
+        self.storage.batch_write(records, Vec::new()).await?;
+        // The imported derivation caches weren't verified yet
+        for account in &accounts {
+            self.verified_derivation_caches.remove(account.index());
+        }
+        self.account_indexes = account_indexes;
+        self.account_aliases = account_aliases;
+
+        Ok(accounts)
+    }
+}
@@ -0,0 +1,98 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    fmt::{Debug, Formatter, Result},
+    sync::Arc,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+/// The kind of write operation that was performed on the storage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum StorageOperation {
+    /// A record was inserted or updated.
+    Set,
+    /// A record was removed.
+    Remove,
+}
+
+/// Information about a single write to the storage, passed to every registered hook.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageEvent {
+    /// The performed operation.
+    pub operation: StorageOperation,
+    /// The key of the record.
+    pub key: String,
+    /// The size in bytes of the record as it was written to the adapter (after a possible encryption), 0 for removals.
+    pub size: usize,
+    /// How long the adapter took to perform the operation.
+    pub duration: Duration,
+}
+
+type Hook = Arc<dyn Fn(&StorageEvent) + Send + Sync + 'static>;
+
+/// Callbacks that are invoked after every successful write to the storage. The events are only recorded during the
+/// write and the hooks are run once the lock of the storage manager is released, so they can access the storage.
+#[derive(Default)]
+pub(crate) struct StorageHooks {
+    hooks: Vec<Hook>,
+    pending_events: Vec<StorageEvent>,
+}
+
+impl StorageHooks {
+    pub(crate) fn add<F>(&mut self, hook: F)
+    where
+        F: Fn(&StorageEvent) + Send + Sync + 'static,
+    {
+        self.hooks.push(Arc::new(hook));
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.hooks.clear();
+        self.pending_events.clear();
+    }
+
+    pub(crate) fn record(&mut self, operation: StorageOperation, key: &str, size: usize, duration: Duration) {
+        if self.hooks.is_empty() {
+            return;
+        }
+        self.pending_events.push(StorageEvent {
+            operation,
+            key: key.to_string(),
+            size,
+            duration,
+        });
+    }
+
+    // Takes the recorded events together with the hooks, to run them without holding the storage
+    pub(crate) fn take_pending(&mut self) -> PendingStorageEvents {
+        PendingStorageEvents {
+            hooks: self.hooks.clone(),
+            events: std::mem::take(&mut self.pending_events),
+        }
+    }
+}
+
+/// Recorded storage events with the hooks that were registered when they got taken.
+pub(crate) struct PendingStorageEvents {
+    hooks: Vec<Hook>,
+    events: Vec<StorageEvent>,
+}
+
+impl PendingStorageEvents {
+    pub(crate) fn run(self) {
+        for event in &self.events {
+            for hook in &self.hooks {
+                hook(event);
+            }
+        }
+    }
+}
+
+impl Debug for StorageHooks {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "StorageHooks({})", self.hooks.len())
+    }
+}
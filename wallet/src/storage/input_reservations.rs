@@ -0,0 +1,88 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+
+use instant::SystemTime;
+use iota_client::block::output::OutputId;
+use serde::{Deserialize, Serialize};
+
+use super::manager::StorageManager;
+use crate::storage::constants::{INPUT_RESERVATIONS, INPUT_RESERVATION_TIMEOUT_MILLISECONDS};
+
+/// An input of an account that is used by an in-progress transaction, stored so other account managers that share
+/// the database don't select it for their transactions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InputReservation {
+    /// The id of the reserved output.
+    pub output_id: OutputId,
+    /// The id of the account manager that reserved the output.
+    pub holder: String,
+    /// Unix timestamp in milliseconds after which the reservation is released, if the holder doesn't renew it
+    /// before, because it stopped or crashed.
+    pub expires_at: u64,
+}
+
+impl StorageManager {
+    /// Returns the input reservations of an account.
+    pub(crate) async fn get_input_reservations(&self, account_index: u32) -> crate::Result<Vec<InputReservation>> {
+        Ok(self
+            .storage
+            .get(&format!("{INPUT_RESERVATIONS}{account_index}"))
+            .await?
+            .unwrap_or_default())
+    }
+
+    /// Sets the reservations of this account manager to the locked outputs of the account and drops expired
+    /// reservations. Outputs that are reserved by another account manager aren't reserved again, they're returned with
+    /// the other reserved outputs, so they can be excluded from the input selection.
+    pub(crate) async fn update_input_reservations(
+        &mut self,
+        account_index: u32,
+        locked_outputs: &HashSet<OutputId>,
+    ) -> crate::Result<HashSet<OutputId>> {
+        let input_reservations = self.get_input_reservations(account_index).await?;
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .expect("time went backwards")
+            .as_millis() as u64;
+
+        let mut reserved_by_others = HashSet::new();
+        let mut updated_reservations = Vec::new();
+        for mut input_reservation in input_reservations.iter().cloned() {
+            if input_reservation.expires_at <= now {
+                continue;
+            }
+            if input_reservation.holder != self.instance_id {
+                reserved_by_others.insert(input_reservation.output_id);
+                updated_reservations.push(input_reservation);
+            } else if locked_outputs.contains(&input_reservation.output_id) {
+                // Renew reservations that are halfway expired, so they aren't written on every update
+                if input_reservation.expires_at - now < INPUT_RESERVATION_TIMEOUT_MILLISECONDS / 2 {
+                    input_reservation.expires_at = now + INPUT_RESERVATION_TIMEOUT_MILLISECONDS;
+                }
+                updated_reservations.push(input_reservation);
+            }
+        }
+        for output_id in locked_outputs {
+            if !updated_reservations
+                .iter()
+                .any(|input_reservation| input_reservation.output_id == *output_id)
+            {
+                updated_reservations.push(InputReservation {
+                    output_id: *output_id,
+                    holder: self.instance_id.clone(),
+                    expires_at: now + INPUT_RESERVATION_TIMEOUT_MILLISECONDS,
+                });
+            }
+        }
+
+        if updated_reservations != input_reservations {
+            self.storage
+                .set(&format!("{INPUT_RESERVATIONS}{account_index}"), &updated_reservations)
+                .await?;
+        }
+        Ok(reserved_by_others)
+    }
+}
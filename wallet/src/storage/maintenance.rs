@@ -0,0 +1,157 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::manager::{account_keys, StorageManager};
+use crate::storage::constants::*;
+
+/// The size of the storage, in total and split by the domain of the stored records.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskUsage {
+    /// The size of the storage files in bytes, if the storage adapter is able to determine it.
+    pub total: Option<u64>,
+    /// The size of the stored records in bytes for each domain (`accountManager`, `accounts`, ...).
+    pub domains: HashMap<String, u64>,
+}
+
+impl StorageManager {
+    /// Returns the on-disk size of the storage and the size of the records per domain. The records of profiles that
+    /// are stored in this storage are included in the `profiles` domain.
+    pub async fn disk_usage(&self) -> crate::Result<DiskUsage> {
+        log::debug!("disk_usage");
+
+        let mut domains = self.domain_sizes("").await?;
+        let mut profiles_size = 0;
+        for profile_name in self.get_profile_names().await? {
+            profiles_size += self
+                .domain_sizes(&format!("{PROFILE_PREFIX}{profile_name}:"))
+                .await?
+                .values()
+                .sum::<u64>();
+        }
+        domains.insert("profiles".to_string(), profiles_size);
+
+        Ok(DiskUsage {
+            total: self.storage.inner.size_on_disk().await?,
+            domains,
+        })
+    }
+
+    // The size of all records with the key prefix by domain, the prefix is empty for the records of this account
+    // manager and the key prefix of a profile for its records
+    async fn domain_sizes(&self, prefix: &str) -> crate::Result<HashMap<String, u64>> {
+        let mut domain_keys: HashMap<&str, Vec<String>> = HashMap::new();
+        domain_keys.insert(
+            "accountManager",
+            [
+                ACCOUNT_MANAGER_INDEXATION_KEY,
+                SECRET_MANAGER_KEY,
+                ENCRYPTED_MNEMONIC_KEY,
+                SEED_FINGERPRINT_KEY,
+                SEED_FINGERPRINT_UNVERIFIED_KEY,
+                STRONGHOLD_SNAPSHOT_GENERATIONS,
+                WALLET_ID,
+                DATABASE_SCHEMA_VERSION_KEY,
+                BALANCE_HISTORY_RETENTION,
+                ACCOUNT_RECOVERY_CHECKPOINT,
+                PROFILES_INDEXATION_KEY,
+            ]
+            .map(String::from)
+            .to_vec(),
+        );
+
+        let account_indexes: Vec<u32> = self
+            .storage
+            .get(&format!("{prefix}{ACCOUNTS_INDEXATION_KEY}"))
+            .await?
+            .unwrap_or_default();
+        let mut accounts = vec![
+            ACCOUNTS_INDEXATION_KEY.to_string(),
+            ACCOUNT_ALIASES_INDEXATION_KEY.to_string(),
+        ];
+        let mut balance_history = Vec::new();
+        #[cfg(feature = "participation")]
+        let mut participation = Vec::new();
+        for account_index in account_indexes {
+            for key in account_keys(account_index) {
+                if key.starts_with(BALANCE_HISTORY) {
+                    balance_history.push(key);
+                } else {
+                    #[cfg(feature = "participation")]
+                    if key.starts_with(PARTICIPATION_EVENTS)
+                        || key.starts_with(PARTICIPATION_CACHED_OUTPUTS)
+                        || key.starts_with(PARTICIPATION_STATE)
+                    {
+                        participation.push(key);
+                        continue;
+                    }
+                    accounts.push(key);
+                }
+            }
+        }
+        domain_keys.insert("accounts", accounts);
+        domain_keys.insert("balanceHistory", balance_history);
+        #[cfg(feature = "participation")]
+        domain_keys.insert("participation", participation);
+
+        let archived_account_indexes: Vec<u32> = self
+            .storage
+            .get(&format!("{prefix}{ARCHIVED_ACCOUNTS_INDEXATION_KEY}"))
+            .await?
+            .unwrap_or_default();
+        let mut archive = vec![ARCHIVED_ACCOUNTS_INDEXATION_KEY.to_string()];
+        for account_index in archived_account_indexes {
+            archive.extend(
+                account_keys(account_index)
+                    .into_iter()
+                    .map(|key| format!("{ARCHIVED_PREFIX}{key}")),
+            );
+        }
+        domain_keys.insert("archive", archive);
+
+        #[allow(unused_mut)]
+        let mut logs = self.audit_log_keys(prefix).await?;
+        #[cfg(feature = "events")]
+        logs.extend(self.event_log_keys(prefix).await?);
+        #[cfg(feature = "webhooks")]
+        logs.push(WEBHOOK_DEAD_LETTERS.to_string());
+        domain_keys.insert("logs", logs);
+
+        let mut domains = HashMap::new();
+        for (domain, keys) in domain_keys {
+            let mut size = 0;
+            for key in keys {
+                size += self.record_size(&format!("{prefix}{key}")).await?;
+            }
+            domains.insert(domain.to_string(), size);
+        }
+        Ok(domains)
+    }
+
+    /// Reclaims the space of removed records.
+    pub async fn compact(&mut self) -> crate::Result<()> {
+        log::debug!("compact");
+        self.storage.inner.compact().await?;
+        self.storage.removals_since_compaction = 0;
+        Ok(())
+    }
+
+    /// The amount of records that got removed since the last compaction.
+    pub fn removals_since_compaction(&self) -> usize {
+        self.storage.removals_since_compaction
+    }
+
+    // Size of the record as it's stored by the adapter
+    async fn record_size(&self, key: &str) -> crate::Result<u64> {
+        Ok(self
+            .storage
+            .inner
+            .get(key)
+            .await?
+            .map_or(0, |record| record.len() as u64))
+    }
+}
@@ -1,16 +1,30 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::sync::Arc;
+use std::{
+    collections::{HashMap, HashSet},
+    ops::{Deref, DerefMut},
+    sync::Arc,
+};
 
+use crypto::utils::rand;
 use iota_client::secret::{SecretManager, SecretManagerDto};
 use serde::{Deserialize, Serialize};
-use tokio::sync::{Mutex, RwLock};
+use tokio::sync::{Mutex, MutexGuard, RwLock};
 
 use crate::{
     account::Account,
-    account_manager::builder::AccountManagerBuilder,
-    storage::{constants::*, Storage, StorageAdapter},
+    account_manager::{builder::AccountManagerBuilder, operations::account_recovery::RecoveryCheckpoint},
+    encryption::PasswordEncryptedData,
+    storage::{
+        constants::*,
+        hooks::{StorageEvent, StorageHooks},
+        migration::migrate_storage,
+        quota::StorageQuota,
+        transaction_index::transaction_index,
+        Storage, StorageAdapter,
+    },
+    ClientOptions,
 };
 
 /// The storage used by the manager.
@@ -42,7 +56,47 @@ impl Default for ManagerStorage {
     }
 }
 
-pub(crate) type StorageManagerHandle = Arc<Mutex<StorageManager>>;
+/// Shared storage manager, the storage hooks are run after its lock got released.
+#[derive(Debug, Clone)]
+pub(crate) struct StorageManagerHandle(Arc<Mutex<StorageManager>>);
+
+impl StorageManagerHandle {
+    pub(crate) fn new(storage_manager: StorageManager) -> Self {
+        Self(Arc::new(Mutex::new(storage_manager)))
+    }
+
+    pub(crate) async fn lock(&self) -> StorageManagerGuard<'_> {
+        StorageManagerGuard(Some(self.0.lock().await))
+    }
+}
+
+/// Lock of the storage manager, runs the hooks for the writes that were made with it when it gets dropped.
+pub(crate) struct StorageManagerGuard<'a>(Option<MutexGuard<'a, StorageManager>>);
+
+impl Deref for StorageManagerGuard<'_> {
+    type Target = StorageManager;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref().expect("storage manager guard is only taken on drop")
+    }
+}
+
+impl DerefMut for StorageManagerGuard<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.0.as_mut().expect("storage manager guard is only taken on drop")
+    }
+}
+
+impl Drop for StorageManagerGuard<'_> {
+    fn drop(&mut self) {
+        if let Some(mut guard) = self.0.take() {
+            let pending_events = guard.storage.hooks.take_pending();
+            // Release the lock first, so the hooks can use the storage manager
+            drop(guard);
+            pending_events.run();
+        }
+    }
+}
 
 /// Sets the storage adapter.
 pub(crate) async fn new_storage_manager(
@@ -52,14 +106,21 @@ pub(crate) async fn new_storage_manager(
     let mut storage = Storage {
         inner: storage,
         encryption_key,
+        hooks: StorageHooks::default(),
+        removals_since_compaction: 0,
+        quota: StorageQuota::default(),
+        used_bytes: 0,
     };
     // Get the db version or set it
     if let Some(db_schema_version) = storage.get::<u8>(DATABASE_SCHEMA_VERSION_KEY).await? {
-        if db_schema_version != DATABASE_SCHEMA_VERSION {
+        if db_schema_version > DATABASE_SCHEMA_VERSION {
             return Err(crate::Error::Storage(format!(
                 "unsupported database schema version {db_schema_version}"
             )));
         }
+        if db_schema_version < DATABASE_SCHEMA_VERSION {
+            migrate_storage(&mut storage, db_schema_version).await?;
+        }
     } else {
         storage
             .set(DATABASE_SCHEMA_VERSION_KEY, DATABASE_SCHEMA_VERSION)
@@ -67,13 +128,44 @@ pub(crate) async fn new_storage_manager(
     };
 
     let account_indexes = storage.get(ACCOUNTS_INDEXATION_KEY).await?.unwrap_or_default();
+    let account_aliases = storage.get(ACCOUNT_ALIASES_INDEXATION_KEY).await?.unwrap_or_default();
+
+    // Identifies the reservations of this account manager, if other account managers share the database
+    let mut instance_id = [0u8; 16];
+    rand::fill(&mut instance_id)?;
 
     let storage_manager = StorageManager {
         storage,
         account_indexes,
+        account_aliases,
+        instance_id: prefix_hex::encode(instance_id),
+        verified_derivation_caches: HashSet::new(),
     };
 
-    Ok(Arc::new(Mutex::new(storage_manager)))
+    Ok(StorageManagerHandle::new(storage_manager))
+}
+
+/// Returns the keys of all records that belong to an account, including the account record itself.
+pub(crate) fn account_keys(account_index: u32) -> Vec<String> {
+    vec![
+        format!("{ACCOUNT_INDEXATION_KEY}{account_index}"),
+        format!("{SYNC_CURSOR}{account_index}"),
+        format!("{ADDRESS_SYNC_CURSORS}{account_index}"),
+        format!("{DERIVATION_CACHE}{account_index}"),
+        format!("{ACCOUNT_CLIENT_OPTIONS}{account_index}"),
+        format!("{BALANCE_HISTORY}{account_index}"),
+        format!("{BALANCE_SUMMARY}{account_index}"),
+        format!("{OFFLINE_QUEUE}{account_index}"),
+        format!("{SCHEDULED_PAYMENTS}{account_index}"),
+        format!("{TRANSACTION_INDEX}{account_index}"),
+        format!("{INPUT_RESERVATIONS}{account_index}"),
+        #[cfg(feature = "participation")]
+        format!("{PARTICIPATION_EVENTS}{account_index}"),
+        #[cfg(feature = "participation")]
+        format!("{PARTICIPATION_CACHED_OUTPUTS}{account_index}"),
+        #[cfg(feature = "participation")]
+        format!("{PARTICIPATION_STATE}{account_index}"),
+    ]
 }
 
 /// Storage manager
@@ -81,7 +173,14 @@ pub(crate) async fn new_storage_manager(
 pub struct StorageManager {
     pub(crate) storage: Storage,
     // account indexes for accounts in the database
-    account_indexes: Vec<u32>,
+    pub(crate) account_indexes: Vec<u32>,
+    // account indexes by the lowercase aliases of the accounts in the database
+    pub(crate) account_aliases: HashMap<String, u32>,
+    // random id of the account manager, to distinguish its input reservations from the ones of other account managers
+    // that share the database
+    pub(crate) instance_id: String,
+    // indexes of the accounts whose derivation cache was verified against the secret manager in this session
+    pub(crate) verified_derivation_caches: HashSet<u32>,
 }
 
 impl StorageManager {
@@ -98,6 +197,20 @@ impl StorageManager {
         self.storage.get(key).await
     }
 
+    /// Registers a hook that gets called after every successful set or remove on the storage, once the lock of the
+    /// storage manager is released.
+    pub fn add_hook<F>(&mut self, hook: F)
+    where
+        F: Fn(&StorageEvent) + Send + Sync + 'static,
+    {
+        self.storage.hooks.add(hook);
+    }
+
+    /// Removes all registered storage hooks.
+    pub fn clear_hooks(&mut self) {
+        self.storage.hooks.clear();
+    }
+
     pub async fn save_account_manager_data(
         &mut self,
         account_manager_builder: &AccountManagerBuilder,
@@ -122,6 +235,79 @@ impl StorageManager {
         Ok(())
     }
 
+    /// Replaces the stored secret manager with a single write. A mnemonic secret manager can't be stored, so the record
+    /// gets removed instead, otherwise the previous secret manager would be loaded again. The encrypted mnemonic of
+    /// the previous secret manager is removed in the same write, so it isn't loaded with the mnemonic passphrase.
+    pub(crate) async fn save_secret_manager(&mut self, secret_manager: &SecretManager) -> crate::Result<()> {
+        log::debug!("save_secret_manager");
+        let mut records = HashMap::new();
+        let mut removals = Vec::new();
+        match SecretManagerDto::from(secret_manager) {
+            SecretManagerDto::Mnemonic(_) => {
+                // Some adapters fail to remove missing records
+                if self
+                    .storage
+                    .get::<SecretManagerDto>(SECRET_MANAGER_KEY)
+                    .await?
+                    .is_some()
+                {
+                    removals.push(SECRET_MANAGER_KEY.to_string());
+                }
+            }
+            secret_manager_dto => {
+                records.insert(
+                    SECRET_MANAGER_KEY.to_string(),
+                    serde_json::to_string(&secret_manager_dto)?,
+                );
+            }
+        }
+        if self.get_encrypted_mnemonic().await?.is_some() {
+            removals.push(ENCRYPTED_MNEMONIC_KEY.to_string());
+        }
+        if records.is_empty() && removals.is_empty() {
+            return Ok(());
+        }
+        self.storage.batch_write(records, removals).await
+    }
+
+    pub(crate) async fn save_encrypted_mnemonic(
+        &mut self,
+        encrypted_mnemonic: &PasswordEncryptedData,
+    ) -> crate::Result<()> {
+        log::debug!("save_encrypted_mnemonic");
+        self.storage.set(ENCRYPTED_MNEMONIC_KEY, encrypted_mnemonic).await
+    }
+
+    pub(crate) async fn get_encrypted_mnemonic(&self) -> crate::Result<Option<PasswordEncryptedData>> {
+        self.storage.get(ENCRYPTED_MNEMONIC_KEY).await
+    }
+
+    #[cfg(feature = "stronghold")]
+    pub(crate) async fn get_stronghold_snapshot_generations(&self) -> crate::Result<usize> {
+        Ok(self
+            .storage
+            .get(STRONGHOLD_SNAPSHOT_GENERATIONS)
+            .await?
+            .unwrap_or_default())
+    }
+
+    #[cfg(feature = "stronghold")]
+    pub(crate) async fn set_stronghold_snapshot_generations(&mut self, generations: usize) -> crate::Result<()> {
+        self.storage.set(STRONGHOLD_SNAPSHOT_GENERATIONS, generations).await
+    }
+
+    // Returns the random id that identifies the wallet across restarts, unlike `instance_id`, it's created on first use
+    pub(crate) async fn get_or_create_wallet_id(&mut self) -> crate::Result<String> {
+        if let Some(wallet_id) = self.storage.get(WALLET_ID).await? {
+            return Ok(wallet_id);
+        }
+        let mut wallet_id = [0u8; 16];
+        rand::fill(&mut wallet_id)?;
+        let wallet_id = prefix_hex::encode(wallet_id);
+        self.storage.set(WALLET_ID, &wallet_id).await?;
+        Ok(wallet_id)
+    }
+
     pub async fn get_account_manager_data(&self) -> crate::Result<Option<AccountManagerBuilder>> {
         log::debug!("get_account_manager_data");
         if let Some(mut builder) = self
@@ -159,7 +345,7 @@ impl StorageManager {
             return Ok(Vec::new());
         }
 
-        let mut accounts = Vec::new();
+        let mut accounts: Vec<Account> = Vec::new();
         for account_index in self.account_indexes.clone() {
             // PANIC: we assume that ACCOUNTS_INDEXATION_KEY and the different indexes are set together and
             // ACCOUNTS_INDEXATION_KEY has already been checked.
@@ -170,30 +356,263 @@ impl StorageManager {
             );
         }
 
+        // Databases created before the alias index existed or with imported accounts need to update it
+        if accounts.len() != self.account_aliases.len()
+            || accounts
+                .iter()
+                .any(|account| self.account_aliases.get(&account.alias().to_lowercase()) != Some(account.index()))
+        {
+            let account_aliases = accounts
+                .iter()
+                .map(|account| (account.alias().to_lowercase(), *account.index()))
+                .collect::<HashMap<_, _>>();
+            self.storage
+                .set(ACCOUNT_ALIASES_INDEXATION_KEY, &account_aliases)
+                .await?;
+            self.account_aliases = account_aliases;
+        }
+
         Ok(accounts)
     }
 
     pub async fn save_account(&mut self, account: &Account) -> crate::Result<()> {
-        // Only add account index if not already present
-        if !self.account_indexes.contains(account.index()) {
-            self.account_indexes.push(*account.index());
+        let new_account = !self.account_indexes.contains(account.index());
+        if new_account {
+            self.check_account_quota(self.account_indexes.len() + 1)?;
         }
 
+        // The transaction index is written with the account, so it always matches the stored transactions
+        let transaction_index = (
+            format!("{TRANSACTION_INDEX}{}", account.index()),
+            serde_json::to_string(&transaction_index(account))?,
+        );
+        if !new_account {
+            return self
+                .storage
+                .batch_write(
+                    HashMap::from([
+                        (
+                            format!("{ACCOUNT_INDEXATION_KEY}{}", account.index()),
+                            serde_json::to_string(account)?,
+                        ),
+                        transaction_index,
+                    ]),
+                    Vec::new(),
+                )
+                .await;
+        }
+
+        // Store the account together with its index and alias, so the indexes never point to a missing account if the
+        // byte quota is exceeded
+        let mut account_indexes = self.account_indexes.clone();
+        account_indexes.push(*account.index());
+        let account_aliases = self.account_aliases_with(account)?;
         self.storage
-            .set(ACCOUNTS_INDEXATION_KEY, self.account_indexes.clone())
+            .batch_write(
+                HashMap::from([
+                    (
+                        format!("{ACCOUNT_INDEXATION_KEY}{}", account.index()),
+                        serde_json::to_string(account)?,
+                    ),
+                    (
+                        ACCOUNTS_INDEXATION_KEY.to_string(),
+                        serde_json::to_string(&account_indexes)?,
+                    ),
+                    (
+                        ACCOUNT_ALIASES_INDEXATION_KEY.to_string(),
+                        serde_json::to_string(&account_aliases)?,
+                    ),
+                    transaction_index,
+                ]),
+                Vec::new(),
+            )
             .await?;
+        self.account_indexes = account_indexes;
+        self.account_aliases = account_aliases;
+
+        Ok(())
+    }
+
+    /// Stores an account with a changed alias and updates the alias index in a single write, fails if another account
+    /// already uses the alias.
+    pub(crate) async fn save_account_alias(&mut self, account: &Account) -> crate::Result<()> {
+        log::debug!("save_account_alias {}", account.index());
+        if !self.account_indexes.contains(account.index()) {
+            return Err(crate::Error::AccountNotFound(account.index().to_string()));
+        }
+
+        let account_aliases = self.account_aliases_with(account)?;
         self.storage
-            .set(&format!("{ACCOUNT_INDEXATION_KEY}{}", account.index()), account)
-            .await
+            .batch_write(
+                HashMap::from([
+                    (
+                        format!("{ACCOUNT_INDEXATION_KEY}{}", account.index()),
+                        serde_json::to_string(account)?,
+                    ),
+                    (
+                        ACCOUNT_ALIASES_INDEXATION_KEY.to_string(),
+                        serde_json::to_string(&account_aliases)?,
+                    ),
+                ]),
+                Vec::new(),
+            )
+            .await?;
+        self.account_aliases = account_aliases;
+
+        Ok(())
+    }
+
+    /// Returns the index of the account with the alias, aliases are compared case-insensitively.
+    pub(crate) fn get_account_index_by_alias(&self, alias: &str) -> Option<u32> {
+        self.account_aliases.get(&alias.to_lowercase()).copied()
+    }
+
+    // Returns the alias index with the current alias of the account, fails if another account already uses the alias
+    pub(crate) fn account_aliases_with(&self, account: &Account) -> crate::Result<HashMap<String, u32>> {
+        let alias = account.alias().to_lowercase();
+        if let Some(account_index) = self.account_aliases.get(&alias) {
+            if account_index != account.index() {
+                return Err(crate::Error::AccountAliasAlreadyExists(account.alias().clone()));
+            }
+        }
+        let mut account_aliases = self.account_aliases.clone();
+        account_aliases.retain(|_, account_index| account_index != account.index());
+        account_aliases.insert(alias, *account.index());
+        Ok(account_aliases)
     }
 
     pub async fn remove_account(&mut self, account_index: u32) -> crate::Result<()> {
+        self.remove_account_records(account_index, false).await?;
+        Ok(())
+    }
+
+    /// Removes the account and all other records of the account and updates the account indexes in a single write.
+    /// Returns the keys of the removed records, if `dry_run` is set nothing gets removed.
+    pub async fn remove_account_records(&mut self, account_index: u32, dry_run: bool) -> crate::Result<Vec<String>> {
+        log::debug!("remove_account_records {account_index} dry_run: {dry_run}");
+        let mut keys = Vec::new();
+        for key in account_keys(account_index) {
+            if self.storage.inner.get(&key).await?.is_some() {
+                keys.push(key);
+            }
+        }
+        if dry_run {
+            return Ok(keys);
+        }
+
+        let account_indexes = self
+            .account_indexes
+            .iter()
+            .copied()
+            .filter(|index| *index != account_index)
+            .collect::<Vec<_>>();
+        let mut account_aliases = self.account_aliases.clone();
+        account_aliases.retain(|_, index| *index != account_index);
         self.storage
-            .remove(&format!("{ACCOUNT_INDEXATION_KEY}{account_index}"))
+            .batch_write(
+                HashMap::from([
+                    (
+                        ACCOUNTS_INDEXATION_KEY.to_string(),
+                        serde_json::to_string(&account_indexes)?,
+                    ),
+                    (
+                        ACCOUNT_ALIASES_INDEXATION_KEY.to_string(),
+                        serde_json::to_string(&account_aliases)?,
+                    ),
+                ]),
+                keys.clone(),
+            )
             .await?;
-        self.account_indexes.retain(|a| a != &account_index);
+        self.account_indexes = account_indexes;
+        self.account_aliases = account_aliases;
+
+        Ok(keys)
+    }
+
+    /// Returns the client options of an account, if it doesn't use the ones of the account manager.
+    pub(crate) async fn get_account_client_options(&self, account_index: u32) -> crate::Result<Option<ClientOptions>> {
         self.storage
-            .set(ACCOUNTS_INDEXATION_KEY, self.account_indexes.clone())
+            .get(&format!("{ACCOUNT_CLIENT_OPTIONS}{account_index}"))
             .await
     }
+
+    /// Stores the client options of an account, with `None` the account uses the ones of the account manager again.
+    pub(crate) async fn save_account_client_options(
+        &mut self,
+        account_index: u32,
+        client_options: Option<&ClientOptions>,
+    ) -> crate::Result<()> {
+        let key = format!("{ACCOUNT_CLIENT_OPTIONS}{account_index}");
+        match client_options {
+            Some(client_options) => self.storage.set(&key, client_options).await,
+            // Some adapters fail to remove missing records
+            None if self.storage.inner.get(&key).await?.is_some() => self.storage.remove(&key).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the names of the profiles that store their records in this storage.
+    pub(crate) async fn get_profile_names(&self) -> crate::Result<Vec<String>> {
+        Ok(self.storage.get(PROFILES_INDEXATION_KEY).await?.unwrap_or_default())
+    }
+
+    pub(crate) async fn add_profile_name(&mut self, profile_name: &str) -> crate::Result<()> {
+        let mut profile_names = self.get_profile_names().await?;
+        if !profile_names.iter().any(|name| name == profile_name) {
+            profile_names.push(profile_name.to_string());
+            self.storage.set(PROFILES_INDEXATION_KEY, profile_names).await?;
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn get_recovery_checkpoint(&self) -> crate::Result<Option<RecoveryCheckpoint>> {
+        self.storage.get(ACCOUNT_RECOVERY_CHECKPOINT).await
+    }
+
+    pub(crate) async fn save_recovery_checkpoint(&mut self, checkpoint: &RecoveryCheckpoint) -> crate::Result<()> {
+        self.storage.set(ACCOUNT_RECOVERY_CHECKPOINT, checkpoint).await
+    }
+
+    pub(crate) async fn remove_recovery_checkpoint(&mut self) -> crate::Result<()> {
+        // Some adapters fail to remove missing records
+        if self.storage.inner.get(ACCOUNT_RECOVERY_CHECKPOINT).await?.is_some() {
+            self.storage.remove(ACCOUNT_RECOVERY_CHECKPOINT).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::adapter::memory::Memory;
+
+    #[tokio::test]
+    async fn hooks_run_after_unlock() {
+        let storage_manager = new_storage_manager(None, Box::<Memory>::default()).await.unwrap();
+
+        // The hook records if it could lock the storage manager itself
+        let unlocked = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let unlocked_clone = unlocked.clone();
+        let storage_manager_clone = storage_manager.clone();
+        storage_manager.lock().await.add_hook(move |event| {
+            unlocked_clone
+                .lock()
+                .unwrap()
+                .push((event.key.clone(), storage_manager_clone.0.try_lock().is_ok()));
+        });
+
+        let mut guard = storage_manager.lock().await;
+        guard.storage.set("first", 1).await.unwrap();
+        guard.storage.remove("first").await.unwrap();
+        // Nothing runs while the lock is held
+        assert!(unlocked.lock().unwrap().is_empty());
+        drop(guard);
+        assert_eq!(
+            *unlocked.lock().unwrap(),
+            vec![("first".to_string(), true), ("first".to_string(), true)]
+        );
+
+        storage_manager.lock().await.clear_hooks();
+    }
 }
@@ -0,0 +1,193 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::storage::{constants::*, manager::account_keys, Storage};
+
+type Records = BTreeMap<String, serde_json::Value>;
+
+/// Converts exported records from a database schema version to the next one.
+pub(crate) type Migration = fn(&mut Records) -> crate::Result<()>;
+
+/// The migrations of all previous database schema versions, `MIGRATIONS[n]` migrates from version `n + 1` to
+/// `n + 2`. Every schema change has to add a migration here.
+pub(crate) const MIGRATIONS: &[Migration] = &[add_account_aliases_index];
+
+/// Migrates exported records from their database schema version to [`DATABASE_SCHEMA_VERSION`], fails for records of
+/// newer versions.
+pub(crate) fn migrate_records(records: &mut Records, database_schema_version: u8) -> crate::Result<()> {
+    migrate_records_with(records, database_schema_version, DATABASE_SCHEMA_VERSION, MIGRATIONS)
+}
+
+/// Migrates the records of a database of an older schema version in place, like exported records, and stores the
+/// current schema version with them in a single write.
+pub(crate) async fn migrate_storage(storage: &mut Storage, database_schema_version: u8) -> crate::Result<()> {
+    log::debug!(
+        "[migrate_storage] from database schema version {}",
+        database_schema_version
+    );
+    // The same records as in an export
+    let mut keys = vec![
+        ACCOUNT_MANAGER_INDEXATION_KEY.to_string(),
+        ACCOUNTS_INDEXATION_KEY.to_string(),
+    ];
+    let account_indexes: Vec<u32> = storage.get(ACCOUNTS_INDEXATION_KEY).await?.unwrap_or_default();
+    for account_index in account_indexes {
+        keys.extend(account_keys(account_index));
+    }
+    let mut records = Records::new();
+    for key in keys {
+        if let Some(record) = storage.get::<serde_json::Value>(&key).await? {
+            records.insert(key, record);
+        }
+    }
+
+    let previous_records = records.clone();
+    migrate_records(&mut records, database_schema_version)?;
+
+    let removals = previous_records
+        .keys()
+        .filter(|key| !records.contains_key(*key))
+        .cloned()
+        .collect();
+    let mut changed_records = HashMap::new();
+    for (key, record) in records {
+        if previous_records.get(&key) != Some(&record) {
+            changed_records.insert(key, serde_json::to_string(&record)?);
+        }
+    }
+    changed_records.insert(
+        DATABASE_SCHEMA_VERSION_KEY.to_string(),
+        serde_json::to_string(&DATABASE_SCHEMA_VERSION)?,
+    );
+    storage.batch_write(changed_records, removals).await
+}
+
+fn migrate_records_with(
+    records: &mut Records,
+    from_version: u8,
+    to_version: u8,
+    migrations: &[Migration],
+) -> crate::Result<()> {
+    if from_version > to_version {
+        return Err(crate::Error::Storage(format!(
+            "unsupported database schema version {from_version}, the records were created with a newer version of \
+             the wallet"
+        )));
+    }
+    for version in from_version..to_version {
+        let migrate = usize::from(version)
+            .checked_sub(1)
+            .and_then(|index| migrations.get(index))
+            .ok_or_else(|| crate::Error::Storage(format!("no migration from database schema version {version}")))?;
+        log::debug!("[migrate_records] migrating from database schema version {}", version);
+        migrate(records)?;
+    }
+    Ok(())
+}
+
+// Version 2 added the index of the lowercase account aliases, it's built from the account records. The records of a
+// single account don't contain the indexes and stay unchanged.
+fn add_account_aliases_index(records: &mut Records) -> crate::Result<()> {
+    let Some(account_indexes) = records.get(ACCOUNTS_INDEXATION_KEY) else {
+        return Ok(());
+    };
+    let account_indexes: Vec<u32> = serde_json::from_value(account_indexes.clone())?;
+    let mut account_aliases = HashMap::new();
+    for account_index in account_indexes {
+        if let Some(alias) = records
+            .get(&format!("{ACCOUNT_INDEXATION_KEY}{account_index}"))
+            .and_then(|account| account.get("alias"))
+            .and_then(serde_json::Value::as_str)
+        {
+            account_aliases.insert(alias.to_lowercase(), account_index);
+        }
+    }
+    records.insert(
+        ACCOUNT_ALIASES_INDEXATION_KEY.to_string(),
+        serde_json::to_value(account_aliases)?,
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn add_coin_type(records: &mut Records) -> crate::Result<()> {
+        if let Some(serde_json::Value::Object(account)) = records.get_mut("account") {
+            account.insert("coinType".to_string(), 4218.into());
+        }
+        Ok(())
+    }
+
+    fn rename_alias(records: &mut Records) -> crate::Result<()> {
+        if let Some(serde_json::Value::Object(account)) = records.get_mut("account") {
+            if let Some(alias) = account.remove("name") {
+                account.insert("alias".to_string(), alias);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn migrations() {
+        let migrations: [Migration; 2] = [add_coin_type, rename_alias];
+        let mut records = Records::new();
+        records.insert("account".to_string(), serde_json::json!({ "name": "Alice" }));
+
+        // Records of the current version are unchanged
+        let mut current_records = records.clone();
+        migrate_records_with(&mut current_records, 3, 3, &migrations).unwrap();
+        assert_eq!(current_records, records);
+
+        // Only the migrations after the version of the records are applied
+        let mut second_version_records = records.clone();
+        migrate_records_with(&mut second_version_records, 2, 3, &migrations).unwrap();
+        assert_eq!(
+            second_version_records["account"],
+            serde_json::json!({ "alias": "Alice" })
+        );
+
+        migrate_records_with(&mut records, 1, 3, &migrations).unwrap();
+        assert_eq!(
+            records["account"],
+            serde_json::json!({ "alias": "Alice", "coinType": 4218 })
+        );
+
+        // Versions without migration and newer versions can't be migrated
+        assert!(migrate_records_with(&mut records, 1, 3, &migrations[..1]).is_err());
+        assert!(migrate_records_with(&mut records, 4, 3, &migrations).is_err());
+        assert!(migrate_records(&mut records, DATABASE_SCHEMA_VERSION + 1).is_err());
+    }
+
+    #[test]
+    fn account_aliases_index() {
+        let mut records = Records::new();
+        records.insert(ACCOUNTS_INDEXATION_KEY.to_string(), serde_json::json!([0, 1]));
+        records.insert(
+            format!("{ACCOUNT_INDEXATION_KEY}0"),
+            serde_json::json!({ "index": 0, "alias": "Alice" }),
+        );
+        records.insert(
+            format!("{ACCOUNT_INDEXATION_KEY}1"),
+            serde_json::json!({ "index": 1, "alias": "Bob" }),
+        );
+        migrate_records(&mut records, 1).unwrap();
+        assert_eq!(
+            records[ACCOUNT_ALIASES_INDEXATION_KEY],
+            serde_json::json!({ "alice": 0, "bob": 1 })
+        );
+
+        // Records of a single account have no indexes
+        let mut account_records = Records::new();
+        account_records.insert(
+            format!("{ACCOUNT_INDEXATION_KEY}0"),
+            serde_json::json!({ "index": 0, "alias": "Alice" }),
+        );
+        let previous_account_records = account_records.clone();
+        migrate_records(&mut account_records, 1).unwrap();
+        assert_eq!(account_records, previous_account_records);
+    }
+}
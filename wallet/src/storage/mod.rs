@@ -3,26 +3,81 @@
 
 /// Storage adapter.
 pub mod adapter;
+/// Storage functions to archive and restore accounts.
+mod archive;
+/// Storage functions for the audit log of sensitive operations.
+pub mod audit_log;
+/// Targets for scheduled backups.
+pub mod backup_target;
+/// Storage functions for the balance history of accounts.
+pub mod balance_history;
+/// Storage functions for the balance summaries of accounts.
+pub mod balance_summary;
+/// Logs that are stored in chunks.
+mod chunked_log;
 /// Storage constants.
 pub mod constants;
+/// Storage functions to cache derived addresses.
+pub(crate) mod derivation_cache;
+/// Storage functions for the log of emitted events.
+#[cfg(feature = "events")]
+#[cfg_attr(docsrs, doc(cfg(feature = "events")))]
+pub mod event_log;
+/// Storage functions to export and import the records as JSON.
+pub mod export;
+/// Storage write hooks.
+pub mod hooks;
+/// Storage functions for the inputs reserved by in-progress transactions.
+pub mod input_reservations;
+/// Storage functions for compaction and disk usage.
+pub mod maintenance;
 /// Storage manager.
 pub mod manager;
+/// Migrations of exported records to the current database schema.
+pub(crate) mod migration;
+/// Storage functions for the offline queue of signed transactions.
+pub mod offline_queue;
 /// Storage functions related to participation.
 #[cfg(feature = "participation")]
 #[cfg_attr(docsrs, doc(cfg(feature = "participation")))]
 mod participation;
+/// Storage quotas.
+pub mod quota;
+/// Storage functions for the scheduled payments of accounts.
+pub mod scheduled_payments;
+/// Storage functions for the fingerprint of the seed.
+pub(crate) mod seed_fingerprint;
+/// Storage functions for the sync state of accounts.
+pub mod sync_cursor;
+/// Storage functions for the transaction index of accounts.
+pub(crate) mod transaction_index;
+/// Storage functions for webhook payloads that couldn't be delivered.
+#[cfg(feature = "webhooks")]
+#[cfg_attr(docsrs, doc(cfg(feature = "webhooks")))]
+pub mod webhook_dead_letters;
 
 use std::collections::HashMap;
 
 use crypto::ciphers::chacha;
+use instant::Instant;
 use serde::{Deserialize, Serialize};
 
-use self::adapter::StorageAdapter;
+use self::{
+    adapter::StorageAdapter,
+    hooks::{StorageHooks, StorageOperation},
+    quota::StorageQuota,
+};
 
 #[derive(Debug)]
 pub(crate) struct Storage {
     inner: Box<dyn StorageAdapter + Sync + Send>,
     encryption_key: Option<[u8; 32]>,
+    hooks: StorageHooks,
+    // amount of removed records since the last compaction
+    removals_since_compaction: usize,
+    quota: StorageQuota,
+    // size of all records in bytes, only tracked if quota.max_bytes is set
+    used_bytes: u64,
 }
 
 impl Storage {
@@ -51,37 +106,105 @@ impl Storage {
 
     async fn set<T: Serialize + Send>(&mut self, key: &str, record: T) -> crate::Result<()> {
         let record = serde_json::to_string(&record)?;
-        self.inner
-            .set(
-                key,
-                if let Some(key) = &self.encryption_key {
-                    let output = chacha::aead_encrypt(key, record.as_bytes())?;
-                    serde_json::to_string(&output)?
-                } else {
-                    record
-                },
-            )
-            .await
+        let record = if let Some(key) = &self.encryption_key {
+            let output = chacha::aead_encrypt(key, record.as_bytes())?;
+            serde_json::to_string(&output)?
+        } else {
+            record
+        };
+        self.set_raw(key, record).await
+    }
+
+    // Writes a serialized and encrypted record, checks the byte quota and records the events for the hooks
+    async fn set_raw(&mut self, key: &str, record: String) -> crate::Result<()> {
+        let size = record.len();
+        let used_bytes = self.check_byte_quota(self.used_bytes, key, size as u64).await?;
+        let start_time = Instant::now();
+        self.inner.set(key, record).await?;
+        self.hooks
+            .record(StorageOperation::Set, key, size, start_time.elapsed());
+        self.used_bytes = used_bytes;
+        Ok(())
     }
 
     #[allow(dead_code)]
     async fn batch_set(&mut self, records: HashMap<String, String>) -> crate::Result<()> {
-        self.inner
-            .batch_set(if let Some(key) = &self.encryption_key {
-                let mut encrypted_records = HashMap::new();
-                for (id, record) in records {
-                    let output = chacha::aead_encrypt(key, record.as_bytes())?;
-                    encrypted_records.insert(id, serde_json::to_string(&output)?);
-                }
-                encrypted_records
-            } else {
-                records
-            })
-            .await
+        self.batch_write(records, Vec::new()).await
+    }
+
+    /// Sets and removes records in a single write, atomic if the adapter supports it.
+    async fn batch_write(&mut self, records: HashMap<String, String>, removals: Vec<String>) -> crate::Result<()> {
+        let records = if let Some(key) = &self.encryption_key {
+            let mut encrypted_records = HashMap::new();
+            for (id, record) in records {
+                let output = chacha::aead_encrypt(key, record.as_bytes())?;
+                encrypted_records.insert(id, serde_json::to_string(&output)?);
+            }
+            encrypted_records
+        } else {
+            records
+        };
+        self.batch_write_raw(records, removals).await
+    }
+
+    // Writes serialized and encrypted records, checks the byte quota and records the events for the hooks
+    async fn batch_write_raw(&mut self, records: HashMap<String, String>, removals: Vec<String>) -> crate::Result<()> {
+        let sizes = records
+            .iter()
+            .map(|(key, record)| (key.clone(), record.len()))
+            .collect::<Vec<_>>();
+        let mut used_bytes = self.used_bytes;
+        for key in &removals {
+            used_bytes = self.check_byte_quota(used_bytes, key, 0).await?;
+        }
+        for (key, size) in &sizes {
+            used_bytes = self.check_byte_quota(used_bytes, key, *size as u64).await?;
+        }
+        let start_time = Instant::now();
+        self.inner.batch_write(records, removals.clone()).await?;
+        let duration = start_time.elapsed();
+        for (key, size) in sizes {
+            self.hooks.record(StorageOperation::Set, &key, size, duration);
+        }
+        for key in &removals {
+            self.hooks.record(StorageOperation::Remove, key, 0, duration);
+        }
+        self.removals_since_compaction += removals.len();
+        self.used_bytes = used_bytes;
+        Ok(())
     }
 
     async fn remove(&mut self, key: &str) -> crate::Result<()> {
-        self.inner.remove(key).await
+        let used_bytes = self.check_byte_quota(self.used_bytes, key, 0).await?;
+        let start_time = Instant::now();
+        self.inner.remove(key).await?;
+        self.used_bytes = used_bytes;
+        self.hooks
+            .record(StorageOperation::Remove, key, 0, start_time.elapsed());
+        self.removals_since_compaction += 1;
+        Ok(())
+    }
+}
+
+impl Storage {
+    // Returns the used bytes after the record for the key got replaced by one with the new size, or an error if that
+    // would exceed the byte quota
+    async fn check_byte_quota(&self, used_bytes: u64, key: &str, new_size: u64) -> crate::Result<u64> {
+        let max_bytes = match self.quota.max_bytes {
+            Some(max_bytes) => max_bytes,
+            None => return Ok(used_bytes),
+        };
+        let previous_size = self.inner.get(key).await?.map_or(0, |record| record.len() as u64);
+        let used_bytes = used_bytes.saturating_sub(previous_size) + new_size;
+        // Writes that don't grow the storage are always allowed, so it's possible to free space again
+        if new_size > previous_size && used_bytes > max_bytes {
+            return Err(crate::Error::StorageQuotaExceeded {
+                quota: "bytes",
+                required: used_bytes,
+                allowed: max_bytes,
+            });
+        }
+        Ok(used_bytes)
     }
 }
 
@@ -0,0 +1,52 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_client::{api::SignedTransactionDataDto, block::payload::transaction::TransactionId};
+use serde::{Deserialize, Serialize};
+
+use super::manager::StorageManager;
+use crate::storage::constants::OFFLINE_QUEUE;
+
+/// The operation a queued transaction was created for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QueuedOperation {
+    /// Outputs are sent.
+    Send,
+    /// Outputs with additional unlock conditions are claimed.
+    ClaimOutputs,
+}
+
+/// A signed transaction that waits in the offline queue of an account until it can be submitted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueuedTransaction {
+    /// The id of the transaction.
+    pub transaction_id: TransactionId,
+    /// The operation the transaction was created for.
+    pub operation: QueuedOperation,
+    /// Unix timestamp in milliseconds when the transaction was queued.
+    pub timestamp: u64,
+    /// The signed transaction with the data of its inputs, to check them for conflicts before submitting.
+    pub signed_transaction_data: SignedTransactionDataDto,
+}
+
+impl StorageManager {
+    /// Returns the queued transactions of an account, oldest first.
+    pub(crate) async fn get_offline_queue(&self, account_index: u32) -> crate::Result<Vec<QueuedTransaction>> {
+        Ok(self
+            .storage
+            .get(&format!("{OFFLINE_QUEUE}{account_index}"))
+            .await?
+            .unwrap_or_default())
+    }
+
+    pub(crate) async fn save_offline_queue(
+        &mut self,
+        account_index: u32,
+        queued_transactions: &[QueuedTransaction],
+    ) -> crate::Result<()> {
+        self.storage
+            .set(&format!("{OFFLINE_QUEUE}{account_index}"), queued_transactions)
+            .await
+    }
+}
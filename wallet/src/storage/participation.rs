@@ -10,8 +10,8 @@ use iota_client::{
 
 use super::manager::StorageManager;
 use crate::{
-    account::operations::participation::ParticipationEventWithNodes,
-    storage::constants::{PARTICIPATION_CACHED_OUTPUTS, PARTICIPATION_EVENTS},
+    account::operations::participation::{ParticipationEventWithNodes, ParticipationState},
+    storage::constants::{PARTICIPATION_CACHED_OUTPUTS, PARTICIPATION_EVENTS, PARTICIPATION_STATE},
 };
 
 impl StorageManager {
@@ -108,4 +108,27 @@ impl StorageManager {
             .await?
             .unwrap_or_default())
     }
+
+    pub(crate) async fn set_participation_state(
+        &mut self,
+        account_index: u32,
+        state: &ParticipationState,
+    ) -> crate::Result<()> {
+        log::debug!("set_participation_state");
+
+        self.storage
+            .set(&format!("{PARTICIPATION_STATE}{account_index}"), state)
+            .await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn get_participation_state(
+        &self,
+        account_index: u32,
+    ) -> crate::Result<Option<ParticipationState>> {
+        log::debug!("get_participation_state");
+
+        self.storage.get(&format!("{PARTICIPATION_STATE}{account_index}")).await
+    }
 }
@@ -0,0 +1,49 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use super::manager::StorageManager;
+
+/// Limits for a storage namespace, so a single wallet can't exhaust a database that is shared with other wallets.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageQuota {
+    /// The maximum amount of accounts.
+    pub max_accounts: Option<usize>,
+    /// The maximum size of all stored records in bytes.
+    pub max_bytes: Option<u64>,
+}
+
+impl StorageManager {
+    /// Sets the quota for this storage. Existing records that already exceed the quota are kept, but writes that would
+    /// grow the storage further are rejected with [`crate::Error::StorageQuotaExceeded`].
+    pub async fn set_quota(&mut self, quota: StorageQuota) -> crate::Result<()> {
+        log::debug!("set_quota {quota:?}");
+        self.storage.used_bytes = self.disk_usage().await?.domains.values().sum();
+        self.storage.quota = quota;
+        Ok(())
+    }
+
+    /// Returns the current storage quota.
+    pub fn quota(&self) -> StorageQuota {
+        self.storage.quota
+    }
+
+    /// Returns the size of all stored records in bytes, only tracked while a byte limit is set.
+    pub fn used_bytes(&self) -> u64 {
+        self.storage.used_bytes
+    }
+
+    // Returns an error if the amount of accounts would exceed the account quota
+    pub(crate) fn check_account_quota(&self, required_accounts: usize) -> crate::Result<()> {
+        match self.storage.quota.max_accounts {
+            Some(max_accounts) if required_accounts > max_accounts => Err(crate::Error::StorageQuotaExceeded {
+                quota: "accounts",
+                required: required_accounts as u64,
+                allowed: max_accounts as u64,
+            }),
+            _ => Ok(()),
+        }
+    }
+}
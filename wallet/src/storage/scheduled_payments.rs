@@ -0,0 +1,50 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use super::manager::StorageManager;
+use crate::storage::constants::SCHEDULED_PAYMENTS;
+
+/// A future-dated or recurring payment of an account, executed by the background syncing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledPayment {
+    /// The id of the payment, unique in the account.
+    pub id: u32,
+    /// The bech32 encoded address of the recipient.
+    pub address: String,
+    /// The amount that is sent with every execution.
+    pub amount: u64,
+    /// Unix timestamp in milliseconds when the payment is executed next.
+    pub next_execution: u64,
+    /// The time between two executions in milliseconds, `None` for a payment that is only executed once.
+    pub interval_in_milliseconds: Option<u64>,
+    /// Unix timestamp in milliseconds after which the payment isn't executed anymore.
+    pub expires_at: Option<u64>,
+    /// How many times the payment was executed.
+    pub executions: u32,
+    /// The error of the last execution, if it failed.
+    pub last_error: Option<String>,
+}
+
+impl StorageManager {
+    /// Returns the scheduled payments of an account.
+    pub(crate) async fn get_scheduled_payments(&self, account_index: u32) -> crate::Result<Vec<ScheduledPayment>> {
+        Ok(self
+            .storage
+            .get(&format!("{SCHEDULED_PAYMENTS}{account_index}"))
+            .await?
+            .unwrap_or_default())
+    }
+
+    pub(crate) async fn save_scheduled_payments(
+        &mut self,
+        account_index: u32,
+        scheduled_payments: &[ScheduledPayment],
+    ) -> crate::Result<()> {
+        self.storage
+            .set(&format!("{SCHEDULED_PAYMENTS}{account_index}"), scheduled_payments)
+            .await
+    }
+}
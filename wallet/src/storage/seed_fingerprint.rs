@@ -0,0 +1,163 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use crypto::hashes::{blake2b::Blake2b256, Digest};
+use iota_client::{block::address::Address, secret::SecretManager};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use super::manager::{StorageManager, StorageManagerHandle};
+#[cfg(feature = "ledger_nano")]
+use crate::account_manager::LedgerNanoQueue;
+use crate::{account::builder::get_first_public_address, storage::constants::*};
+
+// Hashed together with the address, so the fingerprint can't be linked to the address
+const SEED_FINGERPRINT_DOMAIN: &[u8] = b"iota-wallet-seed-fingerprint";
+
+/// A non-reversible fingerprint of the seed of a secret manager, used to detect if a secret manager with another seed
+/// is used for an existing storage.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SeedFingerprint {
+    /// The coin type that was used to derive the address.
+    coin_type: u32,
+    /// Hex encoded hash of the first public address of the account with index 0.
+    fingerprint: String,
+}
+
+impl SeedFingerprint {
+    /// Derives the first public address of the account with index 0 and hashes it.
+    pub(crate) async fn new(
+        secret_manager: &Arc<RwLock<SecretManager>>,
+        #[cfg(feature = "ledger_nano")] ledger_nano_queue: &LedgerNanoQueue,
+        coin_type: u32,
+    ) -> crate::Result<Self> {
+        let mut hasher = Blake2b256::new();
+        hasher.update(SEED_FINGERPRINT_DOMAIN);
+        // Secret managers only generate Ed25519 addresses
+        if let Address::Ed25519(address) = get_first_public_address(
+            secret_manager,
+            #[cfg(feature = "ledger_nano")]
+            ledger_nano_queue,
+            coin_type,
+            0,
+        )
+        .await?
+        {
+            hasher.update(*address);
+        }
+
+        Ok(Self {
+            coin_type,
+            fingerprint: prefix_hex::encode(hasher.finalize().to_vec()),
+        })
+    }
+
+    /// Checks if the secret manager has the same seed as the one this fingerprint was created from.
+    pub(crate) async fn verify(
+        &self,
+        secret_manager: &Arc<RwLock<SecretManager>>,
+        #[cfg(feature = "ledger_nano")] ledger_nano_queue: &LedgerNanoQueue,
+    ) -> crate::Result<()> {
+        let seed_fingerprint = Self::new(
+            secret_manager,
+            #[cfg(feature = "ledger_nano")]
+            ledger_nano_queue,
+            self.coin_type,
+        )
+        .await?;
+        if seed_fingerprint != *self {
+            return Err(crate::Error::SeedFingerprintMismatch);
+        }
+        Ok(())
+    }
+}
+
+impl StorageManager {
+    pub(crate) async fn get_seed_fingerprint(&self) -> crate::Result<Option<SeedFingerprint>> {
+        self.storage.get(SEED_FINGERPRINT_KEY).await
+    }
+
+    pub(crate) async fn has_seed_fingerprint(&self) -> crate::Result<bool> {
+        Ok(self.get_seed_fingerprint().await?.is_some())
+    }
+
+    pub(crate) async fn save_seed_fingerprint(&mut self, seed_fingerprint: &SeedFingerprint) -> crate::Result<()> {
+        log::debug!("save_seed_fingerprint");
+        self.storage.set(SEED_FINGERPRINT_KEY, seed_fingerprint).await
+    }
+
+    pub(crate) async fn remove_seed_fingerprint(&mut self) -> crate::Result<()> {
+        log::debug!("remove_seed_fingerprint");
+        // Some adapters fail to remove missing records
+        if self.has_seed_fingerprint().await? {
+            self.storage.remove(SEED_FINGERPRINT_KEY).await?;
+        }
+        self.set_seed_fingerprint_unverified(false).await
+    }
+
+    /// Returns true if the secret manager wasn't verified against the seed fingerprint yet, because it wasn't
+    /// available when the account manager was built.
+    pub(crate) async fn is_seed_fingerprint_unverified(&self) -> crate::Result<bool> {
+        Ok(self
+            .storage
+            .get(SEED_FINGERPRINT_UNVERIFIED_KEY)
+            .await?
+            .unwrap_or_default())
+    }
+
+    // Stored, so a secret manager that was stored without verification is still verified after a restart
+    pub(crate) async fn set_seed_fingerprint_unverified(&mut self, unverified: bool) -> crate::Result<()> {
+        if unverified {
+            self.storage.set(SEED_FINGERPRINT_UNVERIFIED_KEY, true).await
+        } else if self.is_seed_fingerprint_unverified().await? {
+            self.storage.remove(SEED_FINGERPRINT_UNVERIFIED_KEY).await
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Verifies the secret manager against the seed fingerprint, if that was deferred when the account manager was built,
+/// before the secret manager is used the first time.
+pub(crate) async fn verify_unverified_seed_fingerprint(
+    storage_manager: &StorageManagerHandle,
+    secret_manager: &Arc<RwLock<SecretManager>>,
+    #[cfg(feature = "ledger_nano")] ledger_nano_queue: &LedgerNanoQueue,
+) -> crate::Result<()> {
+    let seed_fingerprint = {
+        let storage_manager = storage_manager.lock().await;
+        if !storage_manager.is_seed_fingerprint_unverified().await? {
+            return Ok(());
+        }
+        storage_manager.get_seed_fingerprint().await?
+    };
+    // The storage isn't locked while the address is derived, a Ledger Nano can take a while
+    if let Some(seed_fingerprint) = seed_fingerprint {
+        seed_fingerprint
+            .verify(
+                secret_manager,
+                #[cfg(feature = "ledger_nano")]
+                ledger_nano_queue,
+            )
+            .await?;
+    }
+    storage_manager
+        .lock()
+        .await
+        .set_seed_fingerprint_unverified(false)
+        .await
+}
+
+/// Returns true if the secret manager can derive addresses without user interaction, a locked Stronghold or a Ledger
+/// Nano can't.
+pub(crate) async fn is_secret_manager_available(secret_manager: &Arc<RwLock<SecretManager>>) -> bool {
+    match &*secret_manager.read().await {
+        SecretManager::Mnemonic(_) => true,
+        #[cfg(feature = "stronghold")]
+        SecretManager::Stronghold(stronghold) => stronghold.is_key_available().await,
+        _ => false,
+    }
+}
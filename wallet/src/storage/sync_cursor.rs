@@ -0,0 +1,86 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::manager::StorageManager;
+use crate::{
+    account::SyncOptions,
+    storage::constants::{ADDRESS_SYNC_CURSORS, SYNC_CURSOR},
+};
+
+/// The state of the last completed sync of an account, stored so a restarted process can continue from it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncCursor {
+    /// The latest milestone index of the node when the sync finished.
+    pub milestone_index: u32,
+    /// The timestamp of the latest milestone.
+    pub milestone_timestamp: Option<u32>,
+    /// The confirmed milestone index, the ledger state of the synced outputs.
+    pub ledger_index: u32,
+    /// The highest synced public address index.
+    pub address_index: u32,
+    /// The highest synced internal address index.
+    pub internal_address_index: u32,
+    /// Unix timestamp in milliseconds when the sync finished.
+    pub synced_at: u64,
+    /// The url of the node that was synced with.
+    #[serde(default)]
+    pub node_url: Option<String>,
+}
+
+/// Milestone timestamps up to which the outputs of the addresses of an account were requested, so following syncs
+/// only need to request the outputs that were created afterwards.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AddressSyncCursors {
+    /// The options of the syncs that set the cursors, with other options other outputs would have been requested.
+    pub(crate) sync_options: SyncOptions,
+    /// Milestone timestamps by the bech32 encoded ed25519, alias and nft addresses.
+    pub(crate) cursors: HashMap<String, u32>,
+}
+
+impl StorageManager {
+    pub(crate) async fn get_sync_cursor(&self, account_index: u32) -> crate::Result<Option<SyncCursor>> {
+        self.storage.get(&format!("{SYNC_CURSOR}{account_index}")).await
+    }
+
+    pub(crate) async fn set_sync_cursor(&mut self, account_index: u32, sync_cursor: &SyncCursor) -> crate::Result<()> {
+        log::debug!("set_sync_cursor {account_index} {sync_cursor:?}");
+        self.storage
+            .set(&format!("{SYNC_CURSOR}{account_index}"), sync_cursor)
+            .await
+    }
+
+    pub(crate) async fn get_address_sync_cursors(
+        &self,
+        account_index: u32,
+    ) -> crate::Result<Option<AddressSyncCursors>> {
+        self.storage
+            .get(&format!("{ADDRESS_SYNC_CURSORS}{account_index}"))
+            .await
+    }
+
+    pub(crate) async fn set_address_sync_cursors(
+        &mut self,
+        account_index: u32,
+        address_sync_cursors: &AddressSyncCursors,
+    ) -> crate::Result<()> {
+        self.storage
+            .set(&format!("{ADDRESS_SYNC_CURSORS}{account_index}"), address_sync_cursors)
+            .await
+    }
+
+    /// Removes the address cursors of an account, so the next sync requests all outputs again.
+    pub(crate) async fn remove_address_sync_cursors(&mut self, account_index: u32) -> crate::Result<()> {
+        let key = format!("{ADDRESS_SYNC_CURSORS}{account_index}");
+        // Some adapters fail to remove missing records
+        if self.storage.get::<serde_json::Value>(&key).await?.is_some() {
+            self.storage.remove(&key).await?;
+        }
+        Ok(())
+    }
+}
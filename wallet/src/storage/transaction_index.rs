@@ -0,0 +1,140 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+
+use iota_client::block::{
+    address::Address,
+    output::TokenId,
+    payload::{
+        transaction::{TransactionEssence, TransactionId},
+        Payload,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+use super::manager::StorageManager;
+use crate::{
+    account::{
+        operations::helpers::output::output_address,
+        types::{InclusionState, Transaction},
+        Account,
+    },
+    storage::constants::TRANSACTION_INDEX,
+};
+
+/// A transaction of an account with the fields it can be filtered by, so the transaction history can be paged without
+/// going over the transactions themselves.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TransactionIndexEntry {
+    pub(crate) transaction_id: TransactionId,
+    pub(crate) timestamp: u128,
+    pub(crate) incoming: bool,
+    pub(crate) inclusion_state: InclusionState,
+    // The base coin amount sent to other addresses, or received by the account for incoming transactions
+    pub(crate) amount: u64,
+    pub(crate) token_ids: Vec<TokenId>,
+    // Addresses of the outputs and their senders
+    pub(crate) addresses: Vec<Address>,
+    // Tags of the tagged data payload and of the outputs
+    pub(crate) tags: Vec<Vec<u8>>,
+}
+
+impl StorageManager {
+    /// Returns the transaction index of an account, newest transactions first, `None` if it wasn't stored yet.
+    pub(crate) async fn get_transaction_index(
+        &self,
+        account_index: u32,
+    ) -> crate::Result<Option<Vec<TransactionIndexEntry>>> {
+        self.storage.get(&format!("{TRANSACTION_INDEX}{account_index}")).await
+    }
+
+    pub(crate) async fn save_transaction_index(
+        &mut self,
+        account_index: u32,
+        transaction_index: &[TransactionIndexEntry],
+    ) -> crate::Result<()> {
+        self.storage
+            .set(&format!("{TRANSACTION_INDEX}{account_index}"), transaction_index)
+            .await
+    }
+}
+
+/// Builds the transaction index of the sent and incoming transactions of an account, newest transactions first.
+pub(crate) fn transaction_index(account: &Account) -> Vec<TransactionIndexEntry> {
+    let account_addresses: HashSet<Address> = account
+        .public_addresses()
+        .iter()
+        .chain(account.internal_addresses().iter())
+        .map(|address| *address.address().as_ref())
+        .collect();
+
+    let mut transaction_index: Vec<TransactionIndexEntry> = account
+        .transactions()
+        .values()
+        .chain(account.incoming_transactions().values())
+        .map(|transaction| transaction_index_entry(transaction, &account_addresses))
+        .collect();
+    transaction_index.sort_by(|a, b| {
+        b.timestamp
+            .cmp(&a.timestamp)
+            .then(a.transaction_id.cmp(&b.transaction_id))
+    });
+
+    transaction_index
+}
+
+fn transaction_index_entry(transaction: &Transaction, account_addresses: &HashSet<Address>) -> TransactionIndexEntry {
+    let TransactionEssence::Regular(essence) = transaction.payload.essence();
+
+    let mut amount = 0;
+    let mut token_ids = Vec::new();
+    let mut addresses = Vec::new();
+    let mut tags = Vec::new();
+    if let Some(Payload::TaggedData(tagged_data_payload)) = essence.payload() {
+        tags.push(tagged_data_payload.tag().to_vec());
+    }
+
+    for output in essence.outputs().iter() {
+        let address = output_address(output);
+        // Sent transactions transfer the outputs to other addresses, incoming ones the outputs to the account
+        if address.map_or(false, |address| {
+            account_addresses.contains(&address) == transaction.incoming
+        }) {
+            amount += output.amount();
+        }
+        let sender = output
+            .features()
+            .and_then(|features| features.sender())
+            .map(|sender| *sender.address());
+        for address in address.into_iter().chain(sender) {
+            if !addresses.contains(&address) {
+                addresses.push(address);
+            }
+        }
+        if let Some(tag) = output.features().and_then(|features| features.tag()) {
+            tags.push(tag.tag().to_vec());
+        }
+        for native_token in output
+            .native_tokens()
+            .into_iter()
+            .flat_map(|native_tokens| native_tokens.iter())
+        {
+            if !token_ids.contains(native_token.token_id()) {
+                token_ids.push(*native_token.token_id());
+            }
+        }
+    }
+
+    TransactionIndexEntry {
+        transaction_id: transaction.transaction_id,
+        timestamp: transaction.timestamp,
+        incoming: transaction.incoming,
+        inclusion_state: transaction.inclusion_state,
+        amount,
+        token_ids,
+        addresses,
+        tags,
+    }
+}
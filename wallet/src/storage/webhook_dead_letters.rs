@@ -0,0 +1,51 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use super::manager::StorageManager;
+use crate::{
+    account_manager::WebhookPayload,
+    storage::constants::{WEBHOOK_DEAD_LETTERS, WEBHOOK_DEAD_LETTERS_CAPACITY},
+};
+
+/// A webhook payload that couldn't be delivered after all retries.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookDeadLetter {
+    /// The url of the webhook.
+    pub url: String,
+    /// The payload that was posted.
+    pub payload: WebhookPayload,
+    /// The error of the last attempt.
+    pub error: String,
+    /// Amount of delivery attempts.
+    pub attempts: u32,
+}
+
+impl StorageManager {
+    /// Returns the webhook payloads that couldn't be delivered, oldest first.
+    pub(crate) async fn get_webhook_dead_letters(&self) -> crate::Result<Vec<WebhookDeadLetter>> {
+        Ok(self.storage.get(WEBHOOK_DEAD_LETTERS).await?.unwrap_or_default())
+    }
+
+    /// Stores a webhook payload that couldn't be delivered, drops the oldest one if more than
+    /// `WEBHOOK_DEAD_LETTERS_CAPACITY` are stored.
+    pub(crate) async fn add_webhook_dead_letter(&mut self, dead_letter: WebhookDeadLetter) -> crate::Result<()> {
+        let mut dead_letters = self.get_webhook_dead_letters().await?;
+        dead_letters.push(dead_letter);
+        if dead_letters.len() > WEBHOOK_DEAD_LETTERS_CAPACITY {
+            dead_letters.drain(..dead_letters.len() - WEBHOOK_DEAD_LETTERS_CAPACITY);
+        }
+        self.storage.set(WEBHOOK_DEAD_LETTERS, &dead_letters).await
+    }
+
+    /// Removes the stored webhook payloads that couldn't be delivered.
+    pub(crate) async fn clear_webhook_dead_letters(&mut self) -> crate::Result<()> {
+        // Some adapters fail to remove missing records
+        if self.storage.inner.get(WEBHOOK_DEAD_LETTERS).await?.is_some() {
+            self.storage.remove(WEBHOOK_DEAD_LETTERS).await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+/// A UTC date and time of the proleptic Gregorian calendar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct UtcDateTime {
+    pub(crate) year: u64,
+    pub(crate) month: u64,
+    pub(crate) day: u64,
+    pub(crate) hour: u64,
+    pub(crate) minute: u64,
+    pub(crate) second: u64,
+}
+
+impl UtcDateTime {
+    /// Converts a unix timestamp in seconds.
+    pub(crate) fn from_unix_seconds(seconds: u64) -> Self {
+        let (days, seconds_of_day) = (seconds / 86400, seconds % 86400);
+
+        // Civil date from the days since the unix epoch, see http://howardhinnant.github.io/date_algorithms.html
+        let days = days + 719_468;
+        let era = days / 146_097;
+        let day_of_era = days % 146_097;
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+        let month = if month_index < 10 {
+            month_index + 3
+        } else {
+            month_index - 9
+        };
+
+        Self {
+            year: year_of_era + era * 400 + u64::from(month <= 2),
+            month,
+            day,
+            hour: seconds_of_day / 3600,
+            minute: seconds_of_day % 3600 / 60,
+            second: seconds_of_day % 60,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_unix_seconds() {
+        let date_time = |year, month, day, hour, minute, second| UtcDateTime {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+        };
+        assert_eq!(UtcDateTime::from_unix_seconds(0), date_time(1970, 1, 1, 0, 0, 0));
+        assert_eq!(
+            UtcDateTime::from_unix_seconds(951_782_400),
+            date_time(2000, 2, 29, 0, 0, 0)
+        );
+        assert_eq!(
+            UtcDateTime::from_unix_seconds(1_709_251_199),
+            date_time(2024, 2, 29, 23, 59, 59)
+        );
+        assert_eq!(
+            UtcDateTime::from_unix_seconds(4_107_542_400),
+            date_time(2100, 3, 1, 0, 0, 0)
+        );
+    }
+}
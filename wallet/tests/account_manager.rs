@@ -7,15 +7,24 @@ mod common;
 use std::path::PathBuf;
 
 use iota_client::constants::IOTA_COIN_TYPE;
-#[cfg(feature = "storage")]
-use iota_client::node_manager::node::{Node, NodeDto, Url};
 #[cfg(feature = "stronghold")]
 use iota_client::secret::stronghold::StrongholdSecretManager;
+#[cfg(feature = "storage")]
+use iota_client::{
+    node_manager::node::{Node, NodeDto, Url},
+    Client,
+};
+#[cfg(feature = "storage")]
+use iota_wallet::account_manager::SchedulePaymentOptions;
+#[cfg(all(feature = "events", feature = "storage"))]
+use iota_wallet::events::types::WalletEvent;
 use iota_wallet::{
     account_manager::AccountManager,
     secret::{mnemonic::MnemonicSecretManager, SecretManager},
     ClientOptions, Result,
 };
+#[cfg(feature = "webhooks")]
+use iota_wallet::{account_manager::Webhook, events::types::EventFilter};
 
 #[tokio::test]
 async fn update_client_options() -> Result<()> {
@@ -58,18 +67,12 @@ async fn different_seed() -> Result<()> {
     drop(_account);
     drop(manager);
 
-    // Recreate AccountManager with a different mnemonic
-    let manager = common::make_manager(storage_path, None, None).await?;
-
-    // Generating a new account needs to return an error, because the seed from the secret_manager is different
-    assert!(
-        manager
-            .create_account()
-            .with_alias("Bob".to_string())
-            .finish()
-            .await
-            .is_err()
-    );
+    // Recreating the AccountManager with a different mnemonic needs to return an error, because the seed from the
+    // secret_manager doesn't match the stored seed fingerprint
+    assert!(matches!(
+        common::make_manager(storage_path, None, None).await,
+        Err(iota_wallet::Error::SeedFingerprintMismatch)
+    ));
 
     common::tear_down(storage_path)
 }
@@ -254,3 +257,603 @@ async fn update_node_auth() -> Result<()> {
 
     common::tear_down(storage_path)
 }
+
+#[cfg(feature = "storage")]
+#[tokio::test]
+async fn encrypted_mnemonic_restored() -> Result<()> {
+    let storage_path = "test-storage/encrypted_mnemonic_restored";
+    common::setup(storage_path)?;
+
+    let manager = AccountManager::builder()
+        .with_encrypted_mnemonic(common::DEFAULT_MNEMONIC.to_string(), "passphrase".to_string(), None)?
+        .with_client_options(ClientOptions::new().with_node(common::NODE_LOCAL)?)
+        .with_coin_type(IOTA_COIN_TYPE)
+        .with_storage_path(storage_path)
+        .finish()
+        .await?;
+    let address = manager.create_account().finish().await?.addresses().await?[0].clone();
+    drop(manager);
+
+    // A wrong passphrase can't decrypt the mnemonic
+    assert!(matches!(
+        AccountManager::builder()
+            .with_mnemonic_passphrase("wrong passphrase".to_string(), None)
+            .with_storage_path(storage_path)
+            .finish()
+            .await,
+        Err(iota_wallet::Error::Decryption(_))
+    ));
+
+    // The secret manager is restored from the storage, so new addresses can be generated
+    let manager = AccountManager::builder()
+        .with_mnemonic_passphrase("passphrase".to_string(), None)
+        .with_storage_path(storage_path)
+        .finish()
+        .await?;
+    let account = manager.get_account(0).await?;
+    assert_eq!(account.addresses().await?[0], address);
+    account.generate_addresses(1, None).await?;
+
+    common::tear_down(storage_path)
+}
+
+#[cfg(feature = "storage")]
+#[tokio::test]
+async fn bip39_passphrase() -> Result<()> {
+    let storage_path = "test-storage/bip39_passphrase";
+    common::setup(storage_path)?;
+
+    let manager = AccountManager::builder()
+        .with_mnemonic(common::DEFAULT_MNEMONIC.to_string(), Some("passphrase".to_string()))?
+        .with_client_options(ClientOptions::new().with_node(common::NODE_LOCAL)?)
+        .with_coin_type(IOTA_COIN_TYPE)
+        .with_storage_path(storage_path)
+        .finish()
+        .await?;
+    let address = manager.create_account().finish().await?.addresses().await?[0].clone();
+    drop(manager);
+
+    // The passphrase results in a different seed
+    let secret_manager = MnemonicSecretManager::try_from_mnemonic(common::DEFAULT_MNEMONIC)?;
+    let manager = AccountManager::builder()
+        .with_secret_manager(SecretManager::Mnemonic(secret_manager))
+        .with_client_options(ClientOptions::new().with_node(common::NODE_LOCAL)?)
+        .with_coin_type(IOTA_COIN_TYPE)
+        .with_storage_path(&format!("{storage_path}/without_passphrase"))
+        .finish()
+        .await?;
+    assert_ne!(manager.create_account().finish().await?.addresses().await?[0], address);
+    drop(manager);
+
+    // Restoring with a wrong passphrase fails
+    assert!(matches!(
+        AccountManager::builder()
+            .with_mnemonic(common::DEFAULT_MNEMONIC.to_string(), None)?
+            .with_storage_path(storage_path)
+            .finish()
+            .await,
+        Err(iota_wallet::Error::SeedFingerprintMismatch)
+    ));
+
+    let manager = AccountManager::builder()
+        .with_mnemonic(common::DEFAULT_MNEMONIC.to_string(), Some("passphrase".to_string()))?
+        .with_storage_path(storage_path)
+        .finish()
+        .await?;
+    assert_eq!(manager.get_account(0).await?.addresses().await?[0], address);
+
+    common::tear_down(storage_path)
+}
+
+#[cfg(feature = "storage")]
+#[tokio::test]
+async fn seed_fingerprint_mismatch() -> Result<()> {
+    let storage_path = "test-storage/seed_fingerprint_mismatch";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+    let address = manager.create_account().finish().await?.addresses().await?[0].clone();
+    drop(manager);
+
+    // A secret manager with another seed is rejected
+    let secret_manager = MnemonicSecretManager::try_from_mnemonic(&Client::generate_mnemonic()?)?;
+    assert!(matches!(
+        AccountManager::builder()
+            .with_secret_manager(SecretManager::Mnemonic(secret_manager))
+            .with_storage_path(storage_path)
+            .finish()
+            .await,
+        Err(iota_wallet::Error::SeedFingerprintMismatch)
+    ));
+
+    // The rejected secret manager didn't replace the stored data
+    let secret_manager = MnemonicSecretManager::try_from_mnemonic(common::DEFAULT_MNEMONIC)?;
+    let manager = AccountManager::builder()
+        .with_secret_manager(SecretManager::Mnemonic(secret_manager))
+        .with_storage_path(storage_path)
+        .finish()
+        .await?;
+    assert_eq!(manager.get_account(0).await?.addresses().await?[0], address);
+
+    common::tear_down(storage_path)
+}
+
+#[cfg(feature = "stronghold")]
+#[tokio::test]
+async fn change_stronghold_password() -> Result<()> {
+    let storage_path = "test-storage/change_stronghold_password";
+    common::setup(storage_path)?;
+
+    std::fs::create_dir_all(storage_path).unwrap_or(());
+    let snapshot_path = PathBuf::from(format!("{storage_path}/test.stronghold"));
+    let mut secret_manager = StrongholdSecretManager::builder()
+        .password("current_password")
+        .build(snapshot_path.clone())?;
+    secret_manager
+        .store_mnemonic(common::DEFAULT_MNEMONIC.to_string())
+        .await?;
+
+    #[allow(unused_mut)]
+    let mut account_manager_builder = AccountManager::builder()
+        .with_secret_manager(SecretManager::Stronghold(secret_manager))
+        .with_client_options(ClientOptions::new().with_node(common::NODE_LOCAL)?)
+        .with_coin_type(IOTA_COIN_TYPE);
+    #[cfg(feature = "storage")]
+    {
+        account_manager_builder = account_manager_builder.with_storage_path(storage_path);
+    }
+    let account_manager = account_manager_builder.finish().await?;
+    let address = account_manager.generate_address(0, false, 0, None).await?;
+
+    // A wrong current password doesn't change anything
+    assert!(
+        account_manager
+            .change_stronghold_password("wrong_password", "new_password")
+            .await
+            .is_err()
+    );
+
+    account_manager
+        .change_stronghold_password("current_password", "new_password")
+        .await?;
+    assert_eq!(account_manager.generate_address(0, false, 0, None).await?, address);
+    // No copy of the snapshot with the old password is left
+    assert!(!PathBuf::from(format!("{storage_path}/test.stronghold.rollback")).exists());
+
+    // The snapshot can only be loaded with the new password
+    let mut secret_manager = StrongholdSecretManager::builder().build(snapshot_path)?;
+    assert!(secret_manager.set_password("current_password").await.is_err());
+    secret_manager.set_password("new_password").await?;
+
+    common::tear_down(storage_path)
+}
+
+#[cfg(feature = "stronghold")]
+#[tokio::test]
+async fn stronghold_snapshot_generations() -> Result<()> {
+    let storage_path = "test-storage/stronghold_snapshot_generations";
+    common::setup(storage_path)?;
+
+    std::fs::create_dir_all(storage_path).unwrap_or(());
+    let secret_manager = StrongholdSecretManager::builder()
+        .password("password_1")
+        .build(PathBuf::from(format!("{storage_path}/test.stronghold")))?;
+
+    #[allow(unused_mut)]
+    let mut account_manager_builder = AccountManager::builder()
+        .with_secret_manager(SecretManager::Stronghold(secret_manager))
+        .with_client_options(ClientOptions::new().with_node(common::NODE_LOCAL)?)
+        .with_coin_type(IOTA_COIN_TYPE);
+    #[cfg(feature = "storage")]
+    {
+        account_manager_builder = account_manager_builder.with_storage_path(storage_path);
+    }
+    let account_manager = account_manager_builder.finish().await?;
+    account_manager.set_stronghold_snapshot_generations(2).await?;
+
+    account_manager
+        .store_mnemonic(common::DEFAULT_MNEMONIC.to_string())
+        .await?;
+    let address = account_manager.generate_address(0, false, 0, None).await?;
+    account_manager
+        .change_stronghold_password("password_1", "password_2")
+        .await?;
+    // The copies are named by their timestamp in milliseconds
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    account_manager
+        .change_stronghold_password("password_2", "password_3")
+        .await?;
+
+    // Only the newest two copies are kept
+    let generations = account_manager.stronghold_snapshot_generations().await?;
+    assert_eq!(generations.len(), 2);
+    assert!(generations[0].timestamp >= generations[1].timestamp);
+
+    // The older copy was written with the second password
+    assert!(
+        account_manager
+            .restore_stronghold_snapshot_generation(generations[1].timestamp, "password_3")
+            .await
+            .is_err()
+    );
+    account_manager
+        .restore_stronghold_snapshot_generation(generations[1].timestamp, "password_2")
+        .await?;
+    assert_eq!(account_manager.generate_address(0, false, 0, None).await?, address);
+
+    // The amount of copies is kept when the account manager is created again
+    #[cfg(feature = "storage")]
+    {
+        drop(account_manager);
+        let secret_manager = StrongholdSecretManager::builder()
+            .password("password_2")
+            .build(PathBuf::from(format!("{storage_path}/test.stronghold")))?;
+        let account_manager = AccountManager::builder()
+            .with_secret_manager(SecretManager::Stronghold(secret_manager))
+            .with_client_options(ClientOptions::new().with_node(common::NODE_LOCAL)?)
+            .with_coin_type(IOTA_COIN_TYPE)
+            .with_storage_path(storage_path)
+            .finish()
+            .await?;
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        account_manager
+            .change_stronghold_password("password_2", "password_4")
+            .await?;
+        let new_generations = account_manager.stronghold_snapshot_generations().await?;
+        assert_eq!(new_generations.len(), 2);
+        assert!(new_generations[0].timestamp > generations[0].timestamp);
+    }
+
+    common::tear_down(storage_path)
+}
+
+#[cfg(feature = "storage")]
+#[tokio::test]
+async fn migrate_secret_manager() -> Result<()> {
+    let storage_path = "test-storage/migrate_secret_manager";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+    let account = manager.create_account().finish().await?;
+    let address = account.addresses().await?[0].clone();
+
+    // A secret manager with another seed is rejected
+    let secret_manager = MnemonicSecretManager::try_from_mnemonic(&Client::generate_mnemonic()?)?;
+    assert!(matches!(
+        manager
+            .migrate_secret_manager(SecretManager::Mnemonic(secret_manager))
+            .await,
+        Err(iota_wallet::Error::SecretManagerMigration { account_index: 0, .. })
+    ));
+
+    // The same seed is accepted
+    let secret_manager = MnemonicSecretManager::try_from_mnemonic(common::DEFAULT_MNEMONIC)?;
+    manager
+        .migrate_secret_manager(SecretManager::Mnemonic(secret_manager))
+        .await?;
+    let new_address = account.generate_addresses(1, None).await?[0].clone();
+    assert_eq!(new_address.key_index(), &1);
+    assert_ne!(new_address, address);
+
+    common::tear_down(storage_path)
+}
+
+#[cfg(all(feature = "storage", feature = "stronghold"))]
+#[tokio::test]
+async fn migrate_secret_manager_removes_encrypted_mnemonic() -> Result<()> {
+    let storage_path = "test-storage/migrate_secret_manager_removes_encrypted_mnemonic";
+    common::setup(storage_path)?;
+
+    let manager = AccountManager::builder()
+        .with_encrypted_mnemonic(common::DEFAULT_MNEMONIC.to_string(), "passphrase".to_string(), None)?
+        .with_client_options(ClientOptions::new().with_node(common::NODE_LOCAL)?)
+        .with_coin_type(IOTA_COIN_TYPE)
+        .with_storage_path(storage_path)
+        .finish()
+        .await?;
+    manager.create_account().finish().await?;
+
+    let mut secret_manager = StrongholdSecretManager::builder()
+        .password("some_hopefully_secure_password")
+        .build(PathBuf::from(format!("{storage_path}/test.stronghold")))?;
+    secret_manager
+        .store_mnemonic(common::DEFAULT_MNEMONIC.to_string())
+        .await?;
+    manager
+        .migrate_secret_manager(SecretManager::Stronghold(secret_manager))
+        .await?;
+    drop(manager);
+
+    // The stored Stronghold is loaded instead of the previous encrypted mnemonic
+    let manager = AccountManager::builder()
+        .with_mnemonic_passphrase("passphrase".to_string(), None)
+        .with_storage_path(storage_path)
+        .finish()
+        .await?;
+    assert!(matches!(
+        *manager.get_secret_manager().read().await,
+        SecretManager::Stronghold(_)
+    ));
+
+    common::tear_down(storage_path)
+}
+
+#[tokio::test]
+async fn verify_derivations() -> Result<()> {
+    let storage_path = "test-storage/verify_derivations";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+    let account = manager.create_account().finish().await?;
+    account.generate_addresses(2, None).await?;
+
+    let report = manager.verify_derivations().await?;
+    assert!(report.is_valid());
+    assert_eq!(report.verified_addresses, 3);
+    assert!(report.skipped_accounts.is_empty());
+
+    common::tear_down(storage_path)
+}
+
+#[cfg(feature = "storage")]
+#[tokio::test]
+async fn profiles() -> Result<()> {
+    let storage_path = "test-storage/profiles";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+    manager.create_account().finish().await?;
+
+    let mnemonic = Client::generate_mnemonic()?;
+    let savings = manager
+        .profile("savings")
+        .await?
+        .with_secret_manager(SecretManager::Mnemonic(MnemonicSecretManager::try_from_mnemonic(
+            &mnemonic,
+        )?))
+        .with_coin_type(IOTA_COIN_TYPE)
+        .finish()
+        .await?;
+    let savings_account = savings.create_account().finish().await?;
+    assert_eq!(*savings_account.read().await.index(), 0);
+
+    // The accounts of the profile are separate from the ones of the account manager
+    assert_eq!(manager.get_accounts().await?.len(), 1);
+    assert_eq!(savings.get_accounts().await?.len(), 1);
+    assert_ne!(
+        manager.get_accounts().await?[0].addresses().await?[0],
+        savings_account.addresses().await?[0]
+    );
+    assert_eq!(manager.get_profile_names().await?, vec!["savings".to_string()]);
+    assert!(matches!(
+        manager.profile("savings:archived").await,
+        Err(iota_wallet::Error::InvalidProfileName(_))
+    ));
+
+    // The profile can be loaded again
+    drop(savings);
+    let savings = manager
+        .profile("savings")
+        .await?
+        .with_mnemonic(mnemonic, None)?
+        .finish()
+        .await?;
+    assert_eq!(savings.get_accounts().await?.len(), 1);
+
+    common::tear_down(storage_path)
+}
+
+#[tokio::test]
+async fn for_each_account_concurrent() -> Result<()> {
+    let storage_path = "test-storage/for_each_account_concurrent";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+    for _ in 0..3 {
+        manager.create_account().finish().await?;
+    }
+
+    let indexes = manager
+        .for_each_account_concurrent(2, |account| async move { Ok(*account.read().await.index()) })
+        .await?;
+    assert_eq!(indexes, vec![0, 1, 2]);
+
+    // Errors of all accounts are returned
+    let result = manager
+        .for_each_account_concurrent(2, |account| async move {
+            let index = *account.read().await.index();
+            if index == 0 {
+                Ok(())
+            } else {
+                Err(iota_wallet::Error::AccountNotFound(index.to_string()))
+            }
+        })
+        .await;
+    match result {
+        Err(iota_wallet::Error::AccountOperationsFailed(errors)) => {
+            assert_eq!(errors.iter().map(|(index, _)| *index).collect::<Vec<u32>>(), vec![1, 2]);
+        }
+        _ => panic!("expected failed account operations"),
+    }
+
+    common::tear_down(storage_path)
+}
+
+#[tokio::test]
+async fn background_syncing_schedule() -> Result<()> {
+    let storage_path = "test-storage/background_syncing_schedule";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+    manager.create_account().finish().await?;
+
+    manager
+        .set_background_syncing_interval(0, Some(std::time::Duration::from_secs(60)))
+        .await?;
+    manager.set_background_syncing_interval(0, None).await?;
+    assert!(matches!(
+        manager.set_background_syncing_interval(1, None).await,
+        Err(iota_wallet::Error::AccountNotFound(_))
+    ));
+
+    manager.pause_background_syncing(Some(0)).await?;
+    assert!(matches!(
+        manager.pause_background_syncing(Some(1)).await,
+        Err(iota_wallet::Error::AccountNotFound(_))
+    ));
+    manager.pause_background_syncing(None).await?;
+    manager.resume_background_syncing(None).await?;
+
+    common::tear_down(storage_path)
+}
+
+#[cfg(feature = "storage")]
+#[tokio::test]
+async fn scheduled_payments() -> Result<()> {
+    let storage_path = "test-storage/scheduled_payments";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+    let account = manager.create_account().finish().await?;
+    let address = account.addresses().await?[0].address().to_bech32();
+
+    // Starts in the future, so the background syncing wouldn't execute it
+    let options = SchedulePaymentOptions {
+        address,
+        amount: 1_000_000,
+        start_time: Some(u64::MAX / 2),
+        interval_in_milliseconds: Some(60_000),
+        expires_at: None,
+    };
+    let first = manager.schedule_payment(0, options.clone()).await?;
+    let second = manager.schedule_payment(0, options.clone()).await?;
+    assert_ne!(first.id, second.id);
+    assert_eq!(
+        manager.scheduled_payments(0).await?,
+        vec![first.clone(), second.clone()]
+    );
+
+    manager.cancel_scheduled_payment(0, first.id).await?;
+    assert_eq!(manager.scheduled_payments(0).await?, vec![second]);
+    assert!(matches!(
+        manager.cancel_scheduled_payment(0, first.id).await,
+        Err(iota_wallet::Error::ScheduledPaymentNotFound(_))
+    ));
+    assert!(matches!(
+        manager.schedule_payment(1, options).await,
+        Err(iota_wallet::Error::AccountNotFound(_))
+    ));
+
+    common::tear_down(storage_path)
+}
+
+#[cfg(all(feature = "events", feature = "storage"))]
+#[tokio::test]
+async fn event_log() -> Result<()> {
+    let storage_path = "test-storage/event_log";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+    assert!(manager.events_since(0).await?.is_empty());
+
+    manager.emit_test_event(WalletEvent::ConsolidationRequired).await?;
+    manager.emit_test_event(WalletEvent::ConsolidationRequired).await?;
+    let events = manager.events_since(0).await?;
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].sequence, 1);
+    assert_eq!(events[1].sequence, 2);
+    assert_eq!(events[0].event.event, WalletEvent::ConsolidationRequired);
+
+    let events = manager.events_since(1).await?;
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].sequence, 2);
+    assert!(manager.events_since(2).await?.is_empty());
+
+    // The log is kept after a restart
+    drop(manager);
+    let manager = common::make_manager(storage_path, None, None).await?;
+    assert_eq!(manager.events_since(0).await?.len(), 2);
+
+    common::tear_down(storage_path)
+}
+
+#[cfg(feature = "webhooks")]
+#[tokio::test]
+async fn webhook_dispatcher() -> Result<()> {
+    let storage_path = "test-storage/webhook_dispatcher";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+    let webhook = Webhook {
+        url: "http://localhost:8080/events".to_string(),
+        secret: "secret".to_string(),
+        filter: EventFilter::default(),
+    };
+    assert!(matches!(
+        manager.start_webhook_dispatcher(vec![webhook.clone()]).await,
+        Err(iota_wallet::Error::InvalidWebhook(_))
+    ));
+    let webhook = Webhook {
+        url: "https://localhost:8080/events".to_string(),
+        ..webhook
+    };
+    assert!(matches!(
+        manager
+            .start_webhook_dispatcher(vec![Webhook {
+                secret: String::new(),
+                ..webhook.clone()
+            }])
+            .await,
+        Err(iota_wallet::Error::InvalidWebhook(_))
+    ));
+
+    manager.start_webhook_dispatcher(vec![webhook]).await?;
+    manager.stop_webhook_dispatcher().await?;
+    assert!(manager.webhook_dead_letters().await?.is_empty());
+    manager.clear_webhook_dead_letters().await?;
+
+    common::tear_down(storage_path)
+}
+
+#[cfg(all(feature = "event_bridge", unix))]
+#[tokio::test]
+async fn event_bridge() -> Result<()> {
+    use iota_wallet::events::{
+        types::{Event, EventFilter, WalletEvent},
+        StreamEvent,
+    };
+    use tokio::io::{AsyncBufReadExt, BufReader};
+
+    let storage_path = "test-storage/event_bridge";
+    common::setup(storage_path)?;
+    let socket_path = format!("{storage_path}/events.sock");
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+    manager
+        .start_event_bridge(socket_path.clone(), EventFilter::default())
+        .await?;
+
+    let stream = tokio::net::UnixStream::connect(&socket_path).await?;
+    let mut lines = BufReader::new(stream).lines();
+    // The client is subscribed once the bridge accepted the connection, emit until the first event arrives
+    let line = loop {
+        manager.emit_test_event(WalletEvent::ConsolidationRequired).await?;
+        if let Ok(line) = tokio::time::timeout(std::time::Duration::from_millis(100), lines.next_line()).await {
+            break line?.expect("bridge closed the connection");
+        }
+    };
+    assert_eq!(
+        serde_json::from_str::<StreamEvent>(&line)?,
+        StreamEvent::Event(Event {
+            account_index: 0,
+            event: WalletEvent::ConsolidationRequired,
+        })
+    );
+
+    // Stopping the bridge disconnects the client and removes the socket
+    manager.stop_event_bridge().await?;
+    while lines.next_line().await?.is_some() {}
+    assert!(!std::path::Path::new(&socket_path).exists());
+
+    common::tear_down(storage_path)
+}
@@ -5,10 +5,15 @@
 
 mod common;
 
-use std::time::Duration;
+#[cfg(feature = "events")]
+use std::sync::{Arc, Mutex};
+use std::{collections::HashMap, time::Duration};
 
 use iota_client::{constants::SHIMMER_COIN_TYPE, Client};
+#[cfg(feature = "events")]
+use iota_wallet::events::types::{WalletEvent, WalletEventType};
 use iota_wallet::{
+    account_manager::RecoverAccountsOptions,
     secret::{mnemonic::MnemonicSecretManager, SecretManager},
     Result,
 };
@@ -92,3 +97,88 @@ async fn account_recovery_with_balance_and_empty_addresses() -> Result<()> {
     assert_eq!(3, account_with_balance.public_addresses().len());
     common::tear_down(storage_path)
 }
+
+#[ignore]
+#[tokio::test]
+async fn account_recovery_account_address_gap_limit() -> Result<()> {
+    let storage_path = "test-storage/account_recovery_account_address_gap_limit";
+    common::setup(storage_path)?;
+
+    let mnemonic = Client::generate_mnemonic()?;
+    let client = Client::builder().with_node(common::NODE_LOCAL)?.finish()?;
+
+    let secret_manager = SecretManager::Mnemonic(MnemonicSecretManager::try_from_mnemonic(&mnemonic)?);
+
+    let address = client
+        .get_addresses(&secret_manager)
+        .with_coin_type(SHIMMER_COIN_TYPE)
+        .with_bech32_hrp(client.get_bech32_hrp().await?)
+        .with_account_index(0)
+        .with_range(5..6)
+        .finish()
+        .await?;
+
+    // Add funds to the address with key_index 5, which isn't found with an address gap limit of 2
+    iota_client::request_funds_from_faucet(common::FAUCET_URL, &address[0]).await?;
+
+    // Wait for faucet transaction
+    tokio::time::sleep(Duration::new(10, 0)).await;
+
+    let manager = common::make_manager(storage_path, Some(&mnemonic), None).await?;
+
+    let (accounts, report) = manager
+        .recover_accounts_with_options(RecoverAccountsOptions {
+            account_start_index: 0,
+            account_gap_limit: 2,
+            address_gap_limit: 2,
+            account_address_gap_limits: HashMap::from([(0, 10)]),
+            sync_options: None,
+        })
+        .await?;
+
+    assert_eq!(1, accounts.len());
+    assert_eq!(report.highest_account_index_with_outputs, Some(0));
+    assert_eq!(report.highest_searched_account_index, Some(2));
+    let address_gap_report = report
+        .address_gaps
+        .iter()
+        .find(|address_gap_report| address_gap_report.account_index == 0)
+        .unwrap();
+    assert_eq!(address_gap_report.address_gap_limit, 10);
+    assert_eq!(address_gap_report.highest_address_index_with_outputs, Some(5));
+    common::tear_down(storage_path)
+}
+
+#[ignore]
+#[cfg(feature = "events")]
+#[tokio::test]
+async fn account_recovery_progress_events() -> Result<()> {
+    let storage_path = "test-storage/account_recovery_progress_events";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+    manager.create_account().finish().await?;
+    manager.create_account().finish().await?;
+
+    let searched_account_indexes = Arc::new(Mutex::new(Vec::new()));
+    let searched_account_indexes_ = searched_account_indexes.clone();
+    manager
+        .listen(vec![WalletEventType::AccountRecoveryProgress], move |event| {
+            if let WalletEvent::AccountRecoveryProgress(address_gap_report) = &event.event {
+                searched_account_indexes_
+                    .lock()
+                    .unwrap()
+                    .push(address_gap_report.account_index);
+            }
+        })
+        .await;
+
+    let accounts = manager.recover_accounts(0, 1, 2, None).await?;
+    assert_eq!(2, accounts.len());
+
+    // An event for each existing account and the new account without outputs
+    let mut searched_account_indexes = searched_account_indexes.lock().unwrap().clone();
+    searched_account_indexes.sort_unstable();
+    assert_eq!(searched_account_indexes, vec![0, 1, 2]);
+    common::tear_down(storage_path)
+}
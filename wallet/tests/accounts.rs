@@ -3,6 +3,9 @@
 
 mod common;
 
+use std::collections::HashMap;
+
+use iota_client::constants::IOTA_COIN_TYPE;
 use iota_wallet::Result;
 #[cfg(feature = "stronghold")]
 use {
@@ -168,6 +171,71 @@ async fn account_rename_alias() -> Result<()> {
     common::tear_down(storage_path)
 }
 
+#[cfg(feature = "storage")]
+#[tokio::test]
+async fn account_alias_unique() -> Result<()> {
+    let storage_path = "test-storage/account_alias_unique";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+    let alice = manager
+        .create_account()
+        .with_alias("Alice".to_string())
+        .finish()
+        .await?;
+    let bob = manager.create_account().with_alias("Bob".to_string()).finish().await?;
+
+    // renaming to an alias of another account fails and keeps the old alias
+    assert!(bob.set_alias("alice").await.is_err());
+    assert_eq!(bob.alias().await, "Bob".to_string());
+
+    bob.set_alias("Carol").await?;
+    assert_eq!(*manager.get_account_by_alias("carol").await?.read().await.index(), 1);
+    assert!(manager.get_account_by_alias("Bob").await.is_err());
+    // the old alias is free again
+    alice.set_alias("Bob").await?;
+    drop(alice);
+    drop(bob);
+    drop(manager);
+
+    // the alias index is persisted
+    let manager = common::make_manager(storage_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+    assert_eq!(*manager.get_account_by_alias("bob").await?.read().await.index(), 0);
+    assert!(manager.get_account("Carol").await?.set_alias("BOB").await.is_err());
+
+    common::tear_down(storage_path)
+}
+
+#[cfg(feature = "storage")]
+#[tokio::test]
+async fn account_metadata() -> Result<()> {
+    let storage_path = "test-storage/account_metadata";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+    let account = manager
+        .create_account()
+        .with_metadata(HashMap::from([("color".to_string(), "blue".to_string())]))
+        .finish()
+        .await?;
+    assert_eq!(account.read().await.metadata()["color"], "blue");
+
+    account
+        .set_metadata(HashMap::from([("customerId".to_string(), "42".to_string())]))
+        .await?;
+    drop(account);
+    drop(manager);
+
+    let manager = common::make_manager(storage_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+    let account = manager.get_account(0).await?;
+    assert_eq!(
+        account.read().await.metadata(),
+        &HashMap::from([("customerId".to_string(), "42".to_string())])
+    );
+
+    common::tear_down(storage_path)
+}
+
 #[tokio::test]
 async fn account_first_address_exists() -> Result<()> {
     let storage_path = "test-storage/account_first_address_exists";
@@ -222,3 +290,122 @@ async fn account_creation_stronghold() -> Result<()> {
 
     common::tear_down(storage_path)
 }
+
+#[tokio::test]
+async fn watch_account() -> Result<()> {
+    let storage_path = "test-storage/watch_account";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+    let address = "rms1qpszqzadsym6wpppd6z037dvlejmjuke7s24hm95s9fg9vpua7vluaw60xu";
+    let watch_account = manager
+        .create_watch_account(vec![address.to_string()], Some("watch".to_string()))
+        .await?;
+    assert!(watch_account.read().await.watch_only());
+    assert_eq!(*watch_account.read().await.index(), 1 << 31);
+    assert_eq!(watch_account.addresses().await?[0].address().to_bech32(), address);
+
+    // Watch-only accounts can't generate addresses
+    assert!(matches!(
+        watch_account.generate_addresses(1, None).await,
+        Err(iota_wallet::Error::WatchOnlyAccount)
+    ));
+
+    // Accounts with addresses of the seed can still be created and start at the first account index
+    let account = manager.create_account().finish().await?;
+    assert!(!account.read().await.watch_only());
+    assert_eq!(*account.read().await.index(), 0);
+    let second_watch_account = manager.create_watch_account(vec![address.to_string()], None).await?;
+    assert_eq!(*second_watch_account.read().await.index(), (1 << 31) + 1);
+
+    common::tear_down(storage_path)
+}
+
+#[tokio::test]
+async fn account_coin_types() -> Result<()> {
+    let storage_path = "test-storage/account_coin_types";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+    let shimmer_account = manager.create_account().finish().await?;
+    let iota_account = manager
+        .create_account()
+        .with_coin_type(IOTA_COIN_TYPE)
+        .with_bech32_hrp("iota".to_string())
+        .finish()
+        .await?;
+
+    assert_eq!(*iota_account.read().await.coin_type(), IOTA_COIN_TYPE);
+    assert!(iota_account.addresses().await?[0].address().to_bech32().starts_with("iota1"));
+    assert_ne!(
+        *shimmer_account.read().await.coin_type(),
+        *iota_account.read().await.coin_type()
+    );
+    // Accounts with the coin type of the account manager can still be created
+    manager.create_account().finish().await?;
+
+    common::tear_down(storage_path)
+}
+
+#[tokio::test]
+async fn account_statistics() -> Result<()> {
+    let storage_path = "test-storage/account_statistics";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+    let account = manager.create_account().finish().await?;
+
+    // No transactions yet
+    let statistics = account.statistics(0..u64::MAX).await?;
+    assert_eq!(statistics.sent, 0);
+    assert_eq!(statistics.received, 0);
+    assert!(statistics.transaction_kinds.is_empty());
+    assert!(statistics.top_counterparts.is_empty());
+
+    common::tear_down(storage_path)
+}
+
+#[cfg(feature = "storage")]
+#[tokio::test]
+async fn account_address_buckets() -> Result<()> {
+    let storage_path = "test-storage/account_address_buckets";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+    let account = manager.create_account().finish().await?;
+    let addresses = account.generate_addresses(2, None).await?;
+    let other_account = manager.create_account().finish().await?;
+
+    account
+        .add_addresses_to_bucket("deposits", &[addresses[0].address().clone()])
+        .await?;
+    account
+        .add_addresses_to_bucket("cold", &[addresses[1].address().clone()])
+        .await?;
+    // Moving the address removes the empty bucket
+    account
+        .add_addresses_to_bucket("deposits", &[addresses[1].address().clone()])
+        .await?;
+    // Addresses of other accounts can't be added
+    assert!(
+        account
+            .add_addresses_to_bucket("deposits", &[other_account.addresses().await?[0].address().clone()])
+            .await
+            .is_err()
+    );
+    drop(account);
+    drop(other_account);
+    drop(manager);
+
+    let manager = common::make_manager(storage_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+    let account = manager.get_account(0).await?;
+    let address_buckets = account.read().await.address_buckets().clone();
+    assert_eq!(address_buckets.len(), 1);
+    assert_eq!(address_buckets["deposits"].len(), 2);
+
+    account.remove_address_bucket("deposits").await?;
+    assert!(account.remove_address_bucket("deposits").await.is_err());
+    assert!(account.address_bucket_balance("deposits").await.is_err());
+
+    common::tear_down(storage_path)
+}
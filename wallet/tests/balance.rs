@@ -3,12 +3,22 @@
 
 mod common;
 
+#[cfg(feature = "storage")]
+use std::collections::HashMap;
+
 use iota_client::block::output::{
     feature::SenderFeature,
-    unlock_condition::{AddressUnlockCondition, ExpirationUnlockCondition},
-    BasicOutputBuilder, Feature, UnlockCondition,
+    unlock_condition::{AddressUnlockCondition, ExpirationUnlockCondition, StorageDepositReturnUnlockCondition},
+    BasicOutputBuilder, Feature, NftId, NftOutputBuilder, UnlockCondition,
+};
+#[cfg(feature = "participation")]
+use iota_client::{api_types::plugins::participation::types::ParticipationEventType, node_manager::node::Node, Url};
+#[cfg(feature = "participation")]
+use iota_wallet::account::{types::participation::ParticipationEventRegistrationOptions, SyncOptions};
+use iota_wallet::{
+    account::{SpamFilter, SpamReason},
+    AddressWithAmount, Result,
 };
-use iota_wallet::Result;
 
 #[ignore]
 #[tokio::test]
@@ -137,3 +147,214 @@ async fn balance_voting_power() -> Result<()> {
 
     common::tear_down(storage_path)
 }
+
+#[ignore]
+#[tokio::test]
+#[cfg(feature = "participation")]
+async fn participation_state() -> Result<()> {
+    let storage_path = "test-storage/participation_state";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+
+    let account = &common::create_accounts_with_funds(&manager, 1).await?[0];
+    assert!(account.get_participation_state().await?.is_none());
+
+    let voting_power = 1_000_000;
+    let tx = account.increase_voting_power(voting_power).await?;
+    account
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+    account
+        .sync(Some(SyncOptions {
+            sync_participation: true,
+            ..Default::default()
+        }))
+        .await?;
+
+    // The state is stored with the sync
+    let state = account.get_participation_state().await?.unwrap();
+    assert_eq!(state.voting_power, voting_power);
+    assert!(state.events.is_empty());
+    assert_eq!(account.update_participation_state().await?.voting_power, voting_power);
+
+    common::tear_down(storage_path)
+}
+
+#[ignore]
+#[tokio::test]
+#[cfg(feature = "participation")]
+async fn staking_rewards() -> Result<()> {
+    let storage_path = "test-storage/staking_rewards";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+
+    let account = &common::create_accounts_with_funds(&manager, 1).await?[0];
+
+    // Requires a node with a staking event
+    let events = account
+        .register_participation_events(&ParticipationEventRegistrationOptions {
+            node: Node {
+                url: Url::parse(common::NODE_LOCAL).map_err(iota_client::Error::Url)?,
+                auth: None,
+                disabled: false,
+            },
+            events_to_register: None,
+            events_to_ignore: None,
+            event_type: Some(ParticipationEventType::Staking),
+        })
+        .await?;
+    let event_id = *events.keys().next().unwrap();
+
+    let staked_amount = 1_000_000;
+    let tx = account.increase_voting_power(staked_amount).await?;
+    account
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+    account.sync(None).await?;
+    let tx = account.stake(event_id).await?;
+    account
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+
+    let balance = account
+        .sync(Some(SyncOptions {
+            sync_participation: true,
+            ..Default::default()
+        }))
+        .await?;
+    let staking_rewards = account.staking_rewards().await?;
+    assert_eq!(staking_rewards.len(), 1);
+    assert_eq!(staking_rewards[0].event_id, event_id);
+    assert_eq!(staking_rewards[0].staked, staked_amount);
+    assert_eq!(balance.staking_rewards[0].staked, staked_amount);
+
+    common::tear_down(storage_path)
+}
+
+#[ignore]
+#[cfg(feature = "storage")]
+#[tokio::test]
+async fn balance_total() -> Result<()> {
+    let storage_path = "test-storage/balance_total";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+    let account_0 = &common::create_accounts_with_funds(&manager, 1).await?[0];
+    let account_1 = manager.create_account().finish().await?;
+    account_0
+        .set_metadata(HashMap::from([("group".to_string(), "savings".to_string())]))
+        .await?;
+    account_1.sync(None).await?;
+
+    let balance_0 = account_0.sync(None).await?;
+    let total_balance = manager.total_balance().await?;
+    assert_eq!(total_balance.base_coin.total, balance_0.base_coin.total);
+    assert_eq!(total_balance.accounts, 2);
+
+    let total_balances = manager.total_balance_by_metadata("group").await?;
+    assert_eq!(total_balances.len(), 1);
+    assert_eq!(total_balances["savings"].base_coin.total, balance_0.base_coin.total);
+    assert_eq!(total_balances["savings"].accounts, 1);
+
+    common::tear_down(storage_path)
+}
+
+#[ignore]
+#[tokio::test]
+async fn balance_spam_filter() -> Result<()> {
+    let storage_path = "test-storage/balance_spam_filter";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+
+    let accounts = common::create_accounts_with_funds(&manager, 2).await?;
+    accounts[0]
+        .set_spam_filter(SpamFilter {
+            flag_unsolicited: true,
+            ..Default::default()
+        })
+        .await?;
+
+    // Send an nft that needs to be claimed to account 0
+    let token_supply = accounts[1].client().get_token_supply().await?;
+    let sender_address = *accounts[1].addresses().await?[0].address().as_ref();
+    let outputs = vec![
+        NftOutputBuilder::new_with_amount(1_000_000, NftId::null())?
+            .with_unlock_conditions(vec![
+                UnlockCondition::Address(AddressUnlockCondition::new(
+                    *accounts[0].addresses().await?[0].address().as_ref(),
+                )),
+                UnlockCondition::StorageDepositReturn(StorageDepositReturnUnlockCondition::new(
+                    sender_address,
+                    1_000_000,
+                    token_supply,
+                )?),
+                UnlockCondition::Expiration(ExpirationUnlockCondition::new(
+                    sender_address,
+                    accounts[1].client().get_time_checked().await? + 5000,
+                )?),
+            ])
+            .finish_output(token_supply)?,
+    ];
+    let tx = accounts[1].send(outputs, None).await?;
+    accounts[1]
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+
+    let balance = accounts[0].sync(None).await?;
+    assert!(balance.potentially_locked_outputs.is_empty());
+    assert_eq!(balance.quarantined.len(), 1);
+    assert_eq!(balance.quarantined[0].reason, SpamReason::Unsolicited);
+    assert_eq!(accounts[0].quarantined_outputs().await, balance.quarantined);
+
+    // Allowed nfts are part of the balance again
+    accounts[0].allow_nft(balance.quarantined[0].nft_id.unwrap()).await?;
+    let balance = accounts[0].balance().await?;
+    assert!(balance.quarantined.is_empty());
+    assert_eq!(balance.potentially_locked_outputs.len(), 1);
+
+    common::tear_down(storage_path)
+}
+
+#[ignore]
+#[tokio::test]
+async fn balance_cached() -> Result<()> {
+    let storage_path = "test-storage/balance_cached";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+    let account = &common::create_accounts_with_funds(&manager, 1).await?[0];
+
+    let balance = account.sync(None).await?;
+    assert_eq!(account.read().await.cached_balance().as_ref(), Some(&balance));
+
+    // Locked inputs aren't available anymore
+    let prepared_transaction = account
+        .prepare_send_amount(
+            vec![AddressWithAmount {
+                address: account.addresses().await?[0].address().to_bech32(),
+                amount: 1_000_000,
+            }],
+            None,
+        )
+        .await?;
+    let locked_amount: u64 = prepared_transaction
+        .inputs_data
+        .iter()
+        .map(|input| input.output.amount())
+        .sum();
+    let cached_balance = account.read().await.cached_balance().clone().unwrap();
+    assert_eq!(cached_balance.base_coin.total, balance.base_coin.total);
+    assert_eq!(
+        cached_balance.base_coin.available,
+        balance.base_coin.available - locked_amount
+    );
+
+    // And available again once the transaction is discarded
+    account.discard_prepared_transaction(prepared_transaction).await?;
+    assert_eq!(account.read().await.cached_balance().as_ref(), Some(&balance));
+
+    common::tear_down(storage_path)
+}
@@ -7,7 +7,10 @@ use iota_client::block::output::{
     unlock_condition::{AddressUnlockCondition, ExpirationUnlockCondition},
     NftId, NftOutputBuilder, OutputId, UnlockCondition,
 };
-use iota_wallet::{account::AccountHandle, NativeTokenOptions, NftOptions, Result, U256};
+use iota_wallet::{
+    account::{types::AliasStatus, AccountHandle},
+    NativeTokenOptions, NftOptions, Result, U256,
+};
 
 #[ignore]
 #[tokio::test]
@@ -277,3 +280,58 @@ async fn mint_and_burn_native_tokens() -> Result<()> {
 
     common::tear_down(storage_path)
 }
+
+#[ignore]
+#[tokio::test]
+async fn alias_lifecycle() -> Result<()> {
+    let storage_path = "test-storage/alias_lifecycle";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+    let account = &common::create_accounts_with_funds(&manager, 1).await?[0];
+
+    let tx = account.create_alias_output(None, None).await?;
+    account
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+    account.sync(None).await?;
+
+    let aliases = account.aliases().await;
+    assert_eq!(aliases.len(), 1);
+    let alias_id = aliases[0].alias_id;
+    assert_eq!(aliases[0].status, AliasStatus::Controlled);
+    assert_eq!(aliases[0].state_index, 0);
+
+    let tx = account
+        .update_alias_state_metadata(alias_id, b"new state".to_vec(), None)
+        .await?;
+    account
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+    account.sync(None).await?;
+    let alias = &account.aliases().await[0];
+    assert_eq!(alias.state_index, 1);
+    assert_eq!(alias.state_metadata, b"new state".to_vec());
+
+    // Rotate the state controller to another address of the account, the governor stays the same
+    let address = account.generate_addresses(1, None).await?[0].address().to_bech32();
+    let tx = account
+        .set_alias_state_controller(alias_id, address.clone(), None)
+        .await?;
+    account
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+    account.sync(None).await?;
+    let alias = &account.aliases().await[0];
+    assert_eq!(alias.state_controller.to_bech32(), address);
+    assert_eq!(alias.state_index, 1);
+
+    let tx = account.destroy_alias(alias_id, None).await?;
+    account
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+    account.sync(None).await?;
+    assert_eq!(account.aliases().await[0].status, AliasStatus::Destroyed);
+
+    common::tear_down(storage_path)
+}
@@ -3,12 +3,15 @@
 
 mod common;
 
+use std::time::Duration;
+
 use iota_client::block::output::{
-    unlock_condition::{AddressUnlockCondition, ExpirationUnlockCondition},
+    unlock_condition::{AddressUnlockCondition, ExpirationUnlockCondition, StorageDepositReturnUnlockCondition},
     BasicOutputBuilder, NativeToken, NftId, NftOutputBuilder, UnlockCondition,
 };
 use iota_wallet::{
-    account::OutputsToClaim, AddressNativeTokens, AddressWithMicroAmount, NativeTokenOptions, Result, U256,
+    account::{ClaimableOutputsFilter, DeclinedAssets, OutputsToClaim},
+    AddressNativeTokens, AddressWithMicroAmount, Error, NativeTokenOptions, Result, U256,
 };
 
 #[ignore]
@@ -425,6 +428,72 @@ async fn claim_2_nft_outputs() -> Result<()> {
     common::tear_down(storage_path)
 }
 
+#[ignore]
+#[tokio::test]
+async fn decline_nft_output() -> Result<()> {
+    let storage_path = "test-storage/decline_nft_output";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+
+    let accounts = common::create_accounts_with_funds(&manager, 2).await?;
+
+    let token_supply = accounts[1].client().get_token_supply().await?;
+    let sender_address = *accounts[1].addresses().await?[0].address().as_ref();
+    let outputs = vec![
+        NftOutputBuilder::new_with_amount(1_000_000, NftId::null())?
+            .with_unlock_conditions(vec![
+                UnlockCondition::Address(AddressUnlockCondition::new(
+                    *accounts[0].addresses().await?[0].address().as_ref(),
+                )),
+                UnlockCondition::StorageDepositReturn(StorageDepositReturnUnlockCondition::new(
+                    sender_address,
+                    1_000_000,
+                    token_supply,
+                )?),
+                UnlockCondition::Expiration(ExpirationUnlockCondition::new(
+                    sender_address,
+                    accounts[1].client().get_time_checked().await? + 5000,
+                )?),
+            ])
+            .finish_output(token_supply)?,
+    ];
+
+    let tx = accounts[1].send(outputs, None).await?;
+    accounts[1]
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+    let sender_balance = accounts[1].sync(None).await?;
+
+    let balance = accounts[0].sync(None).await?;
+    let output_id = *balance.potentially_locked_outputs.keys().next().unwrap();
+
+    let tx = accounts[0]
+        .decline_output(output_id, DeclinedAssets::Burn, None)
+        .await?;
+    accounts[0]
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+
+    let balance = accounts[0].sync(None).await?;
+    assert!(balance.potentially_locked_outputs.is_empty());
+    assert!(balance.nfts.is_empty());
+    // The whole amount went back to the sender
+    let new_sender_balance = accounts[1].sync(None).await?;
+    assert_eq!(
+        new_sender_balance.base_coin.total,
+        sender_balance.base_coin.total + 1_000_000
+    );
+
+    // Declined outputs are gone
+    assert!(matches!(
+        accounts[0].decline_output(output_id, DeclinedAssets::Burn, None).await,
+        Err(Error::OutputNotDeclinable(..))
+    ));
+
+    common::tear_down(storage_path)
+}
+
 #[ignore]
 #[tokio::test]
 async fn claim_2_nft_outputs_no_outputs_in_claim_account() -> Result<()> {
@@ -489,3 +558,128 @@ async fn claim_2_nft_outputs_no_outputs_in_claim_account() -> Result<()> {
 
     common::tear_down(storage_path)
 }
+
+#[ignore]
+#[tokio::test]
+async fn claimable_outputs_in_batches() -> Result<()> {
+    let storage_path = "test-storage/claimable_outputs_in_batches";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+
+    let accounts = common::create_accounts_with_funds(&manager, 2).await?;
+
+    let micro_amount = 1;
+    let tx = accounts[1]
+        .send_micro_transaction(
+            vec![
+                AddressWithMicroAmount {
+                    address: accounts[0].addresses().await?[0].address().to_bech32(),
+                    amount: micro_amount,
+                    return_address: None,
+                    expiration: None,
+                };
+                2
+            ],
+            None,
+        )
+        .await?;
+
+    accounts[1]
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+
+    accounts[0].sync(None).await?;
+    let claimable_outputs = accounts[0]
+        .claimable_outputs(Some(ClaimableOutputsFilter {
+            outputs_to_claim: OutputsToClaim::MicroTransactions,
+            include_timelocked: true,
+        }))
+        .await?;
+    assert_eq!(claimable_outputs.len(), 2);
+    for claimable_output in &claimable_outputs {
+        assert_eq!(claimable_output.claimable_at, None);
+        assert!(claimable_output.expires_at.is_some());
+        assert_eq!(
+            claimable_output.amount - claimable_output.storage_deposit_return,
+            micro_amount
+        );
+    }
+
+    let claim_batch = accounts[0]
+        .claim_outputs_in_batches(
+            claimable_outputs
+                .iter()
+                .map(|claimable_output| claimable_output.output_id)
+                .collect(),
+        )
+        .await?;
+    assert_eq!(claim_batch.transactions.len(), 1);
+    assert!(claim_batch.failed.is_empty());
+    accounts[0]
+        .retry_transaction_until_included(&claim_batch.transactions[0].transaction_id, None, None)
+        .await?;
+
+    accounts[0].sync(None).await?;
+    assert!(accounts[0].claimable_outputs(None).await?.is_empty());
+
+    common::tear_down(storage_path)
+}
+
+#[ignore]
+#[tokio::test]
+async fn expiring_outputs() -> Result<()> {
+    let storage_path = "test-storage/expiring_outputs";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+
+    let accounts = common::create_accounts_with_funds(&manager, 2).await?;
+
+    let tx = accounts[1]
+        .send_micro_transaction(
+            vec![
+                AddressWithMicroAmount {
+                    address: accounts[0].addresses().await?[0].address().to_bech32(),
+                    amount: 1,
+                    return_address: None,
+                    // One hour
+                    expiration: Some(60 * 60),
+                },
+                AddressWithMicroAmount {
+                    address: accounts[0].addresses().await?[0].address().to_bech32(),
+                    amount: 1,
+                    return_address: None,
+                    // Default is one day
+                    expiration: None,
+                },
+            ],
+            None,
+        )
+        .await?;
+
+    accounts[1]
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+
+    accounts[0].sync(None).await?;
+    let expiring_outputs = accounts[0].expiring_outputs(None).await?;
+    assert_eq!(expiring_outputs.len(), 2);
+    assert!(expiring_outputs[0].expires_at <= expiring_outputs[1].expires_at);
+    assert!(expiring_outputs[0].time_to_expiry <= 60 * 60);
+    assert_eq!(
+        expiring_outputs[0].return_address,
+        accounts[1].addresses().await?[0].address().to_bech32()
+    );
+
+    let expiring_within_two_hours = accounts[0]
+        .expiring_outputs(Some(Duration::from_secs(2 * 60 * 60)))
+        .await?;
+    assert_eq!(expiring_within_two_hours, expiring_outputs[..1]);
+
+    // The sender doesn't control the outputs before they expired
+    accounts[1].sync(None).await?;
+    assert!(accounts[1].expiring_outputs(None).await?.is_empty());
+
+    common::tear_down(storage_path)
+}
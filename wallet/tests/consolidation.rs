@@ -3,7 +3,7 @@
 
 mod common;
 
-use iota_wallet::{AddressWithAmount, Result};
+use iota_wallet::{account::ConsolidationPolicy, AddressWithAmount, Result};
 
 #[ignore]
 #[tokio::test]
@@ -52,3 +52,29 @@ async fn consolidation() -> Result<()> {
 
     common::tear_down(storage_path)
 }
+
+#[tokio::test]
+async fn consolidation_policy() -> Result<()> {
+    let storage_path = "test-storage/consolidation_policy";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+    manager.create_account().finish().await?;
+
+    let policy = ConsolidationPolicy {
+        output_threshold: 50,
+        max_inputs: Some(20),
+        interval_in_milliseconds: 60_000,
+    };
+    manager.set_consolidation_policy(0, Some(policy)).await?;
+    assert_eq!(manager.get_consolidation_policy(0).await, Some(policy));
+    assert!(matches!(
+        manager.set_consolidation_policy(1, Some(policy)).await,
+        Err(iota_wallet::Error::AccountNotFound(_))
+    ));
+
+    manager.set_consolidation_policy(0, None).await?;
+    assert_eq!(manager.get_consolidation_policy(0).await, None);
+
+    common::tear_down(storage_path)
+}
@@ -110,6 +110,8 @@ async fn message_interface_create_account() -> Result<()> {
         .send_message(Message::CreateAccount {
             alias: None,
             bech32_hrp: None,
+            coin_type: None,
+            client_options: None,
         })
         .await;
 
@@ -166,6 +168,8 @@ async fn message_interface_events() -> Result<()> {
         .send_message(Message::CreateAccount {
             alias: Some("alias".to_string()),
             bech32_hrp: None,
+            coin_type: None,
+            client_options: None,
         })
         .await;
 
@@ -324,6 +328,8 @@ async fn message_interface_stronghold() -> Result<()> {
         .send_message(Message::CreateAccount {
             alias: None,
             bech32_hrp: None,
+            coin_type: None,
+            client_options: None,
         })
         .await;
 
@@ -124,3 +124,77 @@ async fn native_token_foundry_metadata() -> Result<()> {
 
     common::tear_down(storage_path)
 }
+
+#[ignore]
+#[tokio::test]
+async fn native_token_lifecycle_estimates() -> Result<()> {
+    let storage_path = "test-storage/native_token_lifecycle_estimates";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+
+    let account = &common::create_accounts_with_funds(&manager, 1).await?[0];
+
+    let tx = account.create_alias_output(None, None).await?;
+    account
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+    account.sync(None).await?;
+
+    let native_token_options = NativeTokenOptions {
+        alias_id: None,
+        circulating_supply: U256::from(50),
+        maximum_supply: U256::from(100),
+        foundry_metadata: None,
+    };
+    // The estimate only prepares the transaction, the inputs are available again afterwards
+    let estimate = account
+        .estimate_mint_native_token(native_token_options.clone(), None)
+        .await?;
+    assert!(estimate.additional_storage_deposit > 0);
+    assert!(estimate.inputs_amount >= estimate.required_storage_deposit);
+    assert!(account.read().await.locked_outputs().is_empty());
+
+    let prepared = account.prepare_mint_native_token(native_token_options, None).await?;
+    let mint_tx = account.sign_and_submit_transaction(prepared.transaction).await?;
+    account
+        .retry_transaction_until_included(&mint_tx.transaction_id, None, None)
+        .await?;
+    account.sync(None).await?;
+
+    // Melting and burning don't lock additional storage deposit
+    let estimate = account
+        .estimate_decrease_native_token_supply(prepared.token_id, U256::from(10), None)
+        .await?;
+    assert_eq!(estimate.additional_storage_deposit, 0);
+    let tx = account
+        .decrease_native_token_supply(prepared.token_id, U256::from(10), None)
+        .await?;
+    account
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+    account.sync(None).await?;
+
+    let estimate = account
+        .estimate_burn_native_token(prepared.token_id, U256::from(10), None)
+        .await?;
+    assert_eq!(estimate.additional_storage_deposit, 0);
+    let tx = account
+        .burn_native_token(prepared.token_id, U256::from(10), None)
+        .await?;
+    account
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+    let balance = account.sync(None).await?;
+    assert_eq!(
+        balance
+            .native_tokens
+            .iter()
+            .find(|t| t.token_id == prepared.token_id)
+            .unwrap()
+            .available,
+        U256::from(30)
+    );
+
+    common::tear_down(storage_path)
+}
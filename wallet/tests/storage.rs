@@ -0,0 +1,746 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+mod common;
+
+#[cfg(feature = "storage")]
+use std::sync::{Arc, Mutex};
+
+#[cfg(feature = "storage")]
+use iota_wallet::account_manager::{
+    BackupVerificationReport, PortableBackup, PortableBackupOptions, PortableRestoreOptions, PORTABLE_BACKUP_VERSION,
+};
+#[cfg(feature = "storage")]
+use iota_wallet::storage::{
+    audit_log::{AuditLogFilter, AuditOperation},
+    backup_target::{filesystem::FileSystemBackupTarget, BackupTarget},
+    hooks::StorageOperation,
+    manager::StorageManager,
+    quota::StorageQuota,
+};
+use iota_wallet::{Error, Result};
+
+#[cfg(feature = "storage")]
+#[tokio::test]
+async fn storage_hooks() -> Result<()> {
+    let storage_path = "test-storage/storage_hooks";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let events_clone = events.clone();
+    manager
+        .add_storage_hook(move |event| events_clone.lock().unwrap().push(event.clone()))
+        .await;
+
+    manager.create_account().finish().await?;
+    assert!(
+        events
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|event| event.operation == StorageOperation::Set && event.key == "iota-wallet-account-0")
+    );
+
+    manager.remove_latest_account().await?;
+    assert!(
+        events
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|event| event.operation == StorageOperation::Remove && event.key == "iota-wallet-account-0")
+    );
+
+    // No events after the hooks got removed
+    manager.clear_storage_hooks().await;
+    let events_count = events.lock().unwrap().len();
+    manager.create_account().finish().await?;
+    assert_eq!(events.lock().unwrap().len(), events_count);
+
+    common::tear_down(storage_path)
+}
+
+#[cfg(feature = "storage")]
+#[tokio::test]
+async fn storage_disk_usage_and_compaction() -> Result<()> {
+    let storage_path = "test-storage/storage_disk_usage_and_compaction";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+    manager.create_account().finish().await?;
+    manager.create_account().finish().await?;
+
+    let disk_usage = manager.storage_disk_usage().await?;
+    assert!(disk_usage.domains["accountManager"] > 0);
+    let accounts_size = disk_usage.domains["accounts"];
+    assert!(accounts_size > 0);
+    // All records are counted, also the ones that don't belong to an account
+    for domain in ["archive", "logs", "profiles"] {
+        assert!(disk_usage.domains.contains_key(domain));
+    }
+
+    manager.remove_latest_account().await?;
+    manager.compact_storage().await?;
+
+    let disk_usage = manager.storage_disk_usage().await?;
+    assert!(disk_usage.domains["accounts"] < accounts_size);
+
+    common::tear_down(storage_path)
+}
+
+#[cfg(feature = "storage")]
+#[tokio::test]
+async fn storage_json_export_import() -> Result<()> {
+    let storage_path = "test-storage/storage_json_export_import";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+    manager
+        .create_account()
+        .with_alias("Alice".to_string())
+        .finish()
+        .await?;
+    manager.create_account().with_alias("Bob".to_string()).finish().await?;
+
+    let plain_path = format!("{storage_path}/export.json");
+    let encrypted_path = format!("{storage_path}/export-encrypted.json");
+    manager.export_storage_json(&plain_path, None).await?;
+    manager.export_storage_json(&encrypted_path, Some("password")).await?;
+
+    // The output is deterministic
+    let plain_export = std::fs::read_to_string(&plain_path)?;
+    manager.export_storage_json(&plain_path, None).await?;
+    assert_eq!(plain_export, std::fs::read_to_string(&plain_path)?);
+    assert!(!std::fs::read_to_string(&encrypted_path)?.contains("Alice"));
+
+    let plain_import_path = format!("{storage_path}/plain_import");
+    let plain_import_manager = common::make_manager(&plain_import_path, None, None).await?;
+    plain_import_manager.import_storage_json(&plain_path, None).await?;
+    assert_eq!(plain_import_manager.get_accounts().await?.len(), 2);
+    assert_eq!(plain_import_manager.get_account("Bob").await?.read().await.index(), &1);
+
+    let encrypted_import_path = format!("{storage_path}/encrypted_import");
+    let encrypted_import_manager = common::make_manager(&encrypted_import_path, None, None).await?;
+    assert!(
+        encrypted_import_manager
+            .import_storage_json(&encrypted_path, Some("wrong password"))
+            .await
+            .is_err()
+    );
+    encrypted_import_manager
+        .import_storage_json(&encrypted_path, Some("password"))
+        .await?;
+    assert_eq!(encrypted_import_manager.get_accounts().await?.len(), 2);
+
+    // Importing is not possible when there are already accounts
+    assert!(manager.import_storage_json(&plain_path, None).await.is_err());
+
+    common::tear_down(storage_path)
+}
+
+#[cfg(feature = "storage")]
+#[tokio::test]
+async fn storage_quota() -> Result<()> {
+    let storage_path = "test-storage/storage_quota";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+    manager
+        .set_storage_quota(StorageQuota {
+            max_accounts: Some(1),
+            max_bytes: None,
+        })
+        .await?;
+
+    manager.create_account().finish().await?;
+    assert!(matches!(
+        manager.create_account().finish().await,
+        Err(Error::StorageQuotaExceeded { quota: "accounts", .. })
+    ));
+    assert_eq!(manager.get_accounts().await?.len(), 1);
+
+    // Current size is allowed, but a new account doesn't fit anymore
+    let used_bytes = manager.storage_disk_usage().await?.domains.values().sum();
+    manager
+        .set_storage_quota(StorageQuota {
+            max_accounts: None,
+            max_bytes: Some(used_bytes),
+        })
+        .await?;
+    assert!(matches!(
+        manager.create_account().finish().await,
+        Err(Error::StorageQuotaExceeded { quota: "bytes", .. })
+    ));
+
+    // Removing an account is possible and frees space
+    manager.remove_latest_account().await?;
+    manager.create_account().finish().await?;
+
+    common::tear_down(storage_path)
+}
+
+#[cfg(feature = "storage")]
+#[tokio::test]
+async fn storage_remove_account_records() -> Result<()> {
+    let storage_path = "test-storage/storage_remove_account_records";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+    assert!(manager.remove_latest_account_dry_run().await?.is_empty());
+
+    manager.create_account().finish().await?;
+    let keys = manager.remove_latest_account_dry_run().await?;
+    assert!(keys.contains(&"iota-wallet-account-0".to_string()));
+    // Nothing got removed
+    assert_eq!(manager.get_accounts().await?.len(), 1);
+
+    manager.remove_latest_account().await?;
+    drop(manager);
+
+    let manager = common::make_manager(storage_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+    assert!(manager.get_accounts().await?.is_empty());
+    assert!(manager.remove_latest_account_dry_run().await?.is_empty());
+
+    common::tear_down(storage_path)
+}
+
+#[cfg(feature = "storage")]
+#[tokio::test]
+async fn storage_archive_and_restore_account() -> Result<()> {
+    let storage_path = "test-storage/storage_archive_and_restore_account";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+    manager
+        .create_account()
+        .with_alias("Alice".to_string())
+        .finish()
+        .await?;
+    manager.create_account().with_alias("Bob".to_string()).finish().await?;
+
+    manager.archive_account(0).await?;
+    assert_eq!(manager.get_accounts().await?.len(), 1);
+    assert!(manager.get_account("Alice").await.is_err());
+    assert_eq!(manager.archived_account_indexes().await?, vec![0]);
+
+    // New accounts don't reuse the index of the archived account
+    let account = manager.create_account().finish().await?;
+    assert_eq!(*account.read().await.index(), 2);
+    // Also not if the archived account had the highest index
+    manager.archive_account(2).await?;
+    let account = manager.create_account().finish().await?;
+    assert_eq!(*account.read().await.index(), 3);
+    drop(manager);
+
+    // The account stays archived after a restart
+    let manager = common::make_manager(storage_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+    assert_eq!(manager.get_accounts().await?.len(), 2);
+
+    let account = manager.restore_account(0).await?;
+    assert_eq!(account.read().await.alias(), "Alice");
+    assert_eq!(manager.get_accounts().await?.len(), 3);
+    assert_eq!(manager.archived_account_indexes().await?, vec![2]);
+    assert!(manager.restore_account(0).await.is_err());
+    manager.restore_account(2).await?;
+    assert!(manager.archived_account_indexes().await?.is_empty());
+
+    common::tear_down(storage_path)
+}
+
+#[cfg(feature = "storage")]
+#[tokio::test]
+async fn storage_account_export_import() -> Result<()> {
+    let storage_path = "test-storage/storage_account_export_import";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+    let alice = manager
+        .create_account()
+        .with_alias("Alice".to_string())
+        .finish()
+        .await?;
+    let bob = manager.create_account().with_alias("Bob".to_string()).finish().await?;
+
+    let alice_path = format!("{storage_path}/alice.json");
+    let bob_path = format!("{storage_path}/bob.json");
+    alice.export(&alice_path, "password").await?;
+    bob.export(&bob_path, "password").await?;
+    assert!(!std::fs::read_to_string(&bob_path)?.contains("Bob"));
+
+    let import_path = format!("{storage_path}/import");
+    let import_manager = common::make_manager(&import_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+    import_manager.create_account().finish().await?;
+    assert!(
+        import_manager
+            .import_account(&bob_path, "wrong password")
+            .await
+            .is_err()
+    );
+    // An account with index 0 already exists
+    assert!(import_manager.import_account(&alice_path, "password").await.is_err());
+
+    let account = import_manager.import_account(&bob_path, "password").await?;
+    assert_eq!(account.read().await.alias(), "Bob");
+    assert_eq!(
+        account.read().await.public_addresses(),
+        bob.read().await.public_addresses()
+    );
+    assert!(import_manager.import_account(&bob_path, "password").await.is_err());
+    drop(import_manager);
+
+    // The imported account is stored
+    let import_manager = common::make_manager(&import_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+    assert_eq!(import_manager.get_account("Bob").await?.read().await.index(), &1);
+
+    // Accounts of another seed can't be imported
+    let other_seed_path = format!("{storage_path}/other_seed");
+    let other_seed_manager = common::make_manager(&other_seed_path, None, None).await?;
+    assert!(other_seed_manager.import_account(&bob_path, "password").await.is_err());
+
+    common::tear_down(storage_path)
+}
+
+#[cfg(feature = "storage")]
+#[tokio::test]
+async fn storage_portable_backup() -> Result<()> {
+    let storage_path = "test-storage/storage_portable_backup";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+    let alice = manager
+        .create_account()
+        .with_alias("Alice".to_string())
+        .finish()
+        .await?;
+    alice.generate_addresses(2, None).await?;
+    manager.create_account().with_alias("Bob".to_string()).finish().await?;
+
+    let backup_path = format!("{storage_path}/backup.json");
+    manager.backup_portable(&backup_path, "password", None).await?;
+    assert!(!std::fs::read_to_string(&backup_path)?.contains("Alice"));
+    let backup = PortableBackup::decrypt(&std::fs::read(&backup_path)?, "password")?;
+    assert_eq!(backup.version, PORTABLE_BACKUP_VERSION);
+    // The database schema version is readable without the password
+    assert_eq!(
+        PortableBackup::database_schema_version(&std::fs::read(&backup_path)?)?,
+        backup.bundle.database_schema_version
+    );
+    assert!(PortableBackup::decrypt(&std::fs::read(&backup_path)?, "wrong password").is_err());
+
+    // Accounts of another seed can't be restored
+    let other_seed_path = format!("{storage_path}/other_seed");
+    let other_seed_manager = common::make_manager(&other_seed_path, None, None).await?;
+    assert!(matches!(
+        other_seed_manager
+            .restore_portable_backup(&backup_path, "password", None)
+            .await,
+        Err(Error::SeedFingerprintMismatch)
+    ));
+
+    let restore_path = format!("{storage_path}/restore");
+    let restore_manager = common::make_manager(&restore_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+    restore_manager
+        .restore_portable_backup(&backup_path, "password", None)
+        .await?;
+    assert_eq!(restore_manager.get_accounts().await?.len(), 2);
+    let restored_alice = restore_manager.get_account("Alice").await?;
+    assert_eq!(
+        restored_alice.read().await.public_addresses(),
+        alice.read().await.public_addresses()
+    );
+    // Restoring is not possible when there are already accounts
+    assert!(
+        restore_manager
+            .restore_portable_backup(&backup_path, "password", None)
+            .await
+            .is_err()
+    );
+    drop(restore_manager);
+
+    // The restored accounts are stored
+    let restore_manager = common::make_manager(&restore_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+    assert_eq!(restore_manager.get_accounts().await?.len(), 2);
+
+    // Backups without a file system
+    let bytes = manager.backup_portable_bytes("password", None).await?;
+    let bytes_restore_path = format!("{storage_path}/bytes_restore");
+    let bytes_restore_manager = common::make_manager(&bytes_restore_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+    bytes_restore_manager
+        .restore_portable_backup_bytes(&bytes, "password", None)
+        .await?;
+    assert_eq!(bytes_restore_manager.get_accounts().await?.len(), 2);
+
+    let audit_log = manager
+        .audit_log(&AuditLogFilter {
+            operations: Some(vec![AuditOperation::PortableBackup]),
+            ..Default::default()
+        })
+        .await?;
+    assert_eq!(audit_log.len(), 2);
+
+    common::tear_down(storage_path)
+}
+
+#[cfg(feature = "storage")]
+#[tokio::test]
+async fn storage_portable_backup_selective() -> Result<()> {
+    let storage_path = "test-storage/storage_portable_backup_selective";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+    manager
+        .create_account()
+        .with_alias("Alice".to_string())
+        .finish()
+        .await?;
+    manager.create_account().with_alias("Bob".to_string()).finish().await?;
+    manager
+        .create_account()
+        .with_alias("Carol".to_string())
+        .finish()
+        .await?;
+
+    // Only the selected accounts are included
+    let bytes = manager
+        .backup_portable_bytes(
+            "password",
+            Some(PortableBackupOptions {
+                account_indexes: Some(vec![1, 2]),
+            }),
+        )
+        .await?;
+    let backup = PortableBackup::decrypt(&bytes, "password")?;
+    assert_eq!(backup.bundle.account_indexes()?, vec![1, 2]);
+    assert!(
+        manager
+            .backup_portable_bytes(
+                "password",
+                Some(PortableBackupOptions {
+                    account_indexes: Some(vec![5]),
+                }),
+            )
+            .await
+            .is_err()
+    );
+
+    // Only the selected accounts of the backup are restored
+    let restore_path = format!("{storage_path}/restore");
+    let restore_manager = common::make_manager(&restore_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+    restore_manager
+        .restore_portable_backup_bytes(
+            &bytes,
+            "password",
+            Some(PortableRestoreOptions {
+                account_indexes: Some(vec![2]),
+                merge: false,
+            }),
+        )
+        .await?;
+    assert_eq!(restore_manager.get_accounts().await?.len(), 1);
+    assert_eq!(restore_manager.get_account("Carol").await?.read().await.index(), &2);
+
+    // Accounts that collide with existing accounts aren't merged, nothing is restored
+    assert!(matches!(
+        restore_manager
+            .restore_portable_backup_bytes(
+                &bytes,
+                "password",
+                Some(PortableRestoreOptions {
+                    account_indexes: None,
+                    merge: true,
+                }),
+            )
+            .await,
+        Err(Error::RestoreCollision(_))
+    ));
+    assert_eq!(restore_manager.get_accounts().await?.len(), 1);
+
+    // Accounts without collisions are merged into the existing accounts
+    restore_manager
+        .restore_portable_backup_bytes(
+            &bytes,
+            "password",
+            Some(PortableRestoreOptions {
+                account_indexes: Some(vec![1]),
+                merge: true,
+            }),
+        )
+        .await?;
+    let accounts = restore_manager.get_accounts().await?;
+    assert_eq!(accounts.len(), 2);
+    assert_eq!(accounts[0].read().await.alias(), "Bob");
+    assert_eq!(accounts[1].read().await.alias(), "Carol");
+    drop(accounts);
+    drop(restore_manager);
+
+    // The merged accounts are stored
+    let restore_manager = common::make_manager(&restore_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+    assert_eq!(restore_manager.get_accounts().await?.len(), 2);
+
+    common::tear_down(storage_path)
+}
+
+#[cfg(feature = "storage")]
+#[tokio::test]
+async fn storage_verify_backup() -> Result<()> {
+    let storage_path = "test-storage/storage_verify_backup";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+    let account = manager
+        .create_account()
+        .with_alias("Alice".to_string())
+        .finish()
+        .await?;
+    account.generate_addresses(2, None).await?;
+
+    let backup_path = format!("{storage_path}/backup.json");
+    manager.backup_portable(&backup_path, "password", None).await?;
+
+    let report: BackupVerificationReport = manager.verify_backup(&backup_path, "password").await?;
+    assert!(report.is_valid());
+    assert_eq!(report.seed_fingerprint_matches, Some(true));
+    assert_eq!(report.accounts.len(), 1);
+    assert_eq!(report.accounts[0].alias, "Alice");
+    assert_eq!(report.accounts[0].addresses, 3);
+    // Nothing was restored or changed
+    assert_eq!(manager.get_accounts().await?.len(), 1);
+
+    assert!(manager.verify_backup(&backup_path, "wrong password").await.is_err());
+
+    // The backup belongs to another seed
+    let other_seed_path = format!("{storage_path}/other_seed");
+    let other_seed_manager = common::make_manager(&other_seed_path, None, None).await?;
+    let report = other_seed_manager.verify_backup(&backup_path, "password").await?;
+    assert_eq!(report.seed_fingerprint_matches, Some(false));
+    assert!(!report.is_valid());
+
+    common::tear_down(storage_path)
+}
+
+#[cfg(feature = "storage")]
+#[tokio::test]
+async fn storage_import_schema_version_1() -> Result<()> {
+    let storage_path = "test-storage/storage_import_schema_version_1";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+
+    // An export of a database with schema version 1, before the account aliases were indexed
+    let export_path = format!("{storage_path}/export.json");
+    std::fs::write(
+        &export_path,
+        r#"{"type":"plain","version":1,"databaseSchemaVersion":1,"records":{"iota-wallet-account-0":{"index":0,"coinType":4219,"alias":"Alice","publicAddresses":[{"address":"rms1qzz8m6d6cund3s5lhhc4gwnl05lx8jyqrjnfdrtje2hgkqnkjdmvv2c4enq","keyIndex":0,"internal":false,"used":false}],"internalAddresses":[],"addressesWithUnspentOutputs":[],"outputs":{},"lockedOutputs":[],"unspentOutputs":{},"transactions":{},"pendingTransactions":[],"incomingTransactions":{},"nativeTokenFoundries":{}},"iota-wallet-accounts":[0]}}"#,
+    )?;
+
+    let bundle = StorageManager::read_json_bundle(&export_path, None)?;
+    assert_eq!(bundle.database_schema_version, 2);
+    assert_eq!(
+        bundle.records["iota-wallet-account-aliases"],
+        serde_json::json!({ "alice": 0 })
+    );
+
+    manager.import_storage_json(&export_path, None).await?;
+    assert_eq!(*manager.get_account_by_alias("ALICE").await?.read().await.index(), 0);
+
+    common::tear_down(storage_path)
+}
+
+#[cfg(feature = "storage")]
+#[tokio::test]
+async fn storage_scheduled_backups() -> Result<()> {
+    let storage_path = "test-storage/storage_scheduled_backups";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+    manager.create_account().finish().await?;
+
+    let backup_directory = format!("{storage_path}/backups");
+    // Other files and the scheduled backups of other wallets in the directory aren't removed by the retention
+    std::fs::create_dir_all(&backup_directory)?;
+    std::fs::write(format!("{backup_directory}/other.json"), "{}")?;
+    let other_wallet_backup = "iota-wallet-backup-0x00-00000000000000000001.json".to_string();
+    std::fs::write(format!("{backup_directory}/{other_wallet_backup}"), "{}")?;
+
+    manager
+        .start_scheduled_backups(
+            FileSystemBackupTarget::new(&backup_directory),
+            "password".to_string(),
+            Some(std::time::Duration::from_secs(1)),
+            Some(2),
+        )
+        .await?;
+    tokio::time::sleep(std::time::Duration::from_secs(4)).await;
+    manager.stop_scheduled_backups().await?;
+
+    let target = FileSystemBackupTarget::new(&backup_directory);
+    let mut names = target.list().await?;
+    names.sort();
+    assert!(names.contains(&"other.json".to_string()));
+    assert!(names.contains(&other_wallet_backup));
+    names.retain(|name| name != "other.json" && name != &other_wallet_backup);
+    assert_eq!(names.len(), 2);
+
+    let report = manager
+        .verify_backup(format!("{backup_directory}/{}", names[1]), "password")
+        .await?;
+    assert!(report.is_valid());
+    assert_eq!(report.accounts.len(), 1);
+    drop(manager);
+
+    // The wallet keeps its id, so the backups of the previous run are pruned after a restart
+    let manager = common::make_manager(storage_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+    manager
+        .start_scheduled_backups(
+            FileSystemBackupTarget::new(&backup_directory),
+            "password".to_string(),
+            Some(std::time::Duration::from_secs(1)),
+            Some(2),
+        )
+        .await?;
+    tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+    manager.stop_scheduled_backups().await?;
+
+    let mut restarted_names = target.list().await?;
+    restarted_names.sort();
+    restarted_names.retain(|name| name != "other.json" && name != &other_wallet_backup);
+    assert_eq!(restarted_names.len(), 2);
+    assert!(!names.contains(&restarted_names[1]));
+    assert!(!restarted_names.contains(&names[0]));
+
+    common::tear_down(storage_path)
+}
+
+#[cfg(feature = "storage")]
+#[tokio::test]
+async fn storage_derivation_cache() -> Result<()> {
+    let storage_path = "test-storage/storage_derivation_cache";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+    let account = manager.create_account().finish().await?;
+    let addresses = account.generate_addresses(2, None).await?;
+    assert!(
+        manager
+            .remove_latest_account_dry_run()
+            .await?
+            .contains(&"derivation-cache-0".to_string())
+    );
+
+    // Cached addresses are the same as the derived ones
+    assert_eq!(
+        manager.generate_address(0, false, 2, None).await?,
+        *addresses[1].address().as_ref()
+    );
+
+    manager.clear_derivation_cache().await?;
+    assert!(
+        !manager
+            .remove_latest_account_dry_run()
+            .await?
+            .contains(&"derivation-cache-0".to_string())
+    );
+    assert_eq!(
+        manager.generate_address(0, false, 2, None).await?,
+        *addresses[1].address().as_ref()
+    );
+
+    common::tear_down(storage_path)
+}
+
+#[cfg(feature = "storage")]
+#[tokio::test]
+async fn storage_derivation_cache_verification() -> Result<()> {
+    let storage_path = "test-storage/storage_derivation_cache_verification";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+    let account = manager.create_account().finish().await?;
+    let addresses = account.generate_addresses(2, None).await?;
+
+    // Replace a cached address with another one of the account
+    let export_path = format!("{storage_path}/export.json");
+    manager.export_storage_json(&export_path, None).await?;
+    let mut export: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&export_path)?)?;
+    let cached_addresses = &mut export["records"]["derivation-cache-0"]["public"];
+    cached_addresses["2"] = cached_addresses["1"].clone();
+    std::fs::write(&export_path, export.to_string())?;
+
+    let import_path = format!("{storage_path}/import");
+    let import_manager = common::make_manager(&import_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+    import_manager.import_storage_json(&export_path, None).await?;
+
+    // The modified cache is detected and the address is derived again
+    assert_eq!(
+        import_manager.generate_address(0, false, 2, None).await?,
+        *addresses[1].address().as_ref()
+    );
+    assert!(
+        !import_manager
+            .remove_latest_account_dry_run()
+            .await?
+            .contains(&"derivation-cache-0".to_string())
+    );
+
+    common::tear_down(storage_path)
+}
+
+#[cfg(feature = "storage")]
+#[tokio::test]
+async fn storage_balance_history() -> Result<()> {
+    let storage_path = "test-storage/storage_balance_history";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+    manager
+        .set_balance_history_retention(std::time::Duration::from_secs(60 * 60))
+        .await?;
+    let account = manager.create_account().finish().await?;
+
+    // Snapshots are only recorded after syncs
+    assert!(account.balance_history(0..u64::MAX, None).await?.is_empty());
+    assert!(
+        manager
+            .storage_disk_usage()
+            .await?
+            .domains
+            .contains_key("balanceHistory")
+    );
+
+    common::tear_down(storage_path)
+}
+
+#[cfg(feature = "storage")]
+#[tokio::test]
+async fn storage_audit_log() -> Result<()> {
+    let storage_path = "test-storage/storage_audit_log";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+    let account = manager.create_account().finish().await?;
+    account.generate_addresses(2, None).await?;
+
+    let filter = AuditLogFilter {
+        operations: Some(vec![AuditOperation::GenerateAddresses]),
+        ..Default::default()
+    };
+    let audit_log = manager.audit_log(&filter).await?;
+    assert_eq!(audit_log.len(), 1);
+    assert_eq!(audit_log[0].account_index, Some(0));
+    assert!(audit_log[0].error.is_none());
+
+    let filter = AuditLogFilter {
+        account_index: Some(1),
+        ..Default::default()
+    };
+    assert!(manager.audit_log(&filter).await?.is_empty());
+    drop(account);
+    drop(manager);
+
+    // Entries are persisted
+    let manager = common::make_manager(storage_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+    assert_eq!(manager.audit_log(&AuditLogFilter::default()).await?.len(), 1);
+
+    common::tear_down(storage_path)
+}
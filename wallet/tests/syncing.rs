@@ -3,6 +3,8 @@
 
 mod common;
 
+#[cfg(feature = "events")]
+use futures::{FutureExt, StreamExt};
 use iota_client::block::output::{
     unlock_condition::{
         AddressUnlockCondition, ExpirationUnlockCondition, GovernorAddressUnlockCondition,
@@ -10,7 +12,15 @@ use iota_client::block::output::{
     },
     AliasId, AliasOutputBuilder, BasicOutputBuilder, NftId, NftOutputBuilder, UnlockCondition,
 };
-use iota_wallet::{account::SyncOptions, Result};
+#[cfg(feature = "events")]
+use iota_wallet::events::{
+    types::{EventFilter, WalletEvent, WalletEventType},
+    StreamEvent,
+};
+use iota_wallet::{
+    account::{CancellationToken, ReattachmentOptions, SyncOptions, SyncScope},
+    AddressWithAmount, Result,
+};
 
 #[ignore]
 #[tokio::test]
@@ -119,3 +129,262 @@ async fn sync_only_most_basic_outputs() -> Result<()> {
 
     common::tear_down(storage_path)
 }
+
+#[ignore]
+#[cfg(feature = "storage")]
+#[tokio::test]
+async fn sync_cursor_persisted() -> Result<()> {
+    let storage_path = "test-storage/sync_cursor_persisted";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+    let account = manager.create_account().finish().await?;
+    assert!(account.sync_cursor().await?.is_none());
+
+    account.sync(None).await?;
+    let sync_cursor = account.sync_cursor().await?.unwrap();
+    assert!(sync_cursor.ledger_index > 0);
+    drop(account);
+    drop(manager);
+
+    // The cursor is still available after a restart
+    let manager = common::make_manager(storage_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+    let account = manager.get_account(0).await?;
+    assert_eq!(account.sync_cursor().await?, Some(sync_cursor));
+
+    common::tear_down(storage_path)
+}
+
+#[ignore]
+#[cfg(feature = "storage")]
+#[tokio::test]
+async fn sync_incremental() -> Result<()> {
+    let storage_path = "test-storage/sync_incremental";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, Some(common::DEFAULT_MNEMONIC), None).await?;
+
+    let account_0 = &common::create_accounts_with_funds(&manager, 1).await?[0];
+    let account_1 = manager.create_account().finish().await?;
+    // The first sync requests all outputs and stores the address cursors
+    account_1.sync(None).await?;
+
+    let amount = 1_000_000;
+    let tx = account_0
+        .send_amount(
+            vec![AddressWithAmount {
+                address: account_1.addresses().await?[0].address().to_bech32(),
+                amount,
+            }],
+            None,
+        )
+        .await?;
+    account_0
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+
+    // Only the new output is requested
+    let sync_options = SyncOptions {
+        force_syncing: true,
+        ..Default::default()
+    };
+    let balance = account_1.sync(Some(sync_options.clone())).await?;
+    assert_eq!(balance.base_coin.available, amount);
+
+    // The known output is detected as spent
+    let tx = account_1
+        .send_amount(
+            vec![AddressWithAmount {
+                address: account_0.addresses().await?[0].address().to_bech32(),
+                amount,
+            }],
+            None,
+        )
+        .await?;
+    account_1
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+    let balance = account_1.sync(Some(sync_options)).await?;
+    assert_eq!(balance.base_coin.total, 0);
+
+    // A full sync returns the same balance
+    let full_sync_balance = account_1
+        .sync(Some(SyncOptions {
+            force_syncing: true,
+            full_sync: true,
+            ..Default::default()
+        }))
+        .await?;
+    assert_eq!(full_sync_balance, balance);
+
+    common::tear_down(storage_path)
+}
+
+#[ignore]
+#[tokio::test]
+async fn sync_scopes() -> Result<()> {
+    let storage_path = "test-storage/sync_scopes";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+
+    let account = &common::create_accounts_with_funds(&manager, 1).await?[0];
+    let balance = account.balance().await?;
+
+    // Outputs that aren't part of the scope are kept
+    for scope in [
+        SyncScope::BasicOutputs,
+        SyncScope::NftOutputs,
+        SyncScope::PendingTransactions,
+    ] {
+        let scoped_balance = account
+            .sync(Some(SyncOptions {
+                force_syncing: true,
+                scope,
+                ..Default::default()
+            }))
+            .await?;
+        assert_eq!(scoped_balance.base_coin, balance.base_coin);
+    }
+
+    common::tear_down(storage_path)
+}
+
+#[tokio::test]
+async fn sync_cancelled() -> Result<()> {
+    let storage_path = "test-storage/sync_cancelled";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+    let account = manager.create_account().finish().await?;
+
+    let cancellation = CancellationToken::new();
+    cancellation.cancel();
+    // Stops before any request is sent
+    let result = account.sync_with_cancellation(None, cancellation).await;
+    assert!(matches!(result, Err(iota_wallet::Error::Cancelled)));
+    assert!(account.unspent_outputs(None).await?.is_empty());
+
+    common::tear_down(storage_path)
+}
+
+#[test]
+fn reattachment_options() {
+    let options: SyncOptions =
+        serde_json::from_str(r#"{"reattachment": {"maxReattachments": 3, "maxPendingMilliseconds": 60000}}"#).unwrap();
+    assert_eq!(
+        options.reattachment,
+        ReattachmentOptions {
+            max_reattachments: Some(3),
+            max_pending_milliseconds: Some(60000),
+            ..Default::default()
+        }
+    );
+    assert!(options.reattachment.promote);
+    assert_eq!(SyncOptions::default().reattachment.max_reattachments, None);
+}
+
+#[ignore]
+#[tokio::test]
+async fn sync_preview() -> Result<()> {
+    let storage_path = "test-storage/sync_preview";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+
+    let account_0 = &common::create_accounts_with_funds(&manager, 1).await?[0];
+    let account_1 = manager.create_account().finish().await?;
+
+    let amount = 1_000_000;
+    let tx = account_0
+        .send_amount(
+            vec![AddressWithAmount {
+                address: account_1.addresses().await?[0].address().to_bech32(),
+                amount,
+            }],
+            None,
+        )
+        .await?;
+    account_0
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+
+    let sync_preview = account_1.sync_preview(None).await?;
+    assert_eq!(sync_preview.new_outputs.len(), 1);
+    assert!(sync_preview.spent_output_ids.is_empty());
+    assert_eq!(sync_preview.balance_delta.base_coin_received, amount);
+    // The account isn't changed by the preview
+    assert!(account_1.unspent_outputs(None).await?.is_empty());
+
+    account_1.sync(None).await?;
+    let sync_preview = account_1.sync_preview(None).await?;
+    assert!(sync_preview.new_outputs.is_empty());
+    assert_eq!(sync_preview.balance_delta.base_coin_received, 0);
+
+    common::tear_down(storage_path)
+}
+
+#[ignore]
+#[cfg(feature = "events")]
+#[tokio::test]
+async fn deposit_events() -> Result<()> {
+    let storage_path = "test-storage/deposit_events";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+
+    let account_0 = &common::create_accounts_with_funds(&manager, 1).await?[0];
+    let account_1 = manager.create_account().finish().await?;
+    let mut deposit_events = manager
+        .subscribe(
+            EventFilter {
+                accounts: vec![*account_1.read().await.index()],
+                kinds: vec![WalletEventType::DepositReceived],
+                min_amount: None,
+                min_severity: None,
+            },
+            None,
+        )
+        .await;
+
+    let amount = 1_000_000;
+    let tx = account_0
+        .send_amount(
+            vec![AddressWithAmount {
+                address: account_1.addresses().await?[0].address().to_bech32(),
+                amount,
+            }],
+            None,
+        )
+        .await?;
+    account_0
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+
+    let sync_options = SyncOptions {
+        force_syncing: true,
+        deposit_confirmations: 2,
+        ..Default::default()
+    };
+    // Reported after every sync until it has the confirmations
+    let mut confirmed = false;
+    while !confirmed {
+        account_1.sync(Some(sync_options.clone())).await?;
+        while let Some(Some(StreamEvent::Event(event))) = deposit_events.next().now_or_never() {
+            let WalletEvent::DepositReceived(deposit) = event.event else {
+                panic!("unexpected event");
+            };
+            assert_eq!(deposit.amount, amount);
+            assert_eq!(deposit.confirmed, deposit.confirmations >= 2);
+            confirmed = deposit.confirmed;
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+    }
+    account_1.sync(Some(sync_options)).await?;
+    assert!(deposit_events.next().now_or_never().is_none());
+
+    // Outputs of own transactions aren't deposits
+    assert!(account_0.read().await.unconfirmed_deposits().is_empty());
+
+    common::tear_down(storage_path)
+}
@@ -3,7 +3,18 @@
 
 mod common;
 
-use iota_wallet::{account::TransactionOptions, AddressAndNftId, AddressWithAmount, NftOptions, Result};
+use std::{collections::HashSet, time::Duration};
+
+use iota_client::block::output::NativeToken;
+use iota_wallet::{
+    account::{
+        types::TransactionKind, BatchRecipient, DustPolicy, DustWarning, FilterOptions, HistoryExportFormat,
+        HistoryExportRow, Irc27Metadata, NftMintBuilder, OutputOptions, SendOptions, SendWarning, SigningPolicy,
+        SwapAcceptance, SwapAssets, SwapOffer, TransactionDirection, TransactionFilterOptions,
+        TransactionHistoryFilter, TransactionOptions, VestingSchedule,
+    },
+    AddressAndNftId, AddressWithAmount, Error, NativeTokenOptions, NftOptions, Result, U256,
+};
 
 #[ignore]
 #[tokio::test]
@@ -37,6 +48,124 @@ async fn send_amount() -> Result<()> {
     common::tear_down(storage_path)
 }
 
+#[ignore]
+#[tokio::test]
+async fn send_all() -> Result<()> {
+    let storage_path = "test-storage/send_all";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+
+    let account_0 = &common::create_accounts_with_funds(&manager, 1).await?[0];
+    let account_1 = manager.create_account().finish().await?;
+
+    let balance = account_0.balance().await?;
+    let tx = account_0
+        .send_all(&account_1.addresses().await?[0].address().to_bech32(), false, None)
+        .await?;
+
+    account_0
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+
+    // Everything was sent, without a remainder
+    let balance_0 = account_0.sync(None).await?;
+    assert_eq!(balance_0.base_coin.total, 0);
+    let balance_1 = account_1.sync(None).await?;
+    assert_eq!(balance_1.base_coin.available, balance.base_coin.available);
+
+    // Nothing left to send
+    assert!(matches!(
+        account_0
+            .send_all(&account_1.addresses().await?[0].address().to_bech32(), false, None)
+            .await,
+        Err(Error::InsufficientFunds { available: 0, .. })
+    ));
+
+    common::tear_down(storage_path)
+}
+
+#[ignore]
+#[tokio::test]
+async fn internal_transfer() -> Result<()> {
+    let storage_path = "test-storage/internal_transfer";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+
+    let account_0 = &common::create_accounts_with_funds(&manager, 1).await?[0];
+    let account_1 = manager.create_account().finish().await?;
+
+    let amount = 1_000_000;
+    let tx = manager.internal_transfer(0, 1, amount, None).await?;
+    account_0
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+
+    let balance = account_1.sync(None).await?;
+    assert_eq!(balance.base_coin.available, amount);
+    for account in [account_0, &account_1] {
+        let internal_transfer = account.read().await.internal_transfers()[&tx.transaction_id];
+        assert_eq!(internal_transfer.amount, amount);
+    }
+
+    // The transfer is only an internal transfer in the statistics of the whole wallet
+    let statistics = account_0.statistics(0..u64::MAX).await?;
+    assert_eq!(statistics.sent, amount);
+    assert_eq!(statistics.transaction_kinds[&TransactionKind::InternalTransfer], 1);
+    let total_statistics = manager.statistics(0..u64::MAX).await?;
+    assert_eq!(total_statistics.sent, 0);
+    assert_eq!(total_statistics.received, 0);
+    assert_eq!(
+        total_statistics.transaction_kinds[&TransactionKind::InternalTransfer],
+        1
+    );
+
+    common::tear_down(storage_path)
+}
+
+#[ignore]
+#[tokio::test]
+async fn send_idempotent() -> Result<()> {
+    let storage_path = "test-storage/send_idempotent";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+
+    let account_0 = &common::create_accounts_with_funds(&manager, 1).await?[0];
+    let account_1 = manager.create_account().finish().await?;
+
+    let amount = 1_000_000;
+    let outputs = vec![AddressWithAmount {
+        address: account_1.addresses().await?[0].address().to_bech32(),
+        amount,
+    }];
+    let options = Some(TransactionOptions {
+        idempotency_key: Some("withdrawal-1".to_string()),
+        ..Default::default()
+    });
+
+    let tx = account_0.send_amount(outputs.clone(), options.clone()).await?;
+    // The retried call returns the first transaction instead of sending again
+    let retried_tx = account_0.send_amount(outputs, options).await?;
+    assert_eq!(tx.transaction_id, retried_tx.transaction_id);
+    assert_eq!(
+        account_0
+            .get_transaction_by_idempotency_key("withdrawal-1")
+            .await
+            .map(|tx| tx.transaction_id),
+        Some(tx.transaction_id)
+    );
+
+    account_0
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+    let balance = account_1.sync(None).await?;
+    assert_eq!(balance.base_coin.available, amount);
+
+    common::tear_down(storage_path)
+}
+
 #[ignore]
 #[tokio::test]
 async fn send_amount_127_outputs() -> Result<()> {
@@ -124,6 +253,32 @@ async fn send_amount_custom_input() -> Result<()> {
     assert_eq!(tx.inputs.len(), 1);
     assert_eq!(tx.inputs.first().unwrap().metadata.output_id()?, custom_input.output_id);
 
+    // The custom inputs need to cover the outputs, other outputs aren't added
+    let custom_inputs = account_1
+        .unspent_outputs(Some(FilterOptions {
+            max_amount: Some(amount),
+            addresses: Some(vec![account_1.addresses().await?[0].address().to_bech32()]),
+            ..Default::default()
+        }))
+        .await?;
+    let custom_input = custom_inputs
+        .iter()
+        .find(|output_data| output_data.output_id != custom_input.output_id)
+        .unwrap();
+    let result = account_1
+        .send_amount(
+            vec![AddressWithAmount {
+                address: account_0.addresses().await?[0].address().to_bech32(),
+                amount: 2 * amount,
+            }],
+            Some(TransactionOptions {
+                custom_inputs: Some(vec![custom_input.output_id]),
+                ..Default::default()
+            }),
+        )
+        .await;
+    assert!(matches!(result, Err(Error::InsufficientFunds { .. })));
+
     common::tear_down(storage_path)
 }
 
@@ -172,3 +327,941 @@ async fn send_nft() -> Result<()> {
 
     common::tear_down(storage_path)
 }
+
+#[ignore]
+#[tokio::test]
+async fn send_amount_signing_policy() -> Result<()> {
+    let storage_path = "test-storage/send_amount_signing_policy";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+
+    let account_0 = &common::create_accounts_with_funds(&manager, 1).await?[0];
+    let account_1 = manager.create_account().finish().await?;
+    let address = account_1.addresses().await?[0].address().to_bech32();
+
+    manager
+        .set_signing_policy(SigningPolicy {
+            max_transaction_amount: Some(1_000_000),
+            ..Default::default()
+        })
+        .await;
+    let outputs = vec![AddressWithAmount {
+        address: address.clone(),
+        amount: 2_000_000,
+    }];
+    assert!(matches!(
+        account_0.send_amount(outputs, None).await,
+        Err(Error::SigningPolicyViolation(_))
+    ));
+
+    manager
+        .set_signing_policy(SigningPolicy {
+            denied_addresses: HashSet::from([address.clone()]),
+            ..Default::default()
+        })
+        .await;
+    let outputs = vec![AddressWithAmount {
+        address: address.clone(),
+        amount: 1_000_000,
+    }];
+    assert!(matches!(
+        account_0.send_amount(outputs, None).await,
+        Err(Error::SigningPolicyViolation(_))
+    ));
+
+    manager.set_signing_policy(SigningPolicy::default()).await;
+    manager
+        .add_signing_confirmation(|request| request.amount <= 1_000_000)
+        .await;
+    let outputs = vec![AddressWithAmount {
+        address: address.clone(),
+        amount: 1_000_000,
+    }];
+    account_0.send_amount(outputs, None).await?;
+
+    manager.clear_signing_confirmations().await;
+    manager
+        .set_transaction_approver(|request| async move {
+            if request.amount > 1_000_000 {
+                TransactionApproval::Rejected("needs a manual review".to_string())
+            } else {
+                TransactionApproval::Approved
+            }
+        })
+        .await;
+    let outputs = vec![AddressWithAmount {
+        address: address.clone(),
+        amount: 2_000_000,
+    }];
+    assert!(matches!(
+        account_0.send_amount(outputs, None).await,
+        Err(Error::TransactionRejected(_))
+    ));
+    manager.clear_transaction_approver().await;
+    let outputs = vec![AddressWithAmount {
+        address,
+        amount: 2_000_000,
+    }];
+    account_0.send_amount(outputs, None).await?;
+
+    common::tear_down(storage_path)
+}
+
+#[ignore]
+#[tokio::test]
+async fn send_amount_dust_policy() -> Result<()> {
+    let storage_path = "test-storage/send_amount_dust_policy";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+
+    let account_0 = &common::create_accounts_with_funds(&manager, 1).await?[0];
+    let account_1 = manager.create_account().finish().await?;
+    let address = account_1.addresses().await?[0].address().to_bech32();
+
+    manager
+        .set_dust_policy(DustPolicy {
+            min_output_amount: Some(2_000_000),
+            block: true,
+            ..Default::default()
+        })
+        .await;
+    let outputs = vec![AddressWithAmount {
+        address: address.clone(),
+        amount: 1_000_000,
+    }];
+    assert!(matches!(
+        account_0.send_amount(outputs.clone(), None).await,
+        Err(Error::DustPolicyViolation(_))
+    ));
+
+    // The inputs were unlocked again, so the same transaction can be sent with the override
+    let prepared_transaction = account_0
+        .prepare_send_amount(
+            outputs,
+            Some(TransactionOptions {
+                allow_dust: true,
+                ..Default::default()
+            }),
+        )
+        .await?;
+    assert!(matches!(
+        account_0.dust_warnings(&prepared_transaction).await[..],
+        [DustWarning::Output {
+            amount: 1_000_000,
+            minimum: 2_000_000,
+            ..
+        }]
+    ));
+    account_0.discard_prepared_transaction(prepared_transaction).await?;
+
+    // Only warns if the policy doesn't block
+    manager
+        .set_dust_policy(DustPolicy {
+            min_output_amount: Some(2_000_000),
+            ..Default::default()
+        })
+        .await;
+    let outputs = vec![AddressWithAmount {
+        address,
+        amount: 1_000_000,
+    }];
+    account_0.send_amount(outputs, None).await?;
+
+    common::tear_down(storage_path)
+}
+
+#[ignore]
+#[tokio::test]
+async fn send_queued_transaction() -> Result<()> {
+    let storage_path = "test-storage/send_queued_transaction";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+
+    let account_0 = &common::create_accounts_with_funds(&manager, 1).await?[0];
+    let account_1 = manager.create_account().finish().await?;
+
+    manager.set_offline_mode(true).await?;
+    assert!(manager.is_offline_mode());
+    assert!(matches!(
+        manager.submit_queued_transactions().await,
+        Err(Error::OfflineMode)
+    ));
+
+    let amount = 1_000_000;
+    let output = account_0
+        .prepare_output(
+            OutputOptions {
+                recipient_address: account_1.addresses().await?[0].address().to_bech32(),
+                amount,
+                assets: None,
+                features: None,
+                unlocks: None,
+                storage_deposit: None,
+            },
+            None,
+        )
+        .await?;
+    let queued_transaction = account_0.queue_transaction(vec![output], None).await?;
+    assert_eq!(account_0.queued_transactions().await?.len(), 1);
+
+    // Submits the queued transactions of all accounts
+    manager.set_offline_mode(false).await?;
+    assert!(account_0.queued_transactions().await?.is_empty());
+
+    account_0
+        .retry_transaction_until_included(&queued_transaction.transaction_id, None, None)
+        .await?;
+
+    let balance = account_1.sync(None).await.unwrap();
+    assert_eq!(balance.base_coin.available, amount);
+
+    common::tear_down(storage_path)
+}
+
+#[ignore]
+#[tokio::test]
+async fn prepare_sign_and_submit_transaction() -> Result<()> {
+    let storage_path = "test-storage/prepare_sign_and_submit_transaction";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+
+    let account_0 = &common::create_accounts_with_funds(&manager, 1).await?[0];
+    let account_1 = manager.create_account().finish().await?;
+
+    let available = account_0.balance().await?.base_coin.available;
+    let amount = 1_000_000;
+    let addresses_with_amount = vec![AddressWithAmount {
+        address: account_1.addresses().await?[0].address().to_bech32(),
+        amount,
+    }];
+
+    // The inputs are locked until the prepared transaction is discarded
+    let prepared_transaction = account_0
+        .prepare_send_amount(addresses_with_amount.clone(), None)
+        .await?;
+    assert!(account_0.balance().await?.base_coin.available < available);
+    account_0.discard_prepared_transaction(prepared_transaction).await?;
+    assert_eq!(account_0.balance().await?.base_coin.available, available);
+
+    let prepared_transaction = account_0.prepare_send_amount(addresses_with_amount, None).await?;
+    let signed_transaction = account_0.sign_transaction(&prepared_transaction).await?;
+    let tx = account_0.submit_transaction(signed_transaction).await?;
+
+    account_0
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+
+    let balance = account_1.sync(None).await.unwrap();
+    assert_eq!(balance.base_coin.available, amount);
+
+    common::tear_down(storage_path)
+}
+
+#[ignore]
+#[tokio::test]
+async fn sweep() -> Result<()> {
+    let storage_path = "test-storage/sweep";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+
+    let account_0 = &common::create_accounts_with_funds(&manager, 1).await?[0];
+    let account_1 = manager.create_account().finish().await?;
+
+    let available = account_0.balance().await?.base_coin.available;
+    let sweep = account_0
+        .sweep(&account_1.addresses().await?[0].address().to_bech32(), None)
+        .await?;
+    assert!(sweep.failed.is_empty());
+
+    for tx in &sweep.transactions {
+        account_0
+            .retry_transaction_until_included(&tx.transaction_id, None, None)
+            .await?;
+    }
+
+    let balance = account_0.sync(None).await?;
+    assert_eq!(balance.base_coin.available, 0);
+    let balance = account_1.sync(None).await?;
+    assert_eq!(balance.base_coin.available, available);
+
+    common::tear_down(storage_path)
+}
+
+#[ignore]
+#[tokio::test]
+async fn simulate_transaction() -> Result<()> {
+    let storage_path = "test-storage/simulate_transaction";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+
+    let account_0 = &common::create_accounts_with_funds(&manager, 1).await?[0];
+    let account_1 = manager.create_account().finish().await?;
+
+    let available = account_0.balance().await?.base_coin.available;
+    let tx = account_0
+        .send_amount(
+            vec![AddressWithAmount {
+                address: account_1.addresses().await?[0].address().to_bech32(),
+                amount: 1_000_000,
+            }],
+            Some(TransactionOptions {
+                simulate: true,
+                ..Default::default()
+            }),
+        )
+        .await?;
+
+    // The transaction isn't submitted or stored and the inputs are available again
+    assert!(tx.block_id.is_none());
+    assert!(account_0.get_transaction(&tx.transaction_id).await.is_none());
+    assert_eq!(account_0.balance().await?.base_coin.available, available);
+
+    common::tear_down(storage_path)
+}
+
+#[ignore]
+#[tokio::test]
+async fn send_batch() -> Result<()> {
+    let storage_path = "test-storage/send_batch";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+
+    let account_0 = &common::create_accounts_with_funds(&manager, 1).await?[0];
+    let account_1 = manager.create_account().finish().await?;
+
+    let address = account_1.addresses().await?[0].address().to_bech32();
+    let amount = 1_000_000;
+    let mut recipients: Vec<BatchRecipient> = (0..3)
+        .map(|i| BatchRecipient {
+            address: address.clone(),
+            amount,
+            tag: Some(prefix_hex::encode(format!("payroll {i}").as_bytes())),
+            metadata: None,
+        })
+        .collect();
+    // An invalid recipient doesn't stop the others
+    recipients.push(BatchRecipient {
+        address: "invalid".to_string(),
+        amount,
+        tag: None,
+        metadata: None,
+    });
+
+    let batch_send = account_0.send_batch(recipients, None).await?;
+    assert_eq!(batch_send.transactions.len(), 1);
+    assert!(batch_send.recipients[..3]
+        .iter()
+        .all(|recipient| recipient.output_id.is_some()));
+    assert!(batch_send.recipients[3].error.is_some());
+
+    account_0
+        .retry_transaction_until_included(&batch_send.transactions[0].transaction_id, None, None)
+        .await?;
+
+    let balance = account_1.sync(None).await?;
+    assert_eq!(balance.base_coin.available, 3 * amount);
+
+    common::tear_down(storage_path)
+}
+
+#[ignore]
+#[tokio::test]
+async fn transaction_notes_and_labels() -> Result<()> {
+    let storage_path = "test-storage/transaction_notes_and_labels";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+
+    let account_0 = &common::create_accounts_with_funds(&manager, 1).await?[0];
+    let account_1 = manager.create_account().finish().await?;
+
+    let tx = account_0
+        .send_amount(
+            vec![AddressWithAmount {
+                address: account_1.addresses().await?[0].address().to_bech32(),
+                amount: 1_000_000,
+            }],
+            None,
+        )
+        .await?;
+
+    account_0
+        .set_transaction_note(&tx.transaction_id, Some("Rent for March".to_string()))
+        .await?;
+    account_0
+        .set_transaction_labels(&tx.transaction_id, vec!["rent".to_string(), "rent".to_string()])
+        .await?;
+
+    let transaction = account_0.get_transaction(&tx.transaction_id).await.unwrap();
+    assert_eq!(transaction.note.as_deref(), Some("Rent for March"));
+    assert_eq!(transaction.labels, vec!["rent".to_string()]);
+
+    let found = account_0
+        .filter_transactions(TransactionFilterOptions {
+            label: Some("rent".to_string()),
+            note: Some("march".to_string()),
+            ..Default::default()
+        })
+        .await?;
+    assert_eq!(found.len(), 1);
+    let not_found = account_0
+        .filter_transactions(TransactionFilterOptions {
+            label: Some("salary".to_string()),
+            ..Default::default()
+        })
+        .await?;
+    assert!(not_found.is_empty());
+
+    common::tear_down(storage_path)
+}
+
+#[ignore]
+#[tokio::test]
+async fn transactions_paged() -> Result<()> {
+    let storage_path = "test-storage/transactions_paged";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+
+    let account_0 = &common::create_accounts_with_funds(&manager, 1).await?[0];
+    let account_1 = manager.create_account().finish().await?;
+    let address_1 = account_1.addresses().await?[0].address().to_bech32();
+
+    let mut transaction_ids = Vec::new();
+    for amount in [1_000_000, 2_000_000, 3_000_000] {
+        let tx = account_0
+            .send_amount(
+                vec![AddressWithAmount {
+                    address: address_1.clone(),
+                    amount,
+                }],
+                None,
+            )
+            .await?;
+        account_0
+            .retry_transaction_until_included(&tx.transaction_id, None, None)
+            .await?;
+        transaction_ids.push(tx.transaction_id);
+    }
+
+    let filter = TransactionHistoryFilter {
+        direction: Some(TransactionDirection::Outgoing),
+        address: Some(address_1),
+        ..Default::default()
+    };
+    let first_page = account_0.transactions_paged(Some(filter.clone()), None, 2).await?;
+    assert_eq!(first_page.transactions.len(), 2);
+    // Newest first
+    assert_eq!(first_page.transactions[0].transaction_id, transaction_ids[2]);
+    let second_page = account_0
+        .transactions_paged(Some(filter), first_page.next_cursor, 2)
+        .await?;
+    assert_eq!(second_page.transactions.len(), 1);
+    assert_eq!(second_page.transactions[0].transaction_id, transaction_ids[0]);
+    assert!(second_page.next_cursor.is_none());
+
+    let large_transactions = account_0
+        .transactions_paged(
+            Some(TransactionHistoryFilter {
+                min_amount: Some(2_000_000),
+                max_amount: Some(2_000_000),
+                ..Default::default()
+            }),
+            None,
+            10,
+        )
+        .await?;
+    assert_eq!(large_transactions.transactions.len(), 1);
+    assert_eq!(large_transactions.transactions[0].transaction_id, transaction_ids[1]);
+
+    common::tear_down(storage_path)
+}
+
+#[ignore]
+#[tokio::test]
+async fn export_history() -> Result<()> {
+    let storage_path = "test-storage/export_history";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+
+    let account_0 = &common::create_accounts_with_funds(&manager, 1).await?[0];
+    let account_1 = manager.create_account().finish().await?;
+    let address_1 = account_1.addresses().await?[0].address().to_bech32();
+
+    let tx = account_0
+        .send_amount(
+            vec![AddressWithAmount {
+                address: address_1.clone(),
+                amount: 1_000_000,
+            }],
+            None,
+        )
+        .await?;
+    account_0
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+    account_0
+        .set_transaction_note(&tx.transaction_id, Some("Invoice 42, \"consulting\"".to_string()))
+        .await?;
+
+    let csv = account_0.export_history(HistoryExportFormat::Csv, 0..u64::MAX).await?;
+    let lines: Vec<&str> = csv.lines().collect();
+    assert!(lines[0].starts_with("timestamp,date,transactionId,direction"));
+    let row = lines
+        .iter()
+        .find(|line| line.contains(&tx.transaction_id.to_string()))
+        .unwrap();
+    assert!(row.contains(&format!("Outgoing,Confirmed,{address_1},,1000000,0")));
+    assert!(row.contains("\"Invoice 42, \"\"consulting\"\"\""));
+
+    let json = account_0.export_history(HistoryExportFormat::Json, 0..u64::MAX).await?;
+    let rows: Vec<HistoryExportRow> = serde_json::from_str(&json)?;
+    let row = rows.iter().find(|row| row.transaction_id == tx.transaction_id).unwrap();
+    assert_eq!(row.direction, TransactionDirection::Outgoing);
+    assert_eq!(row.counterpart, Some(address_1));
+    assert_eq!(row.amount, "1000000");
+    assert_eq!(row.note.as_deref(), Some("Invoice 42, \"consulting\""));
+
+    // Nothing in the range before the transaction
+    let csv = account_0
+        .export_history(HistoryExportFormat::Csv, 0..row.timestamp as u64)
+        .await?;
+    assert!(!csv.contains(&tx.transaction_id.to_string()));
+
+    common::tear_down(storage_path)
+}
+
+#[ignore]
+#[tokio::test]
+async fn retry_transaction() -> Result<()> {
+    let storage_path = "test-storage/retry_transaction";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+
+    let account_0 = &common::create_accounts_with_funds(&manager, 1).await?[0];
+    let account_1 = manager.create_account().finish().await?;
+
+    let tx = account_0
+        .send_amount(
+            vec![AddressWithAmount {
+                address: account_1.addresses().await?[0].address().to_bech32(),
+                amount: 1_000_000,
+            }],
+            None,
+        )
+        .await?;
+
+    // Only conflicting or expired transactions can be sent again
+    assert!(matches!(
+        account_0.retry_transaction(&tx.transaction_id).await,
+        Err(Error::TransactionNotRetryable(..))
+    ));
+    account_0
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+    account_0.sync(None).await?;
+    assert!(matches!(
+        account_0.retry_transaction(&tx.transaction_id).await,
+        Err(Error::TransactionNotRetryable(..))
+    ));
+    let transaction = account_0.get_transaction(&tx.transaction_id).await.unwrap();
+    assert_eq!(transaction.retried_by, None);
+
+    common::tear_down(storage_path)
+}
+
+#[ignore]
+#[tokio::test]
+async fn transaction_max_pending() -> Result<()> {
+    let storage_path = "test-storage/transaction_max_pending";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+
+    let account_0 = &common::create_accounts_with_funds(&manager, 1).await?[0];
+    let account_1 = manager.create_account().finish().await?;
+
+    let tx = account_0
+        .send_amount(
+            vec![AddressWithAmount {
+                address: account_1.addresses().await?[0].address().to_bech32(),
+                amount: 1_000_000,
+            }],
+            None,
+        )
+        .await?;
+
+    account_0
+        .set_transaction_max_pending(&tx.transaction_id, Some(60_000))
+        .await?;
+    let transaction = account_0.get_transaction(&tx.transaction_id).await.unwrap();
+    assert_eq!(transaction.expires_at, Some(transaction.timestamp + 60_000));
+
+    account_0
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+    account_0.sync(None).await?;
+    // Only pending transactions can expire
+    assert!(matches!(
+        account_0.set_transaction_max_pending(&tx.transaction_id, None).await,
+        Err(Error::TransactionNotPending(_))
+    ));
+
+    common::tear_down(storage_path)
+}
+
+#[ignore]
+#[tokio::test]
+async fn input_reservations() -> Result<()> {
+    let storage_path = "test-storage/input_reservations";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+
+    let account_0 = &common::create_accounts_with_funds(&manager, 1).await?[0];
+    let account_1 = manager.create_account().finish().await?;
+
+    let tx = account_0
+        .send_amount(
+            vec![AddressWithAmount {
+                address: account_1.addresses().await?[0].address().to_bech32(),
+                amount: 1_000_000,
+            }],
+            None,
+        )
+        .await?;
+
+    // The inputs are reserved while the transaction is pending
+    let input_reservations = account_0.input_reservations().await?;
+    assert_eq!(input_reservations.len(), tx.inputs.len());
+
+    account_0
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+    account_0.sync(None).await?;
+    assert!(account_0.input_reservations().await?.is_empty());
+
+    common::tear_down(storage_path)
+}
+
+#[test]
+fn irc_27_metadata_validation() -> Result<()> {
+    let mut metadata = Irc27Metadata::new("image/png", "https://mywebsite.com/nft-1.png", "My NFT #1");
+    metadata.royalties.insert(
+        "rms1qpszqzadsym6wpppd6z037dvlejmjuke7s24hm95s9fg9vpua7vluaw60xu".to_string(),
+        0.05,
+    );
+    let json: serde_json::Value = serde_json::from_slice(&metadata.to_bytes()?)?;
+    assert_eq!(json["standard"], "IRC27");
+    assert_eq!(json["version"], "v1.0");
+    assert_eq!(json["type"], "image/png");
+    assert!(json.get("collectionName").is_none());
+
+    let mut invalid = metadata.clone();
+    invalid.media_type = "png".to_string();
+    assert!(matches!(invalid.validate(), Err(Error::InvalidIrc27Metadata(_))));
+
+    let mut invalid = metadata.clone();
+    invalid.uri = "mywebsite.com/nft-1.png".to_string();
+    assert!(matches!(invalid.validate(), Err(Error::InvalidIrc27Metadata(_))));
+
+    let mut invalid = metadata.clone();
+    invalid.royalties.values_mut().for_each(|share| *share = 1.5);
+    assert!(matches!(invalid.validate(), Err(Error::InvalidIrc27Metadata(_))));
+
+    // Invalid metadata isn't added to the builder
+    assert!(NftMintBuilder::new().add_nft(invalid, None).is_err());
+    assert!(NftMintBuilder::new().finish().is_err());
+    assert_eq!(NftMintBuilder::new().add_nft(metadata, None)?.finish()?.len(), 1);
+
+    Ok(())
+}
+
+#[ignore]
+#[tokio::test]
+async fn mint_nfts_with_builder() -> Result<()> {
+    let storage_path = "test-storage/mint_nfts_with_builder";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+    let account = &common::create_accounts_with_funds(&manager, 1).await?[0];
+
+    let builder = NftMintBuilder::new()
+        .add_nft(
+            Irc27Metadata::new("image/png", "https://mywebsite.com/nft-1.png", "My NFT #1"),
+            None,
+        )?
+        .add_nft(
+            Irc27Metadata::new("image/png", "https://mywebsite.com/nft-2.png", "My NFT #2"),
+            None,
+        )?;
+    let minted = account.mint_nfts_with_builder(builder, None).await?;
+    assert_eq!(minted.nft_ids.len(), 2);
+    assert_eq!(account.minted_nfts().await, minted.nft_ids);
+
+    account
+        .retry_transaction_until_included(&minted.transaction.transaction_id, None, None)
+        .await?;
+    let balance = account.sync(None).await?;
+    for nft_id in &minted.nft_ids {
+        assert!(balance.nfts.contains(nft_id));
+    }
+
+    common::tear_down(storage_path)
+}
+
+#[ignore]
+#[tokio::test]
+async fn mint_collection_nfts() -> Result<()> {
+    let storage_path = "test-storage/mint_collection_nfts";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+    let account = &common::create_accounts_with_funds(&manager, 1).await?[0];
+
+    let collection_builder = NftMintBuilder::new().add_nft(
+        Irc27Metadata::new("image/png", "https://mywebsite.com/collection.png", "My Collection"),
+        None,
+    )?;
+    let collection = account.mint_nfts_with_builder(collection_builder, None).await?;
+    let collection_id = collection.nft_ids[0];
+    account
+        .retry_transaction_until_included(&collection.transaction.transaction_id, None, None)
+        .await?;
+    account.sync(None).await?;
+
+    let mut metadata = Irc27Metadata::new("image/png", "https://mywebsite.com/nft-1.png", "My NFT #1");
+    metadata.collection_name = Some("My Collection".to_string());
+    let mut fake_metadata = Irc27Metadata::new("image/png", "https://mywebsite.com/nft-2.png", "My NFT #2");
+    fake_metadata.collection_name = Some("Other Collection".to_string());
+    let builder = NftMintBuilder::new()
+        .add_nft(metadata, None)?
+        .add_nft(fake_metadata, None)?;
+    let minted = account.mint_collection_nfts(collection_id, builder, None).await?;
+    account
+        .retry_transaction_until_included(&minted.transaction.transaction_id, None, None)
+        .await?;
+    account.sync(None).await?;
+
+    let nfts = account.nfts(Some(collection_id)).await?;
+    assert_eq!(nfts.len(), 2);
+    for nft in nfts {
+        assert_eq!(nft.collection_id, Some(collection_id));
+        assert_eq!(nft.verified, nft.nft_id == minted.nft_ids[0]);
+    }
+    // The collection NFT itself isn't part of a collection
+    assert_eq!(account.nfts(None).await?.len(), 3);
+
+    common::tear_down(storage_path)
+}
+
+#[ignore]
+#[tokio::test]
+async fn send_with_conditions() -> Result<()> {
+    let storage_path = "test-storage/send_with_conditions";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+
+    let account_0 = &common::create_accounts_with_funds(&manager, 1).await?[0];
+    let account_1 = manager.create_account().finish().await?;
+    let address = account_1.addresses().await?[0].address().to_bech32();
+
+    let send_options = SendOptions::new()
+        .expires_in(Duration::from_secs(60 * 60 * 24))
+        .return_storage_deposit();
+    let conditional_send = account_0
+        .send_with_conditions(
+            vec![AddressWithAmount {
+                address: address.clone(),
+                amount: 1,
+            }],
+            send_options,
+            None,
+        )
+        .await?;
+    let warnings = &conditional_send.warnings;
+    assert!(warnings.iter().any(|warning| matches!(
+        warning,
+        SendWarning::ClaimBeforeDeadline { recipient, .. } if recipient == &address
+    )));
+    assert!(warnings.iter().any(|warning| matches!(
+        warning,
+        SendWarning::StorageDepositReturn { recipient, .. } if recipient == &address
+    )));
+
+    account_0
+        .retry_transaction_until_included(&conditional_send.transaction.transaction_id, None, None)
+        .await?;
+
+    // The output can't be claimed without returning the storage deposit
+    let balance = account_1.sync(None).await?;
+    assert_eq!(balance.potentially_locked_outputs.len(), 1);
+
+    common::tear_down(storage_path)
+}
+
+#[test]
+fn vesting_schedule_unlocks() -> Result<()> {
+    let unlocks = VestingSchedule::new(Duration::from_secs(100), 3)
+        .starting_at(1_000)
+        .unlocks(1_000_001, 0)?;
+    assert_eq!(
+        unlocks.iter().map(|unlock| unlock.unlocks_at).collect::<Vec<_>>(),
+        vec![1_100, 1_200, 1_300]
+    );
+    // The remainder is unlocked last
+    assert_eq!(
+        unlocks.iter().map(|unlock| unlock.amount).collect::<Vec<_>>(),
+        vec![333_333, 333_333, 333_335]
+    );
+
+    // Without start the periods are counted from the current time
+    assert_eq!(
+        VestingSchedule::monthly(1).unlocks(10, 5)?[0].unlocks_at,
+        5 + 60 * 60 * 24 * 30
+    );
+
+    assert!(matches!(
+        VestingSchedule::monthly(0).unlocks(10, 0),
+        Err(Error::InvalidVestingSchedule(_))
+    ));
+    assert!(matches!(
+        VestingSchedule::monthly(1).starting_at(u32::MAX).unlocks(10, 0),
+        Err(Error::InvalidVestingSchedule(_))
+    ));
+
+    Ok(())
+}
+
+#[ignore]
+#[tokio::test]
+async fn send_vesting() -> Result<()> {
+    let storage_path = "test-storage/send_vesting";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+
+    let account_0 = &common::create_accounts_with_funds(&manager, 1).await?[0];
+    let account_1 = manager.create_account().finish().await?;
+
+    let transaction = account_0
+        .send_vesting(
+            account_1.addresses().await?[0].address().to_bech32(),
+            3_000_000,
+            VestingSchedule::monthly(3),
+            None,
+        )
+        .await?;
+    account_0
+        .retry_transaction_until_included(&transaction.transaction_id, None, None)
+        .await?;
+
+    let balance = account_1.sync(None).await?;
+    assert_eq!(balance.base_coin.available, 0);
+    let upcoming_unlocks = account_1.upcoming_unlocks(None).await?;
+    assert_eq!(upcoming_unlocks.len(), 3);
+    assert!(upcoming_unlocks.iter().all(|unlock| unlock.amount == 1_000_000));
+    assert!(upcoming_unlocks[0].unlocks_at < upcoming_unlocks[1].unlocks_at);
+    // Only the first output unlocks within 31 days
+    let within = Duration::from_secs(60 * 60 * 24 * 31);
+    assert_eq!(account_1.upcoming_unlocks(Some(within)).await?.len(), 1);
+
+    common::tear_down(storage_path)
+}
+
+#[ignore]
+#[tokio::test]
+async fn atomic_swap_nft_for_native_tokens() -> Result<()> {
+    let storage_path = "test-storage/atomic_swap_nft_for_native_tokens";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+    let accounts = common::create_accounts_with_funds(&manager, 2).await?;
+
+    let builder = NftMintBuilder::new().add_nft(
+        Irc27Metadata::new("image/png", "https://mywebsite.com/nft.png", "My NFT"),
+        None,
+    )?;
+    let minted = accounts[0].mint_nfts_with_builder(builder, None).await?;
+    let nft_id = minted.nft_ids[0];
+    accounts[0]
+        .retry_transaction_until_included(&minted.transaction.transaction_id, None, None)
+        .await?;
+    accounts[0].sync(None).await?;
+
+    let tx = accounts[1].create_alias_output(None, None).await?;
+    accounts[1]
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+    accounts[1].sync(None).await?;
+    let mint_tx = accounts[1]
+        .mint_native_token(
+            NativeTokenOptions {
+                alias_id: None,
+                circulating_supply: U256::from(100),
+                maximum_supply: U256::from(100),
+                foundry_metadata: None,
+            },
+            None,
+        )
+        .await?;
+    accounts[1]
+        .retry_transaction_until_included(&mint_tx.transaction.transaction_id, None, None)
+        .await?;
+    accounts[1].sync(None).await?;
+
+    let offer = accounts[0]
+        .create_swap_offer(
+            accounts[1].addresses().await?[0].address().to_bech32(),
+            SwapAssets {
+                nft_ids: vec![nft_id],
+                native_tokens: Vec::new(),
+            },
+            SwapAssets {
+                nft_ids: Vec::new(),
+                native_tokens: vec![NativeToken::new(mint_tx.token_id, U256::from(50))?],
+            },
+            None,
+        )
+        .await?;
+    // The offer and the acceptance are exchanged as JSON
+    let offer = SwapOffer::from_json(&offer.to_json()?)?;
+
+    // Asking for more than offered fails the validation of the taker
+    let mut greedy_offer = offer.clone();
+    greedy_offer.offered.nft_ids.push(nft_id);
+    greedy_offer.offered.native_tokens = vec![NativeToken::new(mint_tx.token_id, U256::from(1))?];
+    assert!(matches!(
+        accounts[1].accept_swap_offer(&greedy_offer, None).await,
+        Err(Error::InvalidSwap(_))
+    ));
+
+    let acceptance = accounts[1].accept_swap_offer(&offer, None).await?;
+    let acceptance = SwapAcceptance::from_json(&acceptance.to_json()?)?;
+    let transaction = accounts[0].complete_swap(&offer, &acceptance).await?;
+    accounts[0]
+        .retry_transaction_until_included(&transaction.transaction_id, None, None)
+        .await?;
+
+    let balance_0 = accounts[0].sync(None).await?;
+    assert!(!balance_0.nfts.contains(&nft_id));
+    let received = balance_0
+        .native_tokens
+        .iter()
+        .find(|token| token.token_id == mint_tx.token_id);
+    assert_eq!(received.map(|token| token.total), Some(U256::from(50)));
+    let balance_1 = accounts[1].sync(None).await?;
+    assert!(balance_1.nfts.contains(&nft_id));
+
+    common::tear_down(storage_path)
+}